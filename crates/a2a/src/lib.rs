@@ -67,7 +67,7 @@
 //! - [`http`]: HTTP+JSON protocol types and utilities
 //! - [`streaming`]: Server-Sent Events (SSE) streaming utilities
 //! - [`validation`]: Protocol validation functions
-//! - [`storage`]: Task storage abstraction and implementations
+//! - [`storage`]: Task and push notification config storage abstractions and implementations
 //! - [`server`]: Server abstractions and handler traits
 //!
 //! ## Protocol Version
@@ -89,7 +89,10 @@ pub mod validation;
 // Re-export commonly used types for convenience
 pub use error::{A2aError, A2aResult, JsonRpcError};
 pub use server::{A2aContext, A2aHandler, SseStream, create_http_router, create_jsonrpc_router};
-pub use storage::{InMemoryTaskStorage, TaskFilters, TaskStorage};
+pub use storage::{
+    InMemoryPushNotificationStorage, InMemoryTaskStorage, PushNotificationStorage, TaskFilters,
+    TaskStorage,
+};
 pub use types::{
     AgentCapabilities, AgentCard, AgentSkill, Artifact, Message, MessageRole, Part, Task,
     TaskState, TaskStatus, TextPart, TransportProtocol,
@@ -113,7 +116,10 @@ pub mod prelude {
     pub use crate::server::{
         A2aContext, A2aHandler, SseStream, create_http_router, create_jsonrpc_router,
     };
-    pub use crate::storage::{InMemoryTaskStorage, TaskFilters, TaskStorage};
+    pub use crate::storage::{
+        InMemoryPushNotificationStorage, InMemoryTaskStorage, PushNotificationStorage, TaskFilters,
+        TaskStorage,
+    };
     pub use crate::streaming::{SseEvent, SseEventType, SseStreamBuilder};
     pub use crate::types::{
         AgentCapabilities, AgentCard, AgentInterface, AgentSkill, Artifact, DataPart, FilePart,
@@ -80,9 +80,9 @@ use std::sync::Arc;
 
 use crate::error::A2aError;
 use crate::http::HttpStatus;
-use crate::storage::TaskStorage;
+use crate::storage::{PushNotificationStorage, TaskStorage};
 use crate::streaming::SseEvent;
-use crate::types::{AgentCard, Message, Task};
+use crate::types::{AgentCard, Message, PushNotificationConfig, Task};
 
 const DEFAULT_AUTH_HEADER: &str = "X-API-Key";
 
@@ -224,6 +224,8 @@ pub type SseStream = Pin<Box<dyn Stream<Item = Result<SseEvent, A2aError>> + Sen
 /// - **`handle_send_streaming_message`**: Process a message with streaming updates
 /// - **`handle_get_agent_card`**: Return the agent's capability card
 /// - **`task_storage`**: Provide access to task storage (used by router for task operations)
+/// - **`push_notification_storage`**: Optional push notification config storage
+/// - **`handle_resubscribe`**: Optionally stream updates of an existing task
 ///
 /// # Task Operations
 ///
@@ -467,6 +469,35 @@ pub trait A2aHandler: Send + Sync {
     /// # }
     /// ```
     fn task_storage(&self) -> &dyn TaskStorage;
+
+    /// Get the push notification config storage, if push notifications are supported.
+    ///
+    /// The router handles the `tasks/pushNotificationConfig/*` methods with this
+    /// storage, the same way it handles task operations with `task_storage`.
+    /// The default returns `None`, which makes those methods fail with
+    /// `PushNotificationNotSupported`. Delivering the notifications when a task
+    /// changes is up to the implementation.
+    fn push_notification_storage(&self) -> Option<&dyn PushNotificationStorage> {
+        None
+    }
+
+    /// Handle a tasks/resubscribe request.
+    ///
+    /// Lets a client that lost its `message/stream` connection reattach to a
+    /// task. The returned stream uses the same event format as
+    /// `handle_send_streaming_message` and should end once the task reaches a
+    /// final state.
+    ///
+    /// The default implementation returns `UnsupportedOperation`.
+    async fn handle_resubscribe(
+        &self,
+        _ctx: A2aContext,
+        _task_id: String,
+    ) -> Result<SseStream, A2aError> {
+        Err(A2aError::UnsupportedOperation(
+            "tasks/resubscribe is not supported by this agent".to_string(),
+        ))
+    }
 }
 
 /// Create an axum Router with JSON-RPC endpoint for a single agent.
@@ -479,9 +510,13 @@ pub trait A2aHandler: Send + Sync {
 /// # Supported Methods
 ///
 /// - `message/send` - Send a message and get a task response
+/// - `message/stream` - Send a message and receive SSE stream
 /// - `agent/getAuthenticatedExtendedCard` - Get agent capabilities
 /// - `tasks/get` - Retrieve task status (handled via storage)
 /// - `tasks/cancel` - Cancel a running task (handled via storage)
+/// - `tasks/resubscribe` - Reattach to a task's SSE stream
+/// - `tasks/pushNotificationConfig/{set,get,list,delete}` - Manage push
+///   notification configs (handled via push notification storage)
 ///
 /// # Handler Scoping
 ///
@@ -597,37 +632,34 @@ where
         return resp;
     }
 
-    // Handle message/stream separately since it returns SSE
-    if request.method == "message/stream" {
-        let params: crate::jsonrpc::MessageSendParams = match request.params {
-            Some(p) => match serde_json::from_value(p) {
-                Ok(params) => params,
-                Err(e) => {
-                    let error = crate::error::JsonRpcError::invalid_params(format!(
-                        "Invalid message/stream params: {}",
-                        e
-                    ));
-                    let response = JsonRpcResponse::error(error, id);
-                    let mut resp = axum::Json(response).into_response();
-                    *resp.status_mut() = StatusCode::BAD_REQUEST;
-                    return resp;
+    // Handle streaming methods separately since they return SSE
+    if request.method == "message/stream" || request.method == "tasks/resubscribe" {
+        let result = if request.method == "message/stream" {
+            match parse_stream_params::<crate::jsonrpc::MessageSendParams>(
+                &request.method,
+                request.params,
+                &id,
+            ) {
+                Ok(params) => {
+                    handler
+                        .handle_send_streaming_message(ctx, params.message)
+                        .await
                 }
-            },
-            None => {
-                let error =
-                    crate::error::JsonRpcError::invalid_params("message/stream requires params");
-                let response = JsonRpcResponse::error(error, id);
-                let mut resp = axum::Json(response).into_response();
-                *resp.status_mut() = StatusCode::BAD_REQUEST;
-                return resp;
+                Err(resp) => return *resp,
+            }
+        } else {
+            match parse_stream_params::<crate::jsonrpc::TaskIdParams>(
+                &request.method,
+                request.params,
+                &id,
+            ) {
+                Ok(params) => handler.handle_resubscribe(ctx, params.id).await,
+                Err(resp) => return *resp,
             }
         };
 
         // Get the SSE stream from the handler
-        let stream = match handler
-            .handle_send_streaming_message(ctx, params.message)
-            .await
-        {
+        let stream = match result {
             Ok(stream) => stream,
             Err(e) => {
                 let status = error_to_http_status(&e);
@@ -703,9 +735,18 @@ where
         "agent/getAuthenticatedExtendedCard" => handle_get_agent_card(handler.as_ref(), ctx).await,
         "tasks/get" => handle_tasks_get(handler.as_ref(), ctx, request.params).await,
         "tasks/cancel" => handle_tasks_cancel(handler.as_ref(), ctx, request.params).await,
-        "tasks/resubscribe" => Err(A2aError::UnsupportedOperation(
-            "tasks/resubscribe is not yet implemented".to_string(),
-        )),
+        "tasks/pushNotificationConfig/set" => {
+            handle_push_config_set(handler.as_ref(), request.params).await
+        }
+        "tasks/pushNotificationConfig/get" => {
+            handle_push_config_get(handler.as_ref(), request.params).await
+        }
+        "tasks/pushNotificationConfig/list" => {
+            handle_push_config_list(handler.as_ref(), request.params).await
+        }
+        "tasks/pushNotificationConfig/delete" => {
+            handle_push_config_delete(handler.as_ref(), request.params).await
+        }
         _ => Err(A2aError::MethodNotFound(request.method.clone())),
     };
 
@@ -832,6 +873,208 @@ where
         .map_err(|e| A2aError::SerializationError(format!("Failed to serialize task: {}", e)))
 }
 
+/// Parse the params of a streaming method, or build the JSON-RPC error
+/// response to return instead of a stream.
+fn parse_stream_params<T: serde::de::DeserializeOwned>(
+    method: &str,
+    params: Option<serde_json::Value>,
+    id: &serde_json::Value,
+) -> Result<T, Box<axum::response::Response>> {
+    let error = match params {
+        Some(p) => match serde_json::from_value(p) {
+            Ok(params) => return Ok(params),
+            Err(e) => crate::error::JsonRpcError::invalid_params(format!(
+                "Invalid {} params: {}",
+                method, e
+            )),
+        },
+        None => crate::error::JsonRpcError::invalid_params(format!("{} requires params", method)),
+    };
+    let response = crate::jsonrpc::JsonRpcResponse::error(error, id.clone());
+    let mut resp = axum::Json(response).into_response();
+    *resp.status_mut() = StatusCode::BAD_REQUEST;
+    Err(Box::new(resp))
+}
+
+/// Parse the params of a JSON-RPC method
+fn parse_params<T: serde::de::DeserializeOwned>(
+    method: &str,
+    params: Option<serde_json::Value>,
+) -> Result<T, A2aError> {
+    match params {
+        Some(p) => serde_json::from_value(p)
+            .map_err(|e| A2aError::InvalidParams(format!("Invalid {} params: {}", method, e))),
+        None => Err(A2aError::InvalidParams(format!(
+            "{} requires params",
+            method
+        ))),
+    }
+}
+
+/// Handle tasks/pushNotificationConfig/set method
+async fn handle_push_config_set<H>(
+    handler: &H,
+    params: Option<serde_json::Value>,
+) -> Result<serde_json::Value, A2aError>
+where
+    H: A2aHandler,
+{
+    let params: crate::jsonrpc::TaskPushNotificationConfig =
+        parse_params("tasks/pushNotificationConfig/set", params)?;
+
+    let config =
+        set_push_notification_config(handler, &params.task_id, params.push_notification_config)
+            .await?;
+
+    serde_json::to_value(crate::jsonrpc::TaskPushNotificationConfig {
+        task_id: params.task_id,
+        push_notification_config: config,
+    })
+    .map_err(|e| A2aError::SerializationError(format!("Failed to serialize config: {}", e)))
+}
+
+/// Handle tasks/pushNotificationConfig/get method
+async fn handle_push_config_get<H>(
+    handler: &H,
+    params: Option<serde_json::Value>,
+) -> Result<serde_json::Value, A2aError>
+where
+    H: A2aHandler,
+{
+    let params: crate::jsonrpc::GetTaskPushNotificationConfigParams =
+        parse_params("tasks/pushNotificationConfig/get", params)?;
+
+    let config =
+        get_push_notification_config(handler, &params.id, params.push_notification_config_id)
+            .await?;
+
+    serde_json::to_value(crate::jsonrpc::TaskPushNotificationConfig {
+        task_id: params.id,
+        push_notification_config: config,
+    })
+    .map_err(|e| A2aError::SerializationError(format!("Failed to serialize config: {}", e)))
+}
+
+/// Handle tasks/pushNotificationConfig/list method
+async fn handle_push_config_list<H>(
+    handler: &H,
+    params: Option<serde_json::Value>,
+) -> Result<serde_json::Value, A2aError>
+where
+    H: A2aHandler,
+{
+    let params: crate::jsonrpc::ListTaskPushNotificationConfigParams =
+        parse_params("tasks/pushNotificationConfig/list", params)?;
+
+    let configs: Vec<_> = push_notification_storage(handler, &params.id)
+        .await?
+        .list_configs(params.id.clone())
+        .await?
+        .into_iter()
+        .map(|config| crate::jsonrpc::TaskPushNotificationConfig {
+            task_id: params.id.clone(),
+            push_notification_config: config,
+        })
+        .collect();
+
+    serde_json::to_value(configs)
+        .map_err(|e| A2aError::SerializationError(format!("Failed to serialize configs: {}", e)))
+}
+
+/// Handle tasks/pushNotificationConfig/delete method
+async fn handle_push_config_delete<H>(
+    handler: &H,
+    params: Option<serde_json::Value>,
+) -> Result<serde_json::Value, A2aError>
+where
+    H: A2aHandler,
+{
+    let params: crate::jsonrpc::DeleteTaskPushNotificationConfigParams =
+        parse_params("tasks/pushNotificationConfig/delete", params)?;
+
+    push_notification_storage(handler, &params.id)
+        .await?
+        .delete_config(params.id, params.push_notification_config_id)
+        .await?;
+
+    Ok(serde_json::Value::Null)
+}
+
+/// The handler's push notification storage, once the task is known to exist.
+async fn push_notification_storage<'a, H>(
+    handler: &'a H,
+    task_id: &str,
+) -> Result<&'a dyn PushNotificationStorage, A2aError>
+where
+    H: A2aHandler,
+{
+    let storage = handler
+        .push_notification_storage()
+        .ok_or(A2aError::PushNotificationNotSupported)?;
+
+    handler
+        .task_storage()
+        .get_task(task_id.to_string())
+        .await?
+        .ok_or_else(|| A2aError::TaskNotFound(task_id.to_string()))?;
+
+    Ok(storage)
+}
+
+/// Validate and store a push notification config.
+///
+/// The URL must use https and resolve only to public addresses, so clients
+/// cannot point the server's webhook requests at its own network.
+///
+/// Configs without an `id` get a generated one. Configs without a `token` get
+/// a generated one too, so that every notification carries a per-config value
+/// the client can check; it is returned to the client with the stored config.
+async fn set_push_notification_config<H>(
+    handler: &H,
+    task_id: &str,
+    mut config: PushNotificationConfig,
+) -> Result<PushNotificationConfig, A2aError>
+where
+    H: A2aHandler,
+{
+    let storage = push_notification_storage(handler, task_id).await?;
+
+    crate::validation::validate_push_notification_url(&config.url).await?;
+
+    if config.id.is_none() {
+        config.id = Some(uuid::Uuid::new_v4().to_string());
+    }
+    if config.token.is_none() {
+        config.token = Some(uuid::Uuid::new_v4().simple().to_string());
+    }
+
+    storage.set_config(task_id.to_string(), config).await
+}
+
+/// Get a push notification config, or the task's latest one without an ID.
+async fn get_push_notification_config<H>(
+    handler: &H,
+    task_id: &str,
+    config_id: Option<String>,
+) -> Result<PushNotificationConfig, A2aError>
+where
+    H: A2aHandler,
+{
+    push_notification_storage(handler, task_id)
+        .await?
+        .get_config(task_id.to_string(), config_id.clone())
+        .await?
+        .ok_or_else(|| {
+            A2aError::InvalidParams(match config_id {
+                Some(config_id) => format!(
+                    "Push notification config '{}' not found for task '{}'",
+                    config_id, task_id
+                ),
+                None => format!("Task '{}' has no push notification config", task_id),
+            })
+        })
+}
+
 /// Create an axum Router with HTTP+JSON endpoints for a single agent.
 ///
 /// This function returns a pre-configured axum router that implements the A2A
@@ -841,6 +1084,9 @@ where
 /// - POST `/messages/stream` - Send a message and receive SSE stream
 /// - GET `/tasks/{id}` - Retrieve task status
 /// - DELETE `/tasks/{id}` - Delete/cancel a task
+/// - POST `/tasks/{id}/subscribe` - Reattach to a task's SSE stream
+/// - POST/GET `/tasks/{id}/pushNotificationConfigs` - Set or list push notification configs
+/// - GET/DELETE `/tasks/{id}/pushNotificationConfigs/{config_id}` - Get or delete a config
 /// - GET `/agent` - Get agent capabilities
 ///
 /// # Handler Scoping
@@ -907,6 +1153,15 @@ where
         .route("/messages/stream", post(http_send_streaming_message::<H>))
         .route("/tasks/{id}", get(http_get_task::<H>))
         .route("/tasks/{id}", delete(http_delete_task::<H>))
+        .route("/tasks/{id}/subscribe", post(http_resubscribe::<H>))
+        .route(
+            "/tasks/{id}/pushNotificationConfigs",
+            post(http_set_push_config::<H>).get(http_list_push_configs::<H>),
+        )
+        .route(
+            "/tasks/{id}/pushNotificationConfigs/{config_id}",
+            get(http_get_push_config::<H>).delete(http_delete_push_config::<H>),
+        )
         .route("/agent", get(http_get_agent_card::<H>))
         .with_state(handler)
 }
@@ -969,8 +1224,6 @@ where
     H: A2aHandler,
 {
     use crate::http::{HttpErrorResponse, error_to_http_status};
-    use axum::response::sse::{Event, KeepAlive, Sse};
-    use futures::stream::StreamExt;
 
     // Build context from request
     let ctx = build_context_from_headers(headers);
@@ -1006,7 +1259,15 @@ where
         }
     };
 
-    // Convert SSE stream to axum SSE response
+    http_sse_response(stream)
+}
+
+/// Convert an SSE stream to an axum SSE response
+fn http_sse_response(stream: SseStream) -> axum::response::Response {
+    use crate::http::HttpErrorResponse;
+    use axum::response::sse::{Event, KeepAlive, Sse};
+    use futures::stream::StreamExt;
+
     let sse_stream = stream.map(|result| match result {
         Ok(sse_event) => {
             let mut event = Event::default().data(sse_event.data);
@@ -1033,6 +1294,164 @@ where
         .into_response()
 }
 
+/// Authenticate an HTTP request, or build the error response to return.
+async fn http_authenticate<H>(
+    handler: &H,
+    headers: HeaderMap,
+) -> Result<A2aContext, axum::response::Response>
+where
+    H: A2aHandler,
+{
+    let ctx = build_context_from_headers(headers);
+    handler
+        .authenticate_request(&ctx)
+        .await
+        .map_err(http_error_response)?;
+    Ok(ctx)
+}
+
+/// Convert an error to an HTTP error response
+fn http_error_response(error: A2aError) -> axum::response::Response {
+    use crate::http::{HttpErrorResponse, error_to_http_status};
+
+    let status = error_to_http_status(&error);
+    let error_response = HttpErrorResponse::from(error);
+    let mut resp = (
+        axum::http::StatusCode::from_u16(status.code()).unwrap(),
+        axum::Json(error_response),
+    )
+        .into_response();
+    add_www_authenticate_header_for_status(&mut resp, status);
+    resp
+}
+
+/// HTTP handler for POST /tasks/:id/subscribe
+async fn http_resubscribe<H>(
+    axum::extract::State(handler): axum::extract::State<Arc<H>>,
+    headers: HeaderMap,
+    axum::extract::Path(task_id): axum::extract::Path<String>,
+) -> axum::response::Response
+where
+    H: A2aHandler,
+{
+    let ctx = match http_authenticate(handler.as_ref(), headers).await {
+        Ok(ctx) => ctx,
+        Err(resp) => return resp,
+    };
+
+    match handler.handle_resubscribe(ctx, task_id).await {
+        Ok(stream) => http_sse_response(stream),
+        Err(e) => http_error_response(e),
+    }
+}
+
+/// HTTP handler for POST /tasks/:id/pushNotificationConfigs
+async fn http_set_push_config<H>(
+    axum::extract::State(handler): axum::extract::State<Arc<H>>,
+    headers: HeaderMap,
+    axum::extract::Path(task_id): axum::extract::Path<String>,
+    axum::extract::Json(request): axum::extract::Json<
+        crate::http::CreatePushNotificationConfigRequest,
+    >,
+) -> axum::response::Response
+where
+    H: A2aHandler,
+{
+    use crate::http::CreatePushNotificationConfigResponse;
+
+    if let Err(resp) = http_authenticate(handler.as_ref(), headers).await {
+        return resp;
+    }
+
+    match set_push_notification_config(handler.as_ref(), &task_id, request.push_notification_config)
+        .await
+    {
+        Ok(config) => {
+            let response = CreatePushNotificationConfigResponse {
+                task_id,
+                push_notification_config: config,
+            };
+            (axum::http::StatusCode::CREATED, axum::Json(response)).into_response()
+        }
+        Err(e) => http_error_response(e),
+    }
+}
+
+/// HTTP handler for GET /tasks/:id/pushNotificationConfigs
+async fn http_list_push_configs<H>(
+    axum::extract::State(handler): axum::extract::State<Arc<H>>,
+    headers: HeaderMap,
+    axum::extract::Path(task_id): axum::extract::Path<String>,
+) -> axum::response::Response
+where
+    H: A2aHandler,
+{
+    use crate::http::ListPushNotificationConfigsResponse;
+
+    if let Err(resp) = http_authenticate(handler.as_ref(), headers).await {
+        return resp;
+    }
+
+    let result = match push_notification_storage(handler.as_ref(), &task_id).await {
+        Ok(storage) => storage.list_configs(task_id).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(configs) => axum::Json(ListPushNotificationConfigsResponse { configs }).into_response(),
+        Err(e) => http_error_response(e),
+    }
+}
+
+/// HTTP handler for GET /tasks/:id/pushNotificationConfigs/:config_id
+async fn http_get_push_config<H>(
+    axum::extract::State(handler): axum::extract::State<Arc<H>>,
+    headers: HeaderMap,
+    axum::extract::Path((task_id, config_id)): axum::extract::Path<(String, String)>,
+) -> axum::response::Response
+where
+    H: A2aHandler,
+{
+    use crate::http::GetPushNotificationConfigResponse;
+
+    if let Err(resp) = http_authenticate(handler.as_ref(), headers).await {
+        return resp;
+    }
+
+    match get_push_notification_config(handler.as_ref(), &task_id, Some(config_id)).await {
+        Ok(config) => axum::Json(GetPushNotificationConfigResponse {
+            task_id,
+            push_notification_config: config,
+        })
+        .into_response(),
+        Err(e) => http_error_response(e),
+    }
+}
+
+/// HTTP handler for DELETE /tasks/:id/pushNotificationConfigs/:config_id
+async fn http_delete_push_config<H>(
+    axum::extract::State(handler): axum::extract::State<Arc<H>>,
+    headers: HeaderMap,
+    axum::extract::Path((task_id, config_id)): axum::extract::Path<(String, String)>,
+) -> axum::response::Response
+where
+    H: A2aHandler,
+{
+    if let Err(resp) = http_authenticate(handler.as_ref(), headers).await {
+        return resp;
+    }
+
+    let result = match push_notification_storage(handler.as_ref(), &task_id).await {
+        Ok(storage) => storage.delete_config(task_id, config_id).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(()) => axum::http::StatusCode::NO_CONTENT.into_response(),
+        Err(e) => http_error_response(e),
+    }
+}
+
 /// HTTP handler for GET /tasks/:id
 async fn http_get_task<H>(
     axum::extract::State(handler): axum::extract::State<Arc<H>>,
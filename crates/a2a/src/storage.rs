@@ -1,8 +1,9 @@
 //! Task storage abstraction for A2A protocol.
 //!
-//! This module provides a storage abstraction for managing A2A tasks. The storage
-//! is designed to be scoped to a single agent - multi-agent routing and isolation
-//! is handled by the consumer (e.g., the core crate).
+//! This module provides storage abstractions for managing A2A tasks and the
+//! push notification configs registered for them. The storage is designed to
+//! be scoped to a single agent - multi-agent routing and isolation is handled
+//! by the consumer (e.g., the core crate).
 //!
 //! # Architecture Notes
 //!
@@ -18,7 +19,7 @@ use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
 use crate::error::A2aError;
-use crate::types::{Task, TaskState};
+use crate::types::{PushNotificationConfig, Task, TaskState};
 
 /// Filters for querying tasks.
 ///
@@ -312,6 +313,110 @@ impl TaskStorage for InMemoryTaskStorage {
     }
 }
 
+/// Trait for push notification configuration storage.
+///
+/// Stores the webhook configurations clients register for a task through
+/// `tasks/pushNotificationConfig/set`. Like [`TaskStorage`], implementations
+/// are scoped to a single agent. The router checks that the task exists
+/// before calling into this storage, and assigns an `id` to every config it
+/// passes to [`set_config`](Self::set_config).
+#[async_trait]
+pub trait PushNotificationStorage: Send + Sync {
+    /// Create or replace a task's config with the same `id`.
+    ///
+    /// # Returns
+    ///
+    /// The stored config.
+    async fn set_config(
+        &self,
+        task_id: String,
+        config: PushNotificationConfig,
+    ) -> Result<PushNotificationConfig, A2aError>;
+
+    /// Get a task's config by ID, or its most recently set config when
+    /// `config_id` is `None`.
+    async fn get_config(
+        &self,
+        task_id: String,
+        config_id: Option<String>,
+    ) -> Result<Option<PushNotificationConfig>, A2aError>;
+
+    /// List a task's configs, oldest first.
+    async fn list_configs(&self, task_id: String) -> Result<Vec<PushNotificationConfig>, A2aError>;
+
+    /// Delete a task's config.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config does not exist.
+    async fn delete_config(&self, task_id: String, config_id: String) -> Result<(), A2aError>;
+}
+
+/// In-memory push notification storage for testing and development.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryPushNotificationStorage {
+    configs: Arc<RwLock<HashMap<String, Vec<PushNotificationConfig>>>>,
+}
+
+impl InMemoryPushNotificationStorage {
+    /// Create a new in-memory push notification storage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl PushNotificationStorage for InMemoryPushNotificationStorage {
+    async fn set_config(
+        &self,
+        task_id: String,
+        config: PushNotificationConfig,
+    ) -> Result<PushNotificationConfig, A2aError> {
+        let mut configs = self.configs.write().unwrap();
+        let task_configs = configs.entry(task_id).or_default();
+        task_configs.retain(|existing| existing.id != config.id);
+        task_configs.push(config.clone());
+        Ok(config)
+    }
+
+    async fn get_config(
+        &self,
+        task_id: String,
+        config_id: Option<String>,
+    ) -> Result<Option<PushNotificationConfig>, A2aError> {
+        let configs = self.configs.read().unwrap();
+        let Some(task_configs) = configs.get(&task_id) else {
+            return Ok(None);
+        };
+        Ok(match config_id {
+            Some(config_id) => task_configs
+                .iter()
+                .find(|config| config.id.as_deref() == Some(config_id.as_str()))
+                .cloned(),
+            None => task_configs.last().cloned(),
+        })
+    }
+
+    async fn list_configs(&self, task_id: String) -> Result<Vec<PushNotificationConfig>, A2aError> {
+        let configs = self.configs.read().unwrap();
+        Ok(configs.get(&task_id).cloned().unwrap_or_default())
+    }
+
+    async fn delete_config(&self, task_id: String, config_id: String) -> Result<(), A2aError> {
+        let mut configs = self.configs.write().unwrap();
+        let task_configs = configs.entry(task_id.clone()).or_default();
+        let before = task_configs.len();
+        task_configs.retain(|config| config.id.as_deref() != Some(config_id.as_str()));
+        if task_configs.len() == before {
+            return Err(A2aError::InvalidParams(format!(
+                "Push notification config '{}' not found for task '{}'",
+                config_id, task_id
+            )));
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -534,4 +639,64 @@ mod tests {
         assert!(storage.is_empty());
         assert_eq!(storage.len(), 0);
     }
+
+    fn push_config(id: &str) -> PushNotificationConfig {
+        PushNotificationConfig {
+            id: Some(id.to_string()),
+            url: format!("https://example.com/hooks/{}", id),
+            token: None,
+            authentication: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_push_notification_configs() {
+        let storage = InMemoryPushNotificationStorage::new();
+        let task_id = "task-1".to_string();
+
+        storage
+            .set_config(task_id.clone(), push_config("a"))
+            .await
+            .unwrap();
+        storage
+            .set_config(task_id.clone(), push_config("b"))
+            .await
+            .unwrap();
+
+        // Setting an existing ID replaces it
+        let mut replaced = push_config("a");
+        replaced.url = "https://example.com/other".to_string();
+        storage.set_config(task_id.clone(), replaced).await.unwrap();
+
+        let configs = storage.list_configs(task_id.clone()).await.unwrap();
+        assert_eq!(configs.len(), 2);
+
+        let latest = storage.get_config(task_id.clone(), None).await.unwrap();
+        assert_eq!(latest.unwrap().url, "https://example.com/other");
+
+        let b = storage
+            .get_config(task_id.clone(), Some("b".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(b.unwrap().url, "https://example.com/hooks/b");
+
+        storage
+            .delete_config(task_id.clone(), "b".to_string())
+            .await
+            .unwrap();
+        assert!(
+            storage
+                .delete_config(task_id.clone(), "b".to_string())
+                .await
+                .is_err()
+        );
+        assert_eq!(storage.list_configs(task_id).await.unwrap().len(), 1);
+        assert!(
+            storage
+                .list_configs("task-2".to_string())
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
 }
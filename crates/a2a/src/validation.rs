@@ -6,6 +6,7 @@
 use crate::error::{A2aError, A2aResult};
 use crate::jsonrpc::JsonRpcRequest;
 use crate::types::{AgentCard, Message, Part, Task, TaskState};
+use std::net::{IpAddr, SocketAddr};
 
 /// Validate a JSON-RPC request
 pub fn validate_jsonrpc_request(request: &JsonRpcRequest) -> A2aResult<()> {
@@ -342,6 +343,85 @@ pub fn validate_mime_type(mime_type: &str) -> A2aResult<()> {
     Ok(())
 }
 
+/// Validate a push notification webhook URL.
+///
+/// The URL must use https and its host must resolve only to public
+/// addresses, so that webhook requests cannot reach the server's own network.
+pub async fn validate_push_notification_url(url: &str) -> A2aResult<url::Url> {
+    let url = url::Url::parse(url)
+        .map_err(|e| A2aError::InvalidParams(format!("Invalid push notification URL: {}", e)))?;
+    if url.scheme() != "https" {
+        return Err(A2aError::InvalidParams(
+            "Push notification URL must use https".to_string(),
+        ));
+    }
+
+    let port = url.port_or_known_default().unwrap_or(443);
+    let addrs: Vec<SocketAddr> = match url.host() {
+        Some(url::Host::Ipv4(ip)) => vec![SocketAddr::new(ip.into(), port)],
+        Some(url::Host::Ipv6(ip)) => vec![SocketAddr::new(ip.into(), port)],
+        Some(url::Host::Domain(domain)) => tokio::net::lookup_host((domain, port))
+            .await
+            .map_err(|e| {
+                A2aError::InvalidParams(format!(
+                    "Push notification URL host '{}' does not resolve: {}",
+                    domain, e
+                ))
+            })?
+            .collect(),
+        None => {
+            return Err(A2aError::InvalidParams(
+                "Push notification URL has no host".to_string(),
+            ));
+        }
+    };
+
+    if addrs.is_empty() {
+        return Err(A2aError::InvalidParams(
+            "Push notification URL host does not resolve".to_string(),
+        ));
+    }
+    if let Some(addr) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+        return Err(A2aError::InvalidParams(format!(
+            "Push notification URL resolves to a non-public address: {}",
+            addr.ip()
+        )));
+    }
+
+    Ok(url)
+}
+
+/// Check if an address is routable on the public internet.
+///
+/// Loopback, private, link-local, shared (carrier-grade NAT), unspecified,
+/// broadcast, multicast and documentation addresses are not.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                || a == 0
+                || (a == 100 && (64..128).contains(&b)))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_ip(IpAddr::V4(ip)),
+            None => {
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local())
+            }
+        },
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -646,4 +726,54 @@ mod tests {
         };
         assert!(validate_jsonrpc_request(&empty_method).is_err());
     }
+
+    #[test]
+    fn test_is_public_ip() {
+        for ip in ["93.184.216.34", "8.8.8.8", "2606:4700:4700::1111"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.0.0.5",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validate_push_notification_url() {
+        assert!(
+            validate_push_notification_url("https://93.184.216.34/hooks")
+                .await
+                .is_ok()
+        );
+        for url in [
+            "http://93.184.216.34/hooks",
+            "https://127.0.0.1/hooks",
+            "https://[::1]:8443/hooks",
+            "https://169.254.169.254/latest/meta-data",
+            "https://localhost/hooks",
+            "not a url",
+        ] {
+            assert!(
+                matches!(
+                    validate_push_notification_url(url).await,
+                    Err(A2aError::InvalidParams(_))
+                ),
+                "{url}"
+            );
+        }
+    }
 }
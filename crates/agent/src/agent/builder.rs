@@ -55,6 +55,12 @@ impl Executable<AgentInput> for AgentExecutable {
             .map_err(|e| {
                 OxyError::ConfigurationError(format!("Failed to resolve agent config: {e}"))
             })?;
+        let execution_context = &execution_context
+            .with_run_budget(
+                &format!("agent '{agent_ref}'"),
+                agent_config.budget.as_ref(),
+            )
+            .await?;
 
         // Resolve variables by merging runtime params with defaults
         // Variables are added to the renderer context alongside globals
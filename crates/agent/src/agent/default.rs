@@ -13,7 +13,7 @@ use async_openai::types::chat::{
 };
 use minijinja::{Value, context};
 use oxy::{
-    adapters::openai::{AsyncFunctionObject, IntoOpenAIConfig, OpenAIClient},
+    adapters::{model_chain::ModelChain, openai::AsyncFunctionObject},
     config::{
         ConfigManager,
         constants::AGENT_SOURCE_PROMPT,
@@ -117,11 +117,12 @@ impl Executable<DefaultAgentInput> for DefaultAgentExecutable {
 
         events::agent::default_agent::tools(rendered_tools.clone());
 
-        let config = model_config
-            .into_openai_config(&execution_context.workspace.secrets_manager)
-            .await?;
-
-        let client = OpenAIClient::with_config(config);
+        let models = ModelChain::from_model(
+            model_config,
+            &execution_context.workspace.config_manager,
+            &execution_context.workspace.secrets_manager,
+        )
+        .await?;
 
         let messages: Result<Vec<ChatCompletionRequestMessage>, OxyError> = memory
             .into_iter()
@@ -184,8 +185,7 @@ impl Executable<DefaultAgentInput> for DefaultAgentExecutable {
             agent_name,
             rendered_tools,
             max_tool_concurrency,
-            client,
-            model_config.model_name().to_string(),
+            models,
             max_tool_calls,
             &config,
             reasoning_config,
@@ -194,7 +194,10 @@ impl Executable<DefaultAgentInput> for DefaultAgentExecutable {
         let outputs = react_executable
             .execute(execution_context, messages)
             .await
-            .map_err(|e| OxyError::RuntimeError(format!("Failed to execute react loop: {e}")))?;
+            .map_err(|e| match e {
+                OxyError::BudgetExceeded(_) => e,
+                e => OxyError::RuntimeError(format!("Failed to execute react loop: {e}")),
+            })?;
         let output = outputs
             .into_iter()
             .fold(Output::default(), |m, o| m.merge(&o.content));
@@ -210,8 +213,7 @@ async fn build_react_loop(
     agent_name: String,
     tool_configs: Vec<ToolType>,
     max_concurrency: usize,
-    client: OpenAIClient,
-    model: String,
+    models: ModelChain,
     max_iterations: usize,
     config: &ConfigManager,
     reasoning_config: Option<ReasoningConfig>,
@@ -231,8 +233,7 @@ async fn build_react_loop(
         )
         .memo(vec![])
         .executable(build_openai_executable(
-            client,
-            model,
+            models,
             tools,
             None,
             reasoning_config,
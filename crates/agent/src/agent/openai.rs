@@ -1,4 +1,4 @@
-use std::{collections::HashMap, sync::Arc};
+use std::collections::HashMap;
 
 use async_openai::{
     error::OpenAIError,
@@ -19,7 +19,11 @@ use super::openai_response::OpenAIResponseExecutable;
 use crate::types::Message;
 
 use oxy::{
-    adapters::{lenient_types::LenientChatCompletionResponse, openai::OpenAIClient},
+    adapters::{
+        lenient_types::LenientChatCompletionResponse,
+        model_chain::ModelChain,
+        replay::{FixtureStore, replay_openai_call, replay_openai_stream},
+    },
     config::{
         constants::{AGENT_RETRY_MAX_ELAPSED_TIME, AGENT_SOURCE_CONTENT},
         model::ReasoningConfig,
@@ -173,31 +177,34 @@ impl Executable<Vec<ChatCompletionRequestMessage>> for OpenAIOrOSSExecutable {
 #[derive(Clone, Debug, Serialize)]
 pub struct OpenAIExecutable {
     #[serde(skip)]
-    client: Arc<OpenAIClient>,
-    model: String,
+    models: ModelChain,
     tool_configs: Vec<ChatCompletionTool>,
     tool_choice: Option<ChatCompletionToolChoiceOption>,
     reasoning_config: Option<ReasoningConfig>,
     synthesize_mode: bool,
+    #[serde(skip)]
+    replay: Option<FixtureStore>,
 }
 
 impl OpenAIExecutable {
     pub fn new(
-        client: OpenAIClient,
-        model: String,
+        models: ModelChain,
         tool_configs: Vec<ChatCompletionTool>,
         tool_choice: Option<ChatCompletionToolChoiceOption>,
         reasoning_config: Option<ReasoningConfig>,
         synthesize_mode: bool,
     ) -> Self {
-        tracing::debug!("Building OpenAI executable for model: {}", model);
+        tracing::debug!(
+            "Building OpenAI executable for model: {}",
+            models.primary().model
+        );
         Self {
-            client: Arc::new(client),
-            model,
+            models,
             tool_configs,
             tool_choice,
             reasoning_config,
             synthesize_mode,
+            replay: FixtureStore::from_env(),
         }
     }
 
@@ -371,7 +378,8 @@ impl Executable<Vec<ChatCompletionRequestMessage>> for OpenAIExecutable {
     #[tracing::instrument(skip_all,err, fields(
         oxy.name = events::llm::LLM_OPENAI_CALL,
         oxy.span_type = events::llm::LLM_CALL_TYPE,
-        gen_ai.request.model = %self.model,
+        gen_ai.request.model = %self.models.primary().model,
+        gen_ai.response.model = tracing::field::Empty,
     ))]
     async fn execute(
         &mut self,
@@ -380,128 +388,145 @@ impl Executable<Vec<ChatCompletionRequestMessage>> for OpenAIExecutable {
     ) -> Result<Self::Response, OxyError> {
         events::llm::input(&input);
 
-        let chat = self.client.chat();
-
-        let func = || async {
-            let mut builder = CreateChatCompletionRequestArgs::default();
-            builder
-                .model(self.model.clone())
-                .messages(input.clone())
-                .stream_options(ChatCompletionStreamOptions {
-                    include_usage: Some(true),
-                    include_obfuscation: Some(false),
-                })
-                .stream(true);
-
-            if let Some(ReasoningConfig { effort }) = &self.reasoning_config {
-                builder.reasoning_effort(effort.clone());
-            }
+        let this = &*self;
+        let input = &input;
+        let func = move || {
+            this.models.call(move |route| async move {
+                let chat = route.client.chat();
+                let mut builder = CreateChatCompletionRequestArgs::default();
+                builder
+                    .model(route.model.clone())
+                    .messages(input.clone())
+                    .stream_options(ChatCompletionStreamOptions {
+                        include_usage: Some(true),
+                        include_obfuscation: Some(false),
+                    })
+                    .stream(true);
 
-            if let Some(tool_choice) = &self.tool_choice {
-                builder.tool_choice(tool_choice.clone());
-            }
+                if let Some(ReasoningConfig { effort }) = &this.reasoning_config {
+                    builder.reasoning_effort(effort.clone());
+                }
 
-            if !self.tool_configs.is_empty() {
-                let tools_wrapped: Vec<ChatCompletionTools> = self
-                    .tool_configs
-                    .clone()
-                    .into_iter()
-                    .map(ChatCompletionTools::Function)
-                    .collect();
-                builder.tools(tools_wrapped);
-            }
+                if let Some(tool_choice) = &this.tool_choice {
+                    builder.tool_choice(tool_choice.clone());
+                }
 
-            let request = builder.build().map_err(|err| {
-                tracing::error!("Failed to build completion request: {err:?}");
-                OxyError::RuntimeError(format!("Error building completion request: {err:?}"))
-            })?;
-
-            let mut response = chat.create_stream(request).await.map_err(|err| {
-                tracing::error!("Streaming request failed: {err}");
-                classify_openai_error(err)
-            })?;
-
-            let mut content = String::new();
-            let mut tool_calls = HashMap::<(u32, u32), ChatCompletionMessageToolCall>::new();
-            let mut last_parsed_length = 0;
-            let mut has_written = false;
-
-            while let Some(response) = response.next().await.transpose().map_err(|err| {
-                tracing::error!("Stream processing error: {err}");
-                classify_openai_error(err)
-            })? {
-                if let Some(usage_data) = response.usage {
-                    events::llm::usage(
-                        usage_data.prompt_tokens as i64,
-                        usage_data.completion_tokens as i64,
-                    );
-                    execution_context
-                        .write_usage(Usage::new(
-                            usage_data.prompt_tokens as i32,
-                            usage_data.completion_tokens as i32,
-                        ))
-                        .await?;
+                if !this.tool_configs.is_empty() {
+                    let tools_wrapped: Vec<ChatCompletionTools> = self
+                        .tool_configs
+                        .clone()
+                        .into_iter()
+                        .map(ChatCompletionTools::Function)
+                        .collect();
+                    builder.tools(tools_wrapped);
                 }
 
-                if let Some(chunk) = response.choices.first() {
-                    if let Some(tool_call_chunks) = &chunk.delta.tool_calls {
-                        self.parse_tool_call_chunks(&mut tool_calls, chunk.index, tool_call_chunks);
+                let request = builder.build().map_err(|err| {
+                    tracing::error!("Failed to build completion request: {err:?}");
+                    OxyError::RuntimeError(format!("Error building completion request: {err:?}"))
+                })?;
+
+                let mut response = replay_openai_stream(
+                    this.replay.as_ref(),
+                    &request,
+                    chat.create_stream(request.clone()),
+                )
+                .await
+                .map_err(|err| {
+                    tracing::error!("Streaming request failed: {err}");
+                    classify_openai_error(err)
+                })?;
+
+                let mut content = String::new();
+                let mut tool_calls = HashMap::<(u32, u32), ChatCompletionMessageToolCall>::new();
+                let mut last_parsed_length = 0;
+                let mut has_written = false;
+
+                while let Some(response) = response.next().await.transpose().map_err(|err| {
+                    tracing::error!("Stream processing error: {err}");
+                    classify_openai_error(err)
+                })? {
+                    if let Some(usage_data) = response.usage {
+                        events::llm::usage(
+                            usage_data.prompt_tokens as i64,
+                            usage_data.completion_tokens as i64,
+                        );
+                        route.record_usage(usage_data.prompt_tokens, usage_data.completion_tokens);
+                        execution_context
+                            .write_model_usage(
+                                &route.model,
+                                Usage::new(
+                                    usage_data.prompt_tokens as i32,
+                                    usage_data.completion_tokens as i32,
+                                ),
+                            )
+                            .await?;
                     }
-                    if let Some(message) = &chunk.delta.content {
-                        self.process_content_chunk(
-                            execution_context,
-                            &mut content,
-                            &tool_calls,
-                            &mut last_parsed_length,
-                            &mut has_written,
-                            message,
-                        )
-                        .await?;
+
+                    if let Some(chunk) = response.choices.first() {
+                        if let Some(tool_call_chunks) = &chunk.delta.tool_calls {
+                            this.parse_tool_call_chunks(
+                                &mut tool_calls,
+                                chunk.index,
+                                tool_call_chunks,
+                            );
+                        }
+                        if let Some(message) = &chunk.delta.content {
+                            this.process_content_chunk(
+                                execution_context,
+                                &mut content,
+                                &tool_calls,
+                                &mut last_parsed_length,
+                                &mut has_written,
+                                message,
+                            )
+                            .await?;
+                        }
                     }
                 }
-            }
 
-            let parsed_content = self.finalize_response(&content);
-            let tool_call_count = tool_calls.len();
-            if tool_call_count > 0 {
-                tracing::info!(
-                    llm.tool_calls_count = tool_call_count,
-                    "LLM returned tool calls"
-                );
-            }
+                let parsed_content = this.finalize_response(&content);
+                let tool_call_count = tool_calls.len();
+                if tool_call_count > 0 {
+                    tracing::info!(
+                        llm.tool_calls_count = tool_call_count,
+                        "LLM returned tool calls"
+                    );
+                }
 
-            let delta: Output = if has_written {
-                let mut output = Into::<Output>::into(parsed_content.data.clone());
-                output.replace("".to_string());
-                output
-            } else {
-                parsed_content.data.clone().into()
-            };
+                let delta: Output = if has_written {
+                    let mut output = Into::<Output>::into(parsed_content.data.clone());
+                    output.replace("".to_string());
+                    output
+                } else {
+                    parsed_content.data.clone().into()
+                };
 
-            execution_context
-                .write_chunk(Chunk {
-                    key: Some(AGENT_SOURCE_CONTENT.to_string()),
-                    delta,
-                    finished: true,
-                })
-                .await?;
+                execution_context
+                    .write_chunk(Chunk {
+                        key: Some(AGENT_SOURCE_CONTENT.to_string()),
+                        delta,
+                        finished: true,
+                    })
+                    .await?;
 
-            tracing::info!(
-                llm.response_content_length = content.len(),
-                llm.tool_calls_returned = tool_calls.len(),
-                "OpenAI LLM call completed"
-            );
+                tracing::info!(
+                    llm.response_content_length = content.len(),
+                    llm.tool_calls_returned = tool_calls.len(),
+                    "OpenAI LLM call completed"
+                );
 
-            let tool_calls_vec: Vec<_> = tool_calls.into_values().collect();
+                let tool_calls_vec: Vec<_> = tool_calls.into_values().collect();
 
-            let response = OpenAIExecutableResponse {
-                content: parsed_content.into(),
-                tool_calls: tool_calls_vec,
-            };
+                let response = OpenAIExecutableResponse {
+                    content: parsed_content.into(),
+                    tool_calls: tool_calls_vec,
+                };
 
-            events::llm::output(&response);
+                events::llm::output(&response);
 
-            Ok(response)
+                Ok(response)
+            })
         };
 
         let result = self.execute_with_retry(func, execution_context).await;
@@ -561,27 +586,27 @@ impl OpenAIExecutable {
 #[derive(Clone, Debug, Serialize)]
 pub struct OSSExecutable {
     #[serde(skip)]
-    client: Arc<OpenAIClient>,
-    model: String,
+    models: ModelChain,
     tool_configs: Vec<ChatCompletionTool>,
     tool_choice: Option<ChatCompletionToolChoiceOption>,
     reasoning_config: Option<ReasoningConfig>,
+    #[serde(skip)]
+    replay: Option<FixtureStore>,
 }
 
 impl OSSExecutable {
     pub fn new(
-        client: OpenAIClient,
-        model: String,
+        models: ModelChain,
         tool_configs: Vec<ChatCompletionTool>,
         tool_choice: Option<ChatCompletionToolChoiceOption>,
         reasoning_config: Option<ReasoningConfig>,
     ) -> Self {
         Self {
-            client: Arc::new(client),
-            model,
+            models,
             tool_configs,
             tool_choice,
             reasoning_config,
+            replay: FixtureStore::from_env(),
         }
     }
 
@@ -639,7 +664,8 @@ impl Executable<Vec<ChatCompletionRequestMessage>> for OSSExecutable {
     #[tracing::instrument(skip_all, err, fields(
         oxy.name = events::llm::LLM_OSS_CALL,
         oxy.span_type = events::llm::LLM_CALL_TYPE,
-        gen_ai.request.model = %self.model,
+        gen_ai.request.model = %self.models.primary().model,
+        gen_ai.response.model = tracing::field::Empty,
     ))]
     async fn execute(
         &mut self,
@@ -648,13 +674,16 @@ impl Executable<Vec<ChatCompletionRequestMessage>> for OSSExecutable {
     ) -> Result<Self::Response, OxyError> {
         events::llm::input(&input);
 
-        tracing::debug!("Starting OSS model execution with model: {}", self.model);
-
-        let chat = self.client.chat();
+        tracing::debug!(
+            "Starting OSS model execution with model: {}",
+            self.models.primary().model
+        );
 
         // OSS models: always use non-streaming, plain text output, no structured output
         let mut builder = CreateChatCompletionRequestArgs::default();
-        builder.model(self.model.clone()).messages(input.clone());
+        builder
+            .model(self.models.primary().model.clone())
+            .messages(input.clone());
         builder.stream(false);
 
         if let Some(tool_choice) = &self.tool_choice {
@@ -683,12 +712,25 @@ impl Executable<Vec<ChatCompletionRequestMessage>> for OSSExecutable {
         // Wrap the network call in retry to survive transient failures (TCP timeouts,
         // SSL handshake failures, server-side 5xx). The SDK retries 5xx/429 internally
         // but does not retry connection-level errors like Reqwest TCP timeouts.
-        let response: LenientChatCompletionResponse = self
+        let this = &*self;
+        let request = &request;
+        let (route, response): (_, LenientChatCompletionResponse) = self
             .execute_with_retry(
-                || async {
-                    chat.create_byot(request.clone())
-                        .await
-                        .map_err(classify_openai_error)
+                move || {
+                    this.models.call(move |route| {
+                        let mut request = request.clone();
+                        request.model = route.model.clone();
+                        async move {
+                            let response = replay_openai_call(
+                                this.replay.as_ref(),
+                                &request,
+                                route.client.chat().create_byot(request.clone()),
+                            )
+                            .await
+                            .map_err(classify_openai_error)?;
+                            Ok((route, response))
+                        }
+                    })
                 },
                 execution_context,
             )
@@ -699,11 +741,15 @@ impl Executable<Vec<ChatCompletionRequestMessage>> for OSSExecutable {
                 usage_data.prompt_tokens as i64,
                 usage_data.completion_tokens as i64,
             );
+            route.record_usage(usage_data.prompt_tokens, usage_data.completion_tokens);
             execution_context
-                .write_usage(Usage::new(
-                    usage_data.prompt_tokens as i32,
-                    usage_data.completion_tokens as i32,
-                ))
+                .write_model_usage(
+                    &route.model,
+                    Usage::new(
+                        usage_data.prompt_tokens as i32,
+                        usage_data.completion_tokens as i32,
+                    ),
+                )
                 .await?;
         }
 
@@ -826,8 +872,7 @@ impl oxy::execute::builders::map::ParamMapper<OneShotInput, Vec<ChatCompletionRe
 }
 
 pub fn build_openai_executable(
-    client: OpenAIClient,
-    model: String,
+    models: ModelChain,
     tool_configs: Vec<ChatCompletionTool>,
     tool_choice: Option<ChatCompletionToolChoiceOption>,
     reasoning_config: Option<ReasoningConfig>,
@@ -835,8 +880,7 @@ pub fn build_openai_executable(
 ) -> OpenAIOrOSSExecutable {
     if reasoning_config.is_some() {
         return OpenAIOrOSSExecutable::OpenAIResponse(OpenAIResponseExecutable::new(
-            client,
-            model,
+            models,
             tool_configs,
             tool_choice,
             reasoning_config,
@@ -844,10 +888,9 @@ pub fn build_openai_executable(
         ));
     }
 
-    if is_oss_model(&model) {
+    if is_oss_model(&models.primary().model) {
         return OpenAIOrOSSExecutable::OSS(OSSExecutable::new(
-            client,
-            model,
+            models,
             tool_configs,
             tool_choice,
             reasoning_config,
@@ -855,8 +898,7 @@ pub fn build_openai_executable(
     }
 
     OpenAIOrOSSExecutable::OpenAI(OpenAIExecutable::new(
-        client,
-        model,
+        models,
         tool_configs,
         tool_choice,
         reasoning_config,
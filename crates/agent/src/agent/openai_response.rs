@@ -1,7 +1,4 @@
-use std::{
-    collections::{HashMap, HashSet},
-    sync::Arc,
-};
+use std::collections::{HashMap, HashSet};
 
 use deser_incomplete::from_json_str;
 use futures::{Stream, StreamExt};
@@ -24,7 +21,10 @@ use crate::agent::openai::{
 };
 
 use oxy::{
-    adapters::openai::OpenAIClient,
+    adapters::{
+        model_chain::{ModelChain, ModelRoute},
+        replay::{FixtureStore, replay_openai_stream},
+    },
     config::{
         constants::{AGENT_RETRY_MAX_ELAPSED_TIME, AGENT_SOURCE_CONTENT},
         model::ReasoningConfig,
@@ -43,31 +43,34 @@ use oxy_shared::errors::OxyError;
 #[derive(Clone, Debug, Serialize)]
 pub struct OpenAIResponseExecutable {
     #[serde(skip)]
-    client: Arc<OpenAIClient>,
-    model: String,
+    models: ModelChain,
     tool_configs: Vec<ChatCompletionTool>,
     tool_choice: Option<ChatCompletionToolChoiceOption>,
     reasoning_config: Option<ReasoningConfig>,
     synthesize_mode: bool,
+    #[serde(skip)]
+    replay: Option<FixtureStore>,
 }
 
 impl OpenAIResponseExecutable {
     pub fn new(
-        client: OpenAIClient,
-        model: String,
+        models: ModelChain,
         tool_configs: Vec<ChatCompletionTool>,
         tool_choice: Option<ChatCompletionToolChoiceOption>,
         reasoning_config: Option<ReasoningConfig>,
         synthesize_mode: bool,
     ) -> Self {
-        tracing::debug!("Building OpenAI executable for model: {}", model);
+        tracing::debug!(
+            "Building OpenAI executable for model: {}",
+            models.primary().model
+        );
         Self {
-            client: Arc::new(client),
-            model,
+            models,
             tool_configs,
             tool_choice,
             reasoning_config,
             synthesize_mode,
+            replay: FixtureStore::from_env(),
         }
     }
 
@@ -201,13 +204,14 @@ impl OpenAIResponseExecutable {
 
     fn build_request(
         &self,
+        model: &str,
         input: Vec<ChatCompletionRequestMessage>,
     ) -> Result<CreateResponse, OxyError> {
         let mut builder = CreateResponseArgs::default();
         let response_input = convert_messages_to_response_input(input)?;
 
         builder
-            .model(self.model.clone())
+            .model(model)
             .input(response_input.clone())
             .stream(true);
 
@@ -239,6 +243,7 @@ impl OpenAIResponseExecutable {
     async fn process_stream(
         &self,
         mut event_stream: impl Stream<Item = Result<ResponseStreamEvent, OpenAIError>> + Unpin + Send,
+        route: &ModelRoute,
         execution_context: &ExecutionContext,
     ) -> Result<OpenAIExecutableResponse, backoff::Error<OxyError>> {
         let mut content = String::new();
@@ -299,11 +304,15 @@ impl OpenAIResponseExecutable {
                             usage_data.input_tokens as i64,
                             usage_data.output_tokens as i64,
                         );
+                        route.record_usage(usage_data.input_tokens, usage_data.output_tokens);
                         execution_context
-                            .write_usage(Usage::new(
-                                usage_data.input_tokens as i32,
-                                usage_data.output_tokens as i32,
-                            ))
+                            .write_model_usage(
+                                &route.model,
+                                Usage::new(
+                                    usage_data.input_tokens as i32,
+                                    usage_data.output_tokens as i32,
+                                ),
+                            )
                             .await
                             .map_err(backoff::Error::Permanent)?;
                     }
@@ -470,7 +479,8 @@ impl Executable<Vec<ChatCompletionRequestMessage>> for OpenAIResponseExecutable
     #[tracing::instrument(skip_all,err, fields(
         oxy.name = events::llm::LLM_OPENAI_RESPONSE_CALL,
         oxy.span_type = events::llm::LLM_CALL_TYPE,
-        gen_ai.request.model = %self.model,
+        gen_ai.request.model = %self.models.primary().model,
+        gen_ai.response.model = tracing::field::Empty,
     ))]
     async fn execute(
         &mut self,
@@ -479,22 +489,33 @@ impl Executable<Vec<ChatCompletionRequestMessage>> for OpenAIResponseExecutable
     ) -> Result<Self::Response, OxyError> {
         events::llm::input(&input);
 
-        tracing::debug!("Starting OpenAI execution with model: {}", self.model);
-
-        let responses = self.client.responses();
-        let input_clone = input.clone();
-
-        let func = || async {
-            let request = self
-                .build_request(input_clone.clone())
-                .map_err(backoff::Error::Permanent)?;
+        tracing::debug!(
+            "Starting OpenAI execution with model: {}",
+            self.models.primary().model
+        );
 
-            let event_stream = responses.create_stream(request).await.map_err(|err| {
-                tracing::error!("Streaming request failed: {err}");
-                classify_openai_error(err)
-            })?;
+        let this = &*self;
+        let input = &input;
+        let func = move || {
+            this.models.call(move |route| async move {
+                let request = this
+                    .build_request(&route.model, input.clone())
+                    .map_err(backoff::Error::Permanent)?;
 
-            self.process_stream(event_stream, execution_context).await
+                let event_stream = replay_openai_stream(
+                    this.replay.as_ref(),
+                    &request,
+                    route.client.responses().create_stream(request.clone()),
+                )
+                .await
+                .map_err(|err| {
+                    tracing::error!("Streaming request failed: {err}");
+                    classify_openai_error(err)
+                })?;
+
+                this.process_stream(event_stream, &route, execution_context)
+                    .await
+            })
         };
 
         let result = self.execute_with_retry(func, execution_context).await;
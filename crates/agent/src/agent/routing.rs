@@ -6,10 +6,7 @@ use super::tool::OpenAITool;
 use crate::{agent::openai::OpenAIExecutableResponse, routing::RouteResolver, types::Message};
 
 use oxy::{
-    adapters::{
-        openai::{AsyncFunctionObject, IntoOpenAIConfig, OpenAIClient},
-        secrets::SecretsManager,
-    },
+    adapters::{model_chain::ModelChain, openai::AsyncFunctionObject, secrets::SecretsManager},
     config::{
         ConfigManager,
         constants::{ARTIFACT_SOURCE, OPENAI_API_KEY_VAR},
@@ -181,11 +178,10 @@ async fn build_react_loop(
             .react_once(OpenAITool::new(agent_name, tool_configs, 1)),
     };
 
-    let client = OpenAIClient::with_config(model.into_openai_config(secrets_manager).await?);
+    let models = ModelChain::from_model(model, config, secrets_manager).await?;
     let deduplicated_tools = deduplicate_tools(tools)?;
     Ok(builder.memo(vec![]).executable(build_openai_executable(
-        client,
-        model.model_name().to_string(),
+        models,
         deduplicated_tools,
         None,
         reasoning_config,
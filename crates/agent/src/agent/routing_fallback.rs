@@ -7,10 +7,7 @@ use crate::{agent::openai::OpenAIExecutableResponse, agent::tool::OpenAITool};
 use super::openai::{OpenAIOrOSSExecutable, build_openai_executable};
 
 use oxy::{
-    adapters::{
-        openai::{AsyncFunctionObject, IntoOpenAIConfig, OpenAIClient},
-        secrets::SecretsManager,
-    },
+    adapters::{model_chain::ModelChain, openai::AsyncFunctionObject, secrets::SecretsManager},
     config::{
         ConfigManager,
         model::{Model, ReasoningConfig, ToolType},
@@ -36,12 +33,10 @@ impl FallbackAgent {
         secrets_manager: &SecretsManager,
         reasoning_config: Option<ReasoningConfig>,
     ) -> Result<Self, OxyError> {
-        let model_name = model.model_name();
         Ok(Self {
             agent_name: agent_name.to_string(),
             agent: build_openai_executable(
-                OpenAIClient::with_config(model.into_openai_config(secrets_manager).await?),
-                model_name.to_string(),
+                ModelChain::from_model(model, config, secrets_manager).await?,
                 vec![ChatCompletionTool::from_tool_async(&tool_config, config).await],
                 Some(ChatCompletionToolChoiceOption::Function(
                     tool_config.clone().into(),
@@ -60,6 +60,10 @@ impl Executable<OpenAIExecutableResponse> for OpenAITool {
 
         events::tool::input(&input);
 
+        execution_context
+            .charge_tool_calls(input.tool_calls.len())
+            .await?;
+
         let response = build_tool_executable(
             self.agent_name.to_string(),
             self.tool_configs.clone(),
@@ -117,6 +117,7 @@ impl<S> AutoSQL<S> {
                     dry_run_limit: None,
                     name: Some(slugify::slugify(&sql_params.title, "", "_", None)),
                     persist: false,
+                    cache_ttl_secs: None,
                 },
             )
             .await?;
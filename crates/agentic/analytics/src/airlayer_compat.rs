@@ -3,6 +3,9 @@
 //! This module provides:
 //! - YAML parsing shims for `.view.yml` / `.topic.yml` files that may differ
 //!   slightly from airlayer's expected format (e.g. optional `description`).
+//! - Expansion of oxy's composed measures (`derived`, `ratio`) into airlayer
+//!   `custom` measures, and the windowed measures airlayer cannot compute
+//!   (see [`crate::semantic::windows`]).
 //! - Dialect mapping from [`agentic_connector::SqlDialect`] to [`airlayer::Dialect`].
 //! - Parameter substitution for airlayer's parameterised SQL output.
 
@@ -41,8 +44,10 @@ struct ViewShim {
     entities: Vec<airlayer::Entity>,
     #[serde(default)]
     dimensions: Vec<airlayer::Dimension>,
+    /// Parsed by [`convert_measures`], since oxy has measure types airlayer
+    /// does not know.
     #[serde(default)]
-    measures: Option<Vec<airlayer::Measure>>,
+    measures: Option<Vec<serde_yaml::Value>>,
     #[serde(default)]
     segments: Vec<airlayer::schema::models::Segment>,
 }
@@ -65,16 +70,42 @@ struct TopicShim {
 
 // ── YAML parsing ─────────────────────────────────────────────────────────────
 
+/// An oxy view converted for airlayer, with the windowed measures airlayer
+/// cannot compute itself.
+#[derive(Debug)]
+pub struct ParsedView {
+    pub view: airlayer::View,
+    /// `cumulative`, `window` and `period_over_period` measures, as defined.
+    /// Each is also in `view` as a copy of the measure it is computed from.
+    pub windowed: Vec<oxy_semantic::Measure>,
+}
+
 /// Parse an oxy `.view.yml` string into an `airlayer::View`.
 ///
 /// Handles differences from airlayer's strict format:
 /// - `description` defaults to `None` when absent
 /// - `data_source` accepted as alias for `datasource`
+/// - `derived` and `ratio` measures become `custom` measures
+/// - windowed measures become copies of the measure they are computed from;
+///   use [`parse_oxy_view`] to keep their definitions
 pub fn parse_view_yaml(
     yaml: &str,
 ) -> Result<airlayer::View, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(parse_oxy_view(yaml)?.view)
+}
+
+/// Parse an oxy `.view.yml` string, keeping the definitions of its windowed
+/// measures.
+pub fn parse_oxy_view(yaml: &str) -> Result<ParsedView, Box<dyn std::error::Error + Send + Sync>> {
     let shim: ViewShim = serde_yaml::from_str(yaml)?;
-    Ok(airlayer::View {
+    let (measures, windowed) = match shim.measures {
+        Some(values) => {
+            let (measures, windowed) = convert_measures(values)?;
+            (Some(measures), windowed)
+        }
+        None => (None, Vec::new()),
+    };
+    let view = airlayer::View {
         name: shim.name,
         description: shim.description,
         label: shim.label,
@@ -84,11 +115,101 @@ pub fn parse_view_yaml(
         sql: shim.sql,
         entities: shim.entities,
         dimensions: shim.dimensions,
-        measures: shim.measures,
+        measures,
         segments: shim.segments,
         pre_aggregations: None,
         meta: None,
-    })
+    };
+    Ok(ParsedView { view, windowed })
+}
+
+// ── Composed measures ────────────────────────────────────────────────────────
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Keys oxy uses to describe measures built from other measures.
+const COMPOSITION_KEYS: &[&str] = &[
+    "numerator",
+    "denominator",
+    "measure",
+    "time_dimension",
+    "grain",
+    "window",
+    "aggregation",
+    "offset",
+    "comparison",
+];
+
+/// Convert oxy measure definitions into airlayer measures.
+///
+/// `derived` and `ratio` measures are inlined by
+/// [`oxy_semantic::metrics::expand_measure_list`]. Windowed measures are
+/// computed over grouped results, which airlayer cannot express: they are
+/// returned separately, and listed in the view as a copy of the measure
+/// they are computed from so the catalog can search and qualify them.
+fn convert_measures(
+    values: Vec<serde_yaml::Value>,
+) -> Result<(Vec<airlayer::Measure>, Vec<oxy_semantic::Measure>), BoxError> {
+    // Measures of types oxy does not define are passed to airlayer as they
+    // are; they just cannot be referenced by composed measures.
+    let definitions: Vec<oxy_semantic::Measure> = values
+        .iter()
+        .filter_map(|value| serde_yaml::from_value(value.clone()).ok())
+        .collect();
+    let expanded = oxy_semantic::metrics::expand_measure_list(&definitions)?;
+
+    let mut measures = Vec::with_capacity(values.len());
+    let mut windowed = Vec::new();
+    for value in values {
+        let name = value.get("name").and_then(|n| n.as_str());
+        let definition = definitions.iter().find(|m| Some(m.name.as_str()) == name);
+        match definition.map(|m| &m.measure_type) {
+            Some(measure_type) if measure_type.is_windowed() => {
+                windowed.extend(definition.cloned());
+            }
+            Some(oxy_semantic::MeasureType::Derived | oxy_semantic::MeasureType::Ratio) => {
+                let expr = expanded
+                    .iter()
+                    .find(|m| Some(m.name.as_str()) == name)
+                    .and_then(|m| m.expr.clone())
+                    .unwrap_or_default();
+                let mut custom = value;
+                if let Some(mapping) = custom.as_mapping_mut() {
+                    for key in COMPOSITION_KEYS.iter().chain(&["filters"]) {
+                        mapping.remove(*key);
+                    }
+                    mapping.insert("type".into(), "custom".into());
+                    mapping.insert("expr".into(), expr.into());
+                }
+                measures.push(serde_yaml::from_value(custom)?);
+            }
+            _ => measures.push(serde_yaml::from_value(value)?),
+        }
+    }
+
+    for definition in &windowed {
+        let base_name =
+            definition.composition.measure.as_deref().ok_or_else(|| {
+                format!("Measure '{}' requires a 'measure' field", definition.name)
+            })?;
+        let base: &airlayer::Measure =
+            measures
+                .iter()
+                .find(|m| m.name == base_name)
+                .ok_or_else(|| {
+                    format!(
+                        "Measure '{}' is computed from unknown measure '{base_name}'",
+                        definition.name
+                    )
+                })?;
+        let mut listed = base.clone();
+        listed.name = definition.name.clone();
+        listed.description = definition.description.clone().or(listed.description);
+        listed.samples = definition.samples.clone();
+        listed.synonyms = definition.synonyms.clone();
+        measures.push(listed);
+    }
+    Ok((measures, windowed))
 }
 
 /// Parse an oxy `.topic.yml` string into an `airlayer::Topic`.
@@ -68,14 +68,20 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
+use agentic_core::rate_limit::{RateLimit, RateLimiter};
+use agentic_core::spend::SharedSpendTracker;
+
 use crate::catalog::SchemaCatalog;
 use crate::engine::cube::CubeEngine;
 use crate::engine::looker::LookerEngine;
 use crate::engine::{EngineError, SemanticEngine};
 #[cfg(test)]
 use crate::llm::ReasoningEffort;
-use crate::llm::{DEFAULT_MODEL, LlmClient, OpenAiCompatProvider, OpenAiProvider, ThinkingConfig};
-use crate::semantic::SemanticCatalog;
+use crate::llm::{
+    BedrockProvider, DEFAULT_MODEL, GeminiProvider, LlmClient, OpenAiCompatProvider,
+    OpenAiProvider, ThinkingConfig,
+};
+use crate::semantic::{FieldRestriction, SemanticCatalog};
 use crate::solver::AnalyticsSolver;
 use crate::validation::Validator;
 
@@ -256,6 +262,65 @@ pub struct ResolvedModelInfo {
     /// Azure API version (e.g. `"2025-03-01-preview"`). Present only for
     /// Azure OpenAI models configured with `azure_api_version` in config.yml.
     pub azure_api_version: Option<String>,
+    /// Name of the model entry in config.yml.  Keys the model in the circuit
+    /// breaker used for fallbacks.
+    pub name: String,
+    /// Client-side rate limit from the model's `rate_limit` in config.yml.
+    pub rate_limit: Option<RateLimit>,
+    /// Key the rate limit is shared under; models using the same API key
+    /// share one limiter.
+    pub rate_limit_key: String,
+    /// The models in the model's `fallbacks`, in order.  Their own fallbacks
+    /// are not followed.
+    pub fallbacks: Vec<ResolvedModelInfo>,
+}
+
+/// Apply the rate limit and fallbacks of `info` to a client built for it.
+///
+/// The conversation history is built by the primary model's provider, so a
+/// fallback is only used when it shares that message format: the same vendor,
+/// and both or neither on Azure.  Other fallbacks are skipped with a warning.
+pub fn with_model_routing(client: LlmClient, info: &ResolvedModelInfo) -> LlmClient {
+    let rate_limited = |client: LlmClient, info: &ResolvedModelInfo| match info.rate_limit {
+        Some(limit) => client.with_rate_limit(RateLimiter::shared(&info.rate_limit_key, limit)),
+        None => client,
+    };
+    let client = rate_limited(client, info);
+    if info.fallbacks.is_empty() {
+        return client;
+    }
+
+    let is_azure = |m: &ResolvedModelInfo| m.azure_deployment_id.is_some();
+    let fallbacks: Vec<(String, LlmClient)> = info
+        .fallbacks
+        .iter()
+        .filter(|fallback| {
+            let compatible = fallback.vendor == info.vendor && is_azure(fallback) == is_azure(info);
+            if !compatible {
+                tracing::warn!(
+                    model = %info.name,
+                    fallback = %fallback.name,
+                    "skipping fallback model with a different vendor"
+                );
+            }
+            compatible
+        })
+        .map(|fallback| {
+            let client = build_llm_client(
+                &fallback.vendor,
+                fallback.api_key.as_deref().unwrap_or_default(),
+                &fallback.model,
+                fallback.base_url.as_deref(),
+                fallback.azure_deployment_id.as_deref(),
+                fallback.azure_api_version.as_deref(),
+            );
+            (fallback.name.clone(), rate_limited(client, fallback))
+        })
+        .collect();
+    if fallbacks.is_empty() {
+        return client;
+    }
+    client.with_fallbacks(info.name.clone(), fallbacks)
 }
 
 // ── BuildContext ──────────────────────────────────────────────────────────────
@@ -279,6 +344,10 @@ pub struct BuildContext {
     pub thinking_override: Option<ThinkingConfig>,
     /// Runtime model override (from UI "extended thinking" mode toggle).
     pub model_override: Option<String>,
+    /// Column-level restrictions for the caller, keyed by `view.field`.
+    pub field_restrictions: HashMap<String, FieldRestriction>,
+    /// Budget every LLM client of the run is charged to.
+    pub spend_tracker: Option<SharedSpendTracker>,
 }
 
 // ── AgentConfig methods ───────────────────────────────────────────────────────
@@ -331,7 +400,7 @@ fn build_llm_client(
     if let (Some(deployment_id), Some(api_version), Some(base)) =
         (azure_deployment_id, azure_api_version, base_url)
     {
-        return LlmClient::with_provider(OpenAiCompatProvider::for_azure(
+        return LlmClient::with_live_provider(OpenAiCompatProvider::for_azure(
             api_key,
             model,
             base,
@@ -358,11 +427,27 @@ fn build_llm_client(
             } else {
                 OpenAiProvider::new(api_key, model)
             };
-            LlmClient::with_provider(provider)
+            LlmClient::with_live_provider(provider)
         }
         LlmVendor::OpenAiCompat => {
             let url = base_url.unwrap_or("http://localhost:11434/v1");
-            LlmClient::with_provider(OpenAiCompatProvider::new(api_key, model, url))
+            LlmClient::with_live_provider(OpenAiCompatProvider::new(api_key, model, url))
+        }
+        LlmVendor::Gemini => {
+            let provider = if let Some(url) = base_url {
+                GeminiProvider::with_base_url(api_key, model, url)
+            } else {
+                GeminiProvider::new(api_key, model)
+            };
+            LlmClient::with_live_provider(provider)
+        }
+        LlmVendor::Bedrock => {
+            let provider = if let Some(url) = base_url {
+                BedrockProvider::with_base_url(model, None, url)
+            } else {
+                BedrockProvider::new(model, None)
+            };
+            LlmClient::with_live_provider(provider)
         }
     }
 }
@@ -505,8 +590,12 @@ impl AgentConfig {
         let catalog = if ctx.semantic_files.is_empty() {
             SemanticCatalog::empty()
         } else {
-            SemanticCatalog::load_files(&ctx.semantic_files, dialect_map)
-                .map_err(ConfigError::SemanticError)?
+            SemanticCatalog::load_files_restricted(
+                &ctx.semantic_files,
+                dialect_map,
+                &build_ctx.field_restrictions,
+            )
+            .map_err(ConfigError::SemanticError)?
         };
 
         // 5. Build LLM client.
@@ -554,6 +643,8 @@ impl AgentConfig {
             .or_else(|| match effective_vendor {
                 LlmVendor::Anthropic => std::env::var("ANTHROPIC_API_KEY").ok(),
                 LlmVendor::OpenAi | LlmVendor::OpenAiCompat => std::env::var("OPENAI_API_KEY").ok(),
+                LlmVendor::Gemini => std::env::var("GEMINI_API_KEY").ok(),
+                LlmVendor::Bedrock => None,
             })
             .unwrap_or_default();
 
@@ -576,6 +667,20 @@ impl AgentConfig {
             azure_deployment_id,
             azure_api_version,
         );
+        // Fallbacks and rate limits come from config.yml, so they only apply
+        // when the agent runs the project model.
+        let client = match pmi
+            .as_ref()
+            .filter(|m| m.model == model && &m.vendor == effective_vendor)
+        {
+            Some(info) => with_model_routing(client, info),
+            None => client,
+        };
+        let spend_tracked = |client: LlmClient| match &build_ctx.spend_tracker {
+            Some(tracker) => client.with_spend_tracker(tracker.clone()),
+            None => client,
+        };
+        let client = spend_tracked(client);
 
         // Build per-state clients for states that declare a `model:` override.
         // Inherits vendor / api_key / base_url / azure config from the global config.
@@ -592,7 +697,7 @@ impl AgentConfig {
                         azure_deployment_id,
                         azure_api_version,
                     );
-                    (state_name.clone(), c)
+                    (state_name.clone(), spend_tracked(c))
                 })
             })
             .collect();
@@ -650,7 +755,7 @@ impl AgentConfig {
                 azure_deployment_id,
                 azure_api_version,
             );
-            solver = solver.with_client_override(override_client);
+            solver = solver.with_client_override(spend_tracked(override_client));
         }
         // `with_client_override` already sets `extended_thinking_active = true`
         // when a model override is present.  Handle the case where only a
@@ -283,7 +283,7 @@ fn parse_effort_level(s: &str) -> ThinkingConfig {
 ///   model: llama3.2
 ///   base_url: http://localhost:11434/v1
 /// ```
-#[derive(Debug, Clone, Deserialize, Default, JsonSchema)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Default, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum LlmVendor {
     /// Anthropic Messages API (default).  Uses `ANTHROPIC_API_KEY`.
@@ -296,6 +296,13 @@ pub enum LlmVendor {
     /// backends.  Uses `OPENAI_API_KEY` as fallback (or pass `api_key`
     /// directly).
     OpenAiCompat,
+    /// Google Gemini API (`streamGenerateContent`).  Uses `GEMINI_API_KEY`.
+    Gemini,
+    /// Claude on AWS Bedrock, signed with SigV4.  Credentials and region come
+    /// from the standard AWS environment (`AWS_REGION`, profile, IAM role);
+    /// `model` is the Bedrock model id or inference profile and `base_url`
+    /// an optional runtime endpoint override.
+    Bedrock,
 }
 
 /// LLM configuration section.
@@ -324,8 +331,9 @@ pub struct LlmConfigYaml {
     #[serde(default)]
     pub model: Option<String>,
 
-    /// API key.  Falls back to `ANTHROPIC_API_KEY` (Anthropic) or
-    /// `OPENAI_API_KEY` (OpenAi / OpenAiCompat) environment variables.
+    /// API key.  Falls back to `ANTHROPIC_API_KEY` (Anthropic),
+    /// `OPENAI_API_KEY` (OpenAi / OpenAiCompat) or `GEMINI_API_KEY` (Gemini)
+    /// environment variables.  Unused for Bedrock.
     #[serde(default)]
     pub api_key: Option<String>,
 
@@ -334,6 +342,8 @@ pub struct LlmConfigYaml {
     /// - Anthropic: proxy URL (default: `https://api.anthropic.com/v1/messages`)
     /// - OpenAi: Responses API base (default: `https://api.openai.com/v1/responses`)
     /// - OpenAiCompat: local server root, e.g. `http://localhost:11434/v1`
+    /// - Gemini: API root (default: `https://generativelanguage.googleapis.com/v1beta`)
+    /// - Bedrock: runtime endpoint (default: `https://bedrock-runtime.{region}.amazonaws.com`)
     #[serde(default)]
     pub base_url: Option<String>,
 
@@ -39,7 +39,7 @@ pub use catalog::SchemaCatalog;
 #[doc(hidden)]
 pub use catalog::{Catalog, CatalogError, SemanticLayerError};
 #[doc(hidden)]
-pub use semantic::SemanticCatalog;
+pub use semantic::{FieldRestriction, SemanticCatalog};
 
 // ── Events ──────────────────────────────────────────────────────────────────
 
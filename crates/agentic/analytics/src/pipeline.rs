@@ -16,6 +16,7 @@ use agentic_connector::DatabaseConnector;
 use agentic_core::events::{Event, EventStream};
 use agentic_core::human_input::SuspendedRunData;
 use agentic_core::orchestrator::{Orchestrator, OrchestratorError};
+use agentic_core::spend::SharedSpendTracker;
 use agentic_runtime::handle::{PipelineHandle, PipelineOutcome};
 
 use crate::catalog::SchemaCatalog;
@@ -23,6 +24,7 @@ use crate::config::{AgentConfig, BuildContext, ConfigError, ResolvedModelInfo};
 use crate::events::AnalyticsEvent;
 use crate::metric_sink::SharedMetricSink;
 use crate::procedure::ProcedureRunner;
+use crate::semantic::FieldRestriction;
 use crate::solver::build_analytics_handlers;
 use crate::types::{AnalyticsIntent, ConversationTurn, QuestionType, SpecHint};
 
@@ -55,6 +57,12 @@ pub struct PipelineParams {
     /// dimensions) to an external observability backend. `None` means
     /// metrics won't be recorded — the pipeline still runs.
     pub metric_sink: Option<SharedMetricSink>,
+    /// Column-level restrictions for the caller, keyed by `view.field`.
+    /// Empty when the caller is not subject to column access rules.
+    pub field_restrictions: HashMap<String, FieldRestriction>,
+    /// Budget the run's LLM calls and tool calls are charged to. `None`
+    /// when the host enforces no budgets.
+    pub spend_tracker: Option<SharedSpendTracker>,
 }
 
 // ── start_pipeline ───────────────────────────────────────────────────────────
@@ -107,6 +115,8 @@ pub async fn start_pipeline(
         schema_cache: params.schema_cache,
         thinking_override,
         model_override,
+        field_restrictions: params.field_restrictions,
+        spend_tracker: params.spend_tracker,
     };
 
     let (solver, _procedure_files) = params
@@ -259,6 +269,8 @@ pub async fn resume_pipeline(
         schema_cache: params.schema_cache,
         thinking_override,
         model_override,
+        field_restrictions: params.field_restrictions,
+        spend_tracker: params.spend_tracker,
     };
 
     let (solver, _procedure_files) = params
@@ -337,6 +337,10 @@ pub fn specify_response_schema() -> ResponseSchema {
                 "type": ["integer", "null"],
                 "description": "Maximum number of rows to return. Null for no limit. Use when the user asks for top-N results."
             },
+            "time_spine": {
+                "type": "boolean",
+                "description": "True to return a zero-valued row for every time bucket with no data between the first and last bucket, so trends have no gaps. Requires exactly one time dimension with a granularity."
+            },
             "assumptions": {
                 "type": "array",
                 "description": "Any ambiguous resolutions or assumptions made, so the user can review.",
@@ -351,6 +355,7 @@ pub fn specify_response_schema() -> ResponseSchema {
             "time_dimensions",
             "order",
             "limit",
+            "time_spine",
             "assumptions"
         ]
     });
@@ -480,6 +485,7 @@ mod tests {
         assert!(item_fields.contains(&"time_dimensions"));
         assert!(item_fields.contains(&"order"));
         assert!(item_fields.contains(&"limit"));
+        assert!(item_fields.contains(&"time_spine"));
         assert!(item_fields.contains(&"assumptions"));
     }
 
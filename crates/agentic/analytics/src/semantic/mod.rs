@@ -9,6 +9,8 @@
 //! - [`helpers`]: view/measure/dimension lookup + qualify_names + fuzzy-normalize.
 //! - [`filters`]: parse raw filter strings into structured airlayer `QueryFilter`.
 //! - [`translation`]: translate airlayer `QueryRequest` → raw-schema context.
+//! - [`time_spine`]: fill the time buckets missing from trend results.
+//! - [`windows`]: compile queries with windowed measures.
 //! - [`trait_impl`]: `impl Catalog for SemanticCatalog` (trait surface for tools).
 //!
 //! # Oxy YAML format
@@ -52,6 +54,7 @@
 //!   - customers_view
 //! ```
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use crate::airlayer_compat;
@@ -59,12 +62,45 @@ use crate::catalog::QueryContext;
 
 pub mod filters;
 pub mod helpers;
+pub mod time_spine;
 pub mod trait_impl;
 pub mod translation;
+pub mod windows;
 
 #[cfg(test)]
 mod tests;
 
+// ── Field restrictions ───────────────────────────────────────────────────────
+
+/// Column-level restriction the host applies to a semantic field for the
+/// current caller.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldRestriction {
+    /// Remove the field from the catalog.
+    Hidden,
+    /// Replace the dimension's expression with this masking SQL.
+    Masked(String),
+}
+
+fn restrict_view(view: &mut airlayer::View, restrictions: &HashMap<String, FieldRestriction>) {
+    let name = view.name.clone();
+    let restriction = |field: &str| restrictions.get(&format!("{name}.{field}"));
+    view.dimensions
+        .retain_mut(|dimension| match restriction(&dimension.name) {
+            None => true,
+            Some(FieldRestriction::Hidden) => false,
+            Some(FieldRestriction::Masked(expr)) => {
+                dimension.expr = expr.clone();
+                dimension.original_expr = None;
+                dimension.samples = None;
+                true
+            }
+        });
+    if let Some(measures) = view.measures.as_mut() {
+        measures.retain(|measure| restriction(&measure.name).is_none());
+    }
+}
+
 // ── SemanticCatalog ───────────────────────────────────────────────────────────
 
 /// Semantic layer catalog backed by [`airlayer::SemanticEngine`].
@@ -75,11 +111,14 @@ mod tests;
 /// - **`list_metrics`**: searches measure names and descriptions across all views.
 /// - **`list_dimensions`**: returns dimensions from the metric's view plus views
 ///   joinable via entity relationships.
-/// - **`try_compile`**: delegates to `engine.compile_query()` — supports multi-hop
-///   joins, CTE fan-out protection, and dialect-aware SQL generation.  Returns
-///   [`crate::catalog::CatalogError::TooComplex`] for queries that airlayer cannot compile.
+/// - **`try_compile`**: delegates to [`SemanticCatalog::compile_query`] — supports
+///   multi-hop joins, CTE fan-out protection, dialect-aware SQL generation and
+///   windowed measures.  Returns [`crate::catalog::CatalogError::TooComplex`] for
+///   queries that airlayer cannot compile.
 pub struct SemanticCatalog {
     pub(super) engine: airlayer::SemanticEngine,
+    /// Windowed measures with the name of their view.
+    pub(super) windowed: Vec<(String, oxy_semantic::Measure)>,
 }
 
 impl std::fmt::Debug for SemanticCatalog {
@@ -100,7 +139,7 @@ impl SemanticCatalog {
         let dialects = airlayer::DatasourceDialectMap::new();
         let engine = airlayer::SemanticEngine::from_semantic_layer(layer, dialects)
             .expect("empty semantic layer should always be valid");
-        Self { engine }
+        Self::from_engine(engine)
     }
 
     /// Return `true` when this catalog has no views.
@@ -110,7 +149,10 @@ impl SemanticCatalog {
 
     /// Wrap a pre-built engine (useful for testing).
     pub fn from_engine(engine: airlayer::SemanticEngine) -> Self {
-        Self { engine }
+        Self {
+            engine,
+            windowed: Vec::new(),
+        }
     }
 
     /// Load from a `semantics/` directory containing `views/` and `topics/`
@@ -134,7 +176,7 @@ impl SemanticCatalog {
             },
         )?;
 
-        Ok(Self { engine })
+        Ok(Self::from_engine(engine))
     }
 
     /// Load from an explicit list of `.view.yml` and `.topic.yml` paths.
@@ -146,21 +188,58 @@ impl SemanticCatalog {
     pub fn load_files(
         paths: &[PathBuf],
         dialects: airlayer::DatasourceDialectMap,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Self::load_files_restricted(paths, dialects, &HashMap::new())
+    }
+
+    /// Like [`load_files`], applying the caller's `restrictions` (keyed by
+    /// `view.field`) before the engine is built, so hidden fields can be
+    /// neither searched nor compiled.
+    ///
+    /// [`load_files`]: SemanticCatalog::load_files
+    pub fn load_files_restricted(
+        paths: &[PathBuf],
+        dialects: airlayer::DatasourceDialectMap,
+        restrictions: &HashMap<String, FieldRestriction>,
     ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
         let mut views = Vec::new();
+        let mut windowed = Vec::new();
         let mut topics = Vec::new();
 
         for path in paths {
             let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
             let content = std::fs::read_to_string(path)?;
             if name.ends_with(".view.yml") || name.ends_with(".view.yaml") {
-                views.push(airlayer_compat::parse_view_yaml(&content)?);
+                let parsed = airlayer_compat::parse_oxy_view(&content)?;
+                let view_name = parsed.view.name.clone();
+                windowed.extend(parsed.windowed.into_iter().map(|m| (view_name.clone(), m)));
+                views.push(parsed.view);
             } else if name.ends_with(".topic.yml") || name.ends_with(".topic.yaml") {
                 topics.push(airlayer_compat::parse_topic_yaml(&content)?);
             }
             // Other suffixes silently ignored.
         }
 
+        if !restrictions.is_empty() {
+            // A windowed measure is listed as a copy of the measure it is
+            // computed from, so it is hidden along with that measure.
+            let mut restrictions = restrictions.clone();
+            for (view, measure) in &windowed {
+                if let Some(base) = &measure.composition.measure
+                    && restrictions.contains_key(&format!("{view}.{base}"))
+                {
+                    restrictions
+                        .insert(format!("{view}.{}", measure.name), FieldRestriction::Hidden);
+                }
+            }
+            windowed.retain(|(view, measure)| {
+                !restrictions.contains_key(&format!("{view}.{}", measure.name))
+            });
+            for view in &mut views {
+                restrict_view(view, &restrictions);
+            }
+        }
+
         let topic_opt = if topics.is_empty() {
             None
         } else {
@@ -174,7 +253,7 @@ impl SemanticCatalog {
             },
         )?;
 
-        Ok(Self { engine })
+        Ok(Self { engine, windowed })
     }
 
     // ── Validation helpers (used by validation rules) ────────────────────────
@@ -4,13 +4,17 @@ use crate::catalog::Catalog;
 
 fn build_catalog(view_yamls: &[&str]) -> SemanticCatalog {
     let mut views = Vec::new();
+    let mut windowed = Vec::new();
     for yaml in view_yamls {
-        views.push(airlayer_compat::parse_view_yaml(yaml).unwrap());
+        let parsed = airlayer_compat::parse_oxy_view(yaml).unwrap();
+        let view_name = parsed.view.name.clone();
+        windowed.extend(parsed.windowed.into_iter().map(|m| (view_name.clone(), m)));
+        views.push(parsed.view);
     }
     let layer = airlayer::SemanticLayer::new(views, None);
     let dialects = airlayer::DatasourceDialectMap::with_default(airlayer::Dialect::DuckDB);
     let engine = airlayer::SemanticEngine::from_semantic_layer(layer, dialects).unwrap();
-    SemanticCatalog::from_engine(engine)
+    SemanticCatalog { engine, windowed }
 }
 
 fn orders_view() -> &'static str {
@@ -192,3 +196,223 @@ fn to_table_summary_compact() {
     assert!(s.contains("orders") || s.contains("customers"));
     assert!(s.contains("2")); // view count
 }
+
+// ── field restrictions ────────────────────────────────────────────────────
+
+#[test]
+fn restrict_view_hides_and_masks_fields() {
+    let mut view = airlayer_compat::parse_view_yaml(orders_view()).unwrap();
+    let restrictions = HashMap::from([
+        (
+            "orders_view.status".to_string(),
+            FieldRestriction::Masked("'***'".to_string()),
+        ),
+        ("orders_view.revenue".to_string(), FieldRestriction::Hidden),
+        (
+            "orders_view.order_date".to_string(),
+            FieldRestriction::Hidden,
+        ),
+    ]);
+    restrict_view(&mut view, &restrictions);
+
+    let status = view.dimensions.iter().find(|d| d.name == "status").unwrap();
+    assert_eq!(status.expr, "'***'");
+    assert!(status.samples.is_none());
+    assert!(!view.dimensions.iter().any(|d| d.name == "order_date"));
+
+    let layer = airlayer::SemanticLayer::new(vec![view], None);
+    let dialects = airlayer::DatasourceDialectMap::with_default(airlayer::Dialect::DuckDB);
+    let engine = airlayer::SemanticEngine::from_semantic_layer(layer, dialects).unwrap();
+    let cat = SemanticCatalog::from_engine(engine);
+    assert!(!cat.column_exists("orders_view", "revenue"));
+    assert!(cat.column_exists("orders_view", "order_count"));
+}
+
+// ── composed measures ─────────────────────────────────────────────────────
+
+#[test]
+fn composed_measures_are_inlined_and_windowed_ones_listed() {
+    let yaml = format!(
+        "{}  - name: average_order_value
+    type: ratio
+    numerator: revenue
+    denominator: order_count
+  - name: running_revenue
+    type: cumulative
+    measure: revenue
+    time_dimension: order_date
+    grain: month
+",
+        orders_view()
+    );
+    let view = airlayer_compat::parse_view_yaml(&yaml).unwrap();
+    let measures = view.measures.as_ref().unwrap();
+    let ratio = measures
+        .iter()
+        .find(|m| m.name == "average_order_value")
+        .unwrap();
+    assert_eq!(
+        ratio.expr.as_deref(),
+        Some("(SUM(amount)) * 1.0 / NULLIF((COUNT(*)), 0)")
+    );
+    let running = measures
+        .iter()
+        .find(|m| m.name == "running_revenue")
+        .unwrap();
+    assert_eq!(running.expr.as_deref(), Some("amount"));
+
+    let cat = build_catalog(&[&yaml]);
+    assert!(cat.metric_resolves_in_semantic("average_order_value"));
+    assert!(cat.metric_resolves_in_semantic("running_revenue"));
+}
+
+#[test]
+fn windowed_measures_compile_over_grouped_rows() {
+    let yaml = format!(
+        "{}  - name: running_revenue
+    type: cumulative
+    measure: revenue
+    time_dimension: order_date
+    grain: month
+",
+        orders_view()
+    );
+    let cat = build_catalog(&[&yaml]);
+    let item: crate::types::QueryRequestItem = serde_json::from_value(serde_json::json!({
+        "measures": ["orders_view.revenue", "orders_view.running_revenue"],
+        "dimensions": ["orders_view.status"],
+        "order": [{"id": "orders_view.running_revenue", "desc": true}],
+        "limit": 10,
+    }))
+    .unwrap();
+    let sql = cat.compile_query(&item.to_query_request()).unwrap();
+
+    // Grouped by the measure's grain, partitioned by the other dimensions,
+    // and ordered and paged outside the window.
+    assert!(sql.starts_with("SELECT "), "{sql}");
+    assert!(sql.contains("orders_view__order_date_month"), "{sql}");
+    assert!(sql.contains("OVER (PARTITION BY "), "{sql}");
+    assert!(sql.contains("UNBOUNDED PRECEDING"), "{sql}");
+    assert!(sql.contains("orders_view__running_revenue"), "{sql}");
+    assert!(sql.ends_with("DESC LIMIT 10"), "{sql}");
+}
+
+#[test]
+fn composed_measure_cycle_is_rejected() {
+    let yaml = format!(
+        "{}  - name: a
+    type: derived
+    expr: \"{{b}} + 1\"
+  - name: b
+    type: derived
+    expr: \"{{a}} - 1\"
+",
+        orders_view()
+    );
+    assert!(airlayer_compat::parse_view_yaml(&yaml).is_err());
+}
+
+// ── time grains and time spine ────────────────────────────────────────────
+
+#[test]
+fn grain_dimension_becomes_time_dimension() {
+    let item: crate::types::QueryRequestItem = serde_json::from_value(serde_json::json!({
+        "measures": ["orders_view.revenue"],
+        "dimensions": ["orders_view.order_date.month", "orders_view.status"],
+        "order": [{"id": "orders_view.order_date.month", "desc": false}],
+        "limit": null,
+    }))
+    .unwrap();
+    let request = item.to_query_request();
+    assert_eq!(request.dimensions, ["orders_view.status"]);
+    assert_eq!(
+        request.time_dimensions[0].dimension,
+        "orders_view.order_date"
+    );
+    assert_eq!(
+        request.time_dimensions[0].granularity.as_deref(),
+        Some("month")
+    );
+    assert_eq!(request.order[0].id, "orders_view.order_date");
+    assert_eq!(
+        item.time_buckets(),
+        [("orders_view.order_date".to_string(), "month".to_string())]
+    );
+}
+
+#[test]
+fn time_spine_fills_missing_buckets() {
+    use agentic_core::result::{CellValue, QueryResult, QueryRow};
+
+    let catalog = build_catalog(&[r#"
+name: orders_view
+table: orders
+dimensions:
+  - name: order_date
+    type: date
+    expr: order_date
+measures:
+  - name: revenue
+    type: sum
+    expr: amount
+  - name: average_amount
+    type: average
+    expr: amount
+"#]);
+    let item: crate::types::QueryRequestItem = serde_json::from_value(serde_json::json!({
+        "measures": ["orders_view.revenue", "orders_view.average_amount"],
+        "time_dimensions": [{
+            "dimension": "orders_view.order_date",
+            "granularity": "month",
+            "date_range": null,
+        }],
+        "limit": null,
+        "time_spine": true,
+    }))
+    .unwrap();
+    let row = |date: &str, revenue: f64| {
+        QueryRow(vec![
+            CellValue::Text(date.to_string()),
+            CellValue::Number(revenue),
+            CellValue::Number(revenue / 2.0),
+        ])
+    };
+    let mut result = QueryResult {
+        columns: vec![
+            "orders_view__order_date_month".to_string(),
+            "orders_view__revenue".to_string(),
+            "orders_view__average_amount".to_string(),
+        ],
+        rows: vec![
+            row("2024-01-01 00:00:00", 5.0),
+            row("2024-04-01 00:00:00", 7.0),
+        ],
+        total_row_count: 2,
+        truncated: false,
+    };
+
+    super::time_spine::fill_time_spine(&mut result, &item, &catalog);
+    assert_eq!(result.total_row_count, 4);
+    let cells: Vec<&CellValue> = result.rows.iter().map(|r| &r.0[0]).collect();
+    assert_eq!(
+        cells[1],
+        &CellValue::Text("2024-02-01 00:00:00".to_string())
+    );
+    assert_eq!(result.rows[2].0[1], CellValue::Number(0.0));
+    assert_eq!(result.rows[2].0[2], CellValue::Null);
+    assert_eq!(result.rows[3].0[1], CellValue::Number(7.0));
+}
+
+#[test]
+fn time_series_specs_get_a_time_spine() {
+    use crate::types::ResultShape;
+
+    let item: crate::types::QueryRequestItem = serde_json::from_value(serde_json::json!({
+        "measures": ["orders_view.revenue"],
+        "limit": null,
+    }))
+    .unwrap();
+    let table = ResultShape::Table { columns: vec![] };
+    assert!(!super::time_spine::for_result_shape(item.clone(), &table).time_spine);
+    assert!(super::time_spine::for_result_shape(item, &ResultShape::TimeSeries).time_spine);
+}
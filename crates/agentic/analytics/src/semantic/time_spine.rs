@@ -0,0 +1,203 @@
+//! Filling of the time buckets missing from semantic trend results.
+//!
+//! A [`QueryRequestItem`] with `time_spine` set and exactly one time
+//! dimension at a calendar granularity gets a row for every bucket between
+//! the first and last one returned, for each combination of its other
+//! dimensions — the same time spine oxy semantic query tasks apply.  Items
+//! whose spec expects a [`ResultShape::TimeSeries`] get `time_spine` set by
+//! [`for_result_shape`].
+//!
+//! Additive measures (`count`, `count_distinct`, `sum`) are zero in the added
+//! rows; any other measure is NULL, since an empty period has no average,
+//! minimum or maximum.
+//!
+//! Columns are found by airlayer's output names: `<view>__<member>`, with
+//! `_<granularity>` appended for time dimensions.
+
+use std::collections::{HashMap, HashSet};
+
+use agentic_core::result::{CellValue, QueryResult, QueryRow};
+use airlayer::schema::models::MeasureType;
+use chrono::NaiveDate;
+use oxy_semantic::TimeGrain;
+
+use super::SemanticCatalog;
+use crate::types::{QueryRequestItem, ResultShape};
+
+/// Largest result the time spine fills; bigger ones are left as they are.
+const MAX_FILLED_ROWS: usize = 100_000;
+
+/// `item`, with `time_spine` set when its spec expects a time series.
+pub fn for_result_shape(mut item: QueryRequestItem, shape: &ResultShape) -> QueryRequestItem {
+    item.time_spine |= *shape == ResultShape::TimeSeries;
+    item
+}
+
+/// Fill the buckets missing from `result`, compiled from `item`.  Measure
+/// types are looked up in `catalog`.
+///
+/// Results that are truncated, whose columns cannot be matched to `item`,
+/// or whose buckets are not aligned to the granularity are left unchanged.
+pub fn fill_time_spine(
+    result: &mut QueryResult,
+    item: &QueryRequestItem,
+    catalog: &SemanticCatalog,
+) {
+    if !item.time_spine || result.truncated || result.rows.is_empty() {
+        return;
+    }
+    let buckets = item.time_buckets();
+    let [(dimension, granularity)] = buckets.as_slice() else {
+        tracing::debug!(
+            "time spine skipped: the query groups by {} time buckets",
+            buckets.len()
+        );
+        return;
+    };
+    let Ok(grain) = granularity.parse::<TimeGrain>() else {
+        tracing::debug!("time spine skipped: unsupported granularity '{granularity}'");
+        return;
+    };
+
+    let column = |member: &str| {
+        let alias = member.replace('.', "__");
+        result.columns.iter().position(|c| *c == alias)
+    };
+    let Some(time_index) = column(&format!("{dimension}_{granularity}")) else {
+        return;
+    };
+    // Column of each measure and the value it takes in filled rows
+    let Some(measure_fills) = item
+        .measures
+        .iter()
+        .map(|m| Some((column(m)?, empty_period_value(catalog, m))))
+        .collect::<Option<Vec<(usize, CellValue)>>>()
+    else {
+        return;
+    };
+    let measure_indices: HashSet<usize> = measure_fills.iter().map(|(i, _)| *i).collect();
+
+    // Bucket of each row, and the text after the date in the bucket values
+    // (e.g. a time of day) so filled buckets are formatted the same way
+    let dates: Vec<Option<NaiveDate>> = result
+        .rows
+        .iter()
+        .map(|row| match row.0.get(time_index) {
+            Some(CellValue::Text(s)) => NaiveDate::parse_from_str(s.get(..10)?, "%Y-%m-%d").ok(),
+            _ => None,
+        })
+        .collect();
+    let suffix = result
+        .rows
+        .iter()
+        .find_map(|row| match row.0.get(time_index) {
+            Some(CellValue::Text(s)) if s.len() > 10 => s.get(10..).map(str::to_string),
+            _ => None,
+        })
+        .unwrap_or_default();
+    let (Some(&first), Some(&last)) = (dates.iter().flatten().min(), dates.iter().flatten().max())
+    else {
+        return;
+    };
+
+    // The first row of each group of the other columns, and each row's group
+    let mut groups: Vec<usize> = Vec::new();
+    let mut keys: HashMap<String, usize> = HashMap::new();
+    let group_of: Vec<usize> = result
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let key = row
+                .0
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != time_index && !measure_indices.contains(i))
+                .map(|(_, cell)| format!("{cell:?}"))
+                .collect::<Vec<_>>()
+                .join("\u{1f}");
+            *keys.entry(key).or_insert_with(|| {
+                groups.push(index);
+                groups.len() - 1
+            })
+        })
+        .collect();
+
+    let mut spine = Vec::new();
+    let mut period = Some(first);
+    while let Some(start) = period.filter(|start| *start <= last) {
+        spine.push(start);
+        if spine.len().saturating_mul(groups.len()) > MAX_FILLED_ROWS {
+            tracing::debug!("time spine skipped: more than {MAX_FILLED_ROWS} rows");
+            return;
+        }
+        period = grain.next_period(start);
+    }
+    let spine_dates: HashSet<NaiveDate> = spine.iter().copied().collect();
+    if dates
+        .iter()
+        .flatten()
+        .any(|date| !spine_dates.contains(date))
+    {
+        tracing::debug!("time spine skipped: buckets are not aligned to {granularity}");
+        return;
+    }
+    let descending = item
+        .order
+        .iter()
+        .any(|o| o.desc && (o.id == *dimension || o.id == format!("{dimension}.{granularity}")));
+    if descending {
+        spine.reverse();
+    }
+
+    let mut rows_by_bucket: HashMap<(usize, NaiveDate), Vec<usize>> = HashMap::new();
+    for (index, date) in dates.iter().enumerate() {
+        if let Some(date) = date {
+            rows_by_bucket
+                .entry((group_of[index], *date))
+                .or_default()
+                .push(index);
+        }
+    }
+    let mut rows = Vec::with_capacity(spine.len() * groups.len());
+    for bucket in &spine {
+        for (group, &first_row) in groups.iter().enumerate() {
+            match rows_by_bucket.get(&(group, *bucket)) {
+                Some(existing) => rows.extend(existing.iter().map(|&i| result.rows[i].clone())),
+                None => {
+                    let mut row = result.rows[first_row].0.clone();
+                    row[time_index] = CellValue::Text(format!("{bucket}{suffix}"));
+                    for (index, value) in &measure_fills {
+                        row[*index] = value.clone();
+                    }
+                    rows.push(QueryRow(row));
+                }
+            }
+        }
+    }
+    // Rows without a bucket stay at the end
+    for (index, date) in dates.iter().enumerate() {
+        if date.is_none() {
+            rows.push(result.rows[index].clone());
+        }
+    }
+
+    result.total_row_count = rows.len() as u64;
+    result.rows = rows;
+}
+
+/// Value of `measure` over a period without rows: zero when it adds up,
+/// NULL otherwise or when the catalog does not know the measure.
+fn empty_period_value(catalog: &SemanticCatalog, measure: &str) -> CellValue {
+    // Windowed measures are listed with the type of the measure they are
+    // computed from, but depend on the neighbouring periods.
+    if catalog.find_windowed(measure).is_some() {
+        return CellValue::Null;
+    }
+    match catalog.find_measure(measure).map(|(_, m)| &m.measure_type) {
+        Some(MeasureType::Count | MeasureType::CountDistinct | MeasureType::Sum) => {
+            CellValue::Number(0.0)
+        }
+        _ => CellValue::Null,
+    }
+}
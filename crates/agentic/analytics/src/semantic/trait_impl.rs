@@ -258,14 +258,10 @@ impl Catalog for SemanticCatalog {
             motif_params: Default::default(),
         };
 
-        let result = self.engine.compile_query(&request).map_err(|e| {
+        self.compile_query(&request).map_err(|e| {
             tracing::debug!(error = %e, "airlayer compile_query failed");
             CatalogError::TooComplex(format!("airlayer compile error: {e}"))
-        })?;
-
-        let sql = crate::airlayer_compat::substitute_params(&result.sql, &result.params);
-
-        Ok(sql)
+        })
     }
 
     fn get_context(&self, intent: &AnalyticsIntent) -> QueryContext {
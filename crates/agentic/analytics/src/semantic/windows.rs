@@ -0,0 +1,307 @@
+//! Windowed measures (`cumulative`, `window`, `period_over_period`).
+//!
+//! airlayer has no notion of measures computed over grouped rows, so the
+//! catalog lists a windowed measure as a copy of the measure it is computed
+//! from.  [`SemanticCatalog::compile_query`] asks airlayer for that measure
+//! instead, grouped by the window's time dimension, and wraps the compiled
+//! SQL in a select that applies the window functions from
+//! [`oxy_semantic::metrics`].
+//!
+//! The wrapper refers to airlayer's output column names: `<view>__<field>`,
+//! with `_<granularity>` appended for time dimensions grouped by a grain.
+
+use airlayer::engine::EngineError;
+use airlayer::engine::query::{QueryRequest, TimeDimensionQuery};
+use oxy_semantic::Measure;
+use oxy_semantic::metrics::window_sql;
+
+use super::SemanticCatalog;
+use crate::airlayer_compat::substitute_params;
+
+impl SemanticCatalog {
+    /// Compile `request` into SQL with its parameters substituted.
+    ///
+    /// Use this rather than `engine().compile_query`, which returns windowed
+    /// measures as the measure they are computed from.
+    pub fn compile_query(&self, request: &QueryRequest) -> Result<String, EngineError> {
+        let windowed = WindowedQuery::plan(self, request)?;
+        let compiled = self
+            .engine
+            .compile_query(windowed.as_ref().map_or(request, |w| &w.request))?;
+        let sql = substitute_params(&compiled.sql, &compiled.params);
+        match windowed {
+            Some(windowed) => windowed.wrap(&sql),
+            None => Ok(sql),
+        }
+    }
+
+    /// Find the windowed measure `member` and the name of its view.
+    ///
+    /// Accepts both bare and view-qualified names, like `find_measure`.
+    pub(super) fn find_windowed(&self, member: &str) -> Option<(&str, &Measure)> {
+        self.windowed
+            .iter()
+            .find(|(view, m)| m.name == member || format!("{view}.{}", m.name) == member)
+            .map(|(view, m)| (view.as_str(), m))
+    }
+}
+
+/// A request with windowed measures, split into the request airlayer
+/// compiles and the select applied over its result.
+struct WindowedQuery {
+    /// Request for airlayer: windowed measures replaced by the measures they
+    /// are computed from, without ordering or paging
+    request: QueryRequest,
+    columns: Vec<OutputColumn>,
+    order: Vec<(String, bool)>,
+    limit: Option<u64>,
+    offset: Option<u64>,
+}
+
+enum OutputColumn {
+    Field(String),
+    Windowed {
+        alias: String,
+        measure: Measure,
+        value: String,
+        order_by: String,
+        partition_by: Vec<String>,
+    },
+}
+
+impl OutputColumn {
+    fn alias(&self) -> &str {
+        match self {
+            Self::Field(alias) | Self::Windowed { alias, .. } => alias,
+        }
+    }
+}
+
+impl WindowedQuery {
+    /// Split `request` when it asks for windowed measures of `catalog`.
+    ///
+    /// Returns `None` when it asks for none, so the request can be compiled
+    /// as it is.
+    fn plan(
+        catalog: &SemanticCatalog,
+        request: &QueryRequest,
+    ) -> Result<Option<Self>, EngineError> {
+        let requested: Vec<(&String, Option<(&str, &Measure)>)> = request
+            .measures
+            .iter()
+            .map(|member| (member, catalog.find_windowed(member)))
+            .collect();
+        if requested.iter().all(|(_, windowed)| windowed.is_none()) {
+            return Ok(None);
+        }
+
+        let mut query = request.clone();
+        query.measures = Vec::new();
+        query.order = Vec::new();
+        query.limit = None;
+        query.offset = None;
+
+        // Underlying measures, and the time column each windowed measure
+        // steps through
+        let mut windows = Vec::new();
+        for (member, windowed) in &requested {
+            let Some((view, measure)) = windowed else {
+                push_unique(&mut query.measures, member.to_string());
+                continue;
+            };
+            let composition = &measure.composition;
+            let (Some(base), Some(time_dimension)) =
+                (&composition.measure, &composition.time_dimension)
+            else {
+                return Err(EngineError::QueryError(format!(
+                    "Measure '{member}' requires 'measure' and 'time_dimension' fields"
+                )));
+            };
+            let value = format!("{view}.{base}");
+            push_unique(&mut query.measures, value.clone());
+            let dimension = format!("{view}.{time_dimension}");
+            let order_by = time_column(&mut query, member, measure, &dimension)?;
+            windows.push((member.as_str(), *measure, value, order_by));
+        }
+
+        let groups: Vec<String> = query
+            .dimensions
+            .iter()
+            .map(|member| column_alias(member))
+            .chain(
+                query
+                    .time_dimensions
+                    .iter()
+                    .filter(|td| td.granularity.is_some())
+                    .map(time_alias),
+            )
+            .collect();
+        let mut columns: Vec<OutputColumn> =
+            groups.iter().cloned().map(OutputColumn::Field).collect();
+        for (member, _) in &requested {
+            match windows
+                .iter()
+                .find(|(windowed, ..)| *windowed == member.as_str())
+            {
+                Some((_, measure, value, order_by)) => columns.push(OutputColumn::Windowed {
+                    alias: column_alias(member),
+                    measure: (*measure).clone(),
+                    value: column_alias(value),
+                    order_by: order_by.clone(),
+                    partition_by: groups.iter().filter(|g| *g != order_by).cloned().collect(),
+                }),
+                None => columns.push(OutputColumn::Field(column_alias(member))),
+            }
+        }
+
+        let order = request
+            .order
+            .iter()
+            .map(|order| {
+                let alias = query
+                    .time_dimensions
+                    .iter()
+                    .find(|td| td.dimension == order.id && td.granularity.is_some())
+                    .map(time_alias)
+                    .unwrap_or_else(|| column_alias(&order.id));
+                (alias, order.desc)
+            })
+            .collect();
+
+        Ok(Some(Self {
+            request: query,
+            columns,
+            order,
+            limit: request.limit,
+            offset: request.offset,
+        }))
+    }
+
+    /// Wrap `sql`, compiled from [`Self::request`], in the select that
+    /// computes the windowed measures and applies the original ordering and
+    /// paging.
+    fn wrap(&self, sql: &str) -> Result<String, EngineError> {
+        let quote = |alias: &str| quote_like(sql, alias);
+        let mut select = Vec::with_capacity(self.columns.len());
+        for column in &self.columns {
+            match column {
+                OutputColumn::Field(alias) => select.push(quote(alias)),
+                OutputColumn::Windowed {
+                    alias,
+                    measure,
+                    value,
+                    order_by,
+                    partition_by,
+                } => {
+                    let partition_by: Vec<String> =
+                        partition_by.iter().map(|alias| quote(alias)).collect();
+                    let expr = window_sql(measure, &quote(value), &quote(order_by), &partition_by)
+                        .map_err(|e| EngineError::QueryError(e.to_string()))?;
+                    select.push(format!("{expr} AS {}", quote(alias)));
+                }
+            }
+        }
+
+        let inner = sql.trim().trim_end_matches(';');
+        let mut wrapped = format!(
+            "SELECT {} FROM ({inner}) AS windowed_base",
+            select.join(", ")
+        );
+        let order_by: Vec<String> = self
+            .order
+            .iter()
+            .map(|(alias, desc)| format!("{} {}", quote(alias), if *desc { "DESC" } else { "ASC" }))
+            .collect();
+        // SQL Server quotes with brackets and pages with OFFSET … FETCH.
+        let tsql = self
+            .columns
+            .iter()
+            .any(|column| quote(column.alias()).starts_with('['));
+        let paged = self.limit.is_some() || self.offset.is_some();
+        if !order_by.is_empty() {
+            wrapped.push_str(&format!(" ORDER BY {}", order_by.join(", ")));
+        } else if paged && tsql {
+            wrapped.push_str(" ORDER BY (SELECT NULL)");
+        }
+        if tsql {
+            if paged {
+                wrapped.push_str(&format!(" OFFSET {} ROWS", self.offset.unwrap_or(0)));
+            }
+            if let Some(limit) = self.limit {
+                wrapped.push_str(&format!(" FETCH NEXT {limit} ROWS ONLY"));
+            }
+        } else {
+            if let Some(limit) = self.limit {
+                wrapped.push_str(&format!(" LIMIT {limit}"));
+            }
+            if let Some(offset) = self.offset {
+                wrapped.push_str(&format!(" OFFSET {offset}"));
+            }
+        }
+        Ok(wrapped)
+    }
+}
+
+/// Find the output column of `measure`'s time `dimension`, grouping the
+/// request by it at the measure's grain when it is not grouped by it already.
+fn time_column(
+    query: &mut QueryRequest,
+    member: &str,
+    measure: &Measure,
+    dimension: &str,
+) -> Result<String, EngineError> {
+    if query.dimensions.iter().any(|d| d == dimension) {
+        return Ok(column_alias(dimension));
+    }
+    if let Some(td) = query
+        .time_dimensions
+        .iter()
+        .find(|td| td.dimension == dimension && td.granularity.is_some())
+    {
+        return Ok(time_alias(td));
+    }
+    let grain = measure
+        .composition
+        .grain
+        .filter(|grain| !grain.is_fiscal())
+        .ok_or_else(|| {
+            EngineError::QueryError(format!(
+                "Measure '{member}' needs the query to group by '{dimension}', or a calendar 'grain' in its definition"
+            ))
+        })?;
+    let td = TimeDimensionQuery {
+        dimension: dimension.to_string(),
+        granularity: Some(grain.to_string()),
+        date_range: None,
+    };
+    let alias = time_alias(&td);
+    query.time_dimensions.push(td);
+    Ok(alias)
+}
+
+fn push_unique(members: &mut Vec<String>, member: String) {
+    if !members.contains(&member) {
+        members.push(member);
+    }
+}
+
+fn column_alias(member: &str) -> String {
+    member.replace('.', "__")
+}
+
+fn time_alias(td: &TimeDimensionQuery) -> String {
+    match &td.granularity {
+        Some(granularity) => format!("{}_{granularity}", column_alias(&td.dimension)),
+        None => column_alias(&td.dimension),
+    }
+}
+
+/// Quote `alias` the way airlayer quoted it in `sql`, which depends on the
+/// dialect of the view.
+fn quote_like(sql: &str, alias: &str) -> String {
+    [('"', '"'), ('`', '`'), ('[', ']')]
+        .iter()
+        .map(|(open, close)| format!("{open}{alias}{close}"))
+        .find(|quoted| sql.contains(quoted.as_str()))
+        .unwrap_or_else(|| alias.to_string())
+}
@@ -317,11 +317,8 @@ impl AnalyticsSolver {
             .await;
 
             let query_request = semantic_query.to_query_request();
-            match self.catalog.engine().compile_query(&query_request) {
-                Ok(result) => {
-                    let sql =
-                        crate::airlayer_compat::substitute_params(&result.sql, &result.params);
-
+            match self.catalog.compile_query(&query_request) {
+                Ok(sql) => {
                     emit_domain(
                         &self.event_tx,
                         AnalyticsEvent::SemanticShortcutResolved { sql: sql.clone() },
@@ -19,6 +19,7 @@ use crate::engine::EngineError;
 use crate::events::{AnalyticsEvent, QuerySource};
 #[cfg(test)]
 use crate::procedure::ProcedureOutput;
+use crate::semantic::time_spine::fill_time_spine;
 use crate::types::{SolutionPayload, SolutionSource};
 
 use crate::{AnalyticsDomain, AnalyticsError, AnalyticsResult, AnalyticsSolution};
@@ -167,7 +168,10 @@ impl AnalyticsSolver {
                     .instrument(tool_span.clone())
                     .await;
                 match exec_result {
-                    Ok(exec) => {
+                    Ok(mut exec) => {
+                        if let Some(item) = &solution.semantic_query {
+                            fill_time_spine(&mut exec.result, item, &self.catalog);
+                        }
                         let duration_ms = start.elapsed().as_millis() as u64;
                         let columns = exec.result.columns.clone();
                         let rows: Vec<Vec<serde_json::Value>> = exec
@@ -20,6 +20,7 @@ use crate::llm::{InitialMessages, LlmClient, ThinkingConfig, ToolLoopConfig};
 use crate::metric_sink::SharedMetricSink;
 use crate::schemas::solve_response_schema;
 use crate::semantic::SemanticCatalog;
+use crate::semantic::time_spine::fill_time_spine;
 use crate::solver::executing::execution_type_for;
 use crate::tools::execute_solving_tool;
 use crate::types::{SolutionPayload, SolutionSource};
@@ -483,7 +484,10 @@ impl AnalyticsFanoutWorker {
             .instrument(tool_span.clone())
             .await;
         match exec_result {
-            Ok(exec) => {
+            Ok(mut exec) => {
+                if let Some(item) = &solution.semantic_query {
+                    fill_time_spine(&mut exec.result, item, &self.catalog);
+                }
                 let duration_ms = start.elapsed().as_millis() as u64;
                 let columns = exec.result.columns.clone();
                 let rows: Vec<Vec<serde_json::Value>> = exec
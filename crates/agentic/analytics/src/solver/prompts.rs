@@ -538,6 +538,7 @@ Each spec object has:
   - date_range: [start, end] date strings or null if no date constraint.
 - order: array of order objects (id: view.member, desc: boolean). Empty array for default.
 - limit: integer row limit or null for no limit.
+- time_spine: true to fill the time buckets with no data, false otherwise.
 - assumptions: any ambiguous resolutions for the user to review.
 
 Joins are resolved automatically from the semantic model — do NOT specify tables or join paths.
@@ -582,6 +583,7 @@ dimensions array. Rules by question type:
   Set date_range to the user\u{2019}s time constraint resolved to absolute ISO-8601 dates. \
   Call sample_columns on the date dimension — use date_distinct_count to pick granularity: \
   >365 \u{2192} \"month\", >90 \u{2192} \"week\", otherwise \u{2192} \"day\".
+  Set time_spine: true so periods with no data appear instead of being skipped.
 - Breakdown / Comparison with a date filter: add the date column to time_dimensions with \
   granularity: null and the appropriate date_range. This applies the filter without grouping.
 - SingleValue: add the date column to time_dimensions with granularity: null and date_range \
@@ -623,6 +625,7 @@ Semantic catalog: orders view with measures=[revenue, count], dimensions=[status
     }],
     \"order\": [{\"id\": \"orders.order_date\", \"desc\": false}],
     \"limit\": null,
+    \"time_spine\": true,
     \"assumptions\": [\"Using weekly granularity for 3-month range\"]
   }]
 }
@@ -644,6 +647,7 @@ Semantic catalog: orders view with measures=[revenue], dimensions=[status, regio
     \"time_dimensions\": [],
     \"order\": [{\"id\": \"orders.revenue\", \"desc\": true}],
     \"limit\": null,
+    \"time_spine\": false,
     \"assumptions\": []
   }]
 }
@@ -667,6 +671,7 @@ Note: granularity is null because we only need a date-range filter, not time-bas
     }],
     \"order\": [],
     \"limit\": null,
+    \"time_spine\": false,
     \"assumptions\": [\"'this week' resolved to Monday-Sunday of current week\"]
   }]
 }
@@ -381,6 +381,32 @@ impl AnalyticsSolver {
                     BackTarget::Solve(spec, new_ctx),
                 ));
             }
+            Err(crate::llm::LlmError::BudgetExceeded(msg)) => {
+                // Retrying would only be refused again; stop the run.
+                let summary = format!("Budget exceeded: {msg}");
+                tracing::error!("analytics solving stopped: {summary}");
+                self.store_suspension_data(SuspendedRunData {
+                    from_state: "solving".to_string(),
+                    original_input: spec.intent.raw_question.clone(),
+                    trace_id: String::new(),
+                    stage_data: Default::default(),
+                    question: summary.clone(),
+                    suggestions: vec![],
+                });
+                return Err((
+                    AnalyticsError::NeedsUserInput {
+                        prompt: summary.clone(),
+                    },
+                    BackTarget::Suspend {
+                        reason: SuspendReason::HumanInput {
+                            questions: vec![HumanInputQuestion {
+                                prompt: summary,
+                                suggestions: vec![],
+                            }],
+                        },
+                    },
+                ));
+            }
             Err(e) => {
                 let msg = format!("LLM call failed during solve: {e}");
                 return Err((
@@ -462,12 +488,8 @@ pub(super) fn build_solving_handler()
                     // context so solve_impl can generate SQL via LLM.
                     if spec.query_request.is_some() && spec.precomputed.is_none() {
                         let qr = spec.query_request.as_ref().unwrap();
-                        match solver.catalog.engine().compile_query(qr) {
-                            Ok(result) => {
-                                let sql = crate::airlayer_compat::substitute_params(
-                                    &result.sql,
-                                    &result.params,
-                                );
+                        match solver.catalog.compile_query(qr) {
+                            Ok(sql) => {
                                 tracing::info!(
                                     "[solving] re-compile SUCCESS: {}",
                                     &sql[..sql.len().min(200)]
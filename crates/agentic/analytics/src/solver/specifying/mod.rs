@@ -23,6 +23,7 @@ use crate::events::AnalyticsEvent;
 use crate::llm::{LlmOutput, ThinkingConfig, ToolLoopConfig};
 use crate::schemas::{specify_response_schema, specify_response_schema_legacy};
 use crate::semantic::SemanticCatalog;
+use crate::semantic::time_spine::for_result_shape;
 use crate::tools::{
     execute_clarifying_tool, execute_database_lookup_tool, execute_specifying_tool,
 };
@@ -320,6 +321,7 @@ impl AnalyticsSolver {
                     time_dimensions: vec![],
                     order: vec![],
                     limit: None,
+                    time_spine: false,
                     assumptions: vec![],
                 }],
             });
@@ -675,7 +677,7 @@ impl AnalyticsSolver {
     /// Primary semantic-layer path: LLM → QueryRequest → airlayer compile.
     ///
     /// 1. LLM produces a structured `QueryRequest` (view.member references)
-    /// 2. Try `engine.compile_query` on each request item
+    /// 2. Try `catalog.compile_query` on each request item
     /// 3. On success: precomputed SQL, Solving skips to Executing
     /// 4. On retryable error: retry Specify with error hint
     /// 5. On non-retryable error: forward to Solving with QueryRequest +
@@ -719,10 +721,8 @@ impl AnalyticsSolver {
             )
             .await;
             let compile_start = std::time::Instant::now();
-            match self.catalog.engine().compile_query(&query_request) {
-                Ok(result) => {
-                    let sql =
-                        crate::airlayer_compat::substitute_params(&result.sql, &result.params);
+            match self.catalog.compile_query(&query_request) {
+                Ok(sql) => {
                     let compile_duration_ms = compile_start.elapsed().as_millis() as u64;
                     emit_core(
                         &self.event_tx,
@@ -755,22 +755,24 @@ impl AnalyticsSolver {
                         &self.default_connector,
                     );
 
+                    let expected_result_shape =
+                        infer_result_shape(&intent.dimensions, &item.measures);
                     specs.push(QuerySpec {
                         intent: intent.clone(),
                         resolved_metrics: query_request.measures.clone(),
                         resolved_filters: translation.resolved_filters,
                         resolved_tables: translation.resolved_tables,
                         join_path: translation.join_path,
-                        expected_result_shape: infer_result_shape(
-                            &intent.dimensions,
-                            &item.measures,
-                        ),
+                        expected_result_shape: expected_result_shape.clone(),
                         assumptions: item.assumptions.clone(),
                         solution_source: SolutionSource::SemanticLayer,
                         precomputed: Some(SolutionPayload::Sql(sql)),
                         context: None,
                         connector_name,
-                        query_request_item: Some(item.clone()),
+                        query_request_item: Some(for_result_shape(
+                            item.clone(),
+                            &expected_result_shape,
+                        )),
                         query_request: Some(query_request),
                         compile_error: None,
                     });
@@ -870,22 +872,24 @@ impl AnalyticsSolver {
                     let connector_name =
                         resolve_connector(&[], &self.catalog, &self.default_connector);
 
+                    let expected_result_shape =
+                        infer_result_shape(&intent.dimensions, &item.measures);
                     specs.push(QuerySpec {
                         intent: intent.clone(),
                         resolved_metrics: vec![],
                         resolved_filters: vec![],
                         resolved_tables: vec![],
                         join_path: vec![],
-                        expected_result_shape: infer_result_shape(
-                            &intent.dimensions,
-                            &item.measures,
-                        ),
+                        expected_result_shape: expected_result_shape.clone(),
                         assumptions: item.assumptions.clone(),
                         solution_source: SolutionSource::LlmWithSemanticContext,
                         precomputed: None,
                         context: None,
                         connector_name,
-                        query_request_item: Some(item.clone()),
+                        query_request_item: Some(for_result_shape(
+                            item.clone(),
+                            &expected_result_shape,
+                        )),
                         query_request: Some(query_request),
                         compile_error: Some(e.to_string()),
                     });
@@ -1019,10 +1023,8 @@ impl AnalyticsSolver {
         // view.member paths.
         if let Some(ref qr) = spec.query_request {
             // Try re-compile once more (may succeed now).
-            match self.catalog.engine().compile_query(qr) {
-                Ok(result) => {
-                    let sql =
-                        crate::airlayer_compat::substitute_params(&result.sql, &result.params);
+            match self.catalog.compile_query(qr) {
+                Ok(sql) => {
                     tracing::info!(
                         "[spec_to_executing] re-compile SUCCESS: {}",
                         &sql[..sql.len().min(200)]
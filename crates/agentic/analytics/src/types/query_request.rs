@@ -45,6 +45,9 @@ pub struct QueryRequestItem {
     pub order: Vec<OrderItem>,
     /// Row limit (null for no limit).
     pub limit: Option<u64>,
+    /// Fill the missing buckets of the time dimension the query is grouped by.
+    #[serde(default)]
+    pub time_spine: bool,
     /// Assumptions made during resolution.
     #[serde(default)]
     pub assumptions: Vec<String>,
@@ -83,8 +86,41 @@ pub struct OrderItem {
     pub desc: bool,
 }
 
+/// Calendar grains a dimension can be requested at as `view.dimension.grain`.
+const GRAINS: &[&str] = &["day", "week", "month", "quarter", "year"];
+
+/// Split a `view.dimension.grain` member into `view.dimension` and the grain.
+fn split_grain(member: &str) -> Option<(&str, &str)> {
+    let (dimension, grain) = member.rsplit_once('.')?;
+    (dimension.contains('.') && GRAINS.contains(&grain)).then_some((dimension, grain))
+}
+
 impl QueryRequestItem {
+    /// Time dimensions the query is grouped by, with their granularity:
+    /// `time_dimensions` with a granularity and `view.dimension.grain`
+    /// members of `dimensions`.
+    pub fn time_buckets(&self) -> Vec<(String, String)> {
+        let mut buckets: Vec<(String, String)> = Vec::new();
+        let grained = self.dimensions.iter().filter_map(|d| split_grain(d));
+        let timed = self
+            .time_dimensions
+            .iter()
+            .filter_map(|td| Some((td.dimension.as_str(), td.granularity.as_deref()?)));
+        for (dimension, granularity) in grained.chain(timed) {
+            if !buckets
+                .iter()
+                .any(|(d, g)| d == dimension && g == granularity)
+            {
+                buckets.push((dimension.to_string(), granularity.to_string()));
+            }
+        }
+        buckets
+    }
+
     /// Convert to an airlayer `QueryRequest` for compilation.
+    ///
+    /// `view.dimension.grain` members of `dimensions` become time dimensions
+    /// at that granularity.
     pub fn to_query_request(&self) -> airlayer::engine::query::QueryRequest {
         use airlayer::engine::query::{OrderBy, QueryFilter, QueryRequest, TimeDimensionQuery};
 
@@ -100,7 +136,7 @@ impl QueryRequestItem {
             })
             .collect();
 
-        let time_dimensions = self
+        let mut time_dimensions: Vec<TimeDimensionQuery> = self
             .time_dimensions
             .iter()
             .map(|td| TimeDimensionQuery {
@@ -109,19 +145,37 @@ impl QueryRequestItem {
                 date_range: td.date_range.clone(),
             })
             .collect();
+        let mut dimensions = Vec::new();
+        for member in &self.dimensions {
+            match split_grain(member) {
+                Some((dimension, grain))
+                    if !time_dimensions.iter().any(|td| {
+                        td.dimension == dimension && td.granularity.as_deref() == Some(grain)
+                    }) =>
+                {
+                    time_dimensions.push(TimeDimensionQuery {
+                        dimension: dimension.to_string(),
+                        granularity: Some(grain.to_string()),
+                        date_range: None,
+                    });
+                }
+                Some(_) => {}
+                None => dimensions.push(member.clone()),
+            }
+        }
 
         let order = self
             .order
             .iter()
             .map(|o| OrderBy {
-                id: o.id.clone(),
+                id: split_grain(&o.id).map_or_else(|| o.id.clone(), |(d, _)| d.to_string()),
                 desc: o.desc,
             })
             .collect();
 
         QueryRequest {
             measures: self.measures.clone(),
-            dimensions: self.dimensions.clone(),
+            dimensions,
             filters,
             segments: vec![],
             time_dimensions,
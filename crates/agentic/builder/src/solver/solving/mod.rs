@@ -539,7 +539,12 @@ impl BuilderSolver {
                     Err((BuilderError::Llm(msg), BackTarget::Solve(spec, retry_ctx)))
                 }
             }
-            Err(ref err @ (LlmError::Http(_) | LlmError::Auth(_))) => {
+            Err(
+                ref err @ (LlmError::Http(_)
+                | LlmError::Request(_)
+                | LlmError::Auth(_)
+                | LlmError::BudgetExceeded(_)),
+            ) => {
                 // Non-transient errors — retrying won't help.  Surface the
                 // error to the user via Suspend so the pipeline stops.
                 let msg = err.to_string();
@@ -55,6 +55,22 @@ pub struct MysqlConfig {
     pub database: String,
 }
 
+// ── SQL Server / Azure SQL ────────────────────────────────────────────────────
+
+/// Already-resolved connection parameters for a SQL Server / Azure SQL database.
+#[derive(Debug, Clone)]
+pub struct MssqlConfig {
+    pub host: String,
+    pub port: u16,
+    pub user: String,
+    pub password: String,
+    pub database: String,
+    /// Require TLS for the whole session. Azure SQL rejects unencrypted logins.
+    pub encrypt: bool,
+    /// Skip server certificate validation (self-signed dev servers).
+    pub trust_server_certificate: bool,
+}
+
 // ── ClickHouse ────────────────────────────────────────────────────────────────
 
 /// Already-resolved connection parameters for a ClickHouse HTTP endpoint.
@@ -67,6 +83,25 @@ pub struct ClickHouseConfig {
     pub database: String,
 }
 
+// ── Trino ─────────────────────────────────────────────────────────────────────
+
+/// Already-resolved connection parameters for a Trino / Presto coordinator.
+#[derive(Debug, Clone)]
+pub struct TrinoConfig {
+    /// Coordinator base URL, e.g. `http://localhost:8080`.
+    pub url: String,
+    pub user: String,
+    /// Empty for unauthenticated clusters. Trino only accepts passwords
+    /// over HTTPS.
+    pub password: String,
+    /// Catalog for unqualified table references.
+    pub catalog: String,
+    /// Schema for unqualified table references.
+    pub schema: Option<String>,
+    /// Catalogs to introspect. Empty means `catalog` alone.
+    pub catalogs: Vec<String>,
+}
+
 // ── Snowflake ─────────────────────────────────────────────────────────────────
 
 /// Callback invoked with the browser SSO URL during Snowflake external-browser
@@ -177,6 +212,19 @@ pub struct DuckDbUrlConfig {
     pub url: String,
 }
 
+// ── SQLite ───────────────────────────────────────────────────────────────────
+
+/// Configuration for an in-process SQLite connector over one or more database
+/// files.
+#[derive(Debug, Clone)]
+pub struct SqliteConfig {
+    /// Database files. A single file is opened as `main`; several files are
+    /// each attached as a schema named after their file stem.
+    pub files: Vec<PathBuf>,
+    /// Open the files read-only and reject writes on the connection.
+    pub read_only: bool,
+}
+
 // ── Top-level enum ────────────────────────────────────────────────────────────
 
 /// Which database/warehouse to connect to and how.
@@ -190,14 +238,20 @@ pub enum ConnectorConfig {
     DuckDbRaw(DuckDbRawConfig),
     /// DuckDB opened from a connection URL (MotherDuck).
     DuckDbUrl(DuckDbUrlConfig),
+    /// SQLite database file(s), opened in-process.
+    Sqlite(SqliteConfig),
     Postgres(PostgresConfig),
     /// Redshift (Postgres-compatible wire protocol).
     Redshift(PostgresConfig),
     /// MySQL / MariaDB.
     Mysql(MysqlConfig),
+    /// Microsoft SQL Server / Azure SQL over TDS.
+    Mssql(MssqlConfig),
     /// DOMO via its REST query API.
     Domo(DomoConfig),
     ClickHouse(ClickHouseConfig),
+    /// Trino / Presto via the HTTP client protocol.
+    Trino(TrinoConfig),
     Snowflake(SnowflakeConfig),
     BigQuery(BigQueryConfig),
 }
@@ -31,6 +31,8 @@ pub enum SqlDialect {
     Postgres,
     BigQuery,
     Snowflake,
+    /// Microsoft SQL Server / Azure SQL.
+    SqlServer,
     /// Any vendor not covered by the variants above.  The inner string is a
     /// human-readable label used only in prompts.
     Other(&'static str),
@@ -45,6 +47,7 @@ impl SqlDialect {
             SqlDialect::Postgres => "PostgreSQL",
             SqlDialect::BigQuery => "BigQuery",
             SqlDialect::Snowflake => "Snowflake",
+            SqlDialect::SqlServer => "T-SQL",
             SqlDialect::Other(s) => s,
         }
     }
@@ -60,6 +63,7 @@ impl fmt::Display for SqlDialect {
 
 /// Metadata about a single column as reported by the database.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "replay", derive(serde::Serialize, serde::Deserialize))]
 pub struct SchemaColumnInfo {
     /// Column name (original case, as returned by the database).
     pub name: String,
@@ -75,6 +79,7 @@ pub struct SchemaColumnInfo {
 
 /// Metadata about a single table or view as reported by the database.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "replay", derive(serde::Serialize, serde::Deserialize))]
 pub struct SchemaTableInfo {
     /// Table or view name (original case).
     pub name: String,
@@ -86,6 +91,7 @@ pub struct SchemaTableInfo {
 /// This is a vendor-neutral representation that callers convert into their own
 /// catalog types (e.g. `SchemaCatalog::from_schema_info`).
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "replay", derive(serde::Serialize, serde::Deserialize))]
 pub struct SchemaInfo {
     pub tables: Vec<SchemaTableInfo>,
     /// Auto-detected or pre-declared join keys: `(table_a, table_b, join_column)`.
@@ -94,6 +100,7 @@ pub struct SchemaInfo {
 
 /// Per-column aggregate statistics computed by the database.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "replay", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColumnStats {
     pub name: String,
     /// Database-native type name (e.g. "INTEGER", "VARCHAR", "TIMESTAMP").
@@ -109,6 +116,7 @@ pub struct ColumnStats {
 
 /// Summary statistics for a query result, computed by the database.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "replay", derive(serde::Serialize, serde::Deserialize))]
 pub struct ResultSummary {
     pub row_count: u64,
     pub columns: Vec<ColumnStats>,
@@ -116,6 +124,7 @@ pub struct ResultSummary {
 
 /// Combined result of a connector execution: bounded rows + stats.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "replay", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExecutionResult {
     /// Bounded sample of rows.
     pub result: QueryResult,
@@ -125,6 +134,7 @@ pub struct ExecutionResult {
 
 /// Errors from connector operations.
 #[derive(Debug)]
+#[cfg_attr(feature = "replay", derive(serde::Serialize, serde::Deserialize))]
 pub enum ConnectorError {
     QueryFailed { sql: String, message: String },
     ConnectionError(String),
@@ -5,8 +5,11 @@
 //! | Feature      | Connector               | Crate                  |
 //! |--------------|-------------------------|------------------------|
 //! | `duckdb`     | [`DuckDbConnector`]     | `duckdb`               |
+//! | `sqlite`     | [`SqliteConnector`]     | `rusqlite`             |
 //! | `postgres`   | [`PostgresConnector`]   | `tokio-postgres`       |
+//! | `mssql`      | [`MssqlConnector`]      | `tiberius`             |
 //! | `clickhouse` | [`ClickHouseConnector`] | `reqwest` (HTTP API)   |
+//! | `trino`      | [`TrinoConnector`]      | `reqwest` (HTTP API)   |
 //! | `snowflake`  | [`SnowflakeConnector`]  | `snowflake-api`        |
 //! | `bigquery`   | [`BigQueryConnector`]   | `gcp-bigquery-client`  |
 
@@ -16,6 +19,14 @@ pub mod connector;
 #[cfg(feature = "duckdb")]
 pub mod duckdb;
 
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+/// Typed-row helpers for the SQLite backend: declared types and storage
+/// classes → `TypedDataType`, and value decoding.
+#[cfg(feature = "sqlite")]
+mod sqlite_typed;
+
 #[cfg(feature = "postgres")]
 pub mod postgres;
 
@@ -27,6 +38,9 @@ mod postgres_typed;
 #[cfg(feature = "mysql")]
 pub mod mysql;
 
+#[cfg(feature = "mssql")]
+pub mod mssql;
+
 #[cfg(feature = "domo")]
 pub mod domo;
 
@@ -39,6 +53,14 @@ pub mod clickhouse;
 #[cfg(feature = "clickhouse")]
 mod clickhouse_typed;
 
+#[cfg(feature = "trino")]
+pub mod trino;
+
+/// Typed-row helpers for the Trino backend's `execute_query_full`: type
+/// signatures → `TypedDataType` and JSON cell decoding.
+#[cfg(feature = "trino")]
+mod trino_typed;
+
 #[cfg(feature = "snowflake")]
 pub mod snowflake;
 
@@ -50,12 +72,15 @@ pub mod bigquery;
 #[cfg(feature = "bigquery")]
 mod bigquery_typed;
 
+#[cfg(feature = "replay")]
+pub mod replay;
+
 // ── Config re-exports ─────────────────────────────────────────────────────────
 
 pub use config::{
     BigQueryConfig, ClickHouseConfig, ConnectorConfig, DomoConfig, DuckDbConfig,
-    DuckDbLoadStrategy, DuckDbRawConfig, DuckDbUrlConfig, MysqlConfig, PostgresConfig,
-    SnowflakeAuth, SnowflakeConfig, SsoUrlCallback,
+    DuckDbLoadStrategy, DuckDbRawConfig, DuckDbUrlConfig, MssqlConfig, MysqlConfig, PostgresConfig,
+    SnowflakeAuth, SnowflakeConfig, SqliteConfig, SsoUrlCallback, TrinoConfig,
 };
 
 // ── Trait re-exports ──────────────────────────────────────────────────────────
@@ -73,31 +98,43 @@ pub use connector::{ArrowQueryStream, AsArrowConnector};
 #[cfg(feature = "duckdb")]
 pub use duckdb::{DuckDbConnection, DuckDbConnector, LoadStrategy, TableInfo, TableSource};
 
+#[cfg(feature = "sqlite")]
+pub use sqlite::{SqliteConnection, SqliteConnector};
+
 #[cfg(feature = "postgres")]
 pub use postgres::PostgresConnector;
 
 #[cfg(feature = "mysql")]
 pub use mysql::MysqlConnector;
 
+#[cfg(feature = "mssql")]
+pub use mssql::MssqlConnector;
+
 #[cfg(feature = "domo")]
 pub use domo::DomoConnector;
 
 #[cfg(feature = "clickhouse")]
 pub use clickhouse::ClickHouseConnector;
 
+#[cfg(feature = "trino")]
+pub use trino::TrinoConnector;
+
 #[cfg(feature = "snowflake")]
 pub use snowflake::SnowflakeConnector;
 
 #[cfg(feature = "bigquery")]
 pub use bigquery::BigQueryConnector;
 
+#[cfg(feature = "replay")]
+pub use replay::ReplayConnector;
+
 // ── build_connector ───────────────────────────────────────────────────────────
 
 /// Construct a `Box<dyn DatabaseConnector>` from a sync-compatible config.
 ///
-/// Only [`ConnectorConfig::DuckDb`], [`ConnectorConfig::DuckDbRaw`], and
-/// [`ConnectorConfig::DuckDbUrl`] are supported here (DuckDB opens
-/// synchronously).  For all other variants use [`build_connector_async`].
+/// Only [`ConnectorConfig::DuckDb`], [`ConnectorConfig::DuckDbRaw`],
+/// [`ConnectorConfig::DuckDbUrl`], and [`ConnectorConfig::Sqlite`] are
+/// supported here (both engines open synchronously).  For all other variants use [`build_connector_async`].
 ///
 /// Returns `Err(ConnectorError::ConnectionError)` if the requested backend is
 /// not compiled in (missing feature flag) or if the backend itself fails to
@@ -174,6 +211,22 @@ pub fn build_connector(cfg: ConnectorConfig) -> Result<Box<dyn DatabaseConnector
                 ))
             }
         }
+        ConnectorConfig::Sqlite(c) => {
+            #[cfg(feature = "sqlite")]
+            {
+                let connector = SqliteConnector::open(&c.files, c.read_only)?;
+                Ok(Box::new(connector))
+            }
+            #[cfg(not(feature = "sqlite"))]
+            {
+                let _ = c;
+                Err(ConnectorError::ConnectionError(
+                    "SQLite support is not compiled in — enable the 'sqlite' feature on \
+                     agentic-connector"
+                        .into(),
+                ))
+            }
+        }
         other => Err(ConnectorError::ConnectionError(format!(
             "use build_connector_async for {:?}",
             std::mem::discriminant(&other)
@@ -189,8 +242,9 @@ pub async fn build_connector_async(
     match cfg {
         ConnectorConfig::DuckDb(_)
         | ConnectorConfig::DuckDbRaw(_)
-        | ConnectorConfig::DuckDbUrl(_) => {
-            // DuckDB variants open synchronously — delegate to spawn_blocking
+        | ConnectorConfig::DuckDbUrl(_)
+        | ConnectorConfig::Sqlite(_) => {
+            // DuckDB and SQLite open synchronously — delegate to spawn_blocking
             // so we don't block the async runtime.
             let result: Result<Box<dyn DatabaseConnector>, ConnectorError> =
                 tokio::task::spawn_blocking(move || build_connector(cfg))
@@ -214,6 +268,12 @@ pub async fn build_connector_async(
             Ok(Box::new(conn))
         }
 
+        #[cfg(feature = "mssql")]
+        ConnectorConfig::Mssql(c) => {
+            let conn = MssqlConnector::new(&c).await?;
+            Ok(Box::new(conn))
+        }
+
         #[cfg(feature = "domo")]
         ConnectorConfig::Domo(c) => {
             let conn = DomoConnector::new(c.base_url, c.developer_token, c.dataset_id).await?;
@@ -226,6 +286,12 @@ pub async fn build_connector_async(
             Ok(Box::new(conn))
         }
 
+        #[cfg(feature = "trino")]
+        ConnectorConfig::Trino(c) => {
+            let conn = TrinoConnector::new(c).await?;
+            Ok(Box::new(conn))
+        }
+
         #[cfg(feature = "snowflake")]
         ConnectorConfig::Snowflake(c) => {
             let conn = SnowflakeConnector::new(
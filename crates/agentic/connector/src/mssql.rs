@@ -0,0 +1,700 @@
+//! Microsoft SQL Server / Azure SQL connector over TDS via `tiberius`.
+//!
+//! T-SQL refuses to wrap arbitrary user SQL in a derived table — CTEs and
+//! `ORDER BY` without `TOP` are both rejected inside `FROM (...)` — so this
+//! backend cannot use the temp-table / subquery strategy of the Postgres,
+//! MySQL and ClickHouse connectors. Instead:
+//!
+//! 1. `execute_query`: a single pass over the TDS row stream. The first
+//!    `sample_limit` rows are kept; null / distinct counts, MIN / MAX and
+//!    mean / std-dev are accumulated incrementally over the full result.
+//! 2. `execute_query_full`: the query runs as-is and rows are decoded straight
+//!    from tiberius' self-describing `ColumnData` into [`TypedValue`]s. Column
+//!    types come from the TDS column metadata token.
+//! 3. `introspect_schema`: `INFORMATION_SCHEMA.COLUMNS`, cached at
+//!    construction time. Tables are reported schema-qualified (`dbo.orders`).
+//!
+//! TDS connections are strictly sequential, so the single client lives
+//! behind a `tokio::sync::Mutex`.
+
+#![cfg(feature = "mssql")]
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use async_trait::async_trait;
+use futures::StreamExt as _;
+use tiberius::{AuthMethod, Client, ColumnData, ColumnType, Config, EncryptionLevel, FromSql};
+use tokio::net::TcpStream;
+use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
+
+use agentic_core::result::{
+    CellValue, ColumnSpec, QueryResult, QueryRow, TypedDataType, TypedRowError, TypedRowStream,
+    TypedValue,
+};
+
+use crate::config::MssqlConfig;
+use crate::connector::{
+    ColumnStats, ConnectorError, DatabaseConnector, ExecutionResult, ResultSummary,
+    SchemaColumnInfo, SchemaInfo, SchemaTableInfo, SqlDialect, normalize_sql,
+};
+
+type TdsClient = Client<Compat<TcpStream>>;
+
+/// Stop tracking distinct values for a column once this many have been seen.
+/// Past the cap `distinct_count` is reported as `None` rather than holding an
+/// unbounded set in memory.
+const DISTINCT_TRACKING_CAP: usize = 10_000;
+
+// ── Value / type helpers ────────────────────────────────────────────────────
+
+/// Map a TDS column type to a [`TypedDataType`].
+///
+/// The metadata token does not carry precision / scale for `DECIMAL` /
+/// `NUMERIC`, so those use the same `(38, 0)` placeholder as MySQL; the
+/// values themselves keep their exact string form. `MONEY` decodes to `f64`
+/// in tiberius and is reported as `Float64`.
+pub(crate) fn tds_type_to_typed(ty: ColumnType) -> TypedDataType {
+    match ty {
+        ColumnType::Bit | ColumnType::Bitn => TypedDataType::Bool,
+        ColumnType::Int1 | ColumnType::Int2 | ColumnType::Int4 => TypedDataType::Int32,
+        // `Intn` is the nullable-int wire type for every width; i64 holds them all.
+        ColumnType::Int8 | ColumnType::Intn => TypedDataType::Int64,
+        ColumnType::Float4
+        | ColumnType::Float8
+        | ColumnType::Floatn
+        | ColumnType::Money
+        | ColumnType::Money4 => TypedDataType::Float64,
+        ColumnType::Decimaln | ColumnType::Numericn => TypedDataType::Decimal {
+            precision: 38,
+            scale: 0,
+        },
+        ColumnType::BigVarChar
+        | ColumnType::BigChar
+        | ColumnType::NVarchar
+        | ColumnType::NChar
+        | ColumnType::Text
+        | ColumnType::NText
+        | ColumnType::Xml
+        | ColumnType::Guid
+        | ColumnType::Timen => TypedDataType::Text,
+        ColumnType::BigVarBin | ColumnType::BigBinary | ColumnType::Image => TypedDataType::Bytes,
+        ColumnType::Daten => TypedDataType::Date,
+        ColumnType::Datetime
+        | ColumnType::Datetime4
+        | ColumnType::Datetimen
+        | ColumnType::Datetime2
+        | ColumnType::DatetimeOffsetn => TypedDataType::Timestamp,
+        ColumnType::Null | ColumnType::Udt | ColumnType::SSVariant => TypedDataType::Unknown,
+    }
+}
+
+/// Human-readable T-SQL type name for [`ColumnStats::data_type`].
+fn tds_type_name(ty: ColumnType) -> &'static str {
+    match ty {
+        ColumnType::Bit | ColumnType::Bitn => "bit",
+        ColumnType::Int1 => "tinyint",
+        ColumnType::Int2 => "smallint",
+        ColumnType::Int4 => "int",
+        ColumnType::Int8 | ColumnType::Intn => "bigint",
+        ColumnType::Float4 => "real",
+        ColumnType::Float8 | ColumnType::Floatn => "float",
+        ColumnType::Money | ColumnType::Money4 => "money",
+        ColumnType::Decimaln | ColumnType::Numericn => "decimal",
+        ColumnType::BigVarChar | ColumnType::BigChar | ColumnType::Text => "varchar",
+        ColumnType::NVarchar | ColumnType::NChar | ColumnType::NText => "nvarchar",
+        ColumnType::Xml => "xml",
+        ColumnType::Guid => "uniqueidentifier",
+        ColumnType::Timen => "time",
+        ColumnType::BigVarBin | ColumnType::BigBinary | ColumnType::Image => "varbinary",
+        ColumnType::Daten => "date",
+        ColumnType::Datetime | ColumnType::Datetimen => "datetime",
+        ColumnType::Datetime4 => "smalldatetime",
+        ColumnType::Datetime2 => "datetime2",
+        ColumnType::DatetimeOffsetn => "datetimeoffset",
+        ColumnType::Null => "null",
+        ColumnType::Udt => "udt",
+        ColumnType::SSVariant => "sql_variant",
+    }
+}
+
+/// Decode one TDS cell into a [`TypedValue`]. `ColumnData` is
+/// self-describing, so the declared column type is not needed here.
+fn decode_cell(data: &ColumnData<'static>) -> Result<TypedValue, String> {
+    let value = match data {
+        ColumnData::U8(v) => v.map(|n| TypedValue::Int32(n as i32)),
+        ColumnData::I16(v) => v.map(|n| TypedValue::Int32(n as i32)),
+        ColumnData::I32(v) => v.map(TypedValue::Int32),
+        ColumnData::I64(v) => v.map(TypedValue::Int64),
+        ColumnData::F32(v) => v.map(|n| TypedValue::Float64(n as f64)),
+        ColumnData::F64(v) => v.map(TypedValue::Float64),
+        ColumnData::Bit(v) => v.map(TypedValue::Bool),
+        ColumnData::String(v) => v.as_ref().map(|s| TypedValue::Text(s.to_string())),
+        ColumnData::Guid(v) => v.map(|g| TypedValue::Text(g.to_string())),
+        ColumnData::Binary(v) => v.as_ref().map(|b| TypedValue::Bytes(b.to_vec())),
+        ColumnData::Numeric(v) => v.map(|n| TypedValue::Decimal(n.to_string())),
+        ColumnData::Xml(v) => v
+            .as_ref()
+            .map(|x| TypedValue::Text(x.as_ref().clone().into_string())),
+        ColumnData::Date(_) => chrono::NaiveDate::from_sql(data)
+            .map_err(|e| e.to_string())?
+            .map(|d| TypedValue::Date(days_since_epoch(d))),
+        ColumnData::Time(_) => chrono::NaiveTime::from_sql(data)
+            .map_err(|e| e.to_string())?
+            .map(|t| TypedValue::Text(t.to_string())),
+        ColumnData::DateTime(_) | ColumnData::SmallDateTime(_) | ColumnData::DateTime2(_) => {
+            chrono::NaiveDateTime::from_sql(data)
+                .map_err(|e| e.to_string())?
+                .map(|ts| TypedValue::Timestamp(ts.and_utc().timestamp_micros()))
+        }
+        ColumnData::DateTimeOffset(_) => chrono::DateTime::<chrono::Utc>::from_sql(data)
+            .map_err(|e| e.to_string())?
+            .map(|ts| TypedValue::Timestamp(ts.timestamp_micros())),
+    };
+    Ok(value.unwrap_or(TypedValue::Null))
+}
+
+fn unix_epoch() -> chrono::NaiveDate {
+    chrono::NaiveDate::from_ymd_opt(1970, 1, 1).expect("1970-01-01 valid")
+}
+
+fn days_since_epoch(d: chrono::NaiveDate) -> i32 {
+    (d - unix_epoch()).num_days() as i32
+}
+
+/// Collapse a [`TypedValue`] into the lossy [`CellValue`] the solver samples.
+fn typed_to_cell(value: &TypedValue) -> CellValue {
+    match value {
+        TypedValue::Null => CellValue::Null,
+        TypedValue::Bool(b) => CellValue::Text(b.to_string()),
+        TypedValue::Int32(n) => CellValue::Number(*n as f64),
+        TypedValue::Int64(n) => CellValue::Number(*n as f64),
+        TypedValue::Float64(n) => CellValue::Number(*n),
+        TypedValue::Decimal(s) => s
+            .parse::<f64>()
+            .map(CellValue::Number)
+            .unwrap_or_else(|_| CellValue::Text(s.clone())),
+        TypedValue::Text(s) => CellValue::Text(s.clone()),
+        TypedValue::Bytes(b) => CellValue::Text(format!("0x{}", hex_encode(b))),
+        TypedValue::Date(days) => CellValue::Text(
+            unix_epoch()
+                .checked_add_signed(chrono::Duration::days(i64::from(*days)))
+                .map(|d| d.to_string())
+                .unwrap_or_default(),
+        ),
+        TypedValue::Timestamp(micros) => CellValue::Text(
+            chrono::DateTime::from_timestamp_micros(*micros)
+                .map(|ts| ts.naive_utc().to_string())
+                .unwrap_or_default(),
+        ),
+        TypedValue::Json(v) => CellValue::Text(v.to_string()),
+    }
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02X}")).collect()
+}
+
+/// Order two sample cells: numbers numerically, text lexicographically
+/// (ISO dates and timestamps sort correctly as text). Mixed kinds are
+/// incomparable.
+fn compare_cells(a: &CellValue, b: &CellValue) -> Option<Ordering> {
+    match (a, b) {
+        (CellValue::Number(x), CellValue::Number(y)) => x.partial_cmp(y),
+        (CellValue::Text(x), CellValue::Text(y)) => Some(x.cmp(y)),
+        _ => None,
+    }
+}
+
+// ── Streaming statistics ─────────────────────────────────────────────────────
+
+/// Per-column statistics accumulated over one pass of the row stream.
+/// Mean / std-dev use Welford's online algorithm (population variance, to
+/// match `STDDEV_POP` in the other backends).
+struct ColumnAccumulator {
+    name: String,
+    data_type: &'static str,
+    null_count: u64,
+    distinct: Option<HashSet<String>>,
+    min: Option<CellValue>,
+    max: Option<CellValue>,
+    numeric_count: u64,
+    mean: f64,
+    m2: f64,
+}
+
+impl ColumnAccumulator {
+    fn new(name: String, data_type: &'static str) -> Self {
+        Self {
+            name,
+            data_type,
+            null_count: 0,
+            distinct: Some(HashSet::new()),
+            min: None,
+            max: None,
+            numeric_count: 0,
+            mean: 0.0,
+            m2: 0.0,
+        }
+    }
+
+    fn push(&mut self, cell: &CellValue) {
+        let key = match cell {
+            CellValue::Null => {
+                self.null_count += 1;
+                return;
+            }
+            CellValue::Number(n) => {
+                self.numeric_count += 1;
+                let delta = n - self.mean;
+                self.mean += delta / self.numeric_count as f64;
+                self.m2 += delta * (n - self.mean);
+                n.to_string()
+            }
+            CellValue::Text(s) => s.clone(),
+        };
+
+        if let Some(set) = self.distinct.as_mut() {
+            set.insert(key);
+            if set.len() > DISTINCT_TRACKING_CAP {
+                self.distinct = None;
+            }
+        }
+        if self
+            .min
+            .as_ref()
+            .is_none_or(|m| compare_cells(cell, m) == Some(Ordering::Less))
+        {
+            self.min = Some(cell.clone());
+        }
+        if self
+            .max
+            .as_ref()
+            .is_none_or(|m| compare_cells(cell, m) == Some(Ordering::Greater))
+        {
+            self.max = Some(cell.clone());
+        }
+    }
+
+    fn finish(self) -> ColumnStats {
+        let (mean, std_dev) = if self.numeric_count > 0 {
+            (
+                Some(self.mean),
+                Some((self.m2 / self.numeric_count as f64).sqrt()),
+            )
+        } else {
+            (None, None)
+        };
+        ColumnStats {
+            name: self.name,
+            data_type: Some(self.data_type.to_string()),
+            null_count: self.null_count,
+            distinct_count: self.distinct.map(|s| s.len() as u64),
+            min: self.min,
+            max: self.max,
+            mean,
+            std_dev,
+        }
+    }
+}
+
+// ── Connector ─────────────────────────────────────────────────────────────────
+
+/// SQL Server / Azure SQL connector backed by a single `tiberius` client.
+pub struct MssqlConnector {
+    client: tokio::sync::Mutex<TdsClient>,
+    cached_schema: SchemaInfo,
+}
+
+impl MssqlConnector {
+    /// Open a TDS connection, log in, and pre-fetch the database schema.
+    pub async fn new(config: &MssqlConfig) -> Result<Self, ConnectorError> {
+        let mut client = connect(config).await?;
+        let cached_schema = match fetch_schema(&mut client).await {
+            Ok(schema) => schema,
+            Err(e) => {
+                tracing::warn!("mssql: schema prefetch failed ({e}); schema browsing unavailable");
+                SchemaInfo::default()
+            }
+        };
+        Ok(Self {
+            client: tokio::sync::Mutex::new(client),
+            cached_schema,
+        })
+    }
+}
+
+/// Build the tiberius login config from an already-resolved [`MssqlConfig`].
+fn tds_config(config: &MssqlConfig) -> Config {
+    let mut tds = Config::new();
+    tds.host(&config.host);
+    tds.port(config.port);
+    tds.database(&config.database);
+    tds.application_name("oxy");
+    tds.authentication(AuthMethod::sql_server(&config.user, &config.password));
+    tds.encryption(if config.encrypt {
+        EncryptionLevel::Required
+    } else {
+        EncryptionLevel::NotSupported
+    });
+    if config.trust_server_certificate {
+        tds.trust_cert();
+    }
+    tds
+}
+
+async fn connect(config: &MssqlConfig) -> Result<TdsClient, ConnectorError> {
+    let mut tds = tds_config(config);
+    let tcp = open_tcp(&tds.get_addr()).await?;
+    match Client::connect(tds.clone(), tcp.compat_write()).await {
+        Ok(client) => Ok(client),
+        // Azure SQL gateways answer the first login with a redirect to the
+        // node that actually hosts the database.
+        Err(tiberius::error::Error::Routing { host, port }) => {
+            tds.host(&host);
+            tds.port(port);
+            let tcp = open_tcp(&tds.get_addr()).await?;
+            Client::connect(tds, tcp.compat_write())
+                .await
+                .map_err(|e| ConnectorError::ConnectionError(e.to_string()))
+        }
+        Err(e) => Err(ConnectorError::ConnectionError(e.to_string())),
+    }
+}
+
+async fn open_tcp(addr: &str) -> Result<TcpStream, ConnectorError> {
+    let tcp = TcpStream::connect(addr)
+        .await
+        .map_err(|e| ConnectorError::ConnectionError(format!("{addr}: {e}")))?;
+    tcp.set_nodelay(true)
+        .map_err(|e| ConnectorError::ConnectionError(e.to_string()))?;
+    Ok(tcp)
+}
+
+fn query_failed(sql: &str, err: tiberius::error::Error) -> ConnectorError {
+    ConnectorError::QueryFailed {
+        sql: sql.to_string(),
+        message: err.to_string(),
+    }
+}
+
+// ── DatabaseConnector impl ────────────────────────────────────────────────────
+
+#[async_trait]
+impl DatabaseConnector for MssqlConnector {
+    fn dialect(&self) -> SqlDialect {
+        SqlDialect::SqlServer
+    }
+
+    async fn execute_query(
+        &self,
+        sql: &str,
+        sample_limit: u64,
+    ) -> Result<ExecutionResult, ConnectorError> {
+        let sql = normalize_sql(sql);
+        let mut client = self.client.lock().await;
+
+        let mut stream = client
+            .query(sql, &[])
+            .await
+            .map_err(|e| query_failed(sql, e))?;
+        let columns: Vec<(String, ColumnType)> = stream
+            .columns()
+            .await
+            .map_err(|e| query_failed(sql, e))?
+            .map(|cols| {
+                cols.iter()
+                    .map(|c| (c.name().to_string(), c.column_type()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut accumulators: Vec<ColumnAccumulator> = columns
+            .iter()
+            .map(|(name, ty)| ColumnAccumulator::new(name.clone(), tds_type_name(*ty)))
+            .collect();
+        let mut sample_rows: Vec<QueryRow> = Vec::new();
+        let mut total_row_count: u64 = 0;
+
+        // Only the first result set is analysed; trailing result sets from
+        // multi-statement batches are drained and ignored.
+        let mut rows = stream.into_row_stream();
+        while let Some(row) = rows.next().await {
+            let row = row.map_err(|e| query_failed(sql, e))?;
+            if row.result_index() > 0 {
+                continue;
+            }
+            total_row_count += 1;
+            let cells: Vec<CellValue> = row
+                .cells()
+                .map(|(_, data)| decode_cell(data).map_or(CellValue::Null, |v| typed_to_cell(&v)))
+                .collect();
+            for (acc, cell) in accumulators.iter_mut().zip(cells.iter()) {
+                acc.push(cell);
+            }
+            if (sample_rows.len() as u64) < sample_limit {
+                sample_rows.push(QueryRow(cells));
+            }
+        }
+
+        let truncated = (sample_rows.len() as u64) < total_row_count;
+        Ok(ExecutionResult {
+            result: QueryResult {
+                columns: columns.into_iter().map(|(name, _)| name).collect(),
+                rows: sample_rows,
+                total_row_count,
+                truncated,
+            },
+            summary: ResultSummary {
+                row_count: total_row_count,
+                columns: accumulators.into_iter().map(|a| a.finish()).collect(),
+            },
+        })
+    }
+
+    async fn execute_query_full(&self, sql: &str) -> Result<TypedRowStream, ConnectorError> {
+        let sql = normalize_sql(sql);
+        let mut client = self.client.lock().await;
+
+        let mut stream = client
+            .query(sql, &[])
+            .await
+            .map_err(|e| query_failed(sql, e))?;
+        // DDL / DML produce no column metadata — return an empty result.
+        let columns: Vec<ColumnSpec> = stream
+            .columns()
+            .await
+            .map_err(|e| query_failed(sql, e))?
+            .map(|cols| {
+                cols.iter()
+                    .map(|c| ColumnSpec {
+                        name: c.name().to_string(),
+                        data_type: tds_type_to_typed(c.column_type()),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // The client is borrowed for the life of the TDS stream, so rows are
+        // decoded eagerly (same as the Postgres backend) before the lock drops.
+        let rows = stream
+            .into_first_result()
+            .await
+            .map_err(|e| query_failed(sql, e))?;
+        let typed_rows = rows
+            .iter()
+            .map(|row| {
+                row.cells()
+                    .map(|(col, data)| {
+                        decode_cell(data).map_err(|message| TypedRowError::TypeMappingError {
+                            column: col.name().to_string(),
+                            native_type: tds_type_name(col.column_type()).to_string(),
+                            message,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect();
+
+        Ok(TypedRowStream::from_rows(columns, typed_rows))
+    }
+
+    fn introspect_schema(&self) -> Result<SchemaInfo, ConnectorError> {
+        Ok(self.cached_schema.clone())
+    }
+}
+
+// ── Schema pre-fetch ──────────────────────────────────────────────────────────
+
+async fn fetch_schema(client: &mut TdsClient) -> Result<SchemaInfo, ConnectorError> {
+    let schema_sql = "\
+        SELECT TABLE_SCHEMA, TABLE_NAME, COLUMN_NAME, DATA_TYPE \
+        FROM INFORMATION_SCHEMA.COLUMNS \
+        WHERE TABLE_SCHEMA NOT IN ('sys', 'INFORMATION_SCHEMA') \
+        ORDER BY TABLE_SCHEMA, TABLE_NAME, ORDINAL_POSITION";
+
+    let rows = client
+        .query(schema_sql, &[])
+        .await
+        .map_err(|e| ConnectorError::ConnectionError(format!("schema introspection failed: {e}")))?
+        .into_first_result()
+        .await
+        .map_err(|e| {
+            ConnectorError::ConnectionError(format!("schema introspection failed: {e}"))
+        })?;
+
+    // BTreeMap preserves alphabetical table order across iterations.
+    let mut map: BTreeMap<String, Vec<SchemaColumnInfo>> = BTreeMap::new();
+    for row in &rows {
+        let (Some(schema), Some(table), Some(column), Some(data_type)) = (
+            row.get::<&str, _>(0),
+            row.get::<&str, _>(1),
+            row.get::<&str, _>(2),
+            row.get::<&str, _>(3),
+        ) else {
+            continue;
+        };
+        map.entry(format!("{schema}.{table}"))
+            .or_default()
+            .push(SchemaColumnInfo {
+                name: column.to_string(),
+                data_type: data_type.to_string(),
+                min: None,
+                max: None,
+                sample_values: vec![],
+            });
+    }
+
+    let tables: Vec<SchemaTableInfo> = map
+        .into_iter()
+        .map(|(name, columns)| SchemaTableInfo { name, columns })
+        .collect();
+
+    let join_keys = detect_join_keys(&tables);
+    Ok(SchemaInfo { tables, join_keys })
+}
+
+// ── Join key detection ────────────────────────────────────────────────────────
+
+/// Auto-detect join keys: any column ending in `_id` shared across two tables.
+fn detect_join_keys(tables: &[SchemaTableInfo]) -> Vec<(String, String, String)> {
+    let mut col_to_tables: HashMap<&str, Vec<&str>> = HashMap::new();
+    for t in tables {
+        for c in &t.columns {
+            if c.name.ends_with("_id") {
+                col_to_tables
+                    .entry(c.name.as_str())
+                    .or_default()
+                    .push(t.name.as_str());
+            }
+        }
+    }
+    let mut keys = Vec::new();
+    for (col, tbs) in col_to_tables {
+        for i in 0..tbs.len() {
+            for j in (i + 1)..tbs.len() {
+                keys.push((tbs[i].to_string(), tbs[j].to_string(), col.to_string()));
+            }
+        }
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::borrow::Cow;
+
+    #[test]
+    fn type_mapping_basic() {
+        assert_eq!(tds_type_to_typed(ColumnType::Int4), TypedDataType::Int32);
+        assert_eq!(tds_type_to_typed(ColumnType::Intn), TypedDataType::Int64);
+        assert_eq!(
+            tds_type_to_typed(ColumnType::Floatn),
+            TypedDataType::Float64
+        );
+        assert_eq!(tds_type_to_typed(ColumnType::NVarchar), TypedDataType::Text);
+        assert_eq!(
+            tds_type_to_typed(ColumnType::BigVarBin),
+            TypedDataType::Bytes
+        );
+        assert_eq!(tds_type_to_typed(ColumnType::Daten), TypedDataType::Date);
+        assert_eq!(
+            tds_type_to_typed(ColumnType::Datetime2),
+            TypedDataType::Timestamp
+        );
+        assert_eq!(tds_type_to_typed(ColumnType::Bitn), TypedDataType::Bool);
+        assert!(matches!(
+            tds_type_to_typed(ColumnType::Numericn),
+            TypedDataType::Decimal { .. }
+        ));
+    }
+
+    #[test]
+    fn decode_scalar_cells() {
+        assert_eq!(
+            decode_cell(&ColumnData::I32(Some(7))).unwrap(),
+            TypedValue::Int32(7)
+        );
+        assert_eq!(
+            decode_cell(&ColumnData::U8(Some(255))).unwrap(),
+            TypedValue::Int32(255)
+        );
+        assert_eq!(
+            decode_cell(&ColumnData::String(Some(Cow::Borrowed("héllo")))).unwrap(),
+            TypedValue::Text("héllo".into())
+        );
+        assert_eq!(
+            decode_cell(&ColumnData::Numeric(Some(
+                tiberius::numeric::Numeric::new_with_scale(12345, 2)
+            )))
+            .unwrap(),
+            TypedValue::Decimal("123.45".into())
+        );
+        assert_eq!(
+            decode_cell(&ColumnData::I64(None)).unwrap(),
+            TypedValue::Null
+        );
+    }
+
+    #[test]
+    fn decode_temporal_cells() {
+        use chrono::Timelike;
+        use tiberius::time::{Date, DateTime2, Time};
+
+        // TDS dates count days from 0001-01-01; times are 10^-scale second
+        // increments since midnight.
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let day_zero = chrono::NaiveDate::from_ymd_opt(1, 1, 1).unwrap();
+        let days = (date - day_zero).num_days() as u32;
+        let data = ColumnData::Date(Some(Date::new(days)));
+        assert_eq!(
+            decode_cell(&data).unwrap(),
+            TypedValue::Date(days_since_epoch(date))
+        );
+
+        let ts = date.and_hms_opt(12, 30, 0).unwrap();
+        let increments = u64::from(ts.time().num_seconds_from_midnight()) * 10_000_000;
+        let data = ColumnData::DateTime2(Some(DateTime2::new(
+            Date::new(days),
+            Time::new(increments, 7),
+        )));
+        assert_eq!(
+            decode_cell(&data).unwrap(),
+            TypedValue::Timestamp(ts.and_utc().timestamp_micros())
+        );
+    }
+
+    #[test]
+    fn accumulator_tracks_stats() {
+        let mut acc = ColumnAccumulator::new("x".into(), "int");
+        for cell in [
+            CellValue::Number(2.0),
+            CellValue::Number(4.0),
+            CellValue::Null,
+            CellValue::Number(4.0),
+            CellValue::Number(6.0),
+        ] {
+            acc.push(&cell);
+        }
+        let stats = acc.finish();
+        assert_eq!(stats.null_count, 1);
+        assert_eq!(stats.distinct_count, Some(3));
+        assert_eq!(stats.min, Some(CellValue::Number(2.0)));
+        assert_eq!(stats.max, Some(CellValue::Number(6.0)));
+        assert_eq!(stats.mean, Some(4.0));
+        assert!((stats.std_dev.unwrap() - 2.0_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn accumulator_gives_up_on_high_cardinality() {
+        let mut acc = ColumnAccumulator::new("id".into(), "nvarchar");
+        for i in 0..=DISTINCT_TRACKING_CAP {
+            acc.push(&CellValue::Text(format!("v{i:06}")));
+        }
+        let stats = acc.finish();
+        assert_eq!(stats.distinct_count, None);
+        assert_eq!(stats.min, Some(CellValue::Text("v000000".into())));
+        assert_eq!(stats.mean, None);
+    }
+}
@@ -0,0 +1,164 @@
+//! Record/replay of connector queries for offline, deterministic test runs.
+//!
+//! [`ReplayConnector`] wraps another [`DatabaseConnector`].  When recording,
+//! every query is forwarded and its result — rows, stats or the error — is
+//! written to `<dir>/connector/<hash>.json`; when replaying, the result is
+//! read back from that file and the wrapped connector is never queried.
+//! `<hash>` is a digest of the database name, method, SQL and row limit.
+//!
+//! [`ReplayConnector::wrap_from_env`] reads `OXY_REPLAY_MODE` and
+//! `OXY_REPLAY_DIR`, the same variables the LLM providers and the classic
+//! connector read.  Fixtures are stored through
+//! [`agentic_core::replay::FixtureDir`].
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use agentic_core::replay::{FixtureDir, ReplayMode};
+use agentic_core::result::{ColumnSpec, TypedRowError, TypedRowStream, TypedValue};
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+
+use crate::connector::{
+    ConnectorError, DatabaseConnector, ExecutionResult, SchemaInfo, SqlDialect,
+};
+
+const FIXTURE_NAMESPACE: &str = "connector";
+
+/// A [`DatabaseConnector`] that records or replays another connector's
+/// results.
+///
+/// `as_arrow` is left at the default `None` so Arrow consumers fall back to
+/// the recorded `execute_query_full`.
+pub struct ReplayConnector {
+    inner: Arc<dyn DatabaseConnector>,
+    database: String,
+    fixtures: FixtureDir,
+}
+
+/// A full result as it is stored in a fixture.
+#[derive(Serialize, Deserialize)]
+struct RecordedRows {
+    columns: Vec<ColumnSpec>,
+    rows: Vec<Result<Vec<TypedValue>, TypedRowError>>,
+}
+
+impl ReplayConnector {
+    pub fn new(
+        inner: Arc<dyn DatabaseConnector>,
+        database: impl Into<String>,
+        mode: ReplayMode,
+        dir: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            inner,
+            database: database.into(),
+            fixtures: FixtureDir::new(mode, dir),
+        }
+    }
+
+    /// Wrap `inner` when `OXY_REPLAY_MODE` asks for it, otherwise return it
+    /// unchanged.
+    pub fn wrap_from_env(
+        database: &str,
+        inner: Arc<dyn DatabaseConnector>,
+    ) -> Arc<dyn DatabaseConnector> {
+        match FixtureDir::from_env() {
+            Some(fixtures) => Arc::new(Self {
+                inner,
+                database: database.to_string(),
+                fixtures,
+            }),
+            None => inner,
+        }
+    }
+
+    fn request(&self, method: &str, query: Option<&str>, limit: Option<u64>) -> Value {
+        json!({
+            "database": self.database,
+            "method": method,
+            "query": query,
+            "limit": limit,
+        })
+    }
+
+    fn replay<T: DeserializeOwned>(&self, request: &Value) -> Result<T, ConnectorError> {
+        let response = self
+            .fixtures
+            .load(FIXTURE_NAMESPACE, request)
+            .map_err(|e| ConnectorError::Other(e.to_string()))?;
+        serde_json::from_value::<Result<T, ConnectorError>>(response)
+            .map_err(|e| ConnectorError::Other(format!("invalid connector fixture: {e}")))?
+    }
+
+    /// Store `result`, failures included, so a replayed run sees the same
+    /// errors it did while recording.
+    fn record<T: Serialize>(
+        &self,
+        request: &Value,
+        result: Result<T, ConnectorError>,
+    ) -> Result<T, ConnectorError> {
+        let response = serde_json::to_value(&result)
+            .map_err(|e| ConnectorError::Other(format!("failed to serialize result: {e}")))?;
+        self.fixtures
+            .save(FIXTURE_NAMESPACE, request, response)
+            .map_err(|e| ConnectorError::Other(e.to_string()))?;
+        result
+    }
+}
+
+#[async_trait]
+impl DatabaseConnector for ReplayConnector {
+    fn dialect(&self) -> SqlDialect {
+        self.inner.dialect()
+    }
+
+    async fn execute_query(
+        &self,
+        sql: &str,
+        sample_limit: u64,
+    ) -> Result<ExecutionResult, ConnectorError> {
+        let request = self.request("execute_query", Some(sql), Some(sample_limit));
+        if self.fixtures.is_replaying() {
+            return self.replay(&request);
+        }
+        let result = self.inner.execute_query(sql, sample_limit).await;
+        self.record(&request, result)
+    }
+
+    async fn execute_query_full(&self, sql: &str) -> Result<TypedRowStream, ConnectorError> {
+        let request = self.request("execute_query_full", Some(sql), None);
+        let recorded: RecordedRows = if self.fixtures.is_replaying() {
+            self.replay(&request)?
+        } else {
+            // The whole stream is read before it is handed back so only
+            // complete results are written.
+            let result = match self.inner.execute_query_full(sql).await {
+                Ok(stream) => Ok(RecordedRows {
+                    columns: stream.columns,
+                    rows: stream.rows.collect().await,
+                }),
+                Err(err) => Err(err),
+            };
+            self.record(&request, result)?
+        };
+        Ok(TypedRowStream::from_rows(recorded.columns, recorded.rows))
+    }
+
+    async fn prepare_schema(&self) -> Result<(), ConnectorError> {
+        if self.fixtures.is_replaying() {
+            return Ok(());
+        }
+        self.inner.prepare_schema().await
+    }
+
+    fn introspect_schema(&self) -> Result<SchemaInfo, ConnectorError> {
+        let request = self.request("introspect_schema", None, None);
+        if self.fixtures.is_replaying() {
+            return self.replay(&request);
+        }
+        self.record(&request, self.inner.introspect_schema())
+    }
+}
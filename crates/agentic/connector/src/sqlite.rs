@@ -0,0 +1,552 @@
+//! SQLite connector implementation over `rusqlite`.
+//!
+//! Unlike DuckDB, SQLite cannot create temp tables on a `query_only`
+//! connection, so the user's query is wrapped in a CTE instead:
+//!
+//! 1. `execute_query`: the first `sample_limit` rows are read straight from the
+//!    prepared statement, then a single aggregate over
+//!    `WITH _agentic_q(c0, c1, …) AS ({sql})` yields the row count and the
+//!    per-column null / distinct counts, MIN / MAX, mean and std-dev. The
+//!    positional column list keeps duplicate output names apart.
+//! 2. `execute_query_full`: rows are collected eagerly and each column's type
+//!    is resolved from its declared type and the values returned (see
+//!    [`crate::sqlite_typed`]).
+//! 3. `introspect_schema`: `pragma_table_list` / `pragma_table_info`, computed
+//!    on demand. Tables in `main` keep their bare names; tables in attached
+//!    databases are reported as `schema.table`.
+//!
+//! Several files are opened by attaching each to an in-memory connection
+//! under a schema named after its file stem.
+
+#![cfg(feature = "sqlite")]
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use async_trait::async_trait;
+use rusqlite::{Connection, OpenFlags, types::Value};
+use slugify::slugify;
+
+use agentic_core::result::{
+    CellValue, ColumnSpec, QueryResult, QueryRow, TypedRowError, TypedRowStream, TypedValue,
+};
+
+use crate::connector::{
+    ColumnStats, ConnectorError, DatabaseConnector, ExecutionResult, ResultSummary,
+    SchemaColumnInfo, SchemaInfo, SchemaTableInfo, SqlDialect, normalize_sql,
+};
+use crate::sqlite_typed::{resolve_column_type, storage_class, value_to_cell, value_to_typed};
+
+// Re-export Connection so callers / integration tests can construct connections
+// without adding `rusqlite` as a separate direct dependency.
+pub use rusqlite::Connection as SqliteConnection;
+
+pub struct SqliteConnector {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteConnector {
+    /// Wrap an already-open connection.
+    pub fn new(conn: Connection) -> Self {
+        Self {
+            conn: Mutex::new(conn),
+        }
+    }
+
+    /// Open one or more database files.
+    ///
+    /// With `read_only` the files are opened in `mode=ro` and the connection is
+    /// switched to `PRAGMA query_only`, so writes fail even against the
+    /// in-memory `main` schema used when several files are attached.
+    pub fn open(files: &[PathBuf], read_only: bool) -> Result<Self, ConnectorError> {
+        let conn = match files {
+            [] => {
+                return Err(ConnectorError::ConnectionError(
+                    "no SQLite database file given".into(),
+                ));
+            }
+            [file] => {
+                let mode = if read_only {
+                    OpenFlags::SQLITE_OPEN_READ_ONLY
+                } else {
+                    OpenFlags::SQLITE_OPEN_READ_WRITE
+                };
+                Connection::open_with_flags(
+                    file,
+                    mode | OpenFlags::SQLITE_OPEN_URI | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+                )
+                .map_err(|e| ConnectorError::ConnectionError(format!("{}: {e}", file.display())))?
+            }
+            _ => {
+                let conn = Connection::open_in_memory()
+                    .map_err(|e| ConnectorError::ConnectionError(e.to_string()))?;
+                for (alias, file) in schema_aliases(files) {
+                    conn.execute(
+                        &format!("ATTACH DATABASE ?1 AS \"{alias}\""),
+                        [file_uri(file, read_only)],
+                    )
+                    .map_err(|e| {
+                        ConnectorError::ConnectionError(format!(
+                            "cannot attach {}: {e}",
+                            file.display()
+                        ))
+                    })?;
+                }
+                conn
+            }
+        };
+        if read_only {
+            conn.pragma_update(None, "query_only", true)
+                .map_err(|e| ConnectorError::ConnectionError(e.to_string()))?;
+        }
+        Ok(Self::new(conn))
+    }
+}
+
+/// Schema name for each attached file: the slugified file stem, prefixed with
+/// `_` when it starts with a digit and suffixed when two stems collide or
+/// clash with SQLite's reserved `main` / `temp` schemas.
+fn schema_aliases(files: &[PathBuf]) -> Vec<(String, &Path)> {
+    let mut taken: HashSet<String> = HashSet::from(["main".to_string(), "temp".to_string()]);
+    files
+        .iter()
+        .map(|file| {
+            let stem = file
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default();
+            let mut base = slugify!(stem, separator = "_");
+            if base.is_empty() {
+                base = "db".to_string();
+            } else if base.starts_with(|c: char| c.is_ascii_digit()) {
+                base = format!("_{base}");
+            }
+            let mut alias = base.clone();
+            let mut n = 1;
+            while taken.contains(&alias) {
+                n += 1;
+                alias = format!("{base}_{n}");
+            }
+            taken.insert(alias.clone());
+            (alias, file.as_path())
+        })
+        .collect()
+}
+
+/// `file:` URI for `ATTACH`, so the access mode applies per file.
+fn file_uri(path: &Path, read_only: bool) -> String {
+    let escaped = path
+        .to_string_lossy()
+        .replace('%', "%25")
+        .replace('?', "%3f")
+        .replace('#', "%23");
+    let mode = if read_only { "ro" } else { "rw" };
+    format!("file:{escaped}?mode={mode}")
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn query_failed(sql: &str, err: rusqlite::Error) -> ConnectorError {
+    ConnectorError::QueryFailed {
+        sql: sql.to_string(),
+        message: err.to_string(),
+    }
+}
+
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(n) => Some(*n as f64),
+        Value::Real(f) => Some(*f),
+        _ => None,
+    }
+}
+
+/// MIN / MAX cell; blobs are not meaningful as a range and are dropped.
+fn range_cell(value: Value) -> Option<CellValue> {
+    match value {
+        Value::Null | Value::Blob(_) => None,
+        v => Some(value_to_cell(&v)),
+    }
+}
+
+// ── DatabaseConnector impl ────────────────────────────────────────────────────
+
+#[async_trait]
+impl DatabaseConnector for SqliteConnector {
+    fn dialect(&self) -> SqlDialect {
+        SqlDialect::Sqlite
+    }
+
+    async fn execute_query(
+        &self,
+        sql: &str,
+        sample_limit: u64,
+    ) -> Result<ExecutionResult, ConnectorError> {
+        let sql = normalize_sql(sql);
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| ConnectorError::ConnectionError(format!("mutex poisoned: {e}")))?;
+
+        // 1. Sample rows straight from the statement.
+        let mut stmt = conn.prepare(sql).map_err(|e| query_failed(sql, e))?;
+        let column_names: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+        let decl_types: Vec<Option<String>> = stmt
+            .columns()
+            .iter()
+            .map(|c| c.decl_type().map(str::to_string))
+            .collect();
+        let col_count = column_names.len();
+
+        if col_count == 0 {
+            // DDL / DML: nothing to sample or summarise.
+            stmt.execute([]).map_err(|e| query_failed(sql, e))?;
+            return Ok(ExecutionResult {
+                result: QueryResult {
+                    columns: vec![],
+                    rows: vec![],
+                    total_row_count: 0,
+                    truncated: false,
+                },
+                summary: ResultSummary {
+                    row_count: 0,
+                    columns: vec![],
+                },
+            });
+        }
+
+        let mut sample_values: Vec<Vec<Value>> = Vec::new();
+        {
+            let mut rows = stmt.query([]).map_err(|e| query_failed(sql, e))?;
+            while (sample_values.len() as u64) < sample_limit {
+                let Some(row) = rows.next().map_err(|e| query_failed(sql, e))? else {
+                    break;
+                };
+                let values = (0..col_count)
+                    .map(|i| row.get::<_, Value>(i))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| query_failed(sql, e))?;
+                sample_values.push(values);
+            }
+        }
+        drop(stmt);
+
+        // 2. One aggregate pass over the full result.
+        let aliases: Vec<String> = (0..col_count).map(|i| format!("c{i}")).collect();
+        let aggregates: Vec<String> = aliases
+            .iter()
+            .map(|c| {
+                let numeric = format!("CASE WHEN typeof({c}) IN ('integer', 'real') THEN {c} END");
+                format!(
+                    "COUNT(*) - COUNT({c}), COUNT(DISTINCT {c}), MIN({c}), MAX({c}), \
+                     AVG({numeric}), AVG(({numeric}) * ({numeric}))"
+                )
+            })
+            .collect();
+        let stats_sql = format!(
+            "WITH _agentic_q({}) AS ({sql}) SELECT COUNT(*), {} FROM _agentic_q",
+            aliases.join(", "),
+            aggregates.join(", ")
+        );
+        let stats: Vec<Value> = conn
+            .query_row(&stats_sql, [], |row| {
+                (0..1 + 6 * col_count)
+                    .map(|i| row.get::<_, Value>(i))
+                    .collect()
+            })
+            .map_err(|e| query_failed(&stats_sql, e))?;
+
+        let total_row_count = match stats[0] {
+            Value::Integer(n) => n as u64,
+            _ => 0,
+        };
+
+        let mut stats = stats.into_iter().skip(1);
+        let mut next = || stats.next().unwrap_or(Value::Null);
+        let col_stats: Vec<ColumnStats> = column_names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let null_count = as_f64(&next()).unwrap_or(0.0) as u64;
+                let distinct_count = as_f64(&next()).map(|n| n as u64);
+                let min = range_cell(next());
+                let max = range_cell(next());
+                let mean = as_f64(&next());
+                let mean_sq = as_f64(&next());
+                // SQLite has no STDDEV; population std-dev from E[x²] − E[x]².
+                let std_dev = mean
+                    .zip(mean_sq)
+                    .map(|(m, sq)| (sq - m * m).max(0.0).sqrt());
+                let data_type = decl_types[i]
+                    .clone()
+                    .filter(|d| !d.is_empty())
+                    .or_else(|| {
+                        sample_values
+                            .iter()
+                            .map(|row| storage_class(&row[i]))
+                            .find(|class| *class != "NULL")
+                            .map(str::to_string)
+                    })
+                    .unwrap_or_else(|| "NULL".to_string());
+                ColumnStats {
+                    name: name.clone(),
+                    data_type: Some(data_type),
+                    null_count,
+                    distinct_count,
+                    min,
+                    max,
+                    mean,
+                    std_dev,
+                }
+            })
+            .collect();
+
+        let sample_rows: Vec<QueryRow> = sample_values
+            .iter()
+            .map(|row| QueryRow(row.iter().map(value_to_cell).collect()))
+            .collect();
+        let truncated = (sample_rows.len() as u64) < total_row_count;
+        Ok(ExecutionResult {
+            result: QueryResult {
+                columns: column_names,
+                rows: sample_rows,
+                total_row_count,
+                truncated,
+            },
+            summary: ResultSummary {
+                row_count: total_row_count,
+                columns: col_stats,
+            },
+        })
+    }
+
+    async fn execute_query_full(&self, sql: &str) -> Result<TypedRowStream, ConnectorError> {
+        let sql = normalize_sql(sql);
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| ConnectorError::ConnectionError(format!("mutex poisoned: {e}")))?;
+
+        let mut stmt = conn.prepare(sql).map_err(|e| query_failed(sql, e))?;
+        let column_names: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+        let decl_types: Vec<Option<String>> = stmt
+            .columns()
+            .iter()
+            .map(|c| c.decl_type().map(str::to_string))
+            .collect();
+        let col_count = column_names.len();
+
+        // Collect eagerly: column types depend on every value, and the stream
+        // must not borrow the connection past the lock.
+        let mut values: Vec<Vec<Value>> = Vec::new();
+        let mut rows = stmt.query([]).map_err(|e| query_failed(sql, e))?;
+        while let Some(row) = rows.next().map_err(|e| query_failed(sql, e))? {
+            let cells = (0..col_count)
+                .map(|i| row.get::<_, Value>(i))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| query_failed(sql, e))?;
+            values.push(cells);
+        }
+
+        let columns: Vec<ColumnSpec> = column_names
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| ColumnSpec {
+                name,
+                data_type: resolve_column_type(
+                    decl_types[i].as_deref(),
+                    values.iter().map(|row| &row[i]),
+                ),
+            })
+            .collect();
+
+        let rows: Vec<Result<Vec<TypedValue>, TypedRowError>> = values
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&columns)
+                    .map(|(value, col)| {
+                        value_to_typed(value, &col.data_type).ok_or_else(|| {
+                            TypedRowError::TypeMappingError {
+                                column: col.name.clone(),
+                                native_type: storage_class(value).to_string(),
+                                message: format!("value does not fit {:?}", col.data_type),
+                            }
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok(TypedRowStream::from_rows(columns, rows))
+    }
+
+    fn introspect_schema(&self) -> Result<SchemaInfo, ConnectorError> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| ConnectorError::ConnectionError(format!("mutex poisoned: {e}")))?;
+
+        // ── 1. All user tables + views, across attached databases ─────────────
+        let list_sql = "SELECT t.schema, t.name, c.name, c.type \
+             FROM pragma_table_list AS t \
+             JOIN pragma_table_info(t.name, t.schema) AS c \
+             WHERE t.schema <> 'temp' AND t.type IN ('table', 'view') \
+               AND t.name NOT LIKE 'sqlite_%' \
+             ORDER BY t.schema = 'main' DESC, t.schema, t.name, c.cid";
+        let column_rows: Vec<(String, String, String, String)> = conn
+            .prepare(list_sql)
+            .and_then(|mut stmt| {
+                stmt.query_map([], |row| {
+                    Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+                })?
+                .collect()
+            })
+            .map_err(|e| ConnectorError::Other(e.to_string()))?;
+
+        let mut grouped: Vec<(TableKey, Vec<ColumnDecl>)> = Vec::new();
+        for (schema, table, column, decl) in column_rows {
+            match grouped.last_mut() {
+                Some((key, cols)) if key.0 == schema && key.1 == table => cols.push((column, decl)),
+                _ => grouped.push(((schema, table), vec![(column, decl)])),
+            }
+        }
+
+        let mut tables: Vec<SchemaTableInfo> = Vec::with_capacity(grouped.len());
+        for ((schema, table), cols) in grouped {
+            let qualified = format!("{}.{}", quote_ident(&schema), quote_ident(&table));
+
+            // ── 2. MIN / MAX for every column in one scan ─────────────────────
+            let range_sql = format!(
+                "SELECT {} FROM {qualified}",
+                cols.iter()
+                    .map(|(c, _)| {
+                        let c = quote_ident(c);
+                        format!("MIN({c}), MAX({c})")
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            let ranges: Vec<Value> = conn
+                .query_row(&range_sql, [], |row| {
+                    (0..2 * cols.len())
+                        .map(|i| row.get::<_, Value>(i))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let mut ranges = ranges.into_iter();
+
+            // ── 3. One sample query for all columns ───────────────────────────
+            let mut samples_by_idx: Vec<Vec<CellValue>> = vec![vec![]; cols.len()];
+            let sample_res = conn
+                .prepare(&format!("SELECT * FROM {qualified} LIMIT 5"))
+                .and_then(|mut stmt| {
+                    stmt.query_map([], |row| {
+                        (0..cols.len())
+                            .map(|i| row.get::<_, Value>(i))
+                            .collect::<Result<Vec<_>, _>>()
+                    })?
+                    .collect::<Result<Vec<_>, _>>()
+                });
+            if let Ok(rows) = sample_res {
+                for row_vals in rows {
+                    for (i, v) in row_vals.iter().enumerate() {
+                        if !matches!(v, Value::Null) {
+                            samples_by_idx[i].push(value_to_cell(v));
+                        }
+                    }
+                }
+            }
+
+            let columns: Vec<SchemaColumnInfo> = cols
+                .into_iter()
+                .zip(samples_by_idx)
+                .map(|((name, decl), sample_values)| {
+                    let min = ranges.next().and_then(range_cell);
+                    let max = ranges.next().and_then(range_cell);
+                    SchemaColumnInfo {
+                        name,
+                        // Columns declared without a type accept any value.
+                        data_type: if decl.is_empty() { "ANY".into() } else { decl },
+                        min,
+                        max,
+                        sample_values,
+                    }
+                })
+                .collect();
+
+            let name = if schema == "main" {
+                table
+            } else {
+                format!("{schema}.{table}")
+            };
+            tables.push(SchemaTableInfo { name, columns });
+        }
+
+        // ── 4. Auto-detect join keys ──────────────────────────────────────────
+        let join_keys = detect_join_keys(&tables);
+
+        Ok(SchemaInfo { tables, join_keys })
+    }
+}
+
+/// `(schema, table)` as listed by `pragma_table_list`.
+type TableKey = (String, String);
+
+/// `(column, declared type)` as listed by `pragma_table_info`.
+type ColumnDecl = (String, String);
+
+/// Detect join keys: columns ending in `_id` that appear in multiple tables.
+fn detect_join_keys(tables: &[SchemaTableInfo]) -> Vec<(String, String, String)> {
+    let mut col_to_tables: HashMap<&str, Vec<&str>> = HashMap::new();
+    for t in tables {
+        for c in &t.columns {
+            if c.name.ends_with("_id") {
+                col_to_tables
+                    .entry(c.name.as_str())
+                    .or_default()
+                    .push(t.name.as_str());
+            }
+        }
+    }
+    let mut keys = Vec::new();
+    for (col, tbs) in col_to_tables {
+        for i in 0..tbs.len() {
+            for j in (i + 1)..tbs.len() {
+                keys.push((tbs[i].to_string(), tbs[j].to_string(), col.to_string()));
+            }
+        }
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aliases_are_unique_identifiers() {
+        let files: Vec<PathBuf> = ["a/main.db", "b/2024 sales.sqlite", "c/main.db"]
+            .into_iter()
+            .map(PathBuf::from)
+            .collect();
+        let aliases: Vec<String> = schema_aliases(&files)
+            .into_iter()
+            .map(|(alias, _)| alias)
+            .collect();
+        assert_eq!(aliases, vec!["main_2", "_2024_sales", "main_3"]);
+    }
+
+    #[test]
+    fn file_uri_escapes_uri_delimiters() {
+        assert_eq!(
+            file_uri(Path::new("/data/q?1#2.db"), true),
+            "file:/data/q%3f1%232.db?mode=ro"
+        );
+    }
+}
@@ -0,0 +1,246 @@
+//! Typed-row helpers for the SQLite backend.
+//!
+//! SQLite is dynamically typed: a column's declared type only sets its
+//! *affinity*, and any cell may still hold any storage class. Column types are
+//! therefore resolved from the values actually returned — the declared type is
+//! used when every value conforms to it, otherwise the type is inferred from
+//! the storage classes (see [`resolve_column_type`]).
+
+use agentic_core::result::{CellValue, TypedDataType, TypedValue};
+use rusqlite::types::Value;
+
+/// Map a declared column type to a [`TypedDataType`].
+///
+/// Well-known names (`BOOLEAN`, `DATE`, `DATETIME`, `TIMESTAMP`, `JSON`) map to
+/// their logical type; everything else follows SQLite's affinity rules
+/// (<https://www.sqlite.org/datatype3.html#determination_of_column_affinity>).
+/// `NUMERIC` / `DECIMAL` values are stored as INTEGER or REAL, so they are
+/// reported as `Float64` rather than an exact decimal.
+pub(crate) fn decl_type_to_typed(decl: &str) -> TypedDataType {
+    let upper = decl.trim().to_ascii_uppercase();
+    let base = upper.split('(').next().unwrap_or_default().trim();
+    match base {
+        "BOOL" | "BOOLEAN" => return TypedDataType::Bool,
+        "DATE" => return TypedDataType::Date,
+        "DATETIME" | "TIMESTAMP" => return TypedDataType::Timestamp,
+        "JSON" | "JSONB" => return TypedDataType::Json,
+        _ => {}
+    }
+    if upper.contains("INT") {
+        TypedDataType::Int64
+    } else if upper.contains("CHAR") || upper.contains("CLOB") || upper.contains("TEXT") {
+        TypedDataType::Text
+    } else if upper.is_empty() || upper.contains("BLOB") {
+        TypedDataType::Bytes
+    } else {
+        // REAL / FLOA / DOUB, and NUMERIC affinity.
+        TypedDataType::Float64
+    }
+}
+
+/// Storage-class name of a value, as reported by SQLite's `typeof()`.
+pub(crate) fn storage_class(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "NULL",
+        Value::Integer(_) => "INTEGER",
+        Value::Real(_) => "REAL",
+        Value::Text(_) => "TEXT",
+        Value::Blob(_) => "BLOB",
+    }
+}
+
+/// Infer a column type from the storage classes of its values: integers stay
+/// `Int64`, integers mixed with reals widen to `Float64`, blobs alone are
+/// `Bytes`, and any other mix is `Text`. All-NULL columns are `Unknown`.
+pub(crate) fn infer_from_values<'a>(values: impl Iterator<Item = &'a Value>) -> TypedDataType {
+    let (mut ints, mut reals, mut texts, mut blobs) = (false, false, false, false);
+    for value in values {
+        match value {
+            Value::Null => {}
+            Value::Integer(_) => ints = true,
+            Value::Real(_) => reals = true,
+            Value::Text(_) => texts = true,
+            Value::Blob(_) => blobs = true,
+        }
+    }
+    match (ints, reals, texts, blobs) {
+        (false, false, false, false) => TypedDataType::Unknown,
+        (true, false, false, false) => TypedDataType::Int64,
+        (_, true, false, false) => TypedDataType::Float64,
+        (false, false, false, true) => TypedDataType::Bytes,
+        _ => TypedDataType::Text,
+    }
+}
+
+/// Pick the column type for a fully materialised result column.
+///
+/// The declared type wins when every non-NULL value converts to it; otherwise
+/// (expression columns, or cells that ignore the affinity) the type is
+/// inferred from the values. A column with no values and no declared type is
+/// `Unknown`.
+pub(crate) fn resolve_column_type<'a>(
+    decl: Option<&str>,
+    values: impl Iterator<Item = &'a Value> + Clone,
+) -> TypedDataType {
+    if let Some(decl) = decl.filter(|d| !d.trim().is_empty()) {
+        let declared = decl_type_to_typed(decl);
+        if values
+            .clone()
+            .all(|v| value_to_typed(v, &declared).is_some())
+        {
+            return declared;
+        }
+    }
+    infer_from_values(values)
+}
+
+/// Convert a value to `ty`, or `None` when it does not conform.
+///
+/// `Text` and `Unknown` accept every storage class (blobs are decoded as lossy
+/// UTF-8), so an inferred type always converts.
+pub(crate) fn value_to_typed(value: &Value, ty: &TypedDataType) -> Option<TypedValue> {
+    if matches!(value, Value::Null) {
+        return Some(TypedValue::Null);
+    }
+    match (ty, value) {
+        (TypedDataType::Bool, Value::Integer(n)) if *n == 0 || *n == 1 => {
+            Some(TypedValue::Bool(*n == 1))
+        }
+        (TypedDataType::Bool, Value::Text(s)) => match s.to_ascii_lowercase().as_str() {
+            "true" | "t" => Some(TypedValue::Bool(true)),
+            "false" | "f" => Some(TypedValue::Bool(false)),
+            _ => None,
+        },
+        (TypedDataType::Int32, Value::Integer(n)) => i32::try_from(*n).ok().map(TypedValue::Int32),
+        (TypedDataType::Int64, Value::Integer(n)) => Some(TypedValue::Int64(*n)),
+        (TypedDataType::Float64, Value::Integer(n)) => Some(TypedValue::Float64(*n as f64)),
+        (TypedDataType::Float64, Value::Real(f)) => Some(TypedValue::Float64(*f)),
+        (TypedDataType::Bytes, Value::Blob(b)) => Some(TypedValue::Bytes(b.clone())),
+        (TypedDataType::Date, Value::Text(s)) => parse_date(s).map(TypedValue::Date),
+        (TypedDataType::Timestamp, Value::Text(s)) => parse_timestamp(s).map(TypedValue::Timestamp),
+        // Integer timestamps are unix seconds, as produced by `unixepoch()`.
+        (TypedDataType::Timestamp, Value::Integer(secs)) => {
+            secs.checked_mul(1_000_000).map(TypedValue::Timestamp)
+        }
+        (TypedDataType::Json, Value::Text(s)) => serde_json::from_str(s).ok().map(TypedValue::Json),
+        (TypedDataType::Text | TypedDataType::Unknown, v) => Some(TypedValue::Text(value_text(v))),
+        _ => None,
+    }
+}
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Integer(n) => n.to_string(),
+        Value::Real(f) => f.to_string(),
+        Value::Text(s) => s.clone(),
+        Value::Blob(b) => String::from_utf8_lossy(b).into_owned(),
+    }
+}
+
+fn unix_epoch() -> chrono::NaiveDate {
+    chrono::NaiveDate::from_ymd_opt(1970, 1, 1).expect("1970-01-01 valid")
+}
+
+/// `YYYY-MM-DD`, the format of SQLite's `date()`.
+fn parse_date(s: &str) -> Option<i32> {
+    let date = chrono::NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").ok()?;
+    Some((date - unix_epoch()).num_days() as i32)
+}
+
+/// ISO-8601 text as produced by `datetime()` / `strftime()`, with a `T` or
+/// space separator and optional fractional seconds. Values without an offset
+/// are taken as UTC, matching SQLite's own date functions.
+fn parse_timestamp(s: &str) -> Option<i64> {
+    let s = s.trim();
+    if let Ok(ts) = chrono::DateTime::parse_from_rfc3339(s) {
+        return Some(ts.timestamp_micros());
+    }
+    [
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M",
+    ]
+    .iter()
+    .find_map(|fmt| chrono::NaiveDateTime::parse_from_str(s, fmt).ok())
+    .map(|ts| ts.and_utc().timestamp_micros())
+}
+
+/// Collapse a value into the lossy [`CellValue`] the solver samples.
+pub(crate) fn value_to_cell(value: &Value) -> CellValue {
+    match value {
+        Value::Null => CellValue::Null,
+        Value::Integer(n) => CellValue::Number(*n as f64),
+        Value::Real(f) => CellValue::Number(*f),
+        Value::Text(s) => CellValue::Text(s.clone()),
+        Value::Blob(b) => CellValue::Text(format!(
+            "0x{}",
+            b.iter().map(|b| format!("{b:02X}")).collect::<String>()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decl_types_follow_affinity() {
+        assert_eq!(decl_type_to_typed("INTEGER"), TypedDataType::Int64);
+        assert_eq!(decl_type_to_typed("bigint"), TypedDataType::Int64);
+        assert_eq!(decl_type_to_typed("VARCHAR(255)"), TypedDataType::Text);
+        assert_eq!(
+            decl_type_to_typed("DOUBLE PRECISION"),
+            TypedDataType::Float64
+        );
+        assert_eq!(decl_type_to_typed("DECIMAL(10,2)"), TypedDataType::Float64);
+        assert_eq!(decl_type_to_typed("BLOB"), TypedDataType::Bytes);
+        assert_eq!(decl_type_to_typed(""), TypedDataType::Bytes);
+        assert_eq!(decl_type_to_typed("boolean"), TypedDataType::Bool);
+        assert_eq!(decl_type_to_typed("DATE"), TypedDataType::Date);
+        assert_eq!(decl_type_to_typed("DATETIME"), TypedDataType::Timestamp);
+    }
+
+    #[test]
+    fn declared_type_yields_to_nonconforming_values() {
+        let values = [Value::Integer(1), Value::Text("n/a".into())];
+        assert_eq!(
+            resolve_column_type(Some("INTEGER"), values.iter()),
+            TypedDataType::Text
+        );
+        let values = [Value::Integer(1), Value::Null];
+        assert_eq!(
+            resolve_column_type(Some("INTEGER"), values.iter()),
+            TypedDataType::Int64
+        );
+        let values = [Value::Integer(1), Value::Real(2.5)];
+        assert_eq!(
+            resolve_column_type(None, values.iter()),
+            TypedDataType::Float64
+        );
+        assert_eq!(resolve_column_type(None, [].iter()), TypedDataType::Unknown);
+    }
+
+    #[test]
+    fn temporal_values_decode() {
+        assert_eq!(
+            value_to_typed(&Value::Text("1970-01-11".into()), &TypedDataType::Date),
+            Some(TypedValue::Date(10))
+        );
+        assert_eq!(
+            value_to_typed(
+                &Value::Text("1970-01-01 00:00:01.5".into()),
+                &TypedDataType::Timestamp
+            ),
+            Some(TypedValue::Timestamp(1_500_000))
+        );
+        assert_eq!(
+            value_to_typed(&Value::Integer(2), &TypedDataType::Timestamp),
+            Some(TypedValue::Timestamp(2_000_000))
+        );
+        assert_eq!(
+            value_to_typed(&Value::Text("yesterday".into()), &TypedDataType::Date),
+            None
+        );
+    }
+}
@@ -0,0 +1,669 @@
+//! Trino / Presto connector implementation via the HTTP client protocol.
+//!
+//! A query is a `POST /v1/statement` with the SQL as the body. The
+//! coordinator answers with a `QueryResults` document:
+//!
+//! ```json
+//! {"id":"…","nextUri":"http://…/v1/statement/executing/…/1",
+//!  "columns":[{"name":"n","type":"bigint"}],"data":[[1],[2]],
+//!  "stats":{"state":"RUNNING"}}
+//! ```
+//!
+//! `columns` and `data` may arrive on any page, so the client keeps `GET`ting
+//! `nextUri` until it disappears. A [`Cursor`] hands out one page of `data`
+//! at a time: the sample and stats queries are bounded and collect every
+//! page, while `execute_query_full` streams pages as it decodes them. An
+//! `error` object on any page fails the query.
+//!
+//! Trino accepts CTEs and `ORDER BY` inside derived tables and lets a derived
+//! table rename its columns, so stats use a single aggregate over
+//! `({sql}) AS oxy_q(c0, c1, …)` — positional aliases side-step duplicate or
+//! unnamed (`_col0`) output columns.
+//!
+//! Schema is introspected from `<catalog>.information_schema.columns` for
+//! every configured catalog and cached at construction. Tables are reported
+//! fully qualified (`catalog.schema.table`).
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value;
+
+use agentic_core::result::{
+    CellValue, ColumnSpec, QueryResult, QueryRow, TypedDataType, TypedRowError, TypedRowStream,
+    TypedValue,
+};
+
+use crate::config::TrinoConfig;
+use crate::connector::{
+    ColumnStats, ConnectorError, DatabaseConnector, ExecutionResult, ResultSummary,
+    SchemaColumnInfo, SchemaInfo, SchemaTableInfo, SqlDialect, normalize_sql,
+};
+use crate::trino_typed::{parse_trino_cell, trino_type_to_typed};
+
+/// Give up on a query after this many consecutive 502/503/504 responses.
+const MAX_BUSY_RETRIES: u32 = 20;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Limit for one request. Trino answers each page within a few seconds even
+/// while the query runs, so this doesn't bound the query itself.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+// ── HTTP response types ────────────────────────────────────────────────────────
+
+/// One page of the Trino `QueryResults` document.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TrinoPage {
+    #[serde(default)]
+    next_uri: Option<String>,
+    #[serde(default)]
+    columns: Option<Vec<TrinoColumn>>,
+    #[serde(default)]
+    data: Option<Vec<Vec<Value>>>,
+    #[serde(default)]
+    error: Option<TrinoError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrinoColumn {
+    name: String,
+    r#type: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TrinoError {
+    message: String,
+    #[serde(default)]
+    error_name: Option<String>,
+}
+
+/// Fully drained result of a bounded query: column metadata plus every data
+/// page.
+#[derive(Debug, Default)]
+struct TrinoResult {
+    columns: Vec<TrinoColumn>,
+    data: Vec<Vec<Value>>,
+}
+
+// ── Type classification ────────────────────────────────────────────────────────
+
+/// Which aggregates a column supports inside the stats query.
+#[derive(Debug, PartialEq, Eq)]
+enum TypeCategory {
+    /// Numbers — MIN/MAX plus AVG/STDDEV_POP.
+    Numeric,
+    /// Orderable scalars (text, dates, timestamps, booleans) — MIN/MAX only.
+    Orderable,
+    /// `json`, `array`, `map`, `row`, `varbinary`, … — NULL count only.
+    Other,
+}
+
+fn trino_type_category(raw: &str) -> TypeCategory {
+    match trino_type_to_typed(raw) {
+        TypedDataType::Int32
+        | TypedDataType::Int64
+        | TypedDataType::Float64
+        | TypedDataType::Decimal { .. } => TypeCategory::Numeric,
+        TypedDataType::Bool
+        | TypedDataType::Text
+        | TypedDataType::Date
+        | TypedDataType::Timestamp => TypeCategory::Orderable,
+        TypedDataType::Bytes | TypedDataType::Json | TypedDataType::Unknown => TypeCategory::Other,
+    }
+}
+
+// ── Value converter ────────────────────────────────────────────────────────────
+
+/// Convert a JSON cell from a Trino data page into a [`CellValue`], using the
+/// column's declared Trino type.
+fn json_to_cell(v: &Value, trino_type: &str) -> CellValue {
+    match v {
+        Value::Null => CellValue::Null,
+        Value::Number(n) => CellValue::Number(n.as_f64().unwrap_or(0.0)),
+        Value::Bool(b) => CellValue::Text(b.to_string()),
+        // Decimals arrive as strings. Only numeric columns are parsed so a
+        // varchar holding "00123" stays text.
+        Value::String(s) if trino_type_category(trino_type) == TypeCategory::Numeric => {
+            match s.parse::<f64>() {
+                Ok(n) if n.is_finite() => CellValue::Number(n),
+                _ => CellValue::Text(s.clone()),
+            }
+        }
+        Value::String(s) => CellValue::Text(s.clone()),
+        other => CellValue::Text(other.to_string()),
+    }
+}
+
+fn json_as_u64(v: Option<&Value>) -> u64 {
+    v.and_then(Value::as_u64).unwrap_or(0)
+}
+
+fn json_as_f64(v: Option<&Value>) -> Option<f64> {
+    match v? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+// ── Connector ─────────────────────────────────────────────────────────────────
+
+/// Trino connector that speaks the HTTP client protocol.
+pub struct TrinoConnector {
+    client: reqwest::Client,
+    config: TrinoConfig,
+    cached_schema: SchemaInfo,
+}
+
+impl TrinoConnector {
+    /// Connect to a Trino coordinator and pre-fetch the schema of every
+    /// configured catalog.
+    pub async fn new(config: TrinoConfig) -> Result<Self, ConnectorError> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| ConnectorError::ConnectionError(format!("http client build: {e}")))?;
+        let cached_schema = fetch_schema(&client, &config).await.unwrap_or_else(|e| {
+            tracing::warn!("trino: schema prefetch failed ({e}); schema browsing unavailable");
+            SchemaInfo::default()
+        });
+        Ok(Self {
+            client,
+            config,
+            cached_schema,
+        })
+    }
+
+    async fn http_query(&self, sql: &str) -> Result<TrinoResult, ConnectorError> {
+        http_query(&self.client, &self.config, sql).await
+    }
+}
+
+// ── HTTP helper ────────────────────────────────────────────────────────────────
+
+/// A submitted query, read one `nextUri` page at a time.
+///
+/// Owns its client and config so `execute_query_full` can move it into a
+/// `'static` row stream. A cursor dropped early just stops polling; Trino
+/// abandons the query once the client timeout passes.
+struct Cursor {
+    client: reqwest::Client,
+    config: TrinoConfig,
+    sql: String,
+    columns: Vec<TrinoColumn>,
+    /// Rows fetched before the columns were known.
+    buffered: Vec<Vec<Value>>,
+    next_uri: Option<String>,
+}
+
+impl Cursor {
+    /// Submit `sql` and follow `nextUri` until the column metadata arrives.
+    async fn submit(
+        client: &reqwest::Client,
+        config: &TrinoConfig,
+        sql: &str,
+    ) -> Result<Self, ConnectorError> {
+        let submit = with_headers(
+            client.post(format!("{}/v1/statement", config.url.trim_end_matches('/'))),
+            config,
+        )
+        .body(sql.to_string());
+        let page = fetch_page(submit, sql).await?;
+
+        let mut cursor = Self {
+            client: client.clone(),
+            config: config.clone(),
+            sql: sql.to_string(),
+            columns: Vec::new(),
+            buffered: Vec::new(),
+            next_uri: None,
+        };
+        let data = cursor.absorb(page)?;
+        cursor.buffered.extend(data);
+        while cursor.columns.is_empty()
+            && let Some(next) = cursor.next_uri.take()
+        {
+            let data = cursor.fetch(&next).await?;
+            cursor.buffered.extend(data);
+        }
+        Ok(cursor)
+    }
+
+    /// The next non-empty chunk of rows, or `None` once the query finished.
+    async fn next_chunk(&mut self) -> Result<Option<Vec<Vec<Value>>>, ConnectorError> {
+        if !self.buffered.is_empty() {
+            return Ok(Some(std::mem::take(&mut self.buffered)));
+        }
+        while let Some(next) = self.next_uri.take() {
+            let data = self.fetch(&next).await?;
+            if !data.is_empty() {
+                return Ok(Some(data));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn fetch(&mut self, uri: &str) -> Result<Vec<Vec<Value>>, ConnectorError> {
+        let page = fetch_page(with_headers(self.client.get(uri), &self.config), &self.sql).await?;
+        self.absorb(page)
+    }
+
+    /// Record a page's columns and `nextUri`, returning its rows.
+    fn absorb(&mut self, page: TrinoPage) -> Result<Vec<Vec<Value>>, ConnectorError> {
+        if let Some(err) = page.error {
+            let name = err.error_name.map(|n| format!("{n}: ")).unwrap_or_default();
+            return Err(query_failed(&self.sql, format!("{name}{}", err.message)));
+        }
+        if self.columns.is_empty()
+            && let Some(columns) = page.columns
+        {
+            self.columns = columns;
+        }
+        self.next_uri = page.next_uri;
+        Ok(page.data.unwrap_or_default())
+    }
+}
+
+/// Run a bounded query (sample, stats, introspection) and collect every page.
+async fn http_query(
+    client: &reqwest::Client,
+    config: &TrinoConfig,
+    sql: &str,
+) -> Result<TrinoResult, ConnectorError> {
+    let mut cursor = Cursor::submit(client, config, sql).await?;
+    let mut data = Vec::new();
+    while let Some(chunk) = cursor.next_chunk().await? {
+        data.extend(chunk);
+    }
+    Ok(TrinoResult {
+        columns: std::mem::take(&mut cursor.columns),
+        data,
+    })
+}
+
+fn query_failed(sql: &str, message: String) -> ConnectorError {
+    ConnectorError::QueryFailed {
+        sql: sql.to_string(),
+        message,
+    }
+}
+
+/// Attach identity, session and auth headers shared by every request.
+fn with_headers(req: reqwest::RequestBuilder, config: &TrinoConfig) -> reqwest::RequestBuilder {
+    let mut req = req
+        .header("X-Trino-User", &config.user)
+        .header("X-Trino-Source", "oxy")
+        .header("X-Trino-Catalog", &config.catalog)
+        // Pin rendering of `timestamp with time zone` to UTC so values can be
+        // decoded without a tz database — see trino_typed.
+        .header("X-Trino-Time-Zone", "UTC");
+    if let Some(schema) = &config.schema {
+        req = req.header("X-Trino-Schema", schema);
+    }
+    // Trino rejects passwords over plain HTTP, so only send one when set.
+    if !config.password.is_empty() {
+        req = req.basic_auth(&config.user, Some(&config.password));
+    }
+    req
+}
+
+/// Send one request, retrying while the coordinator reports it is busy.
+async fn fetch_page(req: reqwest::RequestBuilder, sql: &str) -> Result<TrinoPage, ConnectorError> {
+    let failed = |message: String| query_failed(sql, message);
+    let mut attempt = 0;
+    loop {
+        let this_try = req
+            .try_clone()
+            .ok_or_else(|| failed("request body is not cloneable".into()))?;
+        let response = this_try.send().await.map_err(|e| failed(e.to_string()))?;
+        let status = response.status();
+
+        if matches!(status.as_u16(), 502..=504) && attempt < MAX_BUSY_RETRIES {
+            attempt += 1;
+            tokio::time::sleep(Duration::from_millis(50 * u64::from(attempt))).await;
+            continue;
+        }
+
+        let text = response.text().await.map_err(|e| failed(e.to_string()))?;
+        if !status.is_success() {
+            return Err(failed(format!("HTTP {status}: {text}")));
+        }
+        return serde_json::from_str::<TrinoPage>(&text)
+            .map_err(|e| failed(format!("JSON parse error: {e}\nResponse: {text}")));
+    }
+}
+
+// ── DatabaseConnector impl ────────────────────────────────────────────────────
+
+#[async_trait]
+impl DatabaseConnector for TrinoConnector {
+    fn dialect(&self) -> SqlDialect {
+        SqlDialect::Other("Trino")
+    }
+
+    async fn execute_query(
+        &self,
+        sql: &str,
+        sample_limit: u64,
+    ) -> Result<ExecutionResult, ConnectorError> {
+        let sql = normalize_sql(sql);
+
+        // 1. Sample rows. Column metadata is returned even for empty results.
+        let sample_sql = format!("SELECT * FROM ({sql}) AS oxy_q LIMIT {sample_limit}");
+        let sample = self.http_query(&sample_sql).await?;
+
+        let column_names: Vec<String> = sample.columns.iter().map(|c| c.name.clone()).collect();
+        let col_count = column_names.len();
+        let sample_rows: Vec<QueryRow> = sample
+            .data
+            .iter()
+            .map(|row| {
+                QueryRow(
+                    sample
+                        .columns
+                        .iter()
+                        .enumerate()
+                        .map(|(i, col)| {
+                            row.get(i)
+                                .map(|v| json_to_cell(v, &col.r#type))
+                                .unwrap_or(CellValue::Null)
+                        })
+                        .collect(),
+                )
+            })
+            .collect();
+
+        // 2. Row count + per-column stats in one aggregate.
+        let aliases: Vec<String> = (0..col_count).map(|i| format!("c{i}")).collect();
+        let mut exprs = vec!["count(*)".to_string()];
+        for (alias, col) in aliases.iter().zip(&sample.columns) {
+            exprs.push(format!("count_if({alias} IS NULL)"));
+            match trino_type_category(&col.r#type) {
+                TypeCategory::Numeric => exprs.extend([
+                    format!("count(DISTINCT {alias})"),
+                    format!("CAST(min({alias}) AS varchar)"),
+                    format!("CAST(max({alias}) AS varchar)"),
+                    format!("avg(CAST({alias} AS double))"),
+                    format!("stddev_pop(CAST({alias} AS double))"),
+                ]),
+                TypeCategory::Orderable => exprs.extend([
+                    format!("count(DISTINCT {alias})"),
+                    format!("CAST(min({alias}) AS varchar)"),
+                    format!("CAST(max({alias}) AS varchar)"),
+                    "NULL".to_string(),
+                    "NULL".to_string(),
+                ]),
+                TypeCategory::Other => {
+                    exprs.extend(std::iter::repeat_n("NULL".to_string(), 5));
+                }
+            }
+        }
+        let stats_sql = format!(
+            "SELECT {} FROM ({sql}) AS oxy_q({})",
+            exprs.join(", "),
+            aliases.join(", ")
+        );
+        let stats = self.http_query(&stats_sql).await?;
+        let stat_row = stats.data.first().cloned().unwrap_or_default();
+
+        let total_row_count = json_as_u64(stat_row.first());
+        let col_stats: Vec<ColumnStats> = sample
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(idx, col)| {
+                let base = 1 + idx * 6;
+                let has_distinct = trino_type_category(&col.r#type) != TypeCategory::Other;
+                let cell = |offset: usize| {
+                    stat_row
+                        .get(base + offset)
+                        .map(|v| json_to_cell(v, &col.r#type))
+                        .filter(|c| !matches!(c, CellValue::Null))
+                };
+                ColumnStats {
+                    name: col.name.clone(),
+                    data_type: Some(col.r#type.clone()),
+                    null_count: json_as_u64(stat_row.get(base)),
+                    distinct_count: has_distinct.then(|| json_as_u64(stat_row.get(base + 1))),
+                    min: cell(2),
+                    max: cell(3),
+                    mean: json_as_f64(stat_row.get(base + 4)),
+                    std_dev: json_as_f64(stat_row.get(base + 5)),
+                }
+            })
+            .collect();
+
+        let truncated = (sample_rows.len() as u64) < total_row_count;
+        Ok(ExecutionResult {
+            result: QueryResult {
+                columns: column_names,
+                rows: sample_rows,
+                total_row_count,
+                truncated,
+            },
+            summary: ResultSummary {
+                row_count: total_row_count,
+                columns: col_stats,
+            },
+        })
+    }
+
+    async fn execute_query_full(&self, sql: &str) -> Result<TypedRowStream, ConnectorError> {
+        let sql = normalize_sql(sql);
+        // The query runs as-is; rows are decoded page by page as the stream
+        // is polled, with the column types from the first page that has them.
+        let mut cursor = Cursor::submit(&self.client, &self.config, sql).await?;
+
+        let columns: Vec<ColumnSpec> = cursor
+            .columns
+            .iter()
+            .map(|c| ColumnSpec {
+                name: c.name.clone(),
+                data_type: trino_type_to_typed(&c.r#type),
+            })
+            .collect();
+
+        let stream_columns = columns.clone();
+        let rows = async_stream::stream! {
+            loop {
+                match cursor.next_chunk().await {
+                    Ok(Some(chunk)) => {
+                        for row in chunk {
+                            yield decode_row(&row, &stream_columns);
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(e) => {
+                        yield Err(TypedRowError::DriverError(e.to_string()));
+                        break;
+                    }
+                }
+            }
+        };
+
+        Ok(TypedRowStream {
+            columns,
+            rows: Box::pin(rows),
+        })
+    }
+
+    fn introspect_schema(&self) -> Result<SchemaInfo, ConnectorError> {
+        Ok(self.cached_schema.clone())
+    }
+}
+
+/// Decode one data row with the declared column types.
+fn decode_row(row: &[Value], columns: &[ColumnSpec]) -> Result<Vec<TypedValue>, TypedRowError> {
+    columns
+        .iter()
+        .enumerate()
+        .map(|(idx, col)| parse_trino_cell(row.get(idx).unwrap_or(&Value::Null), col))
+        .collect()
+}
+
+// ── Schema pre-fetch ──────────────────────────────────────────────────────────
+
+/// Query `information_schema.columns` in every configured catalog and build
+/// a [`SchemaInfo`].
+async fn fetch_schema(
+    client: &reqwest::Client,
+    config: &TrinoConfig,
+) -> Result<SchemaInfo, ConnectorError> {
+    let catalogs = if config.catalogs.is_empty() {
+        std::slice::from_ref(&config.catalog)
+    } else {
+        config.catalogs.as_slice()
+    };
+
+    // BTreeMap keeps tables sorted by their qualified name.
+    let mut map: BTreeMap<String, Vec<SchemaColumnInfo>> = BTreeMap::new();
+    for catalog in catalogs {
+        let quoted = format!("\"{}\"", catalog.replace('"', "\"\""));
+        let schema_sql = format!(
+            "SELECT table_schema, table_name, column_name, data_type \
+             FROM {quoted}.information_schema.columns \
+             WHERE table_schema <> 'information_schema' \
+             ORDER BY table_schema, table_name, ordinal_position"
+        );
+        let resp = http_query(client, config, &schema_sql).await?;
+
+        for row in &resp.data {
+            let (Some(Value::String(schema)), Some(Value::String(table))) =
+                (row.first(), row.get(1))
+            else {
+                continue;
+            };
+            let Some(Value::String(column)) = row.get(2) else {
+                continue;
+            };
+            let data_type = match row.get(3) {
+                Some(Value::String(s)) => s.clone(),
+                _ => String::new(),
+            };
+            map.entry(format!("{catalog}.{schema}.{table}"))
+                .or_default()
+                .push(SchemaColumnInfo {
+                    name: column.clone(),
+                    data_type,
+                    min: None,
+                    max: None,
+                    sample_values: vec![],
+                });
+        }
+    }
+
+    let tables: Vec<SchemaTableInfo> = map
+        .into_iter()
+        .map(|(name, columns)| SchemaTableInfo { name, columns })
+        .collect();
+
+    let join_keys = detect_join_keys(&tables);
+    Ok(SchemaInfo { tables, join_keys })
+}
+
+// ── Join key detection ────────────────────────────────────────────────────────
+
+/// Auto-detect join keys: any column ending in `_id` shared across two tables.
+fn detect_join_keys(tables: &[SchemaTableInfo]) -> Vec<(String, String, String)> {
+    let mut col_to_tables: HashMap<&str, Vec<&str>> = HashMap::new();
+    for t in tables {
+        for c in &t.columns {
+            if c.name.ends_with("_id") {
+                col_to_tables
+                    .entry(c.name.as_str())
+                    .or_default()
+                    .push(t.name.as_str());
+            }
+        }
+    }
+    let mut keys = Vec::new();
+    for (col, tbs) in col_to_tables {
+        for i in 0..tbs.len() {
+            for j in (i + 1)..tbs.len() {
+                keys.push((tbs[i].to_string(), tbs[j].to_string(), col.to_string()));
+            }
+        }
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn type_category_routing() {
+        assert_eq!(trino_type_category("bigint"), TypeCategory::Numeric);
+        assert_eq!(trino_type_category("decimal(10,2)"), TypeCategory::Numeric);
+        assert_eq!(trino_type_category("varchar(20)"), TypeCategory::Orderable);
+        assert_eq!(
+            trino_type_category("timestamp(3) with time zone"),
+            TypeCategory::Orderable
+        );
+        assert_eq!(trino_type_category("array(integer)"), TypeCategory::Other);
+        assert_eq!(trino_type_category("json"), TypeCategory::Other);
+        assert_eq!(trino_type_category("varbinary"), TypeCategory::Other);
+    }
+
+    #[test]
+    fn page_parses_paging_fields() {
+        let page: TrinoPage = serde_json::from_str(
+            r#"{"id":"q1","nextUri":"http://t/v1/statement/executing/q1/2",
+                "columns":[{"name":"n","type":"bigint","typeSignature":{}}],
+                "data":[[1],[2]],"stats":{"state":"RUNNING"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            page.next_uri.as_deref(),
+            Some("http://t/v1/statement/executing/q1/2")
+        );
+        assert_eq!(page.columns.unwrap()[0].r#type, "bigint");
+        assert_eq!(page.data.unwrap().len(), 2);
+        assert!(page.error.is_none());
+    }
+
+    #[test]
+    fn page_parses_error() {
+        let page: TrinoPage = serde_json::from_str(
+            r#"{"id":"q1","stats":{"state":"FAILED"},
+                "error":{"message":"line 1:15: Table 'x' does not exist",
+                         "errorCode":46,"errorName":"TABLE_NOT_FOUND","errorType":"USER_ERROR"}}"#,
+        )
+        .unwrap();
+        let err = page.error.unwrap();
+        assert_eq!(err.error_name.as_deref(), Some("TABLE_NOT_FOUND"));
+        assert!(page.next_uri.is_none());
+    }
+
+    #[test]
+    fn json_to_cell_keeps_decimal_strings_numeric() {
+        assert_eq!(
+            json_to_cell(&Value::String("12.50".into()), "decimal(10,2)"),
+            CellValue::Number(12.5)
+        );
+        assert_eq!(
+            json_to_cell(&Value::String("NaN".into()), "double"),
+            CellValue::Text("NaN".into())
+        );
+        assert_eq!(
+            json_to_cell(&Value::String("2024-01-01".into()), "date"),
+            CellValue::Text("2024-01-01".into())
+        );
+    }
+
+    #[test]
+    fn json_to_cell_keeps_numeric_looking_varchar_as_text() {
+        assert_eq!(
+            json_to_cell(&Value::String("00123".into()), "varchar(10)"),
+            CellValue::Text("00123".into())
+        );
+        assert_eq!(
+            json_to_cell(&Value::String("1e3".into()), "varchar"),
+            CellValue::Text("1e3".into())
+        );
+    }
+}
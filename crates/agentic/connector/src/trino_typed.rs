@@ -0,0 +1,391 @@
+//! Row-oriented typed conversion helpers for the Trino backend.
+//!
+//! The Trino client protocol returns column metadata as type-signature
+//! strings (`varchar(32)`, `decimal(10,2)`, `timestamp(3) with time zone`,
+//! `array(row(a integer))`, …) and each row as `Vec<serde_json::Value>`.
+//! This module translates those into [`TypedDataType`] / [`TypedValue`] for
+//! [`execute_query_full`].
+//!
+//! Wire encodings worth knowing:
+//! - `bigint` / `integer` / `boolean` arrive as JSON numbers / bools.
+//! - `real` / `double` arrive as numbers, except `"NaN"` / `"Infinity"`.
+//! - `decimal` arrives as a string so precision survives.
+//! - `varbinary` arrives base64-encoded.
+//! - `json` arrives as a string holding the JSON text.
+//! - `array` / `map` / `row` arrive as JSON arrays / objects.
+//! - Temporal types arrive as strings rendered in the session time zone,
+//!   which the connector pins to UTC.
+
+use agentic_core::result::{ColumnSpec, TypedDataType, TypedRowError, TypedValue};
+use base64::Engine;
+use serde_json::Value;
+
+// ── Type mapping: Trino type signature → TypedDataType ──────────────────────
+
+/// Parse a Trino type signature (as returned in `columns[].type` or by
+/// `information_schema.columns.data_type`) into a [`TypedDataType`].
+pub(crate) fn trino_type_to_typed(type_str: &str) -> TypedDataType {
+    let t = type_str.trim().to_ascii_lowercase();
+    // First word before any parameters: `timestamp(3) with time zone` and
+    // `interval day to second` both reduce to their leading keyword.
+    let base = t.split(['(', ' ']).next().unwrap_or(&t);
+
+    match base {
+        "boolean" => TypedDataType::Bool,
+        "tinyint" | "smallint" | "integer" => TypedDataType::Int32,
+        "bigint" => TypedDataType::Int64,
+        "real" | "double" => TypedDataType::Float64,
+        "decimal" => parse_decimal(&t),
+        "varchar" | "char" | "uuid" | "ipaddress" | "time" | "interval" => TypedDataType::Text,
+        "varbinary" => TypedDataType::Bytes,
+        "date" => TypedDataType::Date,
+        "timestamp" => TypedDataType::Timestamp,
+        "json" | "array" | "map" | "row" => TypedDataType::Json,
+        _ => TypedDataType::Unknown,
+    }
+}
+
+/// Parse `decimal(p,s)` / `decimal(p)`; Trino's bare `decimal` is `(38, 0)`.
+fn parse_decimal(t: &str) -> TypedDataType {
+    let inside = t
+        .split_once('(')
+        .and_then(|(_, rest)| rest.strip_suffix(')'))
+        .unwrap_or("");
+    let mut parts = inside.split(',').map(str::trim);
+    let precision = parts
+        .next()
+        .and_then(|s| s.parse::<u8>().ok())
+        .unwrap_or(38);
+    let scale = parts.next().and_then(|s| s.parse::<i8>().ok()).unwrap_or(0);
+    TypedDataType::Decimal { precision, scale }
+}
+
+// ── JSON cell → TypedValue ───────────────────────────────────────────────────
+
+/// Decode a single cell from a Trino `data` page into a [`TypedValue`].
+pub(crate) fn parse_trino_cell(
+    value: &Value,
+    col: &ColumnSpec,
+) -> Result<TypedValue, TypedRowError> {
+    if value.is_null() {
+        return Ok(TypedValue::Null);
+    }
+
+    fn mapping_err(
+        col: &ColumnSpec,
+        value: &Value,
+        detail: impl std::fmt::Display,
+    ) -> TypedRowError {
+        TypedRowError::TypeMappingError {
+            column: col.name.clone(),
+            native_type: format!("{:?}", col.data_type),
+            message: format!("could not decode '{value}': {detail}"),
+        }
+    }
+
+    match &col.data_type {
+        TypedDataType::Bool => value
+            .as_bool()
+            .map(TypedValue::Bool)
+            .ok_or_else(|| mapping_err(col, value, "expected bool")),
+        TypedDataType::Int32 => value
+            .as_i64()
+            .and_then(|n| i32::try_from(n).ok())
+            .map(TypedValue::Int32)
+            .ok_or_else(|| mapping_err(col, value, "not a 32-bit integer")),
+        TypedDataType::Int64 => value
+            .as_i64()
+            .map(TypedValue::Int64)
+            .ok_or_else(|| mapping_err(col, value, "not a 64-bit integer")),
+        TypedDataType::Float64 => match value {
+            Value::Number(n) => n.as_f64().map(TypedValue::Float64),
+            Value::String(s) => match s.as_str() {
+                "NaN" => Some(TypedValue::Float64(f64::NAN)),
+                "Infinity" => Some(TypedValue::Float64(f64::INFINITY)),
+                "-Infinity" => Some(TypedValue::Float64(f64::NEG_INFINITY)),
+                other => other.parse().ok().map(TypedValue::Float64),
+            },
+            _ => None,
+        }
+        .ok_or_else(|| mapping_err(col, value, "not a number")),
+        TypedDataType::Text => match value {
+            Value::String(s) => Ok(TypedValue::Text(s.clone())),
+            other => Ok(TypedValue::Text(other.to_string())),
+        },
+        TypedDataType::Bytes => value
+            .as_str()
+            .ok_or_else(|| mapping_err(col, value, "expected base64 string"))
+            .and_then(|s| {
+                base64::engine::general_purpose::STANDARD
+                    .decode(s)
+                    .map(TypedValue::Bytes)
+                    .map_err(|e| mapping_err(col, value, format!("invalid base64: {e}")))
+            }),
+        TypedDataType::Date => value
+            .as_str()
+            .and_then(parse_date)
+            .map(TypedValue::Date)
+            .ok_or_else(|| mapping_err(col, value, "expected YYYY-MM-DD")),
+        TypedDataType::Timestamp => value
+            .as_str()
+            .ok_or_else(|| mapping_err(col, value, "expected timestamp string"))
+            .and_then(|s| {
+                parse_timestamp_micros(s)
+                    .map(TypedValue::Timestamp)
+                    .map_err(|detail| mapping_err(col, value, detail))
+            }),
+        TypedDataType::Decimal { .. } => match value {
+            Value::String(s) => Ok(TypedValue::Decimal(s.clone())),
+            Value::Number(n) => Ok(TypedValue::Decimal(n.to_string())),
+            _ => Err(mapping_err(col, value, "expected decimal string")),
+        },
+        // `json` columns carry the document as a string; composites are
+        // already structured.
+        TypedDataType::Json => match value {
+            Value::String(s) => Ok(TypedValue::Json(
+                serde_json::from_str(s).unwrap_or_else(|_| value.clone()),
+            )),
+            other => Ok(TypedValue::Json(other.clone())),
+        },
+        TypedDataType::Unknown => match value {
+            Value::String(s) => Ok(TypedValue::Text(s.clone())),
+            other => Ok(TypedValue::Text(other.to_string())),
+        },
+    }
+}
+
+// ── Date / timestamp parsing (dependency-free, mirrors clickhouse_typed) ────
+
+fn parse_date(s: &str) -> Option<i32> {
+    let (y, m, d) = split_ymd(s)?;
+    Some(days_from_civil(y, m, d))
+}
+
+/// Parse `YYYY-MM-DD HH:MM:SS[.fff…][ zone]` into UTC microseconds.
+///
+/// `zone` may be `UTC`, `Z` or a `±HH:MM` offset. Named zones
+/// (`America/New_York`) would need a tz database, so they are rejected with a
+/// hint instead of being silently misread.
+fn parse_timestamp_micros(s: &str) -> Result<i64, String> {
+    let bad = || format!("expected YYYY-MM-DD HH:MM:SS[.fff], got '{s}'");
+    let mut parts = s.trim().splitn(3, ' ');
+    let date_part = parts.next().ok_or_else(bad)?;
+    let time_part = parts.next();
+    let zone = parts.next();
+
+    let (y, m, d) = split_ymd(date_part).ok_or_else(bad)?;
+    let days = days_from_civil(y, m, d) as i64;
+    let sod_micros = match time_part {
+        None => 0i64,
+        Some(t) => parse_time_micros(t).ok_or_else(bad)?,
+    };
+    let offset_micros = match zone {
+        None | Some("UTC") | Some("Z") => 0,
+        Some(z) => parse_offset_micros(z).ok_or_else(|| {
+            format!("time zone '{z}' is not an offset; cast with AT TIME ZONE 'UTC'")
+        })?,
+    };
+    Ok(days * 86_400 * 1_000_000 + sod_micros - offset_micros)
+}
+
+fn parse_offset_micros(z: &str) -> Option<i64> {
+    let (sign, rest) = match z.as_bytes().first()? {
+        b'+' => (1, &z[1..]),
+        b'-' => (-1, &z[1..]),
+        _ => return None,
+    };
+    let (h, m) = rest.split_once(':')?;
+    let h: i64 = h.parse().ok()?;
+    let m: i64 = m.parse().ok()?;
+    Some(sign * (h * 3_600_000_000 + m * 60_000_000))
+}
+
+fn parse_time_micros(s: &str) -> Option<i64> {
+    let mut parts = s.splitn(3, ':');
+    let h: i64 = parts.next()?.parse().ok()?;
+    let m: i64 = parts.next()?.parse().ok()?;
+    let sec_raw = parts.next()?;
+
+    let (sec_i, frac_us) = match sec_raw.split_once('.') {
+        Some((whole, frac)) => {
+            let sec_i: i64 = whole.parse().ok()?;
+            // Trino timestamps go up to picosecond precision; keep micros.
+            let frac_truncated: String = frac.chars().take(6).collect();
+            let frac_padded = format!("{frac_truncated:0<6}");
+            let frac_us: i64 = frac_padded.parse().ok()?;
+            (sec_i, frac_us)
+        }
+        None => (sec_raw.parse().ok()?, 0i64),
+    };
+
+    Some(h * 3_600_000_000 + m * 60_000_000 + sec_i * 1_000_000 + frac_us)
+}
+
+fn split_ymd(s: &str) -> Option<(i64, u32, u32)> {
+    let mut parts = s.splitn(3, '-');
+    let y: i64 = parts.next()?.parse().ok()?;
+    let m: u32 = parts.next()?.parse().ok()?;
+    let d: u32 = parts.next()?.parse().ok()?;
+    Some((y, m, d))
+}
+
+fn days_from_civil(y: i64, m: u32, d: u32) -> i32 {
+    let (y, m) = if m <= 2 { (y - 1, m + 12) } else { (y, m) };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = (y - era * 400) as u32;
+    let doy = (153 * (m - 3) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    (era * 146_097 + doe as i64 - 719_468) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn col(data_type: TypedDataType) -> ColumnSpec {
+        ColumnSpec {
+            name: "c".into(),
+            data_type,
+        }
+    }
+
+    #[test]
+    fn type_mapping_scalars() {
+        assert_eq!(trino_type_to_typed("boolean"), TypedDataType::Bool);
+        assert_eq!(trino_type_to_typed("integer"), TypedDataType::Int32);
+        assert_eq!(trino_type_to_typed("bigint"), TypedDataType::Int64);
+        assert_eq!(trino_type_to_typed("double"), TypedDataType::Float64);
+        assert_eq!(trino_type_to_typed("varchar(32)"), TypedDataType::Text);
+        assert_eq!(trino_type_to_typed("varchar"), TypedDataType::Text);
+        assert_eq!(trino_type_to_typed("varbinary"), TypedDataType::Bytes);
+        assert_eq!(trino_type_to_typed("date"), TypedDataType::Date);
+        assert_eq!(
+            trino_type_to_typed("timestamp(3)"),
+            TypedDataType::Timestamp
+        );
+        assert_eq!(
+            trino_type_to_typed("timestamp(6) with time zone"),
+            TypedDataType::Timestamp
+        );
+        assert_eq!(
+            trino_type_to_typed("timestamp with time zone"),
+            TypedDataType::Timestamp
+        );
+        assert_eq!(
+            trino_type_to_typed("time(3) with time zone"),
+            TypedDataType::Text
+        );
+        assert_eq!(
+            trino_type_to_typed("interval day to second"),
+            TypedDataType::Text
+        );
+    }
+
+    #[test]
+    fn type_mapping_decimals_and_composites() {
+        assert_eq!(
+            trino_type_to_typed("decimal(10,2)"),
+            TypedDataType::Decimal {
+                precision: 10,
+                scale: 2
+            }
+        );
+        assert_eq!(trino_type_to_typed("array(integer)"), TypedDataType::Json);
+        assert_eq!(
+            trino_type_to_typed("row(a integer, b varchar)"),
+            TypedDataType::Json
+        );
+        assert_eq!(
+            trino_type_to_typed("map(varchar, bigint)"),
+            TypedDataType::Json
+        );
+        assert_eq!(trino_type_to_typed("json"), TypedDataType::Json);
+    }
+
+    #[test]
+    fn parse_cell_numbers() {
+        assert_eq!(
+            parse_trino_cell(&serde_json::json!(42), &col(TypedDataType::Int32)).unwrap(),
+            TypedValue::Int32(42)
+        );
+        assert_eq!(
+            parse_trino_cell(
+                &serde_json::json!(9_007_199_254_740_993_i64),
+                &col(TypedDataType::Int64)
+            )
+            .unwrap(),
+            TypedValue::Int64(9_007_199_254_740_993)
+        );
+        match parse_trino_cell(&Value::String("NaN".into()), &col(TypedDataType::Float64)) {
+            Ok(TypedValue::Float64(f)) => assert!(f.is_nan()),
+            other => panic!("expected NaN, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_cell_varbinary_is_base64() {
+        assert_eq!(
+            parse_trino_cell(&Value::String("AQID".into()), &col(TypedDataType::Bytes)).unwrap(),
+            TypedValue::Bytes(vec![1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn parse_cell_json_string_is_parsed() {
+        assert_eq!(
+            parse_trino_cell(
+                &Value::String(r#"{"a":1}"#.into()),
+                &col(TypedDataType::Json)
+            )
+            .unwrap(),
+            TypedValue::Json(serde_json::json!({"a": 1}))
+        );
+    }
+
+    #[test]
+    fn parse_cell_timestamps() {
+        let ts = col(TypedDataType::Timestamp);
+        assert_eq!(
+            parse_trino_cell(&Value::String("1970-01-01 00:00:01.500".into()), &ts).unwrap(),
+            TypedValue::Timestamp(1_500_000)
+        );
+        assert_eq!(
+            parse_trino_cell(&Value::String("1970-01-01 00:00:00.000 UTC".into()), &ts).unwrap(),
+            TypedValue::Timestamp(0)
+        );
+        assert_eq!(
+            parse_trino_cell(&Value::String("1970-01-01 01:00:00.000 +01:00".into()), &ts).unwrap(),
+            TypedValue::Timestamp(0)
+        );
+        assert!(
+            parse_trino_cell(
+                &Value::String("1970-01-01 00:00:00.000 America/New_York".into()),
+                &ts
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_cell_date_and_decimal() {
+        assert_eq!(
+            parse_trino_cell(
+                &Value::String("1970-01-02".into()),
+                &col(TypedDataType::Date)
+            )
+            .unwrap(),
+            TypedValue::Date(1)
+        );
+        assert_eq!(
+            parse_trino_cell(
+                &Value::String("123.45".into()),
+                &col(TypedDataType::Decimal {
+                    precision: 10,
+                    scale: 2
+                })
+            )
+            .unwrap(),
+            TypedValue::Decimal("123.45".into())
+        );
+    }
+}
@@ -0,0 +1,109 @@
+//! Tests for `ReplayConnector`: a recorded connector's results, failures
+//! included, are answered from fixtures without querying it again.
+
+#[cfg(feature = "replay")]
+mod replay {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use agentic_connector::{
+        ColumnStats, ConnectorError, DatabaseConnector, ExecutionResult, ReplayConnector,
+        ResultSummary, SqlDialect,
+    };
+    use agentic_core::replay::ReplayMode;
+    use agentic_core::result::{
+        CellValue, ColumnSpec, QueryResult, QueryRow, TypedDataType, TypedRowStream, TypedValue,
+    };
+    use async_trait::async_trait;
+    use futures::StreamExt;
+
+    /// Answers `SELECT 1` and fails anything else, counting the queries.
+    #[derive(Default)]
+    struct CountingConnector {
+        queries: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl DatabaseConnector for CountingConnector {
+        fn dialect(&self) -> SqlDialect {
+            SqlDialect::DuckDb
+        }
+
+        async fn execute_query(
+            &self,
+            sql: &str,
+            _sample_limit: u64,
+        ) -> Result<ExecutionResult, ConnectorError> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            if sql != "SELECT 1" {
+                return Err(ConnectorError::QueryFailed {
+                    sql: sql.to_string(),
+                    message: "no such table".into(),
+                });
+            }
+            Ok(ExecutionResult {
+                result: QueryResult {
+                    columns: vec!["one".into()],
+                    rows: vec![QueryRow(vec![CellValue::Number(1.0)])],
+                    total_row_count: 1,
+                    truncated: false,
+                },
+                summary: ResultSummary {
+                    row_count: 1,
+                    columns: vec![ColumnStats {
+                        name: "one".into(),
+                        data_type: Some("INTEGER".into()),
+                        null_count: 0,
+                        distinct_count: Some(1),
+                        min: Some(CellValue::Number(1.0)),
+                        max: Some(CellValue::Number(1.0)),
+                        mean: Some(1.0),
+                        std_dev: None,
+                    }],
+                },
+            })
+        }
+
+        async fn execute_query_full(&self, _sql: &str) -> Result<TypedRowStream, ConnectorError> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            Ok(TypedRowStream::from_rows(
+                vec![ColumnSpec {
+                    name: "one".into(),
+                    data_type: TypedDataType::Int64,
+                }],
+                vec![Ok(vec![TypedValue::Int64(1)])],
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_recorded_results_and_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let live = Arc::new(CountingConnector::default());
+        let recorder = ReplayConnector::new(live.clone(), "local", ReplayMode::Record, dir.path());
+        recorder.execute_query("SELECT 1", 10).await.unwrap();
+        recorder.execute_query("SELECT x", 10).await.unwrap_err();
+        recorder.execute_query_full("SELECT 1").await.unwrap();
+        assert_eq!(live.queries.load(Ordering::SeqCst), 3);
+
+        let idle = Arc::new(CountingConnector::default());
+        let replayer = ReplayConnector::new(idle.clone(), "local", ReplayMode::Replay, dir.path());
+        let result = replayer.execute_query("SELECT 1", 10).await.unwrap();
+        assert_eq!(result.result.rows[0].0, vec![CellValue::Number(1.0)]);
+        assert_eq!(result.summary.columns[0].mean, Some(1.0));
+        assert!(matches!(
+            replayer.execute_query("SELECT x", 10).await,
+            Err(ConnectorError::QueryFailed { message, .. }) if message == "no such table"
+        ));
+        let stream = replayer.execute_query_full("SELECT 1").await.unwrap();
+        assert_eq!(stream.columns[0].data_type, TypedDataType::Int64);
+        let rows: Vec<_> = stream.rows.collect().await;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].as_ref().unwrap(), &vec![TypedValue::Int64(1)]);
+
+        // A query that was never recorded fails instead of reaching the
+        // database, and a different limit is a different query.
+        assert!(replayer.execute_query("SELECT 1", 20).await.is_err());
+        assert_eq!(idle.queries.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,281 @@
+//! Integration tests for the SQLite connector.
+//!
+//! Database files are written into a `tempfile` directory with a plain
+//! `SqliteConnection` first, then reopened through `SqliteConnector::open`.
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use std::path::{Path, PathBuf};
+
+    use agentic_connector::{
+        ConnectorConfig, ConnectorError, DatabaseConnector, SqlDialect, SqliteConfig,
+        SqliteConnection, SqliteConnector, build_connector,
+    };
+    use agentic_core::result::{CellValue, ColumnSpec, TypedDataType, TypedRowStream, TypedValue};
+    use futures::StreamExt;
+
+    async fn collect_typed(stream: TypedRowStream) -> (Vec<ColumnSpec>, Vec<Vec<TypedValue>>) {
+        let TypedRowStream { columns, mut rows } = stream;
+        let mut out = Vec::new();
+        while let Some(row) = rows.next().await {
+            match row {
+                Ok(cells) => out.push(cells),
+                Err(e) => panic!("row stream error: {e}"),
+            }
+        }
+        (columns, out)
+    }
+
+    // ── helpers ───────────────────────────────────────────────────────────────
+
+    fn write_db(path: &Path, sql: &str) -> PathBuf {
+        let conn = SqliteConnection::open(path).unwrap();
+        conn.execute_batch(sql).unwrap();
+        path.to_path_buf()
+    }
+
+    /// `shop.db` with `orders` and `customers`, sharing `customer_id`.
+    fn shop_db(dir: &Path) -> PathBuf {
+        write_db(
+            &dir.join("shop.db"),
+            "CREATE TABLE customers (customer_id INTEGER PRIMARY KEY, name TEXT);
+             INSERT INTO customers VALUES (1, 'Ada'), (2, 'Grace');
+             CREATE TABLE orders (
+                 order_id INTEGER PRIMARY KEY,
+                 customer_id INTEGER,
+                 amount REAL,
+                 ordered_on DATE,
+                 paid BOOLEAN,
+                 payload BLOB
+             );
+             INSERT INTO orders VALUES
+                 (1, 1, 10.0, '2024-01-01', 1, x'0102'),
+                 (2, 1, 20.0, '2024-01-02', 0, NULL),
+                 (3, 2, 30.0, '2024-01-03', 1, NULL),
+                 (4, 2, NULL, NULL, NULL, NULL);",
+        )
+    }
+
+    // ── execute_query ─────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn execute_query_samples_and_summarises_full_result() {
+        let dir = tempfile::tempdir().unwrap();
+        let c = SqliteConnector::open(&[shop_db(dir.path())], true).unwrap();
+        assert_eq!(c.dialect(), SqlDialect::Sqlite);
+
+        let result = c
+            .execute_query(
+                "SELECT order_id, amount, customer_id AS cust, customer_id AS cust \
+                 FROM orders ORDER BY order_id;",
+                2,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.result.rows.len(), 2);
+        assert_eq!(result.result.total_row_count, 4);
+        assert!(result.result.truncated);
+
+        let amount = &result.summary.columns[1];
+        assert_eq!(amount.data_type.as_deref(), Some("REAL"));
+        assert_eq!(amount.null_count, 1);
+        assert_eq!(amount.distinct_count, Some(3));
+        assert_eq!(amount.min, Some(CellValue::Number(10.0)));
+        assert_eq!(amount.max, Some(CellValue::Number(30.0)));
+        assert_eq!(amount.mean, Some(20.0));
+        let std_dev = amount.std_dev.unwrap();
+        assert!((std_dev - 8.164_965_809).abs() < 1e-6, "{std_dev}");
+        // Duplicate output names are summarised independently.
+        assert_eq!(result.summary.columns.len(), 4);
+        assert_eq!(result.summary.columns[3].distinct_count, Some(2));
+    }
+
+    #[tokio::test]
+    async fn execute_query_reports_sql_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let c = SqliteConnector::open(&[shop_db(dir.path())], true).unwrap();
+        let err = c
+            .execute_query("SELECT * FROM does_not_exist", 10)
+            .await
+            .expect_err("unknown table must error");
+        match err {
+            ConnectorError::QueryFailed { message, .. } => {
+                assert!(message.contains("no such table"), "{message}");
+            }
+            other => panic!("expected QueryFailed, got {other:?}"),
+        }
+    }
+
+    // ── read-only mode ────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn read_only_rejects_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = shop_db(dir.path());
+
+        let ro = SqliteConnector::open(std::slice::from_ref(&db), true).unwrap();
+        assert!(
+            ro.execute_query("DELETE FROM orders", 10).await.is_err(),
+            "read-only connection must reject writes"
+        );
+
+        let rw = SqliteConnector::open(&[db], false).unwrap();
+        rw.execute_query("DELETE FROM orders WHERE order_id = 4", 10)
+            .await
+            .unwrap();
+        let left = rw
+            .execute_query("SELECT COUNT(*) AS n FROM orders", 10)
+            .await
+            .unwrap();
+        assert_eq!(left.result.rows[0].0[0], CellValue::Number(3.0));
+    }
+
+    // ── execute_query_full ────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn execute_query_full_uses_declared_types() {
+        let dir = tempfile::tempdir().unwrap();
+        let c = SqliteConnector::open(&[shop_db(dir.path())], true).unwrap();
+
+        let stream = c
+            .execute_query_full(
+                "SELECT order_id, amount, ordered_on, paid, payload FROM orders ORDER BY order_id",
+            )
+            .await
+            .unwrap();
+        let (cols, rows) = collect_typed(stream).await;
+
+        let types: Vec<_> = cols.iter().map(|c| c.data_type.clone()).collect();
+        assert_eq!(
+            types,
+            vec![
+                TypedDataType::Int64,
+                TypedDataType::Float64,
+                TypedDataType::Date,
+                TypedDataType::Bool,
+                TypedDataType::Bytes,
+            ]
+        );
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0][0], TypedValue::Int64(1));
+        assert_eq!(rows[0][1], TypedValue::Float64(10.0));
+        assert_eq!(rows[0][2], TypedValue::Date(19_723));
+        assert_eq!(rows[0][3], TypedValue::Bool(true));
+        assert_eq!(rows[0][4], TypedValue::Bytes(vec![1, 2]));
+        assert_eq!(rows[3][1], TypedValue::Null);
+    }
+
+    #[tokio::test]
+    async fn execute_query_full_infers_expression_types() {
+        let c = SqliteConnector::new(SqliteConnection::open_in_memory().unwrap());
+
+        let stream = c
+            .execute_query_full(
+                "SELECT 1 AS i, 1.5 AS f, 'x' AS s, NULL AS n \
+                 UNION ALL SELECT 2, 2, 3, NULL",
+            )
+            .await
+            .unwrap();
+        let (cols, rows) = collect_typed(stream).await;
+
+        assert_eq!(cols[0].data_type, TypedDataType::Int64);
+        // Integers mixed with reals widen.
+        assert_eq!(cols[1].data_type, TypedDataType::Float64);
+        assert_eq!(rows[1][1], TypedValue::Float64(2.0));
+        // Text mixed with an integer falls back to text.
+        assert_eq!(cols[2].data_type, TypedDataType::Text);
+        assert_eq!(rows[1][2], TypedValue::Text("3".into()));
+        assert_eq!(cols[3].data_type, TypedDataType::Unknown);
+        assert_eq!(rows[0][3], TypedValue::Null);
+    }
+
+    // ── multiple files + introspect_schema ────────────────────────────────────
+
+    #[tokio::test]
+    async fn multiple_files_are_attached_by_stem() {
+        let dir = tempfile::tempdir().unwrap();
+        let shop = shop_db(dir.path());
+        let web = write_db(
+            &dir.path().join("web-events.db"),
+            "CREATE TABLE visits (visit_id INTEGER, customer_id INTEGER, url TEXT);
+             INSERT INTO visits VALUES (1, 1, '/'), (2, 2, '/pricing');",
+        );
+
+        let cfg = ConnectorConfig::Sqlite(SqliteConfig {
+            files: vec![shop, web],
+            read_only: true,
+        });
+        let c = build_connector(cfg).unwrap();
+
+        let result = c
+            .execute_query(
+                "SELECT c.name, COUNT(*) AS visits \
+                 FROM shop.customers AS c JOIN web_events.visits AS v USING (customer_id) \
+                 GROUP BY c.name ORDER BY c.name",
+                10,
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.result.total_row_count, 2);
+        assert_eq!(result.result.rows[0].0[0], CellValue::Text("Ada".into()));
+
+        let info = c.introspect_schema().unwrap();
+        let names: Vec<&str> = info.tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["shop.customers", "shop.orders", "web_events.visits"]
+        );
+        assert!(
+            info.join_keys
+                .iter()
+                .any(|(_, _, col)| col == "customer_id"),
+            "{:?}",
+            info.join_keys
+        );
+    }
+
+    #[tokio::test]
+    async fn introspect_schema_reports_ranges_and_samples() {
+        let dir = tempfile::tempdir().unwrap();
+        let c = SqliteConnector::open(&[shop_db(dir.path())], true).unwrap();
+
+        let info = c.introspect_schema().unwrap();
+        let orders = info.tables.iter().find(|t| t.name == "orders").unwrap();
+        let cols: Vec<&str> = orders.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            cols,
+            vec![
+                "order_id",
+                "customer_id",
+                "amount",
+                "ordered_on",
+                "paid",
+                "payload"
+            ]
+        );
+
+        let amount = &orders.columns[2];
+        assert_eq!(amount.data_type, "REAL");
+        assert_eq!(amount.min, Some(CellValue::Number(10.0)));
+        assert_eq!(amount.max, Some(CellValue::Number(30.0)));
+        assert_eq!(amount.sample_values.len(), 3, "NULLs are not sampled");
+
+        let ordered_on = &orders.columns[3];
+        assert_eq!(ordered_on.min, Some(CellValue::Text("2024-01-01".into())));
+        assert_eq!(ordered_on.max, Some(CellValue::Text("2024-01-03".into())));
+
+        // Blob ranges are not reported.
+        assert_eq!(orders.columns[5].min, None);
+    }
+
+    #[test]
+    fn open_rejects_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing.db");
+        assert!(matches!(
+            SqliteConnector::open(&[missing], true),
+            Err(ConnectorError::ConnectionError(_))
+        ));
+    }
+}
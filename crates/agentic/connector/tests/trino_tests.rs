@@ -0,0 +1,287 @@
+//! Integration tests for the Trino connector.
+//!
+//! Spins up the `trinodb/trino` image via testcontainers (anonymous `test`
+//! user, built-in `tpch` and `memory` catalogs) unless `OXY_TEST_TRINO_URL`
+//! is set. There is no Trino module in `testcontainers-modules`, so the
+//! container is a `GenericImage`. Container reused across tests via
+//! `OnceCell`. Skips gracefully when Docker is unavailable.
+//!
+//! Run with:
+//!
+//!   cargo nextest run -p agentic-connector --features trino \
+//!     --test trino_tests
+
+#![cfg(feature = "trino")]
+
+use agentic_connector::{DatabaseConnector, TrinoConfig, TrinoConnector};
+use agentic_core::result::{CellValue, ColumnSpec, TypedDataType, TypedRowStream, TypedValue};
+use futures::StreamExt;
+
+// ── Container plumbing ──────────────────────────────────────────────────────
+
+static TEST_URL: tokio::sync::OnceCell<String> = tokio::sync::OnceCell::const_new();
+static TEST_CONTAINER: tokio::sync::OnceCell<
+    std::sync::Arc<testcontainers::ContainerAsync<testcontainers::GenericImage>>,
+> = tokio::sync::OnceCell::const_new();
+
+async fn test_url() -> Option<String> {
+    TEST_URL
+        .get_or_try_init(|| async {
+            // `http://host:port`
+            if let Ok(url) = std::env::var("OXY_TEST_TRINO_URL") {
+                return Ok::<_, String>(url);
+            }
+
+            use testcontainers::core::{IntoContainerPort, WaitFor};
+            use testcontainers::runners::AsyncRunner;
+            use testcontainers::{GenericImage, ImageExt, ReuseDirective};
+
+            let container = TEST_CONTAINER
+                .get_or_try_init(|| async {
+                    GenericImage::new("trinodb/trino", "latest")
+                        .with_exposed_port(8080.tcp())
+                        .with_wait_for(WaitFor::message_on_either_std("SERVER STARTED"))
+                        .with_reuse(ReuseDirective::Always)
+                        .start()
+                        .await
+                        .map(std::sync::Arc::new)
+                        .map_err(|e| format!("trino testcontainer failed: {e}"))
+                })
+                .await?;
+
+            let host = container
+                .get_host()
+                .await
+                .map_err(|e| format!("get_host: {e}"))?;
+            let port = container
+                .get_host_port_ipv4(8080)
+                .await
+                .map_err(|e| format!("get_host_port: {e}"))?;
+            Ok(format!("http://{host}:{port}"))
+        })
+        .await
+        .ok()
+        .cloned()
+}
+
+fn config(url: String, catalogs: Vec<String>) -> TrinoConfig {
+    TrinoConfig {
+        url,
+        user: "test".to_string(),
+        password: String::new(),
+        catalog: "tpch".to_string(),
+        schema: Some("tiny".to_string()),
+        catalogs,
+    }
+}
+
+async fn skip_without_docker() -> Option<TrinoConnector> {
+    let url = test_url().await?;
+    TrinoConnector::new(config(url, vec![])).await.ok()
+}
+
+// ── Helpers ─────────────────────────────────────────────────────────────────
+
+async fn collect_typed(stream: TypedRowStream) -> (Vec<ColumnSpec>, Vec<Vec<TypedValue>>) {
+    let TypedRowStream { columns, mut rows } = stream;
+    let mut out = Vec::new();
+    while let Some(row) = rows.next().await {
+        match row {
+            Ok(cells) => out.push(cells),
+            Err(e) => panic!("row stream error: {e}"),
+        }
+    }
+    (columns, out)
+}
+
+// ── Tests ───────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn execute_query_full_preserves_scalar_types() {
+    let Some(c) = skip_without_docker().await else {
+        eprintln!("skipping: Docker not available");
+        return;
+    };
+
+    let stream = c
+        .execute_query_full(
+            "SELECT \
+                CAST(1 AS INTEGER) AS i, \
+                CAST(2 AS BIGINT) AS b, \
+                CAST(4.5 AS DOUBLE) AS f, \
+                true AS t, \
+                'hello' AS s, \
+                CAST(NULL AS INTEGER) AS n",
+        )
+        .await
+        .unwrap();
+    let (cols, rows) = collect_typed(stream).await;
+
+    assert_eq!(cols[0].data_type, TypedDataType::Int32);
+    assert_eq!(cols[1].data_type, TypedDataType::Int64);
+    assert_eq!(cols[4].data_type, TypedDataType::Text);
+    assert_eq!(rows[0][0], TypedValue::Int32(1));
+    assert_eq!(rows[0][1], TypedValue::Int64(2));
+    assert!(matches!(rows[0][2], TypedValue::Float64(f) if (f - 4.5).abs() < 1e-9));
+    assert_eq!(rows[0][3], TypedValue::Bool(true));
+    assert_eq!(rows[0][4], TypedValue::Text("hello".into()));
+    assert_eq!(rows[0][5], TypedValue::Null);
+}
+
+#[tokio::test]
+async fn execute_query_full_temporal_decimal_and_composites() {
+    let Some(c) = skip_without_docker().await else {
+        eprintln!("skipping: Docker not available");
+        return;
+    };
+
+    let stream = c
+        .execute_query_full(
+            "SELECT \
+                DATE '2026-04-22' AS d, \
+                TIMESTAMP '2026-04-22 12:34:56.789' AS ts, \
+                TIMESTAMP '2026-04-22 12:34:56 UTC' AS tstz, \
+                CAST(123.45 AS DECIMAL(10, 2)) AS amt, \
+                ARRAY[1, 2, 3] AS arr, \
+                from_hex('010203') AS bin",
+        )
+        .await
+        .unwrap();
+    let (cols, rows) = collect_typed(stream).await;
+
+    assert_eq!(cols[0].data_type, TypedDataType::Date);
+    assert_eq!(cols[1].data_type, TypedDataType::Timestamp);
+    assert_eq!(cols[2].data_type, TypedDataType::Timestamp);
+    assert!(matches!(cols[3].data_type, TypedDataType::Decimal { .. }));
+    assert_eq!(cols[4].data_type, TypedDataType::Json);
+
+    match rows[0][0] {
+        TypedValue::Date(d) => assert!((20_400..=20_700).contains(&d), "date {d}"),
+        ref other => panic!("expected Date, got {other:?}"),
+    }
+    assert!(matches!(rows[0][1], TypedValue::Timestamp(_)));
+    assert!(matches!(rows[0][2], TypedValue::Timestamp(_)));
+    assert_eq!(rows[0][3], TypedValue::Decimal("123.45".into()));
+    assert_eq!(rows[0][4], TypedValue::Json(serde_json::json!([1, 2, 3])));
+    assert_eq!(rows[0][5], TypedValue::Bytes(vec![1, 2, 3]));
+}
+
+#[tokio::test]
+async fn execute_query_full_follows_next_uri_paging() {
+    let Some(c) = skip_without_docker().await else {
+        eprintln!("skipping: Docker not available");
+        return;
+    };
+
+    // 15k rows spans several data pages.
+    let stream = c
+        .execute_query_full("SELECT orderkey FROM tpch.tiny.orders")
+        .await
+        .unwrap();
+    let (_cols, rows) = collect_typed(stream).await;
+    assert_eq!(rows.len(), 15_000);
+}
+
+#[tokio::test]
+async fn execute_query_full_reports_query_errors() {
+    let Some(c) = skip_without_docker().await else {
+        eprintln!("skipping: Docker not available");
+        return;
+    };
+
+    let err = c
+        .execute_query_full("SELECT * FROM does_not_exist_xyz")
+        .await
+        .expect_err("unknown table must error");
+    match err {
+        agentic_connector::ConnectorError::QueryFailed { message, .. } => {
+            assert!(message.contains("TABLE_NOT_FOUND"), "{message}");
+        }
+        other => panic!("expected QueryFailed, got {other:?}"),
+    }
+}
+
+// ── execute_query ─────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn execute_query_samples_and_summarises_full_result() {
+    let Some(c) = skip_without_docker().await else {
+        eprintln!("skipping: Docker not available");
+        return;
+    };
+
+    let result = c
+        .execute_query(
+            "WITH seq AS (SELECT n FROM UNNEST(sequence(1, 100)) AS t(n)) \
+             SELECT n, n % 2 = 0 AS even, n FROM seq ORDER BY n;",
+            10,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(result.result.rows.len(), 10);
+    assert_eq!(result.result.total_row_count, 100);
+    assert!(result.result.truncated);
+
+    let stats = &result.summary.columns[0];
+    assert_eq!(stats.null_count, 0);
+    assert_eq!(stats.distinct_count, Some(100));
+    assert_eq!(stats.min, Some(CellValue::Number(1.0)));
+    assert_eq!(stats.max, Some(CellValue::Number(100.0)));
+    assert_eq!(stats.mean, Some(50.5));
+    // Duplicate output names are summarised independently.
+    assert_eq!(result.summary.columns.len(), 3);
+    assert_eq!(result.summary.columns[1].distinct_count, Some(2));
+}
+
+// ── introspect_schema ─────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn introspect_schema_spans_catalogs() {
+    let Some(url) = test_url().await else {
+        eprintln!("skipping: Docker not available");
+        return;
+    };
+    let setup = TrinoConnector::new(config(url.clone(), vec![]))
+        .await
+        .unwrap();
+    let _ = setup
+        .execute_query_full(
+            "CREATE TABLE IF NOT EXISTS memory.default.oxy_test_orders \
+             (order_id BIGINT, customer_id BIGINT, total DOUBLE)",
+        )
+        .await;
+
+    let c = TrinoConnector::new(config(url, vec!["tpch".into(), "memory".into()]))
+        .await
+        .unwrap();
+    let info = c.introspect_schema().unwrap();
+
+    let names: Vec<&str> = info.tables.iter().map(|t| t.name.as_str()).collect();
+    assert!(names.contains(&"tpch.tiny.orders"), "{names:?}");
+    assert!(
+        names.contains(&"memory.default.oxy_test_orders"),
+        "{names:?}"
+    );
+    assert!(
+        !names.iter().any(|n| n.contains(".information_schema.")),
+        "information_schema must be filtered out"
+    );
+
+    let orders = info
+        .tables
+        .iter()
+        .find(|t| t.name == "memory.default.oxy_test_orders")
+        .unwrap();
+    let cols: Vec<&str> = orders.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(cols, vec!["order_id", "customer_id", "total"]);
+}
+
+#[tokio::test]
+async fn new_falls_back_to_empty_schema_when_prefetch_fails() {
+    // Nothing listens on port 1, so every introspection query fails
+    let c = TrinoConnector::new(config("http://127.0.0.1:1".to_string(), vec![]))
+        .await
+        .unwrap();
+    assert!(c.introspect_schema().unwrap().tables.is_empty());
+}
@@ -1,6 +1,6 @@
-//! Circuit breaker for delegation targets.
+//! Circuit breaker for delegation targets and LLM models.
 //!
-//! Tracks consecutive failures per target and prevents delegation to targets
+//! Tracks consecutive failures per target and prevents calls to targets
 //! that are likely broken. This avoids wasting time and resources on targets
 //! that will just fail again.
 //!
@@ -20,14 +20,18 @@
 //! everything in an [`Orchestrator`] and call [`Orchestrator::run`].
 
 pub mod back_target;
+pub mod circuit_breaker;
 pub mod delegation;
 pub mod domain;
 pub mod evaluator;
 pub mod events;
 pub mod human_input;
 pub mod orchestrator;
+pub mod rate_limit;
+pub mod replay;
 pub mod result;
 pub mod solver;
+pub mod spend;
 pub mod state;
 pub mod tools;
 pub mod transport;
@@ -0,0 +1,191 @@
+//! Client-side token-bucket rate limits for LLM providers.
+//!
+//! A [`RateLimiter`] holds up to two buckets, one for requests and one for
+//! tokens, each refilled continuously at its per-minute limit and holding at
+//! most one minute's worth.  A request is taken from its bucket before each
+//! call.  Tokens are only known once the provider reports usage, so they are
+//! charged afterwards and may drive the bucket negative; later calls then
+//! wait until it has refilled.
+//!
+//! Limiters are shared per provider key through [`RateLimiter::shared`], so
+//! every client using the same API key draws from the same buckets.
+
+use std::num::NonZeroU32;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+
+/// Requests and tokens allowed per minute.  `None` leaves that dimension
+/// unlimited; a limit of zero cannot be expressed, since it would never
+/// refill.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RateLimit {
+    pub requests_per_minute: Option<NonZeroU32>,
+    pub tokens_per_minute: Option<NonZeroU32>,
+}
+
+static SHARED: LazyLock<DashMap<String, Arc<RateLimiter>>> = LazyLock::new(DashMap::new);
+
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    buckets: Mutex<Buckets>,
+}
+
+#[derive(Debug)]
+struct Buckets {
+    requests: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    /// A limiter with full buckets.
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            buckets: Mutex::new(Buckets {
+                requests: limit
+                    .requests_per_minute
+                    .map_or(0.0, |rpm| rpm.get().into()),
+                tokens: limit.tokens_per_minute.map_or(0.0, |tpm| tpm.get().into()),
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// The process-wide limiter for `key`.  A new one replaces the existing
+    /// limiter when `limit` differs from it, e.g. after config.yml changed.
+    pub fn shared(key: &str, limit: RateLimit) -> Arc<Self> {
+        let mut entry = SHARED
+            .entry(key.to_string())
+            .or_insert_with(|| Arc::new(Self::new(limit)));
+        if entry.limit != limit {
+            *entry = Arc::new(Self::new(limit));
+        }
+        entry.clone()
+    }
+
+    pub fn limit(&self) -> RateLimit {
+        self.limit
+    }
+
+    /// Wait until a request may be made, then take it from the bucket.
+    pub async fn acquire(&self) {
+        while let Some(wait) = self.try_acquire(Instant::now()) {
+            tracing::debug!(
+                target: "rate_limit",
+                wait_ms = wait.as_millis() as u64,
+                "rate limited, waiting"
+            );
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Charge the tokens used by a finished request.
+    pub fn record_tokens(&self, tokens: u64) {
+        if self.limit.tokens_per_minute.is_none() {
+            return;
+        }
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        buckets.tokens -= tokens as f64;
+    }
+
+    /// Take a request if both buckets allow it; otherwise return how long to
+    /// wait before trying again.
+    fn try_acquire(&self, now: Instant) -> Option<Duration> {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let minutes = now.duration_since(buckets.refilled_at).as_secs_f64() / 60.0;
+        buckets.refilled_at = now;
+
+        let mut wait: f64 = 0.0;
+        if let Some(rpm) = self
+            .limit
+            .requests_per_minute
+            .map(|rpm| f64::from(rpm.get()))
+        {
+            buckets.requests = (buckets.requests + minutes * rpm).min(rpm);
+            if buckets.requests < 1.0 {
+                wait = wait.max((1.0 - buckets.requests) / rpm);
+            }
+        }
+        if let Some(tpm) = self.limit.tokens_per_minute.map(|tpm| f64::from(tpm.get())) {
+            buckets.tokens = (buckets.tokens + minutes * tpm).min(tpm);
+            if buckets.tokens < 0.0 {
+                wait = wait.max(-buckets.tokens / tpm);
+            }
+        }
+
+        if wait > 0.0 {
+            return Some(Duration::from_secs_f64(wait * 60.0));
+        }
+        if self.limit.requests_per_minute.is_some() {
+            buckets.requests -= 1.0;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requests_per_minute() {
+        let limiter = RateLimiter::new(RateLimit {
+            requests_per_minute: NonZeroU32::new(2),
+            tokens_per_minute: None,
+        });
+        let start = Instant::now();
+
+        assert_eq!(limiter.try_acquire(start), None);
+        assert_eq!(limiter.try_acquire(start), None);
+        let wait = limiter.try_acquire(start).unwrap();
+        assert_eq!(wait.as_secs(), 30);
+
+        // Half a minute refills one request
+        assert_eq!(limiter.try_acquire(start + Duration::from_secs(30)), None);
+        assert!(
+            limiter
+                .try_acquire(start + Duration::from_secs(30))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_tokens_are_charged_after_the_call() {
+        let limiter = RateLimiter::new(RateLimit {
+            requests_per_minute: None,
+            tokens_per_minute: NonZeroU32::new(1_000),
+        });
+        let start = Instant::now();
+
+        assert_eq!(limiter.try_acquire(start), None);
+        limiter.record_tokens(1_500);
+        let wait = limiter.try_acquire(start).unwrap();
+        assert_eq!(wait.as_secs(), 30);
+        assert_eq!(limiter.try_acquire(start + Duration::from_secs(30)), None);
+    }
+
+    #[test]
+    fn test_shared_per_key() {
+        let limit = RateLimit {
+            requests_per_minute: NonZeroU32::new(10),
+            tokens_per_minute: None,
+        };
+        let a = RateLimiter::shared("test:shared", limit);
+        let b = RateLimiter::shared("test:shared", limit);
+        assert!(Arc::ptr_eq(&a, &b));
+
+        let c = RateLimiter::shared(
+            "test:shared",
+            RateLimit {
+                requests_per_minute: NonZeroU32::new(20),
+                ..limit
+            },
+        );
+        assert!(!Arc::ptr_eq(&a, &c));
+        assert_eq!(c.limit().requests_per_minute, NonZeroU32::new(20));
+    }
+}
@@ -0,0 +1,210 @@
+//! Fixture storage shared by every record/replay wrapper.
+//!
+//! `OXY_REPLAY_MODE=record` makes the wrappers call through and write each
+//! response to a fixture file; `OXY_REPLAY_MODE=replay` answers the same
+//! requests from those files without touching the network.  Fixtures live
+//! under `OXY_REPLAY_DIR` (default `replay-fixtures`), one directory per
+//! namespace (`llm`, `openai`, `connector`, ...) and one file per request,
+//! named by [`fixture_key`].
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value, json};
+use sha2::{Digest, Sha256};
+
+pub const REPLAY_MODE_ENV: &str = "OXY_REPLAY_MODE";
+pub const REPLAY_DIR_ENV: &str = "OXY_REPLAY_DIR";
+pub const DEFAULT_REPLAY_DIR: &str = "replay-fixtures";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayMode {
+    /// Call through and write each response to its fixture file.
+    Record,
+    /// Answer from fixture files only; a missing fixture is an error.
+    Replay,
+}
+
+/// Why a fixture could not be used.
+#[derive(Debug)]
+pub enum FixtureError {
+    /// Nothing was recorded for the request.
+    Missing(PathBuf),
+    /// The fixture exists but could not be parsed.
+    Invalid { path: PathBuf, message: String },
+    /// The fixture could not be written.
+    Write { path: PathBuf, message: String },
+}
+
+impl fmt::Display for FixtureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(path) => write!(
+                f,
+                "No recorded fixture '{}': the request was never recorded or has changed \
+                 since. Re-record with {REPLAY_MODE_ENV}=record",
+                path.display()
+            ),
+            Self::Invalid { path, message } => {
+                write!(f, "Invalid fixture '{}': {message}", path.display())
+            }
+            Self::Write { path, message } => {
+                write!(f, "Failed to write fixture '{}': {message}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for FixtureError {}
+
+/// A fixture directory in record or replay mode.
+#[derive(Debug, Clone)]
+pub struct FixtureDir {
+    mode: ReplayMode,
+    dir: PathBuf,
+}
+
+impl FixtureDir {
+    pub fn new(mode: ReplayMode, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            mode,
+            dir: dir.into(),
+        }
+    }
+
+    /// The directory configured through `OXY_REPLAY_MODE` / `OXY_REPLAY_DIR`,
+    /// or `None` when record/replay is off.
+    pub fn from_env() -> Option<Self> {
+        let mode = match std::env::var(REPLAY_MODE_ENV).ok()?.to_lowercase().as_str() {
+            "record" => ReplayMode::Record,
+            "replay" => ReplayMode::Replay,
+            "" | "off" => return None,
+            other => {
+                tracing::warn!("Ignoring unknown {REPLAY_MODE_ENV} '{other}'");
+                return None;
+            }
+        };
+        let dir = std::env::var(REPLAY_DIR_ENV).unwrap_or_else(|_| DEFAULT_REPLAY_DIR.to_string());
+        Some(Self::new(mode, dir))
+    }
+
+    pub fn mode(&self) -> ReplayMode {
+        self.mode
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        self.mode == ReplayMode::Replay
+    }
+
+    /// Fixture file for `request` in `namespace`. The namespace directory is
+    /// created when recording.
+    pub fn path(
+        &self,
+        namespace: &str,
+        request: &Value,
+        extension: &str,
+    ) -> Result<PathBuf, FixtureError> {
+        let dir = self.dir.join(namespace);
+        if self.mode == ReplayMode::Record {
+            std::fs::create_dir_all(&dir).map_err(|e| FixtureError::Write {
+                path: dir.clone(),
+                message: e.to_string(),
+            })?;
+        }
+        Ok(dir.join(format!("{}.{extension}", fixture_key(request))))
+    }
+
+    /// The response recorded for `request`.
+    pub fn load(&self, namespace: &str, request: &Value) -> Result<Value, FixtureError> {
+        let path = self.path(namespace, request, "json")?;
+        let content = read_fixture(&path)?;
+        let mut fixture: Value =
+            serde_json::from_str(&content).map_err(|e| FixtureError::Invalid {
+                path: path.clone(),
+                message: e.to_string(),
+            })?;
+        Ok(fixture["response"].take())
+    }
+
+    /// Record `response` for `request`. The normalized request is stored next
+    /// to it so fixture diffs show what changed.
+    pub fn save(
+        &self,
+        namespace: &str,
+        request: &Value,
+        response: Value,
+    ) -> Result<(), FixtureError> {
+        let path = self.path(namespace, request, "json")?;
+        let fixture = json!({
+            "request": normalize(request.clone()),
+            "response": response,
+        });
+        let content = serde_json::to_string_pretty(&fixture).map_err(|e| FixtureError::Write {
+            path: path.clone(),
+            message: e.to_string(),
+        })?;
+        std::fs::write(&path, content).map_err(|e| FixtureError::Write {
+            path,
+            message: e.to_string(),
+        })
+    }
+}
+
+/// Read a fixture file, reporting a missing one as [`FixtureError::Missing`].
+pub fn read_fixture(path: &Path) -> Result<String, FixtureError> {
+    std::fs::read_to_string(path).map_err(|_| FixtureError::Missing(path.to_path_buf()))
+}
+
+/// Hash of the normalized request, used as the fixture file name.
+pub fn fixture_key(request: &Value) -> String {
+    let canonical = normalize(request.clone()).to_string();
+    hex::encode(Sha256::digest(canonical.as_bytes()))[..32].to_string()
+}
+
+/// Sort object keys and drop nulls so equal requests hash equally however
+/// they were built.
+pub fn normalize(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map
+                .into_iter()
+                .filter(|(_, v)| !v.is_null())
+                .map(|(k, v)| (k, normalize(v)))
+                .collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(entries.into_iter().collect::<Map<_, _>>())
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(normalize).collect()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixture_key_ignores_key_order_and_nulls() {
+        let a = json!({"model": "gpt-4o", "messages": [{"role": "user", "content": "hi"}]});
+        let b = json!({"messages": [{"content": "hi", "role": "user"}], "model": "gpt-4o", "user": null});
+        let c = json!({"model": "gpt-4o", "messages": [{"role": "user", "content": "hello"}]});
+        assert_eq!(fixture_key(&a), fixture_key(&b));
+        assert_ne!(fixture_key(&a), fixture_key(&c));
+    }
+
+    #[test]
+    fn record_then_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let request = json!({"query": "SELECT 1"});
+
+        let recorder = FixtureDir::new(ReplayMode::Record, dir.path());
+        recorder.save("test", &request, json!(["a", "b"])).unwrap();
+
+        let replayer = FixtureDir::new(ReplayMode::Replay, dir.path());
+        assert_eq!(replayer.load("test", &request).unwrap(), json!(["a", "b"]));
+        assert!(matches!(
+            replayer.load("test", &json!({"query": "SELECT 2"})),
+            Err(FixtureError::Missing(_))
+        ));
+    }
+}
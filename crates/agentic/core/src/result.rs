@@ -12,11 +12,12 @@
 use std::pin::Pin;
 
 use futures_core::Stream;
+use serde::{Deserialize, Serialize};
 
 // ── Bounded sample: CellValue / QueryRow / QueryResult ──────────────────────
 
 /// A single cell value in a bounded sample result.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum CellValue {
     /// A text / string value.
     Text(String),
@@ -27,11 +28,11 @@ pub enum CellValue {
 }
 
 /// A single row in a bounded sample result.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueryRow(pub Vec<CellValue>);
 
 /// The result of executing an analytics query (bounded sample).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueryResult {
     /// Column names in the same order as the cell values in each row.
     pub columns: Vec<String>,
@@ -51,7 +52,7 @@ pub struct QueryResult {
 ///
 /// Matches Arrow's logical categories so connectors can translate to the
 /// corresponding `arrow::datatypes::DataType` without information loss.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TypedDataType {
     Bool,
     Int32,
@@ -81,14 +82,14 @@ pub enum TypedDataType {
 }
 
 /// Column metadata emitted alongside a [`TypedRowStream`].
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ColumnSpec {
     pub name: String,
     pub data_type: TypedDataType,
 }
 
 /// A single cell value in a full, typed row stream.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TypedValue {
     Null,
     Bool(bool),
@@ -123,7 +124,7 @@ pub type BoxedRowStream =
 /// Kept structural — connectors translate their driver-specific errors into
 /// these variants so consumers can handle them uniformly without depending on
 /// any given driver.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TypedRowError {
     /// The driver reported an error during row fetch or decoding.
     DriverError(String),
@@ -0,0 +1,35 @@
+//! Spend tracking for LLM calls and the tool calls they make.
+//!
+//! Hosts that enforce budgets implement [`SpendTracker`] and hand it to the
+//! pipeline.  The LLM client checks it before each call, charges it the
+//! tokens each call reports and charges every tool call the model asks for
+//! before the tool runs.  Pricing and the limits themselves are up to the
+//! host.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+pub type SharedSpendTracker = Arc<dyn SpendTracker>;
+
+#[async_trait]
+pub trait SpendTracker: Send + Sync {
+    /// Why no more spend is allowed, once a limit has been reached.  Checked
+    /// before every LLM call so a spent budget stops a run without another
+    /// call.
+    fn exceeded(&self) -> Option<String>;
+
+    /// Charge the tokens of a finished call to `model`.  The tokens are
+    /// already spent, so they are counted even when an `Err` reports that
+    /// the call went over a limit.
+    async fn charge_tokens(
+        &self,
+        model: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+    ) -> Result<(), String>;
+
+    /// Charge tool calls the model asked for, before they run.  An `Err`
+    /// refuses them.
+    async fn charge_tool_calls(&self, count: u64) -> Result<(), String>;
+}
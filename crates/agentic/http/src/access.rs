@@ -0,0 +1,15 @@
+//! Run authorization supplied by the host app.
+//!
+//! Route-level guards only see the path, but `POST /runs` names the agent
+//! (or the builder) in its body. The host inserts an `Arc<dyn RunAccess>`
+//! into every request's extensions so `create_run` can check it.
+
+/// What the caller of a request may start.
+pub trait RunAccess: Send + Sync {
+    /// Whether the caller may run the analytics agent `agent_id`.
+    fn can_run_agent(&self, agent_id: &str) -> bool;
+
+    /// Whether the caller may edit workspace files. Builder runs write
+    /// files, so they need this rather than an agent permission.
+    fn can_edit_files(&self) -> bool;
+}
@@ -21,12 +21,14 @@
 //! | POST   | `/analytics/runs/:id/answer`  | Deliver answer to a suspended run    |
 //! | POST   | `/analytics/runs/:id/cancel`  | Cancel a running or suspended run    |
 
+pub mod access;
 pub mod coordinator;
 pub mod db;
 pub mod routes;
 pub mod sse;
 pub mod state;
 
+pub use access::RunAccess;
 pub use state::{AgenticState, RunStatus};
 
 use sea_orm::DatabaseConnection;
@@ -17,10 +17,10 @@ use uuid::Uuid;
 
 use agentic_pipeline::PipelineBuilder;
 use agentic_pipeline::platform::{BuilderBridges, PlatformContext};
-use agentic_pipeline::{AutoAcceptInputProvider, LlmClient, OpenAiProvider};
+use agentic_pipeline::{AutoAcceptInputProvider, GeminiProvider, LlmClient, OpenAiProvider};
 
 use crate::{
-    db, sse,
+    RunAccess, db, sse,
     state::{AgenticState, RunStatus},
 };
 
@@ -34,6 +34,7 @@ pub async fn create_run(
     Extension(state): Extension<Arc<AgenticState>>,
     Extension(platform): Extension<Arc<dyn PlatformContext>>,
     Extension(bridges): Extension<BuilderBridges>,
+    Extension(access): Extension<Arc<dyn RunAccess>>,
     Json(body): Json<CreateRunRequest>,
 ) -> Response {
     tracing::info!(
@@ -53,6 +54,21 @@ pub async fn create_run(
             .into_response();
     }
 
+    let is_builder = body.domain.as_deref() == Some("builder");
+    let allowed = if is_builder {
+        access.can_edit_files()
+    } else {
+        access.can_run_agent(&body.agent_id)
+    };
+    if !allowed {
+        tracing::info!(
+            agent_id = %body.agent_id,
+            domain = ?body.domain,
+            "create_run: caller may not start this run"
+        );
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
     let db = state.db.clone();
 
     let thread_id_uuid = body
@@ -103,14 +119,16 @@ pub async fn create_run(
             .await
             .unwrap_or_default();
         let client = if mc.vendor == "openai" {
-            LlmClient::with_provider(OpenAiProvider::new(&api_key, &mc.model_ref))
+            LlmClient::with_live_provider(OpenAiProvider::new(&api_key, &mc.model_ref))
+        } else if mc.vendor == "google" {
+            LlmClient::with_live_provider(GeminiProvider::new(&api_key, &mc.model_ref))
         } else {
             LlmClient::with_model(api_key, mc.model_ref.clone())
         };
         builder = builder.with_builder_llm_client(client);
     }
 
-    builder = if body.domain.as_deref() == Some("builder") {
+    builder = if is_builder {
         builder.builder(body.model.clone())
     } else {
         builder.analytics(&body.agent_id)
@@ -305,31 +305,19 @@ impl LlmProvider for AnthropicProvider {
         if !status.is_success() {
             let text = response.text().await.unwrap_or_default();
             if let Ok(api_err) = serde_json::from_str::<ApiError>(&text) {
-                return Err(LlmError::Http(api_err.error.message));
+                return Err(LlmError::from_status(status, api_err.error.message));
             }
-            return Err(LlmError::Http(format!("HTTP {status}: {text}")));
+            return Err(LlmError::from_status(
+                status,
+                format!("HTTP {status}: {text}"),
+            ));
         }
 
         let s = stream! {
             use tokio_stream::StreamExt as _;
 
             let mut sse_buf = String::new();
-            // Current open content block
-            let mut block_type: Option<String> = None;
-            // Thinking accumulator
-            let mut thinking_text = String::new();
-            let mut thinking_sig = String::new();
-            // Tool-use accumulator
-            let mut tool_id = String::new();
-            let mut tool_name = String::new();
-            let mut tool_args = String::new();
-            // Usage
-            let mut input_tokens: usize = 0;
-            let mut output_tokens: usize = 0;
-            let mut cache_creation_input_tokens: usize = 0;
-            let mut cache_read_input_tokens: usize = 0;
-            let mut stop_reason = StopReason::EndTurn;
-
+            let mut decoder = AnthropicEventDecoder::default();
             let mut byte_stream = response.bytes_stream();
 
             'outer: while let Some(bytes_result) = byte_stream.next().await {
@@ -362,147 +350,12 @@ impl LlmProvider for AnthropicProvider {
                         Err(_) => continue,
                     };
 
-                    match ev["type"].as_str().unwrap_or("") {
-                        "message_start" => {
-                            let usage = &ev["message"]["usage"];
-                            input_tokens = usage["input_tokens"].as_u64().unwrap_or(0) as usize;
-                            // Cache token fields are only present when prompt
-                            // caching engaged on this call.  Treat absence as 0.
-                            cache_creation_input_tokens = usage
-                                ["cache_creation_input_tokens"]
-                                .as_u64()
-                                .unwrap_or(0) as usize;
-                            cache_read_input_tokens = usage
-                                ["cache_read_input_tokens"]
-                                .as_u64()
-                                .unwrap_or(0) as usize;
-                        }
-
-                        "content_block_start" => {
-                            let cb = &ev["content_block"];
-                            let btype = cb["type"].as_str().unwrap_or("").to_string();
-                            match btype.as_str() {
-                                "thinking" => {
-                                    thinking_text.clear();
-                                    thinking_sig.clear();
-                                    // Empty initial chunk signals ThinkingStart to the consumer.
-                                    yield Ok(Chunk::ThinkingSummary(String::new()));
-                                }
-                                "text" => {
-                                    // Empty initial chunk signals start of text block.
-                                    yield Ok(Chunk::Text(String::new()));
-                                }
-                                "tool_use" => {
-                                    tool_id = cb["id"].as_str().unwrap_or("").to_string();
-                                    tool_name = cb["name"].as_str().unwrap_or("").to_string();
-                                    tool_args.clear();
-                                }
-                                _ => {}
-                            }
-                            block_type = Some(btype);
-                        }
-
-                        "content_block_delta" => {
-                            let delta = &ev["delta"];
-                            match delta["type"].as_str().unwrap_or("") {
-                                "thinking_delta" => {
-                                    let t = delta["thinking"]
-                                        .as_str()
-                                        .unwrap_or("")
-                                        .to_string();
-                                    thinking_text.push_str(&t);
-                                    yield Ok(Chunk::ThinkingSummary(t));
-                                }
-                                "signature_delta" => {
-                                    thinking_sig.push_str(
-                                        delta["signature"].as_str().unwrap_or(""),
-                                    );
-                                }
-                                "text_delta" => {
-                                    let t =
-                                        delta["text"].as_str().unwrap_or("").to_string();
-                                    yield Ok(Chunk::Text(t));
-                                }
-                                "input_json_delta" => {
-                                    tool_args.push_str(
-                                        delta["partial_json"].as_str().unwrap_or(""),
-                                    );
-                                }
-                                _ => {}
-                            }
-                        }
-
-                        "content_block_stop" => {
-                            match block_type.as_deref() {
-                                Some("thinking") => {
-                                    let mut obj = serde_json::Map::new();
-                                    obj.insert("type".into(), json!("thinking"));
-                                    obj.insert(
-                                        "thinking".into(),
-                                        json!(thinking_text.clone()),
-                                    );
-                                    obj.insert(
-                                        "signature".into(),
-                                        json!(thinking_sig.clone()),
-                                    );
-                                    yield Ok(Chunk::RawBlock(ContentBlock::Thinking {
-                                        provider_data: Value::Object(obj),
-                                    }));
-                                }
-                                Some("tool_use") => {
-                                    let input: Value =
-                                        serde_json::from_str(&tool_args)
-                                            .unwrap_or_else(|_| json!({}));
-                                    yield Ok(Chunk::ToolCall(ToolCallChunk {
-                                        id: tool_id.clone(),
-                                        name: tool_name.clone(),
-                                        input,
-                                        provider_data: None,
-                                    }));
-                                }
-                                _ => {}
-                            }
-                            block_type = None;
-                        }
-
-                        "message_delta" => {
-                            let usage = &ev["usage"];
-                            output_tokens =
-                                usage["output_tokens"].as_u64().unwrap_or(0) as usize;
-                            // Anthropic occasionally re-reports cache tokens
-                            // here; take max so a later 0 doesn't clobber a
-                            // value seen at message_start.
-                            if let Some(v) = usage["cache_creation_input_tokens"].as_u64()
-                            {
-                                cache_creation_input_tokens =
-                                    cache_creation_input_tokens.max(v as usize);
-                            }
-                            if let Some(v) = usage["cache_read_input_tokens"].as_u64() {
-                                cache_read_input_tokens =
-                                    cache_read_input_tokens.max(v as usize);
-                            }
-                            // Parse stop_reason: "end_turn", "max_tokens", or "tool_use".
-                            if let Some(sr) = ev["delta"]["stop_reason"].as_str() {
-                                stop_reason = match sr {
-                                    "max_tokens" => StopReason::MaxTokens,
-                                    "tool_use" => StopReason::ToolUse,
-                                    _ => StopReason::EndTurn,
-                                };
-                            }
-                        }
-
-                        "message_stop" => {
-                            yield Ok(Chunk::Done(Usage {
-                                input_tokens,
-                                output_tokens,
-                                cache_creation_input_tokens,
-                                cache_read_input_tokens,
-                                stop_reason,
-                            }));
+                    for chunk in decoder.decode(&ev) {
+                        let done = matches!(chunk, Chunk::Done(_));
+                        yield Ok(chunk);
+                        if done {
                             break 'outer;
                         }
-
-                        _ => {}
                     }
                 }
             }
@@ -535,3 +388,160 @@ impl LlmProvider for AnthropicProvider {
         &self.model
     }
 }
+
+// ── AnthropicEventDecoder ─────────────────────────────────────────────────────
+
+/// Turns Anthropic streaming events (`message_start`, `content_block_*`,
+/// `message_delta`, `message_stop`) into [`Chunk`]s.
+///
+/// Transport-agnostic: the Messages API delivers these events as SSE, while
+/// Bedrock wraps the same JSON in AWS event-stream frames.  Both feed the
+/// decoded event objects through [`decode`](Self::decode).
+#[derive(Default)]
+pub(super) struct AnthropicEventDecoder {
+    // Current open content block
+    block_type: Option<String>,
+    // Thinking accumulator
+    thinking_text: String,
+    thinking_sig: String,
+    // Tool-use accumulator
+    tool_id: String,
+    tool_name: String,
+    tool_args: String,
+    // Usage
+    input_tokens: usize,
+    output_tokens: usize,
+    cache_creation_input_tokens: usize,
+    cache_read_input_tokens: usize,
+    stop_reason: StopReason,
+}
+
+impl AnthropicEventDecoder {
+    /// Decode one event.  A [`Chunk::Done`] in the output marks the end of
+    /// the message; callers should stop reading after it.
+    pub(super) fn decode(&mut self, ev: &Value) -> Vec<Chunk> {
+        let mut out = Vec::new();
+        match ev["type"].as_str().unwrap_or("") {
+            "message_start" => {
+                let usage = &ev["message"]["usage"];
+                self.input_tokens = usage["input_tokens"].as_u64().unwrap_or(0) as usize;
+                // Cache token fields are only present when prompt
+                // caching engaged on this call.  Treat absence as 0.
+                self.cache_creation_input_tokens =
+                    usage["cache_creation_input_tokens"].as_u64().unwrap_or(0) as usize;
+                self.cache_read_input_tokens =
+                    usage["cache_read_input_tokens"].as_u64().unwrap_or(0) as usize;
+            }
+
+            "content_block_start" => {
+                let cb = &ev["content_block"];
+                let btype = cb["type"].as_str().unwrap_or("").to_string();
+                match btype.as_str() {
+                    "thinking" => {
+                        self.thinking_text.clear();
+                        self.thinking_sig.clear();
+                        // Empty initial chunk signals ThinkingStart to the consumer.
+                        out.push(Chunk::ThinkingSummary(String::new()));
+                    }
+                    "text" => {
+                        // Empty initial chunk signals start of text block.
+                        out.push(Chunk::Text(String::new()));
+                    }
+                    "tool_use" => {
+                        self.tool_id = cb["id"].as_str().unwrap_or("").to_string();
+                        self.tool_name = cb["name"].as_str().unwrap_or("").to_string();
+                        self.tool_args.clear();
+                    }
+                    _ => {}
+                }
+                self.block_type = Some(btype);
+            }
+
+            "content_block_delta" => {
+                let delta = &ev["delta"];
+                match delta["type"].as_str().unwrap_or("") {
+                    "thinking_delta" => {
+                        let t = delta["thinking"].as_str().unwrap_or("").to_string();
+                        self.thinking_text.push_str(&t);
+                        out.push(Chunk::ThinkingSummary(t));
+                    }
+                    "signature_delta" => {
+                        self.thinking_sig
+                            .push_str(delta["signature"].as_str().unwrap_or(""));
+                    }
+                    "text_delta" => {
+                        let t = delta["text"].as_str().unwrap_or("").to_string();
+                        out.push(Chunk::Text(t));
+                    }
+                    "input_json_delta" => {
+                        self.tool_args
+                            .push_str(delta["partial_json"].as_str().unwrap_or(""));
+                    }
+                    _ => {}
+                }
+            }
+
+            "content_block_stop" => {
+                match self.block_type.as_deref() {
+                    Some("thinking") => {
+                        let mut obj = serde_json::Map::new();
+                        obj.insert("type".into(), json!("thinking"));
+                        obj.insert("thinking".into(), json!(self.thinking_text.clone()));
+                        obj.insert("signature".into(), json!(self.thinking_sig.clone()));
+                        out.push(Chunk::RawBlock(ContentBlock::Thinking {
+                            provider_data: Value::Object(obj),
+                        }));
+                    }
+                    Some("tool_use") => {
+                        let input: Value =
+                            serde_json::from_str(&self.tool_args).unwrap_or_else(|_| json!({}));
+                        out.push(Chunk::ToolCall(ToolCallChunk {
+                            id: self.tool_id.clone(),
+                            name: self.tool_name.clone(),
+                            input,
+                            provider_data: None,
+                        }));
+                    }
+                    _ => {}
+                }
+                self.block_type = None;
+            }
+
+            "message_delta" => {
+                let usage = &ev["usage"];
+                self.output_tokens = usage["output_tokens"].as_u64().unwrap_or(0) as usize;
+                // Anthropic occasionally re-reports cache tokens
+                // here; take max so a later 0 doesn't clobber a
+                // value seen at message_start.
+                if let Some(v) = usage["cache_creation_input_tokens"].as_u64() {
+                    self.cache_creation_input_tokens =
+                        self.cache_creation_input_tokens.max(v as usize);
+                }
+                if let Some(v) = usage["cache_read_input_tokens"].as_u64() {
+                    self.cache_read_input_tokens = self.cache_read_input_tokens.max(v as usize);
+                }
+                // Parse stop_reason: "end_turn", "max_tokens", or "tool_use".
+                if let Some(sr) = ev["delta"]["stop_reason"].as_str() {
+                    self.stop_reason = match sr {
+                        "max_tokens" => StopReason::MaxTokens,
+                        "tool_use" => StopReason::ToolUse,
+                        _ => StopReason::EndTurn,
+                    };
+                }
+            }
+
+            "message_stop" => {
+                out.push(Chunk::Done(Usage {
+                    input_tokens: self.input_tokens,
+                    output_tokens: self.output_tokens,
+                    cache_creation_input_tokens: self.cache_creation_input_tokens,
+                    cache_read_input_tokens: self.cache_read_input_tokens,
+                    stop_reason: self.stop_reason.clone(),
+                }));
+            }
+
+            _ => {}
+        }
+        out
+    }
+}
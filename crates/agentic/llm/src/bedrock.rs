@@ -0,0 +1,389 @@
+use std::pin::Pin;
+use std::time::SystemTime;
+
+use async_stream::stream;
+use async_trait::async_trait;
+use aws_config::{BehaviorVersion, SdkConfig};
+use aws_credential_types::provider::ProvideCredentials;
+use aws_sigv4::{
+    http_request::{SignableBody, SignableRequest, SigningSettings, sign},
+    sign::v4,
+};
+use base64::Engine as _;
+use futures_core::Stream;
+use serde_json::{Value, json};
+use tokio::sync::OnceCell;
+
+use agentic_core::tools::ToolDef;
+
+use super::anthropic::AnthropicEventDecoder;
+use super::constants::*;
+use super::eventstream::pop_event_frame;
+use super::{
+    AnthropicProvider, Chunk, ContentBlock, LlmError, LlmProvider, ResponseSchema, ThinkingConfig,
+};
+
+// ── BedrockProvider ───────────────────────────────────────────────────────────
+
+/// Claude on AWS Bedrock (`InvokeModelWithResponseStream`), signed with SigV4.
+///
+/// The request body is the Anthropic Messages body minus `model` / `stream`,
+/// and the response carries the same streaming events wrapped in AWS
+/// event-stream frames — so body building, message shapes and event decoding
+/// are shared with [`AnthropicProvider`].
+///
+/// Credentials and (unless given explicitly) the region come from the default
+/// AWS chain: environment, shared config / profile, web identity, IMDS.
+pub struct BedrockProvider {
+    anthropic: AnthropicProvider,
+    model: String,
+    region: Option<String>,
+    base_url: Option<String>,
+    sdk_config: OnceCell<SdkConfig>,
+    client: reqwest::Client,
+}
+
+impl BedrockProvider {
+    /// Create a provider for a Bedrock model id or inference profile, e.g.
+    /// `us.anthropic.claude-sonnet-4-5-20250929-v1:0`.  `region` overrides
+    /// the one resolved from the AWS environment.
+    pub fn new(model: impl Into<String>, region: Option<String>) -> Self {
+        let model = model.into();
+        Self {
+            anthropic: AnthropicProvider::new("", model.clone()),
+            model,
+            region,
+            base_url: None,
+            sdk_config: OnceCell::new(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Create a provider against a custom runtime endpoint, e.g. a VPC
+    /// interface endpoint.  Requests are still signed for `region`.
+    pub fn with_base_url(
+        model: impl Into<String>,
+        region: Option<String>,
+        base_url: impl Into<String>,
+    ) -> Self {
+        Self {
+            base_url: Some(base_url.into().trim_end_matches('/').to_string()),
+            ..Self::new(model, region)
+        }
+    }
+
+    /// Build the JSON request body for `invoke-with-response-stream`.
+    ///
+    /// Pure helper — see [`AnthropicProvider::build_request_body`].  Bedrock
+    /// takes the model from the URL and the API version and beta flags from
+    /// the body rather than headers.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn build_request_body(
+        &self,
+        system: &str,
+        system_date_suffix: &str,
+        messages: &[Value],
+        tools: &[ToolDef],
+        thinking: &ThinkingConfig,
+        response_schema: Option<&ResponseSchema>,
+        max_tokens_override: Option<u32>,
+    ) -> Value {
+        let mut body = self.anthropic.build_request_body(
+            system,
+            system_date_suffix,
+            messages,
+            tools,
+            thinking,
+            response_schema,
+            max_tokens_override,
+        );
+        if let Some(obj) = body.as_object_mut() {
+            obj.remove("model");
+            obj.remove("stream");
+            obj.insert("anthropic_version".into(), json!(BEDROCK_ANTHROPIC_VERSION));
+            if !matches!(thinking, ThinkingConfig::Disabled) {
+                obj.insert("anthropic_beta".into(), json!([ANTHROPIC_THINKING_BETA]));
+            }
+        }
+        body
+    }
+
+    fn stream_url(&self, region: &str) -> String {
+        let base = self
+            .base_url
+            .clone()
+            .unwrap_or_else(|| format!("https://bedrock-runtime.{region}.amazonaws.com"));
+        format!(
+            "{base}/model/{}/invoke-with-response-stream",
+            encode_path_segment(&self.model)
+        )
+    }
+
+    async fn sdk_config(&self) -> &SdkConfig {
+        self.sdk_config
+            .get_or_init(|| aws_config::load_defaults(BehaviorVersion::latest()))
+            .await
+    }
+
+    /// SigV4-sign a POST of `body` to `url`; returns the headers to attach.
+    async fn sign(
+        &self,
+        url: &str,
+        region: &str,
+        body: &[u8],
+    ) -> Result<Vec<(String, String)>, LlmError> {
+        let credentials = self
+            .sdk_config()
+            .await
+            .credentials_provider()
+            .ok_or_else(|| LlmError::Auth("no AWS credentials provider configured".into()))?
+            .provide_credentials()
+            .await
+            .map_err(|e| LlmError::Auth(format!("failed to load AWS credentials: {e}")))?;
+        let identity = credentials.into();
+
+        let signing_params = v4::SigningParams::builder()
+            .identity(&identity)
+            .region(region)
+            .name("bedrock")
+            .time(SystemTime::now())
+            .settings(SigningSettings::default())
+            .build()
+            .map_err(|e| LlmError::Auth(format!("failed to build SigV4 params: {e}")))?;
+
+        let headers = [("content-type", "application/json")];
+        let signable =
+            SignableRequest::new("POST", url, headers.into_iter(), SignableBody::Bytes(body))
+                .map_err(|e| LlmError::Auth(format!("failed to build signable request: {e}")))?;
+
+        let (instructions, _sig) = sign(signable, &signing_params.into())
+            .map_err(|e| LlmError::Auth(format!("SigV4 signing failed: {e}")))?
+            .into_parts();
+
+        Ok(instructions
+            .headers()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl LlmProvider for BedrockProvider {
+    async fn stream(
+        &self,
+        system: &str,
+        system_date_suffix: &str,
+        messages: &[Value],
+        tools: &[ToolDef],
+        thinking: &ThinkingConfig,
+        response_schema: Option<&ResponseSchema>,
+        max_tokens_override: Option<u32>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Chunk, LlmError>> + Send>>, LlmError> {
+        let body = self.build_request_body(
+            system,
+            system_date_suffix,
+            messages,
+            tools,
+            thinking,
+            response_schema,
+            max_tokens_override,
+        );
+        let body = serde_json::to_vec(&body).map_err(|e| LlmError::Parse(e.to_string()))?;
+
+        let region = match &self.region {
+            Some(region) => region.clone(),
+            None => self
+                .sdk_config()
+                .await
+                .region()
+                .map(|r| r.to_string())
+                .ok_or_else(|| {
+                    LlmError::Auth(
+                        "no AWS region configured for Bedrock; set AWS_REGION or pass a region"
+                            .into(),
+                    )
+                })?,
+        };
+        let url = self.stream_url(&region);
+
+        let mut req = self
+            .client
+            .post(&url)
+            .header("content-type", "application/json")
+            .header("accept", "application/vnd.amazon.eventstream");
+        for (name, value) in self.sign(&url, &region, &body).await? {
+            req = req.header(name, value);
+        }
+
+        let response = req
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| LlmError::Http(e.to_string()))?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+            let text = response.text().await.unwrap_or_default();
+            return Err(LlmError::Auth(text));
+        }
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let text = response.text().await.unwrap_or_default();
+            return Err(LlmError::RateLimit(text));
+        }
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            // Bedrock errors are `{"message": "..."}` rather than the
+            // Anthropic `{"error": {...}}` envelope.
+            if let Some(message) = serde_json::from_str::<Value>(&text)
+                .ok()
+                .and_then(|v| v["message"].as_str().map(str::to_string))
+            {
+                return Err(LlmError::from_status(status, message));
+            }
+            return Err(LlmError::from_status(
+                status,
+                format!("HTTP {status}: {text}"),
+            ));
+        }
+
+        Ok(Box::pin(decode_bedrock_event_stream(
+            response.bytes_stream(),
+        )))
+    }
+
+    fn assistant_message(&self, blocks: &[ContentBlock]) -> Value {
+        self.anthropic.assistant_message(blocks)
+    }
+
+    fn tool_result_messages(&self, results: &[(String, String, bool)]) -> Vec<Value> {
+        self.anthropic.tool_result_messages(results)
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+}
+
+// ── Stream decoding ───────────────────────────────────────────────────────────
+
+/// Decode an `InvokeModelWithResponseStream` body into [`Chunk`]s.
+///
+/// Each `chunk` event carries `{"bytes": "<base64>"}` wrapping one Anthropic
+/// streaming event; `exception` messages surface as errors.  Generic over the
+/// byte stream so tests can replay recorded responses.
+pub(crate) fn decode_bedrock_event_stream<S, B, E>(
+    mut byte_stream: S,
+) -> impl Stream<Item = Result<Chunk, LlmError>> + Send + 'static
+where
+    S: Stream<Item = Result<B, E>> + Send + Unpin + 'static,
+    B: AsRef<[u8]> + Send + 'static,
+    E: std::fmt::Display + Send + 'static,
+{
+    stream! {
+        use tokio_stream::StreamExt as _;
+
+        let mut buf: Vec<u8> = Vec::new();
+        let mut decoder = AnthropicEventDecoder::default();
+
+        while let Some(bytes_result) = byte_stream.next().await {
+            match bytes_result {
+                Ok(b) => buf.extend_from_slice(b.as_ref()),
+                Err(e) => {
+                    yield Err(LlmError::Http(e.to_string()));
+                    return;
+                }
+            }
+
+            loop {
+                let frame = match pop_event_frame(&mut buf) {
+                    Ok(Some(f)) => f,
+                    Ok(None) => break,
+                    Err(e) => {
+                        yield Err(e);
+                        return;
+                    }
+                };
+
+                if frame.header(":message-type") == Some("exception") {
+                    let kind = frame.header(":exception-type").unwrap_or("exception");
+                    let message = serde_json::from_slice::<Value>(&frame.payload)
+                        .ok()
+                        .and_then(|v| v["message"].as_str().map(str::to_string))
+                        .unwrap_or_else(|| String::from_utf8_lossy(&frame.payload).into_owned());
+                    let message = format!("{kind}: {message}");
+                    yield Err(if kind == "throttlingException" {
+                        LlmError::RateLimit(message)
+                    } else {
+                        LlmError::Http(message)
+                    });
+                    return;
+                }
+                if frame.header(":event-type") != Some("chunk") {
+                    continue;
+                }
+
+                let envelope: Value = match serde_json::from_slice(&frame.payload) {
+                    Ok(v) => v,
+                    Err(_) => continue,
+                };
+                let Some(ev) = envelope["bytes"]
+                    .as_str()
+                    .and_then(|b| base64::engine::general_purpose::STANDARD.decode(b).ok())
+                    .and_then(|raw| serde_json::from_slice::<Value>(&raw).ok())
+                else {
+                    continue;
+                };
+
+                for chunk in decoder.decode(&ev) {
+                    let done = matches!(chunk, Chunk::Done(_));
+                    yield Ok(chunk);
+                    if done {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Percent-encode a model id or ARN for use as a single URL path segment
+/// (`:` and `/` are common in Bedrock identifiers).
+fn encode_path_segment(segment: &str) -> String {
+    let mut out = String::with_capacity(segment.len());
+    for b in segment.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(b as char)
+            }
+            _ => out.push_str(&format!("%{b:02X}")),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream_url_encodes_model_id_and_uses_regional_endpoint() {
+        let p = BedrockProvider::new("us.anthropic.claude-sonnet-4-5-20250929-v1:0", None);
+        assert_eq!(
+            p.stream_url("us-west-2"),
+            "https://bedrock-runtime.us-west-2.amazonaws.com/model/us.anthropic.claude-sonnet-4-5-20250929-v1%3A0/invoke-with-response-stream"
+        );
+    }
+
+    #[test]
+    fn stream_url_honours_custom_endpoint_and_arns() {
+        let p = BedrockProvider::with_base_url(
+            "arn:aws:bedrock:eu-central-1:123456789012:inference-profile/eu.anthropic.claude-opus-4-6-v1:0",
+            Some("eu-central-1".into()),
+            "https://vpce-0abc.bedrock-runtime.eu-central-1.vpce.amazonaws.com/",
+        );
+        assert_eq!(
+            p.stream_url("eu-central-1"),
+            "https://vpce-0abc.bedrock-runtime.eu-central-1.vpce.amazonaws.com/model/arn%3Aaws%3Abedrock%3Aeu-central-1%3A123456789012%3Ainference-profile%2Feu.anthropic.claude-opus-4-6-v1%3A0/invoke-with-response-stream"
+        );
+    }
+}
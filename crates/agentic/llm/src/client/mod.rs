@@ -3,6 +3,8 @@ use std::sync::Arc;
 use serde_json::{Value, json};
 
 use agentic_core::events::{CoreEvent, DomainEvents, Event, EventStream};
+use agentic_core::rate_limit::RateLimiter;
+use agentic_core::spend::SharedSpendTracker;
 
 use super::constants::DEFAULT_MODEL;
 
@@ -11,7 +13,8 @@ use super::constants::DEFAULT_MODEL;
 /// readable without losing all context for long outputs.
 const LLM_OUTPUT_PREVIEW_MAX_CHARS: usize = 2000;
 use super::{
-    AnthropicProvider, Chunk, LlmError, LlmProvider, OpenAiCompatProvider, ThinkingConfig, Usage,
+    AnthropicProvider, Chunk, FallbackProvider, LlmError, LlmProvider, OpenAiCompatProvider,
+    RateLimitedProvider, ReplayProvider, SpendTrackedProvider, ThinkingConfig, Usage,
 };
 
 // ── LlmClient ─────────────────────────────────────────────────────────────────
@@ -20,7 +23,10 @@ use super::{
 /// and event emission.
 ///
 /// Construct with [`LlmClient::new`] (defaults to [`AnthropicProvider`]) or
-/// supply a custom provider via [`LlmClient::with_provider`].
+/// supply a custom provider via [`LlmClient::with_live_provider`] or
+/// [`LlmClient::with_provider`].  The constructors for real APIs wrap the
+/// provider in a [`ReplayProvider`] when `OXY_REPLAY_MODE` is set;
+/// [`LlmClient::with_provider`] uses its provider as is.
 ///
 /// # Example
 ///
@@ -42,14 +48,19 @@ impl LlmClient {
     /// Create a client backed by [`AnthropicProvider`] with the default model.
     pub fn new(api_key: impl Into<String>) -> Self {
         Self {
-            provider: Arc::new(AnthropicProvider::new(api_key, DEFAULT_MODEL)),
+            provider: ReplayProvider::wrap_from_env(Arc::new(AnthropicProvider::new(
+                api_key,
+                DEFAULT_MODEL,
+            ))),
         }
     }
 
     /// Create a client backed by [`AnthropicProvider`] with a custom model.
     pub fn with_model(api_key: impl Into<String>, model: impl Into<String>) -> Self {
         Self {
-            provider: Arc::new(AnthropicProvider::new(api_key, model)),
+            provider: ReplayProvider::wrap_from_env(Arc::new(AnthropicProvider::new(
+                api_key, model,
+            ))),
         }
     }
 
@@ -64,17 +75,62 @@ impl LlmClient {
         base_url: impl Into<String>,
     ) -> Self {
         Self {
-            provider: Arc::new(OpenAiCompatProvider::new(api_key, model, base_url)),
+            provider: ReplayProvider::wrap_from_env(Arc::new(OpenAiCompatProvider::new(
+                api_key, model, base_url,
+            ))),
         }
     }
 
-    /// Create a client backed by a fully custom provider.
+    /// Create a client backed by a provider that calls a real API, recorded
+    /// or replayed when `OXY_REPLAY_MODE` is set.
+    pub fn with_live_provider(provider: impl LlmProvider + 'static) -> Self {
+        Self {
+            provider: ReplayProvider::wrap_from_env(Arc::new(provider)),
+        }
+    }
+
+    /// Create a client backed by a fully custom provider, used as is.
     pub fn with_provider(provider: impl LlmProvider + 'static) -> Self {
         Self {
             provider: Arc::new(provider),
         }
     }
 
+    /// Wait on `limiter` before every call and charge it the tokens used.
+    pub fn with_rate_limit(self, limiter: Arc<RateLimiter>) -> Self {
+        Self {
+            provider: Arc::new(RateLimitedProvider::new(self.provider, limiter)),
+        }
+    }
+
+    /// Charge every call and the tool calls it asks for to `tracker`,
+    /// refusing calls once a budget has been spent.
+    pub fn with_spend_tracker(self, tracker: SharedSpendTracker) -> Self {
+        Self {
+            provider: Arc::new(SpendTrackedProvider::new(self.provider, tracker)),
+        }
+    }
+
+    /// Fail over to `fallbacks`, in order, when this client's model is rate
+    /// limited, returns an HTTP error or has an open circuit.  `key` and the
+    /// key paired with each fallback name the models in the circuit breaker.
+    ///
+    /// The fallbacks must use the same message format as this client; see
+    /// [`FallbackProvider`].
+    pub fn with_fallbacks(
+        self,
+        key: impl Into<String>,
+        fallbacks: impl IntoIterator<Item = (String, LlmClient)>,
+    ) -> Self {
+        let provider = fallbacks.into_iter().fold(
+            FallbackProvider::new(key, self.provider),
+            |provider, (key, client)| provider.with_fallback(key, client.provider),
+        );
+        Self {
+            provider: Arc::new(provider),
+        }
+    }
+
     /// Build the message history for resuming after an `ask_user` suspension.
     ///
     /// `prior_messages` is the full provider-native message history returned
@@ -228,6 +284,7 @@ impl LlmClient {
             oxy.name = "llm.call",
             oxy.span_type = "llm",
             gen_ai.request.model = %self.provider.model_name(),
+            gen_ai.response.model = %self.provider.model_name(),
         )
     )]
     pub async fn complete_with_usage(
@@ -314,6 +371,14 @@ pub(crate) fn find_all_unmatched_tool_ids(messages: &[Value]) -> Vec<String> {
         {
             matched.insert(id.to_string());
         }
+        // Gemini: functionResponse parts in a user turn
+        if let Some(parts) = m["parts"].as_array() {
+            for p in parts.iter() {
+                if let Some(id) = p["functionResponse"]["id"].as_str() {
+                    matched.insert(id.to_string());
+                }
+            }
+        }
         if m["type"].as_str() == Some("function_call_output")
             && let Some(id) = m["call_id"].as_str()
         {
@@ -356,6 +421,21 @@ pub(crate) fn find_all_unmatched_tool_ids(messages: &[Value]) -> Vec<String> {
                 return unmatched;
             }
         }
+        // Gemini: functionCall parts in a model turn
+        if m["role"].as_str() == Some("model")
+            && let Some(parts) = m["parts"].as_array()
+        {
+            for p in parts.iter() {
+                if let Some(id) = p["functionCall"]["id"].as_str()
+                    && !matched.contains(id)
+                {
+                    unmatched.push(id.to_string());
+                }
+            }
+            if !unmatched.is_empty() {
+                return unmatched;
+            }
+        }
         // OpenAI Responses: flat function_call item
         if m["type"].as_str() == Some("function_call")
             && let Some(id) = m["call_id"].as_str()
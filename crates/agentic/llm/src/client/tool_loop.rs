@@ -120,6 +120,7 @@ impl LlmClient {
                 oxy.name = "llm.call",
                 oxy.span_type = "llm",
                 gen_ai.request.model = %self.provider.model_name(),
+                gen_ai.response.model = %self.provider.model_name(),
                 llm.state = %config.state,
                 llm.round = rounds,
             );
@@ -5,6 +5,9 @@ pub(super) const ANTHROPIC_VERSION: &str = "2023-06-01";
 /// parameter and no thinking blocks appear in the SSE stream.
 pub(super) const ANTHROPIC_THINKING_BETA: &str = "interleaved-thinking-2025-05-14";
 pub(super) const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+pub(super) const GEMINI_BASE_URL: &str = "https://generativelanguage.googleapis.com/v1beta";
+/// `anthropic_version` body field required by Claude models on Bedrock.
+pub(super) const BEDROCK_ANTHROPIC_VERSION: &str = "bedrock-2023-05-31";
 
 /// The default model used when constructing an [`LlmClient`] via [`LlmClient::new`].
 pub const DEFAULT_MODEL: &str = "claude-opus-4-6";
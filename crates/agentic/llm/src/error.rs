@@ -3,12 +3,25 @@
 pub enum LlmError {
     /// HTTP transport or server error.
     Http(String),
+    /// The provider rejected the request itself (an HTTP 4xx other than
+    /// auth, rate limit or timeout errors), e.g. a prompt that is too long.
+    /// Sending it again will fail the same way.
+    Request(String),
     /// Authentication failure (bad or missing API key).
     Auth(String),
+    /// A token, cost or tool-call budget of the run has been reached (see
+    /// [`SpendTrackedProvider`]).  The run must stop.
+    ///
+    /// [`SpendTrackedProvider`]: crate::SpendTrackedProvider
+    BudgetExceeded(String),
     /// Rate limit exceeded (HTTP 429). Retrying after a backoff delay may succeed.
     RateLimit(String),
     /// Response could not be parsed.
     Parse(String),
+    /// A recorded fixture could not be read or written (see [`ReplayProvider`]).
+    ///
+    /// [`ReplayProvider`]: crate::ReplayProvider
+    Replay(String),
     /// The model produced thinking/reasoning but no text output — likely
     /// hit `max_tokens` during the thinking phase.
     EmptyResponse { reason: String },
@@ -59,9 +72,12 @@ impl std::fmt::Display for LlmError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             LlmError::Http(msg) => write!(f, "HTTP error: {msg}"),
+            LlmError::Request(msg) => write!(f, "request rejected: {msg}"),
             LlmError::Auth(msg) => write!(f, "auth error: {msg}"),
+            LlmError::BudgetExceeded(msg) => write!(f, "budget exceeded: {msg}"),
             LlmError::RateLimit(msg) => write!(f, "rate limit exceeded: {msg}"),
             LlmError::Parse(msg) => write!(f, "parse error: {msg}"),
+            LlmError::Replay(msg) => write!(f, "replay error: {msg}"),
             LlmError::EmptyResponse { reason } => {
                 write!(f, "empty response from model: {reason}")
             }
@@ -87,3 +103,21 @@ impl std::fmt::Display for LlmError {
 }
 
 impl std::error::Error for LlmError {}
+
+impl LlmError {
+    /// The error for an unsuccessful HTTP response that is neither an auth
+    /// error nor a rate limit.
+    pub(crate) fn from_status(status: reqwest::StatusCode, message: String) -> Self {
+        if status.is_client_error() && status != reqwest::StatusCode::REQUEST_TIMEOUT {
+            LlmError::Request(message)
+        } else {
+            LlmError::Http(message)
+        }
+    }
+
+    /// Whether the call may succeed if retried later or on another model:
+    /// rate limits, server errors and transport failures.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, LlmError::RateLimit(_) | LlmError::Http(_))
+    }
+}
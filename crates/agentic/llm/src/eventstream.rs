@@ -0,0 +1,101 @@
+// ── AWS event-stream helpers ──────────────────────────────────────────────────
+//
+// Bedrock's streaming endpoints respond with `application/vnd.amazon.eventstream`
+// rather than SSE.  Each message is a length-prefixed binary frame:
+//
+//   total_len: u32 | headers_len: u32 | prelude_crc: u32
+//   headers (headers_len bytes) | payload | message_crc: u32
+//
+// Only the framing is decoded here; the checksums are not verified because
+// the transport is already TLS-protected.
+
+use super::LlmError;
+
+/// Fixed-size prelude (two lengths + CRC) in front of the headers.
+const PRELUDE_LEN: usize = 12;
+/// Trailing message CRC.
+const TRAILER_LEN: usize = 4;
+
+/// One decoded event-stream message.
+#[derive(Debug)]
+pub(super) struct EventFrame {
+    /// String-typed headers (`:event-type`, `:message-type`, …).  Headers of
+    /// other value types are skipped — Bedrock does not use them.
+    pub(super) headers: Vec<(String, String)>,
+    pub(super) payload: Vec<u8>,
+}
+
+impl EventFrame {
+    pub(super) fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Advance `buf` past the next complete frame.  Returns `Ok(None)` when no
+/// complete frame is buffered yet.
+pub(super) fn pop_event_frame(buf: &mut Vec<u8>) -> Result<Option<EventFrame>, LlmError> {
+    if buf.len() < PRELUDE_LEN {
+        return Ok(None);
+    }
+    let total_len = read_u32(buf, 0) as usize;
+    let headers_len = read_u32(buf, 4) as usize;
+    if total_len < PRELUDE_LEN + TRAILER_LEN + headers_len {
+        return Err(LlmError::Parse(format!(
+            "malformed event-stream frame: total length {total_len}, headers length {headers_len}"
+        )));
+    }
+    if buf.len() < total_len {
+        return Ok(None);
+    }
+
+    let frame: Vec<u8> = buf.drain(..total_len).collect();
+    let headers = parse_headers(&frame[PRELUDE_LEN..PRELUDE_LEN + headers_len])?;
+    let payload = frame[PRELUDE_LEN + headers_len..total_len - TRAILER_LEN].to_vec();
+    Ok(Some(EventFrame { headers, payload }))
+}
+
+fn parse_headers(mut bytes: &[u8]) -> Result<Vec<(String, String)>, LlmError> {
+    let truncated = || LlmError::Parse("truncated event-stream header".into());
+    let mut headers = Vec::new();
+    while !bytes.is_empty() {
+        let name_len = bytes[0] as usize;
+        let name = bytes.get(1..1 + name_len).ok_or_else(truncated)?;
+        let name = String::from_utf8_lossy(name).into_owned();
+        let value_type = *bytes.get(1 + name_len).ok_or_else(truncated)?;
+        let rest = &bytes[2 + name_len..];
+        // Value widths per the event-stream spec; 6 (bytes) and 7 (string)
+        // carry a u16 length prefix.
+        let (value_len, prefix_len) = match value_type {
+            0 | 1 => (0, 0),
+            2 => (1, 0),
+            3 => (2, 0),
+            4 => (4, 0),
+            5 | 8 => (8, 0),
+            9 => (16, 0),
+            6 | 7 => {
+                let len = rest.get(..2).ok_or_else(truncated)?;
+                (u16::from_be_bytes([len[0], len[1]]) as usize, 2)
+            }
+            other => {
+                return Err(LlmError::Parse(format!(
+                    "unknown event-stream header type {other}"
+                )));
+            }
+        };
+        let value = rest
+            .get(prefix_len..prefix_len + value_len)
+            .ok_or_else(truncated)?;
+        if value_type == 7 {
+            headers.push((name, String::from_utf8_lossy(value).into_owned()));
+        }
+        bytes = &rest[prefix_len + value_len..];
+    }
+    Ok(headers)
+}
+
+fn read_u32(buf: &[u8], at: usize) -> u32 {
+    u32::from_be_bytes([buf[at], buf[at + 1], buf[at + 2], buf[at + 3]])
+}
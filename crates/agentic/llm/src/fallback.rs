@@ -0,0 +1,205 @@
+//! Model fallbacks and client-side rate limits.
+//!
+//! [`RateLimitedProvider`] waits on a shared [`RateLimiter`] before each call
+//! and charges it the tokens reported in [`Chunk::Done`].
+//!
+//! [`FallbackProvider`] tries a list of providers in order.  A model whose
+//! circuit is open is skipped; one that fails to open its stream with a
+//! transient error (a rate limit, server error or transport failure, see
+//! [`LlmError::is_transient`]) is recorded as a failure in the process-wide
+//! [`CircuitBreaker`] and the next model is tried.  Other errors, such as a
+//! rejected request or bad credentials, are returned without failing over.
+//! Failover only happens before the first chunk — an error mid-stream is
+//! returned as-is.
+//!
+//! Conversation history is built by the first provider's
+//! [`LlmProvider::assistant_message`] and
+//! [`LlmProvider::tool_result_messages`], so every provider in the list must
+//! share its message format.
+//!
+//! The model that served each call is recorded as `gen_ai.response.model`
+//! on the current span.
+
+use std::{
+    pin::Pin,
+    sync::{Arc, LazyLock},
+};
+
+use agentic_core::circuit_breaker::CircuitBreaker;
+use agentic_core::rate_limit::RateLimiter;
+use agentic_core::tools::ToolDef;
+use async_trait::async_trait;
+use futures_core::Stream;
+use serde_json::Value;
+use tokio_stream::StreamExt;
+
+use super::{Chunk, ContentBlock, LlmError, LlmProvider, ResponseSchema, ThinkingConfig};
+
+static LLM_CIRCUIT_BREAKER: LazyLock<Arc<CircuitBreaker>> =
+    LazyLock::new(CircuitBreaker::with_defaults);
+
+/// An [`LlmProvider`] that waits on a [`RateLimiter`] before each call.
+pub struct RateLimitedProvider {
+    inner: Arc<dyn LlmProvider>,
+    limiter: Arc<RateLimiter>,
+}
+
+impl RateLimitedProvider {
+    pub fn new(inner: Arc<dyn LlmProvider>, limiter: Arc<RateLimiter>) -> Self {
+        Self { inner, limiter }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for RateLimitedProvider {
+    #[allow(clippy::too_many_arguments)]
+    async fn stream(
+        &self,
+        system: &str,
+        system_date_suffix: &str,
+        messages: &[Value],
+        tools: &[ToolDef],
+        thinking: &ThinkingConfig,
+        response_schema: Option<&ResponseSchema>,
+        max_tokens_override: Option<u32>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Chunk, LlmError>> + Send>>, LlmError> {
+        self.limiter.acquire().await;
+        let stream = self
+            .inner
+            .stream(
+                system,
+                system_date_suffix,
+                messages,
+                tools,
+                thinking,
+                response_schema,
+                max_tokens_override,
+            )
+            .await?;
+        let limiter = self.limiter.clone();
+        Ok(Box::pin(stream.map(move |chunk| {
+            if let Ok(Chunk::Done(usage)) = &chunk {
+                limiter.record_tokens((usage.input_tokens + usage.output_tokens) as u64);
+            }
+            chunk
+        })))
+    }
+
+    fn assistant_message(&self, blocks: &[ContentBlock]) -> Value {
+        self.inner.assistant_message(blocks)
+    }
+
+    fn tool_result_messages(&self, results: &[(String, String, bool)]) -> Vec<Value> {
+        self.inner.tool_result_messages(results)
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+}
+
+/// An [`LlmProvider`] that fails over through a list of providers.
+///
+/// Each provider is paired with the key its failures are tracked under in
+/// the circuit breaker, typically the model's name in `config.yml`.
+pub struct FallbackProvider {
+    routes: Vec<(String, Arc<dyn LlmProvider>)>,
+    breaker: Arc<CircuitBreaker>,
+}
+
+impl FallbackProvider {
+    pub fn new(key: impl Into<String>, primary: Arc<dyn LlmProvider>) -> Self {
+        Self {
+            routes: vec![(key.into(), primary)],
+            breaker: LLM_CIRCUIT_BREAKER.clone(),
+        }
+    }
+
+    /// Try `provider` after the providers added so far.
+    pub fn with_fallback(mut self, key: impl Into<String>, provider: Arc<dyn LlmProvider>) -> Self {
+        self.routes.push((key.into(), provider));
+        self
+    }
+
+    /// Use `breaker` instead of the process-wide circuit breaker.
+    pub fn with_breaker(mut self, breaker: Arc<CircuitBreaker>) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    fn primary(&self) -> &Arc<dyn LlmProvider> {
+        &self.routes[0].1
+    }
+}
+
+#[async_trait]
+impl LlmProvider for FallbackProvider {
+    #[allow(clippy::too_many_arguments)]
+    async fn stream(
+        &self,
+        system: &str,
+        system_date_suffix: &str,
+        messages: &[Value],
+        tools: &[ToolDef],
+        thinking: &ThinkingConfig,
+        response_schema: Option<&ResponseSchema>,
+        max_tokens_override: Option<u32>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Chunk, LlmError>> + Send>>, LlmError> {
+        let mut last_error = None;
+        for (key, provider) in &self.routes {
+            if let Err(reason) = self.breaker.check(key) {
+                tracing::debug!(target: "llm_fallback", "skipping model: {reason}");
+                last_error = Some(LlmError::Http(reason));
+                continue;
+            }
+            let result = provider
+                .stream(
+                    system,
+                    system_date_suffix,
+                    messages,
+                    tools,
+                    thinking,
+                    response_schema,
+                    max_tokens_override,
+                )
+                .await;
+            match result {
+                Ok(stream) => {
+                    self.breaker.record_success(key);
+                    tracing::Span::current().record("gen_ai.response.model", provider.model_name());
+                    if provider.model_name() != self.model_name() {
+                        tracing::info!(
+                            target: "llm_fallback",
+                            model = provider.model_name(),
+                            "call served by fallback model"
+                        );
+                    }
+                    return Ok(stream);
+                }
+                Err(err) if err.is_transient() => {
+                    self.breaker.record_failure(key);
+                    tracing::warn!(
+                        target: "llm_fallback",
+                        model = provider.model_name(),
+                        "LLM call failed, trying the next model: {err}"
+                    );
+                    last_error = Some(err);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| LlmError::Http("no model available".to_string())))
+    }
+
+    fn assistant_message(&self, blocks: &[ContentBlock]) -> Value {
+        self.primary().assistant_message(blocks)
+    }
+
+    fn tool_result_messages(&self, results: &[(String, String, bool)]) -> Vec<Value> {
+        self.primary().tool_result_messages(results)
+    }
+
+    fn model_name(&self) -> &str {
+        self.primary().model_name()
+    }
+}
@@ -0,0 +1,547 @@
+use std::collections::HashMap;
+use std::pin::Pin;
+
+use async_stream::stream;
+use async_trait::async_trait;
+use futures_core::Stream;
+use serde_json::{Value, json};
+
+use agentic_core::tools::ToolDef;
+
+use super::constants::*;
+use super::sse::{ApiError, pop_sse_event, sse_data};
+use super::{
+    Chunk, ContentBlock, LlmError, LlmProvider, ReasoningEffort, ResponseSchema, StopReason,
+    ThinkingConfig, ToolCallChunk, Usage,
+};
+
+// ── GeminiProvider ────────────────────────────────────────────────────────────
+
+/// Google Gemini API provider (`streamGenerateContent`, SSE).
+///
+/// Messages use Gemini's native `contents` / `parts` shape.  Plain
+/// `{"role", "content"}` messages and Anthropic-style `tool_use` /
+/// `tool_result` history are translated on the way out, so the tool loop's
+/// synthetic user turns work unchanged.
+///
+/// Gemini does not always return ids for function calls, and its function
+/// responses are matched by name.  Tool-call ids handed to the tool loop are
+/// therefore `"{name}:{suffix}"`, where the suffix is Gemini's id when present
+/// and the call's position in the response otherwise; [`tool_result_messages`]
+/// recovers the function name from the id.
+///
+/// Thought signatures are passed back verbatim: on function calls through
+/// [`ToolCallChunk::provider_data`], on text parts as a
+/// [`ContentBlock::Thinking`] raw block.
+///
+/// [`tool_result_messages`]: LlmProvider::tool_result_messages
+pub struct GeminiProvider {
+    api_key: String,
+    model: String,
+    base_url: String,
+    client: reqwest::Client,
+}
+
+impl GeminiProvider {
+    /// Create a provider using the given API key and model.
+    pub fn new(api_key: impl Into<String>, model: impl Into<String>) -> Self {
+        Self::with_base_url(api_key, model, GEMINI_BASE_URL)
+    }
+
+    /// Create a provider against a custom API root, e.g. a proxy in front of
+    /// `https://generativelanguage.googleapis.com/v1beta`.
+    pub fn with_base_url(
+        api_key: impl Into<String>,
+        model: impl Into<String>,
+        base_url: impl Into<String>,
+    ) -> Self {
+        Self {
+            api_key: api_key.into(),
+            model: model.into(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn stream_url(&self) -> String {
+        format!(
+            "{}/models/{}:streamGenerateContent?alt=sse",
+            self.base_url, self.model
+        )
+    }
+
+    /// Build the JSON request body for `streamGenerateContent`.
+    ///
+    /// Pure helper — no HTTP, no I/O — so unit tests can inspect the wire
+    /// format directly.  Structured output uses `responseJsonSchema` when no
+    /// tools are present; with tools, a synthetic function is added instead
+    /// because Gemini rejects constrained decoding combined with function
+    /// calling on most models.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn build_request_body(
+        &self,
+        system: &str,
+        system_date_suffix: &str,
+        messages: &[Value],
+        tools: &[ToolDef],
+        thinking: &ThinkingConfig,
+        response_schema: Option<&ResponseSchema>,
+        max_tokens_override: Option<u32>,
+    ) -> Value {
+        let mut declarations: Vec<Value> = tools
+            .iter()
+            .map(|t| {
+                json!({
+                    "name": t.name,
+                    "description": t.description,
+                    "parametersJsonSchema": t.parameters
+                })
+            })
+            .collect();
+
+        let max_tokens = max_tokens_override.unwrap_or_else(|| match thinking {
+            ThinkingConfig::Manual { budget_tokens } => {
+                std::cmp::max(THINKING_MAX_TOKENS, *budget_tokens + DEFAULT_MAX_TOKENS)
+            }
+            ThinkingConfig::Adaptive | ThinkingConfig::Effort(_) => THINKING_MAX_TOKENS,
+            ThinkingConfig::Disabled => DEFAULT_MAX_TOKENS,
+        });
+
+        let mut generation_config = json!({ "maxOutputTokens": max_tokens });
+
+        // Gemini 2.5+ accepts `thinkingBudget` on every thinking model;
+        // -1 lets the model decide, which is the closest match for Adaptive.
+        // OpenAI-style effort levels map onto fixed budgets.
+        let budget: Option<i64> = match thinking {
+            ThinkingConfig::Disabled => None,
+            ThinkingConfig::Adaptive => Some(-1),
+            ThinkingConfig::Manual { budget_tokens } => Some(*budget_tokens as i64),
+            ThinkingConfig::Effort(ReasoningEffort::Low) => Some(1024),
+            ThinkingConfig::Effort(ReasoningEffort::Medium) => Some(8192),
+            ThinkingConfig::Effort(ReasoningEffort::High) => Some(24576),
+        };
+        if let Some(budget) = budget {
+            generation_config["thinkingConfig"] = json!({
+                "includeThoughts": true,
+                "thinkingBudget": budget
+            });
+        }
+
+        if let Some(schema) = response_schema {
+            if tools.is_empty() {
+                generation_config["responseMimeType"] = json!("application/json");
+                generation_config["responseJsonSchema"] = schema.schema.clone();
+            } else {
+                declarations.push(json!({
+                    "name": schema.name,
+                    "description": "You MUST call this function to return your final structured response. Do NOT embed JSON in your text — always use this function.",
+                    "parametersJsonSchema": schema.schema
+                }));
+            }
+        }
+
+        let mut body = json!({
+            "contents": to_gemini_contents(messages),
+            "generationConfig": generation_config,
+        });
+
+        let system_parts: Vec<Value> = [system, system_date_suffix]
+            .into_iter()
+            .filter(|s| !s.is_empty())
+            .map(|s| json!({"text": s}))
+            .collect();
+        if !system_parts.is_empty() {
+            body["systemInstruction"] = json!({"parts": system_parts});
+        }
+
+        if !declarations.is_empty() {
+            body["tools"] = json!([{"functionDeclarations": declarations}]);
+        }
+
+        body
+    }
+
+    fn block_to_part(block: &ContentBlock) -> Option<Value> {
+        match block {
+            ContentBlock::Thinking { provider_data }
+            | ContentBlock::RedactedThinking { provider_data } => Some(provider_data.clone()),
+            ContentBlock::Text { text } if text.is_empty() => None,
+            ContentBlock::Text { text } => Some(json!({"text": text})),
+            ContentBlock::ToolUse {
+                id,
+                name,
+                input,
+                provider_data,
+            } => {
+                // Keep the original part (and its thoughtSignature) but stamp
+                // our id on it so tool results can be matched on resume.
+                let mut part = provider_data
+                    .clone()
+                    .unwrap_or_else(|| json!({"functionCall": {"name": name, "args": input}}));
+                part["functionCall"]["id"] = json!(id);
+                Some(part)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for GeminiProvider {
+    async fn stream(
+        &self,
+        system: &str,
+        system_date_suffix: &str,
+        messages: &[Value],
+        tools: &[ToolDef],
+        thinking: &ThinkingConfig,
+        response_schema: Option<&ResponseSchema>,
+        max_tokens_override: Option<u32>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Chunk, LlmError>> + Send>>, LlmError> {
+        let body = self.build_request_body(
+            system,
+            system_date_suffix,
+            messages,
+            tools,
+            thinking,
+            response_schema,
+            max_tokens_override,
+        );
+
+        let response = self
+            .client
+            .post(self.stream_url())
+            .header("x-goog-api-key", &self.api_key)
+            .header("content-type", "application/json")
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| LlmError::Http(e.to_string()))?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+            let text = response.text().await.unwrap_or_default();
+            return Err(LlmError::Auth(text));
+        }
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let text = response.text().await.unwrap_or_default();
+            return Err(LlmError::RateLimit(text));
+        }
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            if let Ok(api_err) = serde_json::from_str::<ApiError>(&text) {
+                return Err(LlmError::from_status(status, api_err.error.message));
+            }
+            return Err(LlmError::from_status(
+                status,
+                format!("HTTP {status}: {text}"),
+            ));
+        }
+
+        Ok(Box::pin(decode_gemini_sse(response.bytes_stream())))
+    }
+
+    fn assistant_message(&self, blocks: &[ContentBlock]) -> Value {
+        let parts: Vec<Value> = blocks.iter().filter_map(Self::block_to_part).collect();
+        json!({"role": "model", "parts": parts})
+    }
+
+    fn tool_result_messages(&self, results: &[(String, String, bool)]) -> Vec<Value> {
+        let parts: Vec<Value> = results
+            .iter()
+            .map(|(id, content, is_error)| {
+                function_response_part(id, function_name(id), content, *is_error)
+            })
+            .collect();
+        vec![json!({"role": "user", "parts": parts})]
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+}
+
+// ── Stream decoding ───────────────────────────────────────────────────────────
+
+/// Decode a `streamGenerateContent?alt=sse` body into [`Chunk`]s.
+///
+/// Generic over the byte stream so tests can replay recorded responses.
+/// Gemini has no terminal event — [`Chunk::Done`] is emitted when the body
+/// ends.
+pub(crate) fn decode_gemini_sse<S, B, E>(
+    mut byte_stream: S,
+) -> impl Stream<Item = Result<Chunk, LlmError>> + Send + 'static
+where
+    S: Stream<Item = Result<B, E>> + Send + Unpin + 'static,
+    B: AsRef<[u8]> + Send + 'static,
+    E: std::fmt::Display + Send + 'static,
+{
+    stream! {
+        use tokio_stream::StreamExt as _;
+
+        let mut sse_buf = String::new();
+        let mut decoder = GeminiEventDecoder::default();
+
+        while let Some(bytes_result) = byte_stream.next().await {
+            let bytes = match bytes_result {
+                Ok(b) => b,
+                Err(e) => {
+                    yield Err(LlmError::Http(e.to_string()));
+                    return;
+                }
+            };
+            sse_buf.push_str(&String::from_utf8_lossy(bytes.as_ref()));
+
+            while let Some(event_text) = pop_sse_event(&mut sse_buf) {
+                let data = match sse_data(&event_text) {
+                    Some(d) if !d.is_empty() => d,
+                    _ => continue,
+                };
+                let ev: Value = match serde_json::from_str(data) {
+                    Ok(v) => v,
+                    Err(_) => continue,
+                };
+                match decoder.decode(&ev) {
+                    Ok(chunks) => {
+                        for chunk in chunks {
+                            yield Ok(chunk);
+                        }
+                    }
+                    Err(e) => {
+                        yield Err(e);
+                        return;
+                    }
+                }
+            }
+        }
+
+        yield Ok(Chunk::Done(decoder.usage()));
+    }
+}
+
+/// Accumulates state across `GenerateContentResponse` events.
+#[derive(Default)]
+struct GeminiEventDecoder {
+    in_thinking: bool,
+    in_text: bool,
+    thinking_text: String,
+    tool_calls: usize,
+    prompt_tokens: usize,
+    output_tokens: usize,
+    cached_tokens: usize,
+    max_tokens_hit: bool,
+}
+
+impl GeminiEventDecoder {
+    fn decode(&mut self, ev: &Value) -> Result<Vec<Chunk>, LlmError> {
+        if let Some(err) = ev.get("error") {
+            let message = err["message"].as_str().unwrap_or("unknown error");
+            return Err(LlmError::Http(message.to_string()));
+        }
+        if let Some(reason) = ev["promptFeedback"]["blockReason"].as_str() {
+            return Err(LlmError::Request(format!(
+                "prompt blocked by Gemini: {reason}"
+            )));
+        }
+
+        let mut out = Vec::new();
+        let candidate = &ev["candidates"][0];
+        for part in candidate["content"]["parts"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+        {
+            self.decode_part(part, &mut out);
+        }
+
+        match candidate["finishReason"].as_str() {
+            Some("MAX_TOKENS") => self.max_tokens_hit = true,
+            Some("STOP") | None => {}
+            Some(other) => {
+                tracing::warn!(finish_reason = other, "Gemini stopped early");
+            }
+        }
+
+        // usageMetadata is cumulative; the last event carries the totals.
+        let usage = &ev["usageMetadata"];
+        if usage.is_object() {
+            let count = |field: &str| usage[field].as_u64().unwrap_or(0) as usize;
+            self.prompt_tokens = count("promptTokenCount");
+            self.cached_tokens = count("cachedContentTokenCount");
+            self.output_tokens = count("candidatesTokenCount") + count("thoughtsTokenCount");
+        }
+
+        Ok(out)
+    }
+
+    fn decode_part(&mut self, part: &Value, out: &mut Vec<Chunk>) {
+        if let Some(call) = part.get("functionCall") {
+            self.in_thinking = false;
+            self.in_text = false;
+            let name = call["name"].as_str().unwrap_or("").to_string();
+            let suffix = call["id"]
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| self.tool_calls.to_string());
+            self.tool_calls += 1;
+            out.push(Chunk::ToolCall(ToolCallChunk {
+                id: format!("{name}:{suffix}"),
+                name,
+                input: call.get("args").cloned().unwrap_or_else(|| json!({})),
+                provider_data: Some(part.clone()),
+            }));
+            return;
+        }
+
+        let text = part["text"].as_str().unwrap_or("");
+        if part["thought"].as_bool() == Some(true) {
+            if !self.in_thinking {
+                self.in_thinking = true;
+                self.in_text = false;
+                self.thinking_text.clear();
+                // Empty initial chunk signals ThinkingStart to the consumer.
+                out.push(Chunk::ThinkingSummary(String::new()));
+            }
+            self.thinking_text.push_str(text);
+            if !text.is_empty() {
+                out.push(Chunk::ThinkingSummary(text.to_string()));
+            }
+        } else if !text.is_empty() {
+            self.in_thinking = false;
+            if !self.in_text {
+                self.in_text = true;
+                // Empty initial chunk signals start of text block.
+                out.push(Chunk::Text(String::new()));
+            }
+            out.push(Chunk::Text(text.to_string()));
+        }
+
+        if let Some(signature) = part["thoughtSignature"].as_str() {
+            out.push(Chunk::RawBlock(ContentBlock::Thinking {
+                provider_data: json!({
+                    "text": self.thinking_text,
+                    "thought": true,
+                    "thoughtSignature": signature
+                }),
+            }));
+        }
+    }
+
+    fn usage(&self) -> Usage {
+        let stop_reason = if self.max_tokens_hit {
+            StopReason::MaxTokens
+        } else if self.tool_calls > 0 {
+            StopReason::ToolUse
+        } else {
+            StopReason::EndTurn
+        };
+        Usage {
+            input_tokens: self.prompt_tokens.saturating_sub(self.cached_tokens),
+            output_tokens: self.output_tokens,
+            cache_creation_input_tokens: 0,
+            cache_read_input_tokens: self.cached_tokens,
+            stop_reason,
+        }
+    }
+}
+
+// ── Message translation ───────────────────────────────────────────────────────
+
+/// Recover the function name from a `"{name}:{suffix}"` tool-call id.
+/// Gemini function names cannot contain `:`.
+fn function_name(id: &str) -> &str {
+    id.split_once(':').map_or(id, |(name, _)| name)
+}
+
+fn function_response_part(id: &str, name: &str, content: &str, is_error: bool) -> Value {
+    let key = if is_error { "error" } else { "content" };
+    json!({
+        "functionResponse": {
+            "id": id,
+            "name": name,
+            "response": { key: content }
+        }
+    })
+}
+
+/// Convert a message history into Gemini `contents`.
+///
+/// Messages that already carry `parts` pass through.  `{"role", "content"}`
+/// messages — plain strings or Anthropic-style blocks — are translated, and
+/// consecutive turns with the same role are merged since Gemini expects
+/// alternating user / model turns.
+pub(crate) fn to_gemini_contents(messages: &[Value]) -> Vec<Value> {
+    let mut contents: Vec<Value> = Vec::new();
+    // tool_use id → function name, for translating later tool_result blocks.
+    let mut names: HashMap<String, String> = HashMap::new();
+    for msg in messages {
+        let role = match msg["role"].as_str() {
+            Some("assistant") | Some("model") => "model",
+            _ => "user",
+        };
+        let parts: Vec<Value> = if let Some(parts) = msg["parts"].as_array() {
+            parts.clone()
+        } else {
+            match &msg["content"] {
+                Value::String(text) if !text.is_empty() => vec![json!({"text": text})],
+                Value::Array(blocks) => blocks
+                    .iter()
+                    .filter_map(|b| anthropic_block_to_part(b, &mut names))
+                    .collect(),
+                _ => Vec::new(),
+            }
+        };
+        if parts.is_empty() {
+            continue;
+        }
+        if let Some(last) = contents.last_mut()
+            && last["role"].as_str() == Some(role)
+            && let Some(existing) = last["parts"].as_array_mut()
+        {
+            existing.extend(parts);
+            continue;
+        }
+        contents.push(json!({"role": role, "parts": parts}));
+    }
+    contents
+}
+
+fn anthropic_block_to_part(block: &Value, names: &mut HashMap<String, String>) -> Option<Value> {
+    match block["type"].as_str()? {
+        "text" => {
+            let text = block["text"].as_str().filter(|t| !t.is_empty())?;
+            Some(json!({"text": text}))
+        }
+        "tool_use" => {
+            let id = block["id"].as_str().unwrap_or("");
+            let name = block["name"].as_str().unwrap_or_else(|| function_name(id));
+            names.insert(id.to_string(), name.to_string());
+            Some(json!({
+                "functionCall": {
+                    "id": id,
+                    "name": name,
+                    "args": block.get("input").cloned().unwrap_or_else(|| json!({}))
+                }
+            }))
+        }
+        "tool_result" => {
+            let id = block["tool_use_id"].as_str().unwrap_or("");
+            let content = match &block["content"] {
+                Value::String(s) => s.clone(),
+                Value::Array(items) => items
+                    .iter()
+                    .filter_map(|b| b["text"].as_str())
+                    .collect::<Vec<_>>()
+                    .join(""),
+                _ => String::new(),
+            };
+            let is_error = block["is_error"].as_bool().unwrap_or(false);
+            let name = names
+                .get(id)
+                .map(String::as_str)
+                .unwrap_or_else(|| function_name(id));
+            Some(function_response_part(id, name, &content, is_error))
+        }
+        // Thinking blocks from other providers carry foreign signatures.
+        _ => None,
+    }
+}
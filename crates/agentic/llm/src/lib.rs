@@ -1,4 +1,5 @@
-//! LLM provider abstraction: Anthropic and OpenAI with token-level streaming.
+//! LLM provider abstraction: Anthropic, OpenAI, Gemini and Bedrock with
+//! token-level streaming.
 //!
 //! # Streaming
 //!
@@ -9,8 +10,8 @@
 //!
 //! # Thinking support
 //!
-//! Both Anthropic and OpenAI use encrypted opaque blobs for reasoning
-//! continuity.  These blobs **must** be passed back verbatim during tool-use
+//! Anthropic, OpenAI and Gemini all use encrypted opaque blobs (Gemini:
+//! thought signatures) for reasoning continuity.  These blobs **must** be passed back verbatim during tool-use
 //! loops within a single FSM state via [`ContentBlock::Thinking`] /
 //! [`ContentBlock::RedactedThinking`].  They **never** cross FSM state
 //! boundaries — the orchestrator discards [`LlmOutput::raw_content_blocks`]
@@ -30,6 +31,8 @@ pub use provider::LlmProvider;
 
 mod sse;
 
+mod eventstream;
+
 mod anthropic;
 pub use anthropic::AnthropicProvider;
 
@@ -41,9 +44,24 @@ pub use openai::validate_openai_strict_schema;
 mod openai_compat;
 pub use openai_compat::OpenAiCompatProvider;
 
+mod gemini;
+pub use gemini::GeminiProvider;
+
+mod bedrock;
+pub use bedrock::BedrockProvider;
+
 mod client;
 pub use client::LlmClient;
 
+mod replay;
+pub use replay::{ReplayMode, ReplayProvider};
+
+mod fallback;
+pub use fallback::{FallbackProvider, RateLimitedProvider};
+
+mod spend;
+pub use spend::SpendTrackedProvider;
+
 mod evaluator;
 pub use evaluator::LlmConsistencyEvaluator;
 
@@ -279,9 +279,12 @@ impl LlmProvider for OpenAiProvider {
         if !status.is_success() {
             let text = response.text().await.unwrap_or_default();
             if let Ok(api_err) = serde_json::from_str::<ApiError>(&text) {
-                return Err(LlmError::Http(api_err.error.message));
+                return Err(LlmError::from_status(status, api_err.error.message));
             }
-            return Err(LlmError::Http(format!("HTTP {status}: {text}")));
+            return Err(LlmError::from_status(
+                status,
+                format!("HTTP {status}: {text}"),
+            ));
         }
 
         let s = stream! {
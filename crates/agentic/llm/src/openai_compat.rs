@@ -372,9 +372,12 @@ impl LlmProvider for OpenAiCompatProvider {
         if !status.is_success() {
             let text = response.text().await.unwrap_or_default();
             if let Ok(api_err) = serde_json::from_str::<ApiError>(&text) {
-                return Err(LlmError::Http(api_err.error.message));
+                return Err(LlmError::from_status(status, api_err.error.message));
             }
-            return Err(LlmError::Http(format!("HTTP {status}: {text}")));
+            return Err(LlmError::from_status(
+                status,
+                format!("HTTP {status}: {text}"),
+            ));
         }
 
         let enable_cot = !matches!(thinking, ThinkingConfig::Disabled);
@@ -0,0 +1,172 @@
+//! Record/replay of LLM calls for offline, deterministic test runs.
+//!
+//! [`ReplayProvider`] wraps another [`LlmProvider`].  When recording, every
+//! request is forwarded and its complete chunk stream written to
+//! `<dir>/llm/<hash>.json`; when replaying, the stream is read back from that
+//! file and the wrapped provider is never called.  `<hash>` is a digest of
+//! the normalized request, so a fixture is found again as long as the model,
+//! prompt, history, tools and output settings are unchanged.
+//!
+//! [`LlmClient::with_live_provider`] and the other constructors for real
+//! APIs wrap their provider automatically from the environment:
+//! `OXY_REPLAY_MODE=record|replay` and `OXY_REPLAY_DIR` (default
+//! `replay-fixtures`), the same variables the classic OpenAI adapter and the
+//! database connectors read.  Fixtures are stored through
+//! [`agentic_core::replay::FixtureDir`].
+//!
+//! [`LlmClient::with_live_provider`]: crate::LlmClient::with_live_provider
+
+use std::{path::PathBuf, pin::Pin, sync::Arc};
+
+use async_trait::async_trait;
+use futures_core::Stream;
+use serde_json::{Value, json};
+use tokio_stream::StreamExt;
+
+use agentic_core::replay::FixtureDir;
+pub use agentic_core::replay::ReplayMode;
+use agentic_core::tools::ToolDef;
+
+use super::{Chunk, ContentBlock, LlmError, LlmProvider, ResponseSchema, ThinkingConfig};
+
+const FIXTURE_NAMESPACE: &str = "llm";
+
+/// An [`LlmProvider`] that records or replays another provider's streams.
+pub struct ReplayProvider {
+    inner: Arc<dyn LlmProvider>,
+    fixtures: FixtureDir,
+}
+
+impl ReplayProvider {
+    pub fn new(inner: Arc<dyn LlmProvider>, mode: ReplayMode, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            fixtures: FixtureDir::new(mode, dir),
+        }
+    }
+
+    /// Wrap `inner` when `OXY_REPLAY_MODE` asks for it, otherwise return it
+    /// unchanged.
+    pub fn wrap_from_env(inner: Arc<dyn LlmProvider>) -> Arc<dyn LlmProvider> {
+        match FixtureDir::from_env() {
+            Some(fixtures) => Arc::new(Self { inner, fixtures }),
+            None => inner,
+        }
+    }
+}
+
+/// The parts of a request that determine the response.  The system date
+/// suffix is left out so fixtures survive the day changing.
+fn request_value(
+    model: &str,
+    system: &str,
+    messages: &[Value],
+    tools: &[ToolDef],
+    thinking: &ThinkingConfig,
+    response_schema: Option<&ResponseSchema>,
+    max_tokens_override: Option<u32>,
+) -> Value {
+    json!({
+        "model": model,
+        "system": system,
+        "messages": messages,
+        "tools": tools
+            .iter()
+            .map(|tool| json!({
+                "name": tool.name,
+                "description": tool.description,
+                "parameters": tool.parameters,
+                "strict": tool.strict,
+            }))
+            .collect::<Vec<_>>(),
+        "thinking": thinking_value(thinking),
+        "response_schema": response_schema.map(|schema| json!({
+            "name": schema.name,
+            "schema": schema.schema,
+        })),
+        "max_tokens": max_tokens_override,
+    })
+}
+
+fn thinking_value(thinking: &ThinkingConfig) -> Value {
+    match thinking {
+        ThinkingConfig::Disabled => json!({"type": "disabled"}),
+        ThinkingConfig::Adaptive => json!({"type": "adaptive"}),
+        ThinkingConfig::Manual { budget_tokens } => {
+            json!({"type": "manual", "budget_tokens": budget_tokens})
+        }
+        ThinkingConfig::Effort(effort) => json!({"type": "effort", "effort": effort.as_str()}),
+    }
+}
+
+#[async_trait]
+impl LlmProvider for ReplayProvider {
+    #[allow(clippy::too_many_arguments)]
+    async fn stream(
+        &self,
+        system: &str,
+        system_date_suffix: &str,
+        messages: &[Value],
+        tools: &[ToolDef],
+        thinking: &ThinkingConfig,
+        response_schema: Option<&ResponseSchema>,
+        max_tokens_override: Option<u32>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Chunk, LlmError>> + Send>>, LlmError> {
+        let request = request_value(
+            self.inner.model_name(),
+            system,
+            messages,
+            tools,
+            thinking,
+            response_schema,
+            max_tokens_override,
+        );
+        let chunks: Vec<Chunk> = match self.fixtures.mode() {
+            ReplayMode::Replay => {
+                let response = self
+                    .fixtures
+                    .load(FIXTURE_NAMESPACE, &request)
+                    .map_err(|e| LlmError::Replay(e.to_string()))?;
+                serde_json::from_value(response)
+                    .map_err(|e| LlmError::Replay(format!("invalid fixture: {e}")))?
+            }
+            ReplayMode::Record => {
+                // Read the whole stream first so only complete responses are
+                // written; errors are passed through unrecorded.
+                let mut stream = self
+                    .inner
+                    .stream(
+                        system,
+                        system_date_suffix,
+                        messages,
+                        tools,
+                        thinking,
+                        response_schema,
+                        max_tokens_override,
+                    )
+                    .await?;
+                let mut chunks = Vec::new();
+                while let Some(chunk) = stream.next().await {
+                    chunks.push(chunk?);
+                }
+                self.fixtures
+                    .save(FIXTURE_NAMESPACE, &request, json!(chunks))
+                    .map_err(|e| LlmError::Replay(e.to_string()))?;
+                chunks
+            }
+        };
+        Ok(Box::pin(tokio_stream::iter(chunks.into_iter().map(Ok))))
+    }
+
+    fn assistant_message(&self, blocks: &[ContentBlock]) -> Value {
+        self.inner.assistant_message(blocks)
+    }
+
+    fn tool_result_messages(&self, results: &[(String, String, bool)]) -> Vec<Value> {
+        self.inner.tool_result_messages(results)
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+}
@@ -0,0 +1,102 @@
+//! Budget enforcement for LLM calls.
+//!
+//! [`SpendTrackedProvider`] refuses calls once its [`SpendTracker`] reports a
+//! limit has been reached, charges it each tool call the model asks for
+//! before the call is passed on, and charges the tokens reported in
+//! [`Chunk::Done`].  A refused tool call or a call that goes over a limit
+//! ends the stream with [`LlmError::BudgetExceeded`].
+
+use std::pin::Pin;
+
+use agentic_core::spend::SharedSpendTracker;
+use agentic_core::tools::ToolDef;
+use async_stream::stream;
+use async_trait::async_trait;
+use futures_core::Stream;
+use serde_json::Value;
+use tokio_stream::StreamExt;
+
+use super::{Chunk, ContentBlock, LlmError, LlmProvider, ResponseSchema, ThinkingConfig};
+
+/// An [`LlmProvider`] that charges its calls to a [`SpendTracker`].
+///
+/// Calls are priced as the wrapped provider's [`LlmProvider::model_name`].
+///
+/// [`SpendTracker`]: agentic_core::spend::SpendTracker
+pub struct SpendTrackedProvider {
+    inner: std::sync::Arc<dyn LlmProvider>,
+    tracker: SharedSpendTracker,
+}
+
+impl SpendTrackedProvider {
+    pub fn new(inner: std::sync::Arc<dyn LlmProvider>, tracker: SharedSpendTracker) -> Self {
+        Self { inner, tracker }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for SpendTrackedProvider {
+    #[allow(clippy::too_many_arguments)]
+    async fn stream(
+        &self,
+        system: &str,
+        system_date_suffix: &str,
+        messages: &[Value],
+        tools: &[ToolDef],
+        thinking: &ThinkingConfig,
+        response_schema: Option<&ResponseSchema>,
+        max_tokens_override: Option<u32>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Chunk, LlmError>> + Send>>, LlmError> {
+        if let Some(reason) = self.tracker.exceeded() {
+            return Err(LlmError::BudgetExceeded(reason));
+        }
+        let mut inner = self
+            .inner
+            .stream(
+                system,
+                system_date_suffix,
+                messages,
+                tools,
+                thinking,
+                response_schema,
+                max_tokens_override,
+            )
+            .await?;
+        let tracker = self.tracker.clone();
+        let model = self.inner.model_name().to_string();
+        Ok(Box::pin(stream! {
+            while let Some(chunk) = inner.next().await {
+                let charged = match &chunk {
+                    Ok(Chunk::ToolCall(_)) => tracker.charge_tool_calls(1).await,
+                    Ok(Chunk::Done(usage)) => {
+                        tracker
+                            .charge_tokens(
+                                &model,
+                                usage.input_tokens as u64,
+                                usage.output_tokens as u64,
+                            )
+                            .await
+                    }
+                    _ => Ok(()),
+                };
+                if let Err(reason) = charged {
+                    yield Err(LlmError::BudgetExceeded(reason));
+                    return;
+                }
+                yield chunk;
+            }
+        }))
+    }
+
+    fn assistant_message(&self, blocks: &[ContentBlock]) -> Value {
+        self.inner.assistant_message(blocks)
+    }
+
+    fn tool_result_messages(&self, results: &[(String, String, bool)]) -> Vec<Value> {
+        self.inner.tool_result_messages(results)
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+}
@@ -3433,3 +3433,729 @@ fn build_request_body_skips_thinking_block_when_marking_last_message() {
         "marker falls back to the preceding non-thinking block"
     );
 }
+
+// ── Recorded stream replay helpers ────────────────────────────────────────
+
+/// Replay a recorded response body in `chunk_size`-byte pieces, so event
+/// boundaries land mid-chunk the way they do on a real connection.
+fn replay(
+    body: &[u8],
+    chunk_size: usize,
+) -> impl futures_core::Stream<Item = Result<Vec<u8>, std::convert::Infallible>> + Send + Unpin + 'static
+{
+    let pieces: Vec<_> = body.chunks(chunk_size).map(|c| Ok(c.to_vec())).collect();
+    tokio_stream::iter(pieces)
+}
+
+async fn collect_chunks(
+    s: impl futures_core::Stream<Item = Result<Chunk, LlmError>>,
+) -> Vec<Result<Chunk, LlmError>> {
+    use tokio_stream::StreamExt as _;
+    Box::pin(s).collect().await
+}
+
+fn ok_chunks(results: Vec<Result<Chunk, LlmError>>) -> Vec<Chunk> {
+    results
+        .into_iter()
+        .map(|r| r.expect("stream yielded an error"))
+        .collect()
+}
+
+// ── Gemini wire format ────────────────────────────────────────────────────
+
+#[test]
+fn gemini_build_request_body_maps_thinking_configs() {
+    let provider = GeminiProvider::new("k", "gemini-2.5-pro");
+    let body_for = |thinking: ThinkingConfig| {
+        provider.build_request_body("sys", "", &[], &[], &thinking, None, None)
+    };
+
+    let disabled = body_for(ThinkingConfig::Disabled);
+    assert!(disabled["generationConfig"]["thinkingConfig"].is_null());
+    assert_eq!(disabled["generationConfig"]["maxOutputTokens"], 4096);
+
+    let adaptive = body_for(ThinkingConfig::Adaptive);
+    assert_eq!(
+        adaptive["generationConfig"]["thinkingConfig"],
+        json!({"includeThoughts": true, "thinkingBudget": -1})
+    );
+    assert_eq!(adaptive["generationConfig"]["maxOutputTokens"], 16384);
+
+    let manual = body_for(ThinkingConfig::Manual {
+        budget_tokens: 20000,
+    });
+    assert_eq!(
+        manual["generationConfig"]["thinkingConfig"]["thinkingBudget"],
+        20000
+    );
+    assert_eq!(manual["generationConfig"]["maxOutputTokens"], 24096);
+
+    let effort = body_for(ThinkingConfig::Effort(ReasoningEffort::Low));
+    assert_eq!(
+        effort["generationConfig"]["thinkingConfig"]["thinkingBudget"],
+        1024
+    );
+
+    assert_eq!(
+        disabled["systemInstruction"],
+        json!({"parts": [{"text": "sys"}]})
+    );
+}
+
+#[test]
+fn gemini_build_request_body_uses_response_json_schema_without_tools() {
+    let provider = GeminiProvider::new("k", "gemini-2.5-flash");
+    let schema = ResponseSchema {
+        name: "answer".into(),
+        schema: json!({"type": "object", "properties": {"region": {"type": "string"}}}),
+    };
+    let body = provider.build_request_body(
+        "",
+        "",
+        &[json!({"role": "user", "content": "top region?"})],
+        &[],
+        &ThinkingConfig::Disabled,
+        Some(&schema),
+        None,
+    );
+    assert_eq!(
+        body["generationConfig"]["responseMimeType"],
+        "application/json"
+    );
+    assert_eq!(
+        body["generationConfig"]["responseJsonSchema"],
+        schema.schema
+    );
+    assert!(body["tools"].is_null());
+    assert!(body["systemInstruction"].is_null());
+}
+
+#[test]
+fn gemini_build_request_body_adds_schema_function_alongside_tools() {
+    let provider = GeminiProvider::new("k", "gemini-2.5-flash");
+    let schema = ResponseSchema {
+        name: "answer".into(),
+        schema: json!({"type": "object"}),
+    };
+    let tool = ToolDef {
+        name: "search_catalog",
+        description: "Search the semantic catalog.",
+        parameters: json!({"type": "object", "properties": {"query": {"type": "string"}}}),
+        ..Default::default()
+    };
+    let body = provider.build_request_body(
+        "",
+        "",
+        &[],
+        &[tool],
+        &ThinkingConfig::Disabled,
+        Some(&schema),
+        None,
+    );
+    let declarations = body["tools"][0]["functionDeclarations"].as_array().unwrap();
+    assert_eq!(declarations.len(), 2);
+    assert_eq!(declarations[0]["name"], "search_catalog");
+    assert_eq!(
+        declarations[0]["parametersJsonSchema"]["properties"]["query"]["type"],
+        "string"
+    );
+    assert_eq!(declarations[1]["name"], "answer");
+    assert!(body["generationConfig"]["responseJsonSchema"].is_null());
+}
+
+/// History built by other providers (plain strings, Anthropic tool blocks)
+/// is translated into Gemini `contents`, and consecutive user turns merge.
+#[test]
+fn gemini_translates_anthropic_history_into_contents() {
+    let messages = vec![
+        json!({"role": "user", "content": "How many orders?"}),
+        json!({"role": "assistant", "content": [
+            {"type": "thinking", "thinking": "...", "signature": "sig"},
+            {"type": "text", "text": "Let me check."},
+            {"type": "tool_use", "id": "toolu_1", "name": "execute_sql", "input": {"sql": "SELECT 1"}}
+        ]}),
+        json!({"role": "user", "content": [
+            {"type": "tool_result", "tool_use_id": "toolu_1", "content": "42", "is_error": false}
+        ]}),
+        json!({"role": "user", "content": "Continue."}),
+    ];
+    let provider = GeminiProvider::new("k", "gemini-2.5-pro");
+    let body = provider.build_request_body(
+        "",
+        "",
+        &messages,
+        &[],
+        &ThinkingConfig::Disabled,
+        None,
+        None,
+    );
+    assert_eq!(
+        body["contents"],
+        json!([
+            {"role": "user", "parts": [{"text": "How many orders?"}]},
+            {"role": "model", "parts": [
+                {"text": "Let me check."},
+                {"functionCall": {"id": "toolu_1", "name": "execute_sql", "args": {"sql": "SELECT 1"}}}
+            ]},
+            {"role": "user", "parts": [
+                {"functionResponse": {"id": "toolu_1", "name": "execute_sql", "response": {"content": "42"}}},
+                {"text": "Continue."}
+            ]}
+        ])
+    );
+}
+
+// ── Gemini stream decoding (recorded fixtures) ────────────────────────────
+
+#[tokio::test]
+async fn gemini_stream_decodes_thinking_then_text() {
+    let body = include_bytes!("../tests/fixtures/gemini_thinking_text.sse");
+    let chunks = ok_chunks(collect_chunks(super::gemini::decode_gemini_sse(replay(body, 7))).await);
+
+    assert!(matches!(&chunks[0], Chunk::ThinkingSummary(t) if t.is_empty()));
+    assert!(matches!(&chunks[1], Chunk::ThinkingSummary(t) if t.contains("top region")));
+    assert!(matches!(&chunks[2], Chunk::Text(t) if t.is_empty()));
+    let text: String = chunks
+        .iter()
+        .filter_map(|c| match c {
+            Chunk::Text(t) => Some(t.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(text, "The West region led Q3 with $1.2M in revenue.");
+
+    let Some(Chunk::RawBlock(ContentBlock::Thinking { provider_data })) =
+        chunks.iter().find(|c| matches!(c, Chunk::RawBlock(_)))
+    else {
+        panic!("expected a thought-signature raw block");
+    };
+    assert_eq!(provider_data["thought"], true);
+    assert!(
+        provider_data["thoughtSignature"]
+            .as_str()
+            .unwrap()
+            .starts_with("CiQB")
+    );
+
+    let Some(Chunk::Done(usage)) = chunks.last() else {
+        panic!("stream must end with Done");
+    };
+    assert_eq!(usage.input_tokens, 156);
+    assert_eq!(usage.cache_read_input_tokens, 256);
+    assert_eq!(usage.output_tokens, 132);
+    assert_eq!(usage.stop_reason, StopReason::EndTurn);
+}
+
+#[tokio::test]
+async fn gemini_stream_decodes_function_calls_and_round_trips_them() {
+    let body = include_bytes!("../tests/fixtures/gemini_tool_calls.sse");
+    let chunks =
+        ok_chunks(collect_chunks(super::gemini::decode_gemini_sse(replay(body, 13))).await);
+
+    let calls: Vec<&ToolCallChunk> = chunks
+        .iter()
+        .filter_map(|c| match c {
+            Chunk::ToolCall(tc) => Some(tc),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0].id, "search_catalog:0");
+    assert_eq!(calls[0].input, json!({"query": "orders"}));
+    assert_eq!(calls[1].id, "list_tables:1");
+    let Some(Chunk::Done(usage)) = chunks.last() else {
+        panic!("stream must end with Done");
+    };
+    assert_eq!(usage.stop_reason, StopReason::ToolUse);
+
+    // The assistant turn keeps the thought signature and carries our ids.
+    let provider = GeminiProvider::new("k", "gemini-2.5-flash");
+    let blocks: Vec<ContentBlock> = calls
+        .iter()
+        .map(|tc| ContentBlock::ToolUse {
+            id: tc.id.clone(),
+            name: tc.name.clone(),
+            input: tc.input.clone(),
+            provider_data: tc.provider_data.clone(),
+        })
+        .collect();
+    let assistant = provider.assistant_message(&blocks);
+    assert_eq!(assistant["role"], "model");
+    assert!(assistant["parts"][0]["thoughtSignature"].is_string());
+    assert_eq!(
+        assistant["parts"][0]["functionCall"]["id"],
+        "search_catalog:0"
+    );
+
+    let messages = vec![assistant];
+    assert_eq!(
+        super::client::find_all_unmatched_tool_ids(&messages),
+        vec!["search_catalog:0".to_string(), "list_tables:1".to_string()]
+    );
+
+    let results = provider.tool_result_messages(&[
+        (
+            "search_catalog:0".into(),
+            "orders(id, amount)".into(),
+            false,
+        ),
+        ("list_tables:1".into(), "permission denied".into(), true),
+    ]);
+    assert_eq!(
+        results[0]["parts"],
+        json!([
+            {"functionResponse": {"id": "search_catalog:0", "name": "search_catalog", "response": {"content": "orders(id, amount)"}}},
+            {"functionResponse": {"id": "list_tables:1", "name": "list_tables", "response": {"error": "permission denied"}}}
+        ])
+    );
+}
+
+#[tokio::test]
+async fn gemini_stream_reports_max_tokens() {
+    let body = include_bytes!("../tests/fixtures/gemini_max_tokens.sse");
+    let chunks =
+        ok_chunks(collect_chunks(super::gemini::decode_gemini_sse(replay(body, 64))).await);
+    let Some(Chunk::Done(usage)) = chunks.last() else {
+        panic!("stream must end with Done");
+    };
+    assert_eq!(usage.stop_reason, StopReason::MaxTokens);
+    assert_eq!(usage.output_tokens, 16);
+}
+
+#[tokio::test]
+async fn gemini_stream_surfaces_inline_errors() {
+    let body = b"data: {\"error\":{\"code\":503,\"message\":\"The model is overloaded.\",\"status\":\"UNAVAILABLE\"}}\r\n\r\n";
+    let results = collect_chunks(super::gemini::decode_gemini_sse(replay(body, 32))).await;
+    assert_eq!(results.len(), 1);
+    assert!(matches!(&results[0], Err(LlmError::Http(m)) if m == "The model is overloaded."));
+}
+
+// ── Bedrock wire format ───────────────────────────────────────────────────
+
+#[test]
+fn bedrock_build_request_body_moves_version_and_beta_into_body() {
+    let provider = BedrockProvider::new(
+        "us.anthropic.claude-sonnet-4-5-20250929-v1:0",
+        Some("us-west-2".into()),
+    );
+    let body = provider.build_request_body(
+        "sys",
+        "",
+        &[json!({"role": "user", "content": "hi"})],
+        &[],
+        &ThinkingConfig::Adaptive,
+        None,
+        None,
+    );
+    assert!(body.get("model").is_none());
+    assert!(body.get("stream").is_none());
+    assert_eq!(body["anthropic_version"], "bedrock-2023-05-31");
+    assert_eq!(
+        body["anthropic_beta"],
+        json!(["interleaved-thinking-2025-05-14"])
+    );
+    assert_eq!(body["thinking"], json!({"type": "adaptive"}));
+    assert_eq!(
+        body["system"][0]["cache_control"],
+        json!({"type": "ephemeral"})
+    );
+
+    let plain =
+        provider.build_request_body("", "", &[], &[], &ThinkingConfig::Disabled, None, None);
+    assert!(plain.get("anthropic_beta").is_none());
+}
+
+// ── Bedrock stream decoding (recorded fixtures) ───────────────────────────
+
+/// Encode one AWS event-stream frame with string headers.  CRCs are zeroed;
+/// the decoder does not check them.
+fn event_frame(headers: &[(&str, &str)], payload: &[u8]) -> Vec<u8> {
+    let mut encoded_headers = Vec::new();
+    for (name, value) in headers {
+        encoded_headers.push(name.len() as u8);
+        encoded_headers.extend_from_slice(name.as_bytes());
+        encoded_headers.push(7);
+        encoded_headers.extend_from_slice(&(value.len() as u16).to_be_bytes());
+        encoded_headers.extend_from_slice(value.as_bytes());
+    }
+    let total = 12 + encoded_headers.len() + payload.len() + 4;
+    let mut frame = Vec::with_capacity(total);
+    frame.extend_from_slice(&(total as u32).to_be_bytes());
+    frame.extend_from_slice(&(encoded_headers.len() as u32).to_be_bytes());
+    frame.extend_from_slice(&[0; 4]);
+    frame.extend_from_slice(&encoded_headers);
+    frame.extend_from_slice(payload);
+    frame.extend_from_slice(&[0; 4]);
+    frame
+}
+
+/// Wrap each recorded Anthropic event the way Bedrock does: base64 in a
+/// `{"bytes": …}` envelope inside a `chunk` event frame.
+fn bedrock_body(events_jsonl: &str) -> Vec<u8> {
+    use base64::Engine as _;
+    events_jsonl
+        .lines()
+        .filter(|l| !l.is_empty())
+        .flat_map(|event| {
+            let payload = json!({
+                "bytes": base64::engine::general_purpose::STANDARD.encode(event),
+                "p": "abcdefghijklmnopqrstuvwxyzABCDEF"
+            });
+            event_frame(
+                &[
+                    (":event-type", "chunk"),
+                    (":content-type", "application/json"),
+                    (":message-type", "event"),
+                ],
+                payload.to_string().as_bytes(),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn bedrock_stream_decodes_wrapped_anthropic_events() {
+    let body = bedrock_body(include_str!("../tests/fixtures/bedrock_tool_use.jsonl"));
+    let chunks = ok_chunks(
+        collect_chunks(super::bedrock::decode_bedrock_event_stream(replay(
+            &body, 5,
+        )))
+        .await,
+    );
+
+    assert!(matches!(&chunks[0], Chunk::ThinkingSummary(t) if t.is_empty()));
+    assert!(
+        matches!(&chunks[1], Chunk::ThinkingSummary(t) if t == "Need the orders table schema.")
+    );
+    let Chunk::RawBlock(ContentBlock::Thinking { provider_data }) = &chunks[2] else {
+        panic!("expected the thinking blob, got {:?}", chunks[2]);
+    };
+    assert_eq!(provider_data["signature"], "EqQBCkYIBxgCKkB0c2lnbmF0dXJl");
+    assert!(
+        chunks
+            .iter()
+            .any(|c| matches!(c, Chunk::Text(t) if t == "Checking the catalog."))
+    );
+
+    let Some(Chunk::ToolCall(call)) = chunks.iter().find(|c| matches!(c, Chunk::ToolCall(_)))
+    else {
+        panic!("expected a tool call");
+    };
+    assert_eq!(call.id, "toolu_bdrk_01Kq7rM2");
+    assert_eq!(call.input, json!({"query": "orders"}));
+
+    let Some(Chunk::Done(usage)) = chunks.last() else {
+        panic!("stream must end with Done");
+    };
+    assert_eq!(usage.input_tokens, 1520);
+    assert_eq!(usage.cache_read_input_tokens, 1024);
+    assert_eq!(usage.output_tokens, 87);
+    assert_eq!(usage.stop_reason, StopReason::ToolUse);
+}
+
+#[tokio::test]
+async fn bedrock_stream_maps_throttling_exception_to_rate_limit() {
+    let body = event_frame(
+        &[
+            (":exception-type", "throttlingException"),
+            (":content-type", "application/json"),
+            (":message-type", "exception"),
+        ],
+        br#"{"message":"Too many requests, please wait before trying again."}"#,
+    );
+    let results = collect_chunks(super::bedrock::decode_bedrock_event_stream(replay(
+        &body, 9,
+    )))
+    .await;
+    assert_eq!(results.len(), 1);
+    assert!(
+        matches!(&results[0], Err(LlmError::RateLimit(m)) if m.starts_with("throttlingException"))
+    );
+}
+
+// ── ReplayProvider ────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn replay_provider_returns_recorded_stream() {
+    let dir = tempfile::tempdir().unwrap();
+    let messages = vec![json!({"role": "user", "content": "How many orders?"})];
+    let collect_text = |provider: ReplayProvider, suffix: &'static str, messages: Vec<Value>| async move {
+        let mut stream = provider
+            .stream(
+                "system",
+                suffix,
+                &messages,
+                &[],
+                &ThinkingConfig::Disabled,
+                None,
+                None,
+            )
+            .await?;
+        let mut text = String::new();
+        while let Some(chunk) = tokio_stream::StreamExt::next(&mut stream).await {
+            if let Chunk::Text(t) = chunk? {
+                text.push_str(&t);
+            }
+        }
+        Ok::<_, LlmError>(text)
+    };
+
+    let live = MockProvider::new(vec![vec![
+        Ok(Chunk::Text("42 ".into())),
+        Ok(Chunk::Text("orders".into())),
+        Ok(Chunk::Done(Usage::default())),
+    ]]);
+    let recorder = ReplayProvider::new(Arc::new(live), ReplayMode::Record, dir.path());
+    let recorded = collect_text(recorder, "Today is 2025-01-01.", messages.clone())
+        .await
+        .unwrap();
+    assert_eq!(recorded, "42 orders");
+
+    // The wrapped provider has nothing left to return, so any text must come
+    // from the fixture. A different date suffix still hits the same fixture.
+    let replayer = || {
+        ReplayProvider::new(
+            Arc::new(MockProvider::new(vec![])),
+            ReplayMode::Replay,
+            dir.path(),
+        )
+    };
+    let replayed = collect_text(replayer(), "Today is 2025-01-02.", messages)
+        .await
+        .unwrap();
+    assert_eq!(replayed, "42 orders");
+
+    let changed = vec![json!({"role": "user", "content": "How many customers?"})];
+    assert!(matches!(
+        collect_text(replayer(), "", changed).await,
+        Err(LlmError::Replay(_))
+    ));
+}
+
+// ── FallbackProvider ──────────────────────────────────────────────────────
+
+/// A provider whose stream never opens, counting how often it was asked.
+struct FailingMockProvider {
+    calls: std::sync::atomic::AtomicUsize,
+    error: fn() -> LlmError,
+}
+
+#[async_trait::async_trait]
+impl LlmProvider for FailingMockProvider {
+    async fn stream(
+        &self,
+        _system: &str,
+        _system_date_suffix: &str,
+        _messages: &[Value],
+        _tools: &[ToolDef],
+        _thinking: &ThinkingConfig,
+        _response_schema: Option<&ResponseSchema>,
+        _max_tokens_override: Option<u32>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Chunk, LlmError>> + Send>>, LlmError> {
+        self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Err((self.error)())
+    }
+
+    fn assistant_message(&self, _blocks: &[ContentBlock]) -> Value {
+        json!({"role": "assistant", "content": []})
+    }
+
+    fn tool_result_messages(&self, _results: &[(String, String, bool)]) -> Vec<Value> {
+        vec![]
+    }
+
+    fn model_name(&self) -> &str {
+        "mock-primary"
+    }
+}
+
+#[tokio::test]
+async fn fallback_provider_fails_over_and_skips_open_circuits() {
+    let primary = Arc::new(FailingMockProvider {
+        calls: Default::default(),
+        error: || LlmError::RateLimit("429 Too Many Requests".into()),
+    });
+    let fallback = MockProvider::new(vec![
+        vec![
+            Ok(Chunk::Text("from fallback".into())),
+            Ok(Chunk::Done(Usage::default())),
+        ],
+        vec![
+            Ok(Chunk::Text("still fallback".into())),
+            Ok(Chunk::Done(Usage::default())),
+        ],
+    ]);
+    let breaker =
+        agentic_core::circuit_breaker::CircuitBreaker::new(1, std::time::Duration::from_secs(60));
+    let client = LlmClient::with_provider(
+        FallbackProvider::new("primary", primary.clone())
+            .with_fallback("fallback", Arc::new(fallback))
+            .with_breaker(breaker.clone()),
+    );
+
+    assert_eq!(
+        client.complete("system", "hi").await.unwrap(),
+        "from fallback"
+    );
+    assert_eq!(breaker.status("primary"), "open");
+
+    // The open circuit keeps the primary from being called again
+    assert_eq!(
+        client.complete("system", "hi").await.unwrap(),
+        "still fallback"
+    );
+    assert_eq!(primary.calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn fallback_provider_returns_rejected_requests_without_failing_over() {
+    let primary = Arc::new(FailingMockProvider {
+        calls: Default::default(),
+        error: || LlmError::Request("prompt is too long".into()),
+    });
+    let fallback = MockProvider::new(vec![vec![
+        Ok(Chunk::Text("from fallback".into())),
+        Ok(Chunk::Done(Usage::default())),
+    ]]);
+    let breaker =
+        agentic_core::circuit_breaker::CircuitBreaker::new(1, std::time::Duration::from_secs(60));
+    let client = LlmClient::with_provider(
+        FallbackProvider::new("primary", primary.clone())
+            .with_fallback("fallback", Arc::new(fallback))
+            .with_breaker(breaker.clone()),
+    );
+
+    assert!(matches!(
+        client.complete("system", "hi").await,
+        Err(LlmError::Request(_))
+    ));
+    assert_eq!(breaker.status("primary"), "closed");
+}
+
+#[test]
+fn llm_errors_are_classified_by_status() {
+    use reqwest::StatusCode;
+
+    for status in [
+        StatusCode::BAD_REQUEST,
+        StatusCode::NOT_FOUND,
+        StatusCode::PAYLOAD_TOO_LARGE,
+    ] {
+        let err = LlmError::from_status(status, "rejected".into());
+        assert!(!err.is_transient(), "{status}");
+    }
+    for status in [
+        StatusCode::REQUEST_TIMEOUT,
+        StatusCode::INTERNAL_SERVER_ERROR,
+        StatusCode::SERVICE_UNAVAILABLE,
+    ] {
+        let err = LlmError::from_status(status, "failed".into());
+        assert!(err.is_transient(), "{status}");
+    }
+    assert!(LlmError::RateLimit("slow down".into()).is_transient());
+    assert!(!LlmError::Auth("bad key".into()).is_transient());
+}
+
+// ── Spend tracking ────────────────────────────────────────────────────────
+
+/// A [`SpendTracker`] with a token limit and a tool-call limit.
+///
+/// [`SpendTracker`]: agentic_core::spend::SpendTracker
+#[derive(Default)]
+struct MockSpendTracker {
+    max_tokens: u64,
+    max_tool_calls: u64,
+    tokens: Mutex<u64>,
+    tool_calls: Mutex<u64>,
+}
+
+#[async_trait::async_trait]
+impl agentic_core::spend::SpendTracker for MockSpendTracker {
+    fn exceeded(&self) -> Option<String> {
+        (*self.tokens.lock().unwrap() > self.max_tokens).then(|| "tokens spent".to_string())
+    }
+
+    async fn charge_tokens(&self, _model: &str, input: u64, output: u64) -> Result<(), String> {
+        let mut tokens = self.tokens.lock().unwrap();
+        *tokens += input + output;
+        match *tokens > self.max_tokens {
+            true => Err(format!("{} tokens", *tokens)),
+            false => Ok(()),
+        }
+    }
+
+    async fn charge_tool_calls(&self, count: u64) -> Result<(), String> {
+        let mut calls = self.tool_calls.lock().unwrap();
+        if *calls + count > self.max_tool_calls {
+            return Err("too many tool calls".to_string());
+        }
+        *calls += count;
+        Ok(())
+    }
+}
+
+fn text_round(text: &str, input_tokens: usize) -> Vec<Result<Chunk, LlmError>> {
+    vec![
+        Ok(Chunk::Text(text.into())),
+        Ok(Chunk::Done(Usage {
+            input_tokens,
+            output_tokens: 10,
+            ..Default::default()
+        })),
+    ]
+}
+
+#[tokio::test]
+async fn spend_tracker_stops_calls_once_spent() {
+    let tracker = Arc::new(MockSpendTracker {
+        max_tokens: 100,
+        ..Default::default()
+    });
+    let client = LlmClient::with_provider(MockProvider::new(vec![
+        text_round("first", 50),
+        text_round("second", 50),
+        text_round("third", 50),
+    ]))
+    .with_spend_tracker(tracker.clone());
+
+    assert_eq!(client.complete("system", "hi").await.unwrap(), "first");
+    assert!(matches!(
+        client.complete("system", "hi").await,
+        Err(LlmError::BudgetExceeded(msg)) if msg == "120 tokens"
+    ));
+    // Refused before the provider is called, so nothing more is charged
+    assert!(matches!(
+        client.complete("system", "hi").await,
+        Err(LlmError::BudgetExceeded(msg)) if msg == "tokens spent"
+    ));
+    assert_eq!(*tracker.tokens.lock().unwrap(), 120);
+}
+
+#[tokio::test]
+async fn spend_tracker_refuses_tool_calls_over_the_limit() {
+    let tracker = Arc::new(MockSpendTracker {
+        max_tokens: 1_000,
+        max_tool_calls: 1,
+        ..Default::default()
+    });
+    let tool_call = |id: &str| {
+        Ok(Chunk::ToolCall(ToolCallChunk {
+            id: id.into(),
+            name: "dry_run".into(),
+            input: json!({"sql": "SELECT 1"}),
+            provider_data: None,
+        }))
+    };
+    let client = LlmClient::with_provider(MockProvider::new(vec![vec![
+        tool_call("tc1"),
+        tool_call("tc2"),
+        Ok(Chunk::Done(Usage::default())),
+    ]]))
+    .with_spend_tracker(tracker.clone());
+
+    assert!(matches!(
+        client.complete("system", "hi").await,
+        Err(LlmError::BudgetExceeded(msg)) if msg == "too many tool calls"
+    ));
+    assert_eq!(*tracker.tool_calls.lock().unwrap(), 1);
+}
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 // ── InitialMessages ───────────────────────────────────────────────────────────
@@ -74,6 +75,10 @@ impl ReasoningEffort {
 /// | `Adaptive` | Claude 4.6+ (model decides when/how much to think) |
 /// | `Manual` | Claude 3.x / earlier Claude 4 (explicit token budget) |
 /// | `Effort` | OpenAI o-series (low / medium / high effort) |
+///
+/// Gemini maps every variant onto `thinkingConfig.thinkingBudget`:
+/// `Adaptive` → dynamic (`-1`), `Manual` → the budget, `Effort` → a fixed
+/// budget per level.
 #[derive(Debug, Clone, Default)]
 pub enum ThinkingConfig {
     /// No extended thinking (default).
@@ -98,6 +103,7 @@ pub enum ThinkingConfig {
 /// decoding mechanism to guarantee the response matches this schema:
 /// - **Anthropic**: native `output_config.format` (structured outputs API).
 /// - **OpenAI**: `response_format: {type: "json_schema", ...}` with `strict: true`.
+/// - **Gemini**: `responseMimeType: application/json` + `responseJsonSchema`.
 #[derive(Debug, Clone)]
 pub struct ResponseSchema {
     /// Machine-readable name for the response schema (e.g. `"clarify_response"`).
@@ -120,7 +126,7 @@ pub struct ResponseSchema {
 /// discards [`LlmOutput::raw_content_blocks`] when transitioning between
 /// states.  Only the human-readable thinking summary (carried by
 /// [`CoreEvent::ThinkingToken`] events) may be retained for logging / display.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ContentBlock {
     /// Extended thinking block.  Contains the provider's encrypted blob
     /// (Anthropic: `signature`; OpenAI: `encrypted_content`).
@@ -150,7 +156,7 @@ pub enum ContentBlock {
 ///
 /// Emitted once per tool call after the full input JSON has been accumulated
 /// from the stream.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolCallChunk {
     pub id: String,
     pub name: String,
@@ -162,7 +168,7 @@ pub struct ToolCallChunk {
 }
 
 /// A single item in the streaming output from an [`LlmProvider`].
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum Chunk {
     /// Human-readable thinking text (Anthropic thinking block / OpenAI
     /// reasoning summary).  May arrive in multiple chunks.  Encrypted blobs
@@ -182,7 +188,7 @@ pub enum Chunk {
 // ── Output types ──────────────────────────────────────────────────────────────
 
 /// Why the model stopped generating.
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum StopReason {
     /// Normal completion — the model emitted a stop token.
     #[default]
@@ -194,7 +200,7 @@ pub enum StopReason {
 }
 
 /// Token usage reported by the provider for a single completion.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Usage {
     pub input_tokens: usize,
     pub output_tokens: usize,
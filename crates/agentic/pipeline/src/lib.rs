@@ -38,7 +38,7 @@ pub use agentic_core::human_input::{
     AutoAcceptInputProvider, HumanInputHandle, HumanInputProvider,
 };
 pub use agentic_llm::LlmClient;
-pub use agentic_llm::{AnthropicProvider, OpenAiProvider};
+pub use agentic_llm::{AnthropicProvider, BedrockProvider, GeminiProvider, OpenAiProvider};
 pub use agentic_workflow::WorkflowMigrator;
 
 // ── ThinkingMode ────────────────────────────────────────────────────────────
@@ -342,6 +342,16 @@ impl PipelineBuilder {
                 .await?;
         }
 
+        let field_restrictions = self
+            .platform
+            .semantic_field_restrictions()
+            .map_err(PipelineError::Config)?;
+        let spend_tracker = self
+            .platform
+            .spend_tracker()
+            .await
+            .map_err(PipelineError::Config)?;
+
         // Resolve project model + connectors via the platform port.
         let project_model = self
             .platform
@@ -405,6 +415,8 @@ impl PipelineBuilder {
             use_extended_thinking: self.thinking_mode.is_extended(),
             procedure_runner,
             metric_sink: self.platform.metric_sink(),
+            field_restrictions,
+            spend_tracker,
         };
 
         // Start pipeline.
@@ -443,6 +455,16 @@ impl PipelineBuilder {
         let config = AgentConfig::from_file(&config_path)
             .map_err(|e| PipelineError::Config(format!("{e}")))?;
 
+        let field_restrictions = self
+            .platform
+            .semantic_field_restrictions()
+            .map_err(PipelineError::Config)?;
+        let spend_tracker = self
+            .platform
+            .spend_tracker()
+            .await
+            .map_err(PipelineError::Config)?;
+
         // Resolve project model + connectors via the platform port.
         let project_model = self
             .platform
@@ -505,6 +527,8 @@ impl PipelineBuilder {
             use_extended_thinking: self.thinking_mode.is_extended(),
             procedure_runner,
             metric_sink: self.platform.metric_sink(),
+            field_restrictions,
+            spend_tracker,
         };
 
         let handle = agentic_analytics::resume_pipeline(params, resume_data, answer)
@@ -535,6 +559,7 @@ impl PipelineBuilder {
 
         // Resolve model + API key (same as start_builder).
         let client = build_builder_llm_client(&*self.platform, model).await;
+        let client = with_spend_tracker(client, &*self.platform).await?;
 
         // Thread history.
         let history: Vec<agentic_builder::ConversationTurn> = if let Some(tid) = self.thread_id {
@@ -623,6 +648,7 @@ impl PipelineBuilder {
             Some(c) => c,
             None => build_builder_llm_client(&*self.platform, model).await,
         };
+        let client = with_spend_tracker(client, &*self.platform).await?;
 
         // Thread history.
         let history: Vec<agentic_builder::ConversationTurn> = if let Some(tid) = self.thread_id {
@@ -695,6 +721,19 @@ async fn build_builder_llm_client(ctx: &dyn ProjectContext, model: Option<String
     LlmClient::with_model("", model.unwrap_or_default())
 }
 
+/// Charge `client`'s calls to the host's budget, when it enforces one.
+async fn with_spend_tracker(
+    client: LlmClient,
+    ctx: &dyn ProjectContext,
+) -> Result<LlmClient, PipelineError> {
+    Ok(
+        match ctx.spend_tracker().await.map_err(PipelineError::Config)? {
+            Some(tracker) => client.with_spend_tracker(tracker),
+            None => client,
+        },
+    )
+}
+
 // ── StartedPipeline (type-erased) ───────────────────────────────────────────
 
 /// A started pipeline with type-erased domain events.
@@ -1290,6 +1329,7 @@ pub async fn run_agentic_eval(
     })?;
 
     let mut ctx = BuildContext::default();
+    ctx.field_restrictions = platform.semantic_field_restrictions()?;
     ctx.project_model_info = platform
         .resolve_model(config.llm.model_ref.as_deref(), config.llm.model.is_some())
         .await;
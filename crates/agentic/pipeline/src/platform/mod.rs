@@ -19,14 +19,17 @@
 
 use std::sync::Arc;
 
-use agentic_analytics::SharedMetricSink;
-use agentic_analytics::config::{LlmVendor, ResolvedModelInfo};
+use agentic_analytics::config::{LlmVendor, ResolvedModelInfo, with_model_routing};
+use agentic_analytics::{FieldRestriction, SharedMetricSink};
 use agentic_builder::{
     BuilderDatabaseProvider, BuilderProjectValidator, BuilderSchemaProvider,
     BuilderSecretsProvider, BuilderSemanticCompiler,
 };
 use agentic_connector::{ConnectorConfig, DatabaseConnector};
-use agentic_llm::{LlmClient, OpenAiCompatProvider, OpenAiProvider};
+use agentic_core::spend::SharedSpendTracker;
+use agentic_llm::{
+    BedrockProvider, GeminiProvider, LlmClient, OpenAiCompatProvider, OpenAiProvider,
+};
 use agentic_workflow::WorkspaceContext;
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -71,6 +74,24 @@ pub trait ProjectContext: Send + Sync {
     fn metric_sink(&self) -> Option<SharedMetricSink> {
         None
     }
+
+    /// Column-level restrictions on semantic fields for the current caller,
+    /// keyed by `view.field`. An `Err` aborts the run rather than exposing
+    /// fields whose rules could not be read.
+    ///
+    /// Default impl returns an empty map, for hosts without column access
+    /// rules.
+    fn semantic_field_restrictions(&self) -> Result<HashMap<String, FieldRestriction>, String> {
+        Ok(HashMap::new())
+    }
+
+    /// Budget that the run's LLM calls and tool calls are charged to. An
+    /// `Err` aborts the run rather than running it without its spend caps.
+    ///
+    /// Default impl returns `None`, for hosts without budgets.
+    async fn spend_tracker(&self) -> Result<Option<SharedSpendTracker>, String> {
+        Ok(None)
+    }
 }
 
 /// Thread-ownership lookup for transport-layer auth checks.
@@ -147,14 +168,21 @@ pub async fn resolve_connectors(
 /// Azure OpenAI models are detected via `azure_deployment_id` / `azure_api_version`
 /// and routed to [`OpenAiCompatProvider`] (Chat Completions) with the correct
 /// deployment URL, bypassing the Responses API used by [`OpenAiProvider`].
+///
+/// The model's `rate_limit` and `fallbacks` from `config.yml` are applied via
+/// [`with_model_routing`].
 pub fn build_llm_client(info: &ResolvedModelInfo) -> LlmClient {
+    with_model_routing(build_vendor_client(info), info)
+}
+
+fn build_vendor_client(info: &ResolvedModelInfo) -> LlmClient {
     let api_key = info.api_key.as_deref().unwrap_or("");
     if let (Some(deployment_id), Some(api_version), Some(base_url)) = (
         info.azure_deployment_id.as_deref(),
         info.azure_api_version.as_deref(),
         info.base_url.as_deref(),
     ) {
-        return LlmClient::with_provider(OpenAiCompatProvider::for_azure(
+        return LlmClient::with_live_provider(OpenAiCompatProvider::for_azure(
             api_key,
             &info.model,
             base_url,
@@ -184,14 +212,30 @@ pub fn build_llm_client(info: &ResolvedModelInfo) -> LlmClient {
             } else {
                 OpenAiProvider::new(api_key, &info.model)
             };
-            LlmClient::with_provider(provider)
+            LlmClient::with_live_provider(provider)
         }
         LlmVendor::OpenAiCompat => {
             let url = info
                 .base_url
                 .as_deref()
                 .unwrap_or("http://localhost:11434/v1");
-            LlmClient::with_provider(OpenAiCompatProvider::new(api_key, &info.model, url))
+            LlmClient::with_live_provider(OpenAiCompatProvider::new(api_key, &info.model, url))
+        }
+        LlmVendor::Gemini => {
+            let provider = if let Some(url) = &info.base_url {
+                GeminiProvider::with_base_url(api_key, &info.model, url)
+            } else {
+                GeminiProvider::new(api_key, &info.model)
+            };
+            LlmClient::with_live_provider(provider)
+        }
+        LlmVendor::Bedrock => {
+            let provider = if let Some(url) = &info.base_url {
+                BedrockProvider::with_base_url(&info.model, None, url)
+            } else {
+                BedrockProvider::new(&info.model, None)
+            };
+            LlmClient::with_live_provider(provider)
         }
     }
 }
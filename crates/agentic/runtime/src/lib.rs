@@ -9,7 +9,6 @@
 //! the `EventRegistry` pattern.
 
 pub mod bridge;
-pub mod circuit_breaker;
 pub mod coordinator;
 pub mod crud;
 pub mod entity;
@@ -19,3 +18,6 @@ pub mod migration;
 pub mod state;
 pub mod transport;
 pub mod worker;
+
+// Lives in agentic-core so the LLM clients can share it.
+pub use agentic_core::circuit_breaker;
@@ -86,7 +86,7 @@ pub async fn build_adapter_from_db(
         _ => anyhow::bail!(
             "Database type '{}' is not yet supported for airform modeling. \
              Supported: snowflake, bigquery, duckdb, postgres, redshift, mysql, clickhouse, motherduck. \
-             Unsupported: domo.",
+             Unsupported: domo, mssql, trino, sqlite.",
             db.database_type_name()
         ),
     }
@@ -751,6 +751,7 @@ mod tests {
                 developer_token_var: "VAR".to_string(),
                 dataset_id: "id".to_string(),
             }),
+            cache_ttl_secs: None,
         };
         let secrets = SecretsManager::from_environment().unwrap();
         let config = ConfigBuilder::new()
@@ -779,6 +780,7 @@ mod tests {
                     file_search_path: dir.path().to_str().unwrap().to_string(),
                 },
             }),
+            cache_ttl_secs: None,
         };
         let secrets = SecretsManager::from_environment().unwrap();
         let config = ConfigBuilder::new()
@@ -815,6 +817,7 @@ mod tests {
                 datasets: Default::default(),
                 filters: Default::default(),
             }),
+            cache_ttl_secs: None,
         };
         let secrets = SecretsManager::from_environment().unwrap();
         let config = ConfigBuilder::new()
@@ -848,6 +851,7 @@ mod tests {
                 database: Some("analytics".to_string()),
                 ..Default::default()
             }),
+            cache_ttl_secs: None,
         };
         let secrets = SecretsManager::from_environment().unwrap();
         let config = ConfigBuilder::new()
@@ -874,6 +878,7 @@ mod tests {
                 database: Some("analytics".to_string()),
                 ..Default::default()
             }),
+            cache_ttl_secs: None,
         };
         let secrets = SecretsManager::from_environment().unwrap();
         let config = ConfigBuilder::new()
@@ -902,6 +907,7 @@ mod tests {
                 database: Some("mydb".to_string()),
                 schemas: Default::default(),
             }),
+            cache_ttl_secs: None,
         };
         unsafe { std::env::set_var("MOTHERDUCK_TEST_TOKEN", "fake_token_for_test") };
         let secrets = SecretsManager::from_environment().unwrap();
@@ -936,6 +942,7 @@ mod tests {
                 database: Some("test_db".to_string()),
                 ..Default::default()
             }),
+            cache_ttl_secs: None,
         };
         let secrets = SecretsManager::from_environment().unwrap();
         let config = ConfigBuilder::new()
@@ -969,6 +976,7 @@ mod tests {
                 database: Some("test_db".to_string()),
                 ..Default::default()
             }),
+            cache_ttl_secs: None,
         };
         let secrets = SecretsManager::from_environment().unwrap();
         let config = ConfigBuilder::new()
@@ -1008,6 +1016,7 @@ mod tests {
                 datasets: Default::default(),
                 dry_run_limit: None,
             }),
+            cache_ttl_secs: None,
         };
         let secrets = SecretsManager::from_environment().unwrap();
         let config = ConfigBuilder::new()
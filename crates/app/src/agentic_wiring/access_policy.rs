@@ -0,0 +1,112 @@
+//! Access-policy enforcement for agentic connectors.
+//!
+//! Agent-written SQL never reaches `oxy::connector::Connector`, so databases
+//! with `access_policies` get their `agentic-connector` instance wrapped in
+//! [`PolicyEnforcingConnector`], which applies the same
+//! [`AccessPolicyEnforcer`] rewrite before delegating.
+
+use std::sync::Arc;
+
+use agentic_connector::{
+    ConnectorError, DatabaseConnector, ExecutionResult, SchemaInfo, SqlDialect,
+};
+use agentic_core::result::TypedRowStream;
+use async_trait::async_trait;
+use oxy::adapters::{session_filters::SessionFilters, workspace::manager::WorkspaceManager};
+use oxy::connector::AccessPolicyEnforcer;
+use oxy_shared::errors::OxyError;
+use uuid::Uuid;
+
+/// Wrap `connector` when `db_name` has access policies, otherwise return it
+/// unchanged.
+///
+/// `user_id` and `filters` come from the [`OxyProjectContext`] building the
+/// connector; when either is unknown, policies that reference
+/// `{{ user.id }}` or `{{ filters.* }}` reject every query on their tables
+/// rather than running them unfiltered.
+///
+/// [`OxyProjectContext`]: super::OxyProjectContext
+pub(crate) fn with_access_policies(
+    db_name: &str,
+    connector: Arc<dyn DatabaseConnector>,
+    workspace_manager: &WorkspaceManager,
+    user_id: Option<Uuid>,
+    filters: Option<SessionFilters>,
+) -> Result<Arc<dyn DatabaseConnector>, OxyError> {
+    let config_manager = &workspace_manager.config_manager;
+    let policies = config_manager.access_policies(db_name)?;
+    if policies.is_empty() {
+        return Ok(connector);
+    }
+    let dialect = config_manager.resolve_database(db_name)?.dialect();
+    Ok(Arc::new(PolicyEnforcingConnector {
+        inner: connector,
+        enforcer: AccessPolicyEnforcer::new(dialect, policies)
+            .with_user_id(user_id)
+            .with_filters(filters),
+    }))
+}
+
+/// Rewrites every query through an [`AccessPolicyEnforcer`] before handing
+/// it to the wrapped connector.
+///
+/// `as_arrow` is deliberately left at the default `None`: the Arrow path
+/// takes raw SQL, so consumers fall back to the enforced
+/// `execute_query_full` instead.
+pub(crate) struct PolicyEnforcingConnector {
+    inner: Arc<dyn DatabaseConnector>,
+    enforcer: AccessPolicyEnforcer,
+}
+
+impl PolicyEnforcingConnector {
+    fn enforce(&self, sql: &str) -> Result<String, ConnectorError> {
+        self.enforcer
+            .enforce(sql)
+            .map_err(|e| ConnectorError::QueryFailed {
+                sql: sql.to_string(),
+                message: e.to_string(),
+            })
+    }
+}
+
+#[async_trait]
+impl DatabaseConnector for PolicyEnforcingConnector {
+    fn dialect(&self) -> SqlDialect {
+        self.inner.dialect()
+    }
+
+    async fn execute_query(
+        &self,
+        sql: &str,
+        sample_limit: u64,
+    ) -> Result<ExecutionResult, ConnectorError> {
+        let sql = self.enforce(sql)?;
+        self.inner.execute_query(&sql, sample_limit).await
+    }
+
+    async fn execute_query_full(&self, sql: &str) -> Result<TypedRowStream, ConnectorError> {
+        let sql = self.enforce(sql)?;
+        self.inner.execute_query_full(&sql).await
+    }
+
+    async fn prepare_schema(&self) -> Result<(), ConnectorError> {
+        self.inner.prepare_schema().await
+    }
+
+    /// Column statistics are sampled without the policy predicate, so they
+    /// are dropped for protected tables rather than leaked into prompts.
+    fn introspect_schema(&self) -> Result<SchemaInfo, ConnectorError> {
+        let mut schema = self.inner.introspect_schema()?;
+        for table in &mut schema.tables {
+            if !self.enforcer.protects(&table.name) {
+                continue;
+            }
+            for column in &mut table.columns {
+                column.min = None;
+                column.max = None;
+                column.sample_values.clear();
+            }
+        }
+        Ok(schema)
+    }
+}
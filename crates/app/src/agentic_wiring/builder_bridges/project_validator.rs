@@ -171,7 +171,9 @@ fn validate_semantic_file(abs: &Path) -> Result<(), String> {
     let file_name = abs.file_name().and_then(|n| n.to_str()).unwrap_or("");
 
     if file_name.ends_with(".view.yml") {
-        parse_view_yaml(&content)
+        // Shared with the analytics catalog, which also expands oxy's
+        // composed measure types
+        agentic_analytics::airlayer_compat::parse_view_yaml(&content)
             .map(|_| ())
             .map_err(|e| e.to_string())
     } else {
@@ -186,31 +188,6 @@ fn validate_semantic_file(abs: &Path) -> Result<(), String> {
 // Thin wrappers that handle differences between oxy's YAML format and
 // airlayer's expected types (e.g. optional `description` field).
 
-#[derive(serde::Deserialize)]
-struct ViewShim {
-    name: String,
-    #[serde(default)]
-    description: Option<String>,
-    #[serde(default)]
-    label: Option<String>,
-    #[serde(default, alias = "data_source")]
-    datasource: Option<String>,
-    #[serde(default)]
-    dialect: Option<String>,
-    #[serde(default)]
-    table: Option<String>,
-    #[serde(default)]
-    sql: Option<String>,
-    #[serde(default)]
-    entities: Vec<airlayer::Entity>,
-    #[serde(default)]
-    dimensions: Vec<airlayer::Dimension>,
-    #[serde(default)]
-    measures: Option<Vec<airlayer::Measure>>,
-    #[serde(default)]
-    segments: Vec<airlayer::schema::models::Segment>,
-}
-
 #[derive(serde::Deserialize)]
 struct TopicShim {
     name: String,
@@ -226,25 +203,6 @@ struct TopicShim {
     default_filters: Option<Vec<airlayer::schema::models::TopicFilter>>,
 }
 
-fn parse_view_yaml(yaml: &str) -> Result<airlayer::View, Box<dyn std::error::Error + Send + Sync>> {
-    let shim: ViewShim = serde_yaml::from_str(yaml)?;
-    Ok(airlayer::View {
-        name: shim.name,
-        description: shim.description,
-        label: shim.label,
-        datasource: shim.datasource,
-        dialect: shim.dialect,
-        table: shim.table,
-        sql: shim.sql,
-        entities: shim.entities,
-        dimensions: shim.dimensions,
-        measures: shim.measures,
-        segments: shim.segments,
-        pre_aggregations: None,
-        meta: None,
-    })
-}
-
 fn parse_topic_yaml(
     yaml: &str,
 ) -> Result<airlayer::Topic, Box<dyn std::error::Error + Send + Sync>> {
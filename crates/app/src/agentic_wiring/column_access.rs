@@ -0,0 +1,149 @@
+//! Column-level access rules for the agentic stack.
+//!
+//! Translates the `access` / `masking` metadata on semantic views into the
+//! vendor-neutral [`FieldRestriction`]s the analytics catalog applies before
+//! it is built, so restricted fields never reach the model.
+//!
+//! Raw SQL — agent-written or typed in the Dev Portal — runs on agentic
+//! connectors, so connectors of databases with restricted columns are
+//! wrapped in [`ColumnGuardConnector`], which applies the same
+//! [`ColumnAccessGuard`] check as `execute_sql` and leaves those columns out
+//! of the introspected schema.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use agentic_analytics::FieldRestriction;
+use agentic_connector::{
+    ConnectorError, DatabaseConnector, ExecutionResult, SchemaInfo, SqlDialect,
+};
+use agentic_core::result::TypedRowStream;
+use async_trait::async_trait;
+use oxy::adapters::workspace::manager::WorkspaceManager;
+use oxy::tools::sql::ColumnAccessGuard;
+use oxy::utils::masking_salt;
+use oxy_semantic::access::{FieldAccess, dimension_access, mask_sql, measure_access};
+use oxy_shared::errors::OxyError;
+
+/// Restrictions for the caller of `workspace_manager`, keyed by `view.field`.
+pub(crate) fn semantic_field_restrictions(
+    workspace_manager: &WorkspaceManager,
+) -> Result<HashMap<String, FieldRestriction>, OxyError> {
+    let config_manager = &workspace_manager.config_manager;
+    let mut restrictions = HashMap::new();
+    let clearance = config_manager.access_level();
+
+    let default_dialect = config_manager
+        .list_databases()
+        .first()
+        .map(|db| db.dialect())
+        .unwrap_or_default();
+    for view in config_manager.semantic_views()? {
+        let dialect = match &view.datasource {
+            Some(datasource) => config_manager.resolve_database(datasource)?.dialect(),
+            None => default_dialect.clone(),
+        };
+        for dimension in &view.dimensions {
+            let restriction = match dimension_access(dimension, Some(clearance)) {
+                FieldAccess::Visible => continue,
+                FieldAccess::Masked(rule) => {
+                    match mask_sql(&rule, &dimension.expr, &dialect, masking_salt()) {
+                        Some(expr) => FieldRestriction::Masked(expr),
+                        None => FieldRestriction::Hidden,
+                    }
+                }
+                FieldAccess::Hidden => FieldRestriction::Hidden,
+            };
+            restrictions.insert(format!("{}.{}", view.name, dimension.name), restriction);
+        }
+        for measure in view.measures.iter().flatten() {
+            if measure_access(measure, Some(clearance)) == FieldAccess::Hidden {
+                restrictions.insert(
+                    format!("{}.{}", view.name, measure.name),
+                    FieldRestriction::Hidden,
+                );
+            }
+        }
+    }
+    Ok(restrictions)
+}
+
+/// Wrap `connector` when the caller of `workspace_manager` has restricted
+/// columns in `db_name`, otherwise return it unchanged.
+pub(crate) fn with_column_access(
+    db_name: &str,
+    connector: Arc<dyn DatabaseConnector>,
+    workspace_manager: &WorkspaceManager,
+) -> Result<Arc<dyn DatabaseConnector>, OxyError> {
+    let guard = ColumnAccessGuard::for_database(&workspace_manager.config_manager, db_name)?;
+    Ok(match guard {
+        Some(guard) => Arc::new(ColumnGuardConnector {
+            inner: connector,
+            guard,
+        }),
+        None => connector,
+    })
+}
+
+/// Rejects queries that read restricted columns before handing them to the
+/// wrapped connector. The check runs on the SQL as the caller wrote it, so
+/// this wraps the access-policy rewrite rather than the other way round.
+///
+/// `as_arrow` is left at the default `None` so Arrow consumers fall back to
+/// the checked `execute_query_full`.
+pub(crate) struct ColumnGuardConnector {
+    inner: Arc<dyn DatabaseConnector>,
+    guard: ColumnAccessGuard,
+}
+
+impl ColumnGuardConnector {
+    fn check(&self, sql: &str) -> Result<(), ConnectorError> {
+        self.guard
+            .check(sql)
+            .map_err(|e| ConnectorError::QueryFailed {
+                sql: sql.to_string(),
+                message: e.to_string(),
+            })
+    }
+}
+
+#[async_trait]
+impl DatabaseConnector for ColumnGuardConnector {
+    fn dialect(&self) -> SqlDialect {
+        self.inner.dialect()
+    }
+
+    async fn execute_query(
+        &self,
+        sql: &str,
+        sample_limit: u64,
+    ) -> Result<ExecutionResult, ConnectorError> {
+        self.check(sql)?;
+        self.inner.execute_query(sql, sample_limit).await
+    }
+
+    async fn execute_query_full(&self, sql: &str) -> Result<TypedRowStream, ConnectorError> {
+        self.check(sql)?;
+        self.inner.execute_query_full(sql).await
+    }
+
+    async fn prepare_schema(&self) -> Result<(), ConnectorError> {
+        self.inner.prepare_schema().await
+    }
+
+    /// Restricted columns, their samples and statistics never reach prompts;
+    /// join keys on them are dropped too.
+    fn introspect_schema(&self) -> Result<SchemaInfo, ConnectorError> {
+        let mut schema = self.inner.introspect_schema()?;
+        for table in &mut schema.tables {
+            let name = table.name.clone();
+            table
+                .columns
+                .retain(|column| !self.guard.restricts(&name, &column.name));
+        }
+        schema.join_keys.retain(|(left, right, column)| {
+            !self.guard.restricts(left, column) && !self.guard.restricts(right, column)
+        });
+        Ok(schema)
+    }
+}
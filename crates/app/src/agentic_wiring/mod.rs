@@ -13,10 +13,22 @@
 //!   traits (database, schema, semantic, validator).
 //! - [`thread_owner`] — platform threads-table adapter for
 //!   [`agentic_pipeline::platform::ThreadOwnerLookup`].
+//! - [`access_policy`] — wraps connectors of databases with
+//!   `access_policies` so agent-written SQL is rewritten before it runs.
+//! - [`column_access`] — hides and masks semantic fields in the analytics
+//!   catalog, and rejects raw SQL on restricted columns, according to the
+//!   caller's clearance.
+//! - [`query_audit`] — records a `query.executed` audit entry for every
+//!   query an agentic connector runs.
+//! - [`spend`] — charges agentic runs to the workspace and user spend caps.
 
+pub mod access_policy;
 pub mod builder_bridges;
+pub mod column_access;
 pub mod metric_sink;
 pub mod project_ctx;
+pub mod query_audit;
+pub mod spend;
 pub mod thread_owner;
 
 use std::sync::Arc;
@@ -7,20 +7,29 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use agentic_analytics::FieldRestriction;
 use agentic_analytics::config::{LlmVendor, ResolvedModelInfo};
 use agentic_connector::{
     BigQueryConfig, ClickHouseConfig, ConnectorConfig, DatabaseConnector, DomoConfig, DuckDbConfig,
-    DuckDbLoadStrategy, DuckDbRawConfig, DuckDbUrlConfig, MysqlConfig, PostgresConfig,
-    SnowflakeAuth, SnowflakeConfig,
+    DuckDbLoadStrategy, DuckDbRawConfig, DuckDbUrlConfig, MssqlConfig, MysqlConfig, PostgresConfig,
+    ReplayConnector, SnowflakeAuth, SnowflakeConfig, SqliteConfig, TrinoConfig,
 };
+use agentic_core::rate_limit::RateLimit;
+use agentic_core::spend::SharedSpendTracker;
 use agentic_pipeline::SharedMetricSink;
 use agentic_pipeline::platform::ProjectContext;
 use agentic_workflow::WorkspaceContext;
 use agentic_workflow::workspace::IntegrationConfig;
 use async_trait::async_trait;
-use oxy::adapters::workspace::manager::WorkspaceManager;
+use oxy::adapters::{session_filters::SessionFilters, workspace::manager::WorkspaceManager};
 use oxy::config::model::{DatabaseType, DuckDBOptions, IntegrationType, Model, SnowflakeAuthType};
 use oxy_shared::errors::OxyError;
+use uuid::Uuid;
+
+use super::access_policy::with_access_policies;
+use super::column_access::{semantic_field_restrictions, with_column_access};
+use super::query_audit::with_query_audit;
+use super::spend::OxySpendTracker;
 
 /// Adapter that exposes a [`WorkspaceManager`] as a [`ProjectContext`] and
 /// (for the workflow runner) an [`agentic_workflow::WorkspaceContext`].
@@ -31,6 +40,10 @@ use oxy_shared::errors::OxyError;
 /// before handing the context around.
 pub struct OxyProjectContext {
     workspace_manager: WorkspaceManager,
+    /// User the runs are charged to, for per-user spend caps
+    user_id: Option<Uuid>,
+    /// Session filters substituted into `{{ filters.* }}` access policies
+    filters: Option<SessionFilters>,
     connectors: tokio::sync::OnceCell<HashMap<String, Arc<dyn DatabaseConnector>>>,
 }
 
@@ -38,10 +51,25 @@ impl OxyProjectContext {
     pub fn new(workspace_manager: WorkspaceManager) -> Self {
         Self {
             workspace_manager,
+            user_id: None,
+            filters: None,
             connectors: tokio::sync::OnceCell::new(),
         }
     }
 
+    /// Charge runs to `user_id`'s spend caps as well as the workspace's, and
+    /// resolve `{{ user.id }}` in access policies to them.
+    pub fn with_user(mut self, user_id: Uuid) -> Self {
+        self.user_id = Some(user_id);
+        self
+    }
+
+    /// Resolve `{{ filters.* }}` in access policies from `filters`.
+    pub fn with_filters(mut self, filters: Option<SessionFilters>) -> Self {
+        self.filters = filters;
+        self
+    }
+
     pub fn workspace_manager(&self) -> &WorkspaceManager {
         &self.workspace_manager
     }
@@ -59,10 +87,11 @@ impl OxyProjectContext {
     /// useless when a user typed a SQL query against a specific database
     /// and wants to know why it didn't work.
     ///
-    /// Dispatches the same way `resolve_pre_built_airhouse` +
-    /// `database_to_connector_config` would (so the routing stays in one
-    /// place), but propagates errors through `OxyError` instead of
-    /// dropping them on the floor.
+    /// The agentic pipeline gets its connectors from here too, through
+    /// `resolve_pre_built_connector`, so every query is audited, recorded or
+    /// replayed when `OXY_REPLAY_MODE` is set, checked against the caller's
+    /// column access rules and, for databases with `access_policies`,
+    /// rewritten by the policy enforcer.
     pub async fn build_connector_for(
         &self,
         db_name: &str,
@@ -80,9 +109,9 @@ impl OxyProjectContext {
         // Airhouse types live outside `agentic-connector`'s vendor-neutral
         // dispatcher; the helper builds them directly with full error
         // propagation. Other types go through the config + dispatcher path.
-        match &db.database_type {
+        let connector = match &db.database_type {
             DatabaseType::Airhouse(_) | DatabaseType::AirhouseManaged(_) => {
-                build_airhouse_connector(&db, &self.workspace_manager).await
+                build_airhouse_connector(&db, &self.workspace_manager).await?
             }
             _ => {
                 let cfg = database_to_connector_config(&db, &self.workspace_manager)
@@ -98,9 +127,26 @@ impl OxyProjectContext {
                     .map_err(|e| {
                         OxyError::DBError(format!("failed to build connector for '{db_name}': {e}"))
                     })?;
-                Ok(Arc::from(built))
+                Arc::from(built)
             }
-        }
+        };
+        // Record/replay sits under the access policies so fixtures hold the
+        // SQL that actually reached the database, as the classic connector's do.
+        let connector = ReplayConnector::wrap_from_env(db_name, connector);
+        let connector = with_access_policies(
+            db_name,
+            connector,
+            &self.workspace_manager,
+            self.user_id,
+            self.filters.clone(),
+        )?;
+        let connector = with_column_access(db_name, connector, &self.workspace_manager)?;
+        Ok(with_query_audit(
+            db_name,
+            connector,
+            self.workspace_manager.workspace_id,
+            self.user_id,
+        ))
     }
 
     /// Build connectors from workspace database configs. Called once lazily
@@ -142,15 +188,23 @@ impl OxyProjectContext {
 
 #[async_trait]
 impl ProjectContext for OxyProjectContext {
-    async fn resolve_connector(&self, db_name: &str) -> Option<ConnectorConfig> {
-        resolve_connector_impl(db_name, &self.workspace_manager).await
+    async fn resolve_connector(&self, _db_name: &str) -> Option<ConnectorConfig> {
+        // A bare config would be built without the audit and policy
+        // wrappers; every database comes from `resolve_pre_built_connector`.
+        None
     }
 
     async fn resolve_pre_built_connector(
         &self,
         db_name: &str,
     ) -> Option<Arc<dyn DatabaseConnector>> {
-        resolve_pre_built_airhouse(db_name, &self.workspace_manager).await
+        match self.build_connector_for(db_name).await {
+            Ok(connector) => Some(connector),
+            Err(e) => {
+                tracing::warn!(db = %db_name, "connector build failed: {e}");
+                None
+            }
+        }
     }
 
     async fn resolve_model(
@@ -188,6 +242,27 @@ impl ProjectContext for OxyProjectContext {
         oxy_observability::global::get_global()?;
         Some(Arc::new(super::metric_sink::OxyAnalyticsMetricSink::new()))
     }
+
+    fn semantic_field_restrictions(&self) -> Result<HashMap<String, FieldRestriction>, String> {
+        semantic_field_restrictions(&self.workspace_manager).map_err(|e| e.to_string())
+    }
+
+    async fn spend_tracker(&self) -> Result<Option<SharedSpendTracker>, String> {
+        let Some(budgets) = self
+            .workspace_manager
+            .config_manager
+            .get_config()
+            .budgets
+            .clone()
+        else {
+            return Ok(None);
+        };
+        let tracker =
+            OxySpendTracker::open(self.workspace_manager.workspace_id, self.user_id, budgets)
+                .await
+                .map_err(|e| e.to_string())?;
+        Ok(Some(Arc::new(tracker)))
+    }
 }
 
 #[async_trait]
@@ -294,25 +369,6 @@ impl WorkspaceContext for OxyProjectContext {
 
 // ── Connector translation ───────────────────────────────────────────────────
 
-async fn resolve_connector_impl(
-    db_name: &str,
-    workspace_manager: &WorkspaceManager,
-) -> Option<ConnectorConfig> {
-    let db = match workspace_manager.config_manager.resolve_database(db_name) {
-        Ok(d) => d,
-        Err(e) => {
-            tracing::warn!(
-                db = %db_name,
-                "databases: '{}' not found in config.yml: {}",
-                db_name,
-                e
-            );
-            return None;
-        }
-    };
-    database_to_connector_config(&db, workspace_manager).await
-}
-
 /// Translate an already-resolved [`oxy::config::model::Database`] into an
 /// [`agentic_connector::ConnectorConfig`].
 ///
@@ -378,6 +434,28 @@ pub async fn database_to_connector_config(
             }
         },
 
+        DatabaseType::Sqlite(sqlite) => {
+            let files = match workspace_manager
+                .config_manager
+                .resolve_glob(&vec![sqlite.path.clone()])
+                .await
+            {
+                Ok(files) if !files.is_empty() => files,
+                Ok(_) => {
+                    tracing::warn!(db = %db.name, "SQLite: '{}' matched no files", sqlite.path);
+                    return None;
+                }
+                Err(e) => {
+                    tracing::warn!(db = %db.name, "SQLite: cannot resolve path: {e}");
+                    return None;
+                }
+            };
+            Some(ConnectorConfig::Sqlite(SqliteConfig {
+                files: files.into_iter().map(PathBuf::from).collect(),
+                read_only: sqlite.read_only,
+            }))
+        }
+
         DatabaseType::Postgres(pg) => {
             let host = pg
                 .get_host(&workspace_manager.secrets_manager)
@@ -411,7 +489,7 @@ pub async fn database_to_connector_config(
         }
 
         // `Airhouse` and `AirhouseManaged` are handled by the host's
-        // `resolve_pre_built_connector` path — see `resolve_pre_built_airhouse`
+        // `resolve_pre_built_connector` path — see `build_airhouse_connector`
         // below — because the connector lives in the standalone `airhouse`
         // crate, not in `agentic-connector::ConnectorConfig`.
         DatabaseType::Airhouse(_) | DatabaseType::AirhouseManaged(_) => None,
@@ -602,6 +680,68 @@ pub async fn database_to_connector_config(
             }))
         }
 
+        DatabaseType::Mssql(ms) => {
+            let host = ms
+                .get_host(&workspace_manager.secrets_manager)
+                .await
+                .unwrap_or_else(|_| "localhost".into());
+            let port: u16 = ms
+                .get_port(&workspace_manager.secrets_manager)
+                .await
+                .unwrap_or_else(|_| "1433".into())
+                .parse()
+                .unwrap_or(1433);
+            let user = ms
+                .get_user(&workspace_manager.secrets_manager)
+                .await
+                .unwrap_or_default();
+            let password = ms
+                .get_password(&workspace_manager.secrets_manager)
+                .await
+                .unwrap_or_default();
+            let database = ms
+                .get_database(&workspace_manager.secrets_manager)
+                .await
+                .unwrap_or_default();
+            Some(ConnectorConfig::Mssql(MssqlConfig {
+                host,
+                port,
+                user,
+                password,
+                database,
+                encrypt: ms.encrypt,
+                trust_server_certificate: ms.trust_server_certificate,
+            }))
+        }
+
+        DatabaseType::Trino(tr) => {
+            let host = tr
+                .get_host(&workspace_manager.secrets_manager)
+                .await
+                .unwrap_or_else(|_| "localhost".into());
+            let port = tr
+                .get_port(&workspace_manager.secrets_manager)
+                .await
+                .unwrap_or_else(|_| "8080".into());
+            let user = tr
+                .get_user(&workspace_manager.secrets_manager)
+                .await
+                .unwrap_or_default();
+            let password = tr
+                .get_password(&workspace_manager.secrets_manager)
+                .await
+                .unwrap_or_default();
+            let scheme = if tr.https { "https" } else { "http" };
+            Some(ConnectorConfig::Trino(TrinoConfig {
+                url: format!("{scheme}://{host}:{port}"),
+                user,
+                password,
+                catalog: tr.catalog.clone(),
+                schema: tr.schema.clone(),
+                catalogs: tr.catalogs.clone(),
+            }))
+        }
+
         DatabaseType::DOMO(d) => {
             let developer_token = match workspace_manager
                 .secrets_manager
@@ -637,39 +777,9 @@ fn extract_project_id_from_key(key_path: &str) -> Option<String> {
     v.get("project_id")?.as_str().map(|s| s.to_string())
 }
 
-/// Pre-build an `airhouse::AirhouseConnector` for `airhouse` /
-/// `airhouse_managed` databases. Errors are swallowed into a warn log and
-/// returned as `None` because this is the agentic-pipeline path: a single
-/// failing database shouldn't kill batch resolution. Non-agentic callers
-/// (the SQL IDE, the builder bridge) use [`OxyProjectContext::build_connector_for`]
-/// instead, which propagates the same errors so the user sees what's wrong.
-async fn resolve_pre_built_airhouse(
-    db_name: &str,
-    workspace_manager: &WorkspaceManager,
-) -> Option<Arc<dyn DatabaseConnector>> {
-    let db = workspace_manager
-        .config_manager
-        .resolve_database(db_name)
-        .ok()?;
-    if !matches!(
-        db.database_type,
-        DatabaseType::Airhouse(_) | DatabaseType::AirhouseManaged(_)
-    ) {
-        return None;
-    }
-    match build_airhouse_connector(&db, workspace_manager).await {
-        Ok(conn) => Some(conn),
-        Err(e) => {
-            tracing::warn!(db = %db.name, "airhouse connector build failed: {e}");
-            None
-        }
-    }
-}
-
-/// Error-propagating airhouse connector builder. Used by both
-/// [`resolve_pre_built_airhouse`] (which discards the error) and
-/// [`OxyProjectContext::build_connector_for`] (which surfaces it). Keeps
-/// the per-`DatabaseType` arms in one place.
+/// Error-propagating airhouse connector builder, used by
+/// [`OxyProjectContext::build_connector_for`]. Keeps the per-`DatabaseType`
+/// arms in one place.
 async fn build_airhouse_connector(
     db: &oxy::config::model::Database,
     workspace_manager: &WorkspaceManager,
@@ -749,68 +859,18 @@ async fn resolve_model_impl(
 
     match workspace_manager.config_manager.resolve_model(name) {
         Ok(model) => {
-            let model_name = model.model_name().to_string();
-            let key_var = model.key_var().map(|s| s.to_string());
-
-            let (vendor, base_url, extra_api_key, azure_deployment_id, azure_api_version) =
-                match model {
-                    Model::Anthropic { config: m } => {
-                        (LlmVendor::Anthropic, m.api_url.clone(), None, None, None)
-                    }
-                    Model::OpenAI { config: m } => {
-                        let (dep_id, api_ver) = m
-                            .azure
-                            .as_ref()
-                            .map(|a| {
-                                (
-                                    Some(a.azure_deployment_id.clone()),
-                                    Some(a.azure_api_version.clone()),
-                                )
-                            })
-                            .unwrap_or((None, None));
-                        (LlmVendor::OpenAi, m.api_url.clone(), None, dep_id, api_ver)
-                    }
-                    Model::Ollama { config: m } => (
-                        LlmVendor::OpenAiCompat,
-                        Some(m.api_url.clone()),
-                        Some(m.api_key.clone()),
-                        None,
-                        None,
-                    ),
-                    Model::Google { .. } => {
-                        tracing::warn!(
-                            model = name,
-                            "Google/Gemini models are not yet supported in analytics agents"
-                        );
-                        return None;
+            let mut info = translate_model(model, is_explicit_ref, workspace_manager).await;
+            for fallback in model.fallbacks() {
+                match workspace_manager.config_manager.resolve_model(fallback) {
+                    Ok(fallback) => info
+                        .fallbacks
+                        .push(translate_model(fallback, is_explicit_ref, workspace_manager).await),
+                    Err(e) => {
+                        tracing::warn!(model = name, "could not resolve fallback model: {e}")
                     }
-                };
-
-            // Resolve api_key via secrets_manager first, env fallback. Ollama
-            // carries its key inline via the config — honor that.
-            let api_key = if let Some(inline) = extra_api_key {
-                Some(inline)
-            } else if let Some(kv) = key_var.as_deref() {
-                workspace_manager
-                    .secrets_manager
-                    .resolve_secret(kv)
-                    .await
-                    .ok()
-                    .flatten()
-                    .or_else(|| std::env::var(kv).ok())
-            } else {
-                None
-            };
-
-            Some(ResolvedModelInfo {
-                model: model_name,
-                vendor,
-                api_key,
-                base_url,
-                is_explicit_ref,
-                azure_deployment_id,
-                azure_api_version,
-            })
+                }
+            }
+            Some(info)
         }
         Err(e) => {
             tracing::warn!(model = name, "could not resolve model from config.yml: {e}");
@@ -818,3 +878,74 @@ async fn resolve_model_impl(
         }
     }
 }
+
+/// Translate a `config.yml` model into a [`ResolvedModelInfo`] without its
+/// fallbacks.
+async fn translate_model(
+    model: &Model,
+    is_explicit_ref: bool,
+    workspace_manager: &WorkspaceManager,
+) -> ResolvedModelInfo {
+    let model_name = model.model_name().to_string();
+    let key_var = model.key_var().map(|s| s.to_string());
+
+    let (vendor, base_url, extra_api_key, azure_deployment_id, azure_api_version) = match model {
+        Model::Anthropic { config: m } => {
+            (LlmVendor::Anthropic, m.api_url.clone(), None, None, None)
+        }
+        Model::OpenAI { config: m } => {
+            let (dep_id, api_ver) = m
+                .azure
+                .as_ref()
+                .map(|a| {
+                    (
+                        Some(a.azure_deployment_id.clone()),
+                        Some(a.azure_api_version.clone()),
+                    )
+                })
+                .unwrap_or((None, None));
+            (LlmVendor::OpenAi, m.api_url.clone(), None, dep_id, api_ver)
+        }
+        Model::Ollama { config: m } => (
+            LlmVendor::OpenAiCompat,
+            Some(m.api_url.clone()),
+            Some(m.api_key.clone()),
+            None,
+            None,
+        ),
+        Model::Google { .. } => (LlmVendor::Gemini, None, None, None, None),
+    };
+
+    // Resolve api_key via secrets_manager first, env fallback. Ollama
+    // carries its key inline via the config — honor that.
+    let api_key = if let Some(inline) = extra_api_key {
+        Some(inline)
+    } else if let Some(kv) = key_var.as_deref() {
+        workspace_manager
+            .secrets_manager
+            .resolve_secret(kv)
+            .await
+            .ok()
+            .flatten()
+            .or_else(|| std::env::var(kv).ok())
+    } else {
+        None
+    };
+
+    ResolvedModelInfo {
+        model: model_name,
+        vendor,
+        api_key,
+        base_url,
+        is_explicit_ref,
+        azure_deployment_id,
+        azure_api_version,
+        name: model.name().to_string(),
+        rate_limit: model.rate_limit().map(|limit| RateLimit {
+            requests_per_minute: limit.requests_per_minute,
+            tokens_per_minute: limit.tokens_per_minute,
+        }),
+        rate_limit_key: model.provider_key(),
+        fallbacks: vec![],
+    }
+}
@@ -0,0 +1,90 @@
+//! Query auditing for agentic connectors.
+//!
+//! Agent-written SQL and Dev Portal queries run on `agentic-connector`
+//! instances rather than `oxy::connector::Connector`, so every connector
+//! [`OxyProjectContext`](super::OxyProjectContext) builds is wrapped in
+//! [`AuditingConnector`], which records a `query.executed` audit entry for
+//! each query it runs.
+
+use std::sync::Arc;
+
+use agentic_connector::{
+    ConnectorError, DatabaseConnector, ExecutionResult, SchemaInfo, SqlDialect,
+};
+use agentic_core::result::TypedRowStream;
+use async_trait::async_trait;
+use oxy::audit::{self, AuditAction, AuditEvent};
+use uuid::Uuid;
+
+/// Wrap `connector` so queries on `db_name` are recorded in `workspace_id`'s
+/// audit log, attributed to `user_id`.
+pub(crate) fn with_query_audit(
+    db_name: &str,
+    connector: Arc<dyn DatabaseConnector>,
+    workspace_id: Uuid,
+    user_id: Option<Uuid>,
+) -> Arc<dyn DatabaseConnector> {
+    Arc::new(AuditingConnector {
+        inner: connector,
+        database: db_name.to_string(),
+        workspace_id,
+        user_id,
+    })
+}
+
+/// Records every query before returning its result. The SQL is hashed as
+/// the caller wrote it, before any access-policy rewrite.
+///
+/// `as_arrow` is left at the default `None` so Arrow consumers fall back to
+/// the audited `execute_query_full`.
+pub(crate) struct AuditingConnector {
+    inner: Arc<dyn DatabaseConnector>,
+    database: String,
+    workspace_id: Uuid,
+    user_id: Option<Uuid>,
+}
+
+impl AuditingConnector {
+    async fn record(&self, sql: &str, success: bool) {
+        audit::record(
+            AuditEvent::new(AuditAction::QueryExecuted)
+                .workspace(self.workspace_id)
+                .actor(self.user_id)
+                .target("database", &self.database)
+                .meta("sql_sha256", audit::sql_hash(sql))
+                .meta("success", success),
+        )
+        .await;
+    }
+}
+
+#[async_trait]
+impl DatabaseConnector for AuditingConnector {
+    fn dialect(&self) -> SqlDialect {
+        self.inner.dialect()
+    }
+
+    async fn execute_query(
+        &self,
+        sql: &str,
+        sample_limit: u64,
+    ) -> Result<ExecutionResult, ConnectorError> {
+        let result = self.inner.execute_query(sql, sample_limit).await;
+        self.record(sql, result.is_ok()).await;
+        result
+    }
+
+    async fn execute_query_full(&self, sql: &str) -> Result<TypedRowStream, ConnectorError> {
+        let result = self.inner.execute_query_full(sql).await;
+        self.record(sql, result.is_ok()).await;
+        result
+    }
+
+    async fn prepare_schema(&self) -> Result<(), ConnectorError> {
+        self.inner.prepare_schema().await
+    }
+
+    fn introspect_schema(&self) -> Result<SchemaInfo, ConnectorError> {
+        self.inner.introspect_schema()
+    }
+}
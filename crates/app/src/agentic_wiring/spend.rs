@@ -0,0 +1,109 @@
+//! Host adapter for [`agentic_core::spend::SpendTracker`] — charges the LLM
+//! and tool calls of agentic runs to Oxy's [`Budget`], so they count toward
+//! the workspace and user caps in config.yml `budgets`.
+
+use std::sync::Arc;
+
+use agentic_core::spend::SpendTracker;
+use async_trait::async_trait;
+use oxy::budget::{Budget, SpendLedger};
+use oxy::config::model::{BudgetsConfig, RunBudget};
+use oxy_shared::errors::OxyError;
+use uuid::Uuid;
+
+pub struct OxySpendTracker {
+    budget: Arc<Budget>,
+    /// Prices the calls are charged at
+    budgets: BudgetsConfig,
+}
+
+impl OxySpendTracker {
+    /// Open the spend ledger for a run, refusing it when a cap has already
+    /// been reached or the ledger cannot be read.
+    pub async fn open(
+        workspace_id: Uuid,
+        user_id: Option<Uuid>,
+        budgets: BudgetsConfig,
+    ) -> Result<Self, OxyError> {
+        let ledger = SpendLedger::open(workspace_id, user_id, &budgets)
+            .await
+            .map_err(|err| {
+                OxyError::DBError(format!(
+                    "Spend caps cannot be enforced, failed to open the spend ledger: {err}"
+                ))
+            })?;
+        if let Some(reason) = ledger.cap_reached()? {
+            return Err(OxyError::BudgetExceeded(reason));
+        }
+        Ok(Self::new(
+            Budget::new("this run", RunBudget::default(), Some(ledger)),
+            budgets,
+        ))
+    }
+
+    fn new(budget: Arc<Budget>, budgets: BudgetsConfig) -> Self {
+        Self { budget, budgets }
+    }
+}
+
+#[async_trait]
+impl SpendTracker for OxySpendTracker {
+    fn exceeded(&self) -> Option<String> {
+        self.budget
+            .exceeded()
+            .unwrap_or_else(|e| Some(e.to_string()))
+    }
+
+    async fn charge_tokens(
+        &self,
+        model: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+    ) -> Result<(), String> {
+        self.budget
+            .charge_call(model, input_tokens, output_tokens, Some(&self.budgets))
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    async fn charge_tool_calls(&self, count: u64) -> Result<(), String> {
+        self.budget
+            .charge_tool_calls(count)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_charges_the_budget() {
+        let tracker = OxySpendTracker::new(
+            Budget::new(
+                "this run",
+                RunBudget {
+                    max_tokens: Some(1_000),
+                    ..Default::default()
+                },
+                None,
+            ),
+            BudgetsConfig::default(),
+        );
+
+        tracker.charge_tokens("gpt-4o", 600, 100).await.unwrap();
+        tracker.charge_tool_calls(3).await.unwrap();
+        assert_eq!(tracker.exceeded(), None);
+
+        let err = tracker.charge_tokens("gpt-4o", 300, 100).await.unwrap_err();
+        assert_eq!(
+            err,
+            "Budget exceeded: this run used 1100 tokens, over its limit of 1000"
+        );
+        assert_eq!(
+            tracker.exceeded().as_deref(),
+            Some("this run used 1100 tokens, over its limit of 1000")
+        );
+    }
+}
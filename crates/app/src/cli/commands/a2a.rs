@@ -5,7 +5,10 @@
 
 use crate::cli::A2aArgs;
 use crate::integrations::a2a::{
-    agent_card::AgentCardService, handler::OxyA2aHandler, storage::OxyTaskStorage,
+    agent_card::AgentCardService,
+    handler::OxyA2aHandler,
+    push_notifications::{OxyPushNotificationStorage, PushNotifier},
+    storage::OxyTaskStorage,
 };
 use a2a::server::{create_http_router, create_jsonrpc_router};
 use agentic_pipeline::{AnalyticsMigrator, WorkflowMigrator};
@@ -227,22 +230,36 @@ async fn create_a2a_application(
         workspace_manager.clone(),
     ));
 
+    // Push notifications are signed only when a server secret is configured
+    let signing_secret = std::env::var("OXY_A2A_SIGNING_SECRET")
+        .ok()
+        .filter(|secret| !secret.is_empty());
+
     // Iterate through configured agents and create routes
     for agent_config in &a2a_config.agents {
         let agent_name = &agent_config.name;
         info!("Setting up A2A routes for agent: {}", agent_name);
 
         // Create agent-scoped storage
-        let storage = Arc::new(OxyTaskStorage::new_for_agent(
+        let push_notifications = Arc::new(OxyPushNotificationStorage::new_for_agent(
             agent_name.clone(),
             db.clone(),
         ));
+        let push_notifier = Arc::new(PushNotifier::new(
+            push_notifications.as_ref().clone(),
+            signing_secret.clone(),
+        ));
+        let storage = Arc::new(
+            OxyTaskStorage::new_for_agent(agent_name.clone(), db.clone())
+                .with_push_notifier(push_notifier),
+        );
 
         // Create agent-scoped handler
         let handler = Arc::new(OxyA2aHandler::new(
             agent_name.clone(),
             config.clone(),
             storage,
+            push_notifications,
             db.clone(),
             workspace_manager.clone(),
             agent_card_service.clone(),
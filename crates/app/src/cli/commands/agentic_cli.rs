@@ -11,6 +11,7 @@ use oxy::adapters::workspace::builder::WorkspaceBuilder;
 use oxy::config::resolve_local_workspace_path;
 use oxy::database::client::establish_connection;
 use oxy::theme::StyledText;
+use oxy_semantic::AccessLevel;
 use oxy_shared::errors::OxyError;
 use sea_orm::DatabaseConnection;
 use serde_json::{Value, json};
@@ -178,7 +179,8 @@ async fn cmd_run(args: RunArgs) -> Result<(), OxyError> {
         .await?
         .with_runs_manager(oxy::adapters::runs::RunsManager::noop())
         .build()
-        .await?;
+        .await?
+        .with_access_level(AccessLevel::Restricted);
 
     let thinking_mode = match args.thinking_mode.as_str() {
         "extended_thinking" => agentic_pipeline::ThinkingMode::ExtendedThinking,
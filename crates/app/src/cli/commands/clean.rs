@@ -4,12 +4,13 @@ use oxy::{
         constants::{CACHE_SOURCE, DATABASE_SEMANTIC_PATH},
     },
     theme::StyledText,
+    tools::sql::result_cache::ResultCache,
 };
 use oxy_shared::errors::OxyError;
 use std::{
     fs,
     io::{self, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 fn confirm_deletion(item_description: &str, require_confirmation: bool) -> Result<bool, OxyError> {
@@ -108,12 +109,52 @@ pub async fn clean_vectors(
     Ok(())
 }
 
+pub async fn report_result_cache(config_manager: &ConfigManager) -> Result<(), OxyError> {
+    let state_dir = config_manager.resolve_state_dir().await?;
+    print_result_cache_report(&state_dir);
+    Ok(())
+}
+
+fn print_result_cache_report(state_dir: &Path) {
+    println!("📊 {} query result cache...", "Inspecting".text());
+    // Sizes and TTLs don't matter for reading the report
+    let report = ResultCache::new(ResultCache::dir_for(state_dir), u64::MAX, None).report();
+    if report.stats.is_empty() {
+        println!("  {} No cached query results", "ℹ️".text());
+        return;
+    }
+
+    println!(
+        "  {} cached results, {:.1} MB",
+        report.entries,
+        report.size_bytes as f64 / (1024.0 * 1024.0)
+    );
+    for (database, stats) in &report.stats {
+        let hit_rate = stats
+            .hit_rate()
+            .map(|rate| format!("{:.1}%", rate * 100.0))
+            .unwrap_or_else(|| "n/a".to_string());
+        println!(
+            "  {}: {} hit rate ({} hits, {} misses), {} expired, {} invalidated, {} evicted",
+            database.as_str().secondary(),
+            hit_rate,
+            stats.hits,
+            stats.misses,
+            stats.expired,
+            stats.invalidated,
+            stats.evicted
+        );
+    }
+}
+
 pub async fn clean_cache(
     require_confirmation: bool,
     config_manager: &ConfigManager,
 ) -> Result<(), OxyError> {
-    println!("🗂️  {} cache folder...", "Clearing".text());
     let state_dir = config_manager.resolve_state_dir().await?;
+    print_result_cache_report(&state_dir);
+
+    println!("🗂️  {} cache folder...", "Clearing".text());
     let cache_dir = state_dir.join(CACHE_SOURCE);
 
     if cache_dir.exists() {
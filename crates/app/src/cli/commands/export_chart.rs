@@ -1,9 +1,21 @@
+use crate::server::api::app::data_container_to_output;
+use crate::server::service::app::{AppService, DisplayWithError, TaskOutput, get_app_displays};
+use ::oxy::adapters::runs::RunsManager;
+use ::oxy::adapters::workspace::builder::WorkspaceBuilder;
+use ::oxy::config::model::Display;
+use ::oxy::config::resolve_local_workspace_path;
+use ::oxy::execute::types::DataContainer;
 use ::oxy::theme::StyledText;
+use ::oxy::tools::visualize::render::{
+    ChartDocument, ChartRenderer, DEFAULT_HEIGHT, DEFAULT_WIDTH,
+};
 use base64::Engine;
 use clap::Parser;
 use headless_chrome::{Browser, browser::tab::Tab};
+use oxy_semantic::AccessLevel;
 use oxy_shared::errors::OxyError;
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 use std::{fs, path::PathBuf, time::Duration};
 use uuid::Uuid;
@@ -22,11 +34,14 @@ pub struct ExportChartArgs {
     /// Output directory for PNG files
     #[clap(long, short = 'o', value_name = "PATH")]
     pub output: PathBuf,
+
+    /// Chart renderer to use
+    #[clap(long, value_enum, default_value_t = ChartRenderer::Native)]
+    pub renderer: ChartRenderer,
 }
 
-/// Export charts to a directory without CLI output.
+/// Export charts to a directory without CLI output, using headless Chromium.
 /// Returns a map of chart_index -> file_name for successfully exported charts.
-/// This is the core logic reused by both the CLI command and the API endpoint.
 /// Uses spawn_blocking to avoid blocking the async runtime (the headless browser
 /// needs the server to remain responsive to serve the app page).
 pub async fn export_charts_to_dir(
@@ -45,55 +60,111 @@ pub async fn export_charts_to_dir(
         let chart_indexes = discover_charts(&tab)?;
         let exported_charts = export_all_charts(&tab, &chart_indexes, &output_dir)?;
 
-        let result: HashMap<i64, String> = exported_charts
-            .into_iter()
-            .filter_map(|(index, path)| {
-                path.map(|p| {
-                    (
-                        index,
-                        p.file_name()
-                            .unwrap_or_default()
-                            .to_string_lossy()
-                            .into_owned(),
-                    )
-                })
-            })
-            .collect();
-
-        Ok(result)
+        Ok(exported_file_names(exported_charts))
     })
     .await
     .map_err(|e| OxyError::RuntimeError(format!("Chart export task panicked: {}", e)))?
 }
 
-pub async fn handle_export_chart_command(
-    args: ExportChartArgs,
-) -> Result<HashMap<i64, String>, OxyError> {
-    let app_path = args.app_path.trim();
-    let url = build_app_url(app_path);
-    print_header(app_path, &url, &args.output);
-
-    fs::create_dir_all(&args.output)
-        .map_err(|e| OxyError::RuntimeError(format!("Failed to create output directory: {}", e)))?;
+/// Build native chart documents for the top-level chart displays of an app,
+/// keyed by the same index the web app gives each chart.
+pub fn app_chart_documents(
+    displays: &[DisplayWithError],
+    task_data: &HashMap<String, TaskOutput>,
+) -> Vec<(i64, ChartDocument)> {
+    displays
+        .iter()
+        .enumerate()
+        .filter_map(|(i, display)| {
+            let DisplayWithError::Display(display) = display else {
+                return None;
+            };
+            let data = match display {
+                Display::LineChart(chart) => &chart.data,
+                Display::BarChart(chart) => &chart.data,
+                Display::PieChart(chart) => &chart.data,
+                _ => return None,
+            };
+            // A chart whose task produced no table still gets an image,
+            // which says there is no data.
+            let rows = match task_data.get(data) {
+                Some(TaskOutput::Table(serde_json::Value::Array(rows))) => rows.as_slice(),
+                _ => &[],
+            };
+            ChartDocument::from_display(display, rows).map(|doc| (i as i64, doc))
+        })
+        .collect()
+}
 
-    let (_browser, tab) = launch_browser_and_navigate(&url)?;
-    let chart_indexes = discover_charts(&tab)?;
+/// Render chart documents to `{name}-{index}-{uuid}.png` files with the
+/// native renderer. Returns a map of chart_index -> file_name; charts that
+/// fail to render are logged and left out.
+pub async fn render_charts_to_dir(
+    charts: Vec<(i64, ChartDocument)>,
+    output_dir: &Path,
+) -> Result<HashMap<i64, String>, OxyError> {
+    let output_dir = output_dir.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        fs::create_dir_all(&output_dir).map_err(|e| {
+            OxyError::RuntimeError(format!("Failed to create output directory: {}", e))
+        })?;
+        let exported = charts
+            .into_iter()
+            .map(
+                |(index, doc)| match render_chart_file(&doc, index, &output_dir) {
+                    Ok(path) => (index, Some(path)),
+                    Err(e) => {
+                        tracing::warn!("Failed to render chart {index}: {e}");
+                        (index, None)
+                    }
+                },
+            )
+            .collect();
+        Ok(exported_file_names(exported))
+    })
+    .await
+    .map_err(|e| OxyError::RuntimeError(format!("Chart render task panicked: {}", e)))?
+}
 
-    println!(
-        "{}",
-        format!(
-            "   Found {} chart(s) with indexes {:?}, exporting sequentially...",
-            chart_indexes.len(),
-            chart_indexes
-        )
-        .text()
-    );
+fn render_chart_file(
+    doc: &ChartDocument,
+    index: i64,
+    output_dir: &Path,
+) -> Result<PathBuf, OxyError> {
+    let png = doc.render_png(DEFAULT_WIDTH, DEFAULT_HEIGHT)?;
+    let output_file = output_dir.join(format!(
+        "{}-{}-{}.png",
+        chart_file_stem(doc),
+        index,
+        Uuid::new_v4()
+    ));
+    fs::write(&output_file, png)
+        .map_err(|e| OxyError::RuntimeError(format!("Failed to write output file: {}", e)))?;
+    Ok(output_file)
+}
 
-    let exported_charts = export_all_charts(&tab, &chart_indexes, &args.output)?;
-    print_summary(&exported_charts, &args.output)?;
+/// Slugified chart title, like the web app's export names.
+fn chart_file_stem(doc: &ChartDocument) -> String {
+    let title = match doc {
+        ChartDocument::Chart(params) => params.title.as_deref(),
+        ChartDocument::Table(spec) => spec.title.as_deref(),
+    };
+    let slug = title
+        .unwrap_or_default()
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "chart".to_string()
+    } else {
+        slug
+    }
+}
 
-    // Build result map with only successful exports (file names only)
-    let result: HashMap<i64, String> = exported_charts
+fn exported_file_names(exported: Vec<(i64, Option<PathBuf>)>) -> HashMap<i64, String> {
+    exported
         .into_iter()
         .filter_map(|(index, path)| {
             path.map(|p| {
@@ -106,17 +177,129 @@ pub async fn handle_export_chart_command(
                 )
             })
         })
+        .collect()
+}
+
+/// Run the app locally and render its charts natively.
+async fn export_charts_native(
+    app_path: &str,
+    output_dir: &Path,
+) -> Result<Vec<(i64, Option<PathBuf>)>, OxyError> {
+    println!("{}", "   Running app...".text());
+    let workspace_manager = WorkspaceBuilder::new(Uuid::nil())
+        .with_workspace_path(&resolve_local_workspace_path()?)
+        .await?
+        .with_runs_manager(RunsManager::noop())
+        .build()
+        .await
+        .map_err(|e| OxyError::from(anyhow::anyhow!("Failed to create project: {e}")))?
+        .with_access_level(AccessLevel::Restricted);
+    let path = PathBuf::from(app_path);
+    let results = match AppService::new(workspace_manager.clone())
+        .run(&path, HashMap::new())
+        .await?
+    {
+        DataContainer::Map(results) => results,
+        _ => {
+            return Err(OxyError::RuntimeError(
+                "Unexpected app output format".to_string(),
+            ));
+        }
+    };
+    let task_data: HashMap<String, TaskOutput> = results
+        .iter()
+        .filter_map(|(name, data)| data_container_to_output(data).map(|o| (name.clone(), o)))
         .collect();
+    let (displays, _controls) = get_app_displays(workspace_manager, &path).await?;
+    let charts = app_chart_documents(&displays, &task_data);
+    if charts.is_empty() {
+        return Err(OxyError::RuntimeError(
+            "No charts found in the app".to_string(),
+        ));
+    }
+    println!(
+        "{}",
+        format!("   Rendering {} chart(s)...", charts.len()).text()
+    );
+
+    fs::create_dir_all(output_dir)
+        .map_err(|e| OxyError::RuntimeError(format!("Failed to create output directory: {}", e)))?;
+    let mut exported = Vec::with_capacity(charts.len());
+    for (index, doc) in charts {
+        match render_chart_file(&doc, index, output_dir) {
+            Ok(path) => {
+                println!(
+                    "{}",
+                    format!(
+                        "   ✓ Exported: {}",
+                        path.file_name().unwrap_or_default().to_string_lossy()
+                    )
+                    .text()
+                );
+                exported.push((index, Some(path)));
+            }
+            Err(e) => {
+                println!(
+                    "{}",
+                    format!("   ⚠ Could not render chart {}: {}", index, e).text()
+                );
+                exported.push((index, None));
+            }
+        }
+    }
+    Ok(exported)
+}
+
+pub async fn handle_export_chart_command(
+    args: ExportChartArgs,
+) -> Result<HashMap<i64, String>, OxyError> {
+    let app_path = args.app_path.trim();
+
+    let exported_charts = match args.renderer {
+        ChartRenderer::Native => {
+            print_header(app_path, None, &args.output);
+            export_charts_native(app_path, &args.output).await?
+        }
+        ChartRenderer::Chrome => {
+            let url = build_app_url(app_path);
+            print_header(app_path, Some(&url), &args.output);
+
+            fs::create_dir_all(&args.output).map_err(|e| {
+                OxyError::RuntimeError(format!("Failed to create output directory: {}", e))
+            })?;
+
+            let (_browser, tab) = launch_browser_and_navigate(&url)?;
+            let chart_indexes = discover_charts(&tab)?;
+
+            println!(
+                "{}",
+                format!(
+                    "   Found {} chart(s) with indexes {:?}, exporting sequentially...",
+                    chart_indexes.len(),
+                    chart_indexes
+                )
+                .text()
+            );
+
+            export_all_charts(&tab, &chart_indexes, &args.output)?
+        }
+    };
+    print_summary(&exported_charts, &args.output)?;
+
+    // Build result map with only successful exports (file names only)
+    let result = exported_file_names(exported_charts);
 
     println!("Export result: {:?}", result);
 
     Ok(result)
 }
 
-fn print_header(app_path: &str, url: &str, output: &PathBuf) {
+fn print_header(app_path: &str, url: Option<&str>, output: &Path) {
     println!("{}", "📊 Exporting charts...".info());
     println!("   App path: {}", app_path.secondary());
-    println!("   URL: {}", url.secondary());
+    if let Some(url) = url {
+        println!("   URL: {}", url.secondary());
+    }
     println!(
         "   Output directory: {}",
         output.display().to_string().secondary()
@@ -377,7 +560,7 @@ fn try_extract_chart_data(tab: &Arc<Tab>, selector: &str) -> Option<(String, Vec
     Some((name, bytes))
 }
 
-fn print_summary(results: &[(i64, Option<PathBuf>)], output_dir: &PathBuf) -> Result<(), OxyError> {
+fn print_summary(results: &[(i64, Option<PathBuf>)], output_dir: &Path) -> Result<(), OxyError> {
     let total = results.len();
     let successful = results.iter().filter(|(_, p)| p.is_some()).count();
     let failed = total - successful;
@@ -73,6 +73,7 @@ fn collect_databases() -> Result<Vec<Database>, InitError> {
         let database = Database {
             name,
             database_type,
+            cache_ttl_secs: None,
         };
 
         databases.push(database);
@@ -310,6 +311,8 @@ fn collect_models() -> Result<Vec<Model>, InitError> {
                         api_url: Some(api_url),
                         azure,
                         headers: None,
+                        fallbacks: vec![],
+                        rate_limit: None,
                     },
                 }
             }
@@ -319,6 +322,8 @@ fn collect_models() -> Result<Vec<Model>, InitError> {
                     model_ref: prompt_with_default("Model reference", "llama3.2:latest", None)?,
                     api_key: prompt_with_default("API Key", "secret", None)?,
                     api_url: prompt_with_default("API URL", "http://localhost:11434/v1", None)?,
+                    fallbacks: vec![],
+                    rate_limit: None,
                 },
             },
             _ => {
@@ -335,6 +340,8 @@ fn collect_models() -> Result<Vec<Model>, InitError> {
                         )?),
                         azure: None,
                         headers: None,
+                        fallbacks: vec![],
+                        rate_limit: None,
                     },
                 }
             }
@@ -439,6 +446,9 @@ fn create_config_file(config_path: &Path) -> Result<(), InitError> {
         slack_legacy: None,
         mcp: None,
         a2a: None,
+        result_cache: None,
+        budgets: None,
+        access_policies: Vec::new(),
         protected_branches: None,
         base_branch: None,
         repositories: vec![],
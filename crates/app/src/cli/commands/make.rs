@@ -83,6 +83,8 @@ fn determine_model() -> (String, Model) {
                     name,
                     model_ref: "gemini-1.5-pro".to_string(),
                     key_var: GEMINI_API_KEY_VAR.to_string(),
+                    fallbacks: vec![],
+                    rate_limit: None,
                 },
             },
         )
@@ -97,6 +99,8 @@ fn determine_model() -> (String, Model) {
                     key_var: ANTHROPIC_API_KEY_VAR.to_string(),
                     api_url: None,
                     headers: None,
+                    fallbacks: vec![],
+                    rate_limit: None,
                 },
             },
         )
@@ -112,6 +116,8 @@ fn determine_model() -> (String, Model) {
                     api_url: None,
                     azure: None,
                     headers: None,
+                    fallbacks: vec![],
+                    rate_limit: None,
                 },
             },
         )
@@ -127,6 +133,8 @@ fn determine_model() -> (String, Model) {
                     api_url: None,
                     azure: None,
                     headers: None,
+                    fallbacks: vec![],
+                    rate_limit: None,
                 },
             },
         )
@@ -205,6 +213,7 @@ async fn create_agent_file(
         description: "".to_string(),
         retrieval: Default::default(),
         reasoning: None,
+        budget: None,
         variables: None,
     };
 
@@ -254,6 +263,7 @@ pub async fn handle_make_command(make_args: &MakeArgs) -> anyhow::Result<()> {
                     file_search_path: "db/".to_string(),
                 },
             }),
+            cache_ttl_secs: None,
         }],
         defaults: Some(Defaults {
             agent: None,
@@ -266,6 +276,9 @@ pub async fn handle_make_command(make_args: &MakeArgs) -> anyhow::Result<()> {
         slack_legacy: None,
         mcp: None,
         a2a: None,
+        result_cache: None,
+        budgets: None,
+        access_policies: Vec::new(),
         protected_branches: None,
         base_branch: None,
         repositories: vec![],
@@ -37,6 +37,7 @@ use clap::Parser;
 use make::handle_make_command;
 use model::AgentConfig;
 use model::{Config, Workflow};
+use oxy_semantic::AccessLevel;
 use oxy_shared::errors::OxyError;
 use serve::start_server_and_web_app;
 use std::backtrace;
@@ -271,10 +272,10 @@ enum SubCommand {
     /// Discover and classify user intents from agent questions using
     /// unsupervised clustering techniques (HDBSCAN) and LLM labeling.
     Intent(intent::IntentArgs),
-    /// Export ECharts configuration to PNG image
+    /// Export app charts to PNG images
     ///
-    /// Render ECharts charts to PNG images using server-side rendering.
-    /// Requires Node.js to be installed on the system.
+    /// Runs the app and renders its charts in-process. Pass `--renderer chrome`
+    /// to screenshot them from the running web app with headless Chromium instead.
     ExportChart(export_chart::ExportChartArgs),
     /// Run and debug agentic analytics pipelines
     ///
@@ -294,6 +295,8 @@ pub struct MakeArgs {
 pub enum OutputFormat {
     Pretty,
     Json,
+    /// JUnit XML, for CI test reports
+    Junit,
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -317,7 +320,7 @@ pub struct TestArgs {
     /// Show full detail including agent steps, actual output, and judge reasoning
     #[clap(long, short = 'v', default_value_t = false)]
     verbose: bool,
-    /// Output format (pretty or json)
+    /// Output format (pretty, json or junit)
     #[clap(long, value_enum, default_value = "pretty")]
     format: OutputFormat,
     /// Minimum accuracy threshold (0.0-1.0). Exit with code 1 if accuracy is below this value
@@ -334,6 +337,18 @@ pub struct TestArgs {
     /// If --tag is also set, both filters apply: the case must match both the index/name/prompt and the tag.
     #[clap(long, value_name = "CASE")]
     case: Option<String>,
+    /// Compare per-case scores against a baseline file and exit with code 1 if any case
+    /// regressed. The comparison is skipped when the file does not exist yet.
+    #[clap(long, value_name = "PATH")]
+    baseline: Option<std::path::PathBuf>,
+    /// Write this run's per-case scores to the --baseline file. The file is only written when
+    /// the run passes: no case regressed and --min-accuracy, if set, is met.
+    #[clap(long, requires = "baseline", default_value_t = false)]
+    update_baseline: bool,
+    /// Largest score drop (0.0-1.0) a case may have against the baseline before it counts as
+    /// a regression
+    #[clap(long, value_name = "DELTA", default_value_t = 0.0)]
+    max_regression: f32,
 }
 
 #[derive(Parser, Debug)]
@@ -474,9 +489,14 @@ pub enum CleanTarget {
     Vectors,
     /// Clear cached files and temporary data
     ///
-    /// Removes cached chart files, logs, and other temporary data
-    /// while preserving .databases folder and vector embeddings.
-    Cache,
+    /// Removes cached chart files, query results, logs, and other temporary
+    /// data while preserving .databases folder and vector embeddings.
+    /// Prints query result cache hit rates before clearing.
+    Cache {
+        /// Only print the query result cache report, without clearing anything
+        #[clap(long)]
+        report: bool,
+    },
 }
 
 /// Validates a single file based on its extension.
@@ -731,6 +751,7 @@ pub async fn cli() -> Result<(), Box<dyn Error>> {
             reindex(ReindexInput {
                 config: config_manager.clone(),
                 secrets_manager,
+                workspace_id: Uuid::nil(),
                 drop_all_tables: build_args.drop_all_tables,
             })
             .await?;
@@ -753,6 +774,7 @@ pub async fn cli() -> Result<(), Box<dyn Error>> {
             search(SearchInput {
                 config: config_manager,
                 secrets_manager,
+                workspace_id: Uuid::nil(),
                 agent_ref: search_args.agent.to_string(),
                 query: search_args.question.to_string(),
             })
@@ -957,7 +979,8 @@ pub async fn cli() -> Result<(), Box<dyn Error>> {
                 .with_runs_manager(RunsManager::default(Uuid::nil(), Uuid::nil()).await?)
                 .build()
                 .await
-                .map_err(|e| OxyError::from(anyhow::anyhow!("Failed to create project: {e}")))?;
+                .map_err(|e| OxyError::from(anyhow::anyhow!("Failed to create project: {e}")))?
+                .with_access_level(AccessLevel::Restricted);
 
             // `oxy ask` uses the same trait-shaped consumer pipeline as
             // web + Slack: BlockHandler converts low-level events into
@@ -1218,6 +1241,12 @@ pub async fn handle_test_command(test_args: TestArgs) -> Result<(), OxyError> {
             "min-accuracy must be between 0.0 and 1.0, got: {threshold}"
         )));
     }
+    if !(0.0..=1.0).contains(&test_args.max_regression) {
+        return Err(OxyError::ConfigurationError(format!(
+            "max-regression must be between 0.0 and 1.0, got: {}",
+            test_args.max_regression
+        )));
+    }
 
     let workspace_path = resolve_local_workspace_path()?;
 
@@ -1227,7 +1256,8 @@ pub async fn handle_test_command(test_args: TestArgs) -> Result<(), OxyError> {
         .with_runs_manager(RunsManager::default(Uuid::nil(), Uuid::nil()).await?)
         .build()
         .await
-        .map_err(|e| OxyError::from(anyhow::anyhow!("Failed to create project: {e}")))?;
+        .map_err(|e| OxyError::from(anyhow::anyhow!("Failed to create project: {e}")))?
+        .with_access_level(AccessLevel::Restricted);
 
     // Determine which files to test
     let file_paths: Vec<std::path::PathBuf> = match &test_args.file {
@@ -1253,7 +1283,10 @@ pub async fn handle_test_command(test_args: TestArgs) -> Result<(), OxyError> {
         }
     };
 
-    use crate::integrations::eval::{JsonReporter, MetricKind, PrettyReporter, Reporter};
+    use crate::integrations::eval::{
+        Baseline, BaselineReporter, JUnitReporter, JsonReporter, MetricKind, PrettyReporter,
+        Reporter,
+    };
     use crate::server::service::eval::{SharedTokenStats, TokenStats};
     use std::sync::Arc;
     use tokio::sync::Mutex;
@@ -1380,8 +1413,16 @@ pub async fn handle_test_command(test_args: TestArgs) -> Result<(), OxyError> {
             handler,
         )
         .await?;
+        let test_file = file_path
+            .strip_prefix(workspace_manager.config_manager.workspace_path())
+            .unwrap_or(file_path)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
         for result in &mut results {
             result.test_name = Some(file_name.clone());
+            result.test_file = Some(test_file.clone());
         }
         all_results.extend(results);
     }
@@ -1399,6 +1440,7 @@ pub async fn handle_test_command(test_args: TestArgs) -> Result<(), OxyError> {
             duration_ms,
         }),
         OutputFormat::Json => Box::new(JsonReporter),
+        OutputFormat::Junit => Box::new(JUnitReporter),
     };
     let mut stdout = std::io::stdout();
     reporter.report(&all_results, &mut stdout)?;
@@ -1420,6 +1462,35 @@ pub async fn handle_test_command(test_args: TestArgs) -> Result<(), OxyError> {
         eprintln!("Results written to {output_path}");
     }
 
+    // Compare against the stored baseline, keeping machine-readable stdout clean
+    let mut regressed = 0;
+    if let Some(baseline_path) = &test_args.baseline {
+        if baseline_path.exists() {
+            let baseline_reporter = BaselineReporter {
+                baseline: Baseline::load(baseline_path)?,
+                max_regression: test_args.max_regression,
+            };
+            regressed = baseline_reporter
+                .baseline
+                .regressions(
+                    &Baseline::from_results(&all_results),
+                    test_args.max_regression,
+                )
+                .len();
+            match test_args.format {
+                OutputFormat::Pretty => baseline_reporter.report(&all_results, &mut stdout)?,
+                OutputFormat::Json | OutputFormat::Junit => {
+                    baseline_reporter.report(&all_results, &mut std::io::stderr())?
+                }
+            }
+        } else {
+            eprintln!(
+                "Warning: baseline '{}' not found, skipping comparison",
+                baseline_path.display()
+            );
+        }
+    }
+
     // Check threshold if provided
     if let Some(min_accuracy) = test_args.min_accuracy {
         // Collect all accuracy scores from all results
@@ -1475,6 +1546,24 @@ pub async fn handle_test_command(test_args: TestArgs) -> Result<(), OxyError> {
         }
     }
 
+    if regressed > 0 {
+        if test_args.update_baseline {
+            eprintln!("Baseline not updated, the run regressed");
+        }
+        return Err(OxyError::RuntimeError(format!(
+            "{regressed} case(s) regressed against the baseline by more than {:.4}",
+            test_args.max_regression
+        )));
+    }
+
+    // Only a passing run may become the new baseline
+    if let Some(baseline_path) = &test_args.baseline
+        && test_args.update_baseline
+    {
+        Baseline::from_results(&all_results).save(baseline_path)?;
+        eprintln!("Baseline written to {}", baseline_path.display());
+    }
+
     Ok(())
 }
 
@@ -1555,7 +1644,10 @@ async fn handle_clean_command(clean_args: CleanArgs) -> Result<(), OxyError> {
         CleanTarget::Vectors => {
             clean_vectors(true, &config_manager).await?;
         }
-        CleanTarget::Cache => {
+        CleanTarget::Cache { report: true } => {
+            report_result_cache(&config_manager).await?;
+        }
+        CleanTarget::Cache { report: false } => {
             clean_cache(true, &config_manager).await?;
         }
     }
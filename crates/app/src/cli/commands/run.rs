@@ -15,6 +15,7 @@ use ::oxy::connector::Connector;
 use ::oxy::execute::types::utils::record_batches_to_table;
 use ::oxy::sentry_config;
 use ::oxy::utils::print_colored_sql;
+use oxy_semantic::AccessLevel;
 use oxy_shared::errors::OxyError;
 use oxy_workflow::loggers::cli::WorkflowCLILogger;
 
@@ -214,7 +215,8 @@ async fn handle_workflow_file(
         .with_runs_manager(runs_manager)
         .build()
         .await
-        .map_err(|e| OxyError::from(anyhow::anyhow!("Failed to create project: {e}")))?;
+        .map_err(|e| OxyError::from(anyhow::anyhow!("Failed to create project: {e}")))?
+        .with_access_level(AccessLevel::Restricted);
     // Add Sentry context for workflow execution
     let workflow_name_str = workflow_path
         .file_name()
@@ -324,7 +326,8 @@ async fn setup_agent_run(
         .with_runs_manager(RunsManager::noop())
         .build()
         .await
-        .map_err(|e| OxyError::from(anyhow::anyhow!("Failed to create project: {e}")))?;
+        .map_err(|e| OxyError::from(anyhow::anyhow!("Failed to create project: {e}")))?
+        .with_access_level(AccessLevel::Restricted);
 
     Ok((question, workspace_manager))
 }
@@ -93,6 +93,7 @@ pub async fn start_server_and_web_app(args: ServeArgs) -> Result<(), OxyError> {
     // present (the providers are ignored in local mode).
     let auth_configured = std::env::var("GOOGLE_CLIENT_ID").is_ok()
         || std::env::var("OKTA_CLIENT_ID").is_ok()
+        || std::env::var("OIDC_CLIENT_ID").is_ok()
         || std::env::var("MAGIC_LINK_SECRET").is_ok();
 
     // Tell `oxy-auth`'s built-in authenticator whether at least one provider
@@ -103,7 +104,9 @@ pub async fn start_server_and_web_app(args: ServeArgs) -> Result<(), OxyError> {
         let yaml_provider = oxy::config::oxy::get_oxy_config()
             .ok()
             .and_then(|c| c.authentication)
-            .map(|a| a.google.is_some() || a.okta.is_some() || a.magic_link.is_some())
+            .map(|a| {
+                a.google.is_some() || a.okta.is_some() || a.oidc.is_some() || a.magic_link.is_some()
+            })
             .unwrap_or(false);
         oxy_auth::built_in::set_auth_configured(yaml_provider);
     }
@@ -227,6 +230,13 @@ pub async fn start_server_and_web_app(args: ServeArgs) -> Result<(), OxyError> {
     }
 
     let shutdown_token = CancellationToken::new();
+
+    // Cron-scheduled workflow runs (`schedule:` blocks in .workflow.yml files).
+    crate::server::service::schedule::spawn_scheduler(mode, shutdown_token.clone());
+
+    // Purges audit log entries past OXY_AUDIT_LOG_RETENTION_DAYS.
+    oxy::audit::spawn_retention_cleanup(shutdown_token.clone());
+
     let startup_cwd = std::env::current_dir().map_err(|e| {
         OxyError::RuntimeError(format!("Failed to resolve startup working directory: {e}"))
     })?;
@@ -158,7 +158,7 @@ impl AgentCardService {
         // Set capabilities
         card.capabilities = AgentCapabilities {
             streaming: Some(true),
-            push_notifications: Some(false),
+            push_notifications: Some(true),
             state_transition_history: Some(true),
             extensions: None,
         };
@@ -117,6 +117,9 @@ impl A2aConfig {
             slack_legacy: None,
             mcp: None,
             a2a: None,
+            result_cache: None,
+            budgets: None,
+            access_policies: Vec::new(),
             protected_branches: None,
             base_branch: None,
             repositories: vec![],
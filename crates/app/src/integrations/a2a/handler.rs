@@ -10,7 +10,8 @@
 //!   via the `agent_name` field. This agent name determines which Oxy agent
 //!   configuration to load and execute.
 //! - **Factory Pattern**: Use `new()` to create agent-scoped instances.
-//! - **Storage Integration**: Uses agent-scoped `TaskStorage` for task persistence.
+//! - **Storage Integration**: Uses agent-scoped `TaskStorage` for task persistence
+//!   and `PushNotificationStorage` for webhook configs.
 //! - **Agent Execution**: Leverages existing Oxy `AgentLauncher` for execution.
 //!
 //! # Example
@@ -23,6 +24,7 @@
 //!     "sales-assistant".to_string(),
 //!     config.clone(),
 //!     storage.clone(),
+//!     push_notifications.clone(),
 //!     workspace_manager.clone(),
 //! );
 //!
@@ -33,7 +35,7 @@
 use a2a::{
     error::A2aError,
     server::{A2aContext, A2aHandler, SseStream},
-    storage::TaskStorage,
+    storage::{PushNotificationStorage, TaskStorage},
     types::{AgentCard, Message, Task},
 };
 use async_trait::async_trait;
@@ -44,12 +46,15 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::server::service::api_key::ApiKeyConfig;
+use crate::server::service::permissions::WorkspacePermissions;
+use entity::custom_roles::Permission;
 use oxy::config::constants::DEFAULT_API_KEY_HEADER;
 use oxy::{adapters::workspace::manager::WorkspaceManager, config::model::Config};
-use oxy_auth::authenticate_header_with_config;
+use oxy_auth::{ValidatedApiKey, authenticate_header_with_config};
 
 use super::agent_card::AgentCardService;
 use super::methods;
+use super::push_notifications::OxyPushNotificationStorage;
 use super::storage::OxyTaskStorage;
 use oxy::config::a2a_config::A2aConfig;
 
@@ -63,6 +68,7 @@ use oxy::config::a2a_config::A2aConfig;
 /// - `agent_name`: The name of the Oxy agent this handler executes
 /// - `config`: Global Oxy configuration (includes A2A config)
 /// - `storage`: Agent-scoped task storage for persistence
+/// - `push_notifications`: Agent-scoped push notification config storage
 /// - `workspace_manager`: Project manager for accessing agent configs and execution context
 /// - `agent_card_service`: Service for generating and caching agent cards
 pub struct OxyA2aHandler {
@@ -72,6 +78,8 @@ pub struct OxyA2aHandler {
     config: Arc<Config>,
     /// Agent-scoped task storage
     storage: Arc<OxyTaskStorage>,
+    /// Agent-scoped push notification config storage
+    push_notifications: Arc<OxyPushNotificationStorage>,
     /// Database connection for auth and auditing
     db: Arc<DatabaseConnection>,
     /// Project manager for agent execution
@@ -90,6 +98,7 @@ impl OxyA2aHandler {
     /// * `agent_name` - The name of the agent (from A2A configuration)
     /// * `config` - Global Oxy configuration
     /// * `storage` - Agent-scoped task storage
+    /// * `push_notifications` - Agent-scoped push notification config storage
     /// * `db` - Database connection for auth and auditing
     /// * `workspace_manager` - Project manager for agent execution
     /// * `agent_card_service` - Service for generating agent cards
@@ -102,6 +111,7 @@ impl OxyA2aHandler {
         agent_name: String,
         config: Arc<Config>,
         storage: Arc<OxyTaskStorage>,
+        push_notifications: Arc<OxyPushNotificationStorage>,
         db: Arc<DatabaseConnection>,
         workspace_manager: Arc<WorkspaceManager>,
         agent_card_service: Arc<AgentCardService>,
@@ -111,6 +121,7 @@ impl OxyA2aHandler {
             agent_name,
             config,
             storage,
+            push_notifications,
             db,
             workspace_manager,
             agent_card_service,
@@ -192,6 +203,8 @@ impl OxyA2aHandler {
             A2aError::Unauthorized(format!("API key authentication failed: {}", err))
         })?;
 
+        self.authorize_key(ctx, &validated_key).await?;
+
         let principal = A2aPrincipal {
             user_id: validated_key.user_id,
             api_key_id: validated_key.id,
@@ -211,6 +224,52 @@ impl OxyA2aHandler {
         Ok(Some(principal))
     }
 
+    /// Check the key's owner may run this agent. Custom roles assigned to
+    /// the owner or attached to the key can limit `agents.run` to particular
+    /// agents; the standalone server has no workspace role, so only custom
+    /// roles apply.
+    async fn authorize_key(
+        &self,
+        ctx: &A2aContext,
+        validated_key: &ValidatedApiKey,
+    ) -> Result<(), A2aError> {
+        let permissions = WorkspacePermissions::all()
+            .narrowed(
+                self.db.as_ref(),
+                validated_key.project_id,
+                Some(validated_key.user_id),
+                validated_key.custom_role_id,
+            )
+            .await
+            .map_err(|e| {
+                tracing::error!(
+                    target = "a2a::auth",
+                    agent = %self.agent_name,
+                    request_id = %ctx.request_id,
+                    "Failed to load permissions: {}",
+                    e
+                );
+                A2aError::ServerError("Failed to load permissions".to_string())
+            })?;
+
+        let agent_ref = self.agent_ref()?;
+        if !permissions.allows(Permission::RunAgents, Some(&agent_ref)) {
+            tracing::warn!(
+                target = "a2a::auth",
+                agent = %self.agent_name,
+                request_id = %ctx.request_id,
+                api_key_id = %validated_key.id,
+                "API key is not allowed to run agent {}",
+                agent_ref
+            );
+            return Err(A2aError::Forbidden(format!(
+                "Not allowed to run agent '{}'",
+                self.agent_name
+            )));
+        }
+        Ok(())
+    }
+
     fn principal_metadata(principal: &A2aPrincipal) -> HashMap<String, Value> {
         let principal_value = serde_json::json!({
             "user_id": principal.user_id,
@@ -361,6 +420,25 @@ impl A2aHandler for OxyA2aHandler {
     fn task_storage(&self) -> &dyn TaskStorage {
         self.storage.as_ref()
     }
+
+    /// Get the push notification config storage.
+    ///
+    /// Notifications are delivered by `OxyTaskStorage` when it persists a
+    /// status update, so every task of this agent supports them.
+    fn push_notification_storage(&self) -> Option<&dyn PushNotificationStorage> {
+        Some(self.push_notifications.as_ref())
+    }
+
+    /// Handle a tasks/resubscribe request.
+    ///
+    /// Replays the task from storage and follows it until it finishes.
+    async fn handle_resubscribe(
+        &self,
+        _ctx: A2aContext,
+        task_id: String,
+    ) -> Result<SseStream, A2aError> {
+        methods::resubscribe::handle_resubscribe(task_id, self.storage.clone()).await
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -5,8 +5,10 @@
 //!
 //! - `message.rs` - Message sending logic (synchronous)
 //! - `streaming.rs` - Streaming message logic (SSE)
+//! - `resubscribe.rs` - Task resubscription logic (SSE replay)
 //! - `agent.rs` - Agent card generation logic
 
 pub mod agent;
 pub mod message;
+pub mod resubscribe;
 pub mod streaming;
@@ -0,0 +1,240 @@
+//! Task resubscription logic for the A2A tasks/resubscribe method.
+//!
+//! This module lets a client that lost its `message/stream` connection
+//! reattach to a task. The task is replayed from the persisted A2A tables
+//! (`a2a_messages`, `a2a_artifacts` and the status history), so this works
+//! from any server instance and after a restart.
+//!
+//! Streaming runs only persist their artifacts once the agent finishes, so
+//! for a task that is still running the stream sends what is stored so far,
+//! then polls storage and sends the artifacts and final status once the task
+//! reaches a final state.
+
+use a2a::{
+    error::A2aError,
+    server::SseStream,
+    storage::TaskStorage,
+    streaming::{SseEvent, SseEventType},
+    types::{
+        ArtifactUpdateKind, StatusUpdateKind, Task, TaskArtifactUpdateEvent, TaskState,
+        TaskStatusUpdateEvent,
+    },
+    validation::is_terminal_state,
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::integrations::a2a::storage::OxyTaskStorage;
+
+/// How often a running task is reloaded from storage.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Handle a tasks/resubscribe request.
+///
+/// Returns an SSE stream in the `message/stream` format:
+/// 1. A snapshot of the task with its message history
+/// 2. An artifact update per persisted artifact
+/// 3. A status update, with `final: true` once the task is done
+///
+/// While the task is running, new artifacts and status changes are sent as
+/// they are persisted. The stream ends after the final status update, or
+/// when the client disconnects.
+pub async fn handle_resubscribe(
+    task_id: String,
+    storage: Arc<OxyTaskStorage>,
+) -> Result<SseStream, A2aError> {
+    tracing::info!("Resubscribing to A2A task {}", task_id);
+
+    let task = storage
+        .get_task(task_id.clone())
+        .await?
+        .ok_or_else(|| A2aError::TaskNotFound(task_id.clone()))?;
+
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<Result<SseEvent, A2aError>>();
+
+    tokio::spawn(async move {
+        let mut replay = Replay::default();
+        let mut task = task;
+        loop {
+            for event in replay.events(&task) {
+                if tx.send(Ok(event)).is_err() {
+                    return;
+                }
+            }
+            if is_terminal_state(&task.status.state) {
+                return;
+            }
+
+            tokio::time::sleep(POLL_INTERVAL).await;
+            if tx.is_closed() {
+                return;
+            }
+
+            task = match storage.get_task(task_id.clone()).await {
+                Ok(Some(task)) => task,
+                Ok(None) => {
+                    let _ = tx.send(Err(A2aError::TaskNotFound(task_id)));
+                    return;
+                }
+                Err(e) => {
+                    tracing::error!("Failed to reload task {} for resubscribe: {}", task_id, e);
+                    let _ = tx.send(Err(e));
+                    return;
+                }
+            };
+        }
+    });
+
+    let stream = tokio_stream::wrappers::UnboundedReceiverStream::new(rx);
+
+    Ok(Box::pin(stream))
+}
+
+/// What has been sent for a task so far.
+#[derive(Default)]
+struct Replay {
+    sent_snapshot: bool,
+    sent_artifacts: HashSet<String>,
+    last_state: Option<TaskState>,
+}
+
+impl Replay {
+    /// Events for whatever changed in `task` since the last call.
+    fn events(&mut self, task: &Task) -> Vec<SseEvent> {
+        let mut events = Vec::new();
+
+        if !self.sent_snapshot {
+            // Artifacts follow as artifact updates, as in message/stream
+            let snapshot = Task {
+                artifacts: None,
+                ..task.clone()
+            };
+            events.push(SseEvent::with_type(
+                SseEventType::TaskProgress,
+                serde_json::to_string(&snapshot).unwrap_or_default(),
+            ));
+            self.sent_snapshot = true;
+        }
+
+        for artifact in task.artifacts.iter().flatten() {
+            if !self.sent_artifacts.insert(artifact.artifact_id.clone()) {
+                continue;
+            }
+            let event_data = TaskArtifactUpdateEvent {
+                task_id: task.id.clone(),
+                context_id: task.context_id.clone(),
+                kind: ArtifactUpdateKind::ArtifactUpdate,
+                artifact: artifact.clone(),
+                append: Some(false),
+                last_chunk: Some(true),
+                metadata: task.metadata.clone(),
+            };
+            events.push(SseEvent::with_type(
+                SseEventType::ArtifactUpdate,
+                serde_json::to_string(&event_data).unwrap_or_default(),
+            ));
+        }
+
+        if self.last_state.as_ref() != Some(&task.status.state) {
+            let status_event = TaskStatusUpdateEvent {
+                task_id: task.id.clone(),
+                context_id: task.context_id.clone(),
+                kind: StatusUpdateKind::StatusUpdate,
+                status: task.status.clone(),
+                is_final: is_terminal_state(&task.status.state),
+                metadata: task.metadata.clone(),
+            };
+            events.push(SseEvent::with_type(
+                SseEventType::TaskStatusUpdate,
+                serde_json::to_string(&status_event).unwrap_or_default(),
+            ));
+            self.last_state = Some(task.status.state.clone());
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use a2a::types::{Artifact, Message, Part, TaskKind, TaskStatus, TextPart};
+
+    fn task(state: TaskState, artifacts: Vec<Artifact>) -> Task {
+        Task {
+            id: "task-1".to_string(),
+            context_id: "context-1".to_string(),
+            status: TaskStatus::new(state),
+            history: Some(vec![Message::new_user(vec![Part::Text(TextPart::new(
+                "How did sales do last quarter?",
+            ))])]),
+            artifacts: if artifacts.is_empty() {
+                None
+            } else {
+                Some(artifacts)
+            },
+            metadata: None,
+            kind: TaskKind::Task,
+        }
+    }
+
+    fn artifact(id: &str) -> Artifact {
+        let mut artifact = Artifact::new(vec![Part::Text(TextPart::new("Revenue grew 12%"))]);
+        artifact.artifact_id = id.to_string();
+        artifact
+    }
+
+    fn event_types(events: &[SseEvent]) -> Vec<SseEventType> {
+        events
+            .iter()
+            .map(|event| event.event_type.clone().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_replays_finished_task() {
+        let mut replay = Replay::default();
+        let events = replay.events(&task(
+            TaskState::Completed,
+            vec![artifact("a"), artifact("b")],
+        ));
+        assert_eq!(
+            event_types(&events),
+            vec![
+                SseEventType::TaskProgress,
+                SseEventType::ArtifactUpdate,
+                SseEventType::ArtifactUpdate,
+                SseEventType::TaskStatusUpdate,
+            ]
+        );
+
+        let snapshot: Task = serde_json::from_str(&events[0].data).unwrap();
+        assert!(snapshot.artifacts.is_none());
+        assert_eq!(snapshot.history.unwrap().len(), 1);
+
+        let status: TaskStatusUpdateEvent = serde_json::from_str(&events[3].data).unwrap();
+        assert!(status.is_final);
+    }
+
+    #[test]
+    fn test_running_task_sends_only_changes() {
+        let mut replay = Replay::default();
+        let events = replay.events(&task(TaskState::Working, vec![]));
+        assert_eq!(
+            event_types(&events),
+            vec![SseEventType::TaskProgress, SseEventType::TaskStatusUpdate]
+        );
+        let status: TaskStatusUpdateEvent = serde_json::from_str(&events[1].data).unwrap();
+        assert!(!status.is_final);
+
+        // Nothing new persisted since the last poll
+        assert!(replay.events(&task(TaskState::Working, vec![])).is_empty());
+
+        let events = replay.events(&task(TaskState::Completed, vec![artifact("a")]));
+        assert_eq!(
+            event_types(&events),
+            vec![SseEventType::ArtifactUpdate, SseEventType::TaskStatusUpdate]
+        );
+    }
+}
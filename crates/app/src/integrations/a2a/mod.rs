@@ -17,6 +17,7 @@
 //!    - Configuration loading and validation
 //!    - Handler implementation with Oxy agent execution
 //!    - Task storage backed by Oxy database
+//!    - Push notification configs and signed webhook delivery
 //!    - Multi-agent routing and management
 //!
 //! # Multi-Agent Support
@@ -55,6 +56,7 @@ pub mod config;
 pub mod handler;
 pub mod mapper;
 pub mod methods;
+pub mod push_notifications;
 pub mod storage;
 
 // Re-export key types from a2a crate for convenience
@@ -0,0 +1,471 @@
+//! A2A push notifications: config storage and webhook delivery.
+//!
+//! Clients register webhooks for a task with `tasks/pushNotificationConfig/set`
+//! instead of keeping a `message/stream` connection open for the whole run.
+//! Configs live in `a2a_push_notification_configs`, scoped to an agent like
+//! the rest of the A2A tables.
+//!
+//! Whenever `OxyTaskStorage` persists a status change, [`PushNotifier`] POSTs
+//! the full task as JSON to every config registered for it. Each request
+//! carries:
+//!
+//! - `X-A2A-Notification-Token`: the config's token
+//! - `X-Oxy-Signature`: `t={unix_ts},v1={hex}`, where `hex` is the
+//!   HMAC-SHA256 of `{unix_ts}.{body}` keyed with the server's
+//!   `OXY_A2A_SIGNING_SECRET`, when that is set. The token travels with the
+//!   request, so it is never used as a signing key.
+//! - `Authorization`: `{scheme} {credentials}` when the config has
+//!   authentication credentials
+//!
+//! Deliveries that fail with a network error, `408`, `429` or a `5xx` are
+//! retried with exponential backoff; other responses are final. Redirects
+//! are not followed, and webhook hosts are only connected to on public
+//! addresses, even if their DNS changes after the config was validated.
+
+use a2a::{
+    error::A2aError,
+    storage::{PushNotificationStorage, TaskStorage},
+    types::{PushNotificationAuthenticationInfo, PushNotificationConfig},
+    validation::is_public_ip,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use entity::a2a_push_notification_configs;
+use hmac::{Hmac, KeyInit, Mac};
+use reqwest::{
+    StatusCode,
+    dns::{Addrs, Name, Resolve, Resolving},
+};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder,
+};
+use sha2::Sha256;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+pub const SIGNATURE_HEADER: &str = "X-Oxy-Signature";
+pub const NOTIFICATION_TOKEN_HEADER: &str = "X-A2A-Notification-Token";
+
+const MAX_ATTEMPTS: u32 = 5;
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Agent-scoped push notification config storage backed by
+/// `a2a_push_notification_configs`.
+#[derive(Clone)]
+pub struct OxyPushNotificationStorage {
+    agent_name: String,
+    db: Arc<DatabaseConnection>,
+}
+
+impl OxyPushNotificationStorage {
+    pub fn new_for_agent(agent_name: String, db: Arc<DatabaseConnection>) -> Self {
+        Self { agent_name, db }
+    }
+
+    async fn find(
+        &self,
+        task_uuid: Uuid,
+        config_id: &str,
+    ) -> Result<Option<a2a_push_notification_configs::Model>, A2aError> {
+        a2a_push_notification_configs::Entity::find()
+            .filter(a2a_push_notification_configs::Column::TaskId.eq(task_uuid))
+            .filter(a2a_push_notification_configs::Column::AgentName.eq(&self.agent_name))
+            .filter(a2a_push_notification_configs::Column::ConfigId.eq(config_id))
+            .one(self.db.as_ref())
+            .await
+            .map_err(|e| {
+                A2aError::StorageError(format!("Failed to query push notification config: {}", e))
+            })
+    }
+}
+
+fn parse_task_id(task_id: &str) -> Result<Uuid, A2aError> {
+    Uuid::parse_str(task_id).map_err(|e| A2aError::InvalidTask(format!("Invalid task ID: {}", e)))
+}
+
+fn model_to_config(model: a2a_push_notification_configs::Model) -> PushNotificationConfig {
+    PushNotificationConfig {
+        id: Some(model.config_id),
+        url: model.url,
+        token: model.token,
+        authentication: model
+            .authentication
+            .and_then(|value| serde_json::from_value(value).ok()),
+    }
+}
+
+fn authentication_to_json(
+    authentication: &Option<PushNotificationAuthenticationInfo>,
+) -> Result<Option<serde_json::Value>, A2aError> {
+    authentication
+        .as_ref()
+        .map(serde_json::to_value)
+        .transpose()
+        .map_err(|e| A2aError::InvalidParams(format!("Invalid authentication info: {}", e)))
+}
+
+#[async_trait]
+impl PushNotificationStorage for OxyPushNotificationStorage {
+    async fn set_config(
+        &self,
+        task_id: String,
+        config: PushNotificationConfig,
+    ) -> Result<PushNotificationConfig, A2aError> {
+        let task_uuid = parse_task_id(&task_id)?;
+        let config_id = config
+            .id
+            .clone()
+            .ok_or_else(|| A2aError::InvalidParams("Push notification config has no ID".into()))?;
+        let authentication = authentication_to_json(&config.authentication)?;
+
+        let result = match self.find(task_uuid, &config_id).await? {
+            Some(existing) => {
+                let mut active: a2a_push_notification_configs::ActiveModel = existing.into();
+                active.url = ActiveValue::Set(config.url.clone());
+                active.token = ActiveValue::Set(config.token.clone());
+                active.authentication = ActiveValue::Set(authentication);
+                active.updated_at = ActiveValue::Set(Utc::now().into());
+                active.update(self.db.as_ref()).await
+            }
+            None => {
+                a2a_push_notification_configs::ActiveModel {
+                    id: ActiveValue::Set(Uuid::new_v4()),
+                    task_id: ActiveValue::Set(task_uuid),
+                    agent_name: ActiveValue::Set(self.agent_name.clone()),
+                    config_id: ActiveValue::Set(config_id),
+                    url: ActiveValue::Set(config.url.clone()),
+                    token: ActiveValue::Set(config.token.clone()),
+                    authentication: ActiveValue::Set(authentication),
+                    created_at: ActiveValue::NotSet,
+                    updated_at: ActiveValue::NotSet,
+                }
+                .insert(self.db.as_ref())
+                .await
+            }
+        };
+
+        result.map(model_to_config).map_err(|e| {
+            A2aError::StorageError(format!("Failed to store push notification config: {}", e))
+        })
+    }
+
+    async fn get_config(
+        &self,
+        task_id: String,
+        config_id: Option<String>,
+    ) -> Result<Option<PushNotificationConfig>, A2aError> {
+        let task_uuid = parse_task_id(&task_id)?;
+        let model = match config_id {
+            Some(config_id) => self.find(task_uuid, &config_id).await?,
+            None => a2a_push_notification_configs::Entity::find()
+                .filter(a2a_push_notification_configs::Column::TaskId.eq(task_uuid))
+                .filter(a2a_push_notification_configs::Column::AgentName.eq(&self.agent_name))
+                .order_by_desc(a2a_push_notification_configs::Column::UpdatedAt)
+                .one(self.db.as_ref())
+                .await
+                .map_err(|e| {
+                    A2aError::StorageError(format!(
+                        "Failed to query push notification config: {}",
+                        e
+                    ))
+                })?,
+        };
+        Ok(model.map(model_to_config))
+    }
+
+    async fn list_configs(&self, task_id: String) -> Result<Vec<PushNotificationConfig>, A2aError> {
+        let task_uuid = parse_task_id(&task_id)?;
+        let models = a2a_push_notification_configs::Entity::find()
+            .filter(a2a_push_notification_configs::Column::TaskId.eq(task_uuid))
+            .filter(a2a_push_notification_configs::Column::AgentName.eq(&self.agent_name))
+            .order_by_asc(a2a_push_notification_configs::Column::CreatedAt)
+            .all(self.db.as_ref())
+            .await
+            .map_err(|e| {
+                A2aError::StorageError(format!("Failed to list push notification configs: {}", e))
+            })?;
+        Ok(models.into_iter().map(model_to_config).collect())
+    }
+
+    async fn delete_config(&self, task_id: String, config_id: String) -> Result<(), A2aError> {
+        let task_uuid = parse_task_id(&task_id)?;
+        let result = a2a_push_notification_configs::Entity::delete_many()
+            .filter(a2a_push_notification_configs::Column::TaskId.eq(task_uuid))
+            .filter(a2a_push_notification_configs::Column::AgentName.eq(&self.agent_name))
+            .filter(a2a_push_notification_configs::Column::ConfigId.eq(&config_id))
+            .exec(self.db.as_ref())
+            .await
+            .map_err(|e| {
+                A2aError::StorageError(format!("Failed to delete push notification config: {}", e))
+            })?;
+
+        if result.rows_affected == 0 {
+            return Err(A2aError::InvalidParams(format!(
+                "Push notification config '{}' not found for task '{}'",
+                config_id, task_id
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Sends a task's current state to the webhooks registered for it.
+pub struct PushNotifier {
+    configs: OxyPushNotificationStorage,
+    client: reqwest::Client,
+    signing_secret: Option<Arc<str>>,
+}
+
+impl PushNotifier {
+    /// Notifications are signed with `signing_secret` when one is given.
+    pub fn new(configs: OxyPushNotificationStorage, signing_secret: Option<String>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::none())
+            .dns_resolver(Arc::new(PublicResolver))
+            .build()
+            .expect("Failed to build push notification HTTP client");
+        Self {
+            configs,
+            client,
+            signing_secret: signing_secret.map(Arc::from),
+        }
+    }
+
+    /// Notify the task's webhooks, if any, of its current state. Deliveries
+    /// run in the background so the caller never waits on retries.
+    pub async fn task_updated(&self, storage: &dyn TaskStorage, task_id: &str) {
+        let configs = match self.configs.list_configs(task_id.to_string()).await {
+            Ok(configs) if configs.is_empty() => return,
+            Ok(configs) => configs,
+            Err(e) => {
+                tracing::error!("Failed to load push notification configs for task {task_id}: {e}");
+                return;
+            }
+        };
+
+        let task = match storage.get_task(task_id.to_string()).await {
+            Ok(Some(task)) => task,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::error!("Failed to load task {task_id} for push notification: {e}");
+                return;
+            }
+        };
+
+        let body = match serde_json::to_vec(&task) {
+            Ok(body) => Arc::new(body),
+            Err(e) => {
+                tracing::error!("Failed to serialize task {task_id} for push notification: {e}");
+                return;
+            }
+        };
+
+        for config in configs {
+            let client = self.client.clone();
+            let signing_secret = self.signing_secret.clone();
+            let body = body.clone();
+            let task_id = task.id.clone();
+            tokio::spawn(async move {
+                deliver(&client, &config, signing_secret.as_deref(), &task_id, &body).await;
+            });
+        }
+    }
+}
+
+/// Resolves webhook hosts to their public addresses only, so a host that
+/// passed validation cannot later be pointed at the server's own network.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} has no public address", name.as_str()).into());
+            }
+            Ok::<Addrs, Box<dyn std::error::Error + Send + Sync>>(Box::new(addrs.into_iter()))
+        })
+    }
+}
+
+/// POST `body` to the config's URL, retrying transient failures.
+async fn deliver(
+    client: &reqwest::Client,
+    config: &PushNotificationConfig,
+    signing_secret: Option<&str>,
+    task_id: &str,
+    body: &[u8],
+) {
+    for attempt in 0..MAX_ATTEMPTS {
+        if attempt > 0 {
+            tokio::time::sleep(retry_delay(attempt)).await;
+        }
+
+        let request = signed_request(client, config, signing_secret, body, Utc::now().timestamp());
+        match request.send().await {
+            Ok(response) if response.status().is_success() => {
+                tracing::debug!(
+                    "Delivered push notification for task {task_id} to {}",
+                    config.url
+                );
+                return;
+            }
+            Ok(response) if !is_retryable(response.status()) => {
+                tracing::warn!(
+                    "Push notification for task {task_id} rejected by {}: {}",
+                    config.url,
+                    response.status()
+                );
+                return;
+            }
+            Ok(response) => tracing::warn!(
+                "Push notification for task {task_id} to {} failed (attempt {}/{MAX_ATTEMPTS}): {}",
+                config.url,
+                attempt + 1,
+                response.status()
+            ),
+            Err(e) => tracing::warn!(
+                "Push notification for task {task_id} to {} failed (attempt {}/{MAX_ATTEMPTS}): {e}",
+                config.url,
+                attempt + 1
+            ),
+        }
+    }
+    tracing::error!(
+        "Giving up on push notification for task {task_id} to {} after {MAX_ATTEMPTS} attempts",
+        config.url
+    );
+}
+
+fn signed_request(
+    client: &reqwest::Client,
+    config: &PushNotificationConfig,
+    signing_secret: Option<&str>,
+    body: &[u8],
+    timestamp: i64,
+) -> reqwest::RequestBuilder {
+    let mut request = client
+        .post(&config.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body.to_vec());
+
+    if let Some(token) = &config.token {
+        request = request.header(NOTIFICATION_TOKEN_HEADER, token);
+    }
+    if let Some(secret) = signing_secret {
+        request = request.header(SIGNATURE_HEADER, sign(secret, timestamp, body));
+    }
+
+    if let Some(PushNotificationAuthenticationInfo {
+        schemes,
+        credentials: Some(credentials),
+    }) = &config.authentication
+        && let Some(scheme) = schemes.first()
+    {
+        request = request.header(
+            reqwest::header::AUTHORIZATION,
+            format!("{scheme} {credentials}"),
+        );
+    }
+
+    request
+}
+
+/// `t={timestamp},v1={hex}` with the HMAC-SHA256 of `{timestamp}.{body}`.
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC key");
+    mac.update(format!("{timestamp}.").as_bytes());
+    mac.update(body);
+    let signature = hex::encode(mac.finalize().into_bytes());
+    format!("t={timestamp},v1={signature}")
+}
+
+/// Delay before retry number `attempt` (1-based): 1s, 2s, 4s, ... up to a minute.
+fn retry_delay(attempt: u32) -> Duration {
+    INITIAL_BACKOFF
+        .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+        .min(MAX_BACKOFF)
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature() {
+        let body = br#"{"id":"task-1"}"#;
+        let signature = sign("secret", 1_700_000_000, body);
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(b"1700000000.");
+        mac.update(body);
+        let expected = hex::encode(mac.finalize().into_bytes());
+        assert_eq!(signature, format!("t=1700000000,v1={expected}"));
+
+        assert_ne!(signature, sign("other", 1_700_000_000, body));
+        assert_ne!(signature, sign("secret", 1_700_000_001, body));
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let delays: Vec<u64> = (1..=8).map(|a| retry_delay(a).as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 32, 60, 60]);
+    }
+
+    #[test]
+    fn test_retryable_statuses() {
+        assert!(is_retryable(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(is_retryable(StatusCode::BAD_GATEWAY));
+        assert!(is_retryable(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable(StatusCode::REQUEST_TIMEOUT));
+        assert!(!is_retryable(StatusCode::BAD_REQUEST));
+        assert!(!is_retryable(StatusCode::UNAUTHORIZED));
+        assert!(!is_retryable(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn test_signed_request_headers() {
+        let client = reqwest::Client::new();
+        let config = PushNotificationConfig {
+            id: Some("hook".to_string()),
+            url: "https://example.com/hooks/a2a".to_string(),
+            token: Some("secret".to_string()),
+            authentication: Some(PushNotificationAuthenticationInfo {
+                schemes: vec!["Bearer".to_string()],
+                credentials: Some("abc".to_string()),
+            }),
+        };
+        let request = signed_request(&client, &config, Some("server-secret"), b"{}", 42)
+            .build()
+            .unwrap();
+        let headers = request.headers();
+        assert_eq!(headers[NOTIFICATION_TOKEN_HEADER], "secret");
+        assert_eq!(
+            headers[SIGNATURE_HEADER].to_str().unwrap(),
+            sign("server-secret", 42, b"{}")
+        );
+        assert_eq!(headers[reqwest::header::AUTHORIZATION], "Bearer abc");
+
+        // Without a server secret, notifications carry the token but no signature
+        let request = signed_request(&client, &config, None, b"{}", 42)
+            .build()
+            .unwrap();
+        assert_eq!(request.headers()[NOTIFICATION_TOKEN_HEADER], "secret");
+        assert!(!request.headers().contains_key(SIGNATURE_HEADER));
+    }
+}
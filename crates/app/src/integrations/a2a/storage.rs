@@ -17,6 +17,8 @@
 //! - **Factory Pattern**: Use `new_for_agent()` to create agent-scoped instances.
 //! - **Transactional Updates**: Status updates, message appending, and artifact
 //!   additions are done transactionally to ensure consistency.
+//! - **Push Notifications**: With a [`PushNotifier`] attached, every persisted
+//!   status update is pushed to the task's registered webhooks.
 //! - **Efficient Queries**: Task queries load only metadata by default. Messages,
 //!   artifacts, and status history are loaded via separate efficient queries.
 //!
@@ -49,6 +51,8 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
+use super::push_notifications::PushNotifier;
+
 /// Oxy implementation of A2A task storage using SeaORM.
 ///
 /// This storage implementation is scoped to a single agent. All operations
@@ -68,6 +72,8 @@ pub struct OxyTaskStorage {
     agent_name: String,
     /// Database connection for querying
     db: Arc<DatabaseConnection>,
+    /// Notifies the task's webhooks after status updates
+    push_notifier: Option<Arc<PushNotifier>>,
 }
 
 impl OxyTaskStorage {
@@ -82,7 +88,17 @@ impl OxyTaskStorage {
     ///
     /// A new storage instance that will automatically filter all queries by the agent name.
     pub fn new_for_agent(agent_name: String, db: Arc<DatabaseConnection>) -> Self {
-        Self { agent_name, db }
+        Self {
+            agent_name,
+            db,
+            push_notifier: None,
+        }
+    }
+
+    /// Push every persisted status update to the task's registered webhooks.
+    pub fn with_push_notifier(mut self, push_notifier: Arc<PushNotifier>) -> Self {
+        self.push_notifier = Some(push_notifier);
+        self
     }
 
     /// Get all messages associated with a context ID.
@@ -641,6 +657,11 @@ impl TaskStorage for OxyTaskStorage {
             .await
             .map_err(|e| A2aError::StorageError(format!("Failed to commit transaction: {}", e)))?;
 
+        // Notify webhooks of the new status
+        if let Some(push_notifier) = &self.push_notifier {
+            push_notifier.task_updated(self, &task.id).await;
+        }
+
         Ok(task)
     }
 
@@ -0,0 +1,467 @@
+//! Deterministic assertions of `.test.yml` cases.
+//!
+//! They run against each output before the LLM judge: a run failing any of
+//! them fails without being judged.
+
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+
+use sqlparser::{
+    ast::visit_relations,
+    dialect::GenericDialect,
+    parser::Parser,
+    tokenizer::{Token, Tokenizer},
+};
+
+use oxy::{
+    config::test_config::{ResultAssertion, SqlAssertion, TestCase},
+    connector::{Connector, name_parts, names_match},
+    execute::{
+        ExecutionContext,
+        types::{AssertionResult, ReferenceKind, TargetOutput, utils::record_batches_to_2d_array},
+    },
+    utils::asyncify,
+};
+use oxy_shared::errors::OxyError;
+
+/// Check every assertion declared on `case` against `output`.
+pub(super) async fn check_assertions(
+    execution_context: &ExecutionContext,
+    case: &TestCase,
+    output: &TargetOutput,
+) -> Vec<AssertionResult> {
+    let mut results = vec![];
+    if let Some(tool) = &case.tool {
+        results.push(check_tool(tool, &output.tool_calls));
+    }
+    if let Some(sql) = &case.sql {
+        results.push(check_sql(sql, &run_queries(output)));
+    }
+    if let Some(result) = &case.result {
+        results.push(check_result(execution_context, result, output).await);
+    }
+    results
+}
+
+fn check_tool(expected: &str, tool_calls: &[String]) -> AssertionResult {
+    if tool_calls.iter().any(|tool| tool == expected) {
+        return AssertionResult::new("tool", true, format!("'{expected}' was called"));
+    }
+    let called = if tool_calls.is_empty() {
+        "no tools were called".to_string()
+    } else {
+        format!("called: {}", tool_calls.join(", "))
+    };
+    AssertionResult::new(
+        "tool",
+        false,
+        format!("expected '{expected}' to be called, {called}"),
+    )
+}
+
+/// SQL generated during the run, including queries only known from the
+/// references of the output.
+fn run_queries(output: &TargetOutput) -> Vec<String> {
+    let mut queries = output.sql_queries.clone();
+    for reference in &output.references {
+        let sql = match reference {
+            ReferenceKind::SqlQuery(query) => Some(&query.sql_query),
+            ReferenceKind::SemanticQuery(query) => query.sql_query.as_ref(),
+            _ => None,
+        };
+        if let Some(sql) = sql
+            && !queries.contains(sql)
+        {
+            queries.push(sql.clone());
+        }
+    }
+    queries
+}
+
+fn check_sql(assertion: &SqlAssertion, queries: &[String]) -> AssertionResult {
+    if queries.is_empty() {
+        return AssertionResult::new("sql", false, "no SQL was generated");
+    }
+    let (tables, identifiers): (Vec<_>, Vec<_>) = queries.iter().map(|sql| sql_names(sql)).unzip();
+    let tables = tables.concat();
+    let identifiers = identifiers.concat();
+    let missing = |expected: &[String], found: &[Vec<String>]| -> Vec<String> {
+        expected
+            .iter()
+            .filter(|name| {
+                let parts = name_parts(name);
+                !found.iter().any(|f| names_match(&parts, f))
+            })
+            .cloned()
+            .collect()
+    };
+    let missing_tables = missing(&assertion.tables, &tables);
+    let missing_columns = missing(&assertion.columns, &identifiers);
+
+    let mut problems = vec![];
+    if !missing_tables.is_empty() {
+        problems.push(format!(
+            "tables not referenced: {}",
+            missing_tables.join(", ")
+        ));
+    }
+    if !missing_columns.is_empty() {
+        problems.push(format!(
+            "columns not referenced: {}",
+            missing_columns.join(", ")
+        ));
+    }
+    if problems.is_empty() {
+        AssertionResult::new("sql", true, "all tables and columns are referenced")
+    } else {
+        AssertionResult::new("sql", false, problems.join("; "))
+    }
+}
+
+/// Tables read by `sql`, and every dotted identifier in it, as lowercase
+/// name parts. Tables fall back to the identifiers when the SQL cannot be
+/// parsed.
+fn sql_names(sql: &str) -> (Vec<Vec<String>>, Vec<Vec<String>>) {
+    let dialect = GenericDialect {};
+    let mut identifiers = vec![];
+    let mut current: Vec<String> = vec![];
+    let mut after_period = false;
+    for token in Tokenizer::new(&dialect, sql).tokenize().unwrap_or_default() {
+        match token {
+            Token::Word(word) if current.is_empty() || after_period => {
+                current.push(word.value.to_lowercase());
+                after_period = false;
+            }
+            Token::Word(word) => {
+                identifiers.push(std::mem::take(&mut current));
+                current.push(word.value.to_lowercase());
+            }
+            Token::Period if !current.is_empty() => after_period = true,
+            Token::Whitespace(_) => {}
+            _ => {
+                if !current.is_empty() {
+                    identifiers.push(std::mem::take(&mut current));
+                }
+                after_period = false;
+            }
+        }
+    }
+    if !current.is_empty() {
+        identifiers.push(current);
+    }
+
+    let tables = match Parser::parse_sql(&dialect, sql) {
+        Ok(statements) => {
+            let mut tables = vec![];
+            for statement in &statements {
+                let _ = visit_relations(statement, |relation| {
+                    tables.push(name_parts(&relation.to_string()));
+                    ControlFlow::<()>::Continue(())
+                });
+            }
+            tables
+        }
+        Err(_) => identifiers.clone(),
+    };
+    (tables, identifiers)
+}
+
+async fn check_result(
+    execution_context: &ExecutionContext,
+    assertion: &ResultAssertion,
+    output: &TargetOutput,
+) -> AssertionResult {
+    // The last query that returned rows is the run's answer
+    let actual = output
+        .references
+        .iter()
+        .rev()
+        .find_map(|reference| match reference {
+            ReferenceKind::SqlQuery(query) if !query.result.is_empty() => {
+                Some((&query.database, &query.result, query.is_result_truncated))
+            }
+            ReferenceKind::SemanticQuery(query) if !query.result.is_empty() => {
+                Some((&query.database, &query.result, query.is_result_truncated))
+            }
+            _ => None,
+        });
+    let Some((database, actual, truncated)) = actual else {
+        return AssertionResult::new("result", false, "no query returned a result");
+    };
+    if truncated {
+        return AssertionResult::new(
+            "result",
+            false,
+            "the result was truncated and cannot be compared",
+        );
+    }
+    let expected = match expected_rows(execution_context, assertion, database).await {
+        Ok(expected) => expected,
+        Err(err) => {
+            return AssertionResult::new(
+                "result",
+                false,
+                format!("could not load the expected result: {err}"),
+            );
+        }
+    };
+    // Both tables start with their header row
+    match compare_rows(
+        expected.get(1..).unwrap_or_default(),
+        actual.get(1..).unwrap_or_default(),
+        assertion.tolerance,
+        assertion.ordered,
+    ) {
+        Ok(()) => AssertionResult::new("result", true, "the result matches"),
+        Err(message) => AssertionResult::new("result", false, message),
+    }
+}
+
+async fn expected_rows(
+    execution_context: &ExecutionContext,
+    assertion: &ResultAssertion,
+    run_database: &str,
+) -> Result<Vec<Vec<String>>, OxyError> {
+    let config_manager = &execution_context.workspace.config_manager;
+    if let Some(csv) = &assertion.csv {
+        let path = config_manager.resolve_file(csv).await?;
+        return asyncify(move || {
+            let read_error =
+                |err: csv::Error| OxyError::RuntimeError(format!("Failed to read {path}: {err}"));
+            let mut reader = csv::Reader::from_path(&path).map_err(read_error)?;
+            let mut rows = vec![
+                reader
+                    .headers()
+                    .map_err(read_error)?
+                    .iter()
+                    .map(str::to_string)
+                    .collect(),
+            ];
+            for record in reader.records() {
+                rows.push(
+                    record
+                        .map_err(read_error)?
+                        .iter()
+                        .map(str::to_string)
+                        .collect(),
+                );
+            }
+            Ok(rows)
+        })
+        .await;
+    }
+    let sql = assertion
+        .sql
+        .as_ref()
+        .ok_or_else(|| OxyError::ConfigurationError("Set 'csv' or 'sql'".to_string()))?;
+    let database = assertion.database.as_deref().unwrap_or(run_database);
+    let connector = Connector::from_database(
+        database,
+        config_manager,
+        &execution_context.workspace.secrets_manager,
+        None,
+        execution_context.filters.clone(),
+        execution_context.connections.clone(),
+    )
+    .await?
+    .with_user_id(execution_context.user_id);
+    let (batches, schema) = connector.run_query_and_load(sql).await?;
+    record_batches_to_2d_array(&batches, &schema)
+        .map_err(|err| OxyError::RuntimeError(format!("Failed to format the result: {err}")))
+}
+
+/// Compare result rows, column by column, allowing `tolerance` between
+/// numbers. Unordered comparisons compare the rows as multisets: they pass
+/// when every expected row can be paired with a distinct matching row.
+fn compare_rows(
+    expected: &[Vec<String>],
+    actual: &[Vec<String>],
+    tolerance: f64,
+    ordered: bool,
+) -> Result<(), String> {
+    let expected_columns = expected.first().map_or(0, Vec::len);
+    let actual_columns = actual.first().map_or(0, Vec::len);
+    if !expected.is_empty() && !actual.is_empty() && expected_columns != actual_columns {
+        return Err(format!(
+            "expected {expected_columns} columns, got {actual_columns}"
+        ));
+    }
+    if expected.len() != actual.len() {
+        return Err(format!(
+            "expected {} rows, got {}",
+            expected.len(),
+            actual.len()
+        ));
+    }
+    let rows_match = |e: &[String], a: &[String]| {
+        e.len() == a.len() && e.iter().zip(a).all(|(e, a)| cells_match(e, a, tolerance))
+    };
+    if ordered {
+        for (index, (e, a)) in expected.iter().zip(actual).enumerate() {
+            if !rows_match(e, a) {
+                return Err(format!(
+                    "row {}: expected [{}], got [{}]",
+                    index + 1,
+                    e.join(", "),
+                    a.join(", ")
+                ));
+            }
+        }
+        return Ok(());
+    }
+
+    // Rows that are equal once numbers and nulls are normalized always
+    // match, so equal row counts per key settle most comparisons
+    let mut counts: HashMap<Vec<String>, i64> = HashMap::new();
+    for row in expected {
+        *counts.entry(row_key(row)).or_default() += 1;
+    }
+    for row in actual {
+        *counts.entry(row_key(row)).or_default() -= 1;
+    }
+    if counts.values().all(|count| *count == 0) {
+        return Ok(());
+    }
+
+    // Otherwise look for a pairing, which a greedy pass can miss when one
+    // row is within tolerance of several
+    let candidates: Vec<Vec<usize>> = expected
+        .iter()
+        .map(|e| {
+            (0..actual.len())
+                .filter(|&i| rows_match(e, &actual[i]))
+                .collect()
+        })
+        .collect();
+    let mut paired: Vec<Option<usize>> = vec![None; actual.len()];
+    for (index, e) in expected.iter().enumerate() {
+        let mut visited = vec![false; actual.len()];
+        if !pair_row(index, &candidates, &mut paired, &mut visited) {
+            return Err(format!("expected row [{}] not found", e.join(", ")));
+        }
+    }
+    Ok(())
+}
+
+/// Pair expected row `index` with one of its `candidates`, moving rows
+/// paired earlier to other candidates when needed.
+fn pair_row(
+    index: usize,
+    candidates: &[Vec<usize>],
+    paired: &mut [Option<usize>],
+    visited: &mut [bool],
+) -> bool {
+    for &candidate in &candidates[index] {
+        if visited[candidate] {
+            continue;
+        }
+        visited[candidate] = true;
+        let current = paired[candidate];
+        if current.is_none_or(|other| pair_row(other, candidates, paired, visited)) {
+            paired[candidate] = Some(index);
+            return true;
+        }
+    }
+    false
+}
+
+fn row_key(row: &[String]) -> Vec<String> {
+    row.iter()
+        .map(|cell| {
+            let cell = cell.trim();
+            if is_null(cell) {
+                String::new()
+            } else if let Ok(number) = cell.parse::<f64>() {
+                number.to_string()
+            } else {
+                cell.to_string()
+            }
+        })
+        .collect()
+}
+
+fn is_null(value: &str) -> bool {
+    value.is_empty() || value.eq_ignore_ascii_case("null")
+}
+
+fn cells_match(expected: &str, actual: &str, tolerance: f64) -> bool {
+    let (expected, actual) = (expected.trim(), actual.trim());
+    if is_null(expected) || is_null(actual) {
+        return is_null(expected) && is_null(actual);
+    }
+    match (expected.parse::<f64>(), actual.parse::<f64>()) {
+        (Ok(e), Ok(a)) => (e - a).abs() <= tolerance,
+        _ => expected == actual,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_check_tool() {
+        let calls = vec!["execute_sql".to_string()];
+        assert!(check_tool("execute_sql", &calls).passed);
+        let failed = check_tool("semantic_query", &calls);
+        assert!(!failed.passed);
+        assert!(failed.message.contains("called: execute_sql"));
+    }
+
+    #[test]
+    fn test_check_sql() {
+        let queries =
+            vec!["SELECT o.region, SUM(o.amount) FROM analytics.orders o GROUP BY 1".to_string()];
+        let assertion = SqlAssertion {
+            tables: vec!["orders".to_string()],
+            columns: vec!["amount".to_string(), "region".to_string()],
+        };
+        assert!(check_sql(&assertion, &queries).passed);
+
+        let assertion = SqlAssertion {
+            tables: vec!["customers".to_string()],
+            columns: vec!["discount".to_string()],
+        };
+        let failed = check_sql(&assertion, &queries);
+        assert_eq!(
+            failed.message,
+            "tables not referenced: customers; columns not referenced: discount"
+        );
+    }
+
+    #[test]
+    fn test_compare_rows() {
+        let expected = rows(&[&["emea", "10.0"], &["apac", "5"]]);
+        let actual = rows(&[&["apac", "5.004"], &["emea", "10"]]);
+        assert!(compare_rows(&expected, &actual, 0.01, false).is_ok());
+        assert_eq!(
+            compare_rows(&expected, &actual, 0.01, true).unwrap_err(),
+            "row 1: expected [emea, 10.0], got [apac, 5.004]"
+        );
+        assert_eq!(
+            compare_rows(&expected, &actual, 0.0, false).unwrap_err(),
+            "expected row [apac, 5] not found"
+        );
+        assert_eq!(
+            compare_rows(&expected, &actual[..1], 0.01, false).unwrap_err(),
+            "expected 2 rows, got 1"
+        );
+
+        // 1.0 is within tolerance of both actual rows, but only 0.6 leaves
+        // 1.4 for 1.6
+        let expected = rows(&[&["a", "1.0"], &["a", "1.6"]]);
+        let actual = rows(&[&["a", "1.4"], &["a", "0.6"]]);
+        assert!(compare_rows(&expected, &actual, 0.5, false).is_ok());
+        let duplicated = rows(&[&["a", "1.0"], &["a", "1.0"]]);
+        assert_eq!(
+            compare_rows(&duplicated, &rows(&[&["a", "1"], &["a", "2"]]), 0.0, false).unwrap_err(),
+            "expected row [a, 1.0] not found"
+        );
+    }
+}
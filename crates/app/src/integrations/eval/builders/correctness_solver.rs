@@ -108,5 +108,6 @@ pub(super) fn parse_correctness_record(output: Output) -> Result<Record, OxyErro
         duration_ms: 0.0,
         input_tokens: 0,
         output_tokens: 0,
+        assertions: vec![],
     })
 }
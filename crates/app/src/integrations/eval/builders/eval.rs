@@ -196,6 +196,16 @@ impl ParamMapper<EvalInput, Vec<(usize, EvalConfig, EvalTarget)>> for EvalMapper
                     )));
                 };
 
+                for (idx, case) in test_config.cases.iter().enumerate() {
+                    if let Some(result) = &case.result {
+                        result.validate().map_err(|err| {
+                            OxyError::ConfigurationError(format!(
+                                "Invalid result assertion in case {idx} of {target_ref}: {err}"
+                            ))
+                        })?;
+                    }
+                }
+
                 let correctness_solver = SolverKind::Correctness(CorrectnessSolver {
                     prompt: default_correctness_prompt(),
                     model_ref: test_config.settings.judge_model.clone(),
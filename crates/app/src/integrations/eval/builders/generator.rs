@@ -14,7 +14,7 @@ use oxy_agent::types::AgentInput;
 use oxy_shared::errors::OxyError;
 use oxy_workflow::builders::WorkflowInput;
 
-use super::{target::TargetExecutable, types::EvalTarget};
+use super::{assertions::check_assertions, target::TargetExecutable, types::EvalTarget};
 
 #[derive(Clone, Debug)]
 pub(super) struct GeneratorExecutable {
@@ -147,6 +147,9 @@ impl Executable<(EvalKind, EvalTarget, Option<String>)> for GeneratorExecutable
                         duration_ms: 0.0,
                         input_tokens: 0,
                         output_tokens: 0,
+                        tool_calls: vec![],
+                        sql_queries: vec![],
+                        assertions: vec![],
                     };
 
                     for _ in 0..runs {
@@ -177,7 +180,7 @@ impl Executable<(EvalKind, EvalTarget, Option<String>)> for GeneratorExecutable
                             }
                         };
                         all_targets.push(target);
-                        expected_outputs.push(expected.clone());
+                        expected_outputs.push((case, expected.clone()));
                     }
                 }
 
@@ -192,10 +195,12 @@ impl Executable<(EvalKind, EvalTarget, Option<String>)> for GeneratorExecutable
                 // Pair results back with their expected outputs
                 let mut all_outputs = Vec::new();
                 let mut all_errors = Vec::new();
-                for (result, expected) in results.into_iter().zip(expected_outputs) {
+                for (result, (case, expected)) in results.into_iter().zip(expected_outputs) {
                     match result {
                         Ok(actual_outputs) => {
-                            for actual in actual_outputs {
+                            for mut actual in actual_outputs {
+                                actual.assertions =
+                                    check_assertions(execution_context, case, &actual).await;
                                 all_outputs.push((actual, expected.clone()));
                             }
                         }
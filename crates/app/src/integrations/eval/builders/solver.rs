@@ -3,7 +3,7 @@ use minijinja::{Value, context};
 use rapidfuzz::distance::levenshtein::normalized_distance;
 
 use oxy::{
-    adapters::openai::{IntoOpenAIConfig, OpenAIClient},
+    adapters::model_chain::ModelChain,
     config::{
         constants::{EVAL_METRICS_POSTFIX, EVAL_SOURCE},
         model::{DistanceMethod, SolverKind},
@@ -119,16 +119,8 @@ impl
                     },
                 };
                 let model = config_manager.resolve_model(model_ref)?;
-                let client =
-                    OpenAIClient::with_config(model.into_openai_config(secret_manager).await?);
-                let agent = build_openai_executable(
-                    client,
-                    model.model_name().to_string(),
-                    vec![],
-                    None,
-                    None,
-                    false,
-                );
+                let models = ModelChain::from_model(model, config_manager, secret_manager).await?;
+                let agent = build_openai_executable(models, vec![], None, None, false);
                 let mut eval_executable = ExecutableBuilder::new()
                     .concurrency(self.concurrency)
                     .map(LLMSolverMapper {
@@ -201,16 +193,13 @@ impl
                     },
                 };
                 let model = config_manager.resolve_model(model_ref)?;
-                let client =
-                    OpenAIClient::with_config(model.into_openai_config(secret_manager).await?);
-                let agent = build_openai_executable(
-                    client,
-                    model.model_name().to_string(),
-                    vec![],
-                    None,
-                    None,
-                    false,
-                );
+                let models = ModelChain::from_model(model, config_manager, secret_manager).await?;
+                let agent = build_openai_executable(models, vec![], None, None, false);
+                // Runs failing a deterministic assertion fail without being judged
+                let (outputs, failed_assertions): (Vec<_>, Vec<_>) = outputs
+                    .into_iter()
+                    .partition(|(actual, _)| actual.assertions.iter().all(|a| a.passed));
+
                 // Capture context from both actual and expected TargetOutputs
                 let run_context: Vec<_> = outputs
                     .iter()
@@ -223,6 +212,7 @@ impl
                             actual.duration_ms,
                             actual.input_tokens,
                             actual.output_tokens,
+                            actual.assertions.clone(),
                         )
                     })
                     .collect();
@@ -249,6 +239,7 @@ impl
                                 duration_ms,
                                 input_tokens,
                                 output_tokens,
+                                assertions,
                             ),
                         )| {
                             let output = res?;
@@ -260,11 +251,34 @@ impl
                             record.duration_ms = duration_ms;
                             record.input_tokens = input_tokens;
                             record.output_tokens = output_tokens;
+                            record.assertions = assertions;
                             Ok(record)
                         },
                     )
                     .collect::<Result<Vec<Record>, OxyError>>()?;
 
+                for (actual, expected) in failed_assertions {
+                    let failures = actual
+                        .assertions
+                        .iter()
+                        .filter(|a| !a.passed)
+                        .map(|a| format!("- {}: {}", a.kind, a.message))
+                        .join("\n");
+                    records.push(Record {
+                        cot: format!("Assertions failed:\n{failures}"),
+                        choice: "FAIL".to_string(),
+                        score: 0.0,
+                        prompt: expected.task_description,
+                        expected: Some(expected.output),
+                        actual_output: Some(actual.output),
+                        references: actual.references,
+                        duration_ms: actual.duration_ms,
+                        input_tokens: actual.input_tokens,
+                        output_tokens: actual.output_tokens,
+                        assertions: actual.assertions,
+                    });
+                }
+
                 // Errored runs count as FAILs so the denominator is correct
                 for (error_msg, expected) in &errors_with_expected {
                     records.push(Record {
@@ -278,6 +292,7 @@ impl
                         duration_ms: 0.0,
                         input_tokens: 0,
                         output_tokens: 0,
+                        assertions: vec![],
                     });
                 }
 
@@ -16,29 +16,53 @@ use oxy_workflow::builders::WorkflowLauncherExecutable;
 
 use super::types::EvalTarget;
 
-// Accumulates token usage from EventKind::Usage events, forwarding all events to the inner handler.
-struct UsageAccumulatorHandler<H> {
+// Accumulates token usage from EventKind::Usage events, along with the tools invoked and the SQL
+// generated during the run, forwarding all events to the inner handler.
+struct RunAccumulatorHandler<H> {
     inner: H,
     usage_in: Arc<Mutex<i32>>,
     usage_out: Arc<Mutex<i32>>,
+    tool_calls: Arc<Mutex<Vec<String>>>,
+    sql_queries: Arc<Mutex<Vec<String>>>,
 }
 
-impl<H> UsageAccumulatorHandler<H> {
+impl<H> RunAccumulatorHandler<H> {
     fn new(inner: H) -> Self {
         Self {
             inner,
             usage_in: Arc::new(Mutex::new(0)),
             usage_out: Arc::new(Mutex::new(0)),
+            tool_calls: Arc::new(Mutex::new(vec![])),
+            sql_queries: Arc::new(Mutex::new(vec![])),
         }
     }
 }
 
 #[async_trait::async_trait]
-impl<H: EventHandler + Send + 'static> EventHandler for UsageAccumulatorHandler<H> {
+impl<H: EventHandler + Send + 'static> EventHandler for RunAccumulatorHandler<H> {
     async fn handle_event(&mut self, event: Event) -> Result<(), OxyError> {
-        if let EventKind::Usage { usage } = &event.kind {
-            *self.usage_in.lock().unwrap() += usage.input_tokens;
-            *self.usage_out.lock().unwrap() += usage.output_tokens;
+        match &event.kind {
+            EventKind::Usage { usage } => {
+                *self.usage_in.lock().unwrap() += usage.input_tokens;
+                *self.usage_out.lock().unwrap() += usage.output_tokens;
+            }
+            // Tools with an artifact announce it under their name
+            EventKind::ArtifactStarted { title, .. } => {
+                self.tool_calls.lock().unwrap().push(title.clone());
+            }
+            // Agentic workflow steps stand in for tools
+            EventKind::StepStarted { step } => {
+                self.tool_calls.lock().unwrap().push(step.kind.to_string());
+            }
+            EventKind::SQLQueryGenerated { query, .. } => {
+                self.sql_queries.lock().unwrap().push(query.clone());
+            }
+            EventKind::Started { attributes, .. } => {
+                if let Some(sql) = attributes.get("sql_query") {
+                    self.sql_queries.lock().unwrap().push(sql.clone());
+                }
+            }
+            _ => {}
         }
         self.inner.handle_event(event).await
     }
@@ -118,9 +142,11 @@ impl Executable<EvalTarget> for TargetExecutable {
     ) -> Result<Self::Response, OxyError> {
         let start = std::time::Instant::now();
 
-        let handler = UsageAccumulatorHandler::new(execution_context.writer.clone());
+        let handler = RunAccumulatorHandler::new(execution_context.writer.clone());
         let usage_in = handler.usage_in.clone();
         let usage_out = handler.usage_out.clone();
+        let tool_calls = handler.tool_calls.clone();
+        let sql_queries = handler.sql_queries.clone();
 
         let output_container =
             execute_with_handler(EvalTargetWrapper, execution_context, input, handler).await?;
@@ -128,6 +154,15 @@ impl Executable<EvalTarget> for TargetExecutable {
         let duration_ms = start.elapsed().as_secs_f64() * 1000.0;
         let input_tokens = *usage_in.lock().unwrap();
         let output_tokens = *usage_out.lock().unwrap();
+        let tool_calls = tool_calls.lock().unwrap().clone();
+        // The SQL tool reports its query both when it is generated and when it runs
+        let sql_queries: Vec<String> = sql_queries
+            .lock()
+            .unwrap()
+            .iter()
+            .unique()
+            .cloned()
+            .collect();
 
         let mut outputs: Vec<TargetOutput> = match &self.task_ref {
             Some(task_ref) => {
@@ -157,6 +192,8 @@ impl Executable<EvalTarget> for TargetExecutable {
             out.duration_ms = duration_ms;
             out.input_tokens = input_tokens;
             out.output_tokens = output_tokens;
+            out.tool_calls = tool_calls.clone();
+            out.sql_queries = sql_queries.clone();
         }
 
         Ok(outputs)
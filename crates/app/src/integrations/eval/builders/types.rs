@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 
 use oxy::{
     checkpoint::types::RetryStrategy,
-    execute::types::{Output, ReferenceKind, TargetOutput},
+    execute::types::{AssertionResult, Output, ReferenceKind, TargetOutput},
     theme::StyledText,
 };
 use oxy_agent::types::AgentInput;
@@ -58,6 +58,9 @@ impl From<EvalRecord> for TargetOutput {
             duration_ms: 0.0,
             input_tokens: 0,
             output_tokens: 0,
+            tool_calls: vec![],
+            sql_queries: vec![],
+            assertions: vec![],
         }
     }
 }
@@ -309,6 +312,8 @@ pub struct Record {
     pub duration_ms: f64,
     pub input_tokens: i32,
     pub output_tokens: i32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub assertions: Vec<AssertionResult>,
 }
 
 impl std::fmt::Display for Record {
@@ -348,6 +353,7 @@ impl TryFrom<Output> for Record {
             duration_ms: 0.0,
             input_tokens: 0,
             output_tokens: 0,
+            assertions: vec![],
         };
         let response = match value {
             Output::Text(text) => text,
@@ -382,6 +388,9 @@ pub struct EvalResult {
     pub metrics: Vec<MetricKind>,
     pub stats: RunStats,
     pub test_name: Option<String>,
+    /// Path of the test file relative to the workspace root, with `/`
+    /// separators. Stable across runs, so baselines are keyed on it.
+    pub test_file: Option<String>,
 }
 
 impl EvalResult {
@@ -391,6 +400,7 @@ impl EvalResult {
             metrics,
             stats,
             test_name: None,
+            test_file: None,
         }
     }
 }
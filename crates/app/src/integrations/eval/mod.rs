@@ -3,4 +3,6 @@ mod reporters;
 
 pub use builders::EvalLauncher;
 pub use builders::types::{EvalInput, EvalResult, MetricKind};
-pub use reporters::{JsonReporter, PrettyReporter, Reporter};
+pub use reporters::{
+    Baseline, BaselineReporter, JUnitReporter, JsonReporter, PrettyReporter, Reporter,
+};
@@ -0,0 +1,286 @@
+use std::{collections::BTreeMap, io::Write, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::integrations::eval::builders::types::{EvalResult, MetricKind};
+use oxy::theme::StyledText;
+use oxy_shared::errors::OxyError;
+
+use super::{Reporter, correctness_cases, suite_name};
+
+/// Per-case scores of a test run, stored so later runs can be compared
+/// against it.
+///
+/// Cases are keyed by `<test file path>::<prompt>`; evals without cases
+/// (consistency and custom datasets) contribute one score per metric.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Baseline {
+    pub cases: BTreeMap<String, f32>,
+}
+
+/// A case whose score dropped further than allowed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub case: String,
+    pub baseline: f32,
+    pub current: f32,
+}
+
+impl Baseline {
+    pub fn from_results(results: &[EvalResult]) -> Self {
+        let mut cases = BTreeMap::new();
+        // Position of each metric within its test file, so keys do not
+        // depend on which other files ran
+        let mut positions: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+        for result in results {
+            let suite = suite_name(result);
+            for case in correctness_cases(result) {
+                cases.insert(format!("{suite}::{}", case.name), case.score());
+            }
+            for metric in &result.metrics {
+                let (label, score) = match metric {
+                    MetricKind::Correctness(_) => continue,
+                    MetricKind::Similarity(similarity) => ("accuracy", similarity.score),
+                    MetricKind::Recall(recall) => ("recall", recall.score),
+                };
+                let position = positions.entry((suite, label)).or_default();
+                *position += 1;
+                cases.insert(format!("{suite}::{label} #{position}"), score);
+            }
+        }
+        Self { cases }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, OxyError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            OxyError::RuntimeError(format!("Failed to read baseline '{}': {e}", path.display()))
+        })?;
+        serde_json::from_str(&content).map_err(|e| {
+            OxyError::SerializerError(format!(
+                "Failed to parse baseline '{}': {e}",
+                path.display()
+            ))
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), OxyError> {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| OxyError::SerializerError(format!("Failed to serialize baseline: {e}")))?;
+        std::fs::write(path, json).map_err(|e| {
+            OxyError::RuntimeError(format!(
+                "Failed to write baseline '{}': {e}",
+                path.display()
+            ))
+        })
+    }
+
+    /// Cases of `current` scoring more than `max_drop` below this baseline.
+    /// Cases missing from either run are not compared.
+    pub fn regressions(&self, current: &Baseline, max_drop: f32) -> Vec<Regression> {
+        current
+            .cases
+            .iter()
+            .filter_map(|(case, &score)| {
+                let &baseline = self.cases.get(case)?;
+                (baseline - score > max_drop + f32::EPSILON).then(|| Regression {
+                    case: case.clone(),
+                    baseline,
+                    current: score,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Reports how each case moved against a stored baseline.
+pub struct BaselineReporter {
+    pub baseline: Baseline,
+    pub max_regression: f32,
+}
+
+impl Reporter for BaselineReporter {
+    fn report(&self, results: &[EvalResult], writer: &mut dyn Write) -> Result<(), OxyError> {
+        let current = Baseline::from_results(results);
+        let regressions = self.baseline.regressions(&current, self.max_regression);
+        let (mut dropped, mut improved, mut unchanged, mut new_cases) = (0usize, 0, 0, 0);
+        for (case, score) in &current.cases {
+            match self.baseline.cases.get(case) {
+                None => new_cases += 1,
+                Some(baseline) if score > baseline => improved += 1,
+                Some(baseline) if score < baseline => dropped += 1,
+                Some(_) => unchanged += 1,
+            }
+        }
+
+        writeln!(writer)?;
+        writeln!(writer, " {}", "Baseline comparison".text())?;
+        writeln!(
+            writer,
+            "   {} regressed · {} within threshold · {} improved · {} unchanged · {} new",
+            regressions.len(),
+            dropped.saturating_sub(regressions.len()),
+            improved,
+            unchanged,
+            new_cases
+        )?;
+        for regression in &regressions {
+            writeln!(
+                writer,
+                "   {} {}  {:.1}% → {:.1}%",
+                "×".error(),
+                regression.case,
+                regression.baseline * 100.0,
+                regression.current * 100.0
+            )?;
+        }
+        writeln!(writer)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::eval::builders::types::{Correctness, Record, RunStats};
+
+    fn baseline(cases: &[(&str, f32)]) -> Baseline {
+        Baseline {
+            cases: cases
+                .iter()
+                .map(|(case, score)| (case.to_string(), *score))
+                .collect(),
+        }
+    }
+
+    fn record(prompt: &str, score: f32) -> Record {
+        Record {
+            cot: String::new(),
+            choice: if score >= 1.0 { "PASS" } else { "FAIL" }.to_string(),
+            score,
+            prompt: Some(prompt.to_string()),
+            expected: None,
+            actual_output: None,
+            references: vec![],
+            duration_ms: 0.0,
+            input_tokens: 0,
+            output_tokens: 0,
+            assertions: vec![],
+        }
+    }
+
+    fn result(test_file: &str, records: Vec<Record>) -> EvalResult {
+        let mut result = EvalResult::new(
+            vec![],
+            vec![MetricKind::Correctness(Correctness::from_records(records))],
+            RunStats::default(),
+        );
+        result.test_name = test_file.rsplit('/').next().map(str::to_string);
+        result.test_file = Some(test_file.to_string());
+        result
+    }
+
+    #[test]
+    fn test_regressions() {
+        let previous = baseline(&[("a::x", 1.0), ("a::y", 1.0), ("a::z", 0.5)]);
+        let current = baseline(&[
+            ("a::x", 2.0 / 3.0),
+            ("a::y", 1.0),
+            ("a::z", 0.0),
+            ("a::new", 0.0),
+        ]);
+
+        assert_eq!(
+            previous.regressions(&current, 0.0),
+            vec![
+                Regression {
+                    case: "a::x".to_string(),
+                    baseline: 1.0,
+                    current: 2.0 / 3.0,
+                },
+                Regression {
+                    case: "a::z".to_string(),
+                    baseline: 0.5,
+                    current: 0.0,
+                },
+            ]
+        );
+        let regressions = previous.regressions(&current, 0.4);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].case, "a::z");
+
+        // Cases missing from the current run are not compared
+        assert!(previous.regressions(&baseline(&[]), 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_cases_are_keyed_by_test_file_path() {
+        let sales = || result("tests/sales.test.yml", vec![record("Revenue?", 1.0)]);
+        let other_sales = || result("legacy/sales.test.yml", vec![record("Revenue?", 0.0)]);
+
+        let run = Baseline::from_results(&[sales(), other_sales()]);
+        assert_eq!(
+            run.cases,
+            BTreeMap::from([
+                ("legacy/sales.test.yml::Revenue?".to_string(), 0.0),
+                ("tests/sales.test.yml::Revenue?".to_string(), 1.0),
+            ])
+        );
+        // Running a subset of the files, in another order, gives the same keys
+        let subset = Baseline::from_results(&[other_sales()]);
+        assert!(run.regressions(&subset, 0.0).is_empty());
+        assert_eq!(
+            Baseline::from_results(&[other_sales(), sales()]).cases,
+            run.cases
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("baselines").join("main.json");
+        let saved = baseline(&[("tests/sales.test.yml::Revenue?", 0.5)]);
+        saved.save(&path).unwrap();
+        assert_eq!(Baseline::load(&path).unwrap().cases, saved.cases);
+        assert!(Baseline::load(&dir.path().join("missing.json")).is_err());
+    }
+
+    #[test]
+    fn test_report_summary() {
+        let reporter = BaselineReporter {
+            baseline: baseline(&[
+                ("tests/sales.test.yml::dropped", 1.0),
+                ("tests/sales.test.yml::dipped", 1.0),
+                ("tests/sales.test.yml::improved", 0.0),
+                ("tests/sales.test.yml::same", 1.0),
+            ]),
+            max_regression: 0.5,
+        };
+        let results = [result(
+            "tests/sales.test.yml",
+            vec![
+                record("dropped", 0.0),
+                record("dipped", 1.0),
+                record("dipped", 0.0),
+                record("dipped", 1.0),
+                record("improved", 1.0),
+                record("same", 1.0),
+                record("new", 1.0),
+            ],
+        )];
+
+        let mut out = Vec::new();
+        reporter.report(&results, &mut out).unwrap();
+        let report = String::from_utf8(out).unwrap();
+        assert!(
+            report.contains("1 regressed · 1 within threshold · 1 improved · 1 unchanged · 1 new"),
+            "{report}"
+        );
+        assert!(report.contains("tests/sales.test.yml::dropped"));
+        assert!(!report.contains("::dipped"));
+    }
+}
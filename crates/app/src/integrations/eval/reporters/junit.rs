@@ -0,0 +1,238 @@
+use std::io::Write;
+
+use crate::integrations::eval::builders::types::{EvalResult, MetricKind};
+use oxy_shared::errors::OxyError;
+
+use super::{Reporter, correctness_cases, suite_name};
+
+/// JUnit XML for CI systems: one test suite per eval, one test case per
+/// prompt. A case fails unless every run passes; generation errors are
+/// reported as errored cases.
+pub struct JUnitReporter;
+
+struct TestCase {
+    name: String,
+    time_secs: f64,
+    failure: Option<(String, String)>,
+    error: Option<(String, String)>,
+}
+
+impl Reporter for JUnitReporter {
+    fn report(&self, results: &[EvalResult], writer: &mut dyn Write) -> Result<(), OxyError> {
+        let suites: Vec<(&str, Vec<TestCase>)> = results
+            .iter()
+            .map(|result| (suite_name(result), test_cases(result)))
+            .collect();
+        let count = |pick: fn(&TestCase) -> bool, cases: &[TestCase]| {
+            cases.iter().filter(|c| pick(c)).count()
+        };
+        let all_cases: Vec<&TestCase> = suites.iter().flat_map(|(_, cases)| cases).collect();
+        let total_failures = all_cases.iter().filter(|c| c.failure.is_some()).count();
+        let total_errors = all_cases.iter().filter(|c| c.error.is_some()).count();
+        let total_time: f64 = all_cases.iter().map(|c| c.time_secs).sum();
+
+        writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            writer,
+            r#"<testsuites name="oxy test" tests="{}" failures="{total_failures}" errors="{total_errors}" time="{total_time:.3}">"#,
+            all_cases.len()
+        )?;
+        for (name, cases) in &suites {
+            let time: f64 = cases.iter().map(|c| c.time_secs).sum();
+            writeln!(
+                writer,
+                r#"  <testsuite name="{}" tests="{}" failures="{}" errors="{}" time="{time:.3}">"#,
+                escape(name),
+                cases.len(),
+                count(|c| c.failure.is_some(), cases),
+                count(|c| c.error.is_some(), cases),
+            )?;
+            for case in cases {
+                write!(
+                    writer,
+                    r#"    <testcase name="{}" classname="{}" time="{:.3}""#,
+                    escape(&case.name),
+                    escape(name),
+                    case.time_secs
+                )?;
+                match (&case.failure, &case.error) {
+                    (None, None) => writeln!(writer, "/>")?,
+                    (failure, error) => {
+                        writeln!(writer, ">")?;
+                        for (tag, (message, body)) in [("failure", failure), ("error", error)]
+                            .into_iter()
+                            .filter_map(|(tag, detail)| Some((tag, detail.as_ref()?)))
+                        {
+                            writeln!(
+                                writer,
+                                r#"      <{tag} message="{}">{}</{tag}>"#,
+                                escape(message),
+                                escape(body)
+                            )?;
+                        }
+                        writeln!(writer, "    </testcase>")?;
+                    }
+                }
+            }
+            writeln!(writer, "  </testsuite>")?;
+        }
+        writeln!(writer, "</testsuites>")?;
+        Ok(())
+    }
+}
+
+fn test_cases(result: &EvalResult) -> Vec<TestCase> {
+    let mut cases: Vec<TestCase> = correctness_cases(result)
+        .into_iter()
+        .map(|case| {
+            let time_secs = case.records.iter().map(|r| r.duration_ms).sum::<f64>() / 1000.0;
+            let passed = case.passed();
+            let failure = (passed < case.records.len()).then(|| {
+                let details = case
+                    .records
+                    .iter()
+                    .filter(|r| r.score < 1.0)
+                    .enumerate()
+                    .map(|(i, r)| format!("--- Failing run {} ---\n{}", i + 1, r.cot.trim()))
+                    .collect::<Vec<_>>()
+                    .join("\n\n");
+                (
+                    format!("{passed}/{} runs passed", case.records.len()),
+                    details,
+                )
+            });
+            TestCase {
+                name: case.name,
+                time_secs,
+                failure,
+                error: None,
+            }
+        })
+        .collect();
+
+    for metric in &result.metrics {
+        let (label, score, failing) = match metric {
+            MetricKind::Correctness(_) => continue,
+            MetricKind::Similarity(similarity) => (
+                "accuracy",
+                similarity.score,
+                similarity.records.iter().filter(|r| r.score < 1.0).count(),
+            ),
+            MetricKind::Recall(recall) => (
+                "recall",
+                recall.score,
+                recall.records.iter().filter(|r| !r.pass).count(),
+            ),
+        };
+        let failure = (failing > 0).then(|| {
+            let mut details = Vec::new();
+            metric.verbose_write(&mut details).ok();
+            (
+                format!("{label} {:.1}%", score * 100.0),
+                strip_ansi(&String::from_utf8_lossy(&details)),
+            )
+        });
+        cases.push(TestCase {
+            name: label.to_string(),
+            time_secs: 0.0,
+            failure,
+            error: None,
+        });
+    }
+
+    if !result.errors.is_empty() {
+        cases.push(TestCase {
+            name: "output generation".to_string(),
+            time_secs: 0.0,
+            failure: None,
+            error: Some((
+                format!("{} output(s) failed to generate", result.errors.len()),
+                result.errors.join("\n"),
+            )),
+        });
+    }
+    cases
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters other than whitespace are not allowed in XML 1.0
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Drop the terminal colour codes the metric writers emit.
+fn strip_ansi(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::eval::builders::types::{Correctness, Record, RunStats};
+
+    fn record(prompt: &str, score: f32, cot: &str) -> Record {
+        Record {
+            cot: cot.to_string(),
+            choice: if score >= 1.0 { "PASS" } else { "FAIL" }.to_string(),
+            score,
+            prompt: Some(prompt.to_string()),
+            expected: None,
+            actual_output: None,
+            references: vec![],
+            duration_ms: 1500.0,
+            input_tokens: 0,
+            output_tokens: 0,
+            assertions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_junit_report() {
+        let mut result = EvalResult::new(
+            vec!["timeout".to_string()],
+            vec![MetricKind::Correctness(Correctness::from_records(vec![
+                record("Revenue?", 1.0, "ok"),
+                record("Revenue?", 1.0, "ok"),
+                record("Top <5> & more", 0.0, "wrong total"),
+            ]))],
+            RunStats::default(),
+        );
+        result.test_name = Some("sales.test.yml".to_string());
+
+        let mut out = Vec::new();
+        JUnitReporter.report(&[result], &mut out).unwrap();
+        let xml = String::from_utf8(out).unwrap();
+
+        assert!(xml.contains(r#"<testsuites name="oxy test" tests="3" failures="1" errors="1""#));
+        assert!(
+            xml.contains(r#"<testcase name="Revenue?" classname="sales.test.yml" time="3.000"/>"#)
+        );
+        assert!(xml.contains(r#"<testcase name="Top &lt;5&gt; &amp; more""#));
+        assert!(xml.contains(r#"<failure message="0/1 runs passed">"#));
+        assert!(xml.contains(r#"<error message="1 output(s) failed to generate">timeout</error>"#));
+    }
+}
@@ -1,14 +1,66 @@
 use std::io::Write;
 
-use super::builders::types::EvalResult;
+use indexmap::IndexMap;
+
+use super::builders::types::{EvalResult, MetricKind, Record};
 use oxy_shared::errors::OxyError;
 
 pub trait Reporter {
     fn report(&self, results: &[EvalResult], writer: &mut dyn Write) -> Result<(), OxyError>;
 }
 
+mod baseline;
 mod json;
+mod junit;
 mod pretty;
 
+pub use baseline::{Baseline, BaselineReporter};
 pub use json::JsonReporter;
+pub use junit::JUnitReporter;
 pub use pretty::PrettyReporter;
+
+/// Runs of one test case, grouped by prompt.
+struct CaseRuns<'a> {
+    name: String,
+    records: Vec<&'a Record>,
+}
+
+impl CaseRuns<'_> {
+    fn passed(&self) -> usize {
+        self.records.iter().filter(|r| r.score >= 1.0).count()
+    }
+
+    fn score(&self) -> f32 {
+        self.passed() as f32 / self.records.len() as f32
+    }
+}
+
+/// Correctness records of `result` grouped into cases, in run order.
+fn correctness_cases(result: &EvalResult) -> Vec<CaseRuns<'_>> {
+    let mut cases: IndexMap<String, Vec<&Record>> = IndexMap::new();
+    for metric in &result.metrics {
+        if let MetricKind::Correctness(correctness) = metric {
+            for record in &correctness.records {
+                let prompt = record
+                    .prompt
+                    .clone()
+                    .unwrap_or_else(|| "(unknown prompt)".to_string());
+                cases.entry(prompt).or_default().push(record);
+            }
+        }
+    }
+    cases
+        .into_iter()
+        .map(|(name, records)| CaseRuns { name, records })
+        .collect()
+}
+
+/// Name results are reported and compared under: the test file's path, or
+/// its name when the path is unknown.
+fn suite_name(result: &EvalResult) -> &str {
+    result
+        .test_file
+        .as_deref()
+        .or(result.test_name.as_deref())
+        .unwrap_or("(unnamed test)")
+}
@@ -3,7 +3,7 @@ use std::io::Write;
 use indexmap::IndexMap;
 
 use crate::integrations::eval::builders::types::{Correctness, EvalResult, MetricKind};
-use oxy::execute::types::ReferenceKind;
+use oxy::execute::types::{AssertionResult, ReferenceKind};
 use oxy::theme::StyledText;
 use oxy_shared::errors::OxyError;
 
@@ -300,6 +300,7 @@ impl PrettyReporter {
                     reasoning: record.cot.clone(),
                     actual_output: record.actual_output.clone(),
                     references: record.references.clone(),
+                    assertions: record.assertions.clone(),
                 });
             }
         }
@@ -377,6 +378,21 @@ impl PrettyReporter {
                                 }
                             }
                         }
+                        if !run.assertions.is_empty() {
+                            writeln!(writer, "     {}", "Assertions:".secondary())?;
+                            for assertion in &run.assertions {
+                                let mark = if assertion.passed {
+                                    "✓".success()
+                                } else {
+                                    "×".error()
+                                };
+                                writeln!(
+                                    writer,
+                                    "       {mark} {}: {}",
+                                    assertion.kind, assertion.message
+                                )?;
+                            }
+                        }
                         writeln!(writer, "     {}", "Reasoning:".secondary())?;
                         for line in run.reasoning.lines() {
                             writeln!(writer, "       {}", line.secondary())?;
@@ -402,4 +418,5 @@ struct FailingRun {
     reasoning: String,
     actual_output: Option<String>,
     references: Vec<ReferenceKind>,
+    assertions: Vec<AssertionResult>,
 }
@@ -0,0 +1,150 @@
+// MCP Streamable HTTP Endpoint
+//
+// Serves `OxyMcpServer` from the HTTP server at `/{workspace_id}/mcp`, so
+// remote MCP clients can use a hosted Oxy without a local checkout. Auth and
+// workspace membership are enforced by the router middleware; this handler
+// checks that the API key was issued for the requested workspace and only
+// serves the tools the caller's permissions allow.
+//
+// The transport runs in stateless mode, so there is no session state to
+// share between server instances. Each workspace's server is built once and
+// reused until `config.yml` or a file its tools come from changes, or for at
+// most `SERVER_TTL`; requests get a copy with their own workspace manager and
+// permissions.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use axum::{
+    Extension,
+    body::Body,
+    extract::Path,
+    http::{Request, StatusCode},
+    response::{IntoResponse, Response},
+};
+use once_cell::sync::Lazy;
+use rmcp::transport::streamable_http_server::{
+    StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
+};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use oxy::adapters::workspace::manager::WorkspaceManager;
+use oxy_auth::ValidatedApiKey;
+use oxy_shared::errors::OxyError;
+
+use super::tools::get_mcp_tool_sources;
+use super::types::OxyMcpServer;
+use crate::server::api::middlewares::workspace_context::{
+    WorkspaceManagerExtractor, WorkspacePath,
+};
+use crate::server::service::permissions::WorkspacePermissions;
+
+/// Servers built for each workspace, with the tool sources they were built from
+static SERVERS: Lazy<RwLock<HashMap<Uuid, CachedServer>>> = Lazy::new(Default::default);
+
+/// Servers older than this are rebuilt on the next request, and evicted when
+/// another server is cached, so deleted or idle workspaces don't stay around.
+const SERVER_TTL: Duration = Duration::from_secs(30 * 60);
+
+struct CachedServer {
+    sources: ToolSources,
+    server: OxyMcpServer,
+    built_at: Instant,
+}
+
+/// `config.yml` and the files the workspace's tools come from, with their
+/// modification times
+type ToolSources = Vec<(PathBuf, Option<SystemTime>)>;
+
+/// Handles MCP Streamable HTTP requests for a single workspace
+pub async fn mcp_http_handler(
+    Path(WorkspacePath { workspace_id }): Path<WorkspacePath>,
+    WorkspaceManagerExtractor(workspace_manager): WorkspaceManagerExtractor,
+    api_key: Option<Extension<ValidatedApiKey>>,
+    permissions: WorkspacePermissions,
+    request: Request<Body>,
+) -> Response {
+    if let Some(Extension(api_key)) = api_key
+        && api_key.project_id != workspace_id
+    {
+        tracing::warn!(
+            "API key {} was issued for workspace {}, not {}",
+            api_key.id,
+            api_key.project_id,
+            workspace_id
+        );
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    let server = match workspace_server(workspace_id, workspace_manager).await {
+        Ok(server) => server.with_permissions(permissions),
+        Err(e) => {
+            tracing::error!(
+                "Failed to create MCP server for workspace {}: {}",
+                workspace_id,
+                e
+            );
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let service = StreamableHttpService::new(
+        move || Ok(server.clone()),
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig {
+            stateful_mode: false,
+            ..Default::default()
+        },
+    );
+    service.handle(request).await.map(Body::new)
+}
+
+/// The workspace's server with `workspace_manager` swapped in, built on first
+/// use and rebuilt when its tool sources change or it expires
+async fn workspace_server(
+    workspace_id: Uuid,
+    workspace_manager: WorkspaceManager,
+) -> Result<OxyMcpServer, OxyError> {
+    let sources = tool_sources(&workspace_manager).await?;
+    if let Some(cached) = SERVERS.read().await.get(&workspace_id)
+        && cached.sources == sources
+        && cached.built_at.elapsed() < SERVER_TTL
+    {
+        return Ok(OxyMcpServer {
+            workspace_manager,
+            ..cached.server.clone()
+        });
+    }
+
+    let server = OxyMcpServer::from_workspace_manager(workspace_manager).await?;
+    let mut servers = SERVERS.write().await;
+    servers.retain(|_, cached| cached.built_at.elapsed() < SERVER_TTL);
+    servers.insert(
+        workspace_id,
+        CachedServer {
+            sources,
+            server: server.clone(),
+            built_at: Instant::now(),
+        },
+    );
+    Ok(server)
+}
+
+async fn tool_sources(workspace_manager: &WorkspaceManager) -> Result<ToolSources, OxyError> {
+    let config_manager = &workspace_manager.config_manager;
+    let mut paths = vec![config_manager.workspace_path().join("config.yml")];
+    paths.extend(get_mcp_tool_sources(config_manager).await?);
+    paths.sort();
+    let mut sources = Vec::with_capacity(paths.len());
+    for path in paths {
+        let modified = tokio::fs::metadata(&path)
+            .await
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        sources.push((path, modified));
+    }
+    Ok(sources)
+}
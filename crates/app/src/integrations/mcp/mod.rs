@@ -3,6 +3,9 @@ mod connections;
 mod context;
 mod executor;
 mod filters;
+mod http;
+mod prompts;
+mod resources;
 mod server;
 mod tools;
 mod types;
@@ -13,5 +16,6 @@ mod variables;
 pub use connections::extract_connection_overrides;
 pub use context::ToolExecutionContext;
 pub use filters::extract_session_filters;
+pub use http::mcp_http_handler;
 pub use types::{OxyMcpServer, OxyTool, ToolType};
 pub use variables::{extract_meta_variables, merge_variables, validate_variables};
@@ -0,0 +1,264 @@
+// MCP Prompts
+//
+// Exposes each agent's instructions as an MCP prompt named after the agent.
+// Prompt arguments are taken from the agent's `variables`: their type, allowed
+// values and default are surfaced in the argument description, and variables
+// without a default are required. Getting a prompt renders the instructions
+// with the supplied arguments, the same way the agent itself sees them.
+
+use std::collections::HashMap;
+
+use rmcp::model::{JsonObject, Prompt, PromptArgument};
+use schemars::schema::{InstanceType, SchemaObject, SingleOrVec};
+use serde_json::Value;
+
+use crate::server::service::agent::{get_agent_config, list_agents};
+use oxy::config::ConfigManager;
+use oxy::config::model::{AgentConfig, AgentType, Variables};
+use oxy_shared::errors::OxyError;
+
+// =============================================================================
+// Type Definitions
+// =============================================================================
+
+/// An agent exposed as an MCP prompt
+#[derive(Debug, Clone)]
+pub struct AgentPrompt {
+    pub agent: AgentConfig,
+}
+
+impl AgentPrompt {
+    pub fn name(&self) -> &str {
+        &self.agent.name
+    }
+
+    pub fn to_prompt(&self) -> Prompt {
+        let arguments = self
+            .agent
+            .variables
+            .as_ref()
+            .map(prompt_arguments)
+            .filter(|arguments| !arguments.is_empty());
+        let description = Some(self.agent.description.clone()).filter(|d| !d.is_empty());
+        Prompt::new(self.agent.name.clone(), description, arguments)
+    }
+
+    /// Renders the agent instructions with the given prompt arguments
+    pub fn render(&self, arguments: Option<JsonObject>) -> Result<String, OxyError> {
+        let variables = match &self.agent.variables {
+            Some(variables) => variables.resolve_params(Some(convert_arguments(
+                variables,
+                arguments.unwrap_or_default(),
+            )))?,
+            None => HashMap::new(),
+        };
+        render_instructions(system_instructions(&self.agent), &variables)
+    }
+}
+
+// =============================================================================
+// Prompt Discovery
+// =============================================================================
+
+/// Loads every agent in the project as a prompt
+pub async fn get_agent_prompts(
+    config_manager: &ConfigManager,
+) -> Result<Vec<AgentPrompt>, OxyError> {
+    let mut prompts = Vec::new();
+    for agent in list_agents(config_manager.clone()).await? {
+        match get_agent_config(config_manager.clone(), agent.clone()).await {
+            Ok(agent) => prompts.push(AgentPrompt { agent }),
+            Err(e) => tracing::warn!("Skipping agent prompt {}: {}", agent, e),
+        }
+    }
+    Ok(prompts)
+}
+
+// =============================================================================
+// Helper Functions
+// =============================================================================
+
+fn system_instructions(agent: &AgentConfig) -> &str {
+    match &agent.r#type {
+        AgentType::Default(default_agent) => &default_agent.system_instructions,
+        AgentType::Routing(routing_agent) => &routing_agent.system_instructions,
+    }
+}
+
+/// Builds prompt arguments from agent variables, sorted by name
+fn prompt_arguments(variables: &Variables) -> Vec<PromptArgument> {
+    let mut arguments: Vec<PromptArgument> = variables
+        .variables
+        .iter()
+        .map(|(name, schema)| {
+            let default = schema.metadata.as_ref().and_then(|m| m.default.as_ref());
+            PromptArgument {
+                name: name.clone(),
+                title: schema.metadata.as_ref().and_then(|m| m.title.clone()),
+                description: Some(argument_description(schema)),
+                required: Some(default.is_none()),
+            }
+        })
+        .collect();
+    arguments.sort_by(|a, b| a.name.cmp(&b.name));
+    arguments
+}
+
+/// Describes a variable for MCP clients, which only see string arguments,
+/// e.g. `Minimum order amount (number, default: 100)`
+fn argument_description(schema: &SchemaObject) -> String {
+    let mut details = vec![type_name(schema)];
+    if let Some(values) = schema.enum_values.as_ref().filter(|v| !v.is_empty()) {
+        let values: Vec<String> = values.iter().map(display_value).collect();
+        details.push(format!("one of: {}", values.join(", ")));
+    }
+    if let Some(default) = schema.metadata.as_ref().and_then(|m| m.default.as_ref()) {
+        details.push(format!("default: {}", display_value(default)));
+    }
+    let details = details.join(", ");
+    match schema
+        .metadata
+        .as_ref()
+        .and_then(|m| m.description.as_deref())
+        .filter(|d| !d.is_empty())
+    {
+        Some(description) => format!("{description} ({details})"),
+        None => details,
+    }
+}
+
+fn type_name(schema: &SchemaObject) -> String {
+    let names: Vec<String> = match &schema.instance_type {
+        Some(SingleOrVec::Single(instance_type)) => vec![instance_type_name(instance_type)],
+        Some(SingleOrVec::Vec(instance_types)) => {
+            instance_types.iter().map(instance_type_name).collect()
+        }
+        None => vec!["string".to_string()],
+    };
+    names.join(" | ")
+}
+
+fn instance_type_name(instance_type: &InstanceType) -> String {
+    serde_json::to_value(instance_type)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_else(|| "string".to_string())
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// MCP prompt arguments are strings; arrays and objects are passed as JSON
+/// and decoded here. Scalars are left to the variable schema conversion.
+fn convert_arguments(variables: &Variables, arguments: JsonObject) -> HashMap<String, Value> {
+    arguments
+        .into_iter()
+        .map(|(name, value)| {
+            let structured = variables
+                .variables
+                .get(&name)
+                .is_some_and(|schema| expects_structured(schema));
+            let value = match value {
+                Value::String(s) if structured => {
+                    serde_json::from_str(&s).unwrap_or(Value::String(s))
+                }
+                other => other,
+            };
+            (name, value)
+        })
+        .collect()
+}
+
+fn expects_structured(schema: &SchemaObject) -> bool {
+    let is_structured = |t: &InstanceType| matches!(t, InstanceType::Array | InstanceType::Object);
+    match &schema.instance_type {
+        Some(SingleOrVec::Single(instance_type)) => is_structured(instance_type),
+        Some(SingleOrVec::Vec(instance_types)) => instance_types.iter().any(is_structured),
+        None => false,
+    }
+}
+
+/// Renders instructions with variables at the top level. Context references
+/// that only resolve inside an agent run (e.g. `{{ context.* }}`) render empty.
+fn render_instructions(
+    instructions: &str,
+    variables: &HashMap<String, Value>,
+) -> Result<String, OxyError> {
+    let mut env = minijinja::Environment::new();
+    env.set_undefined_behavior(minijinja::UndefinedBehavior::Chainable);
+    env.render_str(instructions, minijinja::Value::from_serialize(variables))
+        .map_err(|e| OxyError::RuntimeError(format!("Failed to render agent instructions: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn variables() -> Variables {
+        serde_json::from_value(json!({
+            "variables": {
+                "region": {
+                    "type": "string",
+                    "enum": ["EU", "US"],
+                    "description": "Sales region"
+                },
+                "min_amount": {"type": "integer", "default": 100},
+                "segments": {"type": "array", "items": {"type": "string"}, "default": []}
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_prompt_arguments_from_variables() {
+        let arguments = prompt_arguments(&variables());
+        let names: Vec<&str> = arguments.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["min_amount", "region", "segments"]);
+
+        assert_eq!(arguments[0].required, Some(false));
+        assert_eq!(
+            arguments[0].description.as_deref(),
+            Some("integer, default: 100")
+        );
+        assert_eq!(arguments[1].required, Some(true));
+        assert_eq!(
+            arguments[1].description.as_deref(),
+            Some("Sales region (string, one of: EU, US)")
+        );
+    }
+
+    #[test]
+    fn test_render_instructions_with_arguments() {
+        let variables = variables();
+        let arguments: JsonObject = serde_json::from_value(json!({
+            "region": "EU",
+            "min_amount": "250",
+            "segments": "[\"retail\", \"online\"]"
+        }))
+        .unwrap();
+        let resolved = variables
+            .resolve_params(Some(convert_arguments(&variables, arguments)))
+            .unwrap();
+        let rendered = render_instructions(
+            "Orders in {{ region }} over {{ min_amount }} for {{ segments | join(', ') }}.{{ context.docs }}",
+            &resolved,
+        )
+        .unwrap();
+        assert_eq!(rendered, "Orders in EU over 250 for retail, online.");
+    }
+
+    #[test]
+    fn test_missing_required_argument() {
+        let variables = variables();
+        assert!(
+            variables
+                .resolve_params(Some(convert_arguments(&variables, JsonObject::new())))
+                .is_err()
+        );
+    }
+}
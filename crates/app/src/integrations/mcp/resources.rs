@@ -0,0 +1,470 @@
+// MCP Resources
+//
+// Exposes project definitions (semantic views and topics, SQL files,
+// workflows and agents) as read-only MCP resources so MCP clients can browse
+// the project. Every resource has an `oxy://` URI:
+//
+//   oxy://semantic/views/{name}    .view.yml files
+//   oxy://semantic/topics/{name}   .topic.yml files
+//   oxy://sql/{+path}              .sql files, by path relative to the project
+//   oxy://workflows/{name}         .workflow.yml / .procedure.yml / .automation.yml
+//   oxy://agents/{name}            .agent.yml files
+//
+// The project is scanned on every request, but the index is only rebuilt
+// when a resource file is added, removed or modified, so edits made while a
+// client is connected show up without restarting the server.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use once_cell::sync::Lazy;
+use rmcp::model::{AnnotateAble, RawResource, RawResourceTemplate, Resource, ResourceTemplate};
+use serde::Deserialize;
+use tokio::sync::RwLock;
+use walkdir::WalkDir;
+
+use crate::server::service::agent::{get_agent_config, list_agents};
+use crate::server::service::workflow::list_workflows;
+use oxy::config::ConfigManager;
+use oxy_shared::errors::OxyError;
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+pub const RESOURCE_URI_SCHEME: &str = "oxy://";
+
+const YAML_MIME_TYPE: &str = "application/yaml";
+const SQL_MIME_TYPE: &str = "application/sql";
+
+const SEMANTIC_SUFFIXES: &[&str] = &[".view.yml", ".view.yaml", ".topic.yml", ".topic.yaml"];
+const SQL_SUFFIXES: &[&str] = &[".sql"];
+
+/// Directories never scanned for SQL and semantic files, besides hidden ones.
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target"];
+
+/// Indexes older than this are rebuilt on the next request, and evicted when
+/// another index is cached, so removed or idle projects don't stay around.
+const INDEX_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// Indexes built for each project path, with the files they were built from
+static INDEXES: Lazy<RwLock<HashMap<PathBuf, CachedIndex>>> = Lazy::new(Default::default);
+
+// =============================================================================
+// Type Definitions
+// =============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceKind {
+    SemanticView,
+    SemanticTopic,
+    SqlFile,
+    Workflow,
+    Agent,
+}
+
+impl ResourceKind {
+    const ALL: [ResourceKind; 5] = [
+        ResourceKind::SemanticView,
+        ResourceKind::SemanticTopic,
+        ResourceKind::SqlFile,
+        ResourceKind::Workflow,
+        ResourceKind::Agent,
+    ];
+
+    /// URI prefix shared by every resource of this kind
+    fn uri_prefix(&self) -> String {
+        let segment = match self {
+            ResourceKind::SemanticView => "semantic/views",
+            ResourceKind::SemanticTopic => "semantic/topics",
+            ResourceKind::SqlFile => "sql",
+            ResourceKind::Workflow => "workflows",
+            ResourceKind::Agent => "agents",
+        };
+        format!("{RESOURCE_URI_SCHEME}{segment}/")
+    }
+
+    fn uri(&self, key: &str) -> String {
+        format!("{}{key}", self.uri_prefix())
+    }
+
+    fn mime_type(&self) -> &'static str {
+        match self {
+            ResourceKind::SqlFile => SQL_MIME_TYPE,
+            _ => YAML_MIME_TYPE,
+        }
+    }
+
+    fn template(&self) -> ResourceTemplate {
+        let (variable, name, description) = match self {
+            ResourceKind::SemanticView => (
+                "{name}",
+                "semantic-view",
+                "Semantic view definition: entities, dimensions and measures over a table",
+            ),
+            ResourceKind::SemanticTopic => (
+                "{name}",
+                "semantic-topic",
+                "Semantic topic definition: a group of related views",
+            ),
+            ResourceKind::SqlFile => (
+                "{+path}",
+                "sql-file",
+                "SQL file, addressed by its path relative to the project root",
+            ),
+            ResourceKind::Workflow => ("{name}", "workflow", "Workflow definition"),
+            ResourceKind::Agent => ("{name}", "agent", "Agent definition"),
+        };
+        RawResourceTemplate {
+            uri_template: format!("{}{variable}", self.uri_prefix()),
+            name: name.to_string(),
+            title: None,
+            description: Some(description.to_string()),
+            mime_type: Some(self.mime_type().to_string()),
+        }
+        .no_annotation()
+    }
+}
+
+/// A project file exposed as an MCP resource
+#[derive(Debug, Clone)]
+pub struct McpResource {
+    pub kind: ResourceKind,
+    pub uri: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub path: PathBuf,
+}
+
+impl McpResource {
+    pub fn to_resource(&self) -> Resource {
+        let mut raw = RawResource::new(self.uri.clone(), self.name.clone());
+        raw.description = self.description.clone();
+        raw.mime_type = Some(self.kind.mime_type().to_string());
+        raw.no_annotation()
+    }
+
+    pub async fn read(&self) -> Result<String, OxyError> {
+        tokio::fs::read_to_string(&self.path).await.map_err(|e| {
+            OxyError::IOError(format!(
+                "Failed to read resource {}: {}",
+                self.path.display(),
+                e
+            ))
+        })
+    }
+}
+
+/// Name and description shared by every YAML definition file
+#[derive(Debug, Default, Deserialize)]
+struct DefinitionHeader {
+    name: Option<String>,
+    description: Option<String>,
+}
+
+/// All resources in a project, keyed by URI
+#[derive(Debug, Default)]
+pub struct McpResourceIndex {
+    resources: BTreeMap<String, McpResource>,
+}
+
+struct CachedIndex {
+    sources: ScannedFiles,
+    index: Arc<McpResourceIndex>,
+    built_at: Instant,
+}
+
+/// Files found by a scan, with their modification times
+type ScannedFiles = Vec<(PathBuf, Option<SystemTime>)>;
+
+// =============================================================================
+// Index
+// =============================================================================
+
+impl McpResourceIndex {
+    /// Scans the project for every resource kind, reusing the last index
+    /// while none of the files it was built from changed
+    pub async fn load(config_manager: &ConfigManager) -> Result<Arc<Self>, OxyError> {
+        let workspace_path = config_manager.workspace_path().to_path_buf();
+        let semantic_files =
+            scan_files(config_manager.semantics_scan_path(), SEMANTIC_SUFFIXES).await?;
+        let sql_files = scan_files(workspace_path.clone(), SQL_SUFFIXES).await?;
+        let workflows = list_workflows(config_manager.clone()).await?;
+        let agents = list_agents(config_manager.clone()).await?;
+
+        let mut sources: ScannedFiles = semantic_files.iter().chain(&sql_files).cloned().collect();
+        let definitions = workflows
+            .iter()
+            .map(|workflow| workspace_path.join(&workflow.path))
+            .chain(agents.iter().map(|agent| workspace_path.join(agent)));
+        for path in definitions {
+            let modified = tokio::fs::metadata(&path)
+                .await
+                .and_then(|metadata| metadata.modified())
+                .ok();
+            sources.push((path, modified));
+        }
+        if let Some(cached) = INDEXES.read().await.get(&workspace_path)
+            && cached.sources == sources
+            && cached.built_at.elapsed() < INDEX_TTL
+        {
+            return Ok(cached.index.clone());
+        }
+
+        let mut index = Self::default();
+        for (path, _) in semantic_files {
+            let kind = if is_view_file(&path) {
+                ResourceKind::SemanticView
+            } else {
+                ResourceKind::SemanticTopic
+            };
+            let header = read_header(&path).await;
+            let name = header.name.unwrap_or_else(|| definition_stem(&path));
+            index.insert(kind, &name, name.clone(), header.description, path);
+        }
+
+        for (path, _) in sql_files {
+            let relative = relative_path(&workspace_path, &path);
+            let description = tokio::fs::read_to_string(&path)
+                .await
+                .ok()
+                .and_then(|content| super::utils::extract_sql_description(&content));
+            index.insert(
+                ResourceKind::SqlFile,
+                &relative,
+                relative.clone(),
+                description,
+                path,
+            );
+        }
+
+        for workflow in workflows {
+            let path = workspace_path.join(&workflow.path);
+            let header = read_header(&path).await;
+            index.insert(
+                ResourceKind::Workflow,
+                &workflow.name,
+                workflow.name.clone(),
+                header.description.filter(|d| !d.is_empty()),
+                path,
+            );
+        }
+
+        for agent in agents {
+            let agent_config = match get_agent_config(config_manager.clone(), agent.clone()).await {
+                Ok(config) => config,
+                Err(e) => {
+                    tracing::warn!("Skipping agent resource {}: {}", agent, e);
+                    continue;
+                }
+            };
+            let description =
+                Some(agent_config.description).filter(|description| !description.is_empty());
+            index.insert(
+                ResourceKind::Agent,
+                &agent_config.name,
+                agent_config.name.clone(),
+                description,
+                workspace_path.join(&agent),
+            );
+        }
+
+        tracing::debug!("Discovered {} MCP resources", index.resources.len());
+        let index = Arc::new(index);
+        let mut indexes = INDEXES.write().await;
+        indexes.retain(|_, cached| cached.built_at.elapsed() < INDEX_TTL);
+        indexes.insert(
+            workspace_path,
+            CachedIndex {
+                sources,
+                index: index.clone(),
+                built_at: Instant::now(),
+            },
+        );
+        Ok(index)
+    }
+
+    fn insert(
+        &mut self,
+        kind: ResourceKind,
+        key: &str,
+        name: String,
+        description: Option<String>,
+        path: PathBuf,
+    ) {
+        let uri = kind.uri(key);
+        if let Some(existing) = self.resources.get(&uri) {
+            tracing::warn!(
+                "Duplicate MCP resource {}: keeping {}, skipping {}",
+                uri,
+                existing.path.display(),
+                path.display()
+            );
+            return;
+        }
+        self.resources.insert(
+            uri.clone(),
+            McpResource {
+                kind,
+                uri,
+                name,
+                description,
+                path,
+            },
+        );
+    }
+
+    pub fn resources(&self) -> impl Iterator<Item = &McpResource> {
+        self.resources.values()
+    }
+
+    pub fn get(&self, uri: &str) -> Option<&McpResource> {
+        self.resources.get(uri)
+    }
+}
+
+/// Resource templates for every resource kind
+pub fn resource_templates() -> Vec<ResourceTemplate> {
+    ResourceKind::ALL
+        .iter()
+        .map(|kind| kind.template())
+        .collect()
+}
+
+// =============================================================================
+// Helper Functions
+// =============================================================================
+
+/// Files under `dir` whose name ends with one of `suffixes`, sorted by path.
+/// Hidden and build directories are skipped, and symlinks are not followed
+/// so the scan can't leave the project.
+async fn scan_files(
+    dir: PathBuf,
+    suffixes: &'static [&'static str],
+) -> Result<ScannedFiles, OxyError> {
+    tokio::task::spawn_blocking(move || collect_files(&dir, suffixes))
+        .await
+        .map_err(|e| OxyError::RuntimeError(format!("Failed to scan project files: {e}")))
+}
+
+fn collect_files(dir: &Path, suffixes: &[&str]) -> ScannedFiles {
+    WalkDir::new(dir)
+        .follow_links(false)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            let file_name = entry.file_name().to_string_lossy();
+            entry.depth() == 0
+                || !entry.file_type().is_dir()
+                || !(file_name.starts_with('.') || SKIPPED_DIRS.contains(&file_name.as_ref()))
+        })
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            let file_name = entry.file_name().to_string_lossy();
+            entry.file_type().is_file() && suffixes.iter().any(|suffix| file_name.ends_with(suffix))
+        })
+        .map(|entry| {
+            let modified = entry
+                .metadata()
+                .ok()
+                .and_then(|metadata| metadata.modified().ok());
+            (entry.into_path(), modified)
+        })
+        .collect()
+}
+
+fn is_view_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.ends_with(".view.yml") || n.ends_with(".view.yaml"))
+}
+
+/// File name without the `.<kind>.yml` double extension
+fn definition_stem(path: &Path) -> String {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .and_then(|n| n.split('.').next())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn relative_path(base: &Path, path: &Path) -> String {
+    path.strip_prefix(base)
+        .unwrap_or(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+async fn read_header(path: &Path) -> DefinitionHeader {
+    match tokio::fs::read_to_string(path).await {
+        Ok(content) => serde_yaml::from_str(&content).unwrap_or_default(),
+        Err(_) => DefinitionHeader::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_uris() {
+        assert_eq!(
+            ResourceKind::SemanticView.uri("orders"),
+            "oxy://semantic/views/orders"
+        );
+        assert_eq!(
+            ResourceKind::SqlFile.uri("queries/revenue.sql"),
+            "oxy://sql/queries/revenue.sql"
+        );
+        assert_eq!(
+            ResourceKind::SqlFile.template().raw.uri_template,
+            "oxy://sql/{+path}"
+        );
+    }
+
+    #[test]
+    fn test_collect_files_skips_hidden_dirs_and_symlinks() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("semantics/views")).unwrap();
+        std::fs::create_dir_all(root.join(".oxy/state")).unwrap();
+        std::fs::write(root.join("semantics/views/orders.view.yml"), "name: orders").unwrap();
+        std::fs::write(root.join("semantics/sales.topic.yml"), "name: sales").unwrap();
+        std::fs::write(root.join(".oxy/state/cached.view.yml"), "").unwrap();
+        std::fs::write(root.join("semantics/notes.yml"), "").unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::write(outside.path().join("secret.view.yml"), "").unwrap();
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(outside.path(), root.join("linked")).unwrap();
+            std::os::unix::fs::symlink(
+                outside.path().join("secret.view.yml"),
+                root.join("semantics/secret.view.yml"),
+            )
+            .unwrap();
+        }
+
+        let files: Vec<PathBuf> = collect_files(root, &[".view.yml", ".topic.yml"])
+            .into_iter()
+            .map(|(path, modified)| {
+                assert!(modified.is_some());
+                path
+            })
+            .collect();
+
+        let relative: Vec<String> = files.iter().map(|p| relative_path(root, p)).collect();
+        assert_eq!(
+            relative,
+            vec![
+                "semantics/sales.topic.yml",
+                "semantics/views/orders.view.yml"
+            ]
+        );
+        assert!(is_view_file(&files[1]));
+        assert_eq!(definition_stem(&files[0]), "sales");
+    }
+}
@@ -1,12 +1,15 @@
 // Standard library imports
 use std::path::PathBuf;
+use std::sync::Arc;
 
 // External crate imports
 use rmcp::{
     ErrorData, RoleServer, ServerHandler,
     model::{
-        CallToolRequestParam, CallToolResult, ListToolsResult, PaginatedRequestParam,
-        ServerCapabilities, ServerInfo,
+        CallToolRequestParam, CallToolResult, GetPromptRequestParam, GetPromptResult,
+        ListPromptsResult, ListResourceTemplatesResult, ListResourcesResult, ListToolsResult,
+        PaginatedRequestParam, PromptMessage, PromptMessageRole, ReadResourceRequestParam,
+        ReadResourceResult, ResourceContents, ServerCapabilities, ServerInfo,
     },
     service::RequestContext,
 };
@@ -14,16 +17,22 @@ use uuid::Uuid;
 
 // Internal crate imports
 use oxy::adapters::{
-    runs::RunsManager, secrets::SecretsManager, workspace::builder::WorkspaceBuilder,
+    runs::RunsManager,
+    secrets::SecretsManager,
+    workspace::{builder::WorkspaceBuilder, manager::WorkspaceManager},
 };
 use oxy_shared::errors::OxyError;
 
 use super::connections::extract_connection_overrides;
 use super::context::ToolExecutionContext;
 use super::filters::extract_session_filters;
+use super::prompts::get_agent_prompts;
+use super::resources::{McpResourceIndex, resource_templates};
 use super::tools::get_mcp_tools;
-use super::types::OxyMcpServer;
+use super::types::{OxyMcpServer, OxyTool, ToolType};
 use super::variables::extract_meta_variables;
+use crate::server::service::permissions::WorkspacePermissions;
+use entity::custom_roles::Permission;
 
 // =============================================================================
 // ServerHandler Implementation
@@ -33,7 +42,11 @@ impl ServerHandler for OxyMcpServer {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
             instructions: Some("Oxygen is the Data Agent Platform that brings intelligence to your structured enterprise data. Answer, build, and automate anything.".into()),
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_resources()
+                .enable_prompts()
+                .build(),
             ..Default::default()
         }
     }
@@ -70,6 +83,10 @@ impl ServerHandler for OxyMcpServer {
                     None,
                 ))?;
 
+        if let ToolType::SqlFile = oxy_tool.tool_type {
+            self.ensure_database_allowed(params.arguments.as_ref())?;
+        }
+
         // Build execution context from request metadata
         let context = ToolExecutionContext::new()
             .with_session_filters(extract_session_filters(
@@ -93,6 +110,97 @@ impl ServerHandler for OxyMcpServer {
             )
             .await
     }
+
+    async fn list_resources(
+        &self,
+        _: Option<PaginatedRequestParam>,
+        _: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, ErrorData> {
+        if !self.can_read_files() {
+            return Ok(ListResourcesResult {
+                resources: vec![],
+                next_cursor: None,
+            });
+        }
+        let index = self.resource_index().await?;
+        Ok(ListResourcesResult {
+            resources: index.resources().map(|r| r.to_resource()).collect(),
+            next_cursor: None,
+        })
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _: Option<PaginatedRequestParam>,
+        _: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, ErrorData> {
+        Ok(ListResourceTemplatesResult {
+            resource_templates: resource_templates(),
+            next_cursor: None,
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        params: ReadResourceRequestParam,
+        _: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, ErrorData> {
+        self.ensure_can_read_files()?;
+        let index = self.resource_index().await?;
+        let resource = index.get(&params.uri).ok_or_else(|| {
+            ErrorData::resource_not_found(format!("Resource {} not found", params.uri), None)
+        })?;
+        let content = resource
+            .read()
+            .await
+            .map_err(|e| ErrorData::internal_error(e.to_string(), None))?;
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::text(content, resource.uri.clone())],
+        })
+    }
+
+    async fn list_prompts(
+        &self,
+        _: Option<PaginatedRequestParam>,
+        _: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, ErrorData> {
+        if !self.can_read_files() {
+            return Ok(ListPromptsResult {
+                prompts: vec![],
+                next_cursor: None,
+            });
+        }
+        let prompts = get_agent_prompts(&self.workspace_manager.config_manager)
+            .await
+            .map_err(|e| ErrorData::internal_error(e.to_string(), None))?;
+        Ok(ListPromptsResult {
+            prompts: prompts.iter().map(|p| p.to_prompt()).collect(),
+            next_cursor: None,
+        })
+    }
+
+    async fn get_prompt(
+        &self,
+        params: GetPromptRequestParam,
+        _: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, ErrorData> {
+        self.ensure_can_read_files()?;
+        let prompt = get_agent_prompts(&self.workspace_manager.config_manager)
+            .await
+            .map_err(|e| ErrorData::internal_error(e.to_string(), None))?
+            .into_iter()
+            .find(|p| p.name() == params.name)
+            .ok_or_else(|| {
+                ErrorData::invalid_params(format!("Prompt {} not found", params.name), None)
+            })?;
+        let text = prompt
+            .render(params.arguments)
+            .map_err(|e| ErrorData::invalid_params(e.to_string(), None))?;
+        Ok(GetPromptResult {
+            description: prompt.to_prompt().description,
+            messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+        })
+    }
 }
 
 // =============================================================================
@@ -122,21 +230,147 @@ impl OxyMcpServer {
             .await
             .map_err(|e| OxyError::from(anyhow::anyhow!("Failed to create config manager: {e}")))?;
 
-        let config_manager = workspace_manager.config_manager.clone();
+        let mut server = Self::from_workspace_manager(workspace_manager).await?;
+        server.change_working_directory = true;
+        Ok(server)
+    }
 
-        let tools = get_mcp_tools(config_manager).await?;
+    /// Creates a server for an already built workspace, as the HTTP server
+    /// does for each workspace-scoped MCP request
+    pub async fn from_workspace_manager(
+        workspace_manager: WorkspaceManager,
+    ) -> Result<Self, OxyError> {
+        let tools = get_mcp_tools(workspace_manager.config_manager.clone()).await?;
 
         Ok(Self {
             tools,
             workspace_manager,
+            change_working_directory: false,
+            permissions: WorkspacePermissions::all(),
         })
     }
 
+    /// Limits the server to what `permissions` allow, dropping the tools the
+    /// caller cannot run. Resources and prompts expose workspace files, so
+    /// they need `files.read`.
+    pub fn with_permissions(mut self, permissions: WorkspacePermissions) -> Self {
+        self.tools
+            .retain(|_, oxy_tool| tool_allowed(&permissions, oxy_tool));
+        self.permissions = permissions;
+        self
+    }
+
+    fn can_read_files(&self) -> bool {
+        self.permissions.allows(Permission::ReadFiles, None)
+    }
+
+    fn ensure_can_read_files(&self) -> Result<(), ErrorData> {
+        if self.can_read_files() {
+            Ok(())
+        } else {
+            Err(ErrorData::invalid_request(
+                "Missing permission files.read".to_string(),
+                None,
+            ))
+        }
+    }
+
+    /// SQL tools can run against any database, so the one the call resolves
+    /// to is checked here rather than when listing.
+    fn ensure_database_allowed(
+        &self,
+        arguments: Option<&serde_json::Map<String, serde_json::Value>>,
+    ) -> Result<(), ErrorData> {
+        let database = arguments
+            .and_then(|args| args.get("database"))
+            .and_then(|value| value.as_str())
+            .or_else(|| {
+                self.workspace_manager
+                    .config_manager
+                    .default_database_ref()
+                    .map(String::as_str)
+            })
+            .unwrap_or_default();
+        if self
+            .permissions
+            .allows(Permission::QueryDatabases, Some(database))
+        {
+            Ok(())
+        } else {
+            Err(ErrorData::invalid_request(
+                format!("Not allowed to query database '{database}'"),
+                None,
+            ))
+        }
+    }
+
+    /// Scans the project for resources; rebuilt when files change to pick up edits
+    async fn resource_index(&self) -> Result<Arc<McpResourceIndex>, rmcp::ErrorData> {
+        McpResourceIndex::load(&self.workspace_manager.config_manager)
+            .await
+            .map_err(|e| rmcp::ErrorData::internal_error(e.to_string(), None))
+    }
+
     /// Sets up the working directory for tool execution
     pub fn setup_working_directory(&self) -> Result<(), rmcp::ErrorData> {
+        if !self.change_working_directory {
+            return Ok(());
+        }
         let config = self.workspace_manager.config_manager.get_config();
         std::env::set_current_dir(&config.workspace_path).map_err(|e| {
             rmcp::ErrorData::internal_error(format!("Failed to set current directory: {e}"), None)
         })
     }
 }
+
+fn tool_allowed(permissions: &WorkspacePermissions, oxy_tool: &OxyTool) -> bool {
+    match oxy_tool.tool_type {
+        ToolType::Agent => permissions.allows(Permission::RunAgents, Some(&oxy_tool.path)),
+        ToolType::Workflow => permissions.allows(Permission::RunWorkflows, Some(&oxy_tool.path)),
+        ToolType::SemanticTopic => {
+            permissions.allows(Permission::QuerySemantic, Some(&oxy_tool.name))
+        }
+        ToolType::SqlFile => permissions.allows(Permission::QueryDatabases, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entity::workspace_members::WorkspaceRole;
+
+    fn oxy_tool(tool_type: ToolType, name: &str, path: &str) -> OxyTool {
+        OxyTool {
+            tool: rmcp::model::Tool::new(name.to_string(), "", Arc::new(serde_json::Map::new())),
+            tool_type,
+            name: name.to_string(),
+            path: path.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_tool_allowed_by_scoped_grants() {
+        let permissions = WorkspacePermissions::for_role(&WorkspaceRole::Member).limited_by(
+            ["agents.run:agents/sales.agent.yml", "semantic.query:orders"]
+                .iter()
+                .map(|entry| entry.parse().unwrap())
+                .collect(),
+        );
+
+        let sales = oxy_tool(ToolType::Agent, "sales", "agents/sales.agent.yml");
+        let finance = oxy_tool(ToolType::Agent, "finance", "agents/finance.agent.yml");
+        let orders = oxy_tool(
+            ToolType::SemanticTopic,
+            "orders",
+            "semantics/orders.topic.yml",
+        );
+        let etl = oxy_tool(ToolType::Workflow, "etl", "workflows/etl.workflow.yml");
+        let sql = oxy_tool(ToolType::SqlFile, "report.sql", "report.sql");
+
+        assert!(tool_allowed(&permissions, &sales));
+        assert!(!tool_allowed(&permissions, &finance));
+        assert!(tool_allowed(&permissions, &orders));
+        assert!(!tool_allowed(&permissions, &etl));
+        assert!(!tool_allowed(&permissions, &sql));
+    }
+}
@@ -20,8 +20,8 @@ pub async fn get_all_agent_tools(
     config_manager: ConfigManager,
 ) -> Result<HashMap<String, OxyTool>, OxyError> {
     let mut tools_map = HashMap::new();
-    for agent in list_agents(config_manager.clone()).await? {
-        let agent_config = get_agent_config(config_manager.clone(), agent.clone()).await?;
+    for agent_path in list_agents(config_manager.clone()).await? {
+        let agent_config = get_agent_config(config_manager.clone(), agent_path.clone()).await?;
 
         let schema = schemars::schema_for!(AgentToolInput);
         let schema_json = serde_json::to_value(schema)?;
@@ -36,6 +36,7 @@ pub async fn get_all_agent_tools(
             tool,
             tool_type: ToolType::Agent,
             name: agent_config.name.to_owned(),
+            path: agent_path,
         };
         tools_map.insert(tool_name, oxy_tool);
     }
@@ -84,6 +85,7 @@ pub async fn resolve_agent_tool(
         tool,
         tool_type: ToolType::Agent,
         name: agent_config.name.to_owned(),
+        path: relative_path,
     };
 
     tracing::debug!(
@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::integrations::mcp::ToolType;
 use oxy::config::ConfigManager;
@@ -97,6 +97,32 @@ pub async fn get_mcp_tools(
     }
 }
 
+/// Files [`get_mcp_tools`] discovers tools from: the files matching the
+/// configured resource patterns, or every agent and workflow without them
+pub async fn get_mcp_tool_sources(
+    config_manager: &ConfigManager,
+) -> Result<Vec<PathBuf>, OxyError> {
+    let config = config_manager.get_config();
+    match &config.mcp {
+        Some(mcp) => Ok(mcp
+            .tools
+            .iter()
+            .filter_map(|pattern| {
+                let full_pattern = config.workspace_path.join(pattern);
+                glob::glob(full_pattern.to_str()?).ok()
+            })
+            .flatten()
+            .filter_map(Result::ok)
+            .filter(|path| path.is_file())
+            .collect()),
+        None => {
+            let mut sources = config_manager.list_agents().await?;
+            sources.extend(config_manager.list_workflows().await?);
+            Ok(sources)
+        }
+    }
+}
+
 /// Default discovery strategy: exposes all agents and workflows as MCP tools
 async fn get_default_tools(
     config_manager: ConfigManager,
@@ -5,7 +5,7 @@ mod sql;
 mod workflow;
 
 pub use agent::run_agent_tool;
-pub use discovery::get_mcp_tools;
+pub use discovery::{get_mcp_tool_sources, get_mcp_tools};
 pub use semantic::run_semantic_topic_tool;
 pub use sql::run_sql_file_tool;
 pub use workflow::run_workflow_tool;
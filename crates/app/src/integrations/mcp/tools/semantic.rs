@@ -4,7 +4,7 @@ use crate::integrations::mcp::types::{
 use oxy::adapters::semantic_tool_description::build_semantic_topic_description;
 use oxy::adapters::session_filters::SessionFilters;
 use oxy::config::ConfigManager;
-use oxy_semantic::parse_semantic_layer_from_dir;
+use oxy_semantic::{access::redact_view, parse_semantic_layer_from_dir};
 use oxy_shared::errors::OxyError;
 use rmcp::model::Tool;
 use serde_json::{Map, Value};
@@ -24,8 +24,13 @@ pub async fn resolve_semantic_tool(
     use oxy_semantic::models::Topic;
 
     // Load the semantic layer to get view metadata
-    let semantic_layer =
+    let mut semantic_layer =
         parse_semantic_layer_from_dir(config_manager.semantics_scan_path())?.semantic_layer;
+    semantic_layer.views = semantic_layer
+        .views
+        .iter()
+        .map(|view| redact_view(view, Some(config_manager.access_level())))
+        .collect();
 
     let content = tokio::fs::read_to_string(&topic_path).await.map_err(|e| {
         OxyError::ConfigurationError(format!(
@@ -63,6 +68,11 @@ pub async fn resolve_semantic_tool(
         tool,
         tool_type: ToolType::SemanticTopic,
         name: topic_name.clone(),
+        path: topic_path
+            .strip_prefix(config_manager.workspace_path())
+            .unwrap_or(&topic_path)
+            .to_string_lossy()
+            .to_string(),
     };
 
     tracing::debug!(
@@ -131,6 +141,7 @@ pub async fn run_semantic_topic_tool(
             Some(merged_variables.clone())
         },
         time_dimensions: input.time_dimensions.unwrap_or_default(),
+        time_spine: input.time_spine.unwrap_or_default(),
     };
 
     let task = SemanticQueryTask {
@@ -78,7 +78,8 @@ pub async fn resolve_execute_sql_tool(
     let oxy_tool = OxyTool {
         tool,
         tool_type: ToolType::SqlFile,
-        name: relative_path,
+        name: relative_path.clone(),
+        path: relative_path,
     };
 
     tracing::debug!(
@@ -151,6 +152,7 @@ pub async fn run_sql_file_tool(
                 dry_run_limit: None,
                 name: None,
                 persist: false,
+                cache_ttl_secs: None,
             },
         )
         .await;
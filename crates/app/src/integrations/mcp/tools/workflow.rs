@@ -52,6 +52,7 @@ pub async fn get_all_workflow_tools(
             tool: tool.clone(),
             tool_type: ToolType::Workflow,
             name: workflow.name,
+            path: workflow.path,
         };
         tools_map.insert(tool_name, oxy_tool);
     }
@@ -101,6 +102,7 @@ pub async fn resolve_workflow_tool(
         tool: tool.clone(),
         tool_type: ToolType::Workflow,
         name: workflow_config.name.clone(),
+        path: relative_path.to_string_lossy().to_string(),
     };
 
     tracing::debug!(
@@ -12,6 +12,7 @@ use serde_json::Value;
 use oxy::{adapters::workspace::manager::WorkspaceManager, config::model::SemanticFilter};
 
 use super::executor::ToolExecutor;
+use crate::server::service::permissions::WorkspacePermissions;
 
 // =============================================================================
 // Constants
@@ -53,12 +54,20 @@ pub struct OxyTool {
     pub tool: Tool,
     pub tool_type: ToolType,
     pub name: String,
+    /// Workspace-relative path of the file the tool runs
+    pub path: String,
 }
 
 #[derive(Debug, Clone)]
 pub struct OxyMcpServer {
     pub workspace_manager: WorkspaceManager,
     pub tools: HashMap<String, OxyTool>,
+    /// Whether tool calls switch the process working directory to the
+    /// workspace. Only safe when the process serves a single workspace, so
+    /// the HTTP endpoint leaves it off.
+    pub change_working_directory: bool,
+    /// What the caller may do; tools they cannot run are not listed
+    pub permissions: WorkspacePermissions,
 }
 
 // =============================================================================
@@ -86,7 +95,7 @@ pub struct SemanticTopicToolInput {
     /// Dimensions to group by (e.g., column names from the views in this topic)
     #[serde(skip_serializing_if = "Option::is_none")]
     #[schemars(
-        description = "List of dimensions to include in the query. Format: <view_name>.<dimension_name>"
+        description = "List of dimensions to include in the query. Format: <view_name>.<dimension_name>, or <view_name>.<dimension_name>.<grain> for a date dimension at a grain"
     )]
     pub dimensions: Option<Vec<String>>,
 
@@ -115,4 +124,11 @@ pub struct SemanticTopicToolInput {
     #[schemars(description = "Optional variables maybe required to render some semantic queries.")]
     pub variables: Option<HashMap<String, Value>>,
     pub time_dimensions: Option<Vec<crate::service::types::TimeDimension>>,
+
+    /// Zero-fill the time buckets the query returns no rows for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "Return a row with zero measures for every time bucket between the first and last one returned"
+    )]
+    pub time_spine: Option<bool>,
 }
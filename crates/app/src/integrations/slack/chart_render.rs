@@ -1,10 +1,12 @@
 //! Chart-PNG rendering for Slack messages.
 //!
 //! The chart spec lives on disk as echarts JSON (the web frontend
-//! renders them client-side). For Slack, we drive a headless Chromium
-//! over an inline HTML page that loads echarts and applies the same
-//! JSON → option transform the React `<Chart>` component does, then
-//! screenshot the result.
+//! renders them client-side). For Slack, we render it in-process with
+//! the native renderer in `oxy::tools::visualize::render`. Setting
+//! `OXY_CHART_RENDERER=chrome` switches back to driving a headless
+//! Chromium over an inline HTML page that loads echarts and applies the
+//! same JSON → option transform the React `<Chart>` component does,
+//! then screenshots the result.
 //!
 //! [`get_or_render_chart_png`] caches PNG bytes alongside the chart JSON
 //! in the workspace state dir. The Slack render path takes those bytes
@@ -12,11 +14,12 @@
 //! see [`crate::integrations::slack::render`]) or surfaces the cached
 //! path as a context-block breadcrumb (local dev).
 //!
-//! `render_echarts_to_png` builds an inline HTML page that loads echarts
-//! from a CDN, applies the same JSON → option transform that the web
-//! frontend uses, and waits for the chart's `finished` event before
-//! taking a screenshot. Headless chrome runs on a blocking thread (it's
-//! not async-aware), so we wrap the call in `spawn_blocking`.
+//! On the Chrome path, `render_echarts_to_png` builds an inline HTML page
+//! that loads echarts from a CDN, applies the same JSON → option
+//! transform that the web frontend uses, and waits for the chart's
+//! `finished` event before taking a screenshot. Headless chrome runs on
+//! a blocking thread (it's not async-aware), so we wrap the call in
+//! `spawn_blocking`.
 
 use std::path::PathBuf;
 use std::time::Duration;
@@ -26,6 +29,7 @@ use headless_chrome::Browser;
 use headless_chrome::protocol::cdp::Page::CaptureScreenshotFormatOption;
 use oxy::adapters::workspace::resolve_workspace_path;
 use oxy::config::ConfigBuilder;
+use oxy::tools::visualize::render::{ChartDocument, ChartRenderer};
 use oxy_shared::errors::OxyError;
 use tokio::sync::Mutex;
 use uuid::Uuid;
@@ -38,7 +42,8 @@ const RENDER_POLL_INTERVAL: Duration = Duration::from_millis(100);
 /// Per-process render lock. Headless-chrome browser launches are heavy
 /// and not safe to fan out arbitrarily — a single in-flight render at a
 /// time keeps memory bounded and prevents the "10 chart events arrive
-/// in parallel and we OOM" failure mode.
+/// in parallel and we OOM" failure mode. Native renders are cheap but
+/// share the lock so concurrent callers still hit the cache.
 static RENDER_LOCK: Mutex<()> = Mutex::const_new(());
 
 /// Resolve the on-disk PNG cache path for a given workspace + chart
@@ -122,7 +127,10 @@ pub async fn ensure_chart_png_cached(
     let config: serde_json::Value = serde_json::from_str(&raw)
         .map_err(|e| OxyError::RuntimeError(format!("chart JSON parse failed: {e}")))?;
 
-    let png = render_echarts_to_png(&config).await?;
+    let png = match ChartRenderer::from_env() {
+        ChartRenderer::Native => render_native_png(config).await?,
+        ChartRenderer::Chrome => render_echarts_to_png(&config).await?,
+    };
 
     if let Some(parent) = cache_path.parent() {
         tokio::fs::create_dir_all(parent).await.map_err(|e| {
@@ -164,6 +172,16 @@ pub async fn get_or_render_chart_png(
     })
 }
 
+/// Render `config` with the native renderer. Rasterizing is CPU-bound, so
+/// it runs on a blocking thread.
+async fn render_native_png(config: serde_json::Value) -> Result<Vec<u8>, OxyError> {
+    tokio::task::spawn_blocking(move || {
+        ChartDocument::from_json(&config)?.render_png(RENDER_VIEWPORT_WIDTH, RENDER_VIEWPORT_HEIGHT)
+    })
+    .await
+    .map_err(|e| OxyError::RuntimeError(format!("chart render task panicked: {e}")))?
+}
+
 /// Drive headless Chromium to render `config` (a simplified echarts spec
 /// — `series`, `xAxis`, `yAxis`, `title`) into PNG bytes.
 ///
@@ -298,8 +316,9 @@ fn build_render_html(config: &serde_json::Value) -> Result<String, OxyError> {
     ))
 }
 
-// Renderer-side tests are intentionally light here: spinning up
-// headless Chromium in a unit test would be slow, environment-fragile,
-// and cover the wrong layer. The path-resolution logic above is
-// pure-async-fs and exercised end-to-end by the Slack integration
-// tests when a chart event fires.
+// Renderer-side tests are intentionally light here: the native renderer
+// is covered in `oxy::tools::visualize::render`, and spinning up headless
+// Chromium in a unit test would be slow, environment-fragile, and cover
+// the wrong layer. The path-resolution logic above is pure-async-fs and
+// exercised end-to-end by the Slack integration tests when a chart event
+// fires.
@@ -1,4 +1,3 @@
-use crate::server::api::middlewares::role_guards::WorkspaceAdmin;
 use crate::server::service::api_key::{
     ApiKeyConfig, ApiKeyService, CreateApiKeyParams, CreateApiKeyResponse,
 };
@@ -8,10 +7,14 @@ use axum::{
     response::IntoResponse,
 };
 use entity::api_keys::Model as ApiKeyModel;
+use entity::custom_roles;
+use entity::prelude::{ApiKeys, CustomRoles};
 use garde::Validate;
+use oxy::audit::{self, AuditAction, AuditEvent};
 use oxy::database::client::establish_connection;
 use oxy_auth::extractor::AuthenticatedUserExtractor;
 use oxy_shared::errors::OxyError;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use utoipa::ToSchema;
@@ -55,6 +58,7 @@ pub struct ApiKeyResponse {
     pub is_active: bool,
     #[schema(example = "sk_1234****...5678")]
     pub masked_key: Option<String>, // Only shown for newly created keys or when explicitly requested
+    pub custom_role_id: Option<Uuid>,
 }
 
 impl ApiKeyResponse {
@@ -86,6 +90,11 @@ pub struct CreateApiKeyRequest {
     #[garde(custom(validate_expires_at))]
     #[schema(example = "2025-12-31T23:59:59Z")]
     pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// Custom role limiting what the key can do, on top of its owner's
+    /// permissions. Must belong to the key's workspace.
+    #[garde(skip)]
+    pub custom_role_id: Option<Uuid>,
 }
 
 #[derive(Serialize, ToSchema)]
@@ -125,6 +134,7 @@ impl From<ApiKeyModel> for ApiKeyResponse {
             created_at: model.created_at.into(),
             is_active: model.is_active,
             masked_key: None, // Never expose key data from stored models
+            custom_role_id: model.custom_role_id,
         }
     }
 }
@@ -150,7 +160,6 @@ impl From<ApiKeyModel> for ApiKeyResponse {
     ),
 )]
 pub async fn create_api_key(
-    _: WorkspaceAdmin,
     AuthenticatedUserExtractor(user): AuthenticatedUserExtractor,
     Path(workspace_id): Path<Uuid>,
     extract::Json(request): extract::Json<CreateApiKeyRequest>,
@@ -170,6 +179,26 @@ pub async fn create_api_key(
 
     let db = establish_connection().await?;
 
+    if let Some(role_id) = request.custom_role_id {
+        let role = CustomRoles::find_by_id(role_id)
+            .filter(custom_roles::Column::WorkspaceId.eq(workspace_id))
+            .one(&db)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to load custom role: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        if role.is_none() {
+            return Ok((
+                StatusCode::BAD_REQUEST,
+                extract::Json(json!({
+                    "error": "Custom role not found in this workspace"
+                })),
+            )
+                .into_response());
+        }
+    }
+
     let config = ApiKeyConfig::default();
 
     let create_request = CreateApiKeyParams {
@@ -177,10 +206,19 @@ pub async fn create_api_key(
         name: request.name,
         expires_at: request.expires_at,
         project_id: workspace_id,
+        custom_role_id: request.custom_role_id,
     };
 
     match ApiKeyService::create_api_key(&db, create_request, &config).await {
         Ok(response) => {
+            audit::record(
+                AuditEvent::new(AuditAction::ApiKeyCreated)
+                    .workspace(workspace_id)
+                    .actor(user.id)
+                    .target("api_key", response.id)
+                    .meta("name", response.name.clone()),
+            )
+            .await;
             let dto: CreateApiKeyResponseDto = response.into();
             Ok((StatusCode::CREATED, extract::Json(dto)).into_response())
         }
@@ -313,7 +351,6 @@ pub async fn get_api_key(
     ),
 )]
 pub async fn delete_api_key(
-    _: WorkspaceAdmin,
     AuthenticatedUserExtractor(user): AuthenticatedUserExtractor,
     Path(id): Path<String>,
 ) -> Result<impl IntoResponse, StatusCode> {
@@ -321,7 +358,22 @@ pub async fn delete_api_key(
     let db = establish_connection().await?;
 
     match ApiKeyService::revoke_api_key(&db, key_id, user.id).await {
-        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Ok(()) => {
+            let workspace_id = ApiKeys::find_by_id(key_id)
+                .one(&db)
+                .await
+                .ok()
+                .flatten()
+                .map(|key| key.project_id);
+            audit::record(
+                AuditEvent::new(AuditAction::ApiKeyRevoked)
+                    .workspace(workspace_id)
+                    .actor(user.id)
+                    .target("api_key", key_id),
+            )
+            .await;
+            Ok(StatusCode::NO_CONTENT)
+        }
         Err(OxyError::ValidationError(_)) => Err(StatusCode::NOT_FOUND),
         Err(e) => {
             tracing::error!("Failed to revoke API key: {}", e);
@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-use crate::cli::commands::export_chart::export_charts_to_dir;
+use crate::cli::commands::export_chart::{
+    app_chart_documents, export_charts_to_dir, render_charts_to_dir,
+};
 use crate::server::api::middlewares::workspace_context::WorkspaceManagerExtractor;
 use crate::server::service::app::{
     AppResultChartDisplay, AppResultData, AppResultDisplay, AppResultMarkdownDisplay,
@@ -18,6 +20,7 @@ use oxy::config::model::{
     AppTaskMode, ControlConfig, DatabaseType, Display, DuckDBOptions, SQL, TaskType,
 };
 use oxy::execute::types::{Data, DataContainer};
+use oxy::tools::visualize::render::ChartRenderer;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
@@ -660,8 +663,17 @@ pub async fn get_app_result(
             .get_charts_dir()
             .await
             .unwrap_or_default();
-        let app_path_str = path.to_string_lossy().to_string();
-        match export_charts_to_dir(&app_path_str, &charts_dir).await {
+        let exported = match ChartRenderer::from_env() {
+            ChartRenderer::Native => {
+                let charts = app_chart_documents(&typed_displays, &task_data_map);
+                render_charts_to_dir(charts, &charts_dir).await
+            }
+            ChartRenderer::Chrome => {
+                let app_path_str = path.to_string_lossy().to_string();
+                export_charts_to_dir(&app_path_str, &charts_dir).await
+            }
+        };
+        match exported {
             Ok(map) => map,
             Err(e) => {
                 tracing::warn!("Failed to export charts: {:?}", e);
@@ -747,7 +759,7 @@ pub async fn get_app_result(
     (StatusCode::OK, extract::Json(response))
 }
 
-fn data_container_to_output(data: &DataContainer) -> Option<TaskOutput> {
+pub(crate) fn data_container_to_output(data: &DataContainer) -> Option<TaskOutput> {
     match data {
         DataContainer::Single(Data::Bool(b)) => Some(TaskOutput::Bool(*b)),
         DataContainer::Single(Data::Text(s)) => Some(TaskOutput::Text(s.clone())),
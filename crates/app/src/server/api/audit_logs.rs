@@ -0,0 +1,332 @@
+//! Org audit log (OrgAdmin): a filtered, paginated listing and a CSV / JSON
+//! Lines export of the same filters. Entries are written by `oxy::audit`.
+
+use axum::extract::{Json, Query};
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use entity::prelude::{AuditLogs, OrgMembers};
+use entity::{audit_logs, org_members};
+use oxy::database::client::establish_connection;
+use sea_orm::sea_query::Query;
+use sea_orm::{
+    ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Select,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::middlewares::role_guards::OrgAdmin;
+
+const EXPORT_BATCH: u64 = 1000;
+
+#[derive(Deserialize)]
+pub struct AuditLogQuery {
+    /// An action (`secret.created`) or a category (`secret`)
+    pub action: Option<String>,
+    pub actor_id: Option<Uuid>,
+    pub workspace_id: Option<Uuid>,
+    pub target_id: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+    /// Export only: `csv` (default) or `jsonl`
+    pub format: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct AuditLogEntry {
+    pub id: Uuid,
+    pub created_at: String,
+    pub workspace_id: Option<Uuid>,
+    pub actor_id: Option<Uuid>,
+    pub actor_email: Option<String>,
+    pub action: String,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    pub metadata: serde_json::Value,
+}
+
+impl From<audit_logs::Model> for AuditLogEntry {
+    fn from(entry: audit_logs::Model) -> Self {
+        Self {
+            id: entry.id,
+            created_at: entry.created_at.to_rfc3339(),
+            workspace_id: entry.workspace_id,
+            actor_id: entry.actor_id,
+            actor_email: entry.actor_email,
+            action: entry.action,
+            target_type: entry.target_type,
+            target_id: entry.target_id,
+            metadata: entry.metadata,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct AuditLogListResponse {
+    pub entries: Vec<AuditLogEntry>,
+    pub page: u64,
+    pub limit: u64,
+    pub total: u64,
+}
+
+#[derive(Debug, PartialEq)]
+enum ActionFilter {
+    Exact(String),
+    Category(String),
+}
+
+fn action_filter(action: &str) -> ActionFilter {
+    match action.trim().trim_end_matches('.') {
+        action if action.contains('.') => ActionFilter::Exact(action.to_string()),
+        category => ActionFilter::Category(format!("{category}.")),
+    }
+}
+
+/// Entries of the org, plus org-less entries (sign-ins and sign-outs) of its
+/// current members.
+fn filtered(org_id: Uuid, query: &AuditLogQuery) -> Select<AuditLogs> {
+    let members = Query::select()
+        .column(org_members::Column::UserId)
+        .from(OrgMembers)
+        .and_where(org_members::Column::OrgId.eq(org_id))
+        .to_owned();
+    let mut select = AuditLogs::find().filter(
+        Condition::any()
+            .add(audit_logs::Column::OrgId.eq(org_id))
+            .add(
+                Condition::all()
+                    .add(audit_logs::Column::OrgId.is_null())
+                    .add(audit_logs::Column::ActorId.in_subquery(members)),
+            ),
+    );
+    if let Some(action) = &query.action {
+        select = match action_filter(action) {
+            ActionFilter::Exact(action) => select.filter(audit_logs::Column::Action.eq(action)),
+            ActionFilter::Category(prefix) => {
+                select.filter(audit_logs::Column::Action.starts_with(prefix))
+            }
+        };
+    }
+    if let Some(actor_id) = query.actor_id {
+        select = select.filter(audit_logs::Column::ActorId.eq(actor_id));
+    }
+    if let Some(workspace_id) = query.workspace_id {
+        select = select.filter(audit_logs::Column::WorkspaceId.eq(workspace_id));
+    }
+    if let Some(target_id) = &query.target_id {
+        select = select.filter(audit_logs::Column::TargetId.eq(target_id.as_str()));
+    }
+    if let Some(from) = query.from {
+        select = select.filter(audit_logs::Column::CreatedAt.gte(from.fixed_offset()));
+    }
+    if let Some(to) = query.to {
+        select = select.filter(audit_logs::Column::CreatedAt.lt(to.fixed_offset()));
+    }
+    select
+}
+
+/// GET /orgs/:org_id/audit-logs — newest first.
+pub async fn list_audit_logs(
+    OrgAdmin(ctx): OrgAdmin,
+    Query(query): Query<AuditLogQuery>,
+) -> Result<Json<AuditLogListResponse>, StatusCode> {
+    let db = establish_connection().await.map_err(|e| {
+        tracing::error!("DB connection error: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(100).clamp(1, 500);
+    let paginator = filtered(ctx.org.id, &query)
+        .order_by_desc(audit_logs::Column::CreatedAt)
+        .order_by_desc(audit_logs::Column::Id)
+        .paginate(&db, limit);
+    let total = paginator.num_items().await.map_err(|e| {
+        tracing::error!("Failed to count audit log entries: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let entries = paginator.fetch_page(page - 1).await.map_err(|e| {
+        tracing::error!("Failed to list audit log entries: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(AuditLogListResponse {
+        entries: entries.into_iter().map(AuditLogEntry::from).collect(),
+        page,
+        limit,
+        total,
+    }))
+}
+
+/// GET /orgs/:org_id/audit-logs/export?format=csv|jsonl — every matching
+/// entry, oldest first.
+pub async fn export_audit_logs(
+    OrgAdmin(ctx): OrgAdmin,
+    Query(query): Query<AuditLogQuery>,
+) -> Result<Response, StatusCode> {
+    let format = match query.format.as_deref().unwrap_or("csv") {
+        "csv" => ExportFormat::Csv,
+        "jsonl" => ExportFormat::JsonLines,
+        _ => return Err(StatusCode::BAD_REQUEST),
+    };
+
+    let db = establish_connection().await.map_err(|e| {
+        tracing::error!("DB connection error: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let mut pages = filtered(ctx.org.id, &query)
+        .order_by_asc(audit_logs::Column::CreatedAt)
+        .order_by_asc(audit_logs::Column::Id)
+        .paginate(&db, EXPORT_BATCH);
+    let mut entries = Vec::new();
+    while let Some(batch) = pages.fetch_and_next().await.map_err(|e| {
+        tracing::error!("Failed to export audit log entries: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })? {
+        entries.extend(batch.into_iter().map(AuditLogEntry::from));
+    }
+
+    let body = format.render(&entries).map_err(|e| {
+        tracing::error!("Failed to render audit log export: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"audit-log.{}\"", format.extension()),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ExportFormat {
+    Csv,
+    JsonLines,
+}
+
+impl ExportFormat {
+    fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::JsonLines => "application/x-ndjson",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::JsonLines => "jsonl",
+        }
+    }
+
+    fn render(self, entries: &[AuditLogEntry]) -> anyhow::Result<String> {
+        match self {
+            ExportFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(Vec::new());
+                writer.write_record([
+                    "id",
+                    "created_at",
+                    "workspace_id",
+                    "actor_id",
+                    "actor_email",
+                    "action",
+                    "target_type",
+                    "target_id",
+                    "metadata",
+                ])?;
+                let optional = |id: Option<Uuid>| id.map(|id| id.to_string()).unwrap_or_default();
+                for entry in entries {
+                    writer.write_record([
+                        entry.id.to_string(),
+                        entry.created_at.clone(),
+                        optional(entry.workspace_id),
+                        optional(entry.actor_id),
+                        entry.actor_email.clone().unwrap_or_default(),
+                        entry.action.clone(),
+                        entry.target_type.clone().unwrap_or_default(),
+                        entry.target_id.clone().unwrap_or_default(),
+                        entry.metadata.to_string(),
+                    ])?;
+                }
+                Ok(String::from_utf8(writer.into_inner()?)?)
+            }
+            ExportFormat::JsonLines => {
+                let mut out = String::new();
+                for entry in entries {
+                    out.push_str(&serde_json::to_string(entry)?);
+                    out.push('\n');
+                }
+                Ok(out)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(metadata: serde_json::Value) -> AuditLogEntry {
+        AuditLogEntry {
+            id: Uuid::nil(),
+            created_at: "2026-10-18T00:00:00+00:00".to_string(),
+            workspace_id: None,
+            actor_id: None,
+            actor_email: Some("ada@example.com".to_string()),
+            action: "query.executed".to_string(),
+            target_type: Some("database".to_string()),
+            target_id: Some("warehouse".to_string()),
+            metadata,
+        }
+    }
+
+    #[test]
+    fn test_action_filter() {
+        assert_eq!(
+            action_filter("secret.created"),
+            ActionFilter::Exact("secret.created".to_string())
+        );
+        assert_eq!(
+            action_filter("secret"),
+            ActionFilter::Category("secret.".to_string())
+        );
+        assert_eq!(
+            action_filter("secret."),
+            ActionFilter::Category("secret.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_csv_export_quotes_metadata() {
+        let csv = ExportFormat::Csv
+            .render(&[entry(json!({ "success": true }))])
+            .unwrap();
+        let mut lines = csv.lines();
+        assert!(lines.next().unwrap().starts_with("id,created_at,"));
+        assert_eq!(
+            lines.next().unwrap(),
+            "00000000-0000-0000-0000-000000000000,2026-10-18T00:00:00+00:00,,,\
+             ada@example.com,query.executed,database,warehouse,\
+             \"{\"\"success\"\":true}\""
+        );
+    }
+
+    #[test]
+    fn test_jsonl_export_writes_one_entry_per_line() {
+        let jsonl = ExportFormat::JsonLines
+            .render(&[entry(json!({})), entry(json!({}))])
+            .unwrap();
+        assert_eq!(jsonl.lines().count(), 2);
+        let first: serde_json::Value = serde_json::from_str(jsonl.lines().next().unwrap()).unwrap();
+        assert_eq!(first["action"], "query.executed");
+    }
+}
@@ -13,6 +13,7 @@ use governor::{
 use handlebars::Handlebars;
 use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
 use once_cell::sync::Lazy;
+use oxy::audit::{self, AuditAction, AuditEvent};
 use oxy::config::auth::MagicLinkAuth;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, Set,
@@ -150,6 +151,12 @@ pub struct OAuthStateResponse {
 }
 
 pub async fn issue_oauth_state() -> Result<Json<OAuthStateResponse>, StatusCode> {
+    Ok(Json(OAuthStateResponse {
+        state: sign_oauth_state()?,
+    }))
+}
+
+pub(super) fn sign_oauth_state() -> Result<String, StatusCode> {
     let now = Utc::now();
     let exp = now + Duration::seconds(OAUTH_STATE_TTL_SECS);
     let nonce_bytes: [u8; 16] = rand::random();
@@ -168,10 +175,10 @@ pub async fn issue_oauth_state() -> Result<Json<OAuthStateResponse>, StatusCode>
         tracing::error!("Failed to sign OAuth state: {}", e);
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
-    Ok(Json(OAuthStateResponse { state: token }))
+    Ok(token)
 }
 
-fn verify_oauth_state(state: &str) -> Result<(), StatusCode> {
+pub(super) fn verify_oauth_state(state: &str) -> Result<(), StatusCode> {
     let validation = Validation::default();
     let data = decode::<OAuthStateClaims>(
         state,
@@ -194,6 +201,8 @@ pub struct AuthConfigResponse {
     pub auth_enabled: bool,
     pub google: Option<GoogleConfig>,
     pub okta: Option<OktaConfig>,
+    /// Present when a generic OpenID Connect provider is configured.
+    pub oidc: Option<SsoConfig>,
     pub magic_link: Option<bool>,
     pub enterprise: bool,
     /// True when observability has a backend wired up. False when `--enterprise`
@@ -218,6 +227,11 @@ pub struct OktaConfig {
     pub domain: String,
 }
 
+#[derive(Serialize)]
+pub struct SsoConfig {
+    pub display_name: String,
+}
+
 #[derive(Serialize)]
 pub struct GitHubAuthConfig {
     pub client_id: String,
@@ -238,12 +252,19 @@ pub async fn get_config(
         .as_ref()
         .and_then(|auth| auth.okta.as_ref())
         .is_some();
+    let oidc_config = auth_config
+        .as_ref()
+        .and_then(|auth| auth.oidc.as_ref())
+        .map(|oidc| SsoConfig {
+            display_name: oidc.display_name.clone(),
+        });
     let has_magic_link = auth_config
         .as_ref()
         .and_then(|auth| auth.magic_link.as_ref())
         .is_some();
 
-    let auth_enabled = (has_google || has_okta || has_magic_link) && !app_state.mode.is_local();
+    let auth_enabled = (has_google || has_okta || oidc_config.is_some() || has_magic_link)
+        && !app_state.mode.is_local();
 
     let github_client_id = std::env::var("GITHUB_CLIENT_ID").ok();
 
@@ -254,6 +275,7 @@ pub async fn get_config(
             auth_enabled: false,
             google: None,
             okta: None,
+            oidc: None,
             magic_link: None,
             enterprise: app_state.enterprise,
             observability_enabled,
@@ -278,6 +300,7 @@ pub async fn get_config(
         auth_enabled: true,
         google: google_client_id.map(|client_id| GoogleConfig { client_id }),
         okta: okta_config,
+        oidc: oidc_config,
         magic_link: if has_magic_link { Some(true) } else { None },
         enterprise: app_state.enterprise,
         observability_enabled,
@@ -401,7 +424,7 @@ pub async fn google_auth(
         }
     };
 
-    let (token, user_info_payload, orgs) = finalize_login(user, &connection).await?;
+    let (token, user_info_payload, orgs) = finalize_login(user, "google", &connection).await?;
     Ok(Json(AuthResponse {
         token,
         user: user_info_payload,
@@ -473,7 +496,7 @@ pub async fn okta_auth(
         }
     };
 
-    let (token, user_info_payload, orgs) = finalize_login(user, &connection).await?;
+    let (token, user_info_payload, orgs) = finalize_login(user, "okta", &connection).await?;
     Ok(Json(AuthResponse {
         token,
         user: user_info_payload,
@@ -549,7 +572,7 @@ pub async fn github_auth(
         }
     };
 
-    let (token, user_info_payload, orgs) = finalize_login(user, &connection).await?;
+    let (token, user_info_payload, orgs) = finalize_login(user, "github", &connection).await?;
     Ok(Json(AuthResponse {
         token,
         user: user_info_payload,
@@ -692,7 +715,7 @@ fn is_unique_violation(err: &DbErr) -> bool {
 
 /// Insert a new user, handling the race condition where another request may have
 /// created the same user concurrently.
-async fn insert_user_or_fetch_existing(
+pub(super) async fn insert_user_or_fetch_existing(
     new_user: users::ActiveModel,
     email: &str,
     connection: &DatabaseConnection,
@@ -727,17 +750,25 @@ async fn insert_user_or_fetch_existing(
 
 /// Build the auth token, the `UserInfo` payload, and the user's org memberships.
 ///
-/// Called after every login (Google, Okta, GitHub, magic link verify). Role
-/// and admin status are per-org and appear on `OrgInfo` below — not on
+/// Called after every login (Google, Okta, GitHub, OIDC, magic link verify).
+/// Role and admin status are per-org and appear on `OrgInfo` below — not on
 /// `UserInfo` — so that callers never have to reason about a global role.
-async fn finalize_login(
+/// `method` names the login method in the `auth.login` audit entry.
+pub(super) async fn finalize_login(
     user: users::Model,
+    method: &str,
     connection: &DatabaseConnection,
 ) -> Result<(String, UserInfo, Vec<OrgInfo>), StatusCode> {
     let token = create_auth_token(user.clone()).await.map_err(|e| {
         tracing::error!("Failed to create auth token: {}", e);
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
+    audit::record(
+        AuditEvent::new(AuditAction::Login)
+            .actor(user.id)
+            .meta("method", method),
+    )
+    .await;
     let user_info = UserInfo {
         id: user.id.to_string(),
         email: user.email.clone(),
@@ -1279,7 +1310,7 @@ pub async fn verify_magic_link(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
-    let (token, user_info, orgs) = finalize_login(user, &connection).await?;
+    let (token, user_info, orgs) = finalize_login(user, "magic_link", &connection).await?;
     Ok(Json(AuthResponse {
         token,
         user: user_info,
@@ -1348,6 +1379,7 @@ mod mode_field_tests {
             auth_enabled: false,
             google: None,
             okta: None,
+            oidc: None,
             magic_link: None,
             enterprise: false,
             observability_enabled: false,
@@ -1364,6 +1396,7 @@ mod mode_field_tests {
             auth_enabled: false,
             google: None,
             okta: None,
+            oidc: None,
             magic_link: None,
             enterprise: false,
             observability_enabled: false,
@@ -0,0 +1,501 @@
+//! Custom workspace roles: named sets of permissions that narrow what a
+//! member or API key can do. See `service::permissions` for how they are
+//! applied.
+
+use std::str::FromStr;
+
+use axum::extract::{Json, Path};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use chrono::Utc;
+use entity::custom_roles::{Grant, Permission};
+use entity::org_members::OrgRole;
+use entity::prelude::{ApiKeys, CustomRoleAssignments, CustomRoles, OrgMembers};
+use entity::{api_keys, custom_role_assignments, custom_roles};
+use oxy::audit::{self, AuditAction, AuditEvent};
+use oxy::database::client::establish_connection;
+use oxy_auth::extractor::AuthenticatedUserExtractor;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::server::api::middlewares::role_guards::WorkspaceAdmin;
+use crate::server::api::middlewares::workspace_context::OrgMembershipExtractor;
+use crate::server::api::workspace_members::WorkspaceMemberPath;
+use crate::server::router::WorkspaceExtractor;
+
+// ---------------------------------------------------------------------------
+// Request / Response types
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+pub struct PermissionResponse {
+    pub name: &'static str,
+    /// Whether grants can be limited to a resource, as `name:resource`
+    pub scoped: bool,
+}
+
+#[derive(Serialize)]
+pub struct CustomRoleResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub permissions: Vec<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<custom_roles::Model> for CustomRoleResponse {
+    fn from(role: custom_roles::Model) -> Self {
+        Self {
+            id: role.id,
+            permissions: role.grants().iter().map(Grant::to_string).collect(),
+            name: role.name,
+            description: role.description,
+            created_at: role.created_at.into(),
+            updated_at: role.updated_at.into(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CustomRoleRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub permissions: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CustomRolePath {
+    pub workspace_id: Uuid,
+    pub role_id: Uuid,
+}
+
+#[derive(Deserialize)]
+pub struct AssignCustomRoleRequest {
+    pub custom_role_id: Uuid,
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn error_response(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(serde_json::json!({ "error": message.into() }))).into_response()
+}
+
+/// Validate a role request, returning the trimmed name and normalized grants.
+pub(crate) fn validate_role_request(
+    body: &CustomRoleRequest,
+) -> Result<(String, Vec<String>), String> {
+    let name = body.name.trim();
+    if name.is_empty() || name.len() > 100 {
+        return Err("Role name must be between 1 and 100 characters".to_string());
+    }
+    let mut grants = Vec::new();
+    for entry in &body.permissions {
+        let grant = Grant::from_str(entry)?.to_string();
+        if !grants.contains(&grant) {
+            grants.push(grant);
+        }
+    }
+    Ok((name.to_string(), grants))
+}
+
+async fn find_role(
+    db: &DatabaseConnection,
+    workspace_id: Uuid,
+    role_id: Uuid,
+) -> Result<custom_roles::Model, Response> {
+    CustomRoles::find_by_id(role_id)
+        .filter(custom_roles::Column::WorkspaceId.eq(workspace_id))
+        .one(db)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load custom role: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        })?
+        .ok_or_else(|| StatusCode::NOT_FOUND.into_response())
+}
+
+async fn name_taken(
+    db: &DatabaseConnection,
+    workspace_id: Uuid,
+    name: &str,
+    except: Option<Uuid>,
+) -> Result<bool, Response> {
+    let mut query = CustomRoles::find()
+        .filter(custom_roles::Column::WorkspaceId.eq(workspace_id))
+        .filter(custom_roles::Column::Name.eq(name));
+    if let Some(role_id) = except {
+        query = query.filter(custom_roles::Column::Id.ne(role_id));
+    }
+    let count = query.count(db).await.map_err(|e| {
+        tracing::error!("Failed to check custom role name: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    })?;
+    Ok(count > 0)
+}
+
+/// Custom roles are assigned under the same rules as role overrides: the
+/// caller must be an org Owner or Admin and outrank the target.
+async fn authorize_assignment(
+    db: &DatabaseConnection,
+    workspace: &entity::workspaces::Model,
+    caller: &entity::org_members::Model,
+    user_id: Uuid,
+) -> Result<(), Response> {
+    if !matches!(caller.role, OrgRole::Owner | OrgRole::Admin) {
+        return Err(StatusCode::FORBIDDEN.into_response());
+    }
+    let Some(org_id) = workspace.org_id else {
+        return Err(StatusCode::NOT_FOUND.into_response());
+    };
+    use entity::org_members::Column as OmCol;
+    let target = OrgMembers::find()
+        .filter(OmCol::OrgId.eq(org_id))
+        .filter(OmCol::UserId.eq(user_id))
+        .one(db)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to check target user org membership: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        })?
+        .ok_or_else(|| StatusCode::NOT_FOUND.into_response())?;
+    if target.role >= caller.role {
+        return Err(StatusCode::FORBIDDEN.into_response());
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Endpoints
+// ---------------------------------------------------------------------------
+
+/// List the permissions custom roles can be built from.
+pub async fn list_permissions() -> Json<Vec<PermissionResponse>> {
+    Json(
+        Permission::ALL
+            .iter()
+            .map(|permission| PermissionResponse {
+                name: permission.as_str(),
+                scoped: permission.is_scoped(),
+            })
+            .collect(),
+    )
+}
+
+/// List the workspace's custom roles.
+pub async fn list_custom_roles(
+    WorkspaceExtractor(workspace): WorkspaceExtractor,
+) -> Result<Json<Vec<CustomRoleResponse>>, StatusCode> {
+    let db = establish_connection()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let roles = CustomRoles::find()
+        .filter(custom_roles::Column::WorkspaceId.eq(workspace.id))
+        .order_by_asc(custom_roles::Column::Name)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(roles.into_iter().map(Into::into).collect()))
+}
+
+/// Create a custom role.
+pub async fn create_custom_role(
+    _: WorkspaceAdmin,
+    WorkspaceExtractor(workspace): WorkspaceExtractor,
+    AuthenticatedUserExtractor(user): AuthenticatedUserExtractor,
+    Json(body): Json<CustomRoleRequest>,
+) -> Result<(StatusCode, Json<CustomRoleResponse>), Response> {
+    let (name, grants) = validate_role_request(&body)
+        .map_err(|e| error_response(StatusCode::UNPROCESSABLE_ENTITY, e))?;
+
+    let db = establish_connection()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())?;
+
+    if name_taken(&db, workspace.id, &name, None).await? {
+        return Err(error_response(
+            StatusCode::CONFLICT,
+            format!("A role named '{name}' already exists"),
+        ));
+    }
+
+    let now = Utc::now();
+    let role = custom_roles::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        workspace_id: ActiveValue::Set(workspace.id),
+        name: ActiveValue::Set(name),
+        description: ActiveValue::Set(body.description),
+        permissions: ActiveValue::Set(serde_json::json!(grants)),
+        created_by: ActiveValue::Set(Some(user.id)),
+        created_at: ActiveValue::Set(now.into()),
+        updated_at: ActiveValue::Set(now.into()),
+    }
+    .insert(&db)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to create custom role: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    })?;
+
+    audit::record(
+        AuditEvent::new(AuditAction::CustomRoleCreated)
+            .workspace(workspace.id)
+            .actor(user.id)
+            .target("custom_role", role.id)
+            .meta("name", role.name.clone())
+            .meta("permissions", role.permissions.clone()),
+    )
+    .await;
+
+    Ok((StatusCode::CREATED, Json(role.into())))
+}
+
+/// Replace a custom role's name, description and permissions. Takes effect
+/// on the next request of every member and API key that has the role.
+pub async fn update_custom_role(
+    _: WorkspaceAdmin,
+    WorkspaceExtractor(workspace): WorkspaceExtractor,
+    AuthenticatedUserExtractor(user): AuthenticatedUserExtractor,
+    Path(CustomRolePath {
+        workspace_id: _,
+        role_id,
+    }): Path<CustomRolePath>,
+    Json(body): Json<CustomRoleRequest>,
+) -> Result<Json<CustomRoleResponse>, Response> {
+    let (name, grants) = validate_role_request(&body)
+        .map_err(|e| error_response(StatusCode::UNPROCESSABLE_ENTITY, e))?;
+
+    let db = establish_connection()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())?;
+
+    let role = find_role(&db, workspace.id, role_id).await?;
+    if name_taken(&db, workspace.id, &name, Some(role_id)).await? {
+        return Err(error_response(
+            StatusCode::CONFLICT,
+            format!("A role named '{name}' already exists"),
+        ));
+    }
+
+    let mut active: custom_roles::ActiveModel = role.into();
+    active.name = ActiveValue::Set(name);
+    active.description = ActiveValue::Set(body.description);
+    active.permissions = ActiveValue::Set(serde_json::json!(grants));
+    active.updated_at = ActiveValue::Set(Utc::now().into());
+    let role = active.update(&db).await.map_err(|e| {
+        tracing::error!("Failed to update custom role: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    })?;
+
+    audit::record(
+        AuditEvent::new(AuditAction::CustomRoleUpdated)
+            .workspace(workspace.id)
+            .actor(user.id)
+            .target("custom_role", role.id)
+            .meta("name", role.name.clone())
+            .meta("permissions", role.permissions.clone()),
+    )
+    .await;
+
+    Ok(Json(role.into()))
+}
+
+/// Delete a custom role. Roles still assigned to members or API keys are
+/// rejected with `409` so nobody silently regains access.
+pub async fn delete_custom_role(
+    _: WorkspaceAdmin,
+    WorkspaceExtractor(workspace): WorkspaceExtractor,
+    AuthenticatedUserExtractor(user): AuthenticatedUserExtractor,
+    Path(CustomRolePath {
+        workspace_id: _,
+        role_id,
+    }): Path<CustomRolePath>,
+) -> Result<StatusCode, Response> {
+    let db = establish_connection()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())?;
+
+    let role = find_role(&db, workspace.id, role_id).await?;
+
+    let members = CustomRoleAssignments::find()
+        .filter(custom_role_assignments::Column::CustomRoleId.eq(role_id))
+        .count(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())?;
+    let keys = ApiKeys::find()
+        .filter(api_keys::Column::CustomRoleId.eq(role_id))
+        .count(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())?;
+    if members > 0 || keys > 0 {
+        return Err(error_response(
+            StatusCode::CONFLICT,
+            format!(
+                "Role '{}' is assigned to {members} member(s) and {keys} API key(s)",
+                role.name
+            ),
+        ));
+    }
+
+    let name = role.name.clone();
+    let active: custom_roles::ActiveModel = role.into();
+    active.delete(&db).await.map_err(|e| {
+        tracing::error!("Failed to delete custom role: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    })?;
+
+    audit::record(
+        AuditEvent::new(AuditAction::CustomRoleDeleted)
+            .workspace(workspace.id)
+            .actor(user.id)
+            .target("custom_role", role_id)
+            .meta("name", name),
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Assign a custom role to a member, replacing any previous one.
+pub async fn assign_custom_role(
+    WorkspaceExtractor(workspace): WorkspaceExtractor,
+    OrgMembershipExtractor(org_membership): OrgMembershipExtractor,
+    Path(WorkspaceMemberPath {
+        workspace_id: _,
+        user_id,
+    }): Path<WorkspaceMemberPath>,
+    Json(body): Json<AssignCustomRoleRequest>,
+) -> Result<Json<serde_json::Value>, Response> {
+    let db = establish_connection()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())?;
+
+    authorize_assignment(&db, &workspace, &org_membership, user_id).await?;
+    let role = find_role(&db, workspace.id, body.custom_role_id)
+        .await
+        .map_err(|_| error_response(StatusCode::UNPROCESSABLE_ENTITY, "Unknown custom role"))?;
+
+    let existing = CustomRoleAssignments::find()
+        .filter(custom_role_assignments::Column::WorkspaceId.eq(workspace.id))
+        .filter(custom_role_assignments::Column::UserId.eq(user_id))
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())?;
+
+    let result = match existing {
+        Some(existing) => {
+            let mut active: custom_role_assignments::ActiveModel = existing.into();
+            active.custom_role_id = ActiveValue::Set(role.id);
+            active.update(&db).await.map(|_| ())
+        }
+        None => custom_role_assignments::ActiveModel {
+            id: ActiveValue::Set(Uuid::new_v4()),
+            workspace_id: ActiveValue::Set(workspace.id),
+            user_id: ActiveValue::Set(user_id),
+            custom_role_id: ActiveValue::Set(role.id),
+            created_at: ActiveValue::Set(Utc::now().into()),
+        }
+        .insert(&db)
+        .await
+        .map(|_| ()),
+    };
+    result.map_err(|e| {
+        tracing::error!("Failed to assign custom role: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    })?;
+
+    audit::record(
+        AuditEvent::new(AuditAction::CustomRoleAssigned)
+            .workspace(workspace.id)
+            .actor(org_membership.user_id)
+            .target("user", user_id)
+            .meta("custom_role_id", role.id.to_string())
+            .meta("custom_role", role.name),
+    )
+    .await;
+
+    Ok(Json(serde_json::json!({ "ok": true })))
+}
+
+/// Remove a member's custom role, restoring their built-in role's access.
+pub async fn unassign_custom_role(
+    WorkspaceExtractor(workspace): WorkspaceExtractor,
+    OrgMembershipExtractor(org_membership): OrgMembershipExtractor,
+    Path(WorkspaceMemberPath {
+        workspace_id: _,
+        user_id,
+    }): Path<WorkspaceMemberPath>,
+) -> Result<Json<serde_json::Value>, Response> {
+    let db = establish_connection()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())?;
+
+    authorize_assignment(&db, &workspace, &org_membership, user_id).await?;
+
+    let existing = CustomRoleAssignments::find()
+        .filter(custom_role_assignments::Column::WorkspaceId.eq(workspace.id))
+        .filter(custom_role_assignments::Column::UserId.eq(user_id))
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())?;
+
+    if let Some(assignment) = existing {
+        let active: custom_role_assignments::ActiveModel = assignment.into();
+        active.delete(&db).await.map_err(|e| {
+            tracing::error!("Failed to remove custom role assignment: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        })?;
+        audit::record(
+            AuditEvent::new(AuditAction::CustomRoleUnassigned)
+                .workspace(workspace.id)
+                .actor(org_membership.user_id)
+                .target("user", user_id),
+        )
+        .await;
+    }
+
+    Ok(Json(serde_json::json!({ "ok": true })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(permissions: &[&str]) -> CustomRoleRequest {
+        CustomRoleRequest {
+            name: " analyst ".to_string(),
+            description: None,
+            permissions: permissions.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_validate_role_request_normalizes() {
+        let (name, grants) = validate_role_request(&request(&[
+            "agents.run",
+            " databases.query:sales",
+            "agents.run",
+        ]))
+        .unwrap();
+        assert_eq!(name, "analyst");
+        assert_eq!(grants, vec!["agents.run", "databases.query:sales"]);
+    }
+
+    #[test]
+    fn test_validate_role_request_rejects_bad_entries() {
+        assert!(validate_role_request(&request(&["agents.delete"])).is_err());
+        assert!(validate_role_request(&request(&["members.manage:alice"])).is_err());
+
+        let mut unnamed = request(&[]);
+        unnamed.name = "  ".to_string();
+        assert!(validate_role_request(&unnamed).is_err());
+    }
+}
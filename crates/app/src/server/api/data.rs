@@ -1,4 +1,5 @@
 use crate::agentic_wiring::OxyProjectContext;
+use crate::server::api::middlewares::permission_guard::ensure_allowed;
 use crate::server::api::middlewares::workspace_context::{
     WorkspaceManagerExtractor, WorkspacePath,
 };
@@ -6,17 +7,21 @@ use crate::server::api::semantic::{ErrorResponse, ResultFormat, SemanticQueryRes
 use crate::server::api::typed_stream::{
     EMPTY_RESULT_SENTINEL, typed_stream_to_json_array, typed_stream_to_parquet,
 };
+use crate::server::service::permissions::WorkspacePermissions;
 use crate::server::service::retrieval::{ReindexInput, reindex};
 use agentic_pipeline::platform::ProjectContext;
 use axum::extract::{self, Path};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
+use entity::custom_roles::Permission;
 use oxy::adapters::{session_filters::SessionFilters, workspace::manager::WorkspaceManager};
 use oxy::config::model::ConnectionOverrides;
 use oxy::execute::types::utils::record_batches_to_2d_array;
+use oxy_auth::extractor::AuthenticatedUserExtractor;
 use oxy_shared::errors::OxyError;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use uuid::Uuid;
 
 #[derive(Serialize, Deserialize, Clone, ToSchema)]
 pub struct SQLParams {
@@ -84,9 +89,12 @@ fn user_facing_query_error(err: &OxyError) -> String {
 /// is now the single path for every Dev Portal query.
 async fn run_via_agentic_connector(
     workspace_manager: &WorkspaceManager,
+    user_id: Uuid,
     payload: &SQLParams,
 ) -> Result<SemanticQueryResponse, OxyError> {
-    let ctx = OxyProjectContext::new(workspace_manager.clone());
+    let ctx = OxyProjectContext::new(workspace_manager.clone())
+        .with_user(user_id)
+        .with_filters(payload.filters.clone());
     let connector = ctx.build_connector_for(&payload.database).await?;
 
     let stream = connector
@@ -138,14 +146,28 @@ fn truncate_sql_for_log(sql: &str) -> String {
     }
 }
 
+/// Runs the query; the connector records a `query.executed` audit entry
+/// with the database and the SQL's hash.
 pub async fn execute_sql(
     WorkspaceManagerExtractor(workspace_manager): WorkspaceManagerExtractor,
     Path(WorkspacePath {
         workspace_id: _workspace_id,
     }): Path<WorkspacePath>,
+    AuthenticatedUserExtractor(user): AuthenticatedUserExtractor,
+    permissions: WorkspacePermissions,
     extract::Json(payload): extract::Json<SQLParams>,
 ) -> Result<extract::Json<SemanticQueryResponse>, (StatusCode, extract::Json<ErrorResponse>)> {
-    run_via_agentic_connector(&workspace_manager, &payload)
+    ensure_allowed(&permissions, Permission::QueryDatabases, &payload.database).map_err(
+        |status| {
+            (
+                status,
+                extract::Json(ErrorResponse {
+                    message: format!("Not allowed to query database '{}'", payload.database),
+                }),
+            )
+        },
+    )?;
+    run_via_agentic_connector(&workspace_manager, user.id, &payload)
         .await
         .map(extract::Json)
         .map_err(|e| agentic_error_response(&payload, e))
@@ -154,9 +176,11 @@ pub async fn execute_sql(
 pub async fn execute_sql_query(
     extractor: WorkspaceManagerExtractor,
     path: Path<WorkspacePath>,
+    user: AuthenticatedUserExtractor,
+    permissions: WorkspacePermissions,
     payload: extract::Json<SQLParams>,
 ) -> Result<extract::Json<SemanticQueryResponse>, (StatusCode, extract::Json<ErrorResponse>)> {
-    execute_sql(extractor, path, payload).await
+    execute_sql(extractor, path, user, permissions, payload).await
 }
 
 // TODO: may want to rename this and the `reindex()` function below as we're doing more
@@ -166,9 +190,7 @@ pub async fn execute_sql_query(
 //         - caching enum values for each variable so they can be detected at query time
 pub async fn build_embeddings(
     WorkspaceManagerExtractor(workspace_manager): WorkspaceManagerExtractor,
-    Path(WorkspacePath {
-        workspace_id: _workspace_id,
-    }): Path<WorkspacePath>,
+    Path(WorkspacePath { workspace_id }): Path<WorkspacePath>,
 ) -> Result<extract::Json<EmbeddingsBuildResponse>, Response> {
     handle_omni_sync(&workspace_manager)
         .await
@@ -180,6 +202,7 @@ pub async fn build_embeddings(
     match reindex(ReindexInput {
         config: config_manager,
         secrets_manager: secret_manager,
+        workspace_id,
         drop_all_tables,
     })
     .await
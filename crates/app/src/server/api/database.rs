@@ -625,6 +625,7 @@ pub async fn test_database_connection(
         // Set up scope guard to clean up secrets after testing
         let secret_name = format!("{}_PASSWORD", temp_db_name.to_uppercase());
         let secrets_manager = workspace_manager.secrets_manager.clone();
+        let user_id = user.id;
         let _cleanup_guard = guard((), move |_| {
             let secret_name = secret_name.clone();
             tokio::task::block_in_place(|| {
@@ -632,7 +633,7 @@ pub async fn test_database_connection(
                     tracing::info!("Cleaning up temporary secret: {}", secret_name);
                     // Delete the temporary secret
                     secrets_manager
-                        .remove_secret(&secret_name)
+                        .remove_secret(&secret_name, user_id)
                         .await
                         .unwrap_or_else(|e| {
                             tracing::error!(
@@ -661,7 +662,7 @@ pub async fn test_database_connection(
             db_config.database_type,
             DatabaseType::Airhouse(_) | DatabaseType::AirhouseManaged(_)
         ) {
-            let ctx = OxyProjectContext::new(workspace_manager.clone());
+            let ctx = OxyProjectContext::new(workspace_manager.clone()).with_user(user_id);
             let outcome = async {
                 let connector = ctx
                     .build_connector_for(&db_config.name)
@@ -11,9 +11,10 @@ use axum::{
     response::{IntoResponse, Response},
     routing::get,
 };
+use oxy::budget::daily_spend;
 use oxy::execution_analytics::{
     AgentExecutionStats, ExecutionDetail, ExecutionListResponse, ExecutionSummary,
-    ExecutionTimeBucket,
+    ExecutionTimeBucket, SpendBucket, SpendSummary,
 };
 use serde::Deserialize;
 use utoipa::IntoParams;
@@ -87,6 +88,13 @@ pub struct ExecutionsQuery {
     pub status: Option<String>,
 }
 
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SpendQuery {
+    /// Number of days to look back (default: 7)
+    #[serde(default = "default_days")]
+    pub days: u32,
+}
+
 fn default_days() -> u32 {
     7
 }
@@ -346,6 +354,47 @@ pub async fn get_executions(
     }))
 }
 
+/// Get LLM and tool spend
+///
+/// Returns token, cost and tool-call totals per day, as recorded for budget
+/// enforcement when `budgets` is set in config.yml
+#[utoipa::path(
+    get,
+    path = "/api/{workspace_id}/execution-analytics/spend",
+    params(SpendQuery),
+    responses(
+        (status = 200, description = "Spend per day", body = SpendSummary),
+        (status = 500, description = "Query failed")
+    )
+)]
+pub async fn get_spend(
+    Path(workspace_id): Path<Uuid>,
+    Query(params): Query<SpendQuery>,
+) -> Result<Json<SpendSummary>, ExecutionAnalyticsError> {
+    let days = daily_spend(workspace_id, params.days)
+        .await
+        .map_err(|e| ExecutionAnalyticsError::QueryFailed(e.to_string()))?;
+
+    let daily: Vec<SpendBucket> = days
+        .into_iter()
+        .map(|day| SpendBucket {
+            date: day.day.to_string(),
+            input_tokens: day.input_tokens.max(0) as u64,
+            output_tokens: day.output_tokens.max(0) as u64,
+            cost_usd: day.cost_usd,
+            tool_calls: day.tool_calls.max(0) as u64,
+        })
+        .collect();
+
+    Ok(Json(SpendSummary {
+        input_tokens: daily.iter().map(|day| day.input_tokens).sum(),
+        output_tokens: daily.iter().map(|day| day.output_tokens).sum(),
+        cost_usd: daily.iter().map(|day| day.cost_usd).sum(),
+        tool_calls: daily.iter().map(|day| day.tool_calls).sum(),
+        daily,
+    }))
+}
+
 fn non_empty(s: String) -> Option<String> {
     if s.is_empty() { None } else { Some(s) }
 }
@@ -356,4 +405,5 @@ pub fn execution_analytics_routes() -> Router<AppState> {
         .route("/time-series", get(get_time_series))
         .route("/agents", get(get_agent_stats))
         .route("/executions", get(get_executions))
+        .route("/spend", get(get_spend))
 }
@@ -139,6 +139,7 @@ fn build_execution_context(workspace_manager: WorkspaceManager) -> ExecutionCont
         user_id: None,
         metric_context: None,
         data_app_file_path: None,
+        budget: None,
     }
 }
 
@@ -9,8 +9,8 @@
 //!   2. Fabricates an in-memory `workspaces::Model` at `LOCAL_WORKSPACE_ID`
 //!      (Uuid::nil()). No DB read.
 //!   3. Builds a `WorkspaceManager` from that path and attaches the full
-//!      extension set: the `Model`, `EffectiveWorkspaceRole(Owner)`, and the
-//!      `WorkspaceManager` itself.
+//!      extension set: the `Model`, `EffectiveWorkspaceRole(Owner)`,
+//!      unrestricted `WorkspacePermissions`, and the `WorkspaceManager` itself.
 //!
 //! Local mode has no orgs, so no `OrgMembership` extension is inserted. Any
 //! handler that calls `OrgMembershipExtractor` must not be mounted on the
@@ -18,6 +18,7 @@
 
 use crate::server::api::middlewares::workspace_context::EffectiveWorkspaceRole;
 use crate::server::serve_mode::LOCAL_WORKSPACE_ID;
+use crate::server::service::permissions::WorkspacePermissions;
 use crate::server::service::retrieval::EnumIndexManager;
 use crate::server::service::secret_manager::SecretManagerService;
 use axum::{
@@ -33,6 +34,7 @@ use oxy::adapters::secrets::SecretsManager;
 use oxy::adapters::workspace::builder::WorkspaceBuilder;
 use oxy::adapters::workspace::effective_workspace_path;
 use oxy::config::resolve_local_workspace_path;
+use oxy_semantic::AccessLevel;
 use uuid::Uuid;
 
 pub async fn local_context_middleware(
@@ -74,6 +76,7 @@ pub async fn local_context_middleware(
     request
         .extensions_mut()
         .insert(EffectiveWorkspaceRole(WorkspaceRole::Owner));
+    request.extensions_mut().insert(WorkspacePermissions::all());
 
     if resolved_path.is_some() {
         attach_workspace_manager(&mut request, &workspace).await?;
@@ -132,8 +135,9 @@ async fn attach_workspace_manager(
 
     builder = builder.try_with_intent_classifier().await;
 
+    // Local mode has a single user, who owns the project
     let workspace_manager = match builder.build().await {
-        Ok(m) => m,
+        Ok(m) => m.with_access_level(AccessLevel::Restricted),
         Err(e) => {
             tracing::warn!(
                 "local_context: failed to build workspace manager: {}, continuing",
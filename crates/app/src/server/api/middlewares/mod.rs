@@ -1,6 +1,7 @@
 pub mod local_context;
 pub mod org_context;
 pub mod oxy_owner_guard;
+pub mod permission_guard;
 pub mod role_guards;
 pub mod subscription_guard;
 pub mod timeout;
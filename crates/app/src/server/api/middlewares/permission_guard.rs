@@ -0,0 +1,311 @@
+//! Route-level permission checks for the workspace tree.
+//!
+//! `workspace_middleware` / `local_context_middleware` insert the caller's
+//! [`WorkspacePermissions`]; route groups in `router::workspace` then layer
+//! [`require_access`] with the [`Access`] they need. For permissions that
+//! can be limited to a resource, the resource comes from the route's path
+//! (`{pathb64}` or `{database_name}`); routes that name it in the body check
+//! it in the handler through the [`WorkspacePermissions`] extractor.
+
+use axum::extract::rejection::RawPathParamsRejection;
+use axum::extract::{FromRequestParts, RawPathParams, State};
+use axum::http::request::Parts;
+use axum::http::{Method, Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::Response;
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use entity::custom_roles::Permission;
+use std::future::Future;
+use std::sync::Arc;
+
+use crate::server::service::permissions::WorkspacePermissions;
+
+impl<S> FromRequestParts<S> for WorkspacePermissions
+where
+    S: Send + Sync,
+{
+    type Rejection = StatusCode;
+
+    fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> impl Future<Output = Result<Self, Self::Rejection>> + Send {
+        let result = parts
+            .extensions
+            .get::<WorkspacePermissions>()
+            .cloned()
+            .ok_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+        async move { result }
+    }
+}
+
+/// The permission a route group needs, by request method.
+#[derive(Clone, Copy, Debug)]
+pub struct Access {
+    read: Option<Permission>,
+    write: Option<Permission>,
+}
+
+impl Access {
+    /// Every method needs `permission`.
+    pub const fn all(permission: Permission) -> Self {
+        Self {
+            read: Some(permission),
+            write: Some(permission),
+        }
+    }
+
+    /// `GET` and `HEAD` need `read`, other methods `write`.
+    pub const fn split(read: Permission, write: Permission) -> Self {
+        Self {
+            read: Some(read),
+            write: Some(write),
+        }
+    }
+
+    /// Reads are open to every workspace member; other methods need
+    /// `permission`.
+    pub const fn writes(permission: Permission) -> Self {
+        Self {
+            read: None,
+            write: Some(permission),
+        }
+    }
+
+    fn for_method(&self, method: &Method) -> Option<Permission> {
+        if matches!(*method, Method::GET | Method::HEAD) {
+            self.read
+        } else {
+            self.write
+        }
+    }
+}
+
+pub async fn require_access(
+    State(access): State<Access>,
+    params: Result<RawPathParams, RawPathParamsRejection>,
+    request: Request<axum::body::Body>,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let Some(permission) = access.for_method(request.method()) else {
+        return Ok(next.run(request).await);
+    };
+    let Some(permissions) = request.extensions().get::<WorkspacePermissions>() else {
+        tracing::error!("require_access: no WorkspacePermissions in request extensions");
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    };
+
+    let resource = match &params {
+        Ok(params) => path_resource(permission, params)?,
+        Err(_) => None,
+    };
+    if !permissions.allows(permission, resource.as_deref()) {
+        tracing::info!(
+            "Denied {} {}: missing permission {}{}",
+            request.method(),
+            request.uri().path(),
+            permission,
+            resource.map(|r| format!(" on {r}")).unwrap_or_default()
+        );
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(next.run(request).await)
+}
+
+/// The resource a scoped permission applies to, from the route's path.
+fn path_resource(
+    permission: Permission,
+    params: &RawPathParams,
+) -> Result<Option<String>, StatusCode> {
+    if !permission.is_scoped() {
+        return Ok(None);
+    }
+    for (key, value) in params.iter() {
+        match (permission, key) {
+            (Permission::QueryDatabases, "database_name") => return Ok(Some(value.to_string())),
+            (Permission::RunAgents | Permission::RunWorkflows | Permission::RunApps, "pathb64") => {
+                return decode_path(value).map(Some);
+            }
+            _ => {}
+        }
+    }
+    Ok(None)
+}
+
+fn decode_path(pathb64: &str) -> Result<String, StatusCode> {
+    BASE64_STANDARD
+        .decode(pathb64)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .ok_or(StatusCode::BAD_REQUEST)
+}
+
+/// `403` unless the caller may use `permission` on `resource`; for handlers
+/// whose resource is in the request body.
+pub fn ensure_allowed(
+    permissions: &WorkspacePermissions,
+    permission: Permission,
+    resource: &str,
+) -> Result<(), StatusCode> {
+    if permissions.allows(permission, Some(resource)) {
+        Ok(())
+    } else {
+        tracing::info!("Denied: missing permission {permission} on {resource}");
+        Err(StatusCode::FORBIDDEN)
+    }
+}
+
+impl agentic_http::RunAccess for WorkspacePermissions {
+    fn can_run_agent(&self, agent_id: &str) -> bool {
+        self.allows(Permission::RunAgents, Some(agent_id))
+    }
+
+    fn can_edit_files(&self) -> bool {
+        self.allows(Permission::EditFiles, None)
+    }
+}
+
+/// Hand the caller's permissions to the analytics routes, which check the
+/// agent named in the request body.
+pub async fn provide_run_access(
+    mut request: Request<axum::body::Body>,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let Some(permissions) = request.extensions().get::<WorkspacePermissions>().cloned() else {
+        tracing::error!("provide_run_access: no WorkspacePermissions in request extensions");
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    };
+    let access: Arc<dyn agentic_http::RunAccess> = Arc::new(permissions);
+    request.extensions_mut().insert(access);
+    Ok(next.run(request).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::body::Body;
+    use axum::middleware;
+    use axum::routing::get;
+    use entity::workspace_members::WorkspaceRole;
+    use tower::ServiceExt;
+
+    async fn status(
+        permissions: Option<WorkspacePermissions>,
+        access: Access,
+        method: Method,
+        uri: &str,
+    ) -> StatusCode {
+        let router = Router::new()
+            .route("/agents/{pathb64}", get(|| async {}).post(|| async {}))
+            .route("/databases/{database_name}/schema", get(|| async {}))
+            .route_layer(middleware::from_fn_with_state(access, require_access));
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+        if let Some(permissions) = permissions {
+            request.extensions_mut().insert(permissions);
+        }
+        router.oneshot(request).await.unwrap().status()
+    }
+
+    fn limited(entries: &[&str]) -> WorkspacePermissions {
+        WorkspacePermissions::for_role(&WorkspaceRole::Member)
+            .limited_by(entries.iter().map(|e| e.parse().unwrap()).collect())
+    }
+
+    #[tokio::test]
+    async fn test_scoped_agent_path() {
+        // "YWdlbnRzL2EuYWdlbnQueW1s" is "agents/a.agent.yml"
+        let uri = "/agents/YWdlbnRzL2EuYWdlbnQueW1s";
+        let access = Access::all(Permission::RunAgents);
+        let allowed = limited(&["agents.run:agents/a.agent.yml"]);
+        let other = limited(&["agents.run:agents/b.agent.yml"]);
+        assert_eq!(
+            status(Some(allowed), access, Method::POST, uri).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(Some(other), access, Method::POST, uri).await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            status(
+                Some(limited(&["agents.run"])),
+                access,
+                Method::POST,
+                "/agents/not-base64!"
+            )
+            .await,
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scoped_database_name() {
+        let access = Access::split(Permission::QueryDatabases, Permission::ManageDatabases);
+        let contractor = limited(&["databases.query:sales"]);
+        assert_eq!(
+            status(
+                Some(contractor.clone()),
+                access,
+                Method::GET,
+                "/databases/sales/schema"
+            )
+            .await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(
+                Some(contractor),
+                access,
+                Method::GET,
+                "/databases/finance/schema"
+            )
+            .await,
+            StatusCode::FORBIDDEN
+        );
+    }
+
+    #[tokio::test]
+    async fn test_writes_access_leaves_reads_open() {
+        let access = Access::writes(Permission::EditFiles);
+        let uri = "/agents/YWdlbnRzL2EuYWdlbnQueW1s";
+        let viewer = WorkspacePermissions::for_role(&WorkspaceRole::Viewer);
+        assert_eq!(
+            status(Some(viewer.clone()), access, Method::GET, uri).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(Some(viewer), access, Method::POST, uri).await,
+            StatusCode::FORBIDDEN
+        );
+    }
+
+    #[test]
+    fn test_run_access_checks_body_agent() {
+        use agentic_http::RunAccess;
+
+        let scoped = limited(&["agents.run:agents/a.agent.yml"]);
+        assert!(scoped.can_run_agent("agents/a.agent.yml"));
+        assert!(!scoped.can_run_agent("agents/b.agent.yml"));
+        assert!(!scoped.can_edit_files());
+
+        let member = WorkspacePermissions::for_role(&WorkspaceRole::Member);
+        assert!(member.can_run_agent("agents/b.agent.yml"));
+        assert!(member.can_edit_files());
+    }
+
+    #[tokio::test]
+    async fn test_missing_permissions_is_a_wiring_error() {
+        let access = Access::all(Permission::RunAgents);
+        assert_eq!(
+            status(None, access, Method::GET, "/agents/YQ==").await,
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::server::router::AppState;
+use crate::server::service::permissions::WorkspacePermissions;
 use crate::server::service::retrieval::EnumIndexManager;
 use crate::server::service::secret_manager::SecretManagerService;
 use axum::extract::{FromRequestParts, Path};
@@ -16,7 +17,11 @@ use oxy::adapters::workspace::builder::WorkspaceBuilder;
 use oxy::adapters::workspace::effective_workspace_path;
 use oxy::adapters::workspace::manager::WorkspaceManager;
 use oxy::database::client::establish_connection;
+use oxy_auth::constants::DEFAULT_API_KEY_HEADER;
 use oxy_auth::extractor::AuthenticatedUserExtractor;
+use oxy_auth::{ApiKeyConfig, ApiKeyService, ValidatedApiKey};
+use oxy_semantic::AccessLevel;
+use oxy_shared::errors::OxyError;
 use sea_orm::EntityTrait;
 use std::future::Future;
 use uuid::Uuid;
@@ -140,6 +145,7 @@ pub async fn workspace_middleware(
                 query.branch.as_deref(),
                 workspace_id,
                 branch_id,
+                user.id,
                 &mut request,
             )
             .await?;
@@ -202,9 +208,25 @@ async fn authorize_workspace(
     let (org_membership, effective_role) =
         resolve_effective_role(&db, workspace_id, org_id, user_id).await?;
 
+    let api_key_role_id = api_key_role_id(&db, user_id, request).await?;
+    let member = (effective_role != WorkspaceRole::Owner).then_some(user_id);
+    let permissions = WorkspacePermissions::for_role(&effective_role)
+        .narrowed(&db, workspace_id, member, api_key_role_id)
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                "Failed to resolve permissions (workspace={}, user={}): {}",
+                workspace_id,
+                user_id,
+                e
+            );
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
     request
         .extensions_mut()
         .insert(EffectiveWorkspaceRole(effective_role));
+    request.extensions_mut().insert(permissions);
     request.extensions_mut().insert(org_membership);
 
     if workspace_row.path.is_none() {
@@ -284,6 +306,48 @@ async fn resolve_effective_role(
     Ok((org_membership, effective_role))
 }
 
+/// Custom role of the API key the caller authenticated with, if any. The MCP
+/// stack leaves the validated key in extensions; `auth_middleware` accepts
+/// `X-API-Key` without exposing which key it was, so it is looked up again
+/// with the same checks: a revoked or expired key carries no role.
+async fn api_key_role_id(
+    db: &sea_orm::DatabaseConnection,
+    user_id: Uuid,
+    request: &Request<axum::body::Body>,
+) -> Result<Option<Uuid>, StatusCode> {
+    if let Some(api_key) = request.extensions().get::<ValidatedApiKey>() {
+        return Ok(api_key.custom_role_id);
+    }
+    let Some(key) = request
+        .headers()
+        .get(DEFAULT_API_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+    else {
+        return Ok(None);
+    };
+
+    match ApiKeyService::validate_api_key(db, key, &ApiKeyConfig::default()).await {
+        Ok(api_key) if api_key.user_id == user_id => Ok(api_key.custom_role_id),
+        Err(OxyError::DBError(e)) => {
+            tracing::error!("Failed to look up API key role: {}", e);
+            Err(StatusCode::SERVICE_UNAVAILABLE)
+        }
+        // The caller signed in some other way; the header is not theirs.
+        _ => Ok(None),
+    }
+}
+
+/// Clearance for column-level access rules on semantic fields.
+fn semantic_access_level(role: &WorkspaceRole) -> AccessLevel {
+    match role {
+        WorkspaceRole::Owner | WorkspaceRole::Admin => AccessLevel::Restricted,
+        WorkspaceRole::Member => AccessLevel::Internal,
+        WorkspaceRole::Viewer => AccessLevel::Public,
+    }
+}
+
 /// Best-effort: builds the `WorkspaceManager` (with secrets, runs, intent classifier)
 /// and inserts it into request extensions. The only fatal outcome is an invalid
 /// branch query parameter, which yields BAD_REQUEST.
@@ -292,6 +356,7 @@ async fn try_attach_workspace_manager(
     branch_name: Option<&str>,
     workspace_id: Uuid,
     branch_id: Uuid,
+    user_id: Uuid,
     request: &mut Request<axum::body::Body>,
 ) -> Result<(), StatusCode> {
     // Branch name is validated inside `effective_workspace_path`. The helper
@@ -344,8 +409,15 @@ async fn try_attach_workspace_manager(
 
     builder = builder.try_with_intent_classifier().await;
 
+    // A request without a resolved role gets the lowest clearance
+    let access_level = request
+        .extensions()
+        .get::<EffectiveWorkspaceRole>()
+        .map_or(AccessLevel::Public, |EffectiveWorkspaceRole(role)| {
+            semantic_access_level(role)
+        });
     let workspace_manager = match builder.build().await {
-        Ok(m) => m,
+        Ok(m) => m.with_access_level(access_level),
         Err(e) => {
             tracing::warn!(
                 "Failed to build workspace manager for workspace {}: {}, continuing without it",
@@ -368,9 +440,9 @@ async fn try_attach_workspace_manager(
         ),
     }
 
-    let project_ctx = std::sync::Arc::new(crate::agentic_wiring::OxyProjectContext::new(
-        workspace_manager.clone(),
-    ));
+    let project_ctx = std::sync::Arc::new(
+        crate::agentic_wiring::OxyProjectContext::new(workspace_manager.clone()).with_user(user_id),
+    );
     let platform: std::sync::Arc<dyn agentic_pipeline::platform::PlatformContext> =
         project_ctx.clone();
     let bridges = crate::agentic_wiring::build_builder_bridges(project_ctx);
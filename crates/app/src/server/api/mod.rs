@@ -3,9 +3,11 @@ pub mod agent;
 pub mod api_keys;
 pub mod app;
 pub mod artifacts;
+pub mod audit_logs;
 pub mod auth;
 pub mod billing;
 pub mod chart;
+pub mod custom_roles;
 pub mod data;
 pub mod data_repo;
 pub mod database;
@@ -24,8 +26,10 @@ pub mod onboarding;
 pub mod organizations;
 pub mod result_files;
 pub mod run;
+pub mod scim;
 pub mod secrets;
 pub mod semantic;
+pub mod sso;
 pub mod task;
 pub mod test_file;
 pub mod test_project_run;
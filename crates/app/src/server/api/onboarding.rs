@@ -5,6 +5,7 @@ use axum::{
 };
 use oxy::adapters::secrets::SecretsManager;
 use oxy::adapters::workspace::{resolve_workspace_path, workspace_root_path};
+use oxy::audit::{self, AuditAction, AuditEvent};
 use oxy::config::ConfigBuilder;
 use oxy::github::{GitHubClient, default_git_client, github_token_for_namespace};
 use oxy::service::retrieval::{ReindexInput, reindex};
@@ -226,6 +227,15 @@ async fn register_project(
         )
     })?;
     tracing::info!("Registered workspace '{}' at '{}'", name, path_str);
+    audit::record(
+        AuditEvent::new(AuditAction::WorkspaceCreated)
+            .org(org_id)
+            .workspace(workspace_id)
+            .actor(created_by)
+            .target("workspace", workspace_id)
+            .meta("name", name),
+    )
+    .await;
     Ok(workspace_id)
 }
 
@@ -356,6 +366,7 @@ pub async fn setup_demo(
             reindex(ReindexInput {
                 config,
                 secrets_manager,
+                workspace_id,
                 drop_all_tables: true,
             })
             .await
@@ -967,6 +978,19 @@ fn collect_warehouse_vars(database: &oxy::config::model::Database) -> Vec<Github
             push_opt(&mut out, "password", &m.password_var, m.password.is_some());
             push_opt(&mut out, "database", &m.database_var, m.database.is_some());
         }
+        DatabaseType::Mssql(m) => {
+            push_opt(&mut out, "host", &m.host_var, m.host.is_some());
+            push_opt(&mut out, "port", &m.port_var, m.port.is_some());
+            push_opt(&mut out, "user", &m.user_var, m.user.is_some());
+            push_opt(&mut out, "password", &m.password_var, m.password.is_some());
+            push_opt(&mut out, "database", &m.database_var, m.database.is_some());
+        }
+        DatabaseType::Trino(t) => {
+            push_opt(&mut out, "host", &t.host_var, t.host.is_some());
+            push_opt(&mut out, "port", &t.port_var, t.port.is_some());
+            push_opt(&mut out, "user", &t.user_var, t.user.is_some());
+            push_opt(&mut out, "password", &t.password_var, t.password.is_some());
+        }
         DatabaseType::ClickHouse(c) => {
             push_opt(&mut out, "host", &c.host_var, c.host.is_some());
             push_opt(&mut out, "user", &c.user_var, c.user.is_some());
@@ -987,7 +1011,7 @@ fn collect_warehouse_vars(database: &oxy::config::model::Database) -> Vec<Github
         DatabaseType::DOMO(d) => {
             push_req(&mut out, "developer_token", &d.developer_token_var);
         }
-        DatabaseType::DuckDB(_) => {
+        DatabaseType::DuckDB(_) | DatabaseType::Sqlite(_) => {
             // No credentials.
         }
         DatabaseType::AirhouseManaged(_) => {
@@ -1042,7 +1066,7 @@ pub struct OnboardingResetResponse {
 pub async fn reset_onboarding(
     WorkspaceManagerExtractor(workspace_manager): WorkspaceManagerExtractor,
     Path(WorkspacePath { workspace_id }): Path<WorkspacePath>,
-    AuthenticatedUserExtractor(_user): AuthenticatedUserExtractor,
+    AuthenticatedUserExtractor(user): AuthenticatedUserExtractor,
     Json(request): Json<OnboardingResetRequest>,
 ) -> Result<Json<OnboardingResetResponse>, StatusCode> {
     let db = oxy::database::client::establish_connection()
@@ -1149,7 +1173,7 @@ pub async fn reset_onboarding(
     all_secret_names.dedup();
 
     for name in all_secret_names {
-        match secret_manager.delete_secret(&db, &name).await {
+        match secret_manager.delete_secret(&db, &name, user.id).await {
             Ok(()) => response.secrets_deleted.push(name),
             Err(OxyError::SecretManager(msg)) if msg.to_lowercase().contains("not found") => {
                 // Already absent — silently skip.
@@ -14,6 +14,7 @@ use entity::prelude::*;
 use entity::workspaces;
 use handlebars::Handlebars;
 use once_cell::sync::Lazy;
+use oxy::audit::{self, AuditAction, AuditEvent};
 use oxy::database::client::establish_connection;
 use oxy::database::filters::UserQueryFilterExt;
 use oxy_auth::extractor::AuthenticatedUserExtractor;
@@ -583,6 +584,7 @@ pub async fn update_member_role(
         }
     }
 
+    let previous_role = target.role.as_str();
     let mut active: org_members::ActiveModel = target.into();
     active.role = ActiveValue::Set(new_role);
     active.updated_at = ActiveValue::Set(Utc::now().fixed_offset());
@@ -591,6 +593,21 @@ pub async fn update_member_role(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
+    audit::record_in(
+        &txn,
+        AuditEvent::new(AuditAction::MemberRoleChanged)
+            .org(ctx.org.id)
+            .actor(ctx.membership.user_id)
+            .target("user", target_user_id)
+            .meta("from", previous_role)
+            .meta("to", updated.role.as_str()),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("{e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
     let user = Users::find_by_id(target_user_id)
         .one(&txn)
         .await
@@ -706,12 +723,27 @@ pub async fn remove_member(
         }
     }
 
+    let removed_role = target.role.as_str();
     let active: org_members::ActiveModel = target.into();
     active.delete(&txn).await.map_err(|e| {
         tracing::error!("Failed to remove member: {e}");
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
+    audit::record_in(
+        &txn,
+        AuditEvent::new(AuditAction::MemberRemoved)
+            .org(ctx.org.id)
+            .actor(ctx.membership.user_id)
+            .target("user", target_user_id)
+            .meta("role", removed_role),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("{e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
     txn.commit().await.map_err(|e| {
         tracing::error!("Failed to commit transaction: {e}");
         StatusCode::INTERNAL_SERVER_ERROR
@@ -816,6 +848,7 @@ pub async fn create_invitation(
         tracing::error!("Failed to insert invitation: {e}");
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
+    audit::record(invitation_created_event(&invitation)).await;
 
     // Fire off the invitation email in the background. Failure to send does not
     // block the response — the DB row + returned token remain the source of truth.
@@ -973,6 +1006,12 @@ pub async fn create_bulk_invitations(
             tracing::error!("Failed to insert invitation: {e}");
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
+        audit::record_in(&txn, invitation_created_event(&invitation))
+            .await
+            .map_err(|e| {
+                tracing::error!("{e}");
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
         inserted.push(invitation);
     }
 
@@ -1095,11 +1134,20 @@ pub async fn revoke_invitation(
         })?
         .ok_or(StatusCode::NOT_FOUND)?;
 
+    let email = invitation.email.clone();
     let active: org_invitations::ActiveModel = invitation.into();
     active.delete(&db).await.map_err(|e| {
         tracing::error!("Failed to delete invitation: {e}");
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
+    audit::record(
+        AuditEvent::new(AuditAction::InvitationRevoked)
+            .org(ctx.org.id)
+            .actor(ctx.membership.user_id)
+            .target("invitation", invitation_id)
+            .meta("email", email),
+    )
+    .await;
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -1264,6 +1312,21 @@ pub async fn accept_invitation(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
+    audit::record_in(
+        &txn,
+        AuditEvent::new(AuditAction::MemberAdded)
+            .org(invitation.org_id)
+            .actor(user.id)
+            .target("user", user.id)
+            .meta("role", invitation.role.as_str())
+            .meta("invitation_id", invitation.id.to_string()),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("{e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
     txn.commit().await.map_err(|e| {
         tracing::error!("Failed to commit transaction: {e}");
         StatusCode::INTERNAL_SERVER_ERROR
@@ -1294,6 +1357,15 @@ pub async fn accept_invitation(
     Ok(Json(org_response(&org, &invitation.role)))
 }
 
+fn invitation_created_event(invitation: &org_invitations::Model) -> AuditEvent {
+    AuditEvent::new(AuditAction::InvitationCreated)
+        .org(invitation.org_id)
+        .actor(invitation.invited_by)
+        .target("invitation", invitation.id)
+        .meta("email", invitation.email.clone())
+        .meta("role", invitation.role.as_str())
+}
+
 // ---------------------------------------------------------------------------
 // Invitation email
 // ---------------------------------------------------------------------------
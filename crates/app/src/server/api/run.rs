@@ -12,18 +12,15 @@ use uuid::Uuid;
 
 use crate::server::api::middlewares::role_guards::WorkspaceAdmin;
 use crate::server::api::middlewares::workspace_context::WorkspaceManagerExtractor;
-use crate::server::service::block::GroupBlockHandler;
+use crate::server::service::agent::ExecutionSource;
 use crate::server::service::statics::BROADCASTER;
 use crate::server::service::task_manager::TASK_MANAGER;
 use crate::server::service::types::pagination::{Paginated, Pagination};
 use crate::server::service::types::run::{RunDetails, RunInfo, RunStatus};
-use crate::server::service::workflow::run_workflow_v2;
+use crate::server::service::workflow::spawn_workflow_run;
 use oxy::checkpoint::types::RetryStrategy;
-use oxy::execute::types::OutputContainer;
-use oxy::execute::writer::Handler;
 use oxy::utils::{create_sse_broadcast, file_path_to_source_id};
 use oxy_auth::extractor::AuthenticatedUserExtractor;
-use oxy_shared::errors::OxyError;
 
 #[derive(serde::Deserialize, ToSchema)]
 pub struct PaginationQuery {
@@ -189,102 +186,23 @@ pub async fn create_workflow_run(
     let run_info = root_run_info.unwrap_or(source_run_info);
 
     tracing::debug!("Creating new run {:?} with {:?}", run_info, replay_id);
-    let task_id = run_info.task_id()?;
-    let topic_ref = BROADCASTER.create_topic(&task_id).await.map_err(|err| {
-        tracing::error!("Failed to create topic for task ID {task_id}: {err}");
+    let user_id = user.id.to_string();
+    spawn_workflow_run(
+        workspace_manager,
+        workflow_config,
+        &run_info,
+        replay_id,
+        ExecutionSource::WebApi {
+            thread_id: run_info.task_id()?,
+            user_id,
+        },
+        Some(user.id),
+    )
+    .await
+    .map_err(|err| {
+        tracing::error!("Failed to start run {:?}: {err}", run_info);
         StatusCode::BAD_REQUEST
     })?;
-    let run_index = run_info.run_index.ok_or(StatusCode::BAD_REQUEST)?;
-    let topic_id = task_id.clone();
-    let cb_source_id = run_info.source_id.clone();
-    let cb_user_id = user.id; // Clone user.id for the callback
-    let callback_fn = async move |output: Option<OutputContainer>| -> Result<(), OxyError> {
-        // Handle the completion of the run and broadcast events
-        if let Some(closed) = BROADCASTER.remove_topic(&topic_id).await {
-            let last_task_ref = workflow_config.tasks.last().map(|t| t.name.clone());
-            if let Some(output) = output
-                && let Some(last_task_name) = last_task_ref
-            {
-                let outputs = output.find_ref(&last_task_name)?;
-                let last_output = outputs.first();
-                if let Some(last_output) = last_output {
-                    runs_manager
-                        .update_run_output(
-                            &cb_source_id,
-                            run_index,
-                            last_task_name,
-                            last_output.to_json()?,
-                        )
-                        .await?;
-                }
-            };
-
-            let mut group_handler = GroupBlockHandler::new();
-            for event in closed.items {
-                group_handler.handle_event(event).await?;
-            }
-            let groups = group_handler.collect();
-            for group in groups {
-                runs_manager.upsert_run(group, Some(cb_user_id)).await?;
-            }
-            drop(closed.sender); // Drop the sender to close the channel
-        } else {
-            tracing::warn!(
-                "Failed to remove topic: {} - topic does not exist or was already removed",
-                topic_id
-            );
-        }
-        Ok(())
-    };
-    let source_id = run_info.source_id.clone();
-    let user_id = user.id.to_string();
-
-    TASK_MANAGER
-        .spawn(task_id.clone(), async move |cancellation_token| {
-            let run_fut = {
-                let converted_run_index = run_index
-                    .try_into()
-                    .map_err(|e| tracing::error!("Failed to convert run_index to u32: {}", e))
-                    .unwrap_or(0); // Default to 0 if conversion fails
-                run_workflow_v2(
-                    workspace_manager.clone(),
-                    source_id,
-                    topic_ref,
-                    RetryStrategy::Retry {
-                        replay_id,
-                        run_index: converted_run_index,
-                    },
-                    None,
-                    None,
-                    Some(crate::service::agent::ExecutionSource::WebApi {
-                        thread_id: task_id.clone(),
-                        user_id,
-                    }),
-                    Some(user.id),
-                )
-            };
-            tokio::select! {
-                _ = cancellation_token.cancelled() => {
-                    tracing::debug!("Task {task_id} was cancelled");
-                    if let Err(err) = callback_fn(None).await {
-                        tracing::error!("Failed to handle callback for task {task_id}: {err}");
-                    }
-                }
-                res = run_fut => {
-                    let output = match res {
-                        Ok(value) => Some(value),
-                        Err(err) => {
-                            tracing::error!("Task {task_id} failed: {err}");
-                            None
-                        },
-                    };
-                    if let Err(err) = callback_fn(output).await {
-                        tracing::error!("Failed to handle callback for task {task_id}: {err}");
-                    }
-                }
-            }
-        })
-        .await;
 
     Ok(extract::Json(CreateRunResponse { run: run_info }))
 }
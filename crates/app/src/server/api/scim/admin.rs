@@ -0,0 +1,348 @@
+//! Org-admin endpoints for SCIM: bearer tokens for the identity provider and
+//! the group-to-role mappings applied on sync.
+
+use std::str::FromStr;
+
+use axum::extract::{Json, Path};
+use axum::http::StatusCode;
+use chrono::Utc;
+use entity::org_members::OrgRole;
+use entity::prelude::{ScimRoleMappings, ScimTokens, ScimUsers, Workspaces};
+use entity::workspace_members::WorkspaceRole;
+use entity::{scim_role_mappings, scim_tokens, scim_users, workspaces};
+use oxy::database::client::establish_connection;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::sync::{spawn_seat_sync, sync_user};
+use super::{generate_token, hash_token};
+use crate::api::middlewares::role_guards::OrgAdmin;
+
+// ---------------------------------------------------------------------------
+// Request / Response types
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+pub struct CreateScimTokenRequest {
+    pub name: String,
+}
+
+#[derive(Serialize)]
+pub struct ScimTokenResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct CreatedScimTokenResponse {
+    pub id: Uuid,
+    pub name: String,
+    /// Only returned once, on creation.
+    pub token: String,
+    pub created_at: String,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct RoleMapping {
+    /// SCIM group display name.
+    pub group: String,
+    /// When set, `role` is a workspace role in this workspace; otherwise an
+    /// org role.
+    #[serde(default)]
+    pub workspace_id: Option<Uuid>,
+    pub role: String,
+}
+
+impl From<scim_role_mappings::Model> for RoleMapping {
+    fn from(mapping: scim_role_mappings::Model) -> Self {
+        Self {
+            group: mapping.group_name,
+            workspace_id: mapping.workspace_id,
+            role: mapping.role,
+        }
+    }
+}
+
+impl From<scim_tokens::Model> for ScimTokenResponse {
+    fn from(token: scim_tokens::Model) -> Self {
+        Self {
+            id: token.id,
+            name: token.name,
+            created_at: token.created_at.to_rfc3339(),
+            last_used_at: token.last_used_at.map(|at| at.to_rfc3339()),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tokens
+// ---------------------------------------------------------------------------
+
+/// GET /orgs/:org_id/scim/tokens
+pub async fn list_tokens(
+    OrgAdmin(ctx): OrgAdmin,
+) -> Result<Json<Vec<ScimTokenResponse>>, StatusCode> {
+    let db = establish_connection().await.map_err(|e| {
+        tracing::error!("DB connection error: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let tokens = ScimTokens::find()
+        .filter(scim_tokens::Column::OrgId.eq(ctx.org.id))
+        .order_by_desc(scim_tokens::Column::CreatedAt)
+        .all(&db)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to query SCIM tokens: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(tokens.into_iter().map(Into::into).collect()))
+}
+
+/// POST /orgs/:org_id/scim/tokens
+pub async fn create_token(
+    OrgAdmin(ctx): OrgAdmin,
+    Json(req): Json<CreateScimTokenRequest>,
+) -> Result<Json<CreatedScimTokenResponse>, StatusCode> {
+    let name = req.name.trim();
+    if name.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let db = establish_connection().await.map_err(|e| {
+        tracing::error!("DB connection error: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let token = generate_token();
+    let created = scim_tokens::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        org_id: ActiveValue::Set(ctx.org.id),
+        name: ActiveValue::Set(name.to_string()),
+        token_hash: ActiveValue::Set(hash_token(&token)),
+        created_by: ActiveValue::Set(ctx.membership.user_id),
+        last_used_at: ActiveValue::Set(None),
+        created_at: ActiveValue::Set(Utc::now().into()),
+    }
+    .insert(&db)
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to create SCIM token: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(CreatedScimTokenResponse {
+        id: created.id,
+        name: created.name,
+        token,
+        created_at: created.created_at.to_rfc3339(),
+    }))
+}
+
+/// DELETE /orgs/:org_id/scim/tokens/:token_id
+pub async fn delete_token(
+    OrgAdmin(ctx): OrgAdmin,
+    Path((_org_id, token_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, StatusCode> {
+    let db = establish_connection().await.map_err(|e| {
+        tracing::error!("DB connection error: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let deleted = ScimTokens::delete_many()
+        .filter(scim_tokens::Column::Id.eq(token_id))
+        .filter(scim_tokens::Column::OrgId.eq(ctx.org.id))
+        .exec(&db)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to delete SCIM token: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    if deleted.rows_affected == 0 {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ---------------------------------------------------------------------------
+// Role mappings
+// ---------------------------------------------------------------------------
+
+/// GET /orgs/:org_id/scim/mappings
+pub async fn list_mappings(OrgAdmin(ctx): OrgAdmin) -> Result<Json<Vec<RoleMapping>>, StatusCode> {
+    let db = establish_connection().await.map_err(|e| {
+        tracing::error!("DB connection error: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let mappings = ScimRoleMappings::find()
+        .filter(scim_role_mappings::Column::OrgId.eq(ctx.org.id))
+        .order_by_asc(scim_role_mappings::Column::CreatedAt)
+        .all(&db)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to query SCIM role mappings: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(mappings.into_iter().map(Into::into).collect()))
+}
+
+/// Checks a mapping's role against what `caller` may grant: org mappings
+/// can't grant Owner, and only Owners may map to the workspace Owner role.
+fn validate_mapping(mapping: &RoleMapping, caller: &OrgRole) -> Result<(), StatusCode> {
+    if mapping.group.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    match mapping.workspace_id {
+        None => match OrgRole::from_str(&mapping.role) {
+            Ok(OrgRole::Owner) | Err(_) => Err(StatusCode::BAD_REQUEST),
+            Ok(_) => Ok(()),
+        },
+        Some(_) => match WorkspaceRole::from_str(&mapping.role) {
+            Ok(WorkspaceRole::Owner) if *caller != OrgRole::Owner => Err(StatusCode::FORBIDDEN),
+            Ok(_) => Ok(()),
+            Err(_) => Err(StatusCode::BAD_REQUEST),
+        },
+    }
+}
+
+/// PUT /orgs/:org_id/scim/mappings
+///
+/// Replaces all mappings and resyncs every active SCIM user of the org.
+pub async fn replace_mappings(
+    OrgAdmin(ctx): OrgAdmin,
+    Json(req): Json<Vec<RoleMapping>>,
+) -> Result<Json<Vec<RoleMapping>>, StatusCode> {
+    for mapping in &req {
+        validate_mapping(mapping, &ctx.membership.role)?;
+    }
+
+    let db = establish_connection().await.map_err(|e| {
+        tracing::error!("DB connection error: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let mut workspace_ids: Vec<Uuid> = req.iter().filter_map(|m| m.workspace_id).collect();
+    workspace_ids.sort();
+    workspace_ids.dedup();
+    if !workspace_ids.is_empty() {
+        let in_org = Workspaces::find()
+            .filter(workspaces::Column::OrgId.eq(ctx.org.id))
+            .filter(workspaces::Column::Id.is_in(workspace_ids.clone()))
+            .count(&db)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to query workspaces: {e}");
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        if in_org != workspace_ids.len() as u64 {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+
+    let txn = db.begin().await.map_err(|e| {
+        tracing::error!("Failed to start transaction: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    ScimRoleMappings::delete_many()
+        .filter(scim_role_mappings::Column::OrgId.eq(ctx.org.id))
+        .exec(&txn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to clear SCIM role mappings: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let now = Utc::now();
+    let mut saved = Vec::with_capacity(req.len());
+    for mapping in req {
+        let model = scim_role_mappings::ActiveModel {
+            id: ActiveValue::Set(Uuid::new_v4()),
+            org_id: ActiveValue::Set(ctx.org.id),
+            group_name: ActiveValue::Set(mapping.group.trim().to_string()),
+            workspace_id: ActiveValue::Set(mapping.workspace_id),
+            role: ActiveValue::Set(mapping.role),
+            created_at: ActiveValue::Set(now.into()),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to save SCIM role mapping: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        saved.push(RoleMapping::from(model));
+    }
+
+    let user_ids: Vec<Uuid> = ScimUsers::find()
+        .filter(scim_users::Column::OrgId.eq(ctx.org.id))
+        .filter(scim_users::Column::Active.eq(true))
+        .select_only()
+        .column(scim_users::Column::UserId)
+        .into_tuple()
+        .all(&txn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to list SCIM users: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    for user_id in user_ids {
+        sync_user(&txn, ctx.org.id, user_id)
+            .await
+            .map_err(|e| e.status)?;
+    }
+
+    txn.commit().await.map_err(|e| {
+        tracing::error!("Failed to commit transaction: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    spawn_seat_sync(ctx.org.id).await;
+
+    Ok(Json(saved))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping(workspace_id: Option<Uuid>, role: &str) -> RoleMapping {
+        RoleMapping {
+            group: "Data".to_string(),
+            workspace_id,
+            role: role.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_org_mappings_cannot_grant_owner() {
+        assert_eq!(
+            validate_mapping(&mapping(None, "owner"), &OrgRole::Owner),
+            Err(StatusCode::BAD_REQUEST)
+        );
+        assert!(validate_mapping(&mapping(None, "admin"), &OrgRole::Admin).is_ok());
+        assert_eq!(
+            validate_mapping(&mapping(None, "viewer"), &OrgRole::Owner),
+            Err(StatusCode::BAD_REQUEST)
+        );
+    }
+
+    #[test]
+    fn test_only_owners_map_workspace_owner() {
+        let workspace = Some(Uuid::new_v4());
+        assert_eq!(
+            validate_mapping(&mapping(workspace, "owner"), &OrgRole::Admin),
+            Err(StatusCode::FORBIDDEN)
+        );
+        assert!(validate_mapping(&mapping(workspace, "owner"), &OrgRole::Owner).is_ok());
+        assert!(validate_mapping(&mapping(workspace, "viewer"), &OrgRole::Member).is_ok());
+    }
+}
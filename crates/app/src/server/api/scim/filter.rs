@@ -0,0 +1,69 @@
+//! The subset of SCIM filters identity providers send in practice:
+//! a single `attribute eq "value"` comparison, used to look up a resource
+//! before creating it.
+
+use super::types::ScimError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EqFilter {
+    /// Attribute path, lowercased since SCIM attribute names are
+    /// case-insensitive.
+    pub attribute: String,
+    pub value: String,
+}
+
+pub fn parse_eq_filter(filter: &str) -> Result<EqFilter, ScimError> {
+    let invalid = || {
+        ScimError::bad_request(
+            "invalidFilter",
+            format!("Unsupported filter '{filter}'; only 'attribute eq \"value\"' is supported"),
+        )
+    };
+    let filter = filter.trim();
+    let (attribute, rest) = filter.split_once(char::is_whitespace).ok_or_else(invalid)?;
+    let (operator, value) = rest
+        .trim_start()
+        .split_once(char::is_whitespace)
+        .ok_or_else(invalid)?;
+    if !operator.eq_ignore_ascii_case("eq") {
+        return Err(invalid());
+    }
+    let value = value
+        .trim()
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .ok_or_else(invalid)?;
+    Ok(EqFilter {
+        attribute: attribute.to_ascii_lowercase(),
+        value: value.replace("\\\"", "\"").replace("\\\\", "\\"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_eq_filter() {
+        assert_eq!(
+            parse_eq_filter(r#"userName eq "ada@example.com""#).unwrap(),
+            EqFilter {
+                attribute: "username".to_string(),
+                value: "ada@example.com".to_string(),
+            }
+        );
+        assert_eq!(
+            parse_eq_filter(r#"displayName EQ "Data \"Team\"""#)
+                .unwrap()
+                .value,
+            r#"Data "Team""#
+        );
+    }
+
+    #[test]
+    fn test_rejects_other_operators() {
+        assert!(parse_eq_filter(r#"userName co "ada""#).is_err());
+        assert!(parse_eq_filter(r#"userName eq ada"#).is_err());
+        assert!(parse_eq_filter("userName").is_err());
+    }
+}
@@ -0,0 +1,631 @@
+//! `/scim/v2/Groups`: groups pushed by the IdP. Their members must be users
+//! provisioned to the same org; a change resyncs the roles of everyone who
+//! joined or left the group.
+
+use std::collections::BTreeSet;
+
+use axum::{
+    Json,
+    extract::{Path, Query},
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use entity::prelude::{ScimGroupMembers, ScimGroups, ScimUsers};
+use entity::{scim_group_members, scim_groups, scim_users};
+use oxy::database::client::establish_connection;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection,
+    EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::filter::parse_eq_filter;
+use super::sync::sync_user;
+use super::types::{
+    GROUP_SCHEMA, ListQuery, ListResponse, PatchOp, PatchOperation, PatchRequest, ScimError,
+    ScimGroup, ScimMember, ScimMeta, scim_response,
+};
+use super::{ScimContext, resource_location};
+
+/// The writable part of a group, which PATCH operations apply to.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct GroupState {
+    pub display_name: String,
+    pub external_id: Option<String>,
+    pub members: BTreeSet<String>,
+}
+
+impl From<&ScimGroup> for GroupState {
+    fn from(group: &ScimGroup) -> Self {
+        Self {
+            display_name: group.display_name.clone(),
+            external_id: group.external_id.clone(),
+            members: group
+                .members
+                .iter()
+                .map(|member| member.value.clone())
+                .collect(),
+        }
+    }
+}
+
+async fn connect() -> Result<DatabaseConnection, ScimError> {
+    establish_connection()
+        .await
+        .map_err(|e| ScimError::internal("Database unavailable", e))
+}
+
+fn db_error(context: &'static str) -> impl FnOnce(sea_orm::DbErr) -> ScimError {
+    move |e| ScimError::internal(context, e)
+}
+
+fn group_id_from_path(id: &str) -> Result<Uuid, ScimError> {
+    Uuid::parse_str(id).map_err(|_| ScimError::not_found(format!("Group {id} not found")))
+}
+
+fn to_scim_group(group: &scim_groups::Model, members: &[Uuid]) -> ScimGroup {
+    ScimGroup {
+        schemas: vec![GROUP_SCHEMA.to_string()],
+        id: Some(group.id.to_string()),
+        external_id: group.external_id.clone(),
+        display_name: group.display_name.clone(),
+        members: members
+            .iter()
+            .map(|id| ScimMember {
+                value: id.to_string(),
+                display: None,
+            })
+            .collect(),
+        meta: Some(ScimMeta {
+            resource_type: "Group".to_string(),
+            created: group.created_at.to_rfc3339(),
+            last_modified: group.updated_at.to_rfc3339(),
+            location: resource_location("Groups", group.id),
+        }),
+    }
+}
+
+async fn load_members(db: &impl ConnectionTrait, group_id: Uuid) -> Result<Vec<Uuid>, ScimError> {
+    ScimGroupMembers::find()
+        .filter(scim_group_members::Column::GroupId.eq(group_id))
+        .select_only()
+        .column(scim_group_members::Column::UserId)
+        .into_tuple()
+        .all(db)
+        .await
+        .map_err(db_error("Failed to load group members"))
+}
+
+async fn find_group(
+    db: &impl ConnectionTrait,
+    org_id: Uuid,
+    group_id: Uuid,
+) -> Result<scim_groups::Model, ScimError> {
+    ScimGroups::find_by_id(group_id)
+        .filter(scim_groups::Column::OrgId.eq(org_id))
+        .one(db)
+        .await
+        .map_err(db_error("Failed to load SCIM group"))?
+        .ok_or_else(|| ScimError::not_found(format!("Group {group_id} not found")))
+}
+
+async fn ensure_unique_name(
+    db: &impl ConnectionTrait,
+    org_id: Uuid,
+    display_name: &str,
+    group_id: Option<Uuid>,
+) -> Result<(), ScimError> {
+    let mut query = ScimGroups::find()
+        .filter(scim_groups::Column::OrgId.eq(org_id))
+        .filter(scim_groups::Column::DisplayName.eq(display_name));
+    if let Some(group_id) = group_id {
+        query = query.filter(scim_groups::Column::Id.ne(group_id));
+    }
+    let taken = query
+        .count(db)
+        .await
+        .map_err(db_error("Failed to check group name"))?;
+    if taken > 0 {
+        return Err(ScimError::conflict(format!(
+            "Group {display_name} already exists"
+        )));
+    }
+    Ok(())
+}
+
+/// Member ids, each of which must be a user provisioned to the org.
+async fn resolve_members(
+    db: &impl ConnectionTrait,
+    org_id: Uuid,
+    members: &BTreeSet<String>,
+) -> Result<BTreeSet<Uuid>, ScimError> {
+    let ids = members
+        .iter()
+        .map(|member| {
+            Uuid::parse_str(member).map_err(|_| {
+                ScimError::bad_request("invalidValue", format!("Unknown member {member}"))
+            })
+        })
+        .collect::<Result<BTreeSet<Uuid>, _>>()?;
+    if ids.is_empty() {
+        return Ok(ids);
+    }
+    let known: BTreeSet<Uuid> = ScimUsers::find()
+        .filter(scim_users::Column::OrgId.eq(org_id))
+        .filter(scim_users::Column::UserId.is_in(ids.iter().copied()))
+        .select_only()
+        .column(scim_users::Column::UserId)
+        .into_tuple::<Uuid>()
+        .all(db)
+        .await
+        .map_err(db_error("Failed to load group members"))?
+        .into_iter()
+        .collect();
+    if let Some(unknown) = ids.difference(&known).next() {
+        return Err(ScimError::bad_request(
+            "invalidValue",
+            format!("Unknown member {unknown}"),
+        ));
+    }
+    Ok(ids)
+}
+
+/// Stores the group's new state and resyncs every user whose groups
+/// changed. A rename affects all members, since mappings match on names.
+async fn save_group(
+    db: &impl ConnectionTrait,
+    org_id: Uuid,
+    group: scim_groups::Model,
+    previous_members: &[Uuid],
+    state: GroupState,
+) -> Result<(scim_groups::Model, Vec<Uuid>), ScimError> {
+    let members = resolve_members(db, org_id, &state.members).await?;
+    if state.display_name != group.display_name {
+        ensure_unique_name(db, org_id, &state.display_name, Some(group.id)).await?;
+    }
+    let renamed = state.display_name != group.display_name;
+
+    let mut update: scim_groups::ActiveModel = group.into();
+    update.display_name = ActiveValue::Set(state.display_name);
+    update.external_id = ActiveValue::Set(state.external_id);
+    update.updated_at = ActiveValue::Set(Utc::now().into());
+    let group = update
+        .update(db)
+        .await
+        .map_err(db_error("Failed to update SCIM group"))?;
+
+    let previous: BTreeSet<Uuid> = previous_members.iter().copied().collect();
+    let removed: Vec<Uuid> = previous.difference(&members).copied().collect();
+    let added: Vec<Uuid> = members.difference(&previous).copied().collect();
+    if !removed.is_empty() {
+        ScimGroupMembers::delete_many()
+            .filter(scim_group_members::Column::GroupId.eq(group.id))
+            .filter(scim_group_members::Column::UserId.is_in(removed.clone()))
+            .exec(db)
+            .await
+            .map_err(db_error("Failed to remove group members"))?;
+    }
+    if !added.is_empty() {
+        ScimGroupMembers::insert_many(added.iter().map(|user_id| {
+            scim_group_members::ActiveModel {
+                group_id: ActiveValue::Set(group.id),
+                user_id: ActiveValue::Set(*user_id),
+            }
+        }))
+        .exec(db)
+        .await
+        .map_err(db_error("Failed to add group members"))?;
+    }
+
+    let affected: BTreeSet<Uuid> = if renamed {
+        previous.union(&members).copied().collect()
+    } else {
+        removed.into_iter().chain(added).collect()
+    };
+    resync(db, org_id, affected).await?;
+    Ok((group, members.into_iter().collect()))
+}
+
+/// Resyncs users that are still provisioned; removed users were already
+/// deprovisioned.
+async fn resync(
+    db: &impl ConnectionTrait,
+    org_id: Uuid,
+    user_ids: impl IntoIterator<Item = Uuid>,
+) -> Result<(), ScimError> {
+    for user_id in user_ids {
+        let provisioned = ScimUsers::find_by_id((org_id, user_id))
+            .one(db)
+            .await
+            .map_err(db_error("Failed to load SCIM user"))?;
+        if provisioned.is_some_and(|user| user.active) {
+            sync_user(db, org_id, user_id).await?;
+        }
+    }
+    Ok(())
+}
+
+/// GET /scim/v2/Groups
+pub async fn list_groups(
+    ctx: ScimContext,
+    Query(query): Query<ListQuery>,
+) -> Result<Response, ScimError> {
+    let mut condition = Condition::all().add(scim_groups::Column::OrgId.eq(ctx.org_id));
+    if let Some(filter) = query.filter.as_deref() {
+        let filter = parse_eq_filter(filter)?;
+        condition = match filter.attribute.as_str() {
+            "displayname" => condition.add(scim_groups::Column::DisplayName.eq(filter.value)),
+            "externalid" => condition.add(scim_groups::Column::ExternalId.eq(filter.value)),
+            "id" => match Uuid::parse_str(&filter.value) {
+                Ok(id) => condition.add(scim_groups::Column::Id.eq(id)),
+                Err(_) => {
+                    let (start_index, _) = query.page();
+                    let empty: ListResponse<ScimGroup> = ListResponse::new(vec![], 0, start_index);
+                    return Ok(scim_response(StatusCode::OK, empty));
+                }
+            },
+            attribute => {
+                return Err(ScimError::bad_request(
+                    "invalidFilter",
+                    format!("Filtering groups by '{attribute}' is not supported"),
+                ));
+            }
+        };
+    }
+
+    let db = connect().await?;
+    let (start_index, count) = query.page();
+    let total = ScimGroups::find()
+        .filter(condition.clone())
+        .count(&db)
+        .await
+        .map_err(db_error("Failed to count SCIM groups"))?;
+    let groups = ScimGroups::find()
+        .filter(condition)
+        .order_by_asc(scim_groups::Column::CreatedAt)
+        .offset(start_index - 1)
+        .limit(count)
+        .all(&db)
+        .await
+        .map_err(db_error("Failed to list SCIM groups"))?;
+    let mut resources = Vec::with_capacity(groups.len());
+    for group in &groups {
+        let members = load_members(&db, group.id).await?;
+        resources.push(to_scim_group(group, &members));
+    }
+    Ok(scim_response(
+        StatusCode::OK,
+        ListResponse::new(resources, total, start_index),
+    ))
+}
+
+/// GET /scim/v2/Groups/{id}
+pub async fn get_group(ctx: ScimContext, Path(id): Path<String>) -> Result<Response, ScimError> {
+    let group_id = group_id_from_path(&id)?;
+    let db = connect().await?;
+    let group = find_group(&db, ctx.org_id, group_id).await?;
+    let members = load_members(&db, group.id).await?;
+    Ok(scim_response(
+        StatusCode::OK,
+        to_scim_group(&group, &members),
+    ))
+}
+
+/// POST /scim/v2/Groups
+pub async fn create_group(
+    ctx: ScimContext,
+    Json(body): Json<ScimGroup>,
+) -> Result<Response, ScimError> {
+    let display_name = body.display_name.trim().to_string();
+    if display_name.is_empty() {
+        return Err(ScimError::bad_request(
+            "invalidValue",
+            "displayName is required",
+        ));
+    }
+    let db = connect().await?;
+    let txn = db
+        .begin()
+        .await
+        .map_err(db_error("Failed to start transaction"))?;
+    ensure_unique_name(&txn, ctx.org_id, &display_name, None).await?;
+    let now = Utc::now();
+    let group = scim_groups::ActiveModel {
+        id: ActiveValue::Set(Uuid::new_v4()),
+        org_id: ActiveValue::Set(ctx.org_id),
+        display_name: ActiveValue::Set(display_name.clone()),
+        external_id: ActiveValue::Set(body.external_id.clone()),
+        created_at: ActiveValue::Set(now.into()),
+        updated_at: ActiveValue::Set(now.into()),
+    }
+    .insert(&txn)
+    .await
+    .map_err(db_error("Failed to create SCIM group"))?;
+    let state = GroupState {
+        display_name,
+        ..GroupState::from(&body)
+    };
+    let (group, members) = save_group(&txn, ctx.org_id, group, &[], state).await?;
+    txn.commit()
+        .await
+        .map_err(db_error("Failed to commit transaction"))?;
+
+    let resource = to_scim_group(&group, &members);
+    let mut response = scim_response(StatusCode::CREATED, &resource);
+    if let Some(location) = resource
+        .meta
+        .as_ref()
+        .and_then(|meta| HeaderValue::from_str(&meta.location).ok())
+    {
+        response.headers_mut().insert(header::LOCATION, location);
+    }
+    Ok(response)
+}
+
+/// PUT /scim/v2/Groups/{id}
+pub async fn replace_group(
+    ctx: ScimContext,
+    Path(id): Path<String>,
+    Json(body): Json<ScimGroup>,
+) -> Result<Response, ScimError> {
+    let group_id = group_id_from_path(&id)?;
+    update_group(ctx.org_id, group_id, |_| Ok(GroupState::from(&body))).await
+}
+
+/// PATCH /scim/v2/Groups/{id}
+pub async fn patch_group(
+    ctx: ScimContext,
+    Path(id): Path<String>,
+    Json(body): Json<PatchRequest>,
+) -> Result<Response, ScimError> {
+    let group_id = group_id_from_path(&id)?;
+    update_group(ctx.org_id, group_id, |mut state| {
+        apply_group_patch(&mut state, &body.operations)?;
+        Ok(state)
+    })
+    .await
+}
+
+async fn update_group(
+    org_id: Uuid,
+    group_id: Uuid,
+    update: impl FnOnce(GroupState) -> Result<GroupState, ScimError>,
+) -> Result<Response, ScimError> {
+    let db = connect().await?;
+    let txn = db
+        .begin()
+        .await
+        .map_err(db_error("Failed to start transaction"))?;
+    let group = find_group(&txn, org_id, group_id).await?;
+    let previous_members = load_members(&txn, group.id).await?;
+    let current = GroupState {
+        display_name: group.display_name.clone(),
+        external_id: group.external_id.clone(),
+        members: previous_members.iter().map(Uuid::to_string).collect(),
+    };
+    let mut state = update(current)?;
+    state.display_name = state.display_name.trim().to_string();
+    if state.display_name.is_empty() {
+        return Err(ScimError::bad_request(
+            "invalidValue",
+            "displayName is required",
+        ));
+    }
+    let (group, members) = save_group(&txn, org_id, group, &previous_members, state).await?;
+    txn.commit()
+        .await
+        .map_err(db_error("Failed to commit transaction"))?;
+    Ok(scim_response(
+        StatusCode::OK,
+        to_scim_group(&group, &members),
+    ))
+}
+
+/// DELETE /scim/v2/Groups/{id}
+pub async fn delete_group(ctx: ScimContext, Path(id): Path<String>) -> Result<Response, ScimError> {
+    let group_id = group_id_from_path(&id)?;
+    let db = connect().await?;
+    let txn = db
+        .begin()
+        .await
+        .map_err(db_error("Failed to start transaction"))?;
+    let group = find_group(&txn, ctx.org_id, group_id).await?;
+    let members = load_members(&txn, group.id).await?;
+    // Members are removed by the cascade.
+    ScimGroups::delete_by_id(group.id)
+        .exec(&txn)
+        .await
+        .map_err(db_error("Failed to delete SCIM group"))?;
+    resync(&txn, ctx.org_id, members).await?;
+    txn.commit()
+        .await
+        .map_err(db_error("Failed to commit transaction"))?;
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+fn member_values(value: Option<&Value>) -> Result<Vec<String>, ScimError> {
+    let invalid = || ScimError::bad_request("invalidValue", "members must be a list of members");
+    let members = match value {
+        None => return Ok(vec![]),
+        Some(Value::Array(members)) => members.clone(),
+        Some(member @ Value::Object(_)) => vec![member.clone()],
+        Some(_) => return Err(invalid()),
+    };
+    members
+        .into_iter()
+        .map(|member| {
+            serde_json::from_value::<ScimMember>(member)
+                .map(|member| member.value)
+                .map_err(|_| invalid())
+        })
+        .collect()
+}
+
+fn string_value(value: Option<&Value>, attribute: &str) -> Result<String, ScimError> {
+    match value {
+        Some(Value::String(value)) => Ok(value.clone()),
+        _ => Err(ScimError::bad_request(
+            "invalidValue",
+            format!("{attribute} must be a string"),
+        )),
+    }
+}
+
+/// Applies one operation on a top-level group attribute.
+fn apply_group_attribute(
+    state: &mut GroupState,
+    kind: PatchOp,
+    attribute: &str,
+    value: Option<&Value>,
+) -> Result<(), ScimError> {
+    match (attribute.to_ascii_lowercase().as_str(), kind) {
+        ("members", PatchOp::Add) => state.members.extend(member_values(value)?),
+        ("members", PatchOp::Replace) => {
+            state.members = member_values(value)?.into_iter().collect()
+        }
+        ("members", PatchOp::Remove) => match value {
+            // Okta removes members with a value; without one, all go.
+            Some(_) => {
+                for member in member_values(value)? {
+                    state.members.remove(&member);
+                }
+            }
+            None => state.members.clear(),
+        },
+        ("displayname", PatchOp::Remove) => {
+            return Err(ScimError::bad_request(
+                "mutability",
+                "displayName cannot be removed",
+            ));
+        }
+        ("displayname", _) => state.display_name = string_value(value, "displayName")?,
+        ("externalid", PatchOp::Remove) => state.external_id = None,
+        ("externalid", _) => state.external_id = Some(string_value(value, "externalId")?),
+        // Read-only or unknown attributes such as `id` and `meta`
+        _ => {}
+    }
+    Ok(())
+}
+
+/// `members[value eq "<id>"]`, which Entra ID uses to remove one member.
+fn member_filter_value(path: &str) -> Option<String> {
+    let filter = path
+        .strip_prefix("members[")
+        .or_else(|| path.strip_prefix("Members["))?
+        .strip_suffix(']')?;
+    let filter = parse_eq_filter(filter).ok()?;
+    (filter.attribute == "value").then_some(filter.value)
+}
+
+pub(super) fn apply_group_patch(
+    state: &mut GroupState,
+    operations: &[PatchOperation],
+) -> Result<(), ScimError> {
+    for operation in operations {
+        let kind = operation.kind()?;
+        match operation.path.as_deref().map(str::trim) {
+            None | Some("") => {
+                let Some(Value::Object(values)) = &operation.value else {
+                    return Err(ScimError::bad_request(
+                        "invalidValue",
+                        "A PATCH operation without a path needs an object value",
+                    ));
+                };
+                for (attribute, value) in values {
+                    apply_group_attribute(state, kind, attribute, Some(value))?;
+                }
+            }
+            Some(path) => {
+                if let Some(member) = member_filter_value(path) {
+                    if kind != PatchOp::Remove {
+                        return Err(ScimError::bad_request(
+                            "invalidPath",
+                            format!("Unsupported path {path}"),
+                        ));
+                    }
+                    state.members.remove(&member);
+                } else {
+                    apply_group_attribute(state, kind, path, operation.value.as_ref())?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(members: &[&str]) -> GroupState {
+        GroupState {
+            display_name: "Data".to_string(),
+            external_id: None,
+            members: members.iter().map(|member| member.to_string()).collect(),
+        }
+    }
+
+    fn patch(state: &mut GroupState, operations: Value) -> Result<(), ScimError> {
+        let request: PatchRequest =
+            serde_json::from_value(serde_json::json!({ "Operations": operations })).unwrap();
+        apply_group_patch(state, &request.operations)
+    }
+
+    #[test]
+    fn test_okta_member_add_and_remove() {
+        let mut group = state(&["a"]);
+        patch(
+            &mut group,
+            serde_json::json!([
+                { "op": "add", "path": "members", "value": [{ "value": "b" }, { "value": "c" }] },
+                { "op": "remove", "path": "members", "value": [{ "value": "a" }] },
+            ]),
+        )
+        .unwrap();
+        assert_eq!(group, state(&["b", "c"]));
+    }
+
+    #[test]
+    fn test_entra_member_filter_remove_and_rename() {
+        let mut group = state(&["a", "b"]);
+        patch(
+            &mut group,
+            serde_json::json!([
+                { "op": "Remove", "path": "members[value eq \"a\"]" },
+                { "op": "Replace", "value": { "displayName": "Data Team", "id": "ignored" } },
+            ]),
+        )
+        .unwrap();
+        assert_eq!(group.display_name, "Data Team");
+        assert_eq!(group.members, BTreeSet::from(["b".to_string()]));
+    }
+
+    #[test]
+    fn test_replace_members_and_remove_all() {
+        let mut group = state(&["a", "b"]);
+        patch(
+            &mut group,
+            serde_json::json!([{ "op": "replace", "path": "members", "value": [{ "value": "c" }] }]),
+        )
+        .unwrap();
+        assert_eq!(group, state(&["c"]));
+        patch(
+            &mut group,
+            serde_json::json!([{ "op": "remove", "path": "members" }]),
+        )
+        .unwrap();
+        assert!(group.members.is_empty());
+    }
+
+    #[test]
+    fn test_display_name_cannot_be_removed() {
+        let mut group = state(&[]);
+        let result = patch(
+            &mut group,
+            serde_json::json!([{ "op": "remove", "path": "displayName" }]),
+        );
+        assert!(result.is_err());
+    }
+}
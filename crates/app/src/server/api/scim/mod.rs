@@ -0,0 +1,140 @@
+//! SCIM 2.0 provisioning (RFC 7644) of users and groups from an identity
+//! provider.
+//!
+//! The IdP authenticates with an org-scoped bearer token created by an org
+//! admin; every SCIM resource is scoped to that org. Users are global Oxy
+//! users linked to the org through `scim_users`, groups are stored as-is and
+//! only matter through `scim_role_mappings`, which turn group membership into
+//! an `OrgRole` and per-workspace `WorkspaceRole`s (see [`sync`]).
+
+pub mod admin;
+mod filter;
+pub mod groups;
+mod sync;
+pub mod types;
+pub mod users;
+
+use axum::{
+    extract::{FromRequestParts, Request},
+    http::{StatusCode, header, request::Parts},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use entity::{prelude::ScimTokens, scim_tokens};
+use oxy::database::client::establish_connection;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, sea_query::Expr};
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use uuid::Uuid;
+
+use types::{ScimError, scim_response, service_provider_config};
+
+/// Prefix of SCIM tokens, so leaked tokens are recognisable.
+const TOKEN_PREFIX: &str = "oxy_scim_";
+
+/// Org the calling SCIM token belongs to, inserted by
+/// [`scim_auth_middleware`].
+#[derive(Debug, Clone, Copy)]
+pub struct ScimContext {
+    pub org_id: Uuid,
+}
+
+impl<S> FromRequestParts<S> for ScimContext
+where
+    S: Send + Sync,
+{
+    type Rejection = ScimError;
+
+    fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> impl Future<Output = Result<Self, Self::Rejection>> + Send {
+        let result = parts
+            .extensions
+            .get::<ScimContext>()
+            .copied()
+            .ok_or_else(|| ScimError::internal("Missing SCIM context", "middleware not applied"));
+        async move { result }
+    }
+}
+
+pub(crate) fn generate_token() -> String {
+    format!("{TOKEN_PREFIX}{}", hex::encode(rand::random::<[u8; 32]>()))
+}
+
+pub(crate) fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Resolves the `Authorization: Bearer oxy_scim_...` header to its org.
+pub async fn scim_auth_middleware(mut request: Request, next: Next) -> Response {
+    let token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|token| token.starts_with(TOKEN_PREFIX));
+    let Some(token) = token else {
+        return ScimError::unauthorized().into_response();
+    };
+    let token_hash = hash_token(token);
+
+    let db = match establish_connection().await {
+        Ok(db) => db,
+        Err(e) => return ScimError::internal("Database unavailable", e).into_response(),
+    };
+    let stored = match ScimTokens::find()
+        .filter(scim_tokens::Column::TokenHash.eq(&token_hash))
+        .one(&db)
+        .await
+    {
+        Ok(Some(stored)) => stored,
+        Ok(None) => return ScimError::unauthorized().into_response(),
+        Err(e) => return ScimError::internal("Failed to look up SCIM token", e).into_response(),
+    };
+
+    // Best-effort; a failed timestamp update shouldn't fail provisioning.
+    if let Err(e) = ScimTokens::update_many()
+        .col_expr(
+            scim_tokens::Column::LastUsedAt,
+            Expr::value(Utc::now().fixed_offset()),
+        )
+        .filter(scim_tokens::Column::Id.eq(stored.id))
+        .exec(&db)
+        .await
+    {
+        tracing::warn!("Failed to update SCIM token last_used_at: {e}");
+    }
+
+    request.extensions_mut().insert(ScimContext {
+        org_id: stored.org_id,
+    });
+    next.run(request).await
+}
+
+/// GET /scim/v2/ServiceProviderConfig
+pub async fn get_service_provider_config(_: ScimContext) -> Response {
+    scim_response(StatusCode::OK, service_provider_config())
+}
+
+/// Location of a resource, as returned in `meta.location`. Relative, since
+/// the server doesn't know the host name the IdP reached it through.
+fn resource_location(resource_type: &str, id: Uuid) -> String {
+    format!("/api/scim/v2/{resource_type}/{id}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_tokens_are_prefixed_and_hash_stably() {
+        let token = generate_token();
+        assert!(token.starts_with(TOKEN_PREFIX));
+        assert_eq!(token.len(), TOKEN_PREFIX.len() + 64);
+        assert_eq!(hash_token(&token), hash_token(&token));
+        assert_ne!(hash_token(&token), hash_token(&generate_token()));
+    }
+}
@@ -0,0 +1,454 @@
+//! Applies a SCIM user's state to Oxy: org membership, workspace role
+//! overrides and account status.
+//!
+//! Role mappings are matched against group display names, so admins can set
+//! them up before the IdP pushes its first group. Only what mappings manage
+//! is touched: the org role when the org has at least one org-level mapping,
+//! and workspace overrides in mapped workspaces. Owners are never demoted by
+//! sync.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+
+use axum::http::StatusCode;
+use chrono::Utc;
+use entity::org_members::{self, OrgRole};
+use entity::prelude::{
+    OrgMembers, ScimGroupMembers, ScimGroups, ScimRoleMappings, ScimUsers, Users, WorkspaceMembers,
+    Workspaces,
+};
+use entity::users::{self, UserStatus};
+use entity::workspace_members::{self, WorkspaceRole};
+use entity::{scim_group_members, scim_groups, scim_role_mappings, scim_users, workspaces};
+use oxy::audit::{self, AuditAction, AuditEvent};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait,
+    QueryFilter, QuerySelect, sea_query::Expr,
+};
+use uuid::Uuid;
+
+use super::types::ScimError;
+
+/// Roles granted by a user's groups.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedRoles {
+    /// `None` when the org has no org-level mapping, leaving the role as is.
+    pub org_role: Option<OrgRole>,
+    pub workspace_roles: BTreeMap<Uuid, WorkspaceRole>,
+    /// Workspaces referenced by any mapping; overrides outside these are
+    /// left alone.
+    pub managed_workspaces: BTreeSet<Uuid>,
+}
+
+/// Highest role each of the user's groups maps to. Group names compare
+/// case-insensitively; mappings with an unparseable role are skipped, as
+/// they are validated when saved.
+pub fn resolve_roles(
+    mappings: &[scim_role_mappings::Model],
+    group_names: &[String],
+) -> ResolvedRoles {
+    let in_group = |mapping: &scim_role_mappings::Model| {
+        group_names
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(&mapping.group_name))
+    };
+    let mut resolved = ResolvedRoles {
+        org_role: None,
+        workspace_roles: BTreeMap::new(),
+        managed_workspaces: BTreeSet::new(),
+    };
+    let mut manages_org_role = false;
+    for mapping in mappings {
+        match mapping.workspace_id {
+            None => {
+                manages_org_role = true;
+                if let (true, Ok(role)) = (in_group(mapping), OrgRole::from_str(&mapping.role)) {
+                    resolved.org_role = resolved.org_role.max(Some(role));
+                }
+            }
+            Some(workspace_id) => {
+                resolved.managed_workspaces.insert(workspace_id);
+                if let (true, Ok(role)) =
+                    (in_group(mapping), WorkspaceRole::from_str(&mapping.role))
+                {
+                    resolved
+                        .workspace_roles
+                        .entry(workspace_id)
+                        .and_modify(|current| {
+                            if role > *current {
+                                *current = role.clone();
+                            }
+                        })
+                        .or_insert(role);
+                }
+            }
+        }
+    }
+    if manages_org_role && resolved.org_role.is_none() {
+        resolved.org_role = Some(OrgRole::Member);
+    }
+    resolved
+}
+
+fn db_error(context: &'static str) -> impl FnOnce(sea_orm::DbErr) -> ScimError {
+    move |e| ScimError::internal(context, e)
+}
+
+/// Changes made by sync have no acting user; they are attributed to SCIM.
+async fn record_audit<C: ConnectionTrait>(txn: &C, event: AuditEvent) -> Result<(), ScimError> {
+    audit::record_in(txn, event.meta("source", "scim"))
+        .await
+        .map_err(|e| ScimError::internal("Failed to write audit entry", e))
+}
+
+/// Brings the user's access in the org in line with their SCIM state. Must
+/// run inside the transaction that changed that state.
+pub async fn sync_user<C: ConnectionTrait>(
+    txn: &C,
+    org_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), ScimError> {
+    let scim_user = ScimUsers::find_by_id((org_id, user_id))
+        .one(txn)
+        .await
+        .map_err(db_error("Failed to load SCIM user"))?;
+    match scim_user {
+        Some(scim_user) if scim_user.active => provision(txn, scim_user).await,
+        _ => deprovision(txn, org_id, user_id).await,
+    }
+}
+
+/// Adds the user to the org. A deleted account is only reactivated when this
+/// org's SCIM deleted it; an account deleted for every org stays deleted.
+async fn provision<C: ConnectionTrait>(
+    txn: &C,
+    scim_user: scim_users::Model,
+) -> Result<(), ScimError> {
+    let (org_id, user_id) = (scim_user.org_id, scim_user.user_id);
+    let user = Users::find_by_id(user_id)
+        .one(txn)
+        .await
+        .map_err(db_error("Failed to load user"))?
+        .ok_or_else(|| ScimError::not_found(format!("User {user_id} not found")))?;
+    if user.status == UserStatus::Deleted {
+        if !scim_user.deactivated_account {
+            return Err(ScimError::bad_request(
+                "invalidValue",
+                format!("User {user_id} has been deleted and cannot be provisioned"),
+            ));
+        }
+        let mut active: users::ActiveModel = user.into();
+        active.status = ActiveValue::Set(UserStatus::Active);
+        active
+            .update(txn)
+            .await
+            .map_err(db_error("Failed to reactivate user"))?;
+    }
+    if scim_user.deactivated_account {
+        let mut active: scim_users::ActiveModel = scim_user.into();
+        active.deactivated_account = ActiveValue::Set(false);
+        active
+            .update(txn)
+            .await
+            .map_err(db_error("Failed to update SCIM user"))?;
+    }
+
+    let group_names: Vec<String> = ScimGroups::find()
+        .inner_join(ScimGroupMembers)
+        .filter(scim_groups::Column::OrgId.eq(org_id))
+        .filter(scim_group_members::Column::UserId.eq(user_id))
+        .select_only()
+        .column(scim_groups::Column::DisplayName)
+        .into_tuple()
+        .all(txn)
+        .await
+        .map_err(db_error("Failed to load SCIM groups"))?;
+    let mappings = ScimRoleMappings::find()
+        .filter(scim_role_mappings::Column::OrgId.eq(org_id))
+        .all(txn)
+        .await
+        .map_err(db_error("Failed to load SCIM role mappings"))?;
+    let resolved = resolve_roles(&mappings, &group_names);
+
+    let now = Utc::now();
+    let membership = OrgMembers::find()
+        .filter(org_members::Column::OrgId.eq(org_id))
+        .filter(org_members::Column::UserId.eq(user_id))
+        .one(txn)
+        .await
+        .map_err(db_error("Failed to load org membership"))?;
+    match membership {
+        Some(membership) => {
+            if let Some(role) = resolved.org_role
+                && membership.role != OrgRole::Owner
+                && membership.role != role
+            {
+                let previous_role = membership.role.as_str();
+                let new_role = role.as_str();
+                let mut active: org_members::ActiveModel = membership.into();
+                active.role = ActiveValue::Set(role);
+                active.updated_at = ActiveValue::Set(now.into());
+                active
+                    .update(txn)
+                    .await
+                    .map_err(db_error("Failed to update org membership"))?;
+                record_audit(
+                    txn,
+                    AuditEvent::new(AuditAction::MemberRoleChanged)
+                        .org(org_id)
+                        .target("user", user_id)
+                        .meta("from", previous_role)
+                        .meta("to", new_role),
+                )
+                .await?;
+            }
+        }
+        None => {
+            let role = resolved.org_role.unwrap_or(OrgRole::Member);
+            let role_name = role.as_str();
+            org_members::ActiveModel {
+                id: ActiveValue::Set(Uuid::new_v4()),
+                org_id: ActiveValue::Set(org_id),
+                user_id: ActiveValue::Set(user_id),
+                role: ActiveValue::Set(role),
+                created_at: ActiveValue::Set(now.into()),
+                updated_at: ActiveValue::Set(now.into()),
+            }
+            .insert(txn)
+            .await
+            .map_err(db_error("Failed to add org membership"))?;
+            record_audit(
+                txn,
+                AuditEvent::new(AuditAction::MemberAdded)
+                    .org(org_id)
+                    .target("user", user_id)
+                    .meta("role", role_name),
+            )
+            .await?;
+        }
+    }
+
+    for workspace_id in &resolved.managed_workspaces {
+        let existing = WorkspaceMembers::find()
+            .filter(workspace_members::Column::WorkspaceId.eq(*workspace_id))
+            .filter(workspace_members::Column::UserId.eq(user_id))
+            .one(txn)
+            .await
+            .map_err(db_error("Failed to load workspace override"))?;
+        match (existing, resolved.workspace_roles.get(workspace_id)) {
+            (Some(existing), Some(role)) if existing.role != *role => {
+                let mut active: workspace_members::ActiveModel = existing.into();
+                active.role = ActiveValue::Set(role.clone());
+                active.updated_at = ActiveValue::Set(now.into());
+                active
+                    .update(txn)
+                    .await
+                    .map_err(db_error("Failed to update workspace override"))?;
+                record_audit(txn, override_event(*workspace_id, user_id, Some(role))).await?;
+            }
+            (Some(_), Some(_)) => {}
+            (Some(existing), None) => {
+                let active: workspace_members::ActiveModel = existing.into();
+                active
+                    .delete(txn)
+                    .await
+                    .map_err(db_error("Failed to remove workspace override"))?;
+                record_audit(txn, override_event(*workspace_id, user_id, None)).await?;
+            }
+            (None, Some(role)) => {
+                workspace_members::ActiveModel {
+                    id: ActiveValue::Set(Uuid::new_v4()),
+                    workspace_id: ActiveValue::Set(*workspace_id),
+                    user_id: ActiveValue::Set(user_id),
+                    role: ActiveValue::Set(role.clone()),
+                    created_at: ActiveValue::Set(now.into()),
+                    updated_at: ActiveValue::Set(now.into()),
+                }
+                .insert(txn)
+                .await
+                .map_err(db_error("Failed to add workspace override"))?;
+                record_audit(txn, override_event(*workspace_id, user_id, Some(role))).await?;
+            }
+            (None, None) => {}
+        }
+    }
+    Ok(())
+}
+
+fn override_event(workspace_id: Uuid, user_id: Uuid, role: Option<&WorkspaceRole>) -> AuditEvent {
+    match role {
+        Some(role) => AuditEvent::new(AuditAction::WorkspaceRoleOverridden)
+            .workspace(workspace_id)
+            .target("user", user_id)
+            .meta("role", role.as_str()),
+        None => AuditEvent::new(AuditAction::WorkspaceRoleOverrideRemoved)
+            .workspace(workspace_id)
+            .target("user", user_id),
+    }
+}
+
+/// Removes the user from the org, the same way `remove_member` does, and
+/// marks the account deleted once no org is left. The SCIM user, when it is
+/// kept, remembers that so the org can reactivate the account later.
+async fn deprovision<C: ConnectionTrait>(
+    txn: &C,
+    org_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), ScimError> {
+    let membership = OrgMembers::find()
+        .filter(org_members::Column::OrgId.eq(org_id))
+        .filter(org_members::Column::UserId.eq(user_id))
+        .lock_exclusive()
+        .one(txn)
+        .await
+        .map_err(db_error("Failed to load org membership"))?;
+
+    if let Some(membership) = membership {
+        if membership.role == OrgRole::Owner {
+            let owner_count = OrgMembers::find()
+                .filter(org_members::Column::OrgId.eq(org_id))
+                .filter(org_members::Column::Role.eq(OrgRole::Owner))
+                .lock_exclusive()
+                .count(txn)
+                .await
+                .map_err(db_error("Failed to count owners"))?;
+            if owner_count <= 1 {
+                return Err(ScimError {
+                    status: StatusCode::CONFLICT,
+                    scim_type: None,
+                    detail: "Cannot deprovision the last owner of the organization".to_string(),
+                });
+            }
+        }
+
+        let workspace_ids: Vec<Uuid> = Workspaces::find()
+            .filter(workspaces::Column::OrgId.eq(org_id))
+            .select_only()
+            .column(workspaces::Column::Id)
+            .into_tuple()
+            .all(txn)
+            .await
+            .map_err(db_error("Failed to list org workspaces"))?;
+        if !workspace_ids.is_empty() {
+            WorkspaceMembers::delete_many()
+                .filter(workspace_members::Column::UserId.eq(user_id))
+                .filter(workspace_members::Column::WorkspaceId.is_in(workspace_ids))
+                .exec(txn)
+                .await
+                .map_err(db_error("Failed to remove workspace overrides"))?;
+        }
+
+        let removed_role = membership.role.as_str();
+        let active: org_members::ActiveModel = membership.into();
+        active
+            .delete(txn)
+            .await
+            .map_err(db_error("Failed to remove org membership"))?;
+        record_audit(
+            txn,
+            AuditEvent::new(AuditAction::MemberRemoved)
+                .org(org_id)
+                .target("user", user_id)
+                .meta("role", removed_role),
+        )
+        .await?;
+    }
+
+    let remaining = OrgMembers::find()
+        .filter(org_members::Column::UserId.eq(user_id))
+        .count(txn)
+        .await
+        .map_err(db_error("Failed to count memberships"))?;
+    if remaining == 0 {
+        let deactivated = Users::update_many()
+            .col_expr(users::Column::Status, Expr::value(UserStatus::Deleted))
+            .filter(users::Column::Id.eq(user_id))
+            .filter(users::Column::Status.ne(UserStatus::Deleted))
+            .exec(txn)
+            .await
+            .map_err(db_error("Failed to deactivate user"))?;
+        if deactivated.rows_affected > 0 {
+            ScimUsers::update_many()
+                .col_expr(scim_users::Column::DeactivatedAccount, Expr::value(true))
+                .filter(scim_users::Column::OrgId.eq(org_id))
+                .filter(scim_users::Column::UserId.eq(user_id))
+                .exec(txn)
+                .await
+                .map_err(db_error("Failed to update SCIM user"))?;
+        }
+    }
+    Ok(())
+}
+
+/// Best-effort Stripe seat sync after membership changes; the reconciler
+/// catches any failure.
+pub async fn spawn_seat_sync(org_id: Uuid) {
+    if let Ok(svc) = crate::api::billing::billing_service().await {
+        tokio::spawn(async move {
+            if let Err(e) = svc.sync_seats(org_id).await {
+                tracing::warn!(?e, ?org_id, "sync_seats failed after SCIM provisioning");
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping(group: &str, workspace_id: Option<Uuid>, role: &str) -> scim_role_mappings::Model {
+        scim_role_mappings::Model {
+            id: Uuid::new_v4(),
+            org_id: Uuid::nil(),
+            group_name: group.to_string(),
+            workspace_id,
+            role: role.to_string(),
+            created_at: Utc::now().into(),
+        }
+    }
+
+    fn groups(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_highest_role_wins() {
+        let analytics = Uuid::new_v4();
+        let mappings = vec![
+            mapping("Engineering", None, "member"),
+            mapping("Oxy Admins", None, "admin"),
+            mapping("Engineering", Some(analytics), "member"),
+            mapping("Data", Some(analytics), "admin"),
+        ];
+        let resolved = resolve_roles(&mappings, &groups(&["engineering", "Data", "Oxy Admins"]));
+        assert_eq!(resolved.org_role, Some(OrgRole::Admin));
+        assert_eq!(
+            resolved.workspace_roles,
+            BTreeMap::from([(analytics, WorkspaceRole::Admin)])
+        );
+        assert_eq!(resolved.managed_workspaces, BTreeSet::from([analytics]));
+    }
+
+    #[test]
+    fn test_users_outside_mapped_groups_fall_back_to_member() {
+        let analytics = Uuid::new_v4();
+        let mappings = vec![
+            mapping("Oxy Admins", None, "admin"),
+            mapping("Data", Some(analytics), "admin"),
+        ];
+        let resolved = resolve_roles(&mappings, &groups(&["Sales"]));
+        assert_eq!(resolved.org_role, Some(OrgRole::Member));
+        assert!(resolved.workspace_roles.is_empty());
+        assert_eq!(resolved.managed_workspaces, BTreeSet::from([analytics]));
+    }
+
+    #[test]
+    fn test_org_role_is_unmanaged_without_org_mappings() {
+        let resolved = resolve_roles(
+            &[mapping("Data", Some(Uuid::new_v4()), "viewer")],
+            &groups(&["Data"]),
+        );
+        assert_eq!(resolved.org_role, None);
+        assert_eq!(resolved.workspace_roles.len(), 1);
+    }
+}
@@ -0,0 +1,369 @@
+//! SCIM 2.0 resources and messages (RFC 7643 / RFC 7644), limited to the
+//! attributes Oxy stores.
+
+use axum::{
+    Json,
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub(super) const USER_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:User";
+pub(super) const GROUP_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:Group";
+const LIST_RESPONSE_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:ListResponse";
+const ERROR_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:Error";
+const SERVICE_PROVIDER_CONFIG_SCHEMA: &str =
+    "urn:ietf:params:scim:schemas:core:2.0:ServiceProviderConfig";
+pub(super) const SCIM_CONTENT_TYPE: &str = "application/scim+json";
+
+/// Most resources returned in one page.
+pub(super) const MAX_PAGE_SIZE: u64 = 200;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimName {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub formatted: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub given_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub family_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScimEmail {
+    pub value: String,
+    #[serde(default)]
+    pub primary: bool,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimMeta {
+    pub resource_type: String,
+    pub created: String,
+    pub last_modified: String,
+    pub location: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimUser {
+    #[serde(default)]
+    pub schemas: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    pub user_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<ScimName>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub emails: Vec<ScimEmail>,
+    #[serde(default = "default_active", deserialize_with = "deserialize_bool")]
+    pub active: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<ScimMeta>,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+/// Entra ID sends booleans as "True" / "False" strings in PATCH operations.
+fn deserialize_bool<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::Bool(value) => Ok(value),
+        Value::String(value) if value.eq_ignore_ascii_case("true") => Ok(true),
+        Value::String(value) if value.eq_ignore_ascii_case("false") => Ok(false),
+        other => Err(serde::de::Error::custom(format!(
+            "expected a boolean, got {other}"
+        ))),
+    }
+}
+
+impl ScimUser {
+    /// The primary email, else the first one, else `userName` if it is an
+    /// email address. Lowercased, as Oxy matches users by email.
+    pub fn email(&self) -> Option<String> {
+        self.emails
+            .iter()
+            .find(|email| email.primary)
+            .or_else(|| self.emails.first())
+            .map(|email| email.value.as_str())
+            .or(Some(self.user_name.as_str()))
+            .map(|email| email.trim().to_lowercase())
+            .filter(|email| email.contains('@'))
+    }
+
+    /// Given and family name when present, so PATCHing either one takes
+    /// effect, else the formatted or display name.
+    pub fn full_name(&self) -> Option<String> {
+        let non_empty = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let from_parts = self.name.as_ref().and_then(|name| {
+            let parts: Vec<String> = [non_empty(&name.given_name), non_empty(&name.family_name)]
+                .into_iter()
+                .flatten()
+                .collect();
+            (!parts.is_empty()).then(|| parts.join(" "))
+        });
+        from_parts
+            .or_else(|| {
+                self.name
+                    .as_ref()
+                    .and_then(|name| non_empty(&name.formatted))
+            })
+            .or_else(|| non_empty(&self.display_name))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScimMember {
+    pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimGroup {
+    #[serde(default)]
+    pub schemas: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    pub display_name: String,
+    #[serde(default)]
+    pub members: Vec<ScimMember>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<ScimMeta>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListResponse<T> {
+    schemas: [&'static str; 1],
+    total_results: u64,
+    start_index: u64,
+    items_per_page: u64,
+    #[serde(rename = "Resources")]
+    resources: Vec<T>,
+}
+
+impl<T> ListResponse<T> {
+    pub fn new(resources: Vec<T>, total_results: u64, start_index: u64) -> Self {
+        Self {
+            schemas: [LIST_RESPONSE_SCHEMA],
+            total_results,
+            start_index,
+            items_per_page: resources.len() as u64,
+            resources,
+        }
+    }
+}
+
+/// Pagination parameters shared by the list endpoints.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListQuery {
+    pub filter: Option<String>,
+    pub start_index: Option<u64>,
+    pub count: Option<u64>,
+}
+
+impl ListQuery {
+    /// 1-based start index and page size, clamped to valid values.
+    pub fn page(&self) -> (u64, u64) {
+        (
+            self.start_index.unwrap_or(1).max(1),
+            self.count.unwrap_or(100).min(MAX_PAGE_SIZE),
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PatchRequest {
+    #[serde(rename = "Operations")]
+    pub operations: Vec<PatchOperation>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PatchOperation {
+    pub op: String,
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub value: Option<Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PatchOp {
+    Add,
+    Remove,
+    Replace,
+}
+
+impl PatchOperation {
+    /// Operation names are case-insensitive; Entra ID capitalizes them.
+    pub fn kind(&self) -> Result<PatchOp, ScimError> {
+        match self.op.to_ascii_lowercase().as_str() {
+            "add" => Ok(PatchOp::Add),
+            "remove" => Ok(PatchOp::Remove),
+            "replace" => Ok(PatchOp::Replace),
+            _ => Err(ScimError::bad_request(
+                "invalidSyntax",
+                format!("Unsupported PATCH operation '{}'", self.op),
+            )),
+        }
+    }
+}
+
+/// A SCIM error response, rendered as an `Error` message.
+#[derive(Debug)]
+pub struct ScimError {
+    pub status: StatusCode,
+    pub scim_type: Option<&'static str>,
+    pub detail: String,
+}
+
+impl ScimError {
+    pub fn bad_request(scim_type: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            scim_type: Some(scim_type),
+            detail: detail.into(),
+        }
+    }
+
+    pub fn not_found(detail: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::NOT_FOUND,
+            scim_type: None,
+            detail: detail.into(),
+        }
+    }
+
+    pub fn conflict(detail: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::CONFLICT,
+            scim_type: Some("uniqueness"),
+            detail: detail.into(),
+        }
+    }
+
+    pub fn unauthorized() -> Self {
+        Self {
+            status: StatusCode::UNAUTHORIZED,
+            scim_type: None,
+            detail: "Invalid or missing SCIM token".to_string(),
+        }
+    }
+
+    /// Logs the underlying error; the identity provider only sees a generic
+    /// message.
+    pub fn internal(context: &str, error: impl std::fmt::Display) -> Self {
+        tracing::error!("SCIM: {context}: {error}");
+        Self {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            scim_type: None,
+            detail: context.to_string(),
+        }
+    }
+}
+
+impl IntoResponse for ScimError {
+    fn into_response(self) -> Response {
+        let mut body = serde_json::json!({
+            "schemas": [ERROR_SCHEMA],
+            "status": self.status.as_u16().to_string(),
+            "detail": self.detail,
+        });
+        if let Some(scim_type) = self.scim_type {
+            body["scimType"] = Value::String(scim_type.to_string());
+        }
+        scim_response(self.status, body)
+    }
+}
+
+/// A JSON response with the SCIM media type.
+pub fn scim_response(status: StatusCode, body: impl Serialize) -> Response {
+    let mut response = (status, Json(body)).into_response();
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(SCIM_CONTENT_TYPE),
+    );
+    response
+}
+
+pub fn service_provider_config() -> Value {
+    serde_json::json!({
+        "schemas": [SERVICE_PROVIDER_CONFIG_SCHEMA],
+        "patch": { "supported": true },
+        "bulk": { "supported": false, "maxOperations": 0, "maxPayloadSize": 0 },
+        "filter": { "supported": true, "maxResults": MAX_PAGE_SIZE },
+        "changePassword": { "supported": false },
+        "sort": { "supported": false },
+        "etag": { "supported": false },
+        "authenticationSchemes": [{
+            "type": "oauthbearertoken",
+            "name": "OAuth Bearer Token",
+            "description": "Org-scoped SCIM token created in the organization settings",
+            "primary": true,
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_accepts_string_booleans_and_derives_profile() {
+        let user: ScimUser = serde_json::from_value(serde_json::json!({
+            "schemas": [USER_SCHEMA],
+            "userName": "Ada@Example.com",
+            "name": { "givenName": "Ada", "familyName": "Lovelace" },
+            "active": "False",
+        }))
+        .unwrap();
+        assert!(!user.active);
+        assert_eq!(user.email().as_deref(), Some("ada@example.com"));
+        assert_eq!(user.full_name().as_deref(), Some("Ada Lovelace"));
+    }
+
+    #[test]
+    fn test_user_prefers_primary_email() {
+        let user: ScimUser = serde_json::from_value(serde_json::json!({
+            "userName": "ada",
+            "emails": [
+                { "value": "ada@home.example.com" },
+                { "value": "ada@example.com", "primary": true },
+            ],
+        }))
+        .unwrap();
+        assert!(user.active);
+        assert_eq!(user.email().as_deref(), Some("ada@example.com"));
+    }
+
+    #[test]
+    fn test_page_is_clamped() {
+        let query = ListQuery {
+            filter: None,
+            start_index: Some(0),
+            count: Some(10_000),
+        };
+        assert_eq!(query.page(), (1, MAX_PAGE_SIZE));
+    }
+}
@@ -0,0 +1,663 @@
+//! `/scim/v2/Users`: links global Oxy users to the org and drives their
+//! lifecycle. A user's SCIM `id` is their Oxy user id.
+
+use axum::{
+    Json,
+    extract::{Path, Query},
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use entity::prelude::{OrgMembers, ScimGroupMembers, ScimGroups, ScimUsers, Users};
+use entity::users::{self, UserStatus};
+use entity::{org_members, scim_group_members, scim_groups, scim_users};
+use oxy::database::client::establish_connection;
+use oxy::database::filters::UserQueryFilterExt;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, Condition, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use super::super::auth::insert_user_or_fetch_existing;
+use super::filter::parse_eq_filter;
+use super::sync::{spawn_seat_sync, sync_user};
+use super::types::{
+    ListQuery, ListResponse, PatchOp, PatchOperation, PatchRequest, ScimEmail, ScimError, ScimMeta,
+    ScimName, ScimUser, USER_SCHEMA, scim_response,
+};
+use super::{ScimContext, resource_location};
+
+/// Attribute names as Oxy emits them; incoming names are matched
+/// case-insensitively against these.
+const USER_ATTRIBUTES: &[&str] = &[
+    "externalId",
+    "userName",
+    "name",
+    "displayName",
+    "emails",
+    "active",
+    "formatted",
+    "givenName",
+    "familyName",
+];
+
+async fn connect() -> Result<DatabaseConnection, ScimError> {
+    establish_connection()
+        .await
+        .map_err(|e| ScimError::internal("Database unavailable", e))
+}
+
+fn db_error(context: &'static str) -> impl FnOnce(sea_orm::DbErr) -> ScimError {
+    move |e| ScimError::internal(context, e)
+}
+
+fn to_scim_user(scim_user: &scim_users::Model, user: &users::Model) -> ScimUser {
+    let id = user.id;
+    ScimUser {
+        schemas: vec![USER_SCHEMA.to_string()],
+        id: Some(id.to_string()),
+        external_id: scim_user.external_id.clone(),
+        user_name: user.email.clone(),
+        name: Some(ScimName {
+            formatted: Some(user.name.clone()),
+            ..Default::default()
+        }),
+        display_name: Some(user.name.clone()),
+        emails: vec![ScimEmail {
+            value: user.email.clone(),
+            primary: true,
+            kind: Some("work".to_string()),
+        }],
+        active: scim_user.active,
+        meta: Some(ScimMeta {
+            resource_type: "User".to_string(),
+            created: scim_user.created_at.to_rfc3339(),
+            last_modified: scim_user.updated_at.to_rfc3339(),
+            location: resource_location("Users", id),
+        }),
+    }
+}
+
+fn user_id_from_path(id: &str) -> Result<Uuid, ScimError> {
+    Uuid::parse_str(id).map_err(|_| ScimError::not_found(format!("User {id} not found")))
+}
+
+async fn find_scim_user(
+    db: &impl sea_orm::ConnectionTrait,
+    org_id: Uuid,
+    user_id: Uuid,
+) -> Result<(scim_users::Model, users::Model), ScimError> {
+    let found = ScimUsers::find_by_id((org_id, user_id))
+        .find_also_related(Users)
+        .one(db)
+        .await
+        .map_err(db_error("Failed to load SCIM user"))?;
+    match found {
+        Some((scim_user, Some(user))) => Ok((scim_user, user)),
+        _ => Err(ScimError::not_found(format!("User {user_id} not found"))),
+    }
+}
+
+/// GET /scim/v2/Users
+pub async fn list_users(
+    ctx: ScimContext,
+    Query(query): Query<ListQuery>,
+) -> Result<Response, ScimError> {
+    let mut condition = Condition::all().add(scim_users::Column::OrgId.eq(ctx.org_id));
+    if let Some(filter) = query.filter.as_deref() {
+        let filter = parse_eq_filter(filter)?;
+        condition = match filter.attribute.as_str() {
+            "username" | "emails" | "emails.value" => {
+                condition.add(users::Column::Email.eq(filter.value.trim().to_lowercase()))
+            }
+            "externalid" => condition.add(scim_users::Column::ExternalId.eq(filter.value)),
+            "id" => match Uuid::parse_str(&filter.value) {
+                Ok(id) => condition.add(scim_users::Column::UserId.eq(id)),
+                Err(_) => {
+                    let (start_index, _) = query.page();
+                    let empty: ListResponse<ScimUser> = ListResponse::new(vec![], 0, start_index);
+                    return Ok(scim_response(StatusCode::OK, empty));
+                }
+            },
+            attribute => {
+                return Err(ScimError::bad_request(
+                    "invalidFilter",
+                    format!("Filtering users by '{attribute}' is not supported"),
+                ));
+            }
+        };
+    }
+
+    let db = connect().await?;
+    let (start_index, count) = query.page();
+    let total = ScimUsers::find()
+        .inner_join(Users)
+        .filter(condition.clone())
+        .count(&db)
+        .await
+        .map_err(db_error("Failed to count SCIM users"))?;
+    let rows = ScimUsers::find()
+        .find_also_related(Users)
+        .filter(condition)
+        .order_by_asc(scim_users::Column::CreatedAt)
+        .offset(start_index - 1)
+        .limit(count)
+        .all(&db)
+        .await
+        .map_err(db_error("Failed to list SCIM users"))?;
+    let resources = rows
+        .iter()
+        .filter_map(|(scim_user, user)| Some(to_scim_user(scim_user, user.as_ref()?)))
+        .collect();
+    Ok(scim_response(
+        StatusCode::OK,
+        ListResponse::new(resources, total, start_index),
+    ))
+}
+
+/// GET /scim/v2/Users/{id}
+pub async fn get_user(ctx: ScimContext, Path(id): Path<String>) -> Result<Response, ScimError> {
+    let user_id = user_id_from_path(&id)?;
+    let db = connect().await?;
+    let (scim_user, user) = find_scim_user(&db, ctx.org_id, user_id).await?;
+    Ok(scim_response(
+        StatusCode::OK,
+        to_scim_user(&scim_user, &user),
+    ))
+}
+
+/// POST /scim/v2/Users
+///
+/// Links an existing Oxy user with the same email, or creates one. Accounts
+/// are shared between orgs, so only users who already belong to the org are
+/// linked; anyone else has to join through an invitation first.
+pub async fn create_user(
+    ctx: ScimContext,
+    Json(body): Json<ScimUser>,
+) -> Result<Response, ScimError> {
+    let email = body.email().ok_or_else(|| {
+        ScimError::bad_request("invalidValue", "userName or emails must contain an email")
+    })?;
+    let db = connect().await?;
+
+    let existing = Users::find()
+        .filter_by_email(&email)
+        .one(&db)
+        .await
+        .map_err(db_error("Failed to look up user"))?;
+    let (user, created_user) = match existing {
+        Some(user) => (user, false),
+        None => {
+            let name = body
+                .full_name()
+                .unwrap_or_else(|| email.split('@').next().unwrap_or_default().to_string());
+            let id = Uuid::new_v4();
+            let new_user = users::ActiveModel {
+                id: ActiveValue::Set(id),
+                email: ActiveValue::Set(email.clone()),
+                name: ActiveValue::Set(name),
+                picture: ActiveValue::NotSet,
+                email_verified: ActiveValue::Set(true),
+                magic_link_token: ActiveValue::NotSet,
+                magic_link_token_expires_at: ActiveValue::NotSet,
+                status: ActiveValue::Set(UserStatus::Active),
+                created_at: ActiveValue::NotSet,
+                last_login_at: ActiveValue::NotSet,
+            };
+            let user = insert_user_or_fetch_existing(new_user, &email, &db)
+                .await
+                .map_err(|status| ScimError::internal("Failed to create user", status))?;
+            // Someone else may have created the account in the meantime
+            let created_user = user.id == id;
+            (user, created_user)
+        }
+    };
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(db_error("Failed to start transaction"))?;
+    let linked = ScimUsers::find_by_id((ctx.org_id, user.id))
+        .one(&txn)
+        .await
+        .map_err(db_error("Failed to look up SCIM user"))?;
+    if linked.is_some() {
+        return Err(ScimError::conflict(format!(
+            "User {email} is already provisioned"
+        )));
+    }
+    let is_member = OrgMembers::find()
+        .filter(org_members::Column::OrgId.eq(ctx.org_id))
+        .filter(org_members::Column::UserId.eq(user.id))
+        .count(&txn)
+        .await
+        .map_err(db_error("Failed to look up org membership"))?
+        > 0;
+    if !may_link(created_user, is_member) {
+        return Err(ScimError::conflict(format!(
+            "User {email} has an Oxy account outside this organization; invite them first"
+        )));
+    }
+    let now = Utc::now();
+    let scim_user = scim_users::ActiveModel {
+        org_id: ActiveValue::Set(ctx.org_id),
+        user_id: ActiveValue::Set(user.id),
+        external_id: ActiveValue::Set(body.external_id.clone()),
+        active: ActiveValue::Set(body.active),
+        created_user: ActiveValue::Set(created_user),
+        deactivated_account: ActiveValue::Set(false),
+        created_at: ActiveValue::Set(now.into()),
+        updated_at: ActiveValue::Set(now.into()),
+    }
+    .insert(&txn)
+    .await
+    .map_err(db_error("Failed to provision user"))?;
+    sync_user(&txn, ctx.org_id, user.id).await?;
+    txn.commit()
+        .await
+        .map_err(db_error("Failed to commit transaction"))?;
+    spawn_seat_sync(ctx.org_id).await;
+
+    let user = Users::find_by_id(user.id)
+        .one(&db)
+        .await
+        .map_err(db_error("Failed to load user"))?
+        .unwrap_or(user);
+    let resource = to_scim_user(&scim_user, &user);
+    let mut response = scim_response(StatusCode::CREATED, &resource);
+    if let Some(location) = resource
+        .meta
+        .as_ref()
+        .and_then(|meta| HeaderValue::from_str(&meta.location).ok())
+    {
+        response.headers_mut().insert(header::LOCATION, location);
+    }
+    Ok(response)
+}
+
+/// PUT /scim/v2/Users/{id}
+pub async fn replace_user(
+    ctx: ScimContext,
+    Path(id): Path<String>,
+    Json(body): Json<ScimUser>,
+) -> Result<Response, ScimError> {
+    let user_id = user_id_from_path(&id)?;
+    update_user(ctx.org_id, user_id, |_| Ok(body)).await
+}
+
+/// PATCH /scim/v2/Users/{id}
+pub async fn patch_user(
+    ctx: ScimContext,
+    Path(id): Path<String>,
+    Json(body): Json<PatchRequest>,
+) -> Result<Response, ScimError> {
+    let user_id = user_id_from_path(&id)?;
+    update_user(ctx.org_id, user_id, |current| {
+        let mut resource = serde_json::to_value(current)
+            .map_err(|e| ScimError::internal("Failed to serialize user", e))?;
+        apply_user_patch(&mut resource, &body.operations)?;
+        serde_json::from_value(resource)
+            .map_err(|e| ScimError::bad_request("invalidValue", e.to_string()))
+    })
+    .await
+}
+
+/// Loads the user, computes its new representation with `update` and stores
+/// it. The account is shared between orgs, so the email only changes for
+/// users the org's SCIM created and that belong to no other org.
+async fn update_user(
+    org_id: Uuid,
+    user_id: Uuid,
+    update: impl FnOnce(ScimUser) -> Result<ScimUser, ScimError>,
+) -> Result<Response, ScimError> {
+    let db = connect().await?;
+    let txn = db
+        .begin()
+        .await
+        .map_err(db_error("Failed to start transaction"))?;
+    let (scim_user, user) = find_scim_user(&txn, org_id, user_id).await?;
+    let updated = update(to_scim_user(&scim_user, &user))?;
+
+    let email = updated.email().ok_or_else(|| {
+        ScimError::bad_request("invalidValue", "userName or emails must contain an email")
+    })?;
+    let mut user_update: users::ActiveModel = user.clone().into();
+    if email != user.email {
+        let other_orgs = OrgMembers::find()
+            .filter(org_members::Column::UserId.eq(user_id))
+            .filter(org_members::Column::OrgId.ne(org_id))
+            .count(&txn)
+            .await
+            .map_err(db_error("Failed to count memberships"))?;
+        if !may_change_email(&scim_user, other_orgs) {
+            return Err(ScimError::bad_request(
+                "mutability",
+                "Only the email of a user created by this organization, and in no other, can be changed",
+            ));
+        }
+        let taken = Users::find()
+            .filter_by_email(&email)
+            .one(&txn)
+            .await
+            .map_err(db_error("Failed to look up user"))?;
+        if taken.is_some() {
+            return Err(ScimError::conflict(format!(
+                "Email {email} is already in use"
+            )));
+        }
+        user_update.email = ActiveValue::Set(email);
+    }
+    if let Some(name) = updated.full_name() {
+        user_update.name = ActiveValue::Set(name);
+    }
+    user_update
+        .update(&txn)
+        .await
+        .map_err(db_error("Failed to update user"))?;
+
+    let was_active = scim_user.active;
+    let mut scim_update: scim_users::ActiveModel = scim_user.into();
+    scim_update.external_id = ActiveValue::Set(updated.external_id.clone());
+    scim_update.active = ActiveValue::Set(updated.active);
+    scim_update.updated_at = ActiveValue::Set(Utc::now().into());
+    scim_update
+        .update(&txn)
+        .await
+        .map_err(db_error("Failed to update SCIM user"))?;
+    sync_user(&txn, org_id, user_id).await?;
+    txn.commit()
+        .await
+        .map_err(db_error("Failed to commit transaction"))?;
+    if was_active != updated.active {
+        spawn_seat_sync(org_id).await;
+    }
+
+    let (scim_user, user) = find_scim_user(&db, org_id, user_id).await?;
+    Ok(scim_response(
+        StatusCode::OK,
+        to_scim_user(&scim_user, &user),
+    ))
+}
+
+/// DELETE /scim/v2/Users/{id}
+///
+/// Removes the user from the org and its groups. The Oxy account itself is
+/// kept, deactivated if it belongs to no other org.
+pub async fn delete_user(ctx: ScimContext, Path(id): Path<String>) -> Result<Response, ScimError> {
+    let user_id = user_id_from_path(&id)?;
+    let db = connect().await?;
+    let txn = db
+        .begin()
+        .await
+        .map_err(db_error("Failed to start transaction"))?;
+    let deleted = ScimUsers::delete_by_id((ctx.org_id, user_id))
+        .exec(&txn)
+        .await
+        .map_err(db_error("Failed to delete SCIM user"))?;
+    if deleted.rows_affected == 0 {
+        return Err(ScimError::not_found(format!("User {user_id} not found")));
+    }
+    let group_ids: Vec<Uuid> = ScimGroups::find()
+        .filter(scim_groups::Column::OrgId.eq(ctx.org_id))
+        .select_only()
+        .column(scim_groups::Column::Id)
+        .into_tuple()
+        .all(&txn)
+        .await
+        .map_err(db_error("Failed to list SCIM groups"))?;
+    if !group_ids.is_empty() {
+        ScimGroupMembers::delete_many()
+            .filter(scim_group_members::Column::UserId.eq(user_id))
+            .filter(scim_group_members::Column::GroupId.is_in(group_ids))
+            .exec(&txn)
+            .await
+            .map_err(db_error("Failed to remove group memberships"))?;
+    }
+    sync_user(&txn, ctx.org_id, user_id).await?;
+    txn.commit()
+        .await
+        .map_err(db_error("Failed to commit transaction"))?;
+    spawn_seat_sync(ctx.org_id).await;
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+/// Whether the org may link an existing account: one it just created, or
+/// one that already belongs to it.
+fn may_link(created_user: bool, is_member: bool) -> bool {
+    created_user || is_member
+}
+
+/// Whether the org may change the account's email. Changing it moves the
+/// sign-in to another mailbox, so only accounts the org's SCIM created and
+/// that no other org relies on qualify.
+fn may_change_email(scim_user: &scim_users::Model, other_orgs: u64) -> bool {
+    scim_user.created_user && other_orgs == 0
+}
+
+fn canonical_attribute(name: &str) -> String {
+    USER_ATTRIBUTES
+        .iter()
+        .find(|attribute| attribute.eq_ignore_ascii_case(name))
+        .map(|attribute| attribute.to_string())
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// Applies PATCH operations to the JSON form of a user. Oxy keeps a single
+/// email, so any `emails` path sets that email.
+pub(super) fn apply_user_patch(
+    resource: &mut Value,
+    operations: &[PatchOperation],
+) -> Result<(), ScimError> {
+    for operation in operations {
+        let kind = operation.kind()?;
+        match operation.path.as_deref().map(str::trim) {
+            None | Some("") => {
+                if kind == PatchOp::Remove {
+                    return Err(ScimError::bad_request(
+                        "noTarget",
+                        "A remove operation needs a path",
+                    ));
+                }
+                let Some(Value::Object(values)) = &operation.value else {
+                    return Err(ScimError::bad_request(
+                        "invalidValue",
+                        "A PATCH operation without a path needs an object value",
+                    ));
+                };
+                // Entra ID sends pathless operations with dotted keys such as
+                // `name.givenName`.
+                for (path, value) in values {
+                    set_user_path(resource, path, Some(value.clone()))?;
+                }
+            }
+            Some(path) => {
+                let value = match kind {
+                    PatchOp::Remove => None,
+                    PatchOp::Add | PatchOp::Replace => {
+                        Some(operation.value.clone().ok_or_else(|| {
+                            ScimError::bad_request("invalidValue", "Missing operation value")
+                        })?)
+                    }
+                };
+                set_user_path(resource, path, value)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Sets (or with `None`, removes) the attribute at `path`.
+fn set_user_path(resource: &mut Value, path: &str, value: Option<Value>) -> Result<(), ScimError> {
+    if path.to_ascii_lowercase().starts_with("emails") {
+        let Some(value) = value else {
+            // The only email can't be removed; ignore like other read-only
+            // attributes.
+            return Ok(());
+        };
+        let emails = match value {
+            Value::String(email) => serde_json::json!([{ "value": email, "primary": true }]),
+            Value::Array(emails) => Value::Array(emails),
+            Value::Object(email) => Value::Array(vec![Value::Object(email)]),
+            _ => {
+                return Err(ScimError::bad_request(
+                    "invalidValue",
+                    "emails must be a string or a list of emails",
+                ));
+            }
+        };
+        resource["emails"] = emails;
+        return Ok(());
+    }
+
+    let segments: Vec<String> = path.split('.').map(canonical_attribute).collect();
+    let Some((last, parents)) = segments.split_last() else {
+        return Ok(());
+    };
+    let mut target = resource;
+    for segment in parents {
+        let object = target
+            .as_object_mut()
+            .ok_or_else(|| ScimError::bad_request("invalidPath", format!("Invalid path {path}")))?;
+        target = object
+            .entry(segment.clone())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if target.is_null() {
+            *target = Value::Object(Map::new());
+        }
+    }
+    let object = target
+        .as_object_mut()
+        .ok_or_else(|| ScimError::bad_request("invalidPath", format!("Invalid path {path}")))?;
+    match value {
+        Some(Value::Object(values)) if object.get(last).is_some_and(Value::is_object) => {
+            // Complex attributes merge, e.g. `"name": {"givenName": ...}`
+            for (key, value) in values {
+                set_user_path(&mut object[last.as_str()], &key, Some(value))?;
+            }
+        }
+        Some(value) => {
+            object.insert(last.clone(), value);
+        }
+        None => {
+            object.remove(last);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user_resource() -> Value {
+        serde_json::json!({
+            "schemas": [USER_SCHEMA],
+            "id": "5b3f7e02-6c43-4d3e-9d07-2b2b9c7dbe4a",
+            "userName": "ada@example.com",
+            "name": { "formatted": "Ada Lovelace" },
+            "displayName": "Ada Lovelace",
+            "emails": [{ "value": "ada@example.com", "primary": true }],
+            "active": true,
+        })
+    }
+
+    fn operations(value: Value) -> Vec<PatchOperation> {
+        serde_json::from_value::<PatchRequest>(value)
+            .unwrap()
+            .operations
+    }
+
+    #[test]
+    fn test_entra_deactivation() {
+        let mut resource = user_resource();
+        apply_user_patch(
+            &mut resource,
+            &operations(serde_json::json!({
+                "Operations": [{ "op": "Replace", "path": "active", "value": "False" }]
+            })),
+        )
+        .unwrap();
+        let user: ScimUser = serde_json::from_value(resource).unwrap();
+        assert!(!user.active);
+    }
+
+    #[test]
+    fn test_pathless_replace_with_dotted_keys() {
+        let mut resource = user_resource();
+        apply_user_patch(
+            &mut resource,
+            &operations(serde_json::json!({
+                "Operations": [{
+                    "op": "replace",
+                    "value": {
+                        "active": false,
+                        "name.givenName": "Augusta",
+                        "externalId": "00u1",
+                    }
+                }]
+            })),
+        )
+        .unwrap();
+        let user: ScimUser = serde_json::from_value(resource).unwrap();
+        assert!(!user.active);
+        assert_eq!(user.external_id.as_deref(), Some("00u1"));
+        assert_eq!(user.name.unwrap().given_name.as_deref(), Some("Augusta"));
+    }
+
+    #[test]
+    fn test_email_filter_path_sets_the_email() {
+        let mut resource = user_resource();
+        apply_user_patch(
+            &mut resource,
+            &operations(serde_json::json!({
+                "Operations": [{
+                    "op": "replace",
+                    "path": "emails[type eq \"work\"].value",
+                    "value": "ada.lovelace@example.com",
+                }]
+            })),
+        )
+        .unwrap();
+        let user: ScimUser = serde_json::from_value(resource).unwrap();
+        assert_eq!(user.email().as_deref(), Some("ada.lovelace@example.com"));
+    }
+
+    fn scim_user(created_user: bool) -> scim_users::Model {
+        scim_users::Model {
+            org_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            external_id: None,
+            active: true,
+            created_user,
+            deactivated_account: false,
+            created_at: Utc::now().into(),
+            updated_at: Utc::now().into(),
+        }
+    }
+
+    #[test]
+    fn test_accounts_outside_the_org_cannot_be_taken_over() {
+        // A user with an Oxy account but no membership in the org can't be
+        // linked, and linked users keep their email even in no other org
+        assert!(!may_link(false, false));
+        assert!(may_link(false, true));
+        assert!(may_link(true, false));
+        assert!(!may_change_email(&scim_user(false), 0));
+        assert!(!may_change_email(&scim_user(true), 1));
+        assert!(may_change_email(&scim_user(true), 0));
+    }
+
+    #[test]
+    fn test_remove_requires_path() {
+        let mut resource = user_resource();
+        let result = apply_user_patch(
+            &mut resource,
+            &operations(serde_json::json!({
+                "Operations": [{ "op": "remove" }]
+            })),
+        );
+        assert!(result.is_err());
+    }
+}
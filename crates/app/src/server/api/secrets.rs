@@ -10,6 +10,7 @@ use axum::{
 };
 use entity::users::Entity as Users;
 use garde::Validate;
+use oxy::audit::{self, AuditAction, AuditEvent};
 use oxy::config::constants::{ANTHROPIC_API_KEY_VAR, GEMINI_API_KEY_VAR, OPENAI_API_KEY_VAR};
 use oxy::config::model::{DatabaseType, IntegrationType, SnowflakeAuthType};
 use oxy::database::client::establish_connection;
@@ -492,6 +493,7 @@ fn parse_dotenv_file(path: &std::path::Path) -> Vec<(String, String)> {
 const AUTH_ENV_VAR_PREFIXES: &[&str] = &[
     "GOOGLE_CLIENT_",  // GOOGLE_CLIENT_ID, GOOGLE_CLIENT_SECRET
     "OKTA_",           // OKTA_CLIENT_ID, OKTA_CLIENT_SECRET, OKTA_DOMAIN
+    "OIDC_",           // generic OpenID Connect provider
     "MAGIC_LINK_",     // all magic-link config vars
     "OXY_",            // internal Oxy infrastructure vars (OXY_DATABASE_URL, OXY_OWNER, …)
     "GIT_REPOSITORY_", // GIT_REPOSITORY_URL
@@ -712,6 +714,37 @@ pub async fn list_env_secrets(
                     pairs.push((v.clone(), format!("databases.{name}.database_var")));
                 }
             }
+            DatabaseType::Mssql(ms) => {
+                if let Some(v) = &ms.password_var {
+                    pairs.push((v.clone(), format!("databases.{name}.password_var")));
+                }
+                if let Some(v) = &ms.host_var {
+                    pairs.push((v.clone(), format!("databases.{name}.host_var")));
+                }
+                if let Some(v) = &ms.user_var {
+                    pairs.push((v.clone(), format!("databases.{name}.user_var")));
+                }
+                if let Some(v) = &ms.port_var {
+                    pairs.push((v.clone(), format!("databases.{name}.port_var")));
+                }
+                if let Some(v) = &ms.database_var {
+                    pairs.push((v.clone(), format!("databases.{name}.database_var")));
+                }
+            }
+            DatabaseType::Trino(tr) => {
+                if let Some(v) = &tr.password_var {
+                    pairs.push((v.clone(), format!("databases.{name}.password_var")));
+                }
+                if let Some(v) = &tr.host_var {
+                    pairs.push((v.clone(), format!("databases.{name}.host_var")));
+                }
+                if let Some(v) = &tr.user_var {
+                    pairs.push((v.clone(), format!("databases.{name}.user_var")));
+                }
+                if let Some(v) = &tr.port_var {
+                    pairs.push((v.clone(), format!("databases.{name}.port_var")));
+                }
+            }
             DatabaseType::ClickHouse(ch) => {
                 if let Some(v) = &ch.password_var {
                     pairs.push((v.clone(), format!("databases.{name}.password_var")));
@@ -748,7 +781,7 @@ pub async fn list_env_secrets(
                     format!("databases.{name}.developer_token_var"),
                 ));
             }
-            DatabaseType::DuckDB(_) => {}
+            DatabaseType::DuckDB(_) | DatabaseType::Sqlite(_) => {}
             // Managed Airhouse has no `*_var` fields — credentials are
             // sourced from oxy's own database via the per-user provisioning
             // flow.
@@ -787,7 +820,7 @@ pub async fn list_env_secrets(
 /// Delete a secret by ID
 pub async fn delete_secret(
     _: WorkspaceAdmin,
-    AuthenticatedUserExtractor(_user): AuthenticatedUserExtractor,
+    AuthenticatedUserExtractor(user): AuthenticatedUserExtractor,
     Path((workspace_id, id)): Path<(Uuid, String)>,
 ) -> Result<impl IntoResponse, StatusCode> {
     let secret_id = match Uuid::parse_str(&id) {
@@ -816,7 +849,10 @@ pub async fn delete_secret(
             .into_response());
     };
 
-    match secret_manager.delete_secret(&db, &secret.name).await {
+    match secret_manager
+        .delete_secret(&db, &secret.name, user.id)
+        .await
+    {
         Ok(()) => Ok((StatusCode::NO_CONTENT, axum::Json(json!({}))).into_response()),
         Err(OxyError::SecretManager(msg)) => {
             Ok((StatusCode::BAD_REQUEST, axum::Json(json!({ "error": msg }))).into_response())
@@ -835,7 +871,7 @@ pub async fn delete_secret(
 /// Reveal the plaintext value of a DB secret by ID (WorkspaceAdmin only).
 pub async fn reveal_secret(
     _: WorkspaceAdmin,
-    AuthenticatedUserExtractor(_user): AuthenticatedUserExtractor,
+    AuthenticatedUserExtractor(user): AuthenticatedUserExtractor,
     Path((workspace_id, id)): Path<(Uuid, String)>,
 ) -> Result<impl IntoResponse, StatusCode> {
     let secret_id = match Uuid::parse_str(&id) {
@@ -852,7 +888,16 @@ pub async fn reveal_secret(
     let secret_manager = SecretManagerService::new(workspace_id);
 
     match secret_manager.get_secret_value_by_id(secret_id).await {
-        Some(value) => Ok((StatusCode::OK, axum::Json(json!({ "value": value }))).into_response()),
+        Some(value) => {
+            audit::record(
+                AuditEvent::new(AuditAction::SecretRevealed)
+                    .workspace(workspace_id)
+                    .actor(user.id)
+                    .target("secret", secret_id),
+            )
+            .await;
+            Ok((StatusCode::OK, axum::Json(json!({ "value": value }))).into_response())
+        }
         None => Ok((
             StatusCode::NOT_FOUND,
             axum::Json(json!({ "error": "Secret not found or decryption failed" })),
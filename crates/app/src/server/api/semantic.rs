@@ -1,7 +1,9 @@
+use crate::server::api::middlewares::permission_guard::ensure_allowed;
 use crate::server::api::middlewares::workspace_context::{
     WorkspaceManagerExtractor, WorkspacePath,
 };
 use crate::server::api::result_files::store_result_file;
+use crate::server::service::permissions::WorkspacePermissions;
 use crate::server::service::types::SemanticQueryParams;
 use axum::{
     extract::{self, Path},
@@ -9,6 +11,7 @@ use axum::{
 };
 use base64::Engine;
 use base64::prelude::BASE64_STANDARD;
+use entity::custom_roles::Permission;
 use oxy::adapters::session_filters::SessionFilters;
 use oxy::config::model::{ConnectionOverrides, SemanticQueryTask};
 use oxy::connector::load_result;
@@ -17,7 +20,7 @@ use oxy::execute::{
     renderer::Renderer,
     types::{Output, Source, utils::record_batches_to_2d_array},
 };
-use oxy_semantic::parse_semantic_layer_from_dir;
+use oxy_semantic::{access::redact_view, parse_semantic_layer_from_dir};
 use oxy_workflow::semantic_builder::{SemanticQueryExecutable, render_semantic_query};
 use oxy_workflow::semantic_validator_builder::validate_semantic_query_task;
 use serde::{Deserialize, Serialize};
@@ -67,8 +70,11 @@ pub async fn execute_semantic_query(
     Path(WorkspacePath {
         workspace_id: _workspace_id,
     }): Path<WorkspacePath>,
+    permissions: WorkspacePermissions,
     extract::Json(payload): extract::Json<SemanticQueryRequest>,
 ) -> Result<extract::Json<SemanticQueryResponse>, (StatusCode, extract::Json<ErrorResponse>)> {
+    ensure_topic_allowed(&permissions, payload.query.topic.as_deref())?;
+
     // Create a dummy execution context
     let (tx, _rx) = mpsc::channel(100);
     let renderer = Renderer::new(minijinja::Value::default());
@@ -89,6 +95,7 @@ pub async fn execute_semantic_query(
         user_id: None,
         metric_context: None,
         data_app_file_path: None,
+        budget: None,
     };
 
     // Construct SemanticQueryTask
@@ -199,14 +206,36 @@ pub struct ErrorResponse {
     pub message: String,
 }
 
+/// A query without a topic can reach any view, so it needs a grant that is
+/// not limited to particular topics.
+fn ensure_topic_allowed(
+    permissions: &WorkspacePermissions,
+    topic: Option<&str>,
+) -> Result<(), (StatusCode, extract::Json<ErrorResponse>)> {
+    ensure_allowed(permissions, Permission::QuerySemantic, topic.unwrap_or("")).map_err(|status| {
+        (
+            status,
+            extract::Json(ErrorResponse {
+                message: match topic {
+                    Some(topic) => format!("Not allowed to query topic '{topic}'"),
+                    None => "Not allowed to query without a topic".to_string(),
+                },
+            }),
+        )
+    })
+}
+
 pub async fn compile_semantic_query(
     WorkspaceManagerExtractor(workspace_manager): WorkspaceManagerExtractor,
     Path(WorkspacePath {
         workspace_id: _workspace_id,
     }): Path<WorkspacePath>,
+    permissions: WorkspacePermissions,
     extract::Json(payload): extract::Json<SemanticQueryRequest>,
 ) -> Result<extract::Json<SemanticQueryCompileResponse>, (StatusCode, extract::Json<ErrorResponse>)>
 {
+    ensure_topic_allowed(&permissions, payload.query.topic.as_deref())?;
+
     // Create a dummy execution context
     let (tx, _rx) = mpsc::channel(100);
     let renderer = Renderer::new(minijinja::Value::default());
@@ -227,6 +256,7 @@ pub async fn compile_semantic_query(
         user_id: None,
         metric_context: None,
         data_app_file_path: None,
+        budget: None,
     };
 
     // Construct SemanticQueryTask
@@ -385,6 +415,7 @@ pub async fn get_view_details(
             }),
         )
     })?;
+    let view = redact_view(&view, Some(workspace_manager.config_manager.access_level()));
 
     Ok(extract::Json(ViewResponse {
         view_name: view.name.clone(),
@@ -492,6 +523,7 @@ pub async fn get_topic_details(
 
     let mut views_with_data = Vec::new();
 
+    let access_level = Some(workspace_manager.config_manager.access_level());
     for view_name in &topic.views {
         if let Some(view) = parse_result
             .semantic_layer
@@ -499,6 +531,7 @@ pub async fn get_topic_details(
             .iter()
             .find(|v| v.name == *view_name)
         {
+            let view = &redact_view(view, access_level);
             views_with_data.push(ViewResponse {
                 view_name: view_name.clone(),
                 name: view.name.clone(),
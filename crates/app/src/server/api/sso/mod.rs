@@ -0,0 +1,147 @@
+//! Single sign-on through a generic OpenID Connect provider. Logins create or
+//! update the user the same way as the built-in providers and end in
+//! `finalize_login`.
+
+pub mod oidc;
+
+use axum::http::StatusCode;
+use entity::{
+    prelude::{SsoIdentities, Users},
+    sso_identities, users,
+    users::UserStatus,
+};
+use oxy::database::filters::UserQueryFilterExt;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, Set,
+    sea_query::OnConflict,
+};
+use uuid::Uuid;
+
+use super::auth::insert_user_or_fetch_existing;
+
+/// Profile of a user, mapped from the provider's claims.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SsoProfile {
+    /// Identity provider that vouches for the user
+    pub issuer: String,
+    /// The user's stable identifier at `issuer`
+    pub subject: String,
+    pub email: String,
+    pub name: String,
+    pub picture: Option<String>,
+}
+
+fn db_error(e: DbErr) -> StatusCode {
+    tracing::error!("SSO database error: {}", e);
+    StatusCode::INTERNAL_SERVER_ERROR
+}
+
+/// Create the user on first login, otherwise refresh their profile.
+/// Deleted users are rejected.
+///
+/// Users are found by the provider's issuer and subject. The first login
+/// through a provider links to an existing user with the same email, unless
+/// that user is already linked to another subject at the same provider: the
+/// provider has then reassigned the address, and the account is not theirs.
+async fn upsert_sso_user(
+    profile: &SsoProfile,
+    provider: &str,
+    connection: &DatabaseConnection,
+) -> Result<users::Model, StatusCode> {
+    let identity = SsoIdentities::find_by_id((profile.issuer.clone(), profile.subject.clone()))
+        .one(connection)
+        .await
+        .map_err(db_error)?;
+    let existing = match &identity {
+        Some(identity) => Users::find_by_id(identity.user_id)
+            .one(connection)
+            .await
+            .map_err(db_error)?,
+        None => {
+            let existing = Users::find()
+                .filter_by_email(&profile.email)
+                .one(connection)
+                .await
+                .map_err(db_error)?;
+            if let Some(user) = &existing {
+                let linked_elsewhere = SsoIdentities::find()
+                    .filter(sso_identities::Column::Issuer.eq(&profile.issuer))
+                    .filter(sso_identities::Column::UserId.eq(user.id))
+                    .one(connection)
+                    .await
+                    .map_err(db_error)?;
+                if linked_elsewhere.is_some() {
+                    tracing::warn!(
+                        "{} subject for {} does not match the one linked to the account",
+                        provider,
+                        profile.email
+                    );
+                    return Err(StatusCode::UNAUTHORIZED);
+                }
+            }
+            existing
+        }
+    };
+
+    let user = match existing {
+        Some(existing_user) if existing_user.status == UserStatus::Deleted => {
+            tracing::warn!(
+                "Deleted user {} attempted to authenticate via {}",
+                profile.email,
+                provider
+            );
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+        Some(existing_user) => {
+            let mut user_update: users::ActiveModel = existing_user.into();
+            user_update.name = Set(profile.name.clone());
+            // Providers may leave out the picture claim; keep the one we have
+            if profile.picture.is_some() {
+                user_update.picture = Set(profile.picture.clone());
+            }
+            user_update.email_verified = Set(true);
+            user_update.last_login_at = Set(chrono::Utc::now().into());
+            user_update.update(connection).await.map_err(|e| {
+                tracing::error!("Failed to update user: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?
+        }
+        None => {
+            let new_user = users::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                email: Set(profile.email.clone()),
+                name: Set(profile.name.clone()),
+                picture: Set(profile.picture.clone()),
+                email_verified: Set(true),
+                magic_link_token: sea_orm::ActiveValue::NotSet,
+                magic_link_token_expires_at: sea_orm::ActiveValue::NotSet,
+                status: Set(UserStatus::Active),
+                created_at: sea_orm::ActiveValue::NotSet,
+                last_login_at: sea_orm::ActiveValue::NotSet,
+            };
+            insert_user_or_fetch_existing(new_user, &profile.email, connection).await?
+        }
+    };
+
+    if identity.is_none() {
+        let identity = sso_identities::ActiveModel {
+            issuer: Set(profile.issuer.clone()),
+            subject: Set(profile.subject.clone()),
+            user_id: Set(user.id),
+            created_at: sea_orm::ActiveValue::NotSet,
+        };
+        SsoIdentities::insert(identity)
+            .on_conflict(
+                OnConflict::columns([
+                    sso_identities::Column::Issuer,
+                    sso_identities::Column::Subject,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .exec_without_returning(connection)
+            .await
+            .map_err(db_error)?;
+    }
+    Ok(user)
+}
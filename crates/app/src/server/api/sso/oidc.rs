@@ -0,0 +1,548 @@
+//! Login with any OpenID Connect provider through discovery, the
+//! authorization code flow with PKCE and configurable claim mapping.
+//!
+//! `POST /auth/oidc/authorize` returns the provider's authorization URL, the
+//! signed OAuth `state` and a signed `session` holding the PKCE verifier and
+//! nonce. The frontend keeps the session and sends it back with the code to
+//! `POST /auth/oidc`, so the server stays stateless and the verifier never
+//! goes through the provider.
+
+use axum::{
+    extract,
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{Duration, Utc};
+use jsonwebtoken::{
+    Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, decode_header, encode,
+    jwk::JwkSet,
+};
+use oxy::{
+    config::{auth::OidcAuth, constants::AUTHENTICATION_SECRET_KEY},
+    database::client::establish_connection,
+};
+use oxy_shared::errors::OxyError;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use url::Url;
+
+use super::{SsoProfile, upsert_sso_user};
+use crate::server::api::auth::{
+    AuthResponse, extract_base_url_from_headers, finalize_login, sign_oauth_state,
+    verify_oauth_state,
+};
+
+const SESSION_PURPOSE: &str = "oidc-session";
+/// Same lifetime as the OAuth state the session is bound to.
+const SESSION_TTL_SECS: i64 = 10 * 60;
+
+#[derive(Debug, Deserialize)]
+struct ProviderMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+    userinfo_endpoint: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct OidcSession {
+    purpose: String,
+    /// SHA-256 of the OAuth state this session was issued with
+    state_hash: String,
+    code_verifier: String,
+    nonce: String,
+    redirect_uri: String,
+    exp: usize,
+    iat: usize,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    id_token: String,
+    access_token: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct OidcAuthorizeResponse {
+    pub authorization_url: String,
+    pub state: String,
+    pub session: String,
+}
+
+#[derive(Deserialize)]
+pub struct OidcAuthRequest {
+    pub code: String,
+    /// See `GoogleAuthRequest::state`.
+    pub state: String,
+    /// Returned by `POST /auth/oidc/authorize` alongside the state.
+    pub session: String,
+}
+
+fn oidc_config() -> Result<OidcAuth, OxyError> {
+    oxy::config::oxy::get_oxy_config()
+        .ok()
+        .and_then(|config| config.authentication)
+        .and_then(|auth| auth.oidc)
+        .ok_or_else(|| OxyError::ConfigurationError("OIDC configuration not found".to_string()))
+}
+
+pub async fn authorize(headers: HeaderMap) -> Result<Json<OidcAuthorizeResponse>, StatusCode> {
+    let config = oidc_config().map_err(|e| {
+        tracing::error!("{}", e);
+        StatusCode::NOT_FOUND
+    })?;
+    let metadata = discover(&reqwest::Client::new(), &config.issuer_url)
+        .await
+        .map_err(|e| {
+            tracing::error!("OIDC discovery failed: {}", e);
+            StatusCode::BAD_GATEWAY
+        })?;
+
+    let state = sign_oauth_state()?;
+    let code_verifier = URL_SAFE_NO_PAD.encode(rand::random::<[u8; 32]>());
+    let nonce = hex::encode(rand::random::<[u8; 16]>());
+    let redirect_uri = format!(
+        "{}/auth/oidc/callback",
+        extract_base_url_from_headers(&headers)
+    );
+
+    let mut authorization_url = Url::parse(&metadata.authorization_endpoint).map_err(|e| {
+        tracing::error!("Invalid OIDC authorization endpoint: {}", e);
+        StatusCode::BAD_GATEWAY
+    })?;
+    authorization_url
+        .query_pairs_mut()
+        .append_pair("response_type", "code")
+        .append_pair("client_id", &config.client_id)
+        .append_pair("redirect_uri", &redirect_uri)
+        .append_pair("scope", &config.scopes)
+        .append_pair("state", &state)
+        .append_pair("nonce", &nonce)
+        .append_pair("code_challenge", &pkce_challenge(&code_verifier))
+        .append_pair("code_challenge_method", "S256");
+
+    let now = Utc::now();
+    let session = OidcSession {
+        purpose: SESSION_PURPOSE.to_string(),
+        state_hash: hex::encode(Sha256::digest(state.as_bytes())),
+        code_verifier,
+        nonce,
+        redirect_uri,
+        exp: (now + Duration::seconds(SESSION_TTL_SECS)).timestamp() as usize,
+        iat: now.timestamp() as usize,
+    };
+    let session = encode(
+        &Header::default(),
+        &session,
+        &EncodingKey::from_secret(AUTHENTICATION_SECRET_KEY.as_bytes()),
+    )
+    .map_err(|e| {
+        tracing::error!("Failed to sign OIDC session: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(OidcAuthorizeResponse {
+        authorization_url: authorization_url.to_string(),
+        state,
+        session,
+    }))
+}
+
+pub async fn oidc_auth(
+    extract::Json(request): extract::Json<OidcAuthRequest>,
+) -> Result<Json<AuthResponse>, StatusCode> {
+    verify_oauth_state(&request.state)?;
+    let session = verify_session(&request.session, &request.state)?;
+    let config = oidc_config().map_err(|e| {
+        tracing::error!("{}", e);
+        StatusCode::NOT_FOUND
+    })?;
+    let profile = authenticate(&config, &request.code, &session)
+        .await
+        .map_err(|e| {
+            tracing::error!("OIDC login failed: {}", e);
+            StatusCode::UNAUTHORIZED
+        })?;
+
+    let connection = establish_connection().await.map_err(|e| {
+        tracing::error!("Failed to establish database connection: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let user = upsert_sso_user(&profile, "OIDC", &connection).await?;
+    let (token, user, orgs) = finalize_login(user, "oidc", &connection).await?;
+    Ok(Json(AuthResponse { token, user, orgs }))
+}
+
+fn verify_session(session: &str, state: &str) -> Result<OidcSession, StatusCode> {
+    let session = decode::<OidcSession>(
+        session,
+        &DecodingKey::from_secret(AUTHENTICATION_SECRET_KEY.as_bytes()),
+        &Validation::default(),
+    )
+    .map_err(|e| {
+        tracing::warn!("OIDC session rejected: {}", e);
+        StatusCode::UNAUTHORIZED
+    })?
+    .claims;
+    if session.purpose != SESSION_PURPOSE
+        || session.state_hash != hex::encode(Sha256::digest(state.as_bytes()))
+    {
+        tracing::warn!("OIDC session does not match the OAuth state");
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    Ok(session)
+}
+
+fn pkce_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+async fn get_json<T: serde::de::DeserializeOwned>(
+    request: reqwest::RequestBuilder,
+    what: &str,
+) -> Result<T, OxyError> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| OxyError::AuthenticationError(format!("OIDC {what} request failed: {e}")))?;
+    let status = response.status();
+    if !status.is_success() {
+        let error_body = response.text().await.unwrap_or_default();
+        return Err(OxyError::AuthenticationError(format!(
+            "OIDC {what} request failed with status {status}: {error_body}"
+        )));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| OxyError::AuthenticationError(format!("Invalid OIDC {what} response: {e}")))
+}
+
+async fn discover(
+    client: &reqwest::Client,
+    issuer_url: &str,
+) -> Result<ProviderMetadata, OxyError> {
+    let issuer_url = issuer_url.trim_end_matches('/');
+    let metadata: ProviderMetadata = get_json(
+        client.get(format!("{issuer_url}/.well-known/openid-configuration")),
+        "discovery",
+    )
+    .await?;
+    if metadata.issuer.trim_end_matches('/') != issuer_url {
+        return Err(OxyError::ConfigurationError(format!(
+            "OIDC discovery returned issuer '{}', expected '{issuer_url}'",
+            metadata.issuer
+        )));
+    }
+    Ok(metadata)
+}
+
+/// Exchange the code, validate the ID token and map its claims, completed
+/// from the userinfo endpoint when the ID token lacks the email claim.
+async fn authenticate(
+    config: &OidcAuth,
+    code: &str,
+    session: &OidcSession,
+) -> Result<SsoProfile, OxyError> {
+    let client = reqwest::Client::new();
+    let metadata = discover(&client, &config.issuer_url).await?;
+
+    let mut token_params = vec![
+        ("grant_type", "authorization_code"),
+        ("code", code),
+        ("redirect_uri", session.redirect_uri.as_str()),
+        ("client_id", config.client_id.as_str()),
+        ("code_verifier", session.code_verifier.as_str()),
+    ];
+    if let Some(client_secret) = &config.client_secret {
+        token_params.push(("client_secret", client_secret.as_str()));
+    }
+    let tokens: TokenResponse = get_json(
+        client.post(&metadata.token_endpoint).form(&token_params),
+        "token",
+    )
+    .await?;
+
+    let jwks: JwkSet = get_json(client.get(&metadata.jwks_uri), "JWKS").await?;
+    let mut claims = validate_id_token(
+        &tokens.id_token,
+        &jwks,
+        &metadata.issuer,
+        &config.client_id,
+        &session.nonce,
+    )?;
+
+    if !claims.contains_key(&config.claims.email)
+        && let (Some(userinfo_endpoint), Some(access_token)) =
+            (&metadata.userinfo_endpoint, &tokens.access_token)
+    {
+        let userinfo: Map<String, Value> = get_json(
+            client.get(userinfo_endpoint).bearer_auth(access_token),
+            "userinfo",
+        )
+        .await?;
+        if userinfo.get("sub") != claims.get("sub") {
+            return Err(OxyError::AuthenticationError(
+                "OIDC userinfo subject does not match the ID token".to_string(),
+            ));
+        }
+        for (claim, value) in userinfo {
+            claims.entry(claim).or_insert(value);
+        }
+    }
+
+    map_claims(&claims, config)
+}
+
+fn validate_id_token(
+    id_token: &str,
+    jwks: &JwkSet,
+    issuer: &str,
+    client_id: &str,
+    nonce: &str,
+) -> Result<Map<String, Value>, OxyError> {
+    let invalid = |e: &dyn std::fmt::Display| {
+        OxyError::AuthenticationError(format!("Invalid OIDC ID token: {e}"))
+    };
+    let header = decode_header(id_token).map_err(|e| invalid(&e))?;
+    if matches!(
+        header.alg,
+        Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+    ) {
+        return Err(invalid(&"symmetric signatures are not accepted"));
+    }
+    let jwk = match &header.kid {
+        Some(kid) => jwks.find(kid),
+        None if jwks.keys.len() == 1 => jwks.keys.first(),
+        None => None,
+    }
+    .ok_or_else(|| invalid(&"no matching signing key"))?;
+    let key = DecodingKey::from_jwk(jwk).map_err(|e| invalid(&e))?;
+
+    let mut validation = Validation::new(header.alg);
+    validation.set_issuer(&[issuer]);
+    validation.set_audience(&[client_id]);
+    validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+    let claims = decode::<Map<String, Value>>(id_token, &key, &validation)
+        .map_err(|e| invalid(&e))?
+        .claims;
+
+    if claims.get("nonce").and_then(Value::as_str) != Some(nonce) {
+        return Err(invalid(&"nonce mismatch"));
+    }
+    Ok(claims)
+}
+
+fn map_claims(claims: &Map<String, Value>, config: &OidcAuth) -> Result<SsoProfile, OxyError> {
+    let string_claim = |name: &str| {
+        claims
+            .get(name)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    let email = string_claim(&config.claims.email)
+        .filter(|email| email.contains('@'))
+        .ok_or_else(|| {
+            OxyError::AuthenticationError(format!(
+                "OIDC claims have no email in '{}'",
+                config.claims.email
+            ))
+        })?;
+    // The email links the login to an existing user, so the provider must
+    // vouch for it: either verified, or in a domain the provider owns
+    let domain = email.rsplit('@').next().unwrap_or_default();
+    let verified = match claims.get("email_verified") {
+        Some(Value::Bool(verified)) => *verified,
+        _ => config
+            .allowed_domains
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(domain)),
+    };
+    if !verified {
+        return Err(OxyError::AuthenticationError(format!(
+            "OIDC provider has not verified {email} and its domain is not allowed"
+        )));
+    }
+    let (Some(issuer), Some(subject)) = (string_claim("iss"), string_claim("sub")) else {
+        return Err(OxyError::AuthenticationError(
+            "OIDC claims have no issuer or subject".to_string(),
+        ));
+    };
+    Ok(SsoProfile {
+        issuer,
+        subject,
+        name: string_claim(&config.claims.name).unwrap_or_else(|| email.clone()),
+        picture: string_claim(&config.claims.picture),
+        email,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use oxy::config::auth::OidcClaimMapping;
+    use serde_json::json;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{body_string_contains, method, path},
+    };
+
+    use super::*;
+
+    const IDP_KEY: &str = include_str!("testdata/idp_key.pem");
+    const IDP_KEY_MODULUS: &str = include_str!("testdata/idp_key_modulus.txt");
+
+    fn config(issuer_url: &str) -> OidcAuth {
+        OidcAuth {
+            issuer_url: issuer_url.to_string(),
+            client_id: "oxy".to_string(),
+            client_secret: Some("secret".to_string()),
+            scopes: "openid email profile".to_string(),
+            display_name: "Keycloak".to_string(),
+            claims: OidcClaimMapping {
+                email: "preferred_username".to_string(),
+                ..OidcClaimMapping::default()
+            },
+            allowed_domains: Vec::new(),
+        }
+    }
+
+    fn session() -> OidcSession {
+        OidcSession {
+            purpose: SESSION_PURPOSE.to_string(),
+            state_hash: String::new(),
+            code_verifier: "verifier".to_string(),
+            nonce: "nonce-1".to_string(),
+            redirect_uri: "http://localhost:3000/auth/oidc/callback".to_string(),
+            exp: 0,
+            iat: 0,
+        }
+    }
+
+    fn id_token(issuer: &str, nonce: &str) -> String {
+        let now = Utc::now().timestamp();
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some("idp-key".to_string());
+        encode(
+            &header,
+            &json!({
+                "iss": issuer,
+                "aud": "oxy",
+                "sub": "user-1",
+                "exp": now + 300,
+                "iat": now,
+                "nonce": nonce,
+                "preferred_username": "ada@example.com",
+                "name": "Ada Lovelace",
+                "email_verified": true,
+            }),
+            &EncodingKey::from_rsa_pem(IDP_KEY.as_bytes()).unwrap(),
+        )
+        .unwrap()
+    }
+
+    /// A local identity provider serving discovery, JWKS and a token
+    /// endpoint that only accepts the expected PKCE verifier.
+    async fn mock_idp(nonce: &str) -> MockServer {
+        let server = MockServer::start().await;
+        let issuer = server.uri();
+        Mock::given(method("GET"))
+            .and(path("/.well-known/openid-configuration"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "issuer": issuer,
+                "authorization_endpoint": format!("{issuer}/authorize"),
+                "token_endpoint": format!("{issuer}/token"),
+                "jwks_uri": format!("{issuer}/jwks"),
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/jwks"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "keys": [{
+                    "kty": "RSA",
+                    "kid": "idp-key",
+                    "use": "sig",
+                    "alg": "RS256",
+                    "n": IDP_KEY_MODULUS.trim(),
+                    "e": "AQAB",
+                }]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(body_string_contains("code_verifier=verifier"))
+            .and(body_string_contains("code=code-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id_token": id_token(&issuer, nonce),
+                "access_token": "access-1",
+                "token_type": "Bearer",
+            })))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_against_mock_idp() {
+        let server = mock_idp("nonce-1").await;
+        let profile = authenticate(&config(&server.uri()), "code-1", &session())
+            .await
+            .unwrap();
+        assert_eq!(
+            profile,
+            SsoProfile {
+                issuer: server.uri(),
+                subject: "user-1".to_string(),
+                email: "ada@example.com".to_string(),
+                name: "Ada Lovelace".to_string(),
+                picture: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_rejects_nonce_mismatch() {
+        let server = mock_idp("another-nonce").await;
+        let result = authenticate(&config(&server.uri()), "code-1", &session()).await;
+        assert!(matches!(result, Err(OxyError::AuthenticationError(_))));
+    }
+
+    #[test]
+    fn test_map_claims_requires_verified_email_or_allowed_domain() {
+        let mut config = config("https://idp.example.com");
+        config.claims = OidcClaimMapping::default();
+        let claims = |verified: Value| {
+            json!({
+                "iss": "https://idp.example.com",
+                "sub": "user-1",
+                "email": "ada@example.com",
+                "email_verified": verified,
+            })
+            .as_object()
+            .unwrap()
+            .clone()
+        };
+        assert!(map_claims(&claims(json!(true)), &config).is_ok());
+        assert!(map_claims(&claims(json!(false)), &config).is_err());
+        assert!(map_claims(&claims(Value::Null), &config).is_err());
+
+        config.allowed_domains = vec!["Example.com".to_string()];
+        assert!(map_claims(&claims(Value::Null), &config).is_ok());
+        assert!(map_claims(&claims(json!(false)), &config).is_err());
+    }
+
+    #[test]
+    fn test_pkce_challenge() {
+        // RFC 7636, appendix B
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mJ0lFFM4lFtRJfYQ0r9xo6BLzIDn5U"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGVq-cM"
+        );
+    }
+}
@@ -1,11 +1,14 @@
+use crate::server::api::middlewares::permission_guard::ensure_allowed;
 use crate::server::api::middlewares::role_guards::WorkspaceEditor;
 use crate::server::api::middlewares::workspace_context::WorkspaceManagerExtractor;
 use crate::server::router::WorkspaceExtractor;
+use crate::server::service::permissions::WorkspacePermissions;
 use crate::server::service::task_manager::TASK_MANAGER;
 use axum::{
     extract::{self, Path, Query},
     http::StatusCode,
 };
+use entity::custom_roles::Permission;
 use entity::logs;
 use entity::prelude::Logs;
 use entity::prelude::Threads;
@@ -236,6 +239,25 @@ pub async fn get_thread(
     Ok(extract::Json(thread_item))
 }
 
+/// `source` of threads that talk to the builder copilot rather than an agent.
+const BUILDER_SOURCE: &str = "__builder__";
+
+/// Check the caller may run what a new thread points at. Builder threads
+/// edit workspace files, so they need `files.edit` rather than a run
+/// permission.
+fn ensure_can_run_source(
+    permissions: &WorkspacePermissions,
+    source_type: &str,
+    source: &str,
+) -> Result<(), StatusCode> {
+    match source_type {
+        "workflow" => ensure_allowed(permissions, Permission::RunWorkflows, source),
+        "task" => ensure_allowed(permissions, Permission::EditFiles, source),
+        _ if source == BUILDER_SOURCE => ensure_allowed(permissions, Permission::EditFiles, source),
+        _ => ensure_allowed(permissions, Permission::RunAgents, source),
+    }
+}
+
 /// Create a new thread
 #[utoipa::path(
     post,
@@ -248,6 +270,7 @@ pub async fn get_thread(
         (status = 200, description = "Thread created successfully", body = ThreadItem),
         (status = 400, description = "Invalid request data"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not allowed to run the thread's source"),
         (status = 500, description = "Internal server error")
     ),
     security(
@@ -258,8 +281,15 @@ pub async fn get_thread(
 pub async fn create_thread(
     WorkspaceExtractor(project): WorkspaceExtractor,
     AuthenticatedUserExtractor(user): AuthenticatedUserExtractor,
+    permissions: WorkspacePermissions,
     extract::Json(thread_request): extract::Json<CreateThreadRequest>,
 ) -> Result<extract::Json<ThreadItem>, StatusCode> {
+    ensure_can_run_source(
+        &permissions,
+        &thread_request.source_type,
+        &thread_request.source,
+    )?;
+
     let connection = establish_connection().await.map_err(|e| {
         tracing::error!("Failed to establish database connection: {}", e);
         StatusCode::INTERNAL_SERVER_ERROR
@@ -1,4 +1,5 @@
 use axum::{http::StatusCode, response::Json};
+use oxy::audit::{self, AuditAction, AuditEvent};
 use oxy_auth::extractor::AuthenticatedUserExtractor;
 use oxy_auth::types::AuthenticatedUser;
 use oxy_auth::user::UserService;
@@ -42,7 +43,10 @@ impl From<AuthenticatedUser> for UserInfo {
     }
 }
 
-pub async fn logout() -> Result<Json<LogoutResponse>, StatusCode> {
+pub async fn logout(
+    AuthenticatedUserExtractor(user): AuthenticatedUserExtractor,
+) -> Result<Json<LogoutResponse>, StatusCode> {
+    audit::record(AuditEvent::new(AuditAction::Logout).actor(user.id)).await;
     Ok(Json(LogoutResponse {
         logout_url: None,
         success: true,
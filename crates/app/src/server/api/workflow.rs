@@ -18,6 +18,7 @@ use std::{
 use crate::{
     api::middlewares::{timeout::TimeoutConfig, workspace_context::WorkspaceManagerExtractor},
     service::{
+        schedule::{self as schedule_service, WorkflowScheduleInfo},
         statics::BROADCASTER,
         types::run::RunStatus,
         workflow as service,
@@ -162,6 +163,78 @@ pub async fn get(
     }
 }
 
+/// List workflow schedules
+///
+/// Returns every workflow with a `schedule:` block along with its cron expressions,
+/// timezone, the next slot the scheduler will fire and the last scheduled run.
+#[utoipa::path(
+    method(get),
+    path = "/{workspace_id}/workflows/schedules",
+    params(
+        ("workspace_id" = Uuid, Path, description = "Workspace UUID")
+    ),
+    responses(
+        (status = 200, description = "Success", body = Vec<WorkflowScheduleInfo>, content_type = "application/json"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("ApiKey" = [])
+    ),
+    tag = "Automations"
+)]
+pub async fn list_schedules(
+    WorkspaceManagerExtractor(workspace_manager): WorkspaceManagerExtractor,
+) -> Result<extract::Json<Vec<WorkflowScheduleInfo>>, StatusCode> {
+    schedule_service::list_schedules(&workspace_manager)
+        .await
+        .map(extract::Json)
+        .map_err(|e| {
+            tracing::error!("Failed to list workflow schedules: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+/// Get the schedule of a workflow
+///
+/// Returns the workflow's cron expressions and timezone with its next and last
+/// scheduled run times. Responds 404 when the workflow has no `schedule:` block.
+#[utoipa::path(
+    method(get),
+    path = "/{workspace_id}/workflows/{pathb64}/schedule",
+    params(
+        ("workspace_id" = Uuid, Path, description = "Workspace UUID"),
+        ("pathb64" = String, Path, description = "Base64 encoded path to the workflow")
+    ),
+    responses(
+        (status = 200, description = "Success", body = WorkflowScheduleInfo, content_type = "application/json"),
+        (status = 400, description = "Bad request - invalid path encoding"),
+        (status = 404, description = "Workflow has no schedule"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("ApiKey" = [])
+    ),
+    tag = "Automations"
+)]
+pub async fn get_schedule(
+    Path((_workspace_id, pathb64)): Path<(Uuid, String)>,
+    WorkspaceManagerExtractor(workspace_manager): WorkspaceManagerExtractor,
+) -> Result<extract::Json<WorkflowScheduleInfo>, StatusCode> {
+    let decoded_path = BASE64_STANDARD
+        .decode(pathb64)
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    let path = PathBuf::from(String::from_utf8(decoded_path).map_err(|_| StatusCode::BAD_REQUEST)?);
+
+    schedule_service::get_schedule(&workspace_manager, &path)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get workflow schedule: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .map(extract::Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
 #[derive(Serialize, ToSchema)]
 pub struct GetLogsResponse {
     logs: Vec<LogItem>,
@@ -1559,6 +1632,8 @@ fn reconstruct_events_from_run_details(
             description: String::new(),
             retrieval: None,
             consistency_prompt: None,
+            schedule: None,
+            budget: None,
         },
     });
 
@@ -5,8 +5,9 @@ use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use chrono::Utc;
 use entity::org_members::OrgRole;
-use entity::prelude::{OrgMembers, Users, WorkspaceMembers};
+use entity::prelude::{CustomRoleAssignments, OrgMembers, Users, WorkspaceMembers};
 use entity::workspace_members::WorkspaceRole;
+use oxy::audit::{self, AuditAction, AuditEvent};
 use oxy::database::client::establish_connection;
 use sea_orm::{ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, QueryFilter};
 use serde::{Deserialize, Serialize};
@@ -27,6 +28,7 @@ pub struct WorkspaceMemberResponse {
     pub org_role: String,
     pub workspace_role: String,
     pub is_override: bool,
+    pub custom_role_id: Option<Uuid>,
 }
 
 #[derive(Deserialize)]
@@ -109,6 +111,16 @@ pub async fn list_workspace_members(
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
+    use entity::custom_role_assignments::Column as CraCol;
+    let custom_role_map: std::collections::HashMap<Uuid, Uuid> = CustomRoleAssignments::find()
+        .filter(CraCol::WorkspaceId.eq(workspace.id))
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .map(|a| (a.user_id, a.custom_role_id))
+        .collect();
+
     // Collect user IDs to fetch user details
     let user_ids: Vec<Uuid> = org_members.iter().map(|m| m.user_id).collect();
 
@@ -144,6 +156,7 @@ pub async fn list_workspace_members(
                 org_role: om.role.as_str().to_string(),
                 workspace_role: workspace_role.as_str().to_string(),
                 is_override,
+                custom_role_id: custom_role_map.get(&om.user_id).copied(),
             })
         })
         .collect();
@@ -195,6 +208,8 @@ pub async fn set_workspace_role_override(
     validate_role_override(&org_membership, &target_membership, &role)
         .map_err(|s| s.into_response())?;
 
+    let role_name = role.as_str();
+
     // Check if override already exists
     use entity::workspace_members::Column as WmCol;
     let existing = WorkspaceMembers::find()
@@ -229,6 +244,15 @@ pub async fn set_workspace_role_override(
         })?;
     }
 
+    audit::record(
+        AuditEvent::new(AuditAction::WorkspaceRoleOverridden)
+            .workspace(workspace.id)
+            .actor(org_membership.user_id)
+            .target("user", user_id)
+            .meta("role", role_name),
+    )
+    .await;
+
     Ok(Json(serde_json::json!({ "ok": true })))
 }
 
@@ -282,6 +306,13 @@ pub async fn remove_workspace_role_override(
             tracing::error!("Failed to delete workspace member override: {}", e);
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
+        audit::record(
+            AuditEvent::new(AuditAction::WorkspaceRoleOverrideRemoved)
+                .workspace(workspace.id)
+                .actor(org_membership.user_id)
+                .target("user", user_id),
+        )
+        .await;
     }
 
     Ok(Json(serde_json::json!({ "ok": true })))
@@ -20,6 +20,7 @@ use oxy::adapters::workspace::effective_workspace_path;
 use oxy::api_types::{
     BranchType, CommitEntry, ProjectBranch, RecentCommitsResponse, RevisionInfoResponse,
 };
+use oxy::audit::{self, AuditAction, AuditEvent};
 use oxy::config::ConfigBuilder;
 use oxy::github::{default_git_client, github_token_for_workspace};
 use oxy_auth::extractor::AuthenticatedUserExtractor;
@@ -1290,7 +1291,7 @@ pub struct DeleteProjectQuery {
 }
 
 pub async fn delete_workspace(
-    _: OrgAdmin,
+    OrgAdmin(ctx): OrgAdmin,
     State(_app_state): State<AppState>,
     Path((org_id, workspace_id)): Path<(Uuid, Uuid)>,
     Query(query): Query<DeleteProjectQuery>,
@@ -1320,6 +1321,7 @@ pub async fn delete_workspace(
 
     // Capture the workspace path before deleting the record
     let workspace_path = effective_workspace_path(&workspace, None).await.ok();
+    let workspace_name = workspace.name.clone();
 
     workspace.delete(&db).await.map_err(|e| {
         error!("Failed to delete workspace {}: {}", workspace_id, e);
@@ -1327,6 +1329,16 @@ pub async fn delete_workspace(
     })?;
 
     info!("Deleted workspace {}", workspace_id);
+    audit::record(
+        AuditEvent::new(AuditAction::WorkspaceDeleted)
+            .org(org_id)
+            .workspace(workspace_id)
+            .actor(ctx.membership.user_id)
+            .target("workspace", workspace_id)
+            .meta("name", workspace_name)
+            .meta("delete_files", query.delete_files),
+    )
+    .await;
 
     // Only remove files from disk when the caller explicitly opts in.
     // Without `?delete_files=true` we only remove the DB record, leaving
@@ -25,11 +25,13 @@ use crate::server::builder_app_runner::OxyAppRunner;
 use crate::server::builder_test_runner::OxyTestRunner;
 use crate::server::serve_mode::ServeMode;
 
+use super::mcp::{build_local_mcp_routes, build_mcp_routes};
 use super::protected::{
     apply_local_middleware, apply_middleware, build_local_protected_routes, build_protected_routes,
 };
 use super::public::build_public_routes;
 use super::recovery::{spawn_recovery, spawn_shutdown_hook};
+use super::scim::build_scim_routes;
 use super::{AppState, build_cors_layer};
 
 pub async fn api_router(
@@ -59,11 +61,14 @@ pub async fn api_router(
             // Disabled for now; will re-enable later.
             // spawn_billing_reconciler().await;
             apply_middleware(build_protected_routes(app_state.clone(), agentic_state))?
+                .merge(build_mcp_routes(app_state.clone()))
+                .merge(build_scim_routes())
         }
         ServeMode::Local => apply_local_middleware(build_local_protected_routes(
             app_state.clone(),
             agentic_state,
-        ))?,
+        ))?
+        .merge(build_local_mcp_routes()),
     };
     let app_routes = build_public_routes().merge(protected_routes);
 
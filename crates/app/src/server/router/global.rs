@@ -1,18 +1,20 @@
 //! Cloud-only global routes: logout, organization CRUD (with org-scoped
-//! sub-routes for members, invitations, onboarding, workspaces, GitHub
-//! integration) and the per-user GitHub account/installation routes.
+//! sub-routes for members, invitations, audit log, onboarding, workspaces,
+//! GitHub integration, SCIM settings) and the per-user GitHub
+//! account/installation routes.
 //!
 //! Not mounted in local mode — see [`super::protected`].
 
 use axum::Router;
 use axum::middleware;
-use axum::routing::{delete, get, patch, post};
+use axum::routing::{delete, get, patch, post, put};
 
 use crate::api::billing;
 use crate::api::github::namespaces as github;
 use crate::api::github::{account, callback, installations};
 use crate::api::middlewares::{org_context, oxy_owner_guard, subscription_guard};
-use crate::api::{admin, onboarding, organizations, user, workspaces};
+use crate::api::scim::admin as scim_admin;
+use crate::api::{admin, audit_logs, onboarding, organizations, user, workspaces};
 
 use super::AppState;
 
@@ -58,6 +60,8 @@ fn build_org_routes() -> Router<AppState> {
         .route("/", patch(organizations::update_org))
         .route("/", delete(organizations::delete_org))
         .route("/members", get(organizations::list_members))
+        .route("/audit-logs", get(audit_logs::list_audit_logs))
+        .route("/audit-logs/export", get(audit_logs::export_audit_logs))
         .route(
             "/members/{user_id}",
             patch(organizations::update_member_role),
@@ -83,6 +87,7 @@ fn build_org_routes() -> Router<AppState> {
             patch(workspaces::rename_workspace),
         )
         .nest("/github", build_github_routes())
+        .nest("/scim", build_scim_admin_routes())
         // Slack installation management (requires org membership, admin check inside handlers)
         .route(
             "/slack/install",
@@ -104,6 +109,15 @@ fn build_org_routes() -> Router<AppState> {
         .layer(middleware::from_fn(org_context::org_middleware))
 }
 
+fn build_scim_admin_routes() -> Router<AppState> {
+    Router::new()
+        .route("/tokens", get(scim_admin::list_tokens))
+        .route("/tokens", post(scim_admin::create_token))
+        .route("/tokens/{token_id}", delete(scim_admin::delete_token))
+        .route("/mappings", get(scim_admin::list_mappings))
+        .route("/mappings", put(scim_admin::replace_mappings))
+}
+
 fn build_github_routes() -> Router<AppState> {
     Router::new()
         .route("/repositories", get(github::list_repositories))
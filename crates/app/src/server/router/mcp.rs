@@ -0,0 +1,44 @@
+//! The hosted MCP endpoint, `/{workspace_id}/mcp`.
+//!
+//! Kept out of the protected tree because MCP clients authenticate with API
+//! keys only: in cloud mode the route gets [`api_key_auth_middleware`] instead
+//! of the JWT-or-key `auth_middleware`, then the usual `workspace_middleware`
+//! for membership. Local mode reuses the guest-only stack.
+
+use axum::Router;
+use axum::middleware;
+use axum::routing::any;
+
+use oxy_auth::middleware::{AuthState, api_key_auth_middleware, auth_middleware};
+
+use crate::api::middlewares::local_context::local_context_middleware;
+use crate::api::middlewares::workspace_context::workspace_middleware;
+use crate::integrations::mcp::mcp_http_handler;
+
+use super::AppState;
+
+const MCP_PATH: &str = "/{workspace_id}/mcp";
+
+pub(super) fn build_mcp_routes(app_state: AppState) -> Router<AppState> {
+    Router::new().route(
+        MCP_PATH,
+        any(mcp_http_handler)
+            .layer(middleware::from_fn_with_state(
+                app_state,
+                workspace_middleware,
+            ))
+            .layer(middleware::from_fn(api_key_auth_middleware)),
+    )
+}
+
+pub(super) fn build_local_mcp_routes() -> Router<AppState> {
+    Router::new().route(
+        MCP_PATH,
+        any(mcp_http_handler)
+            .layer(middleware::from_fn(local_context_middleware))
+            .layer(middleware::from_fn_with_state(
+                AuthState::guest_only(),
+                auth_middleware,
+            )),
+    )
+}
@@ -5,15 +5,19 @@
 //! - [`workspace`] — the per-workspace route tree and its sub-builders
 //! - [`secrets`] — secret routes gated behind an admin-only middleware
 //! - [`protected`] — cloud/local composition of protected routes + middleware
+//! - [`mcp`] — the hosted MCP endpoint with its API-key-only auth stack
+//! - [`scim`] — cloud-only SCIM provisioning with its SCIM-token auth stack
 //! - [`entry`] — [`api_router`] / [`internal_api_router`] public entry points
 //! - [`openapi`] — the utoipa OpenAPI router used by Swagger UI
 
 mod entry;
 mod global;
+mod mcp;
 mod openapi;
 mod protected;
 mod public;
 mod recovery;
+mod scim;
 mod secrets;
 mod workspace;
 
@@ -166,6 +170,47 @@ mod router_split_tests {
         );
     }
 
+    #[tokio::test]
+    async fn cloud_mcp_endpoint_requires_api_key() {
+        let router = api_router(
+            ServeMode::Cloud,
+            false,
+            None,
+            std::path::PathBuf::new(),
+            tokio_util::sync::CancellationToken::new(),
+        )
+        .await
+        .expect("router built");
+        let uri = format!("/{}/mcp", uuid::Uuid::new_v4());
+        let req = Request::builder()
+            .method("POST")
+            .uri(&uri)
+            .body(Body::empty())
+            .unwrap();
+        let resp = router.oneshot(req).await.expect("oneshot");
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn cloud_scim_endpoint_requires_scim_token() {
+        let router = api_router(
+            ServeMode::Cloud,
+            false,
+            None,
+            std::path::PathBuf::new(),
+            tokio_util::sync::CancellationToken::new(),
+        )
+        .await
+        .expect("router built");
+        let req = Request::builder()
+            .uri("/scim/v2/Users")
+            .header("authorization", "Bearer not-a-scim-token")
+            .body(Body::empty())
+            .unwrap();
+        let resp = router.oneshot(req).await.expect("oneshot");
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
     #[tokio::test]
     async fn cloud_router_still_has_organizations_mounted() {
         let router = api_router(
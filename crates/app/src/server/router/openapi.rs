@@ -57,6 +57,8 @@ pub async fn openapi_router() -> OpenApiRouter<AppState> {
         .routes(routes!(workflow::list))
         .routes(routes!(workflow::get))
         .routes(routes!(workflow::get_logs))
+        .routes(routes!(workflow::list_schedules))
+        .routes(routes!(workflow::get_schedule))
         .routes(routes!(workflow::run_workflow))
         .routes(routes!(workflow::run_workflow_sync))
         .routes(routes!(workflow::run_workflow_thread))
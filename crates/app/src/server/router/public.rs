@@ -4,7 +4,7 @@
 use axum::Router;
 use axum::routing::{get, post};
 
-use crate::api::{auth, billing, healthcheck, user};
+use crate::api::{auth, billing, healthcheck, sso, user};
 
 use super::AppState;
 
@@ -19,6 +19,8 @@ pub(super) fn build_public_routes() -> Router<AppState> {
         .route("/auth/google", post(auth::google_auth))
         .route("/auth/github", post(auth::github_auth))
         .route("/auth/okta", post(auth::okta_auth))
+        .route("/auth/oidc/authorize", post(sso::oidc::authorize))
+        .route("/auth/oidc", post(sso::oidc::oidc_auth))
         .route("/auth/magic-link/request", post(auth::request_magic_link))
         .route("/auth/magic-link/verify", post(auth::verify_magic_link))
         .route("/user", get(user::get_current_user_public))
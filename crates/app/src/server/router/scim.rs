@@ -0,0 +1,43 @@
+//! SCIM 2.0 provisioning endpoints, `/scim/v2/…`.
+//!
+//! Kept out of the protected tree because identity providers authenticate
+//! with an org-scoped SCIM token, not a user session or API key. Cloud only;
+//! local mode has no orgs to provision into.
+
+use axum::Router;
+use axum::middleware;
+use axum::routing::get;
+
+use crate::api::scim::{self, groups, scim_auth_middleware, users};
+
+use super::AppState;
+
+pub(super) fn build_scim_routes() -> Router<AppState> {
+    let routes = Router::new()
+        .route(
+            "/ServiceProviderConfig",
+            get(scim::get_service_provider_config),
+        )
+        .route("/Users", get(users::list_users).post(users::create_user))
+        .route(
+            "/Users/{id}",
+            get(users::get_user)
+                .put(users::replace_user)
+                .patch(users::patch_user)
+                .delete(users::delete_user),
+        )
+        .route(
+            "/Groups",
+            get(groups::list_groups).post(groups::create_group),
+        )
+        .route(
+            "/Groups/{id}",
+            get(groups::get_group)
+                .put(groups::replace_group)
+                .patch(groups::patch_group)
+                .delete(groups::delete_group),
+        )
+        .layer(middleware::from_fn(scim_auth_middleware));
+
+    Router::new().nest("/scim/v2", routes)
+}
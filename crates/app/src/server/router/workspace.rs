@@ -8,15 +8,19 @@
 use std::sync::Arc;
 
 use axum::Router;
+use axum::middleware;
 use axum::routing::{delete, get, post, put};
 
 use agentic_http::{AgenticState, router as agentic_router};
 
+use entity::custom_roles::Permission::*;
+
+use crate::api::middlewares::permission_guard::{Access, provide_run_access, require_access};
 use crate::api::{
-    agent, api_keys, app, artifacts, chart, data, data_repo, database, execution_analytics,
-    exported_chart, file, integration, local_setup, message, metrics, modeling, onboarding,
-    result_files, run, semantic, task, test_file, test_project_run, test_run, thread, traces,
-    workflow, workspace_members, workspaces,
+    agent, api_keys, app, artifacts, chart, custom_roles, data, data_repo, database,
+    execution_analytics, exported_chart, file, integration, local_setup, message, metrics,
+    modeling, onboarding, result_files, run, semantic, task, test_file, test_project_run, test_run,
+    thread, traces, workflow, workspace_members, workspaces,
 };
 
 use super::AppState;
@@ -32,31 +36,64 @@ pub(super) fn build_workspace_routes(
         .route("/details", get(workspaces::get_workspace))
         .route("/status", get(workspaces::get_workspace_status))
         .nest("/workflows", build_workflow_routes())
-        .nest("/automations", build_automation_routes())
+        .nest(
+            "/automations",
+            with_access(build_automation_routes(), Access::all(EditFiles)),
+        )
         .nest("/threads", build_thread_routes())
-        .nest("/agents", build_agent_routes())
-        .nest("/api-keys", build_api_key_routes())
-        .nest("/files", build_file_routes(include_git_features))
-        .nest("/databases", build_database_routes())
-        .nest("/integrations", build_integration_routes())
-        .nest("/secrets", build_secret_routes(app_state))
-        .route("/members", get(workspace_members::list_workspace_members))
-        .route(
-            "/members/{user_id}",
-            put(workspace_members::set_workspace_role_override),
+        .nest(
+            "/agents",
+            with_access(build_agent_routes(), Access::all(RunAgents)),
         )
-        .route(
-            "/members/{user_id}",
-            delete(workspace_members::remove_workspace_role_override),
+        .nest(
+            "/api-keys",
+            with_access(build_api_key_routes(), Access::writes(ManageOwnApiKeys)),
         )
-        .nest("/tests", build_test_file_routes())
-        .nest("/apps", build_app_routes())
-        .nest("/traces", traces::traces_routes())
-        .nest("/metrics", metrics::metrics_routes())
         .nest(
-            "/execution-analytics",
-            execution_analytics::execution_analytics_routes(),
+            "/files",
+            with_access(
+                build_file_routes(include_git_features),
+                Access::split(ReadFiles, EditFiles),
+            ),
         )
+        .nest(
+            "/databases",
+            with_access(
+                build_database_routes(),
+                Access::split(QueryDatabases, ManageDatabases),
+            ),
+        )
+        .nest(
+            "/integrations",
+            with_access(build_integration_routes(), Access::all(QueryDatabases)),
+        )
+        .nest(
+            "/secrets",
+            with_access(build_secret_routes(app_state), Access::all(ManageSecrets)),
+        )
+        .merge(with_access(
+            build_member_routes(),
+            Access::writes(ManageMembers),
+        ))
+        .nest(
+            "/roles",
+            with_access(build_custom_role_routes(), Access::writes(ManageMembers)),
+        )
+        .nest(
+            "/tests",
+            with_access(build_test_file_routes(), Access::split(ReadFiles, RunTests)),
+        )
+        .nest("/apps", build_app_routes())
+        .merge(with_access(
+            Router::new()
+                .nest("/traces", traces::traces_routes())
+                .nest("/metrics", metrics::metrics_routes())
+                .nest(
+                    "/execution-analytics",
+                    execution_analytics::execution_analytics_routes(),
+                ),
+            Access::all(ViewTraces),
+        ))
         .route("/artifacts/{id}", get(artifacts::get_artifact))
         .route("/charts/{file_path}", get(chart::get_chart))
         .route(
@@ -80,20 +117,32 @@ pub(super) fn build_workspace_routes(
             "/onboarding-readiness",
             get(onboarding::onboarding_readiness),
         )
-        .route("/onboarding/github-setup", get(onboarding::github_setup))
-        .nest("/onboarding", build_onboarding_routes())
-        .route("/sql/{pathb64}", post(data::execute_sql))
-        .route("/sql/query", post(data::execute_sql_query))
-        .route("/semantic", post(semantic::execute_semantic_query))
-        .route("/semantic/compile", post(semantic::compile_semantic_query))
-        .route(
-            "/semantic/topic/{file_path_b64}",
-            get(semantic::get_topic_details),
-        )
-        .route(
-            "/semantic/view/{file_path_b64}",
-            get(semantic::get_view_details),
-        )
+        .merge(with_access(
+            Router::new()
+                .route("/onboarding/github-setup", get(onboarding::github_setup))
+                .nest("/onboarding", build_onboarding_routes()),
+            Access::all(EditFiles),
+        ))
+        .merge(with_access(
+            Router::new()
+                .route("/sql/{pathb64}", post(data::execute_sql))
+                .route("/sql/query", post(data::execute_sql_query)),
+            Access::all(QueryDatabases),
+        ))
+        .merge(with_access(
+            Router::new()
+                .route("/semantic", post(semantic::execute_semantic_query))
+                .route("/semantic/compile", post(semantic::compile_semantic_query))
+                .route(
+                    "/semantic/topic/{file_path_b64}",
+                    get(semantic::get_topic_details),
+                )
+                .route(
+                    "/semantic/view/{file_path_b64}",
+                    get(semantic::get_view_details),
+                ),
+            Access::all(QuerySemantic),
+        ))
         .route(
             "/results/files/{file_id}",
             get(result_files::get_result_file),
@@ -102,24 +151,54 @@ pub(super) fn build_workspace_routes(
             "/results/files/{file_id}",
             delete(result_files::delete_result_file),
         )
-        .nest("/analytics", agentic_router(agentic_state))
-        .nest("/modeling", modeling::build_modeling_routes());
+        .nest(
+            "/analytics",
+            with_access(
+                agentic_router(agentic_state).route_layer(middleware::from_fn(provide_run_access)),
+                Access::all(RunAgents),
+            ),
+        )
+        .nest(
+            "/modeling",
+            with_access(
+                modeling::build_modeling_routes(),
+                Access::all(ManageDatabases),
+            ),
+        );
 
     if include_git_features {
         router = router
-            .merge(build_git_routes())
-            .nest("/repositories", build_data_repo_routes());
+            .merge(with_access(
+                build_git_routes(),
+                Access::split(ReadFiles, EditFiles),
+            ))
+            .nest(
+                "/repositories",
+                with_access(
+                    build_data_repo_routes(),
+                    Access::split(ReadFiles, EditFiles),
+                ),
+            );
     }
 
     if include_local_setup {
-        router = router
-            .route("/setup/empty", post(local_setup::setup_empty))
-            .route("/setup/demo", post(local_setup::setup_demo));
+        router = router.merge(with_access(
+            Router::new()
+                .route("/setup/empty", post(local_setup::setup_empty))
+                .route("/setup/demo", post(local_setup::setup_demo)),
+            Access::all(EditFiles),
+        ));
     }
 
     router
 }
 
+/// Checks the caller's permissions on every route of `router`, see
+/// [`permission_guard`](crate::api::middlewares::permission_guard).
+fn with_access(router: Router<AppState>, access: Access) -> Router<AppState> {
+    router.route_layer(middleware::from_fn_with_state(access, require_access))
+}
+
 /// Git-backed workspace routes: local and remote git operations on the
 /// workspace itself. Mounted only when `include_git_features` is true —
 /// local mode (`ServeMode::Local`) omits the entire set.
@@ -150,27 +229,37 @@ fn build_git_routes() -> Router<AppState> {
         .route("/reset-to-commit", post(workspaces::reset_to_commit))
 }
 
+/// Runs are part of running a workflow, so reading them needs
+/// `workflows.run` too, scoped to the workflow in the path.
 fn build_workflow_routes() -> Router<AppState> {
-    Router::new()
+    let create = Router::new().route("/from-query", post(workflow::create_from_query));
+    let workflows = Router::new()
         .route("/", get(workflow::list))
-        .route("/from-query", post(workflow::create_from_query))
         .route("/runs/bulk-delete", post(run::bulk_delete_workflow_runs))
+        .route("/schedules", get(workflow::list_schedules))
         .route("/{pathb64}", get(workflow::get))
         .route("/{pathb64}/run", post(workflow::run_workflow))
         .route("/{pathb64}/run-sync", post(workflow::run_workflow_sync))
         .route("/{pathb64}/logs", get(workflow::get_logs))
+        .route("/{pathb64}/schedule", get(workflow::get_schedule))
         .route("/{pathb64}/runs", get(run::get_workflow_runs))
         .route("/{pathb64}/runs", post(run::create_workflow_run))
         .route(
             "/{pathb64}/runs/{run_id}",
             get(workflow::get_workflow_run).delete(run::delete_workflow_run),
-        )
+        );
+
+    with_access(workflows, Access::all(RunWorkflows))
+        .merge(with_access(create, Access::all(EditFiles)))
 }
 
 fn build_automation_routes() -> Router<AppState> {
     Router::new().route("/save", post(workflow::save_automation))
 }
 
+/// Threads belong to their creator, so managing them needs no permission;
+/// `create_thread` checks the thread's source, and running one needs the
+/// permission for its kind.
 fn build_thread_routes() -> Router<AppState> {
     Router::new()
         .route("/", get(thread::get_threads))
@@ -179,16 +268,27 @@ fn build_thread_routes() -> Router<AppState> {
         .route("/bulk-delete", post(thread::bulk_delete_threads))
         .route("/{id}", get(thread::get_thread))
         .route("/{id}", delete(thread::delete_thread))
-        .route("/{id}/task", post(task::ask_task))
-        .route("/{id}/agentic", post(task::ask_agentic))
-        .route("/{id}/workflow", post(workflow::run_workflow_thread))
-        .route(
-            "/{id}/workflow-sync",
-            post(workflow::run_workflow_thread_sync),
-        )
         .route("/{id}/messages", get(message::get_messages_by_thread))
-        .route("/{id}/agent", post(agent::ask_agent))
         .route("/{id}/stop", post(thread::stop_thread))
+        .merge(with_access(
+            Router::new().route("/{id}/task", post(task::ask_task)),
+            Access::all(EditFiles),
+        ))
+        .merge(with_access(
+            Router::new()
+                .route("/{id}/agentic", post(task::ask_agentic))
+                .route("/{id}/agent", post(agent::ask_agent)),
+            Access::all(RunAgents),
+        ))
+        .merge(with_access(
+            Router::new()
+                .route("/{id}/workflow", post(workflow::run_workflow_thread))
+                .route(
+                    "/{id}/workflow-sync",
+                    post(workflow::run_workflow_thread_sync),
+                ),
+            Access::all(RunWorkflows),
+        ))
 }
 
 fn build_agent_routes() -> Router<AppState> {
@@ -197,7 +297,10 @@ fn build_agent_routes() -> Router<AppState> {
         .route("/{pathb64}", get(agent::get_agent))
         .route("/{pathb64}/ask", post(agent::ask_agent_preview))
         .route("/{pathb64}/ask-sync", post(agent::ask_agent_sync))
-        .route("/{pathb64}/tests/{test_index}", post(agent::run_test))
+        .merge(with_access(
+            Router::new().route("/{pathb64}/tests/{test_index}", post(agent::run_test)),
+            Access::all(RunTests),
+        ))
 }
 
 fn build_api_key_routes() -> Router<AppState> {
@@ -288,6 +391,32 @@ fn build_integration_routes() -> Router<AppState> {
         .route("/looker/query/sql", post(integration::compile_looker_query))
 }
 
+fn build_member_routes() -> Router<AppState> {
+    Router::new()
+        .route("/members", get(workspace_members::list_workspace_members))
+        .route(
+            "/members/{user_id}",
+            put(workspace_members::set_workspace_role_override),
+        )
+        .route(
+            "/members/{user_id}",
+            delete(workspace_members::remove_workspace_role_override),
+        )
+        .route(
+            "/members/{user_id}/custom-role",
+            put(custom_roles::assign_custom_role).delete(custom_roles::unassign_custom_role),
+        )
+}
+
+fn build_custom_role_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(custom_roles::list_custom_roles))
+        .route("/", post(custom_roles::create_custom_role))
+        .route("/permissions", get(custom_roles::list_permissions))
+        .route("/{role_id}", put(custom_roles::update_custom_role))
+        .route("/{role_id}", delete(custom_roles::delete_custom_role))
+}
+
 fn build_test_file_routes() -> Router<AppState> {
     Router::new()
         .route("/", get(test_file::list_test_files))
@@ -322,17 +451,24 @@ fn build_test_file_routes() -> Router<AppState> {
         )
 }
 
+/// App routes are scoped to the app in the path; `/file` and `/source`
+/// take other files' paths, so they only need `files.read`.
 fn build_app_routes() -> Router<AppState> {
-    Router::new()
+    let apps = Router::new()
         .route("/", get(app::list_apps))
         .route("/{pathb64}", get(app::get_app_data))
         .route("/{pathb64}/run", post(app::run_app))
         .route("/{pathb64}/result", post(app::get_app_result))
         .route("/{pathb64}/displays", get(app::get_displays))
-        .route("/{pathb64}/charts/{chart_path}", get(app::get_chart_image))
+        .route("/{pathb64}/charts/{chart_path}", get(app::get_chart_image));
+    let files = Router::new()
         .route("/file/{pathb64}", get(app::get_data))
-        .route("/source/{pathb64}", get(app::get_source_file))
-        .route("/save-from-run/{run_id}", post(app::save_app_builder_run))
+        .route("/source/{pathb64}", get(app::get_source_file));
+    let save = Router::new().route("/save-from-run/{run_id}", post(app::save_app_builder_run));
+
+    with_access(apps, Access::all(RunApps))
+        .merge(with_access(files, Access::all(ReadFiles)))
+        .merge(with_access(save, Access::all(EditFiles)))
 }
 
 #[cfg(test)]
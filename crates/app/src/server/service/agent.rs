@@ -59,6 +59,8 @@ pub enum ExecutionSource {
     },
     /// Executed from MCP (Model Context Protocol)
     Mcp { session_id: Option<String> },
+    /// Executed by the workflow scheduler for a cron slot (RFC 3339)
+    Schedule { scheduled_at: String },
     /// Internal/programmatic execution (tests, etc)
     Internal,
 }
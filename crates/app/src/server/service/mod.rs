@@ -11,7 +11,9 @@ pub mod app;
 pub mod chat;
 pub mod eval;
 pub mod formatters; // CLI-specific formatters (different from oxy::service::formatters)
+pub mod permissions;
 pub mod project;
+pub mod schedule;
 pub mod test;
 pub mod test_runs;
 pub mod thread;
@@ -0,0 +1,206 @@
+//! What a caller may do inside a workspace.
+//!
+//! Every workspace role maps to a fixed set of [`Permission`]s. A custom role
+//! assigned to the member, or attached to the API key they authenticated
+//! with, narrows that set: a permission is allowed only when the built-in
+//! role and every custom role in play allow it. Custom roles never grant
+//! more than the built-in role, so raising someone's access still goes
+//! through a workspace role override. Member assignments do not apply to
+//! workspace owners, so a workspace can never lock out all its owners.
+
+use entity::custom_roles::{Grant, Permission};
+use entity::prelude::{CustomRoleAssignments, CustomRoles};
+use entity::workspace_members::WorkspaceRole;
+use entity::{custom_role_assignments, custom_roles};
+use oxy_shared::errors::OxyError;
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter};
+use uuid::Uuid;
+
+#[derive(Clone, Debug)]
+pub struct WorkspacePermissions {
+    base: Vec<Permission>,
+    /// Grants of each custom role that applies; all of them must allow
+    limits: Vec<Vec<Grant>>,
+}
+
+impl WorkspacePermissions {
+    /// Unrestricted, as in local mode.
+    pub fn all() -> Self {
+        Self {
+            base: Permission::ALL.to_vec(),
+            limits: Vec::new(),
+        }
+    }
+
+    pub fn for_role(role: &WorkspaceRole) -> Self {
+        use Permission::*;
+
+        let base = match role {
+            WorkspaceRole::Owner | WorkspaceRole::Admin => Permission::ALL.to_vec(),
+            WorkspaceRole::Member => Permission::ALL
+                .into_iter()
+                .filter(|permission| !matches!(permission, ManageSecrets | ManageMembers))
+                .collect(),
+            WorkspaceRole::Viewer => vec![
+                ReadFiles,
+                RunAgents,
+                RunWorkflows,
+                RunApps,
+                QueryDatabases,
+                QuerySemantic,
+                ViewTraces,
+            ],
+        };
+        Self {
+            base,
+            limits: Vec::new(),
+        }
+    }
+
+    /// Narrow to what `grants` allow as well.
+    pub fn limited_by(mut self, grants: Vec<Grant>) -> Self {
+        self.limits.push(grants);
+        self
+    }
+
+    /// Apply the custom roles in play in `workspace_id`: the one assigned to
+    /// `member` (pass `None` for owners) and the one on the caller's API key.
+    pub async fn narrowed<C: ConnectionTrait>(
+        self,
+        db: &C,
+        workspace_id: Uuid,
+        member: Option<Uuid>,
+        api_key_role_id: Option<Uuid>,
+    ) -> Result<Self, OxyError> {
+        let mut permissions = self;
+        if let Some(user_id) = member {
+            let assignment = CustomRoleAssignments::find()
+                .filter(custom_role_assignments::Column::WorkspaceId.eq(workspace_id))
+                .filter(custom_role_assignments::Column::UserId.eq(user_id))
+                .one(db)
+                .await
+                .map_err(|e| OxyError::DBError(format!("Failed to load custom role: {e}")))?;
+            if let Some(assignment) = assignment {
+                let grants = role_grants(db, workspace_id, assignment.custom_role_id).await?;
+                permissions = permissions.limited_by(grants);
+            }
+        }
+        if let Some(role_id) = api_key_role_id {
+            let grants = role_grants(db, workspace_id, role_id).await?;
+            permissions = permissions.limited_by(grants);
+        }
+        Ok(permissions)
+    }
+
+    /// Whether `permission` is allowed on `resource`. With `resource: None`,
+    /// whether it is allowed on anything at all; handlers that know the
+    /// resource check again with it.
+    pub fn allows(&self, permission: Permission, resource: Option<&str>) -> bool {
+        self.base.contains(&permission)
+            && self.limits.iter().all(|grants| {
+                grants
+                    .iter()
+                    .any(|grant| grant.allows(permission, resource))
+            })
+    }
+}
+
+/// Grants of a custom role. A role that is missing or belongs to another
+/// workspace allows nothing.
+async fn role_grants<C: ConnectionTrait>(
+    db: &C,
+    workspace_id: Uuid,
+    role_id: Uuid,
+) -> Result<Vec<Grant>, OxyError> {
+    let role = CustomRoles::find_by_id(role_id)
+        .filter(custom_roles::Column::WorkspaceId.eq(workspace_id))
+        .one(db)
+        .await
+        .map_err(|e| OxyError::DBError(format!("Failed to load custom role: {e}")))?;
+    match role {
+        Some(role) => Ok(role.grants()),
+        None => {
+            tracing::warn!("Custom role {role_id} not found in workspace {workspace_id}");
+            Ok(Vec::new())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grants(entries: &[&str]) -> Vec<Grant> {
+        entries.iter().map(|entry| entry.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_built_in_roles() {
+        let member = WorkspacePermissions::for_role(&WorkspaceRole::Member);
+        assert!(member.allows(Permission::EditFiles, None));
+        assert!(!member.allows(Permission::ManageSecrets, None));
+        assert!(!member.allows(Permission::ManageMembers, None));
+        assert!(member.allows(Permission::ManageOwnApiKeys, None));
+
+        let viewer = WorkspacePermissions::for_role(&WorkspaceRole::Viewer);
+        assert!(viewer.allows(Permission::RunAgents, Some("agents/a.agent.yml")));
+        assert!(!viewer.allows(Permission::EditFiles, None));
+        assert!(!viewer.allows(Permission::RunTests, None));
+        assert!(!viewer.allows(Permission::ManageOwnApiKeys, None));
+
+        let admin = WorkspacePermissions::for_role(&WorkspaceRole::Admin);
+        assert!(Permission::ALL.iter().all(|p| admin.allows(*p, None)));
+    }
+
+    #[test]
+    fn test_custom_role_narrows_built_in_role() {
+        let analyst = WorkspacePermissions::for_role(&WorkspaceRole::Member).limited_by(grants(&[
+            "files.read",
+            "agents.run",
+            "traces.view",
+        ]));
+        assert!(analyst.allows(Permission::RunAgents, Some("agents/a.agent.yml")));
+        assert!(analyst.allows(Permission::ReadFiles, None));
+        assert!(!analyst.allows(Permission::EditFiles, None));
+    }
+
+    #[test]
+    fn test_custom_role_cannot_exceed_built_in_role() {
+        let viewer = WorkspacePermissions::for_role(&WorkspaceRole::Viewer)
+            .limited_by(grants(&["files.edit", "secrets.manage"]));
+        assert!(!viewer.allows(Permission::EditFiles, None));
+        assert!(!viewer.allows(Permission::ManageSecrets, None));
+    }
+
+    #[test]
+    fn test_scoped_grants() {
+        let contractor = WorkspacePermissions::for_role(&WorkspaceRole::Member)
+            .limited_by(grants(&["databases.query:sales", "semantic.query:orders"]));
+        assert!(contractor.allows(Permission::QueryDatabases, Some("sales")));
+        assert!(!contractor.allows(Permission::QueryDatabases, Some("finance")));
+        assert!(contractor.allows(Permission::QueryDatabases, None));
+        assert!(contractor.allows(Permission::QuerySemantic, Some("orders")));
+        assert!(!contractor.allows(Permission::RunAgents, None));
+    }
+
+    #[test]
+    fn test_member_and_api_key_roles_both_apply() {
+        let service_account = WorkspacePermissions::for_role(&WorkspaceRole::Admin)
+            .limited_by(grants(&["workflows.run", "files.read"]))
+            .limited_by(grants(&["workflows.run:workflows/etl.workflow.yml"]));
+        assert!(
+            service_account.allows(Permission::RunWorkflows, Some("workflows/etl.workflow.yml"))
+        );
+        assert!(!service_account.allows(
+            Permission::RunWorkflows,
+            Some("workflows/other.workflow.yml")
+        ));
+        assert!(!service_account.allows(Permission::ReadFiles, None));
+    }
+
+    #[test]
+    fn test_empty_custom_role_allows_nothing() {
+        let locked = WorkspacePermissions::all().limited_by(Vec::new());
+        assert!(Permission::ALL.iter().all(|p| !locked.allows(*p, None)));
+    }
+}
@@ -0,0 +1,684 @@
+//! Cron-triggered workflow runs for `oxy serve`.
+//!
+//! Every tick the scheduler walks the workspaces served by this process and
+//! compares the `schedule:` block of each workflow against its
+//! `workflow_schedules` row. The row remembers the last cron slot that was
+//! claimed: a slot fires at most once (the claim is a compare-and-set, so
+//! replicas sharing a database don't double-fire), slots missed while the
+//! server was down collapse into a single catch-up run, and a slot is skipped
+//! when the previous run of the workflow is still in progress.
+//!
+//! A scheduled run is tracked across replicas with a lease on its row: the
+//! replica that starts the run renews the lease every tick until the run is
+//! done, and a lease left behind by a replica that went down expires after
+//! `RUN_LEASE_TTL`.
+//!
+//! Workspace managers are kept between ticks and only rebuilt when the
+//! workspace's config or semantic layer changes.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Utc};
+use entity::prelude::{WorkflowSchedules, Workspaces};
+use entity::workflow_schedules;
+use entity::workspaces::{self, WorkspaceStatus};
+use once_cell::sync::Lazy;
+use oxy::adapters::runs::RunsManager;
+use oxy::adapters::secrets::SecretsManager;
+use oxy::adapters::workspace::builder::WorkspaceBuilder;
+use oxy::adapters::workspace::effective_workspace_path;
+use oxy::adapters::workspace::manager::WorkspaceManager;
+use oxy::config::model::{Workflow, WorkflowSchedule};
+use oxy::config::resolve_local_workspace_path;
+use oxy::database::client::establish_connection;
+use oxy::utils::file_path_to_source_id;
+use oxy_semantic::ChangeDetector;
+use oxy_shared::errors::OxyError;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{ActiveValue, ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter};
+use serde::Serialize;
+use tokio_util::sync::CancellationToken;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::agent::ExecutionSource;
+use super::secret_manager::SecretManagerService;
+use super::task_manager::TASK_MANAGER;
+use super::types::run::RunInfo;
+use super::workflow::spawn_workflow_run;
+use crate::server::serve_mode::{LOCAL_WORKSPACE_ID, ServeMode};
+
+const SCHEDULER_TICK: Duration = Duration::from_secs(30);
+
+/// How late a slot may be picked up and still count as on time. Anything
+/// older was missed while the server was down and only runs with `catch_up`.
+const MISSED_SLOT_GRACE: chrono::TimeDelta = chrono::TimeDelta::minutes(5);
+
+/// How long a run lease lasts without renewal. The owner renews it every
+/// tick, so it only lapses when the owner stopped while the run was active.
+const RUN_LEASE_TTL: chrono::TimeDelta = chrono::TimeDelta::seconds(90);
+
+/// Owner id of the run leases taken by this process.
+static REPLICA_ID: Lazy<Uuid> = Lazy::new(Uuid::new_v4);
+
+#[derive(Serialize, ToSchema)]
+pub struct WorkflowScheduleInfo {
+    pub path: String,
+    pub cron: Vec<String>,
+    pub timezone: String,
+    pub enabled: bool,
+    pub catch_up: bool,
+    /// Next slot the scheduler will fire, `None` when disabled
+    pub next_run_at: Option<DateTime<Utc>>,
+    /// Last slot the scheduler claimed, whether it ran or was skipped
+    pub last_scheduled_at: Option<DateTime<Utc>>,
+    /// The run started for the most recent slot that ran
+    pub last_run: Option<RunInfo>,
+}
+
+/// Spawns the scheduler loop; it stops with the server's shutdown token.
+pub fn spawn_scheduler(mode: ServeMode, shutdown_token: CancellationToken) {
+    tokio::spawn(async move {
+        let mut workspaces = WorkspaceCache::default();
+        loop {
+            tokio::select! {
+                _ = shutdown_token.cancelled() => break,
+                _ = tokio::time::sleep(SCHEDULER_TICK) => {
+                    if let Err(e) = tick(mode, &mut workspaces).await {
+                        tracing::warn!("Workflow scheduler tick failed: {e}");
+                    }
+                }
+            }
+        }
+        tracing::debug!("Workflow scheduler stopped");
+    });
+}
+
+async fn tick(mode: ServeMode, workspaces: &mut WorkspaceCache) -> Result<(), OxyError> {
+    let db = establish_connection().await?;
+    let served = served_workspaces(mode, &db).await?;
+    workspaces.retain(&served);
+    for (workspace_id, workspace_path) in served {
+        let workspace_manager = match workspaces.get(workspace_id, &workspace_path).await {
+            Ok(workspace_manager) => workspace_manager,
+            Err(e) => {
+                tracing::warn!("Failed to load workspace {workspace_id} for schedules: {e}");
+                continue;
+            }
+        };
+        if let Err(e) = run_due_schedules(&db, workspace_manager).await {
+            tracing::warn!("Failed to run schedules for workspace {workspace_id}: {e}");
+        }
+    }
+    Ok(())
+}
+
+async fn served_workspaces(
+    mode: ServeMode,
+    db: &DatabaseConnection,
+) -> Result<Vec<(Uuid, PathBuf)>, OxyError> {
+    if mode.is_local() {
+        return Ok(resolve_local_workspace_path()
+            .map(|path| vec![(LOCAL_WORKSPACE_ID, path)])
+            .unwrap_or_default());
+    }
+    let rows = Workspaces::find()
+        .filter(workspaces::Column::Status.eq(WorkspaceStatus::Ready))
+        .all(db)
+        .await
+        .map_err(|e| OxyError::DBError(format!("Failed to list workspaces: {e}")))?;
+    let mut served = Vec::with_capacity(rows.len());
+    for row in rows {
+        match effective_workspace_path(&row, None).await {
+            Ok(path) => served.push((row.id, path)),
+            Err(e) => tracing::debug!("Skipping schedules for workspace {}: {e}", row.id),
+        }
+    }
+    Ok(served)
+}
+
+/// Workspace managers of the served workspaces, kept between ticks.
+#[derive(Default)]
+struct WorkspaceCache {
+    entries: HashMap<Uuid, CachedWorkspace>,
+}
+
+struct CachedWorkspace {
+    path: PathBuf,
+    revision: WorkspaceRevision,
+    manager: WorkspaceManager,
+}
+
+/// What the cached manager was built from: `config.yml`, and the semantic
+/// views and topics its `ConfigManager` caches on first use.
+#[derive(PartialEq)]
+struct WorkspaceRevision {
+    config_modified: Option<SystemTime>,
+    semantic_fingerprint: String,
+}
+
+impl WorkspaceCache {
+    /// Manager of the workspace, rebuilt when the workspace moved or its
+    /// revision changed since the last tick.
+    async fn get(
+        &mut self,
+        workspace_id: Uuid,
+        workspace_path: &Path,
+    ) -> Result<&WorkspaceManager, OxyError> {
+        let revision = workspace_revision(workspace_path).await?;
+        let fresh = self
+            .entries
+            .get(&workspace_id)
+            .is_some_and(|cached| cached.path == workspace_path && cached.revision == revision);
+        if !fresh {
+            let manager = build_workspace_manager(workspace_id, workspace_path).await?;
+            self.entries.insert(
+                workspace_id,
+                CachedWorkspace {
+                    path: workspace_path.to_path_buf(),
+                    revision,
+                    manager,
+                },
+            );
+        }
+        Ok(&self.entries[&workspace_id].manager)
+    }
+
+    /// Drops the managers of workspaces that are no longer served.
+    fn retain(&mut self, served: &[(Uuid, PathBuf)]) {
+        self.entries
+            .retain(|workspace_id, _| served.iter().any(|(id, _)| id == workspace_id));
+    }
+}
+
+async fn workspace_revision(workspace_path: &Path) -> Result<WorkspaceRevision, OxyError> {
+    let workspace_path = workspace_path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let config_modified = std::fs::metadata(workspace_path.join("config.yml"))
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        let detector =
+            ChangeDetector::new(workspace_path.clone(), workspace_path.join(".semantics"));
+        WorkspaceRevision {
+            config_modified,
+            semantic_fingerprint: detector.semantic_fingerprint().unwrap_or_default(),
+        }
+    })
+    .await
+    .map_err(|e| OxyError::RuntimeError(format!("Failed to check workspace for changes: {e}")))
+}
+
+async fn build_workspace_manager(
+    workspace_id: Uuid,
+    workspace_path: &Path,
+) -> Result<WorkspaceManager, OxyError> {
+    WorkspaceBuilder::new(workspace_id)
+        .with_workspace_path_and_fallback_config(workspace_path)
+        .await?
+        .with_secrets_manager(SecretsManager::from_database_with_env_fallback(
+            SecretManagerService::new(workspace_id),
+        )?)
+        // Branch runs are not scheduled; nil is the default-branch sentinel
+        .with_runs_manager(RunsManager::default(workspace_id, Uuid::nil()).await?)
+        .build()
+        .await
+}
+
+async fn run_due_schedules(
+    db: &DatabaseConnection,
+    workspace_manager: &WorkspaceManager,
+) -> Result<(), OxyError> {
+    for (source_id, workflow, schedule) in scheduled_workflows(workspace_manager).await? {
+        if !schedule.enabled {
+            continue;
+        }
+        if let Err(e) = fire_due_slot(db, workspace_manager, &source_id, workflow, &schedule).await
+        {
+            tracing::warn!("Failed to run schedule for workflow {source_id}: {e}");
+        }
+    }
+    Ok(())
+}
+
+/// Workflows of the workspace that have a `schedule:` block, keyed by the
+/// workspace-relative source id the web app uses for run history.
+async fn scheduled_workflows(
+    workspace_manager: &WorkspaceManager,
+) -> Result<Vec<(String, Workflow, WorkflowSchedule)>, OxyError> {
+    let config_manager = &workspace_manager.config_manager;
+    let mut scheduled = vec![];
+    for path in config_manager.list_workflows().await? {
+        let workflow = match config_manager.resolve_workflow(&path).await {
+            Ok(workflow) => workflow,
+            Err(e) => {
+                tracing::debug!("Skipping schedule of {}: {e}", path.display());
+                continue;
+            }
+        };
+        if let Some(schedule) = workflow.schedule.clone() {
+            let relative_path = path
+                .strip_prefix(config_manager.workspace_path())
+                .unwrap_or(&path);
+            scheduled.push((file_path_to_source_id(relative_path), workflow, schedule));
+        }
+    }
+    Ok(scheduled)
+}
+
+#[derive(Debug, PartialEq)]
+enum SlotAction {
+    Run,
+    SkipMissed,
+    SkipOverlapping,
+}
+
+fn slot_action(
+    schedule: &WorkflowSchedule,
+    slot: DateTime<Utc>,
+    now: DateTime<Utc>,
+    previous_run_active: bool,
+) -> SlotAction {
+    if previous_run_active {
+        SlotAction::SkipOverlapping
+    } else if !schedule.catch_up && now - slot > MISSED_SLOT_GRACE {
+        SlotAction::SkipMissed
+    } else {
+        SlotAction::Run
+    }
+}
+
+async fn fire_due_slot(
+    db: &DatabaseConnection,
+    workspace_manager: &WorkspaceManager,
+    source_id: &str,
+    workflow: Workflow,
+    schedule: &WorkflowSchedule,
+) -> Result<(), OxyError> {
+    let workspace_id = workspace_manager.workspace_id;
+    let now = Utc::now();
+    let Some(state) = find_state(db, workspace_id, source_id).await? else {
+        // New schedules start from now rather than catching up on history
+        return init_state(db, workspace_id, source_id, now).await;
+    };
+    let runs_manager = workspace_manager
+        .runs_manager
+        .clone()
+        .ok_or_else(|| OxyError::RuntimeError("RunsManager is not initialized".to_string()))?;
+    let lease_active = renew_lease(db, &runs_manager, &state, now).await?;
+
+    let last_scheduled_at = state.last_scheduled_at.with_timezone(&Utc);
+    let Some(slot) = schedule.latest_between(last_scheduled_at, now)? else {
+        return Ok(());
+    };
+    if !claim_slot(db, workspace_id, source_id, last_scheduled_at, slot).await? {
+        // Another replica claimed this slot first
+        return Ok(());
+    }
+
+    // The lease covers scheduled runs on any replica, the task manager
+    // manual runs on this one
+    let previous_run_active = lease_active
+        || match runs_manager.last_run(source_id).await? {
+            Some(run) => TASK_MANAGER.has_task(run.task_id()?).await,
+            None => false,
+        };
+    match slot_action(schedule, slot, now, previous_run_active) {
+        SlotAction::SkipOverlapping => {
+            tracing::info!("Skipping {source_id} slot {slot}: previous run is still active");
+            return Ok(());
+        }
+        SlotAction::SkipMissed => {
+            tracing::info!("Skipping {source_id} slot {slot}: missed and catch_up is disabled");
+            return Ok(());
+        }
+        SlotAction::Run => {}
+    }
+    if !acquire_lease(db, workspace_id, source_id, now).await? {
+        tracing::info!("Skipping {source_id} slot {slot}: previous run is still active");
+        return Ok(());
+    }
+
+    let started = start_run(
+        db,
+        workspace_manager,
+        &runs_manager,
+        source_id,
+        workflow,
+        slot,
+    )
+    .await;
+    if started.is_err() {
+        set_lease(db, workspace_id, source_id, None).await?;
+    }
+    started
+}
+
+async fn start_run(
+    db: &DatabaseConnection,
+    workspace_manager: &WorkspaceManager,
+    runs_manager: &RunsManager,
+    source_id: &str,
+    workflow: Workflow,
+    slot: DateTime<Utc>,
+) -> Result<(), OxyError> {
+    let run_info = runs_manager.new_run(source_id, None, None, None).await?;
+    tracing::info!(
+        "Starting scheduled run {:?} of {source_id} for slot {slot}",
+        run_info.run_index
+    );
+    record_run(
+        db,
+        workspace_manager.workspace_id,
+        source_id,
+        run_info.run_index,
+    )
+    .await?;
+    spawn_workflow_run(
+        workspace_manager.clone(),
+        workflow,
+        &run_info,
+        None,
+        ExecutionSource::Schedule {
+            scheduled_at: slot.to_rfc3339(),
+        },
+        None,
+    )
+    .await
+}
+
+async fn find_state(
+    db: &DatabaseConnection,
+    workspace_id: Uuid,
+    source_id: &str,
+) -> Result<Option<workflow_schedules::Model>, OxyError> {
+    WorkflowSchedules::find_by_id((workspace_id, source_id.to_string()))
+        .one(db)
+        .await
+        .map_err(|e| OxyError::DBError(format!("Failed to load workflow schedule: {e}")))
+}
+
+async fn init_state(
+    db: &DatabaseConnection,
+    workspace_id: Uuid,
+    source_id: &str,
+    now: DateTime<Utc>,
+) -> Result<(), OxyError> {
+    WorkflowSchedules::insert(workflow_schedules::ActiveModel {
+        workspace_id: ActiveValue::Set(workspace_id),
+        source_id: ActiveValue::Set(source_id.to_string()),
+        last_scheduled_at: ActiveValue::Set(now.into()),
+        last_run_index: ActiveValue::Set(None),
+        run_lease_owner: ActiveValue::Set(None),
+        run_lease_expires_at: ActiveValue::Set(None),
+        created_at: ActiveValue::Set(now.into()),
+        updated_at: ActiveValue::Set(now.into()),
+    })
+    .on_conflict(
+        OnConflict::columns([
+            workflow_schedules::Column::WorkspaceId,
+            workflow_schedules::Column::SourceId,
+        ])
+        .do_nothing()
+        .to_owned(),
+    )
+    .exec_without_returning(db)
+    .await
+    .map_err(|e| OxyError::DBError(format!("Failed to create workflow schedule: {e}")))?;
+    Ok(())
+}
+
+/// Advances `last_scheduled_at` from `previous` to `slot`. Returns false when
+/// the row no longer holds `previous`, i.e. someone else claimed the slot.
+async fn claim_slot(
+    db: &DatabaseConnection,
+    workspace_id: Uuid,
+    source_id: &str,
+    previous: DateTime<Utc>,
+    slot: DateTime<Utc>,
+) -> Result<bool, OxyError> {
+    let result = WorkflowSchedules::update_many()
+        .col_expr(
+            workflow_schedules::Column::LastScheduledAt,
+            Expr::value(slot),
+        )
+        .col_expr(
+            workflow_schedules::Column::UpdatedAt,
+            Expr::value(Utc::now()),
+        )
+        .filter(workflow_schedules::Column::WorkspaceId.eq(workspace_id))
+        .filter(workflow_schedules::Column::SourceId.eq(source_id))
+        .filter(workflow_schedules::Column::LastScheduledAt.eq(previous))
+        .exec(db)
+        .await
+        .map_err(|e| OxyError::DBError(format!("Failed to claim schedule slot: {e}")))?;
+    Ok(result.rows_affected == 1)
+}
+
+async fn record_run(
+    db: &DatabaseConnection,
+    workspace_id: Uuid,
+    source_id: &str,
+    run_index: Option<i32>,
+) -> Result<(), OxyError> {
+    WorkflowSchedules::update_many()
+        .col_expr(
+            workflow_schedules::Column::LastRunIndex,
+            Expr::value(run_index),
+        )
+        .col_expr(
+            workflow_schedules::Column::UpdatedAt,
+            Expr::value(Utc::now()),
+        )
+        .filter(workflow_schedules::Column::WorkspaceId.eq(workspace_id))
+        .filter(workflow_schedules::Column::SourceId.eq(source_id))
+        .exec(db)
+        .await
+        .map_err(|e| OxyError::DBError(format!("Failed to record scheduled run: {e}")))?;
+    Ok(())
+}
+
+/// Renews this replica's lease while the scheduled run it started is in
+/// progress, and releases it once the run is done. Returns whether a lease is
+/// still live, whichever replica holds it.
+async fn renew_lease(
+    db: &DatabaseConnection,
+    runs_manager: &RunsManager,
+    state: &workflow_schedules::Model,
+    now: DateTime<Utc>,
+) -> Result<bool, OxyError> {
+    if state.run_lease_owner != Some(*REPLICA_ID) {
+        return Ok(state
+            .run_lease_expires_at
+            .is_some_and(|expires_at| expires_at.with_timezone(&Utc) > now));
+    }
+    let running = match state.last_run_index {
+        Some(run_index) => match runs_manager
+            .find_run(&state.source_id, Some(run_index))
+            .await?
+        {
+            Some(run) => TASK_MANAGER.has_task(run.task_id()?).await,
+            None => false,
+        },
+        None => false,
+    };
+    set_lease(
+        db,
+        state.workspace_id,
+        &state.source_id,
+        running.then(|| now + RUN_LEASE_TTL),
+    )
+    .await?;
+    Ok(running)
+}
+
+/// Takes the lease for a new scheduled run. Returns false when another
+/// replica holds a live lease.
+async fn acquire_lease(
+    db: &DatabaseConnection,
+    workspace_id: Uuid,
+    source_id: &str,
+    now: DateTime<Utc>,
+) -> Result<bool, OxyError> {
+    let result = WorkflowSchedules::update_many()
+        .col_expr(
+            workflow_schedules::Column::RunLeaseOwner,
+            Expr::value(*REPLICA_ID),
+        )
+        .col_expr(
+            workflow_schedules::Column::RunLeaseExpiresAt,
+            Expr::value(now + RUN_LEASE_TTL),
+        )
+        .filter(workflow_schedules::Column::WorkspaceId.eq(workspace_id))
+        .filter(workflow_schedules::Column::SourceId.eq(source_id))
+        .filter(
+            Condition::any()
+                .add(workflow_schedules::Column::RunLeaseExpiresAt.is_null())
+                .add(workflow_schedules::Column::RunLeaseExpiresAt.lte(now)),
+        )
+        .exec(db)
+        .await
+        .map_err(|e| OxyError::DBError(format!("Failed to acquire schedule lease: {e}")))?;
+    Ok(result.rows_affected == 1)
+}
+
+/// Extends this replica's lease until `expires_at`, or releases it when
+/// `None`. Leaves leases taken over by other replicas alone.
+async fn set_lease(
+    db: &DatabaseConnection,
+    workspace_id: Uuid,
+    source_id: &str,
+    expires_at: Option<DateTime<Utc>>,
+) -> Result<(), OxyError> {
+    WorkflowSchedules::update_many()
+        .col_expr(
+            workflow_schedules::Column::RunLeaseOwner,
+            Expr::value(expires_at.map(|_| *REPLICA_ID)),
+        )
+        .col_expr(
+            workflow_schedules::Column::RunLeaseExpiresAt,
+            Expr::value(expires_at),
+        )
+        .filter(workflow_schedules::Column::WorkspaceId.eq(workspace_id))
+        .filter(workflow_schedules::Column::SourceId.eq(source_id))
+        .filter(workflow_schedules::Column::RunLeaseOwner.eq(*REPLICA_ID))
+        .exec(db)
+        .await
+        .map_err(|e| OxyError::DBError(format!("Failed to update schedule lease: {e}")))?;
+    Ok(())
+}
+
+async fn schedule_info(
+    db: &DatabaseConnection,
+    workspace_manager: &WorkspaceManager,
+    source_id: String,
+    schedule: WorkflowSchedule,
+) -> Result<WorkflowScheduleInfo, OxyError> {
+    let state = find_state(db, workspace_manager.workspace_id, &source_id).await?;
+    let last_run = match (
+        state.as_ref().and_then(|state| state.last_run_index),
+        &workspace_manager.runs_manager,
+    ) {
+        (Some(run_index), Some(runs_manager)) => {
+            runs_manager.find_run(&source_id, Some(run_index)).await?
+        }
+        _ => None,
+    };
+    let last_scheduled_at = state.map(|state| state.last_scheduled_at.with_timezone(&Utc));
+    let next_run_at = if schedule.enabled {
+        schedule.next_after(Utc::now())?
+    } else {
+        None
+    };
+    Ok(WorkflowScheduleInfo {
+        path: source_id,
+        cron: schedule.cron.as_slice().to_vec(),
+        timezone: schedule.timezone,
+        enabled: schedule.enabled,
+        catch_up: schedule.catch_up,
+        next_run_at,
+        last_scheduled_at,
+        last_run,
+    })
+}
+
+pub async fn list_schedules(
+    workspace_manager: &WorkspaceManager,
+) -> Result<Vec<WorkflowScheduleInfo>, OxyError> {
+    let db = establish_connection().await?;
+    let mut schedules = vec![];
+    for (source_id, _, schedule) in scheduled_workflows(workspace_manager).await? {
+        schedules.push(schedule_info(&db, workspace_manager, source_id, schedule).await?);
+    }
+    Ok(schedules)
+}
+
+/// Schedule of a single workflow, `None` when it has no `schedule:` block.
+pub async fn get_schedule(
+    workspace_manager: &WorkspaceManager,
+    path: &Path,
+) -> Result<Option<WorkflowScheduleInfo>, OxyError> {
+    let workflow = workspace_manager
+        .config_manager
+        .resolve_workflow(path)
+        .await?;
+    let Some(schedule) = workflow.schedule else {
+        return Ok(None);
+    };
+    let db = establish_connection().await?;
+    schedule_info(
+        &db,
+        workspace_manager,
+        file_path_to_source_id(path),
+        schedule,
+    )
+    .await
+    .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn schedule(catch_up: bool) -> WorkflowSchedule {
+        serde_yaml::from_str(&format!("cron: '0 * * * *'\ncatch_up: {catch_up}")).unwrap()
+    }
+
+    fn utc(h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 2, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn runs_on_time_slots() {
+        assert_eq!(
+            slot_action(&schedule(false), utc(9, 0), utc(9, 0), false),
+            SlotAction::Run
+        );
+        assert_eq!(
+            slot_action(&schedule(false), utc(9, 0), utc(9, 4), false),
+            SlotAction::Run
+        );
+    }
+
+    #[test]
+    fn missed_slots_only_run_with_catch_up() {
+        assert_eq!(
+            slot_action(&schedule(true), utc(9, 0), utc(11, 30), false),
+            SlotAction::Run
+        );
+        assert_eq!(
+            slot_action(&schedule(false), utc(9, 0), utc(11, 30), false),
+            SlotAction::SkipMissed
+        );
+    }
+
+    #[test]
+    fn never_overlaps_an_active_run() {
+        assert_eq!(
+            slot_action(&schedule(true), utc(9, 0), utc(9, 0), true),
+            SlotAction::SkipOverlapping
+        );
+    }
+}
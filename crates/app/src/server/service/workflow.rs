@@ -4,7 +4,11 @@ use slugify::slugify;
 use std::path::{Path, PathBuf};
 use utoipa::ToSchema;
 
+use super::agent::ExecutionSource;
+use super::block::GroupBlockHandler;
 use super::eval::PBarsHandler;
+use super::statics::BROADCASTER;
+use super::task_manager::TASK_MANAGER;
 
 use oxy::{
     adapters::{session_filters::SessionFilters, workspace::manager::WorkspaceManager},
@@ -23,9 +27,10 @@ use oxy::{
     },
     execute::{
         types::{Event, EventKind, Output, OutputContainer, ProgressType},
-        writer::EventHandler,
+        writer::{EventHandler, Handler},
     },
     observability::events::workflow as workflow_events,
+    types::run::RunInfo,
 };
 use oxy_shared::errors::OxyError;
 use oxy_workflow::{
@@ -343,6 +348,118 @@ pub async fn run_workflow_v2<P: AsRef<Path>, H: EventHandler + Send + Sync + 'st
     result
 }
 
+/// Runs a workflow in the background under the task manager, broadcasting its
+/// events on the run's topic. Once the run finishes (or is cancelled) the last
+/// task's output and the run blocks are persisted through the `RunsManager`.
+pub async fn spawn_workflow_run(
+    workspace_manager: WorkspaceManager,
+    workflow_config: Workflow,
+    run_info: &RunInfo,
+    replay_id: Option<String>,
+    source: ExecutionSource,
+    user_id: Option<uuid::Uuid>,
+) -> Result<(), OxyError> {
+    let runs_manager = workspace_manager
+        .runs_manager
+        .clone()
+        .ok_or_else(|| OxyError::RuntimeError("RunsManager is not initialized".to_string()))?;
+    let task_id = run_info.task_id()?;
+    let topic_ref = BROADCASTER.create_topic(&task_id).await.map_err(|err| {
+        OxyError::RuntimeError(format!(
+            "Failed to create topic for task ID {task_id}: {err}"
+        ))
+    })?;
+    let run_index = run_info
+        .run_index
+        .ok_or_else(|| OxyError::RuntimeError(format!("Run {task_id} has no run index")))?;
+    let topic_id = task_id.clone();
+    let cb_source_id = run_info.source_id.clone();
+    let callback_fn = async move |output: Option<OutputContainer>| -> Result<(), OxyError> {
+        // Handle the completion of the run and broadcast events
+        if let Some(closed) = BROADCASTER.remove_topic(&topic_id).await {
+            let last_task_ref = workflow_config.tasks.last().map(|t| t.name.clone());
+            if let Some(output) = output
+                && let Some(last_task_name) = last_task_ref
+            {
+                let outputs = output.find_ref(&last_task_name)?;
+                let last_output = outputs.first();
+                if let Some(last_output) = last_output {
+                    runs_manager
+                        .update_run_output(
+                            &cb_source_id,
+                            run_index,
+                            last_task_name,
+                            last_output.to_json()?,
+                        )
+                        .await?;
+                }
+            };
+
+            let mut group_handler = GroupBlockHandler::new();
+            for event in closed.items {
+                group_handler.handle_event(event).await?;
+            }
+            let groups = group_handler.collect();
+            for group in groups {
+                runs_manager.upsert_run(group, user_id).await?;
+            }
+            drop(closed.sender); // Drop the sender to close the channel
+        } else {
+            tracing::warn!(
+                "Failed to remove topic: {} - topic does not exist or was already removed",
+                topic_id
+            );
+        }
+        Ok(())
+    };
+    let source_id = run_info.source_id.clone();
+
+    TASK_MANAGER
+        .spawn(task_id.clone(), async move |cancellation_token| {
+            let run_fut = {
+                let converted_run_index = run_index
+                    .try_into()
+                    .map_err(|e| tracing::error!("Failed to convert run_index to u32: {}", e))
+                    .unwrap_or(0); // Default to 0 if conversion fails
+                run_workflow_v2(
+                    workspace_manager.clone(),
+                    source_id,
+                    topic_ref,
+                    RetryStrategy::Retry {
+                        replay_id,
+                        run_index: converted_run_index,
+                    },
+                    None,
+                    None,
+                    Some(source),
+                    user_id,
+                )
+            };
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {
+                    tracing::debug!("Task {task_id} was cancelled");
+                    if let Err(err) = callback_fn(None).await {
+                        tracing::error!("Failed to handle callback for task {task_id}: {err}");
+                    }
+                }
+                res = run_fut => {
+                    let output = match res {
+                        Ok(value) => Some(value),
+                        Err(err) => {
+                            tracing::error!("Task {task_id} failed: {err}");
+                            None
+                        },
+                    };
+                    if let Err(err) = callback_fn(output).await {
+                        tracing::error!("Failed to handle callback for task {task_id}: {err}");
+                    }
+                }
+            }
+        })
+        .await;
+    Ok(())
+}
+
 pub async fn get_workflow_logs(
     path: &PathBuf,
     config_manager: ConfigManager,
@@ -399,6 +516,8 @@ pub async fn create_workflow_from_query(
         variables: None,
         retrieval: Default::default(),
         consistency_prompt: None,
+        schedule: None,
+        budget: None,
     };
     // write workflow to file
     let workflow_dir = config_manager
@@ -447,6 +566,8 @@ pub async fn create_automation(
         variables: None,
         retrieval,
         consistency_prompt: None,
+        schedule: None,
+        budget: None,
     };
 
     let procedure_dir = config_manager.resolve_file(PROCEDURE_SAVED_DIR).await?;
@@ -7,6 +7,9 @@ use sea_orm::DatabaseConnection;
 pub struct ApiKeyConfig {
     pub require_user_active: bool,
     pub allow_multiple_keys: bool,
+    /// Reject keys that were revoked or have expired. Only turn this off
+    /// with a comment explaining why the caller may see such keys.
+    pub require_key_active: bool,
 }
 
 impl Default for ApiKeyConfig {
@@ -14,6 +17,7 @@ impl Default for ApiKeyConfig {
         Self {
             require_user_active: true,
             allow_multiple_keys: true,
+            require_key_active: true,
         }
     }
 }
@@ -23,6 +27,10 @@ pub struct ValidatedApiKey {
     pub id: uuid::Uuid,
     pub key: String,
     pub user_id: uuid::Uuid,
+    /// Workspace the key was issued for
+    pub project_id: uuid::Uuid,
+    /// Custom role limiting what the key may do in that workspace
+    pub custom_role_id: Option<uuid::Uuid>,
 }
 
 #[derive(Debug, Clone)]
@@ -31,6 +39,7 @@ pub struct CreateApiKeyParams {
     pub name: String,
     pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
     pub project_id: uuid::Uuid,
+    pub custom_role_id: Option<uuid::Uuid>,
 }
 
 #[derive(Debug, Clone)]
@@ -48,7 +57,7 @@ impl ApiKeyService {
     pub async fn validate_api_key(
         db: &DatabaseConnection,
         key: &str,
-        _config: &ApiKeyConfig,
+        config: &ApiKeyConfig,
     ) -> Result<ValidatedApiKey, OxyError> {
         use ::entity::prelude::*;
         use sea_orm::*;
@@ -60,10 +69,27 @@ impl ApiKeyService {
             .map_err(|e| OxyError::DBError(format!("Database error: {}", e)))?
             .ok_or_else(|| OxyError::AuthenticationError("Invalid API key".to_string()))?;
 
+        if config.require_key_active && !api_key.is_active {
+            return Err(OxyError::AuthenticationError(
+                "API key has been revoked".to_string(),
+            ));
+        }
+        if config.require_key_active
+            && api_key
+                .expires_at
+                .is_some_and(|expires_at| expires_at <= chrono::Utc::now())
+        {
+            return Err(OxyError::AuthenticationError(
+                "API key has expired".to_string(),
+            ));
+        }
+
         Ok(ValidatedApiKey {
             id: api_key.id,
             key: key.to_string(),
             user_id: api_key.user_id,
+            project_id: api_key.project_id,
+            custom_role_id: api_key.custom_role_id,
         })
     }
 
@@ -87,6 +113,7 @@ impl ApiKeyService {
             updated_at: Set(now.into()),
             is_active: Set(true),
             project_id: Set(params.project_id),
+            custom_role_id: Set(params.custom_role_id),
             last_used_at: NotSet,
         };
 
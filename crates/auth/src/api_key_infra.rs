@@ -1,5 +1,5 @@
 use crate::api_key_domain::{ApiKeyConfig, ApiKeyService, ValidatedApiKey};
-use crate::constants::DEFAULT_API_KEY_HEADER;
+use crate::constants::{AUTHENTICATION_HEADER_KEY, DEFAULT_API_KEY_HEADER};
 use crate::types::Identity;
 use axum::http::HeaderMap;
 use entity::prelude::Users;
@@ -18,6 +18,43 @@ fn extract_api_key_from_headers_with_name(
         .map(|s| s.trim().to_string())
 }
 
+/// Extracts an API key from `X-API-Key`, falling back to
+/// `Authorization: Bearer <key>` for clients (such as remote MCP clients) that
+/// can only send bearer tokens.
+pub fn extract_api_key(headers: &HeaderMap) -> Option<String> {
+    extract_api_key_from_headers_with_name(headers, DEFAULT_API_KEY_HEADER)
+        .filter(|key| !key.is_empty())
+        .or_else(|| {
+            headers
+                .get(AUTHENTICATION_HEADER_KEY)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| {
+                    v.split_once(' ')
+                        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+                })
+                .map(|(_, key)| key.trim().to_string())
+                .filter(|key| !key.is_empty())
+        })
+}
+
+/// Authenticates a request by API key only, accepting either header form
+/// understood by [`extract_api_key`].
+pub async fn authenticate_api_key(
+    db: &DatabaseConnection,
+    headers: &HeaderMap,
+    config: &ApiKeyConfig,
+) -> Result<(Identity, ValidatedApiKey), OxyError> {
+    let key = extract_api_key(headers).ok_or_else(|| {
+        OxyError::AuthenticationError(format!(
+            "No API key found in headers (expected: {} or Authorization: Bearer)",
+            DEFAULT_API_KEY_HEADER
+        ))
+    })?;
+    let validated_key = ApiKeyService::validate_api_key(db, &key, config).await?;
+    let identity = identity_for_key(db, &validated_key).await?;
+    Ok((identity, validated_key))
+}
+
 pub async fn authenticate_header(headers: &HeaderMap) -> Result<Identity, OxyError> {
     // Establish database connection
     let db = establish_connection().await.map_err(|e| {
@@ -52,6 +89,15 @@ pub async fn authenticate_header_with_config(
     // Validate the API key
     let validated_key = ApiKeyService::validate_api_key(db, &key, config).await?;
 
+    let identity = identity_for_key(db, &validated_key).await?;
+
+    Ok((identity, validated_key))
+}
+
+async fn identity_for_key(
+    db: &DatabaseConnection,
+    validated_key: &ValidatedApiKey,
+) -> Result<Identity, OxyError> {
     // Get the user associated with the API key
     let user = Users::find_by_id(validated_key.user_id)
         .one(db)
@@ -69,11 +115,51 @@ pub async fn authenticate_header_with_config(
         })?;
 
     // Create Identity with real user information
-    let identity = Identity {
+    Ok(Identity {
         picture: user.picture,
         email: user.email,
         name: Some(user.name),
-    };
+    })
+}
 
-    Ok((identity, validated_key))
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn extract_api_key_prefers_api_key_header() {
+        let headers = headers(&[
+            ("x-api-key", "oxy_header"),
+            ("authorization", "Bearer oxy_bearer"),
+        ]);
+        assert_eq!(extract_api_key(&headers).as_deref(), Some("oxy_header"));
+    }
+
+    #[test]
+    fn extract_api_key_accepts_bearer_token() {
+        let headers = headers(&[("authorization", "bearer  oxy_bearer ")]);
+        assert_eq!(extract_api_key(&headers).as_deref(), Some("oxy_bearer"));
+    }
+
+    #[test]
+    fn extract_api_key_ignores_other_schemes() {
+        assert_eq!(
+            extract_api_key(&headers(&[("authorization", "Basic abc")])),
+            None
+        );
+        assert_eq!(
+            extract_api_key(&headers(&[("authorization", "oxy_raw")])),
+            None
+        );
+        assert_eq!(extract_api_key(&HeaderMap::new()), None);
+    }
 }
@@ -6,10 +6,13 @@ use axum::{
 };
 use std::sync::Arc;
 
+use crate::api_key_domain::ApiKeyConfig;
+use crate::api_key_infra::{authenticate_api_key, extract_api_key};
 use crate::user::UserService;
 
 use crate::{authenticator::Authenticator, built_in::BuiltInAuthenticator};
 use entity::users::UserStatus;
+use oxy_platform::db::establish_connection;
 
 pub struct AuthState<T> {
     authenticator: Arc<T>,
@@ -119,6 +122,62 @@ pub async fn auth_middleware<T: Authenticator>(
     Ok(next.run(request).await)
 }
 
+/// Middleware for routes that only accept API keys, such as the hosted MCP
+/// endpoint. Unlike [`auth_middleware`] it never falls back to JWTs or the
+/// guest user. The validated key is added to request extensions next to the
+/// user so handlers can check which workspace it was issued for.
+pub async fn api_key_auth_middleware(
+    mut request: Request<axum::body::Body>,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    if request.method() == Method::OPTIONS {
+        return Ok(next.run(request).await);
+    }
+
+    // Reject requests without a key before touching the database.
+    if extract_api_key(request.headers()).is_none() {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    let db = establish_connection().await.map_err(|e| {
+        tracing::error!(
+            "Failed to establish database connection for API key validation: {}",
+            e
+        );
+        StatusCode::SERVICE_UNAVAILABLE
+    })?;
+
+    let (identity, validated_key) =
+        authenticate_api_key(&db, request.headers(), &ApiKeyConfig::default())
+            .await
+            .map_err(|err| {
+                tracing::warn!("API key authentication failed: {}", err);
+                StatusCode::UNAUTHORIZED
+            })?;
+
+    let user = UserService::get_or_create_user(&identity)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to find user for API key: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    if user.status != UserStatus::Active {
+        tracing::warn!(
+            "Inactive user {} (status: {}) attempted to use API key {}",
+            user.email,
+            user.status.as_str(),
+            validated_key.id
+        );
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    request.extensions_mut().insert(user);
+    request.extensions_mut().insert(validated_key);
+
+    Ok(next.run(request).await)
+}
+
 /// Middleware for the internal port that auto-authenticates as an internal user.
 /// Uses UserService::get_or_create_user to ensure the user exists in the database,
 /// so that foreign key constraints in downstream handlers work correctly.
@@ -2,8 +2,10 @@ pub mod create_app_schema;
 pub mod edit_app_schema;
 pub mod lenient_types;
 pub mod looker_tool_description;
+pub mod model_chain;
 pub mod openai;
 pub mod read_app_schema;
+pub mod replay;
 pub mod runs;
 pub mod secrets;
 pub mod semantic_tool_description;
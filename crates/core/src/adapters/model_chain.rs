@@ -0,0 +1,221 @@
+//! Fallback chains and client-side rate limits for classic agents.
+//!
+//! A [`ModelChain`] is a model from `config.yml` followed by the models in
+//! its `fallbacks`.  [`ModelChain::call`] tries them in order: a model whose
+//! circuit is open is skipped, a transient failure is recorded in the
+//! circuit breaker before moving on to the next model, and a permanent
+//! failure is returned as-is.  Every model is called through the
+//! OpenAI-compatible chat API, so fallbacks may use any vendor.
+//!
+//! Models with a `rate_limit` wait on the limiter shared by their API key
+//! before each call.
+
+use std::sync::{Arc, LazyLock};
+
+use agentic_core::{
+    circuit_breaker::CircuitBreaker,
+    rate_limit::{RateLimit, RateLimiter},
+};
+use oxy_shared::errors::OxyError;
+
+use crate::{
+    adapters::{
+        openai::{IntoOpenAIConfig, OpenAIClient},
+        secrets::SecretsManager,
+    },
+    config::{ConfigManager, model::Model},
+};
+
+static MODEL_CIRCUIT_BREAKER: LazyLock<Arc<CircuitBreaker>> =
+    LazyLock::new(CircuitBreaker::with_defaults);
+
+/// One model of a [`ModelChain`].
+#[derive(Clone, Debug)]
+pub struct ModelRoute {
+    /// Name of the model in `config.yml`
+    pub name: String,
+    /// Model name sent to the provider
+    pub model: String,
+    pub client: OpenAIClient,
+    limiter: Option<Arc<RateLimiter>>,
+}
+
+impl ModelRoute {
+    async fn from_model(model: &Model, secrets_manager: &SecretsManager) -> Result<Self, OxyError> {
+        let client = OpenAIClient::with_config(model.into_openai_config(secrets_manager).await?);
+        let limiter = model.rate_limit().map(|limit| {
+            RateLimiter::shared(
+                &model.provider_key(),
+                RateLimit {
+                    requests_per_minute: limit.requests_per_minute,
+                    tokens_per_minute: limit.tokens_per_minute,
+                },
+            )
+        });
+        Ok(Self {
+            name: model.name().to_string(),
+            model: model.model_name().to_string(),
+            client,
+            limiter,
+        })
+    }
+
+    /// Charge the tokens used by a finished call to the model's rate limit.
+    pub fn record_usage(&self, input_tokens: u32, output_tokens: u32) {
+        if let Some(limiter) = &self.limiter {
+            limiter.record_tokens(u64::from(input_tokens) + u64::from(output_tokens));
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ModelChain {
+    routes: Vec<ModelRoute>,
+    breaker: Arc<CircuitBreaker>,
+}
+
+impl std::fmt::Debug for ModelChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ModelChain")
+            .field("routes", &self.routes)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ModelChain {
+    /// `model` followed by its fallbacks, resolved by name from `config`.
+    pub async fn from_model(
+        model: &Model,
+        config: &ConfigManager,
+        secrets_manager: &SecretsManager,
+    ) -> Result<Self, OxyError> {
+        let mut routes = vec![ModelRoute::from_model(model, secrets_manager).await?];
+        for fallback in model.fallbacks() {
+            let fallback = config.resolve_model(fallback)?;
+            routes.push(ModelRoute::from_model(fallback, secrets_manager).await?);
+        }
+        Ok(Self {
+            routes,
+            breaker: MODEL_CIRCUIT_BREAKER.clone(),
+        })
+    }
+
+    pub fn primary(&self) -> &ModelRoute {
+        &self.routes[0]
+    }
+
+    /// Run `f` against each model in turn until one succeeds or fails
+    /// permanently.  The model that served the call is recorded as
+    /// `gen_ai.response.model` on the current span.
+    ///
+    /// A chain without fallbacks never consults the circuit breaker, so
+    /// callers retrying it keep their existing backoff behaviour.
+    pub async fn call<T, F, Fut>(&self, f: F) -> Result<T, backoff::Error<OxyError>>
+    where
+        F: Fn(ModelRoute) -> Fut,
+        Fut: std::future::Future<Output = Result<T, backoff::Error<OxyError>>>,
+    {
+        let has_fallbacks = self.routes.len() > 1;
+        let mut last_error = None;
+        for route in &self.routes {
+            if has_fallbacks && let Err(reason) = self.breaker.check(&route.name) {
+                tracing::debug!("Skipping model: {reason}");
+                last_error = Some(backoff::Error::transient(OxyError::LLMError(reason)));
+                continue;
+            }
+            if let Some(limiter) = &route.limiter {
+                limiter.acquire().await;
+            }
+            match f(route.clone()).await {
+                Ok(value) => {
+                    if has_fallbacks {
+                        self.breaker.record_success(&route.name);
+                    }
+                    tracing::Span::current().record("gen_ai.response.model", route.model.as_str());
+                    if route.name != self.primary().name {
+                        tracing::info!("Call served by fallback model '{}'", route.name);
+                    }
+                    return Ok(value);
+                }
+                Err(err @ backoff::Error::Permanent(_)) => return Err(err),
+                Err(err) => {
+                    if has_fallbacks {
+                        self.breaker.record_failure(&route.name);
+                        tracing::warn!(
+                            "Model '{}' failed, trying the next model: {err}",
+                            route.name
+                        );
+                    }
+                    last_error = Some(err);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| {
+            backoff::Error::transient(OxyError::LLMError("No model available".to_string()))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use async_openai::config::OpenAIConfig;
+    use oxy_openai::ConfigType;
+
+    use super::*;
+
+    fn route(name: &str) -> ModelRoute {
+        ModelRoute {
+            name: name.to_string(),
+            model: format!("{name}-model"),
+            client: OpenAIClient::with_config(ConfigType::Default(OpenAIConfig::new())),
+            limiter: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_call_fails_over_and_skips_open_circuits() {
+        let chain = ModelChain {
+            routes: vec![route("primary"), route("backup")],
+            breaker: CircuitBreaker::new(1, Duration::from_secs(60)),
+        };
+        let primary_calls = AtomicUsize::new(0);
+        let call = || {
+            chain.call(|route| {
+                let primary_calls = &primary_calls;
+                async move {
+                    if route.name == "primary" {
+                        primary_calls.fetch_add(1, Ordering::SeqCst);
+                        return Err(backoff::Error::transient(OxyError::LLMError(
+                            "rate limited".to_string(),
+                        )));
+                    }
+                    Ok(route.model)
+                }
+            })
+        };
+
+        assert_eq!(call().await.unwrap(), "backup-model");
+        // The primary's circuit is now open, so it is not called again
+        assert_eq!(call().await.unwrap(), "backup-model");
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_call_returns_permanent_errors() {
+        let chain = ModelChain {
+            routes: vec![route("primary"), route("backup")],
+            breaker: CircuitBreaker::new(1, Duration::from_secs(60)),
+        };
+        let result: Result<String, _> = chain
+            .call(|_| async {
+                Err(backoff::Error::permanent(OxyError::LLMError(
+                    "context length exceeded".to_string(),
+                )))
+            })
+            .await;
+        assert!(matches!(result, Err(backoff::Error::Permanent(_))));
+    }
+}
@@ -10,9 +10,9 @@ use async_openai::{
         chat::{
             ChatCompletionMessageToolCall, ChatCompletionMessageToolCallChunk,
             ChatCompletionNamedToolChoice, ChatCompletionRequestMessage, ChatCompletionTool,
-            ChatCompletionToolChoiceOption, ChatCompletionTools, CreateChatCompletionRequestArgs,
-            CreateChatCompletionStreamResponse, FunctionName, FunctionObject, FunctionObjectArgs,
-            ReasoningEffort as OpenAIReasoningEffort,
+            ChatCompletionToolChoiceOption, ChatCompletionTools, CreateChatCompletionRequest,
+            CreateChatCompletionRequestArgs, CreateChatCompletionStreamResponse, FunctionName,
+            FunctionObject, FunctionObjectArgs, ReasoningEffort as OpenAIReasoningEffort,
         },
         responses::Reasoning,
     },
@@ -22,13 +22,18 @@ use omni::MetadataStorage;
 pub use oxy_openai::{ConfigType, CustomOpenAIConfig, OpenAIClient, StreamChunk};
 use schemars::schema::RootSchema;
 use std::collections::HashMap;
-use tokio_stream::StreamExt;
+use tokio_stream::{Stream, StreamExt};
 
 use crate::{
     adapters::{
-        create_app_schema, edit_app_schema, looker_tool_description::get_looker_query_description,
-        read_app_schema, secrets::SecretsManager,
-        semantic_tool_description::get_semantic_query_description, viz_schema,
+        create_app_schema, edit_app_schema,
+        lenient_types::LenientChatCompletionResponse,
+        looker_tool_description::get_looker_query_description,
+        read_app_schema,
+        replay::{FixtureStore, replay_openai_call, replay_openai_stream},
+        secrets::SecretsManager,
+        semantic_tool_description::get_semantic_query_description,
+        viz_schema,
         workspace::manager::WorkspaceManager,
     },
     config::{
@@ -692,6 +697,7 @@ impl AsyncFunctionObject for ChatCompletionTool {
 pub struct OpenAIAdapter {
     client: OpenAIClient,
     model_name: String,
+    replay: Option<FixtureStore>,
 }
 
 impl OpenAIAdapter {
@@ -702,14 +708,15 @@ impl OpenAIAdapter {
         let model = workspace.config_manager.resolve_model(model_ref)?;
         let config_type = model.into_openai_config(&workspace.secrets_manager).await?;
         let client = Client::with_config(config_type);
-        Ok(Self {
-            client,
-            model_name: model.model_name().to_string(),
-        })
+        Ok(Self::new(client, model.model_name().to_string()))
     }
 
     pub fn new(client: OpenAIClient, model_name: String) -> Self {
-        Self { client, model_name }
+        Self {
+            client,
+            model_name,
+            replay: FixtureStore::from_env(),
+        }
     }
 
     #[tracing::instrument(
@@ -730,9 +737,7 @@ impl OpenAIAdapter {
             .build()
             .map_err(|e| OxyError::RuntimeError(format!("Failed to build request: {e}")))?;
 
-        // Use lenient types for better compatibility with OpenAI-compatible APIs (Groq, Mistral, etc.)
-        let response: super::lenient_types::LenientChatCompletionResponse =
-            self.client.chat().create_byot(request).await?;
+        let response = self.create(request).await?;
 
         if let Some(usage) = &response.usage {
             events::llm::usage(usage.prompt_tokens as i64, usage.completion_tokens as i64);
@@ -784,13 +789,7 @@ impl OpenAIAdapter {
             .build()
             .map_err(|e| OxyError::RuntimeError(format!("Failed to build request: {e}")))?;
 
-        // Use lenient types for better compatibility with OpenAI-compatible APIs (Groq, Mistral, etc.)
-        let response: super::lenient_types::LenientChatCompletionResponse = self
-            .client
-            .chat()
-            .create_byot(request)
-            .await
-            .map_err(|e| OxyError::RuntimeError(format!("OpenAI API error: {e}")))?;
+        let response = self.create(request).await?;
 
         if let Some(usage_data) = &response.usage {
             events::llm::usage(
@@ -798,10 +797,13 @@ impl OpenAIAdapter {
                 usage_data.completion_tokens as i64,
             );
             execution_context
-                .write_usage(crate::execute::types::Usage::new(
-                    usage_data.prompt_tokens as i32,
-                    usage_data.completion_tokens as i32,
-                ))
+                .write_model_usage(
+                    &self.model_name,
+                    crate::execute::types::Usage::new(
+                        usage_data.prompt_tokens as i32,
+                        usage_data.completion_tokens as i32,
+                    ),
+                )
                 .await?;
         }
 
@@ -820,18 +822,9 @@ impl OpenAIAdapter {
             .map_err(|e| OxyError::RuntimeError(format!("Failed to build request: {e}")))?;
 
         let stream = self
-            .client
-            .chat()
             .create_stream(request)
-            .await
-            .map_err(|e| OxyError::RuntimeError(format!("OpenAI API error: {e}")))?
-            .map(|result| match result {
-                Ok(response) => {
-                    let stream_response = self.extract_stream(&response);
-                    Ok(stream_response)
-                }
-                Err(e) => Err(OxyError::RuntimeError(format!("OpenAI API error: {e}"))),
-            });
+            .await?
+            .map(|result| result.map(|response| self.extract_stream(&response)));
         Ok(stream)
     }
 
@@ -860,12 +853,7 @@ impl OpenAIAdapter {
             .stream(true)
             .build()
             .map_err(|e| OxyError::RuntimeError(format!("Failed to build request: {e}")))?;
-        let stream = self
-            .client
-            .chat()
-            .create_stream(request)
-            .await
-            .map_err(|e| OxyError::RuntimeError(format!("OpenAI API error: {e}")))?;
+        let stream = self.create_stream(request).await?;
 
         let mut tool_calls_buffer = HashMap::<u32, StreamChunk>::new();
         // Aggregate tool call chunks by their index to form complete tool calls
@@ -917,11 +905,42 @@ impl OpenAIAdapter {
                     stream_response.map(|text| Ok(StreamChunk::Text(text)))
                 }
             }
-            Err(_) => Some(Err(OxyError::RuntimeError("OpenAI API error".to_string()))),
+            Err(e) => Some(Err(e)),
         });
         Ok(stream)
     }
 
+    async fn create(
+        &self,
+        request: CreateChatCompletionRequest,
+    ) -> Result<LenientChatCompletionResponse, OxyError> {
+        // Use lenient types for better compatibility with OpenAI-compatible APIs (Groq, Mistral, etc.)
+        replay_openai_call(
+            self.replay.as_ref(),
+            &request,
+            self.client.chat().create_byot(&request),
+        )
+        .await
+        .map_err(|e| OxyError::RuntimeError(format!("OpenAI API error: {e}")))
+    }
+
+    async fn create_stream(
+        &self,
+        request: CreateChatCompletionRequest,
+    ) -> Result<impl Stream<Item = Result<CreateChatCompletionStreamResponse, OxyError>>, OxyError>
+    {
+        let stream = replay_openai_stream(
+            self.replay.as_ref(),
+            &request,
+            self.client.chat().create_stream(request.clone()),
+        )
+        .await
+        .map_err(|e| OxyError::RuntimeError(format!("OpenAI API error: {e}")))?;
+        Ok(stream.map(|result| {
+            result.map_err(|e| OxyError::RuntimeError(format!("OpenAI API error: {e}")))
+        }))
+    }
+
     fn request_builder<M: Into<Vec<ChatCompletionRequestMessage>>>(
         &self,
         messages: M,
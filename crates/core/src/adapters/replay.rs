@@ -0,0 +1,202 @@
+//! Record/replay of LLM and database calls, so a test suite can run offline
+//! and deterministically.
+//!
+//! Set `OXY_REPLAY_MODE=record` to capture every OpenAI API request and
+//! connector query into fixture files, then `OXY_REPLAY_MODE=replay` to
+//! answer the same requests from those files without touching the network.
+//! Fixtures live under `OXY_REPLAY_DIR` (default `replay-fixtures`), one
+//! file per request, named by a hash of the normalized request. The files
+//! are shared with the agentic crates through [`agentic_core::replay`].
+
+use std::{
+    path::{Path, PathBuf},
+    pin::Pin,
+};
+
+use agentic_core::replay::{FixtureDir, FixtureError};
+use async_openai::error::OpenAIError;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use tokio_stream::{Stream, StreamExt};
+
+use oxy_shared::errors::OxyError;
+
+pub use agentic_core::replay::{DEFAULT_REPLAY_DIR, REPLAY_DIR_ENV, REPLAY_MODE_ENV, ReplayMode};
+
+/// Fixture namespace for OpenAI chat completion requests.
+pub const OPENAI_FIXTURES: &str = "openai";
+/// Fixture namespace for connector queries.
+pub const CONNECTOR_FIXTURES: &str = "connector";
+
+/// [`FixtureDir`] for serializable requests and responses.
+#[derive(Debug, Clone)]
+pub struct FixtureStore {
+    fixtures: FixtureDir,
+}
+
+impl FixtureStore {
+    pub fn new(mode: ReplayMode, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            fixtures: FixtureDir::new(mode, dir),
+        }
+    }
+
+    /// The store configured through `OXY_REPLAY_MODE` / `OXY_REPLAY_DIR`, or
+    /// `None` when record/replay is off.
+    pub fn from_env() -> Option<Self> {
+        FixtureDir::from_env().map(|fixtures| Self { fixtures })
+    }
+
+    pub fn mode(&self) -> ReplayMode {
+        self.fixtures.mode()
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        self.fixtures.is_replaying()
+    }
+
+    /// Fixture file for `request` under `kind`. The namespace directory is
+    /// created when recording.
+    pub fn path(
+        &self,
+        kind: &str,
+        request: &impl Serialize,
+        extension: &str,
+    ) -> Result<PathBuf, OxyError> {
+        self.fixtures
+            .path(kind, &to_value(request)?, extension)
+            .map_err(fixture_oxy_error)
+    }
+
+    /// The recorded response for `request`.
+    pub fn load<T: DeserializeOwned>(
+        &self,
+        kind: &str,
+        request: &impl Serialize,
+    ) -> Result<T, OxyError> {
+        let response = self
+            .fixtures
+            .load(kind, &to_value(request)?)
+            .map_err(fixture_oxy_error)?;
+        serde_json::from_value(response)
+            .map_err(|e| OxyError::SerializerError(format!("Invalid fixture response: {e}")))
+    }
+
+    /// Record `response` for `request`.
+    pub fn save<T: Serialize>(
+        &self,
+        kind: &str,
+        request: &impl Serialize,
+        response: &T,
+    ) -> Result<(), OxyError> {
+        self.fixtures
+            .save(kind, &to_value(request)?, to_value(response)?)
+            .map_err(fixture_oxy_error)
+    }
+}
+
+pub type ReplayStream<T> = Pin<Box<dyn Stream<Item = Result<T, OpenAIError>> + Send>>;
+
+/// Make an OpenAI API call through `replay`: answered from its fixture when
+/// replaying, recorded when recording, and passed through when off.
+pub async fn replay_openai_call<T: Serialize + DeserializeOwned>(
+    replay: Option<&FixtureStore>,
+    request: &impl Serialize,
+    live: impl Future<Output = Result<T, OpenAIError>>,
+) -> Result<T, OpenAIError> {
+    let Some(replay) = replay else {
+        return live.await;
+    };
+    if replay.is_replaying() {
+        return replay.load(OPENAI_FIXTURES, request).map_err(fixture_error);
+    }
+    let response = live.await?;
+    replay
+        .save(OPENAI_FIXTURES, request, &response)
+        .map_err(fixture_error)?;
+    Ok(response)
+}
+
+/// Streaming counterpart of [`replay_openai_call`]. While recording, the
+/// whole stream is read before it is handed back so only complete responses
+/// are written.
+pub async fn replay_openai_stream<T, S>(
+    replay: Option<&FixtureStore>,
+    request: &impl Serialize,
+    live: impl Future<Output = Result<S, OpenAIError>>,
+) -> Result<ReplayStream<T>, OpenAIError>
+where
+    T: Serialize + DeserializeOwned + Send + 'static,
+    S: Stream<Item = Result<T, OpenAIError>> + Send + 'static,
+{
+    let Some(replay) = replay else {
+        return Ok(Box::pin(live.await?));
+    };
+    let events: Vec<T> = if replay.is_replaying() {
+        replay
+            .load(OPENAI_FIXTURES, request)
+            .map_err(fixture_error)?
+    } else {
+        let events = live.await?.collect::<Result<Vec<T>, _>>().await?;
+        replay
+            .save(OPENAI_FIXTURES, request, &events)
+            .map_err(fixture_error)?;
+        events
+    };
+    Ok(Box::pin(tokio_stream::iter(events.into_iter().map(Ok))))
+}
+
+/// Fixture problems are never fixed by retrying, so surface them as the
+/// OpenAI error kind callers treat as permanent.
+fn fixture_error(err: OxyError) -> OpenAIError {
+    OpenAIError::InvalidArgument(err.to_string())
+}
+
+/// Read a fixture, pointing at re-recording when it does not exist.
+pub fn read_fixture(path: &Path) -> Result<String, OxyError> {
+    agentic_core::replay::read_fixture(path).map_err(fixture_oxy_error)
+}
+
+pub fn missing_fixture(path: &Path) -> OxyError {
+    fixture_oxy_error(FixtureError::Missing(path.to_path_buf()))
+}
+
+fn fixture_oxy_error(err: FixtureError) -> OxyError {
+    match err {
+        FixtureError::Invalid { .. } => OxyError::SerializerError(err.to_string()),
+        FixtureError::Missing(_) | FixtureError::Write { .. } => {
+            OxyError::RuntimeError(err.to_string())
+        }
+    }
+}
+
+fn to_value(value: &impl Serialize) -> Result<Value, OxyError> {
+    serde_json::to_value(value)
+        .map_err(|e| OxyError::SerializerError(format!("Failed to serialize request: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_record_then_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let request = json!({"query": "SELECT 1"});
+
+        let recorder = FixtureStore::new(ReplayMode::Record, dir.path());
+        recorder
+            .save("test", &request, &vec!["a".to_string(), "b".to_string()])
+            .unwrap();
+
+        let replayer = FixtureStore::new(ReplayMode::Replay, dir.path());
+        let response: Vec<String> = replayer.load("test", &request).unwrap();
+        assert_eq!(response, vec!["a", "b"]);
+        assert!(
+            replayer
+                .load::<Vec<String>>("test", &json!({"query": "SELECT 2"}))
+                .is_err()
+        );
+    }
+}
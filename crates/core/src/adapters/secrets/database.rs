@@ -43,11 +43,13 @@ impl SecretsStorage for SecretsDatabaseStorage {
         Ok(())
     }
 
-    async fn remove_secret(&self, secret_name: &str) -> Result<(), OxyError> {
+    async fn remove_secret(&self, secret_name: &str, removed_by: Uuid) -> Result<(), OxyError> {
         let db = establish_connection()
             .await
             .map_err(|e| OxyError::Database(format!("Failed to establish connection: {}", e)))?;
 
-        self.secret_manager.delete_secret(&db, secret_name).await
+        self.secret_manager
+            .delete_secret(&db, secret_name, removed_by)
+            .await
     }
 }
@@ -96,7 +96,7 @@ impl SecretsStorage for SecretsEnvironmentStorage {
         Ok(())
     }
 
-    async fn remove_secret(&self, secret_name: &str) -> Result<(), OxyError> {
+    async fn remove_secret(&self, secret_name: &str, _removed_by: Uuid) -> Result<(), OxyError> {
         let env_path = Self::get_env_file_path()?;
         let lines = Self::read_env_file(&env_path)?;
 
@@ -55,8 +55,8 @@ impl SecretsManager {
             .await
     }
 
-    pub async fn remove_secret(&self, secret_name: &str) -> Result<(), OxyError> {
-        self.storage.remove_secret(secret_name).await
+    pub async fn remove_secret(&self, secret_name: &str, removed_by: Uuid) -> Result<(), OxyError> {
+        self.storage.remove_secret(secret_name, removed_by).await
     }
 
     /// Resolve a config value from either a direct value or an environment variable.
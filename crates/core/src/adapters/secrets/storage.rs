@@ -13,7 +13,7 @@ pub trait SecretsStorage {
         secret_value: &str,
         created_by: Uuid,
     ) -> Result<(), OxyError>;
-    async fn remove_secret(&self, secret_name: &str) -> Result<(), OxyError>;
+    async fn remove_secret(&self, secret_name: &str, removed_by: Uuid) -> Result<(), OxyError>;
 }
 
 #[enum_dispatch::enum_dispatch(SecretsStorage)]
@@ -63,7 +63,7 @@ impl SecretsStorage for SecretsFallbackStorage {
             .await
     }
 
-    async fn remove_secret(&self, secret_name: &str) -> Result<(), OxyError> {
-        self.db.remove_secret(secret_name).await
+    async fn remove_secret(&self, secret_name: &str, removed_by: Uuid) -> Result<(), OxyError> {
+        self.db.remove_secret(secret_name, removed_by).await
     }
 }
@@ -1,5 +1,7 @@
 use crate::config;
-use oxy_semantic::{self, SemanticLayer, Topic, View, parse_semantic_layer_from_dir};
+use oxy_semantic::{
+    self, SemanticLayer, Topic, View, access::redact_view, parse_semantic_layer_from_dir,
+};
 use oxy_shared::errors::OxyError;
 
 /// Get enhanced description for semantic query tool with semantic layer metadata
@@ -58,7 +60,15 @@ fn load_semantic_layer(config_manager: &config::ConfigManager) -> Result<Semanti
         ))
     })?;
 
-    Ok(parse_result.semantic_layer)
+    // Fields the caller may not see never reach the prompt
+    let mut semantic_layer = parse_result.semantic_layer;
+    let access_level = Some(config_manager.access_level());
+    semantic_layer.views = semantic_layer
+        .views
+        .iter()
+        .map(|view| redact_view(view, access_level))
+        .collect();
+    Ok(semantic_layer)
 }
 
 fn get_topics_metadata(
@@ -234,6 +244,13 @@ fn build_dimensions_metadata(description: &mut String, view: &View) {
             dimension_line.push_str(&format!(" [synonyms: {}]", synonyms.join(", ")));
         }
 
+        // Add the grains a date dimension can be queried at as `<dimension>.<grain>`
+        let grains = dimension.available_grains();
+        if !grains.is_empty() {
+            let grains: Vec<String> = grains.iter().map(ToString::to_string).collect();
+            dimension_line.push_str(&format!(" [grains: {}]", grains.join(", ")));
+        }
+
         dimension_line.push('\n');
         description.push_str(&dimension_line);
     }
@@ -14,6 +14,7 @@ use oxy_semantic::Topic;
 use oxy_shared::errors::OxyError;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use uuid::Uuid;
 
 use parse::parse_retrieval_object;
 pub use parse::parse_sql_source_type;
@@ -186,6 +187,7 @@ pub async fn build_all_retrieval_objects(
 pub async fn ingest_retrieval_objects(
     config: &ConfigManager,
     secrets_manager: &SecretsManager,
+    workspace_id: Uuid,
     retrieval_objects: &[RetrievalObject],
     drop_all_tables: bool,
 ) -> Result<(), OxyError> {
@@ -199,6 +201,7 @@ pub async fn ingest_retrieval_objects(
                         let db = VectorStore::from_retrieval(
                             config,
                             secrets_manager,
+                            workspace_id,
                             &agent.name,
                             retrieval,
                         )
@@ -224,6 +227,7 @@ pub async fn ingest_retrieval_objects(
                 let db = VectorStore::from_routing_agent(
                     config,
                     secrets_manager,
+                    workspace_id,
                     &agent.name,
                     &agent.model,
                     routing_agent,
@@ -254,6 +258,7 @@ pub async fn ingest_retrieval_objects(
             let db = VectorStore::new(
                 config,
                 secrets_manager,
+                workspace_id,
                 &routing_config.db_config,
                 &format!("{}-routing", aw.name),
                 model.clone(),
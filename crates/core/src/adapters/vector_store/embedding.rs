@@ -46,3 +46,28 @@ pub async fn create_embeddings_batched(
 
     Ok(all_embeddings)
 }
+
+/// Embed a single text, e.g. a search query.
+pub async fn create_embedding(
+    client: &OpenAIClient,
+    embedding_config: &EmbeddingConfig,
+    content: &str,
+) -> Result<Embedding, OxyError> {
+    let embeddings_request = CreateEmbeddingRequestArgs::default()
+        .model(embedding_config.embed_model.clone())
+        .input(EmbeddingInput::String(content.to_string()))
+        .dimensions(embedding_config.n_dims as u32)
+        .build()
+        .map_err(|e| OxyError::RuntimeError(format!("Failed to build embedding request: {e}")))?;
+    let embeddings_response = client
+        .embeddings()
+        .create(embeddings_request)
+        .await
+        .map_err(|e| OxyError::RuntimeError(format!("Failed to create embeddings: {e}")))?;
+    embeddings_response
+        .data
+        .into_iter()
+        .next()
+        .map(|data| data.embedding)
+        .ok_or_else(|| OxyError::RuntimeError("No embedding returned".to_string()))
+}
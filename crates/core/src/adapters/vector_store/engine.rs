@@ -1,10 +1,33 @@
-use super::types::{RetrievalObject, SearchRecord};
+use super::types::{Embedding, RetrievalItem};
 use enum_dispatch::enum_dispatch;
 use oxy_shared::errors::OxyError;
 
+/// Storage for the retrieval items of one retrieval tool or routing agent.
+///
+/// Embedding, radius and ranking are handled by [`super::VectorStore`], so a
+/// backend only stores items and finds candidates.
 #[enum_dispatch]
 pub(super) trait VectorEngine {
-    async fn ingest(&self, retrieval_objects: &Vec<RetrievalObject>) -> Result<(), OxyError>;
-    async fn search(&self, query: &str) -> Result<Vec<SearchRecord>, OxyError>;
+    /// Insert items, replacing any stored under the same
+    /// [`RetrievalItem::upsert_key`].
+    async fn upsert(&self, items: &[RetrievalItem]) -> Result<(), OxyError>;
+    /// Rebuild indexes after a bulk ingestion.
+    async fn optimize(&self) -> Result<(), OxyError>;
+    /// The `limit` nearest items to `query_vector` with their cosine distance.
+    async fn vector_search(
+        &self,
+        query_vector: &Embedding,
+        limit: usize,
+    ) -> Result<Vec<(RetrievalItem, f32)>, OxyError>;
+    /// Up to `limit` items whose embedding content shares words with `query`,
+    /// best first.
+    async fn keyword_search(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<RetrievalItem>, OxyError>;
+    /// Delete every item of the given sources.
+    async fn delete(&self, source_identifiers: &[String]) -> Result<(), OxyError>;
+    /// Delete everything, including the table or collection.
     async fn cleanup(&self) -> Result<(), OxyError>;
 }
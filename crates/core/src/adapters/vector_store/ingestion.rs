@@ -0,0 +1,121 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    adapters::{
+        openai::OpenAIClient,
+        vector_store::{
+            build_index_key,
+            embedding::create_embeddings_batched,
+            types::{Embedding, RetrievalItem, RetrievalObject},
+        },
+    },
+    config::{
+        constants::{
+            RETRIEVAL_CHILD_INCLUSION_RADIUS, RETRIEVAL_DEFAULT_INCLUSION_RADIUS,
+            RETRIEVAL_EXCLUSION_BUFFER_MULTIPLIER,
+        },
+        model::EmbeddingConfig,
+    },
+};
+use oxy_shared::errors::OxyError;
+
+use super::math::MathUtils;
+
+/// Embed the inclusions of `retrieval_objects` and work out the radius of
+/// each, shrunk to keep clear of the object's exclusions.
+pub(super) async fn build_retrieval_items(
+    client: &OpenAIClient,
+    embedding_config: &EmbeddingConfig,
+    retrieval_objects: &[RetrievalObject],
+) -> Result<Vec<RetrievalItem>, OxyError> {
+    let all_texts_to_embed = collect_unique_retrieval_strings(retrieval_objects);
+    let all_embeddings =
+        create_embeddings_batched(client, embedding_config, &all_texts_to_embed).await?;
+    let text_to_embedding: HashMap<String, Embedding> =
+        all_texts_to_embed.into_iter().zip(all_embeddings).collect();
+
+    // Use a HashMap to deduplicate items by their upsert_key (source_identifier + embedding_content)
+    // This prevents "Ambiguous merge insert" errors when the same item appears multiple times
+    let mut retrieval_items_by_key: HashMap<String, RetrievalItem> = HashMap::new();
+
+    for obj in retrieval_objects.iter() {
+        let content = obj.determine_content(); // a retrieval object's inclusions all have the same content
+        let mut exclusion_embeddings: Vec<Embedding> = Vec::with_capacity(obj.exclusions.len());
+
+        for exclusion_text in obj.exclusions.iter() {
+            let embedding = text_to_embedding
+                .get(exclusion_text)
+                .cloned()
+                .ok_or_else(|| {
+                    OxyError::RuntimeError(format!(
+                        "Embedding not found for exclusion: {exclusion_text}"
+                    ))
+                })?;
+            exclusion_embeddings.push(embedding.clone());
+        }
+
+        for inclusion_text in obj.inclusions.iter() {
+            let embedding = text_to_embedding
+                .get(inclusion_text)
+                .cloned()
+                .ok_or_else(|| {
+                    OxyError::RuntimeError(format!(
+                        "Embedding not found for inclusion: {inclusion_text}"
+                    ))
+                })?;
+            let max_radius = if obj.is_child {
+                RETRIEVAL_CHILD_INCLUSION_RADIUS
+            } else {
+                RETRIEVAL_DEFAULT_INCLUSION_RADIUS
+            };
+
+            let radius = if exclusion_embeddings.is_empty() {
+                max_radius
+            } else {
+                match MathUtils::find_min_distance(&embedding, &exclusion_embeddings) {
+                    Ok(Some(d)) => (d * RETRIEVAL_EXCLUSION_BUFFER_MULTIPLIER).min(max_radius),
+                    Ok(None) => max_radius,
+                    Err(e) => {
+                        return Err(OxyError::RuntimeError(format!(
+                            "Vector dimension error: {e}"
+                        )));
+                    }
+                }
+            };
+
+            // Generate the same upsert_key used by the backends
+            let upsert_key =
+                build_index_key([obj.source_identifier.as_str(), inclusion_text.as_str()]);
+
+            // If duplicate, later item wins (consistent behavior)
+            retrieval_items_by_key.insert(
+                upsert_key,
+                RetrievalItem {
+                    source_identifier: obj.source_identifier.clone(),
+                    embedding_content: inclusion_text.clone(),
+                    embedding,
+                    content: content.clone(),
+                    source_type: obj.source_type.clone(),
+                    radius,
+                },
+            );
+        }
+    }
+
+    Ok(retrieval_items_by_key.into_values().collect())
+}
+
+fn collect_unique_retrieval_strings(retrieval_objects: &[RetrievalObject]) -> Vec<String> {
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut unique_texts: Vec<String> = Vec::new();
+
+    for obj in retrieval_objects.iter() {
+        for text in obj.exclusions.iter().chain(obj.inclusions.iter()) {
+            if seen.insert(text.as_str()) {
+                unique_texts.push(text.clone());
+            }
+        }
+    }
+
+    unique_texts
+}
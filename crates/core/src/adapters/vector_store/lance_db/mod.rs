@@ -1,76 +1,147 @@
-mod ingestion;
-mod math;
 mod schema;
-mod search;
 mod serialization;
 mod table;
 
-use lancedb::Connection;
-
-use crate::{
-    adapters::openai::OpenAIClient, config::model::EmbeddingConfig,
-    service::retrieval::enum_index::EnumIndexManager,
+use futures::TryStreamExt;
+use lancedb::{
+    Connection, DistanceType,
+    index::scalar::FullTextSearchQuery,
+    query::{ExecutableQuery, QueryBase},
 };
-use oxy_shared::errors::OxyError;
 
-use super::{engine::VectorEngine, types::RetrievalObject};
+use crate::config::constants::{RETRIEVAL_EMBEDDINGS_COLUMN, RETRIEVAL_INCLUSIONS_TABLE};
+use oxy_shared::errors::OxyError;
 
-use std::sync::Arc;
+use super::{
+    engine::VectorEngine,
+    types::{Embedding, RetrievalItem},
+};
 
-use ingestion::IngestionManager;
-use search::SearchManager;
-use table::TableManager;
+use serialization::SerializationUtils;
+use table::{RETRIEVAL_KEYWORD_COLUMN, TableManager};
 
 pub(super) struct LanceDB {
-    client: OpenAIClient,
     connection: Connection,
-    embedding_config: EmbeddingConfig,
-    table_manager: Arc<TableManager>,
-    enum_index_manager: Arc<EnumIndexManager>,
+    n_dims: usize,
+    table_manager: TableManager,
 }
 
 impl LanceDB {
-    pub(super) fn new(
-        client: OpenAIClient,
-        connection: Connection,
-        embedding_config: EmbeddingConfig,
-        enum_index_manager: Arc<EnumIndexManager>,
-    ) -> Self {
-        let table_manager = Arc::new(TableManager::new(
-            connection.clone(),
-            embedding_config.n_dims,
-        ));
-
+    pub(super) fn new(connection: Connection, n_dims: usize) -> Self {
+        let table_manager = TableManager::new(connection.clone(), n_dims);
         Self {
-            client,
             connection,
-            embedding_config,
+            n_dims,
             table_manager,
-            enum_index_manager,
         }
     }
 }
 
 impl VectorEngine for LanceDB {
-    async fn ingest(&self, retrieval_objects: &Vec<RetrievalObject>) -> Result<(), OxyError> {
-        let ingestion_manager = IngestionManager::new(
-            self.client.clone(),
-            self.embedding_config.clone(),
-            self.table_manager.clone(),
-        );
-        // Build-time ingestion: reindex after upsert
-        let reindex = true;
-        ingestion_manager.ingest(retrieval_objects, reindex).await
+    async fn upsert(&self, items: &[RetrievalItem]) -> Result<(), OxyError> {
+        if items.is_empty() {
+            return Ok(());
+        }
+        let batch = SerializationUtils::create_retrieval_record_batch(items, self.n_dims)?;
+        let table = self
+            .table_manager
+            .get_or_create_table(RETRIEVAL_INCLUSIONS_TABLE)
+            .await?;
+        self.table_manager.upsert_batch(&table, batch).await
+    }
+
+    async fn optimize(&self) -> Result<(), OxyError> {
+        let table = self
+            .table_manager
+            .get_or_create_table(RETRIEVAL_INCLUSIONS_TABLE)
+            .await?;
+        self.table_manager
+            .reindex_and_optimize(&table, &[RETRIEVAL_EMBEDDINGS_COLUMN])
+            .await
+    }
+
+    async fn vector_search(
+        &self,
+        query_vector: &Embedding,
+        limit: usize,
+    ) -> Result<Vec<(RetrievalItem, f32)>, OxyError> {
+        let table = self
+            .table_manager
+            .get_or_create_table(RETRIEVAL_INCLUSIONS_TABLE)
+            .await?;
+        if table.count_rows(None).await? == 0 {
+            tracing::info!("No inclusions found in table, returning empty results");
+            return Ok(vec![]);
+        }
+
+        let mut stream = table
+            .vector_search(query_vector.as_slice())?
+            .column(RETRIEVAL_EMBEDDINGS_COLUMN)
+            .distance_type(DistanceType::Cosine)
+            .limit(limit)
+            .execute()
+            .await?;
+        let mut results = vec![];
+        while let Some(record_batch) = stream.try_next().await? {
+            results.extend(SerializationUtils::deserialize_search_records(
+                &record_batch,
+            )?);
+        }
+        Ok(results)
+    }
+
+    async fn keyword_search(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<RetrievalItem>, OxyError> {
+        let table = self
+            .table_manager
+            .get_or_create_table(RETRIEVAL_INCLUSIONS_TABLE)
+            .await?;
+        // Tables built before keyword search was added have no full-text index
+        // until the next `oxy build`
+        let has_fts_index = table
+            .list_indices()
+            .await?
+            .iter()
+            .any(|index| index.columns.iter().any(|c| c == RETRIEVAL_KEYWORD_COLUMN));
+        if !has_fts_index {
+            return Ok(vec![]);
+        }
+
+        let mut stream = table
+            .query()
+            .full_text_search(FullTextSearchQuery::new(query.to_string()))
+            .limit(limit)
+            .execute()
+            .await?;
+        let mut results = vec![];
+        while let Some(record_batch) = stream.try_next().await? {
+            results.extend(SerializationUtils::deserialize_retrieval_items(
+                &record_batch,
+            )?);
+        }
+        Ok(results)
     }
 
-    async fn search(&self, query: &str) -> Result<Vec<super::types::SearchRecord>, OxyError> {
-        let search_manager = SearchManager::new(
-            self.embedding_config.clone(),
-            self.client.clone(),
-            self.table_manager.clone(),
-            self.enum_index_manager.clone(),
-        );
-        search_manager.search(query).await
+    async fn delete(&self, source_identifiers: &[String]) -> Result<(), OxyError> {
+        if source_identifiers.is_empty() {
+            return Ok(());
+        }
+        let table = self
+            .table_manager
+            .get_or_create_table(RETRIEVAL_INCLUSIONS_TABLE)
+            .await?;
+        let sources = source_identifiers
+            .iter()
+            .map(|source| format!("'{}'", source.replace('\'', "''")))
+            .collect::<Vec<_>>()
+            .join(", ");
+        table
+            .delete(&format!("source_identifier IN ({sources})"))
+            .await?;
+        Ok(())
     }
 
     async fn cleanup(&self) -> Result<(), OxyError> {
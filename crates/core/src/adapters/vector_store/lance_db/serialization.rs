@@ -1,6 +1,5 @@
 use crate::{
-    adapters::vector_store::{build_index_key, types::RetrievalItem},
-    config::constants::RETRIEVAL_EMBEDDINGS_COLUMN,
+    adapters::vector_store::types::RetrievalItem, config::constants::RETRIEVAL_EMBEDDINGS_COLUMN,
 };
 use arrow57::array::{Array, FixedSizeListArray, Float32Array, RecordBatch, StringArray};
 use oxy_shared::errors::OxyError;
@@ -10,7 +9,7 @@ pub(super) struct SerializationUtils;
 
 impl SerializationUtils {
     pub(super) fn create_retrieval_record_batch(
-        items: &Vec<RetrievalItem>,
+        items: &[RetrievalItem],
         n_dims: usize,
     ) -> Result<arrow57::array::RecordBatch, OxyError> {
         let schema = super::schema::SchemaUtils::create_retrieval_schema(n_dims);
@@ -25,9 +24,9 @@ impl SerializationUtils {
             items.iter().map(|it| it.source_identifier.clone()),
         ));
         // upsert_key built from composite of source_identifier and embedding_content
-        let upsert_keys = Arc::new(StringArray::from_iter_values(items.iter().map(|it| {
-            build_index_key([it.source_identifier.as_str(), it.embedding_content.as_str()])
-        })));
+        let upsert_keys = Arc::new(StringArray::from_iter_values(
+            items.iter().map(|it| it.upsert_key()),
+        ));
         let embedding_contents = Arc::new(StringArray::from_iter_values(
             items.iter().map(|it| it.embedding_content.clone()),
         ));
@@ -67,6 +66,28 @@ impl SerializationUtils {
     pub(super) fn deserialize_search_records(
         record_batch: &RecordBatch,
     ) -> Result<Vec<(RetrievalItem, f32)>, OxyError> {
+        let items = Self::deserialize_retrieval_items(record_batch)?;
+        let distance_array = Self::get_optional_float32_array(record_batch, "_distance")
+            .ok_or_else(|| OxyError::RuntimeError("Missing _distance column".into()))?;
+
+        items
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| {
+                if distance_array.is_null(i) {
+                    return Err(OxyError::RuntimeError(format!(
+                        "Null distance for inclusion '{}:{}' - this should not be possible after vector search",
+                        item.source_identifier, item.embedding_content
+                    )));
+                }
+                Ok((item, distance_array.value(i)))
+            })
+            .collect()
+    }
+
+    pub(super) fn deserialize_retrieval_items(
+        record_batch: &RecordBatch,
+    ) -> Result<Vec<RetrievalItem>, OxyError> {
         let num_rows = record_batch.num_rows();
 
         let content_array = Self::get_string_array(record_batch, "content")?;
@@ -78,8 +99,6 @@ impl SerializationUtils {
                 .ok_or_else(|| OxyError::RuntimeError("Missing embedding column".into()))?;
         let radius_array = Self::get_optional_float32_array(record_batch, "radius")
             .ok_or_else(|| OxyError::RuntimeError("Missing radius column".into()))?;
-        let distance_array = Self::get_optional_float32_array(record_batch, "_distance")
-            .ok_or_else(|| OxyError::RuntimeError("Missing _distance column".into()))?;
 
         let mut results = Vec::new();
         for i in 0..num_rows {
@@ -112,23 +131,14 @@ impl SerializationUtils {
                 return Err(OxyError::RuntimeError("Null radius encountered".into()));
             };
 
-            let distance = if !distance_array.is_null(i) {
-                distance_array.value(i)
-            } else {
-                return Err(OxyError::RuntimeError(format!(
-                    "Null distance for inclusion '{source_identifier}:{embedding_content}' - this should not be possible after vector search"
-                )));
-            };
-
-            let item = RetrievalItem {
+            results.push(RetrievalItem {
                 content,
                 source_type,
                 source_identifier,
                 embedding_content,
                 embedding,
                 radius,
-            };
-            results.push((item, distance));
+            });
         }
 
         Ok(results)
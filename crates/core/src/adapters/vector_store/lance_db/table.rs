@@ -6,11 +6,14 @@ use arrow57::array::{RecordBatch, RecordBatchIterator};
 use lancedb::{
     Connection, Table,
     database::CreateTableMode,
-    index::{Index, vector::IvfHnswPqIndexBuilder},
+    index::{Index, scalar::FtsIndexBuilder, vector::IvfHnswPqIndexBuilder},
     table::OptimizeAction,
 };
 use oxy_shared::errors::OxyError;
 
+/// Column with the full-text index used for keyword search.
+pub(super) const RETRIEVAL_KEYWORD_COLUMN: &str = "embedding_content";
+
 pub(super) struct TableManager {
     connection: Connection,
     n_dims: usize,
@@ -109,6 +112,19 @@ impl TableManager {
             }
         }
 
+        let has_fts_index = indices
+            .iter()
+            .any(|index| index.columns.iter().any(|c| c == RETRIEVAL_KEYWORD_COLUMN));
+        if !has_fts_index {
+            table
+                .create_index(
+                    &[RETRIEVAL_KEYWORD_COLUMN],
+                    Index::FTS(FtsIndexBuilder::default()),
+                )
+                .execute()
+                .await?;
+        }
+
         let optimization_stats = table.optimize(OptimizeAction::All).await?;
         tracing::info!(
             "Table optimization stats:\n- Compaction: {:?} \n- Prune: {:?}\n",
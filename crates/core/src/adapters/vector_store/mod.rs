@@ -1,7 +1,12 @@
 pub mod builders;
 pub mod embedding;
 mod engine;
+mod ingestion;
 pub mod lance_db;
+mod math;
+mod pgvector;
+mod qdrant;
+mod ranking;
 mod search;
 mod store;
 pub mod types;
@@ -0,0 +1,429 @@
+//! Retrieval items in Postgres with the pgvector extension.
+//!
+//! Each store gets its own table per workspace, created on first use rather
+//! than by a migration so that Oxy's internal database does not require the
+//! extension unless a retrieval tool uses it.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use sea_orm::{
+    ConnectionTrait, Database, DatabaseBackend, DatabaseConnection, FromQueryResult, Statement,
+    Value,
+};
+
+use super::{
+    build_index_key,
+    engine::VectorEngine,
+    types::{Embedding, RetrievalItem},
+};
+use crate::{
+    adapters::secrets::SecretsManager, config::model::PgVectorConfig,
+    database::client::establish_connection,
+};
+use oxy_shared::errors::OxyError;
+use uuid::Uuid;
+
+// Rows per INSERT, well below Postgres' limit of 65535 bind parameters
+const UPSERT_CHUNK_SIZE: usize = 500;
+// pgvector cannot build an HNSW index on wider `vector` columns
+const MAX_INDEXED_DIMS: usize = 2000;
+
+pub(super) struct PgVector {
+    db: DatabaseConnection,
+    table: String,
+    n_dims: usize,
+    /// Set by `cleanup`; the next upsert recreates the table.
+    dropped: AtomicBool,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct PgVectorRow {
+    source_identifier: String,
+    source_type: String,
+    content: String,
+    embedding_content: String,
+    embedding: String,
+    radius: f32,
+    distance: Option<f32>,
+}
+
+impl PgVectorRow {
+    fn into_item(self) -> Result<(RetrievalItem, Option<f32>), OxyError> {
+        let embedding = parse_vector(&self.embedding)?;
+        Ok((
+            RetrievalItem {
+                source_identifier: self.source_identifier,
+                embedding_content: self.embedding_content,
+                embedding,
+                content: self.content,
+                source_type: self.source_type,
+                radius: self.radius,
+            },
+            self.distance,
+        ))
+    }
+}
+
+impl PgVector {
+    pub(super) async fn connect(
+        config: &PgVectorConfig,
+        secrets_manager: &SecretsManager,
+        workspace_id: Uuid,
+        name: &str,
+        n_dims: usize,
+    ) -> Result<Self, OxyError> {
+        let db = match &config.connection_string_var {
+            Some(var) => {
+                let url = secrets_manager
+                    .resolve_secret(var)
+                    .await?
+                    .ok_or_else(|| OxyError::SecretNotFound(Some(var.clone())))?;
+                Database::connect(url)
+                    .await
+                    .map_err(|e| OxyError::DBError(format!("Failed to connect to pgvector: {e}")))?
+            }
+            None => establish_connection().await?,
+        };
+        let store = Self {
+            db,
+            table: table_name(workspace_id, name),
+            n_dims,
+            dropped: AtomicBool::new(false),
+        };
+        store
+            .execute("CREATE EXTENSION IF NOT EXISTS vector".to_string(), vec![])
+            .await?;
+        store.ensure_table().await?;
+        Ok(store)
+    }
+
+    async fn execute(&self, sql: String, values: Vec<Value>) -> Result<(), OxyError> {
+        self.db
+            .execute(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                sql,
+                values,
+            ))
+            .await
+            .map_err(|e| OxyError::DBError(format!("pgvector table '{}': {e}", self.table)))?;
+        Ok(())
+    }
+
+    async fn query(&self, sql: String, values: Vec<Value>) -> Result<Vec<PgVectorRow>, OxyError> {
+        PgVectorRow::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            sql,
+            values,
+        ))
+        .all(&self.db)
+        .await
+        .map_err(|e| OxyError::DBError(format!("pgvector table '{}': {e}", self.table)))
+    }
+
+    /// Create the table and its indexes, recreating it if it was built with
+    /// a different embedding size.
+    async fn ensure_table(&self) -> Result<(), OxyError> {
+        let table = &self.table;
+
+        #[derive(FromQueryResult)]
+        struct Dims {
+            dims: i32,
+        }
+        let existing = Dims::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            "SELECT atttypmod AS dims FROM pg_attribute \
+             WHERE attrelid = to_regclass($1) AND attname = 'embedding'",
+            [table.clone().into()],
+        ))
+        .one(&self.db)
+        .await
+        .map_err(|e| OxyError::DBError(format!("pgvector table '{table}': {e}")))?;
+        if let Some(existing) = existing
+            && existing.dims != self.n_dims as i32
+        {
+            tracing::info!(
+                "Recreating pgvector table '{table}': embedding size changed from {} to {}",
+                existing.dims,
+                self.n_dims
+            );
+            self.execute(format!("DROP TABLE \"{table}\""), vec![])
+                .await?;
+        }
+
+        self.execute(
+            format!(
+                "CREATE TABLE IF NOT EXISTS \"{table}\" (\
+                     upsert_key TEXT PRIMARY KEY, \
+                     source_identifier TEXT NOT NULL, \
+                     source_type TEXT NOT NULL, \
+                     content TEXT NOT NULL, \
+                     embedding_content TEXT NOT NULL, \
+                     embedding vector({n_dims}) NOT NULL, \
+                     radius REAL NOT NULL, \
+                     search_text tsvector GENERATED ALWAYS AS \
+                         (to_tsvector('simple', embedding_content)) STORED\
+                 )",
+                n_dims = self.n_dims
+            ),
+            vec![],
+        )
+        .await?;
+        if self.n_dims <= MAX_INDEXED_DIMS {
+            self.execute(
+                format!(
+                    "CREATE INDEX IF NOT EXISTS \"{table}_embedding\" \
+                     ON \"{table}\" USING hnsw (embedding vector_cosine_ops)"
+                ),
+                vec![],
+            )
+            .await?;
+        }
+        self.execute(
+            format!(
+                "CREATE INDEX IF NOT EXISTS \"{table}_search_text\" \
+                 ON \"{table}\" USING gin (search_text)"
+            ),
+            vec![],
+        )
+        .await?;
+        self.execute(
+            format!(
+                "CREATE INDEX IF NOT EXISTS \"{table}_source\" \
+                 ON \"{table}\" (source_identifier)"
+            ),
+            vec![],
+        )
+        .await?;
+        self.dropped.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+impl VectorEngine for PgVector {
+    async fn upsert(&self, items: &[RetrievalItem]) -> Result<(), OxyError> {
+        if items.is_empty() {
+            return Ok(());
+        }
+        if self.dropped.load(Ordering::Relaxed) {
+            self.ensure_table().await?;
+        }
+        for chunk in items.chunks(UPSERT_CHUNK_SIZE) {
+            let mut rows = Vec::with_capacity(chunk.len());
+            let mut values: Vec<Value> = Vec::with_capacity(chunk.len() * 7);
+            for item in chunk {
+                let i = values.len();
+                rows.push(format!(
+                    "(${}, ${}, ${}, ${}, ${}, ${}::vector, ${})",
+                    i + 1,
+                    i + 2,
+                    i + 3,
+                    i + 4,
+                    i + 5,
+                    i + 6,
+                    i + 7
+                ));
+                values.extend([
+                    item.upsert_key().into(),
+                    item.source_identifier.clone().into(),
+                    item.source_type.clone().into(),
+                    item.content.clone().into(),
+                    item.embedding_content.clone().into(),
+                    format_vector(&item.embedding).into(),
+                    item.radius.into(),
+                ]);
+            }
+            self.execute(
+                format!(
+                    "INSERT INTO \"{}\" \
+                     (upsert_key, source_identifier, source_type, content, embedding_content, embedding, radius) \
+                     VALUES {} \
+                     ON CONFLICT (upsert_key) DO UPDATE SET \
+                     source_identifier = EXCLUDED.source_identifier, \
+                     source_type = EXCLUDED.source_type, \
+                     content = EXCLUDED.content, \
+                     embedding_content = EXCLUDED.embedding_content, \
+                     embedding = EXCLUDED.embedding, \
+                     radius = EXCLUDED.radius",
+                    self.table,
+                    rows.join(", ")
+                ),
+                values,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn optimize(&self) -> Result<(), OxyError> {
+        self.execute(format!("ANALYZE \"{}\"", self.table), vec![])
+            .await
+    }
+
+    async fn vector_search(
+        &self,
+        query_vector: &Embedding,
+        limit: usize,
+    ) -> Result<Vec<(RetrievalItem, f32)>, OxyError> {
+        let rows = self
+            .query(
+                format!(
+                    "SELECT source_identifier, source_type, content, embedding_content, \
+                         embedding::text AS embedding, radius, \
+                         (embedding <=> $1::vector)::real AS distance \
+                     FROM \"{}\" ORDER BY embedding <=> $1::vector LIMIT $2",
+                    self.table
+                ),
+                vec![format_vector(query_vector).into(), (limit as i64).into()],
+            )
+            .await?;
+        rows.into_iter()
+            .map(|row| {
+                let (item, distance) = row.into_item()?;
+                let distance = distance.ok_or_else(|| {
+                    OxyError::DBError(format!(
+                        "Null distance for inclusion '{}:{}'",
+                        item.source_identifier, item.embedding_content
+                    ))
+                })?;
+                Ok((item, distance))
+            })
+            .collect()
+    }
+
+    async fn keyword_search(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<RetrievalItem>, OxyError> {
+        // plainto_tsquery requires every word; match any of them instead
+        let rows = self
+            .query(
+                format!(
+                    "SELECT source_identifier, source_type, content, embedding_content, \
+                         embedding::text AS embedding, radius, NULL::real AS distance \
+                     FROM \"{}\", \
+                         to_tsquery('simple', replace(plainto_tsquery('simple', $1)::text, '&', '|')) AS q \
+                     WHERE search_text @@ q \
+                     ORDER BY ts_rank(search_text, q) DESC LIMIT $2",
+                    self.table
+                ),
+                vec![query.to_string().into(), (limit as i64).into()],
+            )
+            .await?;
+        rows.into_iter()
+            .map(|row| row.into_item().map(|(item, _)| item))
+            .collect()
+    }
+
+    async fn delete(&self, source_identifiers: &[String]) -> Result<(), OxyError> {
+        for chunk in source_identifiers.chunks(UPSERT_CHUNK_SIZE) {
+            let placeholders = (1..=chunk.len())
+                .map(|i| format!("${i}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            self.execute(
+                format!(
+                    "DELETE FROM \"{}\" WHERE source_identifier IN ({placeholders})",
+                    self.table
+                ),
+                chunk.iter().map(|source| source.clone().into()).collect(),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn cleanup(&self) -> Result<(), OxyError> {
+        self.execute(format!("DROP TABLE IF EXISTS \"{}\"", self.table), vec![])
+            .await?;
+        self.dropped.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+/// Postgres identifiers are limited to 63 bytes, so the table is named after
+/// the start of the workspace id and of the store name, leaving room for
+/// index name suffixes, and disambiguated with a hash of both in full.
+fn table_name(workspace_id: Uuid, name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .take(20)
+        .collect();
+    let workspace = workspace_id.simple().to_string();
+    let hash = build_index_key([workspace.as_str(), name]);
+    format!("oxy_vectors_{}_{sanitized}_{}", &workspace[..8], &hash[..8])
+}
+
+fn format_vector(embedding: &Embedding) -> String {
+    let values = embedding
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("[{values}]")
+}
+
+fn parse_vector(text: &str) -> Result<Embedding, OxyError> {
+    let inner = text.trim().trim_start_matches('[').trim_end_matches(']');
+    if inner.is_empty() {
+        return Ok(vec![]);
+    }
+    inner
+        .split(',')
+        .map(|v| {
+            v.trim()
+                .parse::<f32>()
+                .map_err(|e| OxyError::DBError(format!("Invalid vector value '{v}': {e}")))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_name_is_a_valid_identifier() {
+        let workspace_id = Uuid::parse_str("0b5e2f4c-8d1a-4e8b-9c3f-6a7d2e1f0a9b").unwrap();
+        let name = table_name(
+            workspace_id,
+            "Sales Agent-retrieval tool with a very long name indeed",
+        );
+        assert!(format!("{name}_search_text").len() <= 63);
+        assert!(name.starts_with("oxy_vectors_0b5e2f4c_sales_agent_"));
+        assert!(
+            name.chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        );
+        assert_ne!(
+            name,
+            table_name(
+                workspace_id,
+                "sales_agent_retrieval_tool_with_a_very_long_name_indeed"
+            )
+        );
+    }
+
+    #[test]
+    fn test_table_name_is_per_workspace() {
+        let name = "sales-agent-retrieval";
+        assert_ne!(
+            table_name(Uuid::new_v4(), name),
+            table_name(Uuid::new_v4(), name)
+        );
+    }
+
+    #[test]
+    fn test_vector_text_round_trip() {
+        let embedding = vec![0.5, -1.0, 0.25];
+        assert_eq!(parse_vector(&format_vector(&embedding)).unwrap(), embedding);
+        assert!(parse_vector("[]").unwrap().is_empty());
+    }
+}
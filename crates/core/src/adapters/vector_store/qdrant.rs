@@ -0,0 +1,391 @@
+//! Retrieval items in a Qdrant collection, through its REST API.
+//!
+//! Each store gets its own collection per workspace, created on first use
+//! with cosine distance and payload indexes for keyword search and deletion.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use reqwest::{Client, Method, RequestBuilder};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::json;
+use uuid::Uuid;
+
+use super::{
+    engine::VectorEngine,
+    types::{Embedding, RetrievalItem},
+};
+use crate::{adapters::secrets::SecretsManager, config::model::QdrantConfig};
+use oxy_shared::errors::OxyError;
+
+const UPSERT_CHUNK_SIZE: usize = 256;
+
+pub(super) struct Qdrant {
+    client: Client,
+    url: String,
+    api_key: Option<String>,
+    collection: String,
+    n_dims: usize,
+    /// Set by `cleanup`; the next upsert recreates the collection.
+    dropped: AtomicBool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Payload {
+    source_identifier: String,
+    embedding_content: String,
+    content: String,
+    source_type: String,
+    radius: f32,
+}
+
+#[derive(Deserialize)]
+struct Point {
+    #[serde(default)]
+    score: Option<f32>,
+    payload: Payload,
+    vector: Embedding,
+}
+
+impl Point {
+    fn into_item(self) -> RetrievalItem {
+        RetrievalItem {
+            source_identifier: self.payload.source_identifier,
+            embedding_content: self.payload.embedding_content,
+            embedding: self.vector,
+            content: self.payload.content,
+            source_type: self.payload.source_type,
+            radius: self.payload.radius,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ScrollResult {
+    points: Vec<Point>,
+}
+
+#[derive(Deserialize)]
+struct CollectionInfo {
+    config: CollectionConfig,
+}
+
+#[derive(Deserialize)]
+struct CollectionConfig {
+    params: CollectionParams,
+}
+
+#[derive(Deserialize)]
+struct CollectionParams {
+    vectors: VectorParams,
+}
+
+#[derive(Deserialize)]
+struct VectorParams {
+    size: usize,
+}
+
+#[derive(Deserialize)]
+struct QdrantResponse<T> {
+    result: T,
+}
+
+impl Qdrant {
+    pub(super) async fn connect(
+        config: &QdrantConfig,
+        secrets_manager: &SecretsManager,
+        workspace_id: Uuid,
+        name: &str,
+        n_dims: usize,
+    ) -> Result<Self, OxyError> {
+        let api_key = match &config.api_key_var {
+            Some(var) => Some(
+                secrets_manager
+                    .resolve_secret(var)
+                    .await?
+                    .ok_or_else(|| OxyError::SecretNotFound(Some(var.clone())))?,
+            ),
+            None => None,
+        };
+        let store = Self {
+            client: Client::new(),
+            url: config.url.trim_end_matches('/').to_string(),
+            api_key,
+            collection: collection_name(workspace_id, name),
+            n_dims,
+            dropped: AtomicBool::new(false),
+        };
+        store.ensure_collection().await?;
+        Ok(store)
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = self.client.request(
+            method,
+            format!("{}/collections/{}{path}", self.url, self.collection),
+        );
+        match &self.api_key {
+            Some(api_key) => request.header("api-key", api_key),
+            None => request,
+        }
+    }
+
+    async fn send(&self, request: RequestBuilder) -> Result<reqwest::Response, OxyError> {
+        let response = request.send().await.map_err(|e| {
+            OxyError::RuntimeError(format!("Qdrant collection '{}': {e}", self.collection))
+        })?;
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(OxyError::RuntimeError(format!(
+                "Qdrant collection '{}': {status} - {error_text}",
+                self.collection
+            )));
+        }
+        Ok(response)
+    }
+
+    async fn send_json<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T, OxyError> {
+        let response = self.send(request).await?;
+        response
+            .json::<QdrantResponse<T>>()
+            .await
+            .map(|response| response.result)
+            .map_err(|e| {
+                OxyError::RuntimeError(format!(
+                    "Failed to parse Qdrant response for '{}': {e}",
+                    self.collection
+                ))
+            })
+    }
+
+    /// Create the collection and its payload indexes, recreating it if it
+    /// was built with a different embedding size.
+    async fn ensure_collection(&self) -> Result<(), OxyError> {
+        let response = self.request(Method::GET, "").send().await.map_err(|e| {
+            OxyError::RuntimeError(format!("Qdrant collection '{}': {e}", self.collection))
+        })?;
+        if response.status().is_success() {
+            let info = response
+                .json::<QdrantResponse<CollectionInfo>>()
+                .await
+                .map_err(|e| {
+                    OxyError::RuntimeError(format!(
+                        "Failed to parse Qdrant collection '{}': {e}",
+                        self.collection
+                    ))
+                })?;
+            let size = info.result.config.params.vectors.size;
+            if size == self.n_dims {
+                return Ok(());
+            }
+            tracing::info!(
+                "Recreating Qdrant collection '{}': embedding size changed from {size} to {}",
+                self.collection,
+                self.n_dims
+            );
+            self.cleanup().await?;
+        }
+
+        self.send(self.request(Method::PUT, "").json(&json!({
+            "vectors": { "size": self.n_dims, "distance": "Cosine" }
+        })))
+        .await?;
+        self.send(self.request(Method::PUT, "/index?wait=true").json(&json!({
+            "field_name": "embedding_content",
+            "field_schema": { "type": "text", "tokenizer": "word", "lowercase": true }
+        })))
+        .await?;
+        self.send(self.request(Method::PUT, "/index?wait=true").json(&json!({
+            "field_name": "source_identifier",
+            "field_schema": "keyword"
+        })))
+        .await?;
+        self.dropped.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+impl VectorEngine for Qdrant {
+    async fn upsert(&self, items: &[RetrievalItem]) -> Result<(), OxyError> {
+        if items.is_empty() {
+            return Ok(());
+        }
+        if self.dropped.load(Ordering::Relaxed) {
+            self.ensure_collection().await?;
+        }
+        for chunk in items.chunks(UPSERT_CHUNK_SIZE) {
+            let points = chunk
+                .iter()
+                .map(|item| {
+                    Ok(json!({
+                        "id": point_id(&item.upsert_key())?,
+                        "vector": item.embedding,
+                        "payload": Payload {
+                            source_identifier: item.source_identifier.clone(),
+                            embedding_content: item.embedding_content.clone(),
+                            content: item.content.clone(),
+                            source_type: item.source_type.clone(),
+                            radius: item.radius,
+                        },
+                    }))
+                })
+                .collect::<Result<Vec<_>, OxyError>>()?;
+            self.send(
+                self.request(Method::PUT, "/points?wait=true")
+                    .json(&json!({ "points": points })),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn optimize(&self) -> Result<(), OxyError> {
+        // Qdrant builds its indexes in the background
+        Ok(())
+    }
+
+    async fn vector_search(
+        &self,
+        query_vector: &Embedding,
+        limit: usize,
+    ) -> Result<Vec<(RetrievalItem, f32)>, OxyError> {
+        let points: Vec<Point> = self
+            .send_json(self.request(Method::POST, "/points/search").json(&json!({
+                "vector": query_vector,
+                "limit": limit,
+                "with_payload": true,
+                "with_vector": true,
+            })))
+            .await?;
+        Ok(points
+            .into_iter()
+            .map(|point| {
+                // Qdrant scores cosine similarity
+                let distance = 1.0 - point.score.unwrap_or_default();
+                (point.into_item(), distance)
+            })
+            .collect())
+    }
+
+    async fn keyword_search(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<RetrievalItem>, OxyError> {
+        let mut words: Vec<String> = query
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+        words.sort();
+        words.dedup();
+        if words.is_empty() {
+            return Ok(vec![]);
+        }
+        // Scrolled points are not ranked; VectorStore ranks them by distance
+        let should = words
+            .iter()
+            .map(|word| json!({ "key": "embedding_content", "match": { "text": word } }))
+            .collect::<Vec<_>>();
+        let result: ScrollResult = self
+            .send_json(self.request(Method::POST, "/points/scroll").json(&json!({
+                "filter": { "should": should },
+                "limit": limit,
+                "with_payload": true,
+                "with_vector": true,
+            })))
+            .await?;
+        Ok(result.points.into_iter().map(Point::into_item).collect())
+    }
+
+    async fn delete(&self, source_identifiers: &[String]) -> Result<(), OxyError> {
+        if source_identifiers.is_empty() {
+            return Ok(());
+        }
+        self.send(
+            self.request(Method::POST, "/points/delete?wait=true")
+                .json(&json!({
+                    "filter": {
+                        "must": [{
+                            "key": "source_identifier",
+                            "match": { "any": source_identifiers }
+                        }]
+                    }
+                })),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn cleanup(&self) -> Result<(), OxyError> {
+        let response = self.request(Method::DELETE, "").send().await.map_err(|e| {
+            OxyError::RuntimeError(format!("Qdrant collection '{}': {e}", self.collection))
+        })?;
+        if !response.status().is_success() && response.status() != reqwest::StatusCode::NOT_FOUND {
+            return Err(OxyError::RuntimeError(format!(
+                "Failed to delete Qdrant collection '{}': {}",
+                self.collection,
+                response.status()
+            )));
+        }
+        self.dropped.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+fn collection_name(workspace_id: Uuid, name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{workspace_id}-{sanitized}")
+}
+
+/// Qdrant point ids must be integers or UUIDs, so the upsert key (a SHA-256
+/// hex digest) is truncated to 128 bits.
+fn point_id(upsert_key: &str) -> Result<Uuid, OxyError> {
+    upsert_key
+        .get(..32)
+        .and_then(|prefix| u128::from_str_radix(prefix, 16).ok())
+        .map(Uuid::from_u128)
+        .ok_or_else(|| OxyError::RuntimeError(format!("Invalid upsert key: {upsert_key}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_id_is_stable_per_upsert_key() {
+        let key = super::super::build_index_key(["orders.sql", "monthly revenue"]);
+        assert_eq!(point_id(&key).unwrap(), point_id(&key).unwrap());
+        assert_ne!(
+            point_id(&key).unwrap(),
+            point_id(&super::super::build_index_key([
+                "orders.sql",
+                "weekly revenue"
+            ]))
+            .unwrap()
+        );
+        assert!(point_id("not hex").is_err());
+    }
+
+    #[test]
+    fn test_collection_name_is_per_workspace() {
+        let workspace_id = Uuid::parse_str("0b5e2f4c-8d1a-4e8b-9c3f-6a7d2e1f0a9b").unwrap();
+        assert_eq!(
+            collection_name(workspace_id, "sales agent-retrieval"),
+            "0b5e2f4c-8d1a-4e8b-9c3f-6a7d2e1f0a9b-sales_agent-retrieval"
+        );
+        assert_ne!(
+            collection_name(Uuid::new_v4(), "sales"),
+            collection_name(Uuid::new_v4(), "sales")
+        );
+    }
+}
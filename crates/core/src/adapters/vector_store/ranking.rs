@@ -0,0 +1,193 @@
+//! Turns search candidates from a backend into ranked [`SearchRecord`]s.
+//!
+//! Candidates come from a vector search and a keyword search. Keyword
+//! matches are given their cosine distance from the query so that both go
+//! through the same epsilon ball filtering: a keyword match outside an
+//! inclusion's radius is dropped just like a distant vector match, which
+//! keeps exclusions effective.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::adapters::vector_store::types::{Embedding, RetrievalItem, SearchRecord};
+
+use super::math::MathUtils;
+
+/// Add keyword matches missing from the vector candidates, with their
+/// distance from `query_vector`.
+pub(super) fn merge_keyword_matches(
+    mut candidates: Vec<(RetrievalItem, f32)>,
+    keyword_matches: Vec<RetrievalItem>,
+    query_vector: &Embedding,
+) -> Vec<(RetrievalItem, f32)> {
+    let mut seen: HashSet<String> = candidates
+        .iter()
+        .map(|(item, _)| item.upsert_key())
+        .collect();
+    for item in keyword_matches {
+        if !seen.insert(item.upsert_key()) {
+            continue;
+        }
+        match MathUtils::find_min_distance(query_vector, std::slice::from_ref(&item.embedding)) {
+            Ok(Some(distance)) => candidates.push((item, distance)),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Skipping keyword match '{}': {e}", item.source_identifier),
+        }
+    }
+    candidates
+}
+
+/// Filter candidates by inclusion radius (epsilon ball filtering)
+pub(super) fn filter_by_inclusion_radius(
+    retrieval_items: Vec<(RetrievalItem, f32)>,
+) -> Vec<(RetrievalItem, f32)> {
+    tracing::info!(
+        "Found {} candidates, applying epsilon ball filtering",
+        retrieval_items.len()
+    );
+
+    let filtered: Vec<_> = retrieval_items
+        .into_iter()
+        .filter(|(item, distance)| {
+            let radius = item.radius;
+            let within_radius = *distance <= radius;
+            tracing::debug!(
+                "Inclusion '{}:{}' is {} epsilon ball (distance: {:.3}, radius: {:.3})",
+                item.source_identifier,
+                item.embedding_content,
+                if within_radius { "within" } else { "outside" },
+                distance,
+                radius
+            );
+            within_radius
+        })
+        .collect();
+
+    tracing::info!(
+        "Epsilon ball filtering completed: {} candidates passed",
+        filtered.len()
+    );
+    filtered
+}
+
+/// Deduplicate by source identifier, keeping the best match per source
+pub(super) fn deduplicate_by_source_identifier(
+    retrieval_items: Vec<(RetrievalItem, f32)>,
+) -> Vec<(RetrievalItem, f32)> {
+    tracing::info!(
+        "Deduplicating {} inclusions by source_identifier",
+        retrieval_items.len()
+    );
+
+    let deduplicated: Vec<_> = retrieval_items
+        .into_iter()
+        .fold(
+            HashMap::new(),
+            |mut best_by_source: HashMap<String, (RetrievalItem, f32)>, (item, distance)| {
+                let source_id = item.source_identifier.clone();
+
+                match best_by_source.get(&source_id) {
+                    Some((_, existing_distance)) if distance < *existing_distance => {
+                        tracing::debug!(
+                            "Replacing candidate for '{}' (new distance: {:.3} < existing: {:.3})",
+                            source_id,
+                            distance,
+                            existing_distance
+                        );
+                        best_by_source.insert(source_id, (item, distance));
+                    }
+                    None => {
+                        best_by_source.insert(source_id, (item, distance));
+                    }
+                    _ => {} // Keep existing better match
+                }
+                best_by_source
+            },
+        )
+        .into_values()
+        .collect();
+
+    tracing::info!(
+        "Deduplication completed: {} unique sources",
+        deduplicated.len()
+    );
+    deduplicated
+}
+
+/// Convert filtered retrieval items to SearchRecord objects, sort by relevance, and truncate to top_k
+pub(super) fn finalize_search_results(
+    retrieval_items: Vec<(RetrievalItem, f32)>,
+    top_k: usize,
+) -> Vec<SearchRecord> {
+    let mut search_records: Vec<SearchRecord> = retrieval_items
+        .into_iter()
+        .map(|(item, distance)| {
+            let radius = item.radius;
+            let relevance_score = if radius > 0.0 {
+                1.0 - (distance / radius)
+            } else {
+                1.0
+            };
+
+            SearchRecord {
+                retrieval_item: item,
+                distance,
+                score: None,
+                relevance_score: Some(relevance_score),
+            }
+        })
+        .collect();
+
+    search_records.sort_by(|a, b| {
+        b.relevance_score
+            .partial_cmp(&a.relevance_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    search_records.truncate(top_k);
+
+    tracing::info!(
+        "Processing pipeline completed: {} final results",
+        search_records.len()
+    );
+
+    search_records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(source_identifier: &str, embedding: Embedding, radius: f32) -> RetrievalItem {
+        RetrievalItem {
+            source_identifier: source_identifier.to_string(),
+            embedding_content: source_identifier.to_string(),
+            embedding,
+            content: String::new(),
+            source_type: "file".to_string(),
+            radius,
+        }
+    }
+
+    #[test]
+    fn test_keyword_matches_go_through_the_radius_filter() {
+        let query = vec![1.0, 0.0];
+        let vector_match = item("vector", vec![1.0, 0.1], 0.3);
+        let candidates = vec![(vector_match.clone(), 0.005)];
+        let keyword_matches = vec![
+            // Already a vector candidate
+            vector_match,
+            item("near", vec![0.9, 0.1], 0.3),
+            item("far", vec![0.0, 1.0], 0.3),
+        ];
+
+        let merged = merge_keyword_matches(candidates, keyword_matches, &query);
+        assert_eq!(merged.len(), 3);
+
+        let mut sources: Vec<_> = filter_by_inclusion_radius(merged)
+            .into_iter()
+            .map(|(item, _)| item.source_identifier)
+            .collect();
+        sources.sort();
+        assert_eq!(sources, vec!["near", "vector"]);
+    }
+}
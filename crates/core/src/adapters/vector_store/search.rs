@@ -6,12 +6,14 @@ use crate::{
     },
 };
 use oxy_shared::errors::OxyError;
+use uuid::Uuid;
 
 use super::{VectorStore, types::SearchRecord};
 
 pub async fn search_agent(
     config: &ConfigManager,
     secrets_manager: &SecretsManager,
+    workspace_id: Uuid,
     agent_ref: &str,
     query: &str,
 ) -> Result<Vec<SearchRecord>, OxyError> {
@@ -25,6 +27,7 @@ pub async fn search_agent(
                     let vector_store = VectorStore::from_retrieval(
                         config,
                         secrets_manager,
+                        workspace_id,
                         &agent.name,
                         retrieval,
                     )
@@ -43,6 +46,7 @@ pub async fn search_agent(
             let vector_store = VectorStore::from_routing_agent(
                 config,
                 secrets_manager,
+                workspace_id,
                 &agent.name,
                 &agent.model,
                 routing_agent,
@@ -1,8 +1,17 @@
 use std::{path::PathBuf, sync::Arc};
 
 use super::{
+    builders::parameterized::build_parameterized_retrieval_objects,
+    embedding::create_embedding,
     engine::VectorEngine,
+    ingestion::build_retrieval_items,
     lance_db::LanceDB,
+    pgvector::PgVector,
+    qdrant::Qdrant,
+    ranking::{
+        deduplicate_by_source_identifier, filter_by_inclusion_radius, finalize_search_results,
+        merge_keyword_matches,
+    },
     types::{RetrievalObject, SearchRecord},
 };
 use crate::{
@@ -17,38 +26,29 @@ use crate::{
     service::retrieval::EnumIndexManager,
 };
 use enum_dispatch::enum_dispatch;
-use lancedb::{Connection, connect};
+use lancedb::connect;
 use oxy_shared::errors::OxyError;
+use uuid::Uuid;
 
 #[enum_dispatch(VectorEngine)]
 enum VectorStoreImpl {
     LanceDB,
-}
-
-impl VectorStoreImpl {
-    fn lance_db(
-        client: OpenAIClient,
-        connection: Connection,
-        embedding_config: EmbeddingConfig,
-        enum_index_manager: Arc<EnumIndexManager>,
-    ) -> Self {
-        VectorStoreImpl::LanceDB(LanceDB::new(
-            client,
-            connection,
-            embedding_config,
-            enum_index_manager,
-        ))
-    }
+    PgVector,
+    Qdrant,
 }
 
 pub struct VectorStore {
     inner: VectorStoreImpl,
+    client: OpenAIClient,
+    embedding_config: EmbeddingConfig,
+    enum_index_manager: Arc<EnumIndexManager>,
 }
 
 impl VectorStore {
     pub async fn new(
         config_manager: &ConfigManager,
         secrets_manager: &SecretsManager,
+        workspace_id: Uuid,
         db_config: &VectorDBConfig,
         name: &str,
         openai_config: impl IntoOpenAIConfig,
@@ -58,7 +58,7 @@ impl VectorStore {
             OpenAIClient::with_config(openai_config.into_openai_config(secrets_manager).await?);
         // Create minimal enum index config for VectorStore (main enum index is managed at higher level)
         let enum_index_manager = Arc::new(EnumIndexManager::from_config(config_manager).await?);
-        let connection = match &db_config {
+        let inner = match &db_config {
             VectorDBConfig::LanceDB { db_path } => {
                 let resolved_root = config_manager.resolve_file(db_path).await?;
                 let db_path = PathBuf::from(&resolved_root)
@@ -66,30 +66,51 @@ impl VectorStore {
                     .to_string_lossy()
                     .to_string();
 
-                connect(&db_path)
+                let connection = connect(&db_path)
                     .execute()
                     .await
-                    .map_err(OxyError::LanceDBError)?
+                    .map_err(OxyError::LanceDBError)?;
+                VectorStoreImpl::LanceDB(LanceDB::new(connection, embedding_config.n_dims))
             }
+            VectorDBConfig::PgVector { pgvector } => VectorStoreImpl::PgVector(
+                PgVector::connect(
+                    pgvector,
+                    secrets_manager,
+                    workspace_id,
+                    name,
+                    embedding_config.n_dims,
+                )
+                .await?,
+            ),
+            VectorDBConfig::Qdrant { qdrant } => VectorStoreImpl::Qdrant(
+                Qdrant::connect(
+                    qdrant,
+                    secrets_manager,
+                    workspace_id,
+                    name,
+                    embedding_config.n_dims,
+                )
+                .await?,
+            ),
         };
         Ok(Self {
-            inner: VectorStoreImpl::lance_db(
-                client,
-                connection,
-                embedding_config,
-                enum_index_manager,
-            ),
+            inner,
+            client,
+            embedding_config,
+            enum_index_manager,
         })
     }
     pub async fn from_retrieval(
         config_manager: &ConfigManager,
         secrets_manager: &SecretsManager,
+        workspace_id: Uuid,
         agent_name: &str,
         retrieval: &RetrievalConfig,
     ) -> Result<Self, OxyError> {
         VectorStore::new(
             config_manager,
             secrets_manager,
+            workspace_id,
             &retrieval.db_config,
             &format!("{}-{}", agent_name, retrieval.name),
             retrieval.clone(),
@@ -100,6 +121,7 @@ impl VectorStore {
     pub async fn from_routing_agent(
         config_manager: &ConfigManager,
         secrets_manager: &SecretsManager,
+        workspace_id: Uuid,
         agent_name: &str,
         model: &str,
         routing_agent: &RoutingAgent,
@@ -108,6 +130,7 @@ impl VectorStore {
         VectorStore::new(
             config_manager,
             secrets_manager,
+            workspace_id,
             &routing_agent.db_config,
             &format!("{agent_name}-routing"),
             model.clone(),
@@ -115,13 +138,64 @@ impl VectorStore {
         )
         .await
     }
+    /// Build-time ingestion: embed and store the inclusions, then reindex.
     pub async fn ingest(&self, retrieval_objects: &Vec<RetrievalObject>) -> Result<(), OxyError> {
-        self.inner.ingest(retrieval_objects).await
+        let items =
+            build_retrieval_items(&self.client, &self.embedding_config, retrieval_objects).await?;
+        self.inner.upsert(&items).await?;
+        self.inner.optimize().await
     }
+    /// Hybrid search: vector and keyword candidates are merged, filtered by
+    /// inclusion radius and deduplicated by source.
     pub async fn search(&self, query: &str) -> Result<Vec<SearchRecord>, OxyError> {
-        self.inner.search(query).await
+        self.ingest_parameterized_retrieval_objects(query).await?;
+
+        tracing::info!("Embedding search query: {}", query);
+        let query_vector = create_embedding(&self.client, &self.embedding_config, query).await?;
+        let limit = self.embedding_config.top_k * self.embedding_config.factor;
+        let candidates = self.inner.vector_search(&query_vector, limit).await?;
+        let keyword_matches = self.inner.keyword_search(query, limit).await?;
+
+        for (item, distance) in candidates.iter() {
+            tracing::info!(
+                "Candidate: {} text: {} radius: {} distance: {}",
+                item.source_identifier,
+                item.embedding_content,
+                item.radius,
+                distance
+            );
+        }
+        tracing::info!("Keyword search found {} candidates", keyword_matches.len());
+
+        let merged = merge_keyword_matches(candidates, keyword_matches, &query_vector);
+        let filtered = filter_by_inclusion_radius(merged);
+        let deduplicated = deduplicate_by_source_identifier(filtered);
+        let final_results = finalize_search_results(deduplicated, self.embedding_config.top_k);
+
+        tracing::info!(
+            "Search completed with {} unique results",
+            final_results.len()
+        );
+        Ok(final_results)
+    }
+    /// Remove every stored inclusion of the given sources.
+    pub async fn delete(&self, source_identifiers: &[String]) -> Result<(), OxyError> {
+        self.inner.delete(source_identifiers).await
     }
     pub async fn cleanup(&self) -> Result<(), OxyError> {
         self.inner.cleanup().await
     }
+
+    // Since this only runs at query time, we don't reindex to avoid latency -
+    // may want to schedule reindexing thru background job eventually
+    async fn ingest_parameterized_retrieval_objects(&self, query: &str) -> Result<(), OxyError> {
+        let param_objects =
+            build_parameterized_retrieval_objects(&self.enum_index_manager, query).await?;
+        if param_objects.is_empty() {
+            return Ok(());
+        }
+        let items =
+            build_retrieval_items(&self.client, &self.embedding_config, &param_objects).await?;
+        self.inner.upsert(&items).await
+    }
 }
@@ -60,11 +60,21 @@ pub struct RetrievalItem {
     pub radius: f32,
 }
 
+impl RetrievalItem {
+    /// Key an item is stored under: one row per inclusion of each source.
+    pub fn upsert_key(&self) -> String {
+        super::build_index_key([
+            self.source_identifier.as_str(),
+            self.embedding_content.as_str(),
+        ])
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct SearchRecord {
     #[serde(flatten)]
     pub retrieval_item: RetrievalItem,
-    /// Distance from LanceDB vector search (cosine distance)
+    /// Cosine distance from the query
     pub distance: f32,
     /// Optional score (may not be meaningful for all search types)
     #[serde(alias = "_score")]
@@ -8,6 +8,7 @@ use crate::{
     config::ConfigManager,
     intent::IntentClassifier,
 };
+use oxy_semantic::AccessLevel;
 use oxy_shared::errors::OxyError;
 
 #[derive(Debug, Clone)]
@@ -36,6 +37,12 @@ impl WorkspaceManager {
         }
     }
 
+    /// Apply column-level access rules for a caller with `access_level`.
+    pub fn with_access_level(mut self, access_level: AccessLevel) -> Self {
+        self.config_manager = self.config_manager.with_access_level(access_level);
+        self
+    }
+
     pub async fn get_required_secrets(&self) -> Result<Option<Vec<String>>, OxyError> {
         let mut secrets_to_check: HashSet<String> = HashSet::new();
 
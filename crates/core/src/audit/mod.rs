@@ -0,0 +1,221 @@
+//! Append-only audit log of security-relevant actions.
+//!
+//! Call sites build an [`AuditEvent`] and either [`record`] it after the
+//! change has been committed, or [`record_in`] the transaction that makes the
+//! change so the entry commits or rolls back with it. Entries are never
+//! updated; the `audit_logs` table rejects UPDATE and DELETE, except for the
+//! retention cleanup in [`spawn_retention_cleanup`]. Queries are recorded by
+//! the connectors that run them, see [`crate::connector::Connector::audited`].
+
+use sea_orm::{ActiveModelTrait, ConnectionTrait, EntityTrait, Set};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::database::client::establish_connection;
+use entity::audit_logs;
+use entity::prelude::{Users, Workspaces};
+use oxy_shared::errors::OxyError;
+
+mod retention;
+
+pub use retention::{purge_expired, retention_days, spawn_retention_cleanup};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Login,
+    Logout,
+    SecretCreated,
+    SecretUpdated,
+    SecretDeleted,
+    SecretRevealed,
+    MemberAdded,
+    MemberRoleChanged,
+    MemberRemoved,
+    InvitationCreated,
+    InvitationRevoked,
+    WorkspaceRoleOverridden,
+    WorkspaceRoleOverrideRemoved,
+    CustomRoleCreated,
+    CustomRoleUpdated,
+    CustomRoleDeleted,
+    CustomRoleAssigned,
+    CustomRoleUnassigned,
+    ApiKeyCreated,
+    ApiKeyRevoked,
+    WorkspaceCreated,
+    WorkspaceDeleted,
+    QueryExecuted,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Login => "auth.login",
+            AuditAction::Logout => "auth.logout",
+            AuditAction::SecretCreated => "secret.created",
+            AuditAction::SecretUpdated => "secret.updated",
+            AuditAction::SecretDeleted => "secret.deleted",
+            AuditAction::SecretRevealed => "secret.revealed",
+            AuditAction::MemberAdded => "member.added",
+            AuditAction::MemberRoleChanged => "member.role_changed",
+            AuditAction::MemberRemoved => "member.removed",
+            AuditAction::InvitationCreated => "invitation.created",
+            AuditAction::InvitationRevoked => "invitation.revoked",
+            AuditAction::WorkspaceRoleOverridden => "workspace_member.role_overridden",
+            AuditAction::WorkspaceRoleOverrideRemoved => "workspace_member.override_removed",
+            AuditAction::CustomRoleCreated => "role.created",
+            AuditAction::CustomRoleUpdated => "role.updated",
+            AuditAction::CustomRoleDeleted => "role.deleted",
+            AuditAction::CustomRoleAssigned => "workspace_member.custom_role_assigned",
+            AuditAction::CustomRoleUnassigned => "workspace_member.custom_role_removed",
+            AuditAction::ApiKeyCreated => "api_key.created",
+            AuditAction::ApiKeyRevoked => "api_key.revoked",
+            AuditAction::WorkspaceCreated => "workspace.created",
+            AuditAction::WorkspaceDeleted => "workspace.deleted",
+            AuditAction::QueryExecuted => "query.executed",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AuditEvent {
+    action: AuditAction,
+    org_id: Option<Uuid>,
+    workspace_id: Option<Uuid>,
+    actor_id: Option<Uuid>,
+    target_type: Option<&'static str>,
+    target_id: Option<String>,
+    metadata: Map<String, Value>,
+}
+
+impl AuditEvent {
+    pub fn new(action: AuditAction) -> Self {
+        Self {
+            action,
+            org_id: None,
+            workspace_id: None,
+            actor_id: None,
+            target_type: None,
+            target_id: None,
+            metadata: Map::new(),
+        }
+    }
+
+    pub fn org(mut self, org_id: impl Into<Option<Uuid>>) -> Self {
+        self.org_id = org_id.into();
+        self
+    }
+
+    /// The workspace the action happened in. The org is filled in from the
+    /// workspace when not set explicitly.
+    pub fn workspace(mut self, workspace_id: impl Into<Option<Uuid>>) -> Self {
+        self.workspace_id = workspace_id.into();
+        self
+    }
+
+    /// The user who performed the action; `None` for system actions.
+    pub fn actor(mut self, user_id: impl Into<Option<Uuid>>) -> Self {
+        self.actor_id = user_id.into();
+        self
+    }
+
+    pub fn target(mut self, target_type: &'static str, target_id: impl ToString) -> Self {
+        self.target_type = Some(target_type);
+        self.target_id = Some(target_id.to_string());
+        self
+    }
+
+    pub fn meta(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.metadata.insert(key.to_string(), value.into());
+        self
+    }
+}
+
+/// Write `event` on `db`, failing if the entry cannot be written.
+pub async fn record_in<C: ConnectionTrait>(db: &C, event: AuditEvent) -> Result<(), OxyError> {
+    let org_id = match (event.org_id, event.workspace_id) {
+        (Some(org_id), _) => Some(org_id),
+        (None, Some(workspace_id)) => Workspaces::find_by_id(workspace_id)
+            .one(db)
+            .await
+            .map_err(|e| OxyError::DBError(format!("Failed to load workspace: {e}")))?
+            .and_then(|workspace| workspace.org_id),
+        (None, None) => None,
+    };
+    let actor_email = match event.actor_id {
+        Some(actor_id) => Users::find_by_id(actor_id)
+            .one(db)
+            .await
+            .map_err(|e| OxyError::DBError(format!("Failed to load user: {e}")))?
+            .map(|user| user.email),
+        None => None,
+    };
+
+    audit_logs::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        created_at: Set(chrono::Utc::now().into()),
+        org_id: Set(org_id),
+        workspace_id: Set(event.workspace_id),
+        actor_id: Set(event.actor_id),
+        actor_email: Set(actor_email),
+        action: Set(event.action.as_str().to_string()),
+        target_type: Set(event.target_type.map(str::to_string)),
+        target_id: Set(event.target_id),
+        metadata: Set(Value::Object(event.metadata)),
+    }
+    .insert(db)
+    .await
+    .map_err(|e| {
+        OxyError::DBError(format!(
+            "Failed to write audit entry '{}': {e}",
+            event.action.as_str()
+        ))
+    })?;
+    Ok(())
+}
+
+/// Write `event` on its own connection. For actions that have already been
+/// committed, so a failure is logged rather than returned.
+pub async fn record(event: AuditEvent) {
+    let action = event.action.as_str();
+    let result = match establish_connection().await {
+        Ok(db) => record_in(&db, event).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        tracing::error!("Failed to record audit entry '{action}': {e}");
+    }
+}
+
+/// SHA-256 hex of a SQL statement, so executed queries can be correlated
+/// without storing their text.
+pub fn sql_hash(sql: &str) -> String {
+    hex::encode(Sha256::digest(sql.trim().as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sql_hash_ignores_surrounding_whitespace() {
+        assert_eq!(sql_hash("SELECT 1"), sql_hash("  SELECT 1\n"));
+        assert_ne!(sql_hash("SELECT 1"), sql_hash("SELECT 2"));
+        assert_eq!(sql_hash("").len(), 64);
+    }
+
+    #[test]
+    fn test_event_builder() {
+        let workspace_id = Uuid::new_v4();
+        let event = AuditEvent::new(AuditAction::QueryExecuted)
+            .workspace(workspace_id)
+            .target("database", "warehouse")
+            .meta("success", true);
+        assert_eq!(event.action.as_str(), "query.executed");
+        assert_eq!(event.workspace_id, Some(workspace_id));
+        assert_eq!(event.actor_id, None);
+        assert_eq!(event.target_id.as_deref(), Some("warehouse"));
+        assert_eq!(event.metadata["success"], Value::Bool(true));
+    }
+}
@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, Statement};
+use tokio_util::sync::CancellationToken;
+
+use crate::database::client::establish_connection;
+use oxy_shared::errors::OxyError;
+
+const RETENTION_ENV: &str = "OXY_AUDIT_LOG_RETENTION_DAYS";
+const DEFAULT_RETENTION_DAYS: u32 = 365;
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(6 * 3600);
+
+/// Days audit entries are kept, from `OXY_AUDIT_LOG_RETENTION_DAYS`
+/// (default 365). `0` keeps entries forever.
+pub fn retention_days() -> u32 {
+    parse_retention_days(std::env::var(RETENTION_ENV).ok().as_deref())
+}
+
+fn parse_retention_days(value: Option<&str>) -> u32 {
+    match value.map(str::trim) {
+        None | Some("") => DEFAULT_RETENTION_DAYS,
+        Some(value) => value.parse().unwrap_or_else(|_| {
+            tracing::warn!(
+                "Invalid {RETENTION_ENV}='{value}', using {DEFAULT_RETENTION_DAYS} days"
+            );
+            DEFAULT_RETENTION_DAYS
+        }),
+    }
+}
+
+/// Delete entries older than `days`. The `audit_logs` triggers only allow
+/// deletes made by the `purge_audit_logs` database function.
+pub async fn purge_expired(db: &DatabaseConnection, days: u32) -> Result<u64, OxyError> {
+    let purged = db
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            "SELECT purge_audit_logs($1) AS purged",
+            [(days.min(i32::MAX as u32) as i32).into()],
+        ))
+        .await
+        .and_then(|row| match row {
+            Some(row) => row.try_get::<i64>("", "purged"),
+            None => Ok(0),
+        })
+        .map_err(|e| OxyError::DBError(format!("Failed to purge audit log: {e}")))?;
+    Ok(purged as u64)
+}
+
+/// Purge expired audit entries every 6 hours until `shutdown_token` fires.
+/// Does nothing when retention is disabled.
+pub fn spawn_retention_cleanup(shutdown_token: CancellationToken) {
+    let days = retention_days();
+    if days == 0 {
+        tracing::info!("Audit log retention disabled; entries are kept forever");
+        return;
+    }
+    tracing::info!("Audit log retention: {days} days (cleanup every 6h)");
+
+    tokio::spawn(async move {
+        // First run a minute after startup, out of the way of migrations.
+        let start = tokio::time::Instant::now() + Duration::from_secs(60);
+        let mut interval = tokio::time::interval_at(start, CLEANUP_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = shutdown_token.cancelled() => break,
+                _ = interval.tick() => {
+                    let result = match establish_connection().await {
+                        Ok(db) => purge_expired(&db, days).await,
+                        Err(e) => Err(e),
+                    };
+                    match result {
+                        Ok(0) => tracing::debug!("Audit log cleanup: no entries purged"),
+                        Ok(n) => tracing::info!(
+                            "Audit log cleanup: purged {n} entries older than {days}d"
+                        ),
+                        Err(e) => tracing::warn!("Audit log cleanup failed: {e}"),
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_retention_days() {
+        assert_eq!(parse_retention_days(None), 365);
+        assert_eq!(parse_retention_days(Some("")), 365);
+        assert_eq!(parse_retention_days(Some(" 30 ")), 30);
+        assert_eq!(parse_retention_days(Some("0")), 0);
+        assert_eq!(parse_retention_days(Some("forever")), 365);
+    }
+}
@@ -0,0 +1,296 @@
+use std::sync::Mutex;
+
+use chrono::{Datelike, NaiveDate, Utc};
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, FromQueryResult, Statement};
+use uuid::Uuid;
+
+use super::Spend;
+use crate::{
+    config::model::{BudgetsConfig, SpendCaps},
+    database::client::establish_connection,
+};
+use oxy_shared::errors::OxyError;
+
+/// Spend of a workspace, or of one user in it, per UTC day and month, kept
+/// in `usage_spend`.
+///
+/// Totals from before the run are read once when the ledger is opened and
+/// this run's spend is added in memory, so concurrent runs only see each
+/// other's spend when they start.
+#[derive(Debug)]
+pub struct SpendLedger {
+    db: DatabaseConnection,
+    workspace_id: Uuid,
+    user_id: Option<Uuid>,
+    workspace_caps: Option<SpendCaps>,
+    user_caps: Option<SpendCaps>,
+    workspace_before: PeriodSpend,
+    user_before: PeriodSpend,
+    run: Mutex<Spend>,
+}
+
+#[derive(Debug, Default, Clone, Copy, FromQueryResult)]
+struct PeriodSpend {
+    day_tokens: i64,
+    day_cost_usd: f64,
+    month_tokens: i64,
+    month_cost_usd: f64,
+}
+
+impl PeriodSpend {
+    fn with(self, run: &Spend) -> Self {
+        Self {
+            day_tokens: self.day_tokens + run.tokens() as i64,
+            day_cost_usd: self.day_cost_usd + run.cost_usd,
+            month_tokens: self.month_tokens + run.tokens() as i64,
+            month_cost_usd: self.month_cost_usd + run.cost_usd,
+        }
+    }
+}
+
+impl SpendLedger {
+    pub async fn open(
+        workspace_id: Uuid,
+        user_id: Option<Uuid>,
+        config: &BudgetsConfig,
+    ) -> Result<Self, OxyError> {
+        let db = establish_connection().await?;
+        let workspace_before = match &config.workspace {
+            Some(_) => period_spend(&db, workspace_id, None).await?,
+            None => PeriodSpend::default(),
+        };
+        let user_before = match (&config.user, user_id) {
+            (Some(_), Some(user_id)) => period_spend(&db, workspace_id, Some(user_id)).await?,
+            _ => PeriodSpend::default(),
+        };
+        Ok(Self {
+            db,
+            workspace_id,
+            user_id,
+            workspace_caps: config.workspace.clone(),
+            user_caps: config.user.clone(),
+            workspace_before,
+            user_before,
+            run: Mutex::default(),
+        })
+    }
+
+    /// Add `spend` to today's row. Failing to persist is logged rather than
+    /// failing the run; the caps are still checked against this run's spend.
+    pub async fn record(&self, spend: &Spend) -> Result<(), OxyError> {
+        self.run.lock()?.add(spend);
+        let result = self
+            .db
+            .execute(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                "INSERT INTO usage_spend \
+                     (workspace_id, user_id, day, input_tokens, output_tokens, cost_usd, tool_calls) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7) \
+                 ON CONFLICT (workspace_id, user_id, day) DO UPDATE SET \
+                     input_tokens = usage_spend.input_tokens + EXCLUDED.input_tokens, \
+                     output_tokens = usage_spend.output_tokens + EXCLUDED.output_tokens, \
+                     cost_usd = usage_spend.cost_usd + EXCLUDED.cost_usd, \
+                     tool_calls = usage_spend.tool_calls + EXCLUDED.tool_calls, \
+                     updated_at = now()",
+                [
+                    self.workspace_id.into(),
+                    self.user_id.unwrap_or_default().into(),
+                    Utc::now().date_naive().into(),
+                    (spend.input_tokens as i64).into(),
+                    (spend.output_tokens as i64).into(),
+                    spend.cost_usd.into(),
+                    (spend.tool_calls as i64).into(),
+                ],
+            ))
+            .await;
+        if let Err(err) = result {
+            tracing::warn!(
+                "Failed to record spend for workspace {}: {err}",
+                self.workspace_id
+            );
+        }
+        Ok(())
+    }
+
+    /// The first workspace or user cap that has been reached, if any.
+    pub fn cap_reached(&self) -> Result<Option<String>, OxyError> {
+        let run = *self.run.lock()?;
+        let scopes = [
+            (
+                "the workspace",
+                self.workspace_caps.as_ref(),
+                self.workspace_before,
+            ),
+            (
+                "this user",
+                self.user_caps.as_ref().filter(|_| self.user_id.is_some()),
+                self.user_before,
+            ),
+        ];
+        for (scope, caps, before) in scopes {
+            if let Some(caps) = caps
+                && let Some(reason) = caps_reached(scope, caps, &before.with(&run))
+            {
+                return Ok(Some(reason));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The first scope with a USD cap, if any.
+    pub fn cost_capped(&self) -> Option<&'static str> {
+        let usd_capped = |caps: &Option<SpendCaps>| {
+            caps.as_ref()
+                .is_some_and(|c| c.daily_usd.or(c.monthly_usd).is_some())
+        };
+        if usd_capped(&self.workspace_caps) {
+            Some("the workspace")
+        } else if self.user_id.is_some() && usd_capped(&self.user_caps) {
+            Some("this user")
+        } else {
+            None
+        }
+    }
+}
+
+fn caps_reached(scope: &str, caps: &SpendCaps, spent: &PeriodSpend) -> Option<String> {
+    let usd = [
+        ("daily", caps.daily_usd, spent.day_cost_usd),
+        ("monthly", caps.monthly_usd, spent.month_cost_usd),
+    ];
+    for (period, cap, spent) in usd {
+        if let Some(cap) = cap
+            && spent >= cap
+        {
+            return Some(format!(
+                "{scope} has spent ${spent:.2}, reaching its {period} cap of ${cap:.2}"
+            ));
+        }
+    }
+    let tokens = [
+        ("daily", caps.daily_tokens, spent.day_tokens),
+        ("monthly", caps.monthly_tokens, spent.month_tokens),
+    ];
+    for (period, cap, spent) in tokens {
+        if let Some(cap) = cap
+            && spent as u64 >= cap
+        {
+            return Some(format!(
+                "{scope} has used {spent} tokens, reaching its {period} cap of {cap}"
+            ));
+        }
+    }
+    None
+}
+
+/// Spend recorded today and this month, for the whole workspace or one user.
+async fn period_spend(
+    db: &DatabaseConnection,
+    workspace_id: Uuid,
+    user_id: Option<Uuid>,
+) -> Result<PeriodSpend, OxyError> {
+    let today = Utc::now().date_naive();
+    let month_start = today.with_day(1).unwrap_or(today);
+    PeriodSpend::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "SELECT \
+             COALESCE(SUM(input_tokens + output_tokens) FILTER (WHERE day = $2), 0)::BIGINT AS day_tokens, \
+             COALESCE(SUM(cost_usd) FILTER (WHERE day = $2), 0)::DOUBLE PRECISION AS day_cost_usd, \
+             COALESCE(SUM(input_tokens + output_tokens), 0)::BIGINT AS month_tokens, \
+             COALESCE(SUM(cost_usd), 0)::DOUBLE PRECISION AS month_cost_usd \
+         FROM usage_spend \
+         WHERE workspace_id = $1 AND day >= $3 AND ($4::UUID IS NULL OR user_id = $4)",
+        [
+            workspace_id.into(),
+            today.into(),
+            month_start.into(),
+            user_id.into(),
+        ],
+    ))
+    .one(db)
+    .await
+    .map_err(|e| OxyError::DBError(format!("Failed to read spend: {e}")))
+    .map(Option::unwrap_or_default)
+}
+
+/// Spend of a workspace on one day, across all users.
+#[derive(Debug, Clone, FromQueryResult)]
+pub struct DailySpend {
+    pub day: NaiveDate,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost_usd: f64,
+    pub tool_calls: i64,
+}
+
+/// Spend of `workspace_id` per day over the last `days` days, oldest first.
+/// Days without spend are left out.
+pub async fn daily_spend(workspace_id: Uuid, days: u32) -> Result<Vec<DailySpend>, OxyError> {
+    let db = establish_connection().await?;
+    let since = Utc::now().date_naive() - chrono::Days::new(days.saturating_sub(1) as u64);
+    DailySpend::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "SELECT day, \
+             SUM(input_tokens)::BIGINT AS input_tokens, \
+             SUM(output_tokens)::BIGINT AS output_tokens, \
+             SUM(cost_usd)::DOUBLE PRECISION AS cost_usd, \
+             SUM(tool_calls)::BIGINT AS tool_calls \
+         FROM usage_spend \
+         WHERE workspace_id = $1 AND day >= $2 \
+         GROUP BY day \
+         ORDER BY day",
+        [workspace_id.into(), since.into()],
+    ))
+    .all(&db)
+    .await
+    .map_err(|e| OxyError::DBError(format!("Failed to read spend: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_caps_reached() {
+        let caps = SpendCaps {
+            daily_usd: Some(10.0),
+            monthly_tokens: Some(1_000),
+            ..Default::default()
+        };
+        let before = PeriodSpend {
+            day_tokens: 100,
+            day_cost_usd: 9.0,
+            month_tokens: 900,
+            month_cost_usd: 30.0,
+        };
+
+        assert_eq!(caps_reached("the workspace", &caps, &before), None);
+        let run = Spend {
+            input_tokens: 50,
+            output_tokens: 10,
+            cost_usd: 0.5,
+            tool_calls: 0,
+        };
+        assert_eq!(
+            caps_reached("the workspace", &caps, &before.with(&run)),
+            None
+        );
+        let run = Spend {
+            cost_usd: 1.0,
+            ..run
+        };
+        assert_eq!(
+            caps_reached("the workspace", &caps, &before.with(&run)).unwrap(),
+            "the workspace has spent $10.00, reaching its daily cap of $10.00"
+        );
+        let run = Spend {
+            input_tokens: 100,
+            cost_usd: 0.0,
+            ..run
+        };
+        assert_eq!(
+            caps_reached("this user", &caps, &before.with(&run)).unwrap(),
+            "this user has used 1010 tokens, reaching its monthly cap of 1000"
+        );
+    }
+}
@@ -0,0 +1,282 @@
+//! Run budgets and workspace/user spend caps.
+//!
+//! An agent or workflow run with a `budget:` gets a [`Budget`] on its
+//! [`ExecutionContext`]. Budgets of nested runs link to the enclosing one, so
+//! spend counts toward every level and the tightest limit wins. When
+//! config.yml has `budgets:`, the outermost budget also carries a
+//! [`SpendLedger`] that persists spend per workspace, user and day and
+//! enforces the daily and monthly caps.
+//!
+//! [`ExecutionContext`]: crate::execute::ExecutionContext
+
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+
+use crate::config::model::{BudgetsConfig, RunBudget};
+use oxy_shared::errors::OxyError;
+
+mod ledger;
+
+pub use ledger::{DailySpend, SpendLedger, daily_spend};
+
+/// Tokens, cost and tool calls spent by a run or period.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+pub struct Spend {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+    pub tool_calls: u64,
+}
+
+impl Spend {
+    pub fn tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+
+    pub fn add(&mut self, other: &Spend) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cost_usd += other.cost_usd;
+        self.tool_calls += other.tool_calls;
+    }
+}
+
+#[derive(Debug)]
+pub struct Budget {
+    /// What the limits belong to, e.g. `agent 'sales.agent.yml'`
+    scope: String,
+    limits: RunBudget,
+    spent: Mutex<Spend>,
+    parent: Option<Arc<Budget>>,
+    ledger: Option<SpendLedger>,
+}
+
+impl Budget {
+    pub fn new(
+        scope: impl Into<String>,
+        limits: RunBudget,
+        ledger: Option<SpendLedger>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            scope: scope.into(),
+            limits,
+            spent: Mutex::default(),
+            parent: None,
+            ledger,
+        })
+    }
+
+    /// Budget of a run nested in this one.
+    pub fn child(self: &Arc<Self>, scope: impl Into<String>, limits: RunBudget) -> Arc<Self> {
+        Arc::new(Self {
+            scope: scope.into(),
+            limits,
+            spent: Mutex::default(),
+            parent: Some(self.clone()),
+            ledger: None,
+        })
+    }
+
+    pub fn spent(&self) -> Result<Spend, OxyError> {
+        Ok(*self.spent.lock()?)
+    }
+
+    /// Charge one LLM call. The tokens are already spent, so they are
+    /// counted even when the call pushes a budget over its limit.
+    pub async fn charge_tokens(
+        &self,
+        input_tokens: u64,
+        output_tokens: u64,
+        cost_usd: f64,
+    ) -> Result<(), OxyError> {
+        self.charge(Spend {
+            input_tokens,
+            output_tokens,
+            cost_usd,
+            tool_calls: 0,
+        })
+        .await
+    }
+
+    /// Charge one LLM call to `model`, priced with `budgets.prices`. Models
+    /// without a price count as free, unless a cost limit applies: then the
+    /// call is refused, since its cost could not be counted toward it.
+    pub async fn charge_call(
+        &self,
+        model: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+        budgets: Option<&BudgetsConfig>,
+    ) -> Result<(), OxyError> {
+        let cost = budgets.and_then(|b| b.cost(model, input_tokens, output_tokens));
+        self.charge_tokens(input_tokens, output_tokens, cost.unwrap_or(0.0))
+            .await?;
+        match (cost, self.cost_limit()) {
+            (None, Some(scope)) => Err(OxyError::ConfigurationError(format!(
+                "{scope} has a cost limit, but model '{model}' has no price in `budgets.prices`"
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// The reason to refuse further calls, when a limit or cap has already
+    /// been reached.
+    pub fn exceeded(&self) -> Result<Option<String>, OxyError> {
+        for budget in self.chain() {
+            if let Some(reason) = budget.over_limit(&budget.spent()?) {
+                return Ok(Some(reason));
+            }
+        }
+        match self.ledger() {
+            Some(ledger) => ledger.cap_reached(),
+            None => Ok(None),
+        }
+    }
+
+    /// Charge tool calls about to be made, refusing them when they would go
+    /// over a limit.
+    pub async fn charge_tool_calls(&self, count: u64) -> Result<(), OxyError> {
+        for budget in self.chain() {
+            if let Some(max) = budget.limits.max_tool_calls {
+                let made = budget.spent()?.tool_calls;
+                if made + count > max {
+                    return Err(OxyError::BudgetExceeded(format!(
+                        "{} has made {made} tool calls, {count} more would go over its limit of {max}",
+                        budget.scope
+                    )));
+                }
+            }
+        }
+        self.charge(Spend {
+            tool_calls: count,
+            ..Default::default()
+        })
+        .await
+    }
+
+    async fn charge(&self, spend: Spend) -> Result<(), OxyError> {
+        let mut exceeded = None;
+        for budget in self.chain() {
+            let spent = {
+                let mut spent = budget.spent.lock()?;
+                spent.add(&spend);
+                *spent
+            };
+            exceeded = exceeded.or_else(|| budget.over_limit(&spent));
+        }
+        if let Some(ledger) = self.ledger() {
+            ledger.record(&spend).await?;
+            exceeded = exceeded.or(ledger.cap_reached()?);
+        }
+        match exceeded {
+            Some(reason) => Err(OxyError::BudgetExceeded(reason)),
+            None => Ok(()),
+        }
+    }
+
+    /// This budget followed by the budgets of the enclosing runs.
+    fn chain(&self) -> impl Iterator<Item = &Budget> {
+        std::iter::successors(Some(self), |budget| budget.parent.as_deref())
+    }
+
+    fn ledger(&self) -> Option<&SpendLedger> {
+        self.chain().find_map(|budget| budget.ledger.as_ref())
+    }
+
+    /// What a USD limit or cap belongs to, if any applies to this budget.
+    fn cost_limit(&self) -> Option<&str> {
+        self.chain()
+            .find(|budget| budget.limits.max_cost_usd.is_some())
+            .map(|budget| budget.scope.as_str())
+            .or_else(|| self.ledger().and_then(SpendLedger::cost_capped))
+    }
+
+    fn over_limit(&self, spent: &Spend) -> Option<String> {
+        if let Some(max) = self.limits.max_tokens
+            && spent.tokens() > max
+        {
+            return Some(format!(
+                "{} used {} tokens, over its limit of {max}",
+                self.scope,
+                spent.tokens()
+            ));
+        }
+        if let Some(max) = self.limits.max_cost_usd
+            && spent.cost_usd > max
+        {
+            return Some(format!(
+                "{} spent ${:.4}, over its limit of ${max:.2}",
+                self.scope, spent.cost_usd
+            ));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_nested_budgets() {
+        let workflow = Budget::new(
+            "workflow 'report'",
+            RunBudget {
+                max_tokens: Some(1_000),
+                ..Default::default()
+            },
+            None,
+        );
+        let agent = workflow.child(
+            "agent 'sales'",
+            RunBudget {
+                max_tool_calls: Some(2),
+                ..Default::default()
+            },
+        );
+
+        agent.charge_tokens(400, 100, 0.0).await.unwrap();
+        agent.charge_tool_calls(2).await.unwrap();
+        assert!(matches!(
+            agent.charge_tool_calls(1).await,
+            Err(OxyError::BudgetExceeded(_))
+        ));
+        assert_eq!(agent.spent().unwrap().tool_calls, 2);
+
+        // Spend of the agent counts toward the workflow
+        let err = agent.charge_tokens(500, 100, 0.0).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Budget exceeded: workflow 'report' used 1100 tokens, over its limit of 1000"
+        );
+        assert_eq!(workflow.spent().unwrap().tokens(), 1_100);
+        assert!(agent.exceeded().unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_unpriced_models_under_cost_limits() {
+        let budgets = BudgetsConfig::default();
+        let free = Budget::new("agent 'sales'", RunBudget::default(), None);
+        free.charge_call("gpt-4o", 100, 10, Some(&budgets))
+            .await
+            .unwrap();
+        assert_eq!(free.exceeded().unwrap(), None);
+
+        let capped = Budget::new(
+            "agent 'sales'",
+            RunBudget {
+                max_cost_usd: Some(1.0),
+                ..Default::default()
+            },
+            None,
+        );
+        let err = capped
+            .child("agent 'lookup'", RunBudget::default())
+            .charge_call("gpt-4o", 100, 10, Some(&budgets))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, OxyError::ConfigurationError(_)));
+        assert_eq!(capped.spent().unwrap().tokens(), 110);
+    }
+}
@@ -11,6 +11,8 @@ pub struct Authentication {
     #[garde(dive)]
     pub okta: Option<OktaAuth>,
     #[garde(dive)]
+    pub oidc: Option<OidcAuth>,
+    #[garde(dive)]
     pub magic_link: Option<MagicLinkAuth>,
 }
 
@@ -50,6 +52,64 @@ pub struct OktaAuth {
     pub domain: String,
 }
 
+/// Any OpenID Connect provider (Entra ID, Keycloak, Auth0, ...), configured
+/// through discovery at `{issuer_url}/.well-known/openid-configuration`.
+#[derive(Serialize, Deserialize, Validate, Debug, Clone, JsonSchema)]
+pub struct OidcAuth {
+    #[garde(length(min = 1))]
+    pub issuer_url: String,
+    #[garde(length(min = 1))]
+    pub client_id: String,
+    /// Omit for public clients, which rely on PKCE alone
+    #[garde(skip)]
+    pub client_secret: Option<String>,
+    #[garde(skip)]
+    #[serde(default = "default_oidc_scopes")]
+    pub scopes: String,
+    /// Label of the login button
+    #[garde(skip)]
+    #[serde(default = "default_sso_display_name")]
+    pub display_name: String,
+    #[garde(dive)]
+    #[serde(default)]
+    pub claims: OidcClaimMapping,
+    /// Email domains the provider is trusted for without an
+    /// `email_verified: true` claim, for providers such as Entra ID that
+    /// never send one
+    #[garde(skip)]
+    #[serde(default)]
+    pub allowed_domains: Vec<String>,
+}
+
+/// ID token or userinfo claims holding the user's profile.
+#[derive(Serialize, Deserialize, Validate, Debug, Clone, JsonSchema)]
+pub struct OidcClaimMapping {
+    #[garde(length(min = 1))]
+    pub email: String,
+    #[garde(length(min = 1))]
+    pub name: String,
+    #[garde(length(min = 1))]
+    pub picture: String,
+}
+
+impl Default for OidcClaimMapping {
+    fn default() -> Self {
+        Self {
+            email: "email".to_string(),
+            name: "name".to_string(),
+            picture: "picture".to_string(),
+        }
+    }
+}
+
+fn default_oidc_scopes() -> String {
+    "openid email profile".to_string()
+}
+
+fn default_sso_display_name() -> String {
+    "SSO".to_string()
+}
+
 impl Authentication {
     pub fn from_env() -> Result<Self, oxy_shared::errors::OxyError> {
         let client_id = env::var("GOOGLE_CLIENT_ID").ok();
@@ -74,6 +134,35 @@ impl Authentication {
             _ => None,
         };
 
+        let oidc = match (env::var("OIDC_ISSUER_URL"), env::var("OIDC_CLIENT_ID")) {
+            (Ok(issuer_url), Ok(client_id)) => {
+                let defaults = OidcClaimMapping::default();
+                Some(OidcAuth {
+                    issuer_url,
+                    client_id,
+                    client_secret: env::var("OIDC_CLIENT_SECRET").ok(),
+                    scopes: env::var("OIDC_SCOPES").unwrap_or_else(|_| default_oidc_scopes()),
+                    display_name: env::var("OIDC_DISPLAY_NAME")
+                        .unwrap_or_else(|_| default_sso_display_name()),
+                    claims: OidcClaimMapping {
+                        email: env::var("OIDC_EMAIL_CLAIM").unwrap_or(defaults.email),
+                        name: env::var("OIDC_NAME_CLAIM").unwrap_or(defaults.name),
+                        picture: env::var("OIDC_PICTURE_CLAIM").unwrap_or(defaults.picture),
+                    },
+                    allowed_domains: env::var("OIDC_ALLOWED_DOMAINS")
+                        .map(|domains| {
+                            domains
+                                .split(',')
+                                .map(|domain| domain.trim().to_lowercase())
+                                .filter(|domain| !domain.is_empty())
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                })
+            }
+            _ => None,
+        };
+
         let magic_link_local_test = env::var("MAGIC_LINK_LOCAL_TEST").is_ok();
         let magic_link_from_email = env::var("MAGIC_LINK_FROM_EMAIL").ok();
         let magic_link = if magic_link_local_test || magic_link_from_email.is_some() {
@@ -103,6 +192,7 @@ impl Authentication {
         let auth = Authentication {
             google,
             okta,
+            oidc,
             magic_link,
         };
 
@@ -1,18 +1,19 @@
 use std::{
     path::{Path, PathBuf},
-    sync::{Arc, RwLock},
+    sync::{Arc, OnceLock, RwLock},
 };
 
 use crate::{
     config::{agent_config::AgenticConfig, constants::DATABASE_SEMANTIC_PATH},
     observability::events,
 };
+use oxy_semantic::{AccessLevel, ChangeDetector, ParserConfig, SemanticLayerParser, View};
 use oxy_shared::errors::OxyError;
 
 use super::{
     model::{
-        AgentConfig, AppConfig, BuilderAgentConfig, Config, Database, Model, Workflow,
-        WorkflowWithRawVariables,
+        AccessPolicy, AgentConfig, AppConfig, BuilderAgentConfig, Config, Database, Model,
+        Workflow, WorkflowWithRawVariables,
     },
     storage::{ConfigSource, ConfigStorage},
     test_config::TestFileConfig,
@@ -24,6 +25,14 @@ pub struct ConfigManager {
     config: Arc<Config>,
     /// Runtime-registered databases (e.g. modeling outputs). Checked before static config.
     runtime_databases: Arc<RwLock<Vec<Database>>>,
+    /// Semantic views, parsed on first use by access control.
+    semantic_views: Arc<OnceLock<Vec<View>>>,
+    /// Hash of the semantic views and topics, computed on first use by the
+    /// result cache.
+    semantic_fingerprint: Arc<OnceLock<String>>,
+    /// Caller's clearance for column-level access rules; `None` when the
+    /// caller is not subject to them.
+    access_level: Option<AccessLevel>,
 }
 
 impl ConfigManager {
@@ -32,6 +41,9 @@ impl ConfigManager {
             storage: Arc::new(storage),
             config: Arc::new(config),
             runtime_databases: Arc::new(RwLock::new(Vec::new())),
+            semantic_views: Arc::new(OnceLock::new()),
+            semantic_fingerprint: Arc::new(OnceLock::new()),
+            access_level: None,
         }
     }
 
@@ -109,6 +121,100 @@ impl ConfigManager {
         self.config.base_branch.as_deref()
     }
 
+    /// Copy of this manager that applies column-level access rules for a
+    /// caller with `access_level`.
+    pub fn with_access_level(mut self, access_level: AccessLevel) -> Self {
+        self.access_level = Some(access_level);
+        self
+    }
+
+    /// Caller's clearance for column-level access rules on semantic fields.
+    ///
+    /// Managers that were never given one get the lowest clearance, so an
+    /// entry point that forgets to set it fails closed.
+    pub fn access_level(&self) -> AccessLevel {
+        self.access_level.unwrap_or(AccessLevel::Public)
+    }
+
+    /// Access policies that apply to `database`: the `access_policies` in
+    /// config.yml plus those declared on semantic views over that database.
+    ///
+    /// Fails when the semantic layer cannot be read, since a policy hidden in
+    /// an unreadable view must not silently stop being enforced.
+    pub fn access_policies(&self, database: &str) -> Result<Vec<AccessPolicy>, OxyError> {
+        let mut policies: Vec<AccessPolicy> = self
+            .config
+            .access_policies
+            .iter()
+            .filter(|policy| policy.applies_to(database))
+            .cloned()
+            .collect();
+        for view in self.semantic_views()? {
+            let Some(view_policies) = &view.access_policies else {
+                continue;
+            };
+            let Some(table) = &view.table else {
+                return Err(OxyError::ConfigurationError(format!(
+                    "View '{}' declares access_policies but has no 'table'",
+                    view.name
+                )));
+            };
+            policies.extend(
+                view_policies
+                    .iter()
+                    .map(|policy| AccessPolicy {
+                        name: format!("{}.{}", view.name, policy.name),
+                        database: view.datasource.clone(),
+                        tables: vec![table.clone()],
+                        predicate: policy.predicate.clone(),
+                    })
+                    .filter(|policy| policy.applies_to(database)),
+            );
+        }
+        Ok(policies)
+    }
+
+    /// Semantic views used for access control, parsed once per manager.
+    ///
+    /// Fails when the semantic layer cannot be read, so rules declared there
+    /// are never silently skipped.
+    pub fn semantic_views(&self) -> Result<&[View], OxyError> {
+        if let Some(views) = self.semantic_views.get() {
+            return Ok(views);
+        }
+        let views = self.load_semantic_views()?;
+        Ok(self.semantic_views.get_or_init(|| views))
+    }
+
+    /// Hash of every semantic view and topic in the workspace, computed once
+    /// per manager. Empty when the files cannot be read.
+    pub fn semantic_fingerprint(&self) -> &str {
+        self.semantic_fingerprint.get_or_init(|| {
+            let scan_path = self.semantics_scan_path();
+            let detector = ChangeDetector::new(scan_path.clone(), scan_path.join(".semantics"));
+            detector.semantic_fingerprint().unwrap_or_else(|e| {
+                tracing::warn!("Failed to fingerprint semantic layer: {}", e);
+                String::new()
+            })
+        })
+    }
+
+    fn load_semantic_views(&self) -> Result<Vec<View>, OxyError> {
+        let scan_path = self.semantics_scan_path();
+        if !scan_path.exists() {
+            return Ok(Vec::new());
+        }
+        let parse_result =
+            SemanticLayerParser::new(ParserConfig::new(&scan_path).with_validation(false))
+                .parse()
+                .map_err(|e| {
+                    OxyError::ConfigurationError(format!(
+                        "Failed to load access rules from semantic views: {e}"
+                    ))
+                })?;
+        Ok(parse_result.semantic_layer.views)
+    }
+
     pub async fn resolve_file<P: AsRef<Path>>(&self, file_ref: P) -> Result<String, OxyError> {
         self.storage.fs_link(file_ref).await
     }
@@ -286,6 +392,8 @@ impl ConfigManager {
             crate::config::model::DatabaseType::Postgres(postgres) => postgres.password_var.clone(),
             crate::config::model::DatabaseType::Airhouse(airhouse) => airhouse.password_var.clone(),
             crate::config::model::DatabaseType::Mysql(mysql) => mysql.password_var.clone(),
+            crate::config::model::DatabaseType::Mssql(mssql) => mssql.password_var.clone(),
+            crate::config::model::DatabaseType::Trino(trino) => trino.password_var.clone(),
             crate::config::model::DatabaseType::Snowflake(snowflake) => {
                 snowflake.auth_type.get_password_var().cloned()
             }
@@ -12,6 +12,7 @@ use std::hash::Hash;
 use std::path::PathBuf;
 use utoipa::ToSchema;
 
+pub use budget::{BudgetsConfig, ModelPrice, RunBudget, SpendCaps};
 pub use variables::{Variable, Variables};
 
 use super::validate::{
@@ -28,12 +29,15 @@ use crate::config::validate::{
 pub use duckdb::{CatalogConfig, DuckDBOptions, DuckLakeConfig, S3StorageSecret, StorageConfig};
 pub use oxy_llm::{
     AnthropicModelConfig, GeminiModelConfig, HeaderValue, Model, OllamaModelConfig,
-    OpenAIModelConfig, default_openai_api_url,
+    OpenAIModelConfig, RateLimitConfig, default_openai_api_url,
 };
 use oxy_shared::errors::OxyError;
+pub use schedule::{CronExpressions, WorkflowSchedule};
 pub use workflow::WorkflowWithRawVariables;
 
+mod budget;
 mod duckdb;
+mod schedule;
 mod variables;
 mod workflow;
 
@@ -94,6 +98,42 @@ pub struct Config {
     #[garde(dive)]
     pub a2a: Option<crate::config::a2a_config::A2aConfig>,
 
+    /// Optional cache for SQL query results.
+    /// If not specified, every query runs against the database.
+    ///
+    /// Example config.yml:
+    ///   result_cache:
+    ///     ttl_secs: 3600
+    ///     max_size_mb: 512
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    #[garde(dive)]
+    pub result_cache: Option<ResultCacheConfig>,
+
+    /// Model prices and workspace/user spend caps used to enforce budgets.
+    ///
+    /// Example config.yml:
+    ///   budgets:
+    ///     prices:
+    ///       gpt-4o: { input: 2.5, output: 10 }
+    ///     workspace:
+    ///       monthly_usd: 1000
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    #[garde(skip)]
+    pub budgets: Option<BudgetsConfig>,
+
+    /// Row-level access policies Oxy enforces on every connector by rewriting
+    /// SQL before it runs. Queries that cannot be rewritten safely are rejected.
+    ///
+    /// Example config.yml:
+    ///   access_policies:
+    ///     - name: region_isolation
+    ///       database: warehouse
+    ///       tables: [orders, customers]
+    ///       predicate: "region = {{ filters.region }}"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[garde(dive)]
+    pub access_policies: Vec<AccessPolicy>,
+
     /// Branches that are protected: saving a file while on one of these branches
     /// will auto-create a new feature branch instead of writing directly.
     /// Defaults to [default_branch] (usually "main") when not set.
@@ -262,6 +302,57 @@ pub struct McpConfig {
     pub tools: Vec<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, Validate)]
+#[garde(context(ValidationContext))]
+pub struct ResultCacheConfig {
+    #[serde(default = "default_result_cache_enabled")]
+    #[garde(skip)]
+    pub enabled: bool,
+    /// Seconds before a cached result expires. Results never expire when unset.
+    /// Databases and tasks can override this with their own TTL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[garde(range(min = 1))]
+    pub ttl_secs: Option<u64>,
+    /// Total size of cached results before the least recently used ones are evicted
+    #[serde(default = "default_result_cache_max_size_mb")]
+    #[garde(range(min = 1))]
+    pub max_size_mb: u64,
+}
+
+/// A row-level security rule: rows of `tables` are only visible when
+/// `predicate` holds.
+///
+/// The predicate is a SQL boolean expression over the table's columns and may
+/// reference `{{ filters.<name> }}` (request session filters) and
+/// `{{ user.id }}`. Values are bound as SQL literals; arrays bind as a
+/// parenthesised list for use with `IN`.
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, Validate, PartialEq)]
+#[garde(context(ValidationContext))]
+pub struct AccessPolicy {
+    #[garde(length(min = 1))]
+    pub name: String,
+    /// Database the policy applies to. Applies to every database when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[garde(custom(|db: &Option<String>, ctx: &ValidationContext| {
+        match db {
+            Some(database) => validate_database_exists(database.as_str(), ctx),
+            None => Ok(()),
+        }
+    }))]
+    pub database: Option<String>,
+    /// Tables the policy protects, optionally schema-qualified
+    #[garde(length(min = 1))]
+    pub tables: Vec<String>,
+    #[garde(length(min = 1))]
+    pub predicate: String,
+}
+
+impl AccessPolicy {
+    pub fn applies_to(&self, database: &str) -> bool {
+        self.database.as_deref().is_none_or(|db| db == database)
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, JsonSchema, ToSchema)]
 pub struct SemanticModels {
     pub table: String,
@@ -697,6 +788,221 @@ impl Mysql {
     }
 }
 
+/// Microsoft SQL Server / Azure SQL Database, spoken over TDS.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Validate, Default)]
+#[garde(context(ValidationContext))]
+pub struct Mssql {
+    #[serde(default)]
+    #[garde(skip)]
+    pub host: Option<String>,
+    #[serde(default)]
+    #[garde(skip)]
+    pub host_var: Option<String>,
+    #[serde(default)]
+    #[garde(skip)]
+    pub port: Option<String>,
+    #[serde(default)]
+    #[garde(skip)]
+    pub port_var: Option<String>,
+    #[serde(default)]
+    #[garde(skip)]
+    pub user: Option<String>,
+    #[serde(default)]
+    #[garde(skip)]
+    pub user_var: Option<String>,
+    #[garde(skip)]
+    #[schemars(skip)]
+    #[serde(default)]
+    pub password: Option<String>,
+    #[serde(default)]
+    #[garde(skip)]
+    pub password_var: Option<String>,
+    #[serde(default)]
+    #[garde(skip)]
+    pub database: Option<String>,
+    #[serde(default)]
+    #[garde(skip)]
+    pub database_var: Option<String>,
+    /// Require TLS for the connection. Azure SQL rejects unencrypted logins,
+    /// so leave this on unless you are talking to a local dev container.
+    #[serde(default = "default_mssql_encrypt")]
+    #[garde(skip)]
+    pub encrypt: bool,
+    /// Accept the server certificate without validating its chain. Needed
+    /// for SQL Server containers that ship a self-signed certificate.
+    #[serde(default)]
+    #[garde(skip)]
+    pub trust_server_certificate: bool,
+    /// Schema → tables to sync. Empty means every schema except `sys` and
+    /// `INFORMATION_SCHEMA`.
+    #[serde(default)]
+    #[garde(skip)]
+    pub schemas: HashMap<String, Vec<String>>,
+}
+
+fn default_mssql_encrypt() -> bool {
+    true
+}
+
+impl Mssql {
+    pub async fn get_password(&self, secret_manager: &SecretsManager) -> Result<String, OxyError> {
+        secret_manager
+            .resolve_config_value(
+                self.password.as_deref(),
+                self.password_var.as_deref(),
+                "password",
+                None,
+            )
+            .await
+    }
+
+    pub async fn get_host(&self, secret_manager: &SecretsManager) -> Result<String, OxyError> {
+        secret_manager
+            .resolve_config_value(
+                self.host.as_deref(),
+                self.host_var.as_deref(),
+                "host",
+                Some("localhost"),
+            )
+            .await
+    }
+
+    pub async fn get_port(&self, secret_manager: &SecretsManager) -> Result<String, OxyError> {
+        secret_manager
+            .resolve_config_value(
+                self.port.as_deref(),
+                self.port_var.as_deref(),
+                "port",
+                Some("1433"),
+            )
+            .await
+    }
+
+    pub async fn get_user(&self, secret_manager: &SecretsManager) -> Result<String, OxyError> {
+        secret_manager
+            .resolve_config_value(
+                self.user.as_deref(),
+                self.user_var.as_deref(),
+                "user",
+                Some("sa"),
+            )
+            .await
+    }
+
+    pub async fn get_database(&self, secret_manager: &SecretsManager) -> Result<String, OxyError> {
+        secret_manager
+            .resolve_config_value(
+                self.database.as_deref(),
+                self.database_var.as_deref(),
+                "database",
+                Some("master"),
+            )
+            .await
+    }
+}
+
+/// Trino (or Presto) coordinator, spoken over the Trino HTTP client protocol.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Validate, Default)]
+#[garde(context(ValidationContext))]
+pub struct Trino {
+    #[serde(default)]
+    #[garde(skip)]
+    pub host: Option<String>,
+    #[serde(default)]
+    #[garde(skip)]
+    pub host_var: Option<String>,
+    #[serde(default)]
+    #[garde(skip)]
+    pub port: Option<String>,
+    #[serde(default)]
+    #[garde(skip)]
+    pub port_var: Option<String>,
+    #[serde(default)]
+    #[garde(skip)]
+    pub user: Option<String>,
+    #[serde(default)]
+    #[garde(skip)]
+    pub user_var: Option<String>,
+    #[garde(skip)]
+    #[schemars(skip)]
+    #[serde(default)]
+    pub password: Option<String>,
+    #[serde(default)]
+    #[garde(skip)]
+    pub password_var: Option<String>,
+    /// Catalog used for unqualified table references.
+    #[garde(length(min = 1))]
+    pub catalog: String,
+    /// Schema used for unqualified table references.
+    #[serde(default)]
+    #[garde(skip)]
+    pub schema: Option<String>,
+    /// Catalogs whose `information_schema` is introspected. Defaults to
+    /// `catalog` alone.
+    #[serde(default)]
+    #[garde(skip)]
+    pub catalogs: Vec<String>,
+    /// Connect over HTTPS. Trino only accepts passwords on TLS connections.
+    #[serde(default)]
+    #[garde(skip)]
+    pub https: bool,
+}
+
+impl Trino {
+    pub async fn get_password(&self, secret_manager: &SecretsManager) -> Result<String, OxyError> {
+        secret_manager
+            .resolve_config_value(
+                self.password.as_deref(),
+                self.password_var.as_deref(),
+                "password",
+                Some(""),
+            )
+            .await
+    }
+
+    pub async fn get_host(&self, secret_manager: &SecretsManager) -> Result<String, OxyError> {
+        secret_manager
+            .resolve_config_value(
+                self.host.as_deref(),
+                self.host_var.as_deref(),
+                "host",
+                Some("localhost"),
+            )
+            .await
+    }
+
+    pub async fn get_port(&self, secret_manager: &SecretsManager) -> Result<String, OxyError> {
+        secret_manager
+            .resolve_config_value(
+                self.port.as_deref(),
+                self.port_var.as_deref(),
+                "port",
+                Some("8080"),
+            )
+            .await
+    }
+
+    pub async fn get_user(&self, secret_manager: &SecretsManager) -> Result<String, OxyError> {
+        secret_manager
+            .resolve_config_value(
+                self.user.as_deref(),
+                self.user_var.as_deref(),
+                "user",
+                Some("oxy"),
+            )
+            .await
+    }
+
+    /// Catalogs to introspect: the configured list, or the default catalog.
+    pub fn introspected_catalogs(&self) -> Vec<String> {
+        if self.catalogs.is_empty() {
+            vec![self.catalog.clone()]
+        } else {
+            self.catalogs.clone()
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Validate, Default)]
 #[garde(context(ValidationContext))]
 pub struct ClickHouse {
@@ -882,6 +1188,11 @@ pub struct AgentConfig {
     #[garde(dive)]
     pub reasoning: Option<ReasoningConfig>,
 
+    /// Token, cost and tool-call limits for each run of this agent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[garde(skip)]
+    pub budget: Option<RunBudget>,
+
     #[serde(flatten)]
     #[garde(skip)]
     pub variables: Option<Variables>,
@@ -1105,6 +1416,25 @@ pub struct DuckDB {
     pub options: DuckDBOptions,
 }
 
+/// SQLite database file(s), opened in-process.
+#[derive(Serialize, Deserialize, Debug, Validate, Clone, JsonSchema)]
+#[garde(context(ValidationContext))]
+pub struct Sqlite {
+    /// Database file relative to the project root. A glob pattern
+    /// (`data/*.sqlite`) attaches every matching file as a schema named
+    /// after its file stem.
+    #[garde(length(min = 1))]
+    pub path: String,
+    /// Open the file(s) read-only. Turn off to let `execute_sql` tasks write.
+    #[serde(default = "default_sqlite_read_only")]
+    #[garde(skip)]
+    pub read_only: bool,
+}
+
+fn default_sqlite_read_only() -> bool {
+    true
+}
+
 #[derive(Serialize, Deserialize, Debug, Validate, Clone, JsonSchema)]
 #[garde(context(ValidationContext))]
 #[serde(untagged)] // Consider using tagged enum here if migrations are possible
@@ -1197,6 +1527,8 @@ pub enum DatabaseType {
     Bigquery(#[garde(dive)] BigQuery),
     #[serde(rename = "duckdb")]
     DuckDB(#[garde(dive)] DuckDB),
+    #[serde(rename = "sqlite")]
+    Sqlite(#[garde(dive)] Sqlite),
     #[serde(rename = "snowflake")]
     Snowflake(#[garde(dive)] Snowflake),
     #[serde(rename = "postgres")]
@@ -1214,6 +1546,10 @@ pub enum DatabaseType {
     Redshift(#[garde(dive)] Redshift),
     #[serde(rename = "mysql")]
     Mysql(#[garde(dive)] Mysql),
+    #[serde(rename = "mssql")]
+    Mssql(#[garde(dive)] Mssql),
+    #[serde(rename = "trino")]
+    Trino(#[garde(dive)] Trino),
     #[serde(rename = "clickhouse")]
     ClickHouse(#[garde(dive)] ClickHouse),
     #[serde(rename = "domo")]
@@ -1227,12 +1563,15 @@ impl std::fmt::Display for DatabaseType {
         match self {
             DatabaseType::Bigquery(_) => write!(f, "bigquery"),
             DatabaseType::DuckDB(_) => write!(f, "duckdb"),
+            DatabaseType::Sqlite(_) => write!(f, "sqlite"),
             DatabaseType::Snowflake(_) => write!(f, "snowflake"),
             DatabaseType::Postgres(_) => write!(f, "postgres"),
             DatabaseType::Airhouse(_) => write!(f, "airhouse"),
             DatabaseType::AirhouseManaged(_) => write!(f, "airhouse_managed"),
             DatabaseType::Redshift(_) => write!(f, "redshift"),
             DatabaseType::Mysql(_) => write!(f, "mysql"),
+            DatabaseType::Mssql(_) => write!(f, "mssql"),
+            DatabaseType::Trino(_) => write!(f, "trino"),
             DatabaseType::ClickHouse(_) => write!(f, "clickhouse"),
             DatabaseType::DOMO(_) => write!(f, "domo"),
             DatabaseType::MotherDuck(_) => write!(f, "motherduck"),
@@ -1249,6 +1588,12 @@ pub struct Database {
     #[serde(flatten)]
     #[garde(dive)]
     pub database_type: DatabaseType,
+
+    /// Seconds before cached query results for this database expire.
+    /// Overrides `result_cache.ttl_secs`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[garde(range(min = 1))]
+    pub cache_ttl_secs: Option<u64>,
 }
 
 impl Database {
@@ -1256,10 +1601,13 @@ impl Database {
         match &self.database_type {
             DatabaseType::Bigquery(_) => "bigquery",
             DatabaseType::DuckDB(_) => "duckdb",
+            DatabaseType::Sqlite(_) => "sqlite",
             DatabaseType::Snowflake(_) => "snowflake",
             DatabaseType::Postgres(_) => "postgres",
             DatabaseType::Redshift(_) => "redshift",
             DatabaseType::Mysql(_) => "mysql",
+            DatabaseType::Mssql(_) => "mssql",
+            DatabaseType::Trino(_) => "trino",
             DatabaseType::ClickHouse(_) => "clickhouse",
             DatabaseType::DOMO(_) => "domo",
             DatabaseType::MotherDuck(_) => "motherduck",
@@ -1272,11 +1620,14 @@ impl Database {
         match &self.database_type {
             DatabaseType::Bigquery(_) => "bigquery".to_owned(),
             DatabaseType::DuckDB(_) => "duckdb".to_owned(),
+            DatabaseType::Sqlite(_) => "sqlite".to_owned(),
             DatabaseType::Postgres(_) => "postgres".to_owned(),
             DatabaseType::Airhouse(_) => "duckdb".to_owned(),
             DatabaseType::AirhouseManaged(_) => "duckdb".to_owned(),
             DatabaseType::Redshift(_) => "postgres".to_owned(),
             DatabaseType::Mysql(_) => "mysql".to_owned(),
+            DatabaseType::Mssql(_) => "mssql".to_owned(),
+            DatabaseType::Trino(_) => "trino".to_owned(),
             DatabaseType::ClickHouse(_) => "clickhouse".to_string(),
             DatabaseType::Snowflake(_) => "snowflake".to_string(),
             DatabaseType::DOMO(_) => "domo".to_string(),
@@ -1318,6 +1669,20 @@ impl Database {
                     md.schemas.clone()
                 }
             }
+            DatabaseType::Mssql(ms) => {
+                if ms.schemas.is_empty() {
+                    // Empty key signals "all user schemas" — see the Mssql branch in loader::get_schemas_queries
+                    HashMap::from_iter([(String::default(), vec!["*".to_string()])])
+                } else {
+                    ms.schemas.clone()
+                }
+            }
+            // Keyed by catalog — see the Trino branch in loader::get_schemas_queries
+            DatabaseType::Trino(tr) => tr
+                .introspected_catalogs()
+                .into_iter()
+                .map(|catalog| (catalog, vec!["*".to_string()]))
+                .collect(),
             _ => Default::default(),
         }
     }
@@ -1356,6 +1721,27 @@ impl Database {
                     ..self
                 }
             }
+            DatabaseType::Mssql(ms) => {
+                let mut datasets_map = HashMap::new();
+                for dataset in datasets {
+                    let tables = ms.schemas.get(&dataset).cloned();
+                    datasets_map.insert(dataset, tables.unwrap_or(vec!["*".to_string()]));
+                }
+                Database {
+                    database_type: DatabaseType::Mssql(Mssql {
+                        schemas: datasets_map,
+                        ..ms.clone()
+                    }),
+                    ..self
+                }
+            }
+            DatabaseType::Trino(tr) => Database {
+                database_type: DatabaseType::Trino(Trino {
+                    catalogs: datasets,
+                    ..tr.clone()
+                }),
+                ..self
+            },
             _ => self,
         }
     }
@@ -1396,6 +1782,13 @@ impl Database {
                 }),
                 ..self
             },
+            DatabaseType::Mssql(ms) => Database {
+                database_type: DatabaseType::Mssql(Mssql {
+                    schemas: schema_tables,
+                    ..ms.clone()
+                }),
+                ..self
+            },
             _ => self,
         }
     }
@@ -1563,6 +1956,32 @@ fn validate_models(models: &Vec<Model>, ctx: &ValidationContext) -> garde::Resul
                     .map_err(|e| garde::Error::new(format!("models[{}].key_var: {}", i, e)))?;
             }
         }
+        for fallback in model.fallbacks() {
+            if fallback == model.name() {
+                return Err(garde::Error::new(format!(
+                    "models[{i}].fallbacks: '{fallback}' is the model itself"
+                )));
+            }
+            if !models.iter().any(|other| other.name() == fallback) {
+                return Err(garde::Error::new(format!(
+                    "models[{i}].fallbacks: model '{fallback}' not found"
+                )));
+            }
+        }
+        // Models sharing an API key share one rate limiter, so their limits must agree
+        if let Some(limit) = model.rate_limit()
+            && let Some(other) = models.iter().find(|other| {
+                other.provider_key() == model.provider_key()
+                    && other
+                        .rate_limit()
+                        .is_some_and(|other_limit| other_limit != limit)
+            })
+        {
+            return Err(garde::Error::new(format!(
+                "models[{i}].rate_limit: differs from the rate_limit of '{}', which uses the same key",
+                other.name()
+            )));
+        }
     }
     Ok(())
 }
@@ -1658,6 +2077,10 @@ pub enum ExportFormat {
     TXT,
     #[serde(rename = "docx")]
     DOCX,
+    #[serde(rename = "parquet")]
+    Parquet,
+    #[serde(rename = "xlsx")]
+    XLSX,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Validate, JsonSchema)]
@@ -1677,6 +2100,11 @@ pub struct TaskCache {
     pub enabled: bool,
     #[garde(length(min = 1))]
     pub path: String,
+    /// Seconds before the cached output of this task expires. For `execute_sql`
+    /// tasks this also overrides the TTL of the query result cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[garde(range(min = 1))]
+    pub ttl_secs: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Validate, JsonSchema, Hash)]
@@ -2581,6 +3009,14 @@ pub struct Workflow {
     /// This can be overridden per-task via AgentTask.consistency_prompt
     #[garde(custom(validate_consistency_prompt))]
     pub consistency_prompt: Option<String>,
+    /// Cron triggers for running this workflow from `oxy serve`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[garde(dive)]
+    pub schedule: Option<WorkflowSchedule>,
+    /// Token, cost and tool-call limits for each run of this workflow
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[garde(skip)]
+    pub budget: Option<RunBudget>,
 }
 
 fn default_is_verified() -> bool {
@@ -2633,15 +3069,48 @@ pub struct EmbeddingConfig {
     pub factor: usize,
 }
 
+/// Where a retrieval tool stores its embeddings. Selected by the `pgvector`
+/// or `qdrant` key on the tool; LanceDB is used when neither is set.
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
 #[serde(untagged)]
 pub enum VectorDBConfig {
+    // Untagged: the variants with a required key must come before LanceDB,
+    // which matches any tool.
+    PgVector {
+        pgvector: PgVectorConfig,
+    },
+    Qdrant {
+        qdrant: QdrantConfig,
+    },
     LanceDB {
         #[serde(default = "default_lance_db_path")]
         db_path: String,
     },
 }
 
+/// Store embeddings in Postgres with the pgvector extension. Each retrieval
+/// tool gets its own table, created on first use.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct PgVectorConfig {
+    /// Environment variable holding the Postgres connection string. Defaults
+    /// to Oxy's internal database.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection_string_var: Option<String>,
+}
+
+/// Store embeddings in a Qdrant collection, created on first use.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct QdrantConfig {
+    /// URL of the Qdrant REST API
+    #[serde(default = "default_qdrant_url")]
+    pub url: String,
+    /// Environment variable holding the Qdrant API key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_var: Option<String>,
+}
+
 impl Default for VectorDBConfig {
     fn default() -> Self {
         VectorDBConfig::LanceDB {
@@ -3709,6 +4178,10 @@ fn default_lance_db_path() -> String {
     ".lancedb".to_string()
 }
 
+fn default_qdrant_url() -> String {
+    "http://localhost:6333".to_string()
+}
+
 fn default_embed_model() -> String {
     "text-embedding-3-small".to_string()
 }
@@ -3877,6 +4350,14 @@ fn default_cache_enabled() -> bool {
     false
 }
 
+fn default_result_cache_enabled() -> bool {
+    true
+}
+
+fn default_result_cache_max_size_mb() -> u64 {
+    1024
+}
+
 fn default_scores() -> HashMap<String, f32> {
     HashMap::from_iter([("A".to_string(), 1.0), ("B".to_string(), 0.0)])
 }
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Limits for a single agent or workflow run. A run that goes over one is
+/// stopped with an error; output it streamed before that point is kept.
+///
+/// ```yaml
+/// budget:
+///   max_tokens: 200000
+///   max_cost_usd: 0.5
+///   max_tool_calls: 20
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RunBudget {
+    /// Input plus output tokens across every LLM call of the run, including
+    /// nested agents and workflows.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u64>,
+    /// Spend in USD, priced with `budgets.prices` from config.yml. A run with
+    /// this limit fails when it calls a model without a price.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cost_usd: Option<f64>,
+    /// Tool calls made by agents during the run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tool_calls: Option<u64>,
+}
+
+/// Workspace-wide spend settings in config.yml: model prices and caps on
+/// what the workspace and each user may spend per day and month.
+///
+/// ```yaml
+/// budgets:
+///   prices:
+///     gpt-4o: { input: 2.5, output: 10 }
+///   workspace:
+///     daily_usd: 50
+///     monthly_usd: 1000
+///   user:
+///     daily_tokens: 2000000
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BudgetsConfig {
+    /// USD per million tokens, keyed by the model name sent to the provider
+    /// (the `model_ref` of an entry in `models`).
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub prices: HashMap<String, ModelPrice>,
+    /// Caps on the spend of the whole workspace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<SpendCaps>,
+    /// Caps on the spend of each user, applied to every user separately.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<SpendCaps>,
+}
+
+impl BudgetsConfig {
+    /// Cost in USD of a call to `model`, or `None` when it has no price.
+    pub fn cost(&self, model: &str, input_tokens: u64, output_tokens: u64) -> Option<f64> {
+        self.prices
+            .get(model)
+            .map(|price| price.cost(input_tokens, output_tokens))
+    }
+}
+
+/// Price of a model in USD per million tokens.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+}
+
+impl ModelPrice {
+    pub fn cost(&self, input_tokens: u64, output_tokens: u64) -> f64 {
+        (input_tokens as f64 * self.input + output_tokens as f64 * self.output) / 1_000_000.0
+    }
+}
+
+/// Daily and monthly spend caps. Days and months are calendar periods in
+/// UTC. Once a cap is reached new runs are refused and running ones stop.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SpendCaps {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_usd: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly_usd: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_tokens: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly_tokens: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budgets_config_prices_calls() {
+        let config: BudgetsConfig = serde_yaml::from_str(
+            r#"
+prices:
+  gpt-4o: { input: 2.5, output: 10 }
+workspace:
+  daily_usd: 50
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.cost("gpt-4o", 1_000_000, 100_000), Some(3.5));
+        assert_eq!(config.cost("unknown", 1_000, 1_000), None);
+        assert_eq!(config.workspace.unwrap().daily_usd, Some(50.0));
+        assert!(config.user.is_none());
+    }
+}
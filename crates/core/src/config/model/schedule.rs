@@ -0,0 +1,221 @@
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use croner::Cron;
+use garde::Validate;
+use oxy_shared::errors::OxyError;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::validate::ValidationContext;
+
+/// A single cron expression or a list of them.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum CronExpressions {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl CronExpressions {
+    pub fn as_slice(&self) -> &[String] {
+        match self {
+            CronExpressions::Single(expression) => std::slice::from_ref(expression),
+            CronExpressions::Multiple(expressions) => expressions,
+        }
+    }
+}
+
+/// Cron triggers for a workflow, evaluated by the scheduler in `oxy serve`.
+///
+/// ```yaml
+/// schedule:
+///   cron: "0 6 * * 1-5"
+///   timezone: Europe/Berlin
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Validate, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[garde(context(ValidationContext))]
+pub struct WorkflowSchedule {
+    /// Five-field cron expression (`minute hour day-of-month month day-of-week`),
+    /// or a list of them. The workflow runs whenever any of them fires.
+    #[garde(custom(validate_cron_expressions))]
+    pub cron: CronExpressions,
+    /// IANA timezone the cron expressions are evaluated in, e.g. `America/New_York`.
+    #[serde(default = "default_schedule_timezone")]
+    #[garde(custom(validate_timezone))]
+    pub timezone: String,
+    #[serde(default = "default_schedule_enabled")]
+    #[garde(skip)]
+    pub enabled: bool,
+    /// Run once for the most recent slot missed while the server was down.
+    #[serde(default = "default_schedule_catch_up")]
+    #[garde(skip)]
+    pub catch_up: bool,
+}
+
+impl WorkflowSchedule {
+    pub fn tz(&self) -> Result<Tz, OxyError> {
+        parse_timezone(&self.timezone).map_err(OxyError::ConfigurationError)
+    }
+
+    fn crons(&self) -> Result<Vec<Cron>, OxyError> {
+        self.cron
+            .as_slice()
+            .iter()
+            .map(|expression| parse_cron(expression).map_err(OxyError::ConfigurationError))
+            .collect()
+    }
+
+    /// The first slot strictly after `after`, across all expressions.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Result<Option<DateTime<Utc>>, OxyError> {
+        let tz = self.tz()?;
+        let after = after.with_timezone(&tz);
+        Ok(self
+            .crons()?
+            .iter()
+            .filter_map(|cron| cron.find_next_occurrence(&after, false).ok())
+            .map(|slot| slot.with_timezone(&Utc))
+            .min())
+    }
+
+    /// The most recent slot in `(since, until]`, if any fired in that window.
+    pub fn latest_between(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Option<DateTime<Utc>>, OxyError> {
+        let mut latest = None;
+        let mut cursor = since;
+        while let Some(slot) = self.next_after(cursor)?
+            && slot <= until
+        {
+            latest = Some(slot);
+            cursor = slot;
+        }
+        Ok(latest)
+    }
+}
+
+fn parse_cron(expression: &str) -> Result<Cron, String> {
+    Cron::new(expression)
+        .parse()
+        .map_err(|e| format!("Invalid cron expression '{expression}': {e}"))
+}
+
+fn parse_timezone(timezone: &str) -> Result<Tz, String> {
+    timezone
+        .parse::<Tz>()
+        .map_err(|_| format!("Unknown timezone '{timezone}'"))
+}
+
+fn validate_cron_expressions(
+    expressions: &CronExpressions,
+    _context: &ValidationContext,
+) -> garde::Result {
+    if expressions.as_slice().is_empty() {
+        return Err(garde::Error::new(
+            "At least one cron expression is required",
+        ));
+    }
+    for expression in expressions.as_slice() {
+        parse_cron(expression).map_err(garde::Error::new)?;
+    }
+    Ok(())
+}
+
+fn validate_timezone(timezone: &str, _context: &ValidationContext) -> garde::Result {
+    parse_timezone(timezone)
+        .map(|_| ())
+        .map_err(garde::Error::new)
+}
+
+fn default_schedule_timezone() -> String {
+    "UTC".to_string()
+}
+
+fn default_schedule_enabled() -> bool {
+    true
+}
+
+fn default_schedule_catch_up() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn schedule(yaml: &str) -> WorkflowSchedule {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn defaults_to_utc_enabled_with_catch_up() {
+        let schedule = schedule("cron: '0 6 * * *'");
+        assert_eq!(schedule.timezone, "UTC");
+        assert!(schedule.enabled);
+        assert!(schedule.catch_up);
+        assert_eq!(schedule.cron.as_slice(), ["0 6 * * *"]);
+    }
+
+    #[test]
+    fn next_after_is_evaluated_in_the_schedule_timezone() {
+        let schedule = schedule("cron: '0 6 * * *'\ntimezone: America/New_York");
+        // 06:00 in New York is 10:00 UTC during daylight saving time
+        assert_eq!(
+            schedule.next_after(utc(2026, 7, 1, 0, 0)).unwrap(),
+            Some(utc(2026, 7, 1, 10, 0))
+        );
+        // and 11:00 UTC in winter
+        assert_eq!(
+            schedule.next_after(utc(2026, 1, 15, 12, 0)).unwrap(),
+            Some(utc(2026, 1, 16, 11, 0))
+        );
+    }
+
+    #[test]
+    fn next_after_picks_the_earliest_expression() {
+        let schedule = schedule("cron:\n  - '0 18 * * *'\n  - '30 9 * * *'");
+        assert_eq!(
+            schedule.next_after(utc(2026, 3, 2, 8, 0)).unwrap(),
+            Some(utc(2026, 3, 2, 9, 30))
+        );
+        assert_eq!(
+            schedule.next_after(utc(2026, 3, 2, 9, 30)).unwrap(),
+            Some(utc(2026, 3, 2, 18, 0))
+        );
+    }
+
+    #[test]
+    fn latest_between_returns_the_most_recent_missed_slot() {
+        let schedule = schedule("cron: '0 * * * *'");
+        assert_eq!(
+            schedule
+                .latest_between(utc(2026, 3, 2, 8, 0), utc(2026, 3, 2, 11, 45))
+                .unwrap(),
+            Some(utc(2026, 3, 2, 11, 0))
+        );
+        assert_eq!(
+            schedule
+                .latest_between(utc(2026, 3, 2, 8, 0), utc(2026, 3, 2, 8, 59))
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_expressions_and_timezones() {
+        assert!(parse_cron("not a cron").is_err());
+        assert!(parse_timezone("Mars/Olympus_Mons").is_err());
+        assert!(
+            schedule("cron: '0 6 * * *'\ntimezone: Nowhere")
+                .next_after(Utc::now())
+                .is_err()
+        );
+    }
+}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use serde::Deserialize;
 
-use super::{EvalConfig, RouteRetrievalConfig, Task};
+use super::{EvalConfig, RouteRetrievalConfig, Task, WorkflowSchedule};
 
 #[derive(Deserialize, Debug)]
 pub struct WorkflowWithRawVariables {
@@ -18,4 +18,5 @@ pub struct WorkflowWithRawVariables {
     pub description: String,
     pub retrieval: Option<RouteRetrievalConfig>,
     pub consistency_prompt: Option<String>,
+    pub schedule: Option<WorkflowSchedule>,
 }
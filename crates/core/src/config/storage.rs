@@ -246,6 +246,9 @@ impl ConfigStorage for LocalSource {
             slack_legacy: None,
             mcp: None,
             a2a: None,
+            result_cache: None,
+            budgets: None,
+            access_policies: Vec::new(),
             protected_branches: None,
             base_branch: None,
             repositories: vec![],
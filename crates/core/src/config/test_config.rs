@@ -49,6 +49,52 @@ pub struct TestCase {
     #[serde(default)]
     pub tags: Vec<String>,
     /// Optional: assert that this tool was invoked (deterministic check).
-    /// TODO: implement tool-use verification in the eval pipeline.
     pub tool: Option<String>,
+    /// Optional: assert that the generated SQL references these tables and columns.
+    pub sql: Option<SqlAssertion>,
+    /// Optional: assert that the last query returned this result set.
+    pub result: Option<ResultAssertion>,
+}
+
+/// Tables and columns the SQL generated during a run must reference.
+///
+/// Names match case-insensitively, and on their trailing parts: `orders`
+/// matches `analytics.orders`, `amount` matches `o.amount`.
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SqlAssertion {
+    #[serde(default)]
+    pub tables: Vec<String>,
+    #[serde(default)]
+    pub columns: Vec<String>,
+}
+
+/// Result set the last query of a run must return, from either a CSV file
+/// (with a header row) or a query. Columns are compared by position.
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ResultAssertion {
+    /// Path to a CSV file holding the expected rows, relative to the project.
+    pub csv: Option<String>,
+    /// Query returning the expected rows.
+    pub sql: Option<String>,
+    /// Database to run `sql` against. Defaults to the database the run queried.
+    pub database: Option<String>,
+    /// Largest absolute difference allowed between numeric values.
+    #[serde(default)]
+    pub tolerance: f64,
+    /// Whether rows must come back in the expected order.
+    #[serde(default)]
+    pub ordered: bool,
+}
+
+impl ResultAssertion {
+    pub fn validate(&self) -> Result<(), String> {
+        match (&self.csv, &self.sql) {
+            (Some(_), Some(_)) => Err("set either 'csv' or 'sql', not both".to_string()),
+            (None, None) => Err("set 'csv' or 'sql'".to_string()),
+            _ if self.tolerance < 0.0 => Err("'tolerance' cannot be negative".to_string()),
+            _ => Ok(()),
+        }
+    }
 }
@@ -175,38 +175,37 @@ pub fn validate_looker_integration_exists(
     }
 }
 
+/// Export formats for tasks whose output is a table
+const TABLE_EXPORT_FORMATS: &[ExportFormat] = &[
+    ExportFormat::JSON,
+    ExportFormat::CSV,
+    ExportFormat::SQL,
+    ExportFormat::Parquet,
+    ExportFormat::XLSX,
+];
+
 pub fn validate_task(task_type: &TaskType, _context: &ValidationContext) -> garde::Result {
     match task_type {
-        TaskType::Agent(task) => validate_export(
-            task.export.as_ref(),
-            &[ExportFormat::JSON, ExportFormat::CSV, ExportFormat::SQL],
-            "agent",
-        ),
-        TaskType::ExecuteSQL(task) => validate_export(
-            task.export.as_ref(),
-            &[ExportFormat::JSON, ExportFormat::CSV, ExportFormat::SQL],
-            "ExecuteSQL",
-        ),
+        TaskType::Agent(task) => {
+            validate_export(task.export.as_ref(), TABLE_EXPORT_FORMATS, "agent")
+        }
+        TaskType::ExecuteSQL(task) => {
+            validate_export(task.export.as_ref(), TABLE_EXPORT_FORMATS, "ExecuteSQL")
+        }
         TaskType::Formatter(task) => validate_export(
             task.export.as_ref(),
             &[ExportFormat::TXT, ExportFormat::DOCX],
             "Formatter",
         ),
-        TaskType::SemanticQuery(task) => validate_export(
-            task.export.as_ref(),
-            &[ExportFormat::JSON, ExportFormat::CSV, ExportFormat::SQL],
-            "SemanticQuery",
-        ),
-        TaskType::OmniQuery(task) => validate_export(
-            task.export.as_ref(),
-            &[ExportFormat::JSON, ExportFormat::CSV, ExportFormat::SQL],
-            "OmniQuery",
-        ),
-        TaskType::LookerQuery(task) => validate_export(
-            task.export.as_ref(),
-            &[ExportFormat::JSON, ExportFormat::CSV, ExportFormat::SQL],
-            "LookerQuery",
-        ),
+        TaskType::SemanticQuery(task) => {
+            validate_export(task.export.as_ref(), TABLE_EXPORT_FORMATS, "SemanticQuery")
+        }
+        TaskType::OmniQuery(task) => {
+            validate_export(task.export.as_ref(), TABLE_EXPORT_FORMATS, "OmniQuery")
+        }
+        TaskType::LookerQuery(task) => {
+            validate_export(task.export.as_ref(), TABLE_EXPORT_FORMATS, "LookerQuery")
+        }
         TaskType::Workflow(_)
         | TaskType::LoopSequential(_)
         | TaskType::Visualize(_)
@@ -328,6 +327,9 @@ mod tests {
                 slack_legacy: None,
                 mcp: None,
                 a2a: None,
+                result_cache: None,
+                budgets: None,
+                access_policies: Vec::new(),
                 protected_branches: None,
                 base_branch: None,
                 admins: vec![],
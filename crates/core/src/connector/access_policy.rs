@@ -0,0 +1,552 @@
+//! Oxy-enforced row-level security.
+//!
+//! Every query bound for a database with [`AccessPolicy`]s is parsed and each
+//! reference to a protected table is replaced by a filtered subquery:
+//!
+//! ```sql
+//! SELECT * FROM orders o
+//! -- becomes
+//! SELECT * FROM (SELECT * FROM orders WHERE (region = 'emea')) AS o
+//! ```
+//!
+//! Enforcement fails closed: SQL that cannot be parsed, statements other than
+//! queries that touch a protected table, and predicates whose placeholders
+//! have no value for the current request are all rejected.
+
+use std::ops::ControlFlow;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+use sqlparser::{
+    ast::{SetExpr, Statement, TableFactor, VisitMut, VisitorMut, visit_relations},
+    dialect::{Dialect, GenericDialect, dialect_from_str},
+    parser::Parser,
+};
+use uuid::Uuid;
+
+use crate::{adapters::session_filters::SessionFilters, config::model::AccessPolicy};
+use oxy_shared::errors::OxyError;
+
+static PLACEHOLDER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\.([A-Za-z_][A-Za-z0-9_]*)\s*\}\}")
+        .expect("Placeholder regex pattern should be valid")
+});
+
+/// Rewrites SQL so it only sees rows the current request may access
+#[derive(Debug, Clone, Default)]
+pub struct AccessPolicyEnforcer {
+    dialect: String,
+    policies: Vec<AccessPolicy>,
+    filters: Option<SessionFilters>,
+    user_id: Option<Uuid>,
+}
+
+impl AccessPolicyEnforcer {
+    pub fn new(dialect: impl Into<String>, policies: Vec<AccessPolicy>) -> Self {
+        Self {
+            dialect: dialect.into(),
+            policies,
+            filters: None,
+            user_id: None,
+        }
+    }
+
+    pub fn with_filters(mut self, filters: Option<SessionFilters>) -> Self {
+        self.filters = filters;
+        self
+    }
+
+    pub fn with_user_id(mut self, user_id: Option<Uuid>) -> Self {
+        self.user_id = user_id;
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.policies.is_empty()
+    }
+
+    /// Whether any policy covers `table`
+    pub fn protects(&self, table: &str) -> bool {
+        !self.matching_policies(table).is_empty()
+    }
+
+    /// Apply the policies to `sql`. Queries that touch no protected table
+    /// are returned unchanged.
+    pub fn enforce(&self, sql: &str) -> Result<String, OxyError> {
+        if self.policies.is_empty() {
+            return Ok(sql.to_string());
+        }
+        let dialect = self.sql_dialect();
+        let mut statements = Parser::parse_sql(dialect.as_ref(), sql).map_err(|e| {
+            OxyError::AuthorizationError(format!(
+                "Query rejected: access policies apply to this database but the query could not be parsed to enforce them: {e}"
+            ))
+        })?;
+
+        let mut rewriter = PolicyRewriter {
+            enforcer: self,
+            dialect: dialect.as_ref(),
+            rewritten: 0,
+        };
+        for statement in statements.iter_mut() {
+            if matches!(statement, Statement::Query(_)) {
+                if let ControlFlow::Break(err) = statement.visit(&mut rewriter) {
+                    return Err(err);
+                }
+                continue;
+            }
+            // Only reads can be narrowed with a subquery; anything else that
+            // touches a protected table is refused outright.
+            let protected = visit_relations(&*statement, |relation| {
+                match self.matching_policies(&relation.to_string()).first() {
+                    Some(policy) => ControlFlow::Break((relation.to_string(), policy.name.clone())),
+                    None => ControlFlow::Continue(()),
+                }
+            });
+            if let ControlFlow::Break((table, policy)) = protected {
+                return Err(OxyError::AuthorizationError(format!(
+                    "Query rejected: table '{table}' is protected by access policy '{policy}' and only SELECT queries can be rewritten to enforce it"
+                )));
+            }
+        }
+
+        if rewriter.rewritten == 0 {
+            return Ok(sql.to_string());
+        }
+        Ok(statements
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(";\n"))
+    }
+
+    fn sql_dialect(&self) -> Box<dyn Dialect> {
+        dialect_from_str(&self.dialect).unwrap_or_else(|| Box::new(GenericDialect {}))
+    }
+
+    fn matching_policies(&self, table: &str) -> Vec<&AccessPolicy> {
+        let parts = name_parts(table);
+        self.policies
+            .iter()
+            .filter(|policy| {
+                policy
+                    .tables
+                    .iter()
+                    .any(|protected| names_match(&name_parts(protected), &parts))
+            })
+            .collect()
+    }
+
+    /// Substitute the request's values into the policy predicate
+    fn render_predicate(&self, policy: &AccessPolicy) -> Result<String, OxyError> {
+        let mut missing = None;
+        let rendered = PLACEHOLDER.replace_all(&policy.predicate, |caps: &regex::Captures| {
+            let value = match (&caps[1], &caps[2]) {
+                ("filters", name) => self
+                    .filters
+                    .as_ref()
+                    .and_then(|filters| filters.get(name))
+                    .map(|value| self.literal(value)),
+                ("user", "id") => self
+                    .user_id
+                    .map(|id| Ok(self.string_literal(&id.to_string()))),
+                _ => None,
+            };
+            match value {
+                Some(Ok(literal)) => literal,
+                Some(Err(err)) => {
+                    missing.get_or_insert(err);
+                    String::new()
+                }
+                None => {
+                    missing.get_or_insert(OxyError::AuthorizationError(format!(
+                        "Query rejected: access policy '{}' needs `{}` but it is not set for this request",
+                        policy.name, &caps[0]
+                    )));
+                    String::new()
+                }
+            }
+        });
+        if let Some(err) = missing {
+            return Err(err);
+        }
+        if rendered.contains("{{") {
+            return Err(OxyError::ConfigurationError(format!(
+                "Access policy '{}' has an unsupported placeholder in its predicate; use {{{{ filters.<name> }}}} or {{{{ user.id }}}}",
+                policy.name
+            )));
+        }
+        Ok(format!("({rendered})"))
+    }
+
+    fn literal(&self, value: &Value) -> Result<String, OxyError> {
+        match value {
+            Value::Null => Ok("NULL".to_string()),
+            Value::Bool(b) => Ok(if *b { "TRUE" } else { "FALSE" }.to_string()),
+            Value::Number(n) => Ok(n.to_string()),
+            Value::String(s) => Ok(self.string_literal(s)),
+            Value::Array(items) if items.is_empty() => Ok("(NULL)".to_string()),
+            Value::Array(items) => {
+                let literals = items
+                    .iter()
+                    .map(|item| match item {
+                        Value::Array(_) | Value::Object(_) => Err(OxyError::AuthorizationError(
+                            "Query rejected: nested values cannot be bound into an access policy"
+                                .to_string(),
+                        )),
+                        item => self.literal(item),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(format!("({})", literals.join(", ")))
+            }
+            Value::Object(_) => Err(OxyError::AuthorizationError(
+                "Query rejected: objects cannot be bound into an access policy".to_string(),
+            )),
+        }
+    }
+
+    fn string_literal(&self, value: &str) -> String {
+        let mut escaped = value.replace('\'', "''");
+        // These dialects treat backslash as an escape inside string literals
+        if matches!(self.dialect.as_str(), "mysql" | "bigquery" | "clickhouse") {
+            escaped = escaped.replace('\\', "\\\\");
+        }
+        format!("'{escaped}'")
+    }
+}
+
+struct PolicyRewriter<'a> {
+    enforcer: &'a AccessPolicyEnforcer,
+    dialect: &'a dyn Dialect,
+    rewritten: usize,
+}
+
+impl PolicyRewriter<'_> {
+    fn filtered_relation(
+        &self,
+        table: &str,
+        alias: &str,
+        predicate: &str,
+    ) -> Result<TableFactor, OxyError> {
+        let wrapped = format!("SELECT * FROM (SELECT * FROM {table} WHERE {predicate}) AS {alias}");
+        let invalid = |reason: String| {
+            OxyError::ConfigurationError(format!(
+                "Access policy predicate for table '{table}' is not valid SQL: {reason}"
+            ))
+        };
+        let mut statements =
+            Parser::parse_sql(self.dialect, &wrapped).map_err(|e| invalid(e.to_string()))?;
+        if let Some(Statement::Query(query)) = statements.pop()
+            && let SetExpr::Select(select) = *query.body
+            && let Some(from) = select.from.into_iter().next()
+        {
+            return Ok(from.relation);
+        }
+        Err(invalid("it changes the shape of the query".to_string()))
+    }
+}
+
+impl VisitorMut for PolicyRewriter<'_> {
+    type Break = OxyError;
+
+    // Post-visit so the subquery we splice in is not visited (and wrapped) again
+    fn post_visit_table_factor(
+        &mut self,
+        table_factor: &mut TableFactor,
+    ) -> ControlFlow<Self::Break> {
+        let TableFactor::Table {
+            name,
+            alias,
+            args: None,
+            ..
+        } = table_factor
+        else {
+            return ControlFlow::Continue(());
+        };
+        let table = name.to_string();
+        let policies = self.enforcer.matching_policies(&table);
+        if policies.is_empty() {
+            return ControlFlow::Continue(());
+        }
+
+        let predicate = match policies
+            .iter()
+            .map(|policy| self.enforcer.render_predicate(policy))
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(predicates) => predicates.join(" AND "),
+            Err(err) => return ControlFlow::Break(err),
+        };
+        // Keep the name columns are qualified with: the explicit alias, or
+        // the bare table name
+        let alias = match alias {
+            Some(alias) => {
+                let alias = alias.to_string();
+                match alias.get(..3) {
+                    Some(keyword) if keyword.eq_ignore_ascii_case("as ") => alias[3..].to_string(),
+                    _ => alias,
+                }
+            }
+            None => default_alias(&table),
+        };
+        match self.filtered_relation(&table, &alias, &predicate) {
+            Ok(relation) => {
+                *table_factor = relation;
+                self.rewritten += 1;
+                ControlFlow::Continue(())
+            }
+            Err(err) => ControlFlow::Break(err),
+        }
+    }
+}
+
+/// Split a possibly quoted, dotted table name into its segments as written
+fn raw_segments(name: &str) -> Vec<&str> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut quote = None;
+    for (i, c) in name.char_indices() {
+        match (quote, c) {
+            (None, '"' | '`') => quote = Some(c),
+            (None, '[') => quote = Some(']'),
+            (Some(q), c) if c == q => quote = None,
+            (None, '.') => {
+                segments.push(&name[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    segments.push(&name[start..]);
+    segments
+}
+
+/// Normalised name segments used for matching.
+///
+/// Names are split on dots outside quotes, so `"my.schema"."t"` is the two
+/// segments `my.schema` and `t`. Backtick-quoted segments are the exception:
+/// BigQuery writes a whole path as `` `project.dataset.table` ``, so their
+/// dots still separate segments.
+pub fn name_parts(name: &str) -> Vec<String> {
+    raw_segments(name)
+        .into_iter()
+        .flat_map(|segment| {
+            let segment = segment.trim();
+            let quoted = |open: char, close: char| segment.strip_prefix(open)?.strip_suffix(close);
+            if let Some(path) = quoted('`', '`') {
+                return path.split('.').map(str::to_lowercase).collect();
+            }
+            let ident = match quoted('"', '"') {
+                Some(ident) => ident.replace("\"\"", "\""),
+                None => quoted('[', ']').unwrap_or(segment).to_string(),
+            };
+            vec![ident.to_lowercase()]
+        })
+        .collect()
+}
+
+/// `orders` matches `public.orders` and vice versa: an unqualified name may
+/// resolve to any schema, so the shorter name only has to be a suffix.
+pub fn names_match(a: &[String], b: &[String]) -> bool {
+    let len = a.len().min(b.len());
+    len > 0 && a[a.len() - len..] == b[b.len() - len..]
+}
+
+fn default_alias(table: &str) -> String {
+    let last = raw_segments(table).pop().unwrap_or(table).trim();
+    match last.chars().next() {
+        Some(quote @ ('"' | '`')) if last.contains('.') => {
+            let inner = last.trim_matches(quote);
+            let bare = inner.rsplit('.').next().unwrap_or(inner);
+            format!("{quote}{bare}{quote}")
+        }
+        _ => last.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn policy(name: &str, tables: &[&str], predicate: &str) -> AccessPolicy {
+        AccessPolicy {
+            name: name.to_string(),
+            database: None,
+            tables: tables.iter().map(|t| t.to_string()).collect(),
+            predicate: predicate.to_string(),
+        }
+    }
+
+    fn filters(values: Value) -> Option<SessionFilters> {
+        let map: HashMap<String, Value> = serde_json::from_value(values).unwrap();
+        Some(SessionFilters::from(map))
+    }
+
+    fn region_enforcer(dialect: &str) -> AccessPolicyEnforcer {
+        AccessPolicyEnforcer::new(
+            dialect,
+            vec![policy(
+                "region",
+                &["orders"],
+                "region = {{ filters.region }}",
+            )],
+        )
+        .with_filters(filters(json!({"region": "emea"})))
+    }
+
+    #[test]
+    fn test_no_policies_passes_sql_through() {
+        let enforcer = AccessPolicyEnforcer::new("postgres", vec![]);
+        let sql = "this is not sql";
+        assert_eq!(enforcer.enforce(sql).unwrap(), sql);
+    }
+
+    #[test]
+    fn test_unprotected_query_is_unchanged() {
+        let sql = "select id from customers";
+        assert_eq!(region_enforcer("postgres").enforce(sql).unwrap(), sql);
+    }
+
+    #[test]
+    fn test_wraps_protected_table_keeping_alias() {
+        let rewritten = region_enforcer("postgres")
+            .enforce("SELECT o.id FROM orders AS o WHERE o.amount > 10")
+            .unwrap();
+        assert_eq!(
+            rewritten,
+            "SELECT o.id FROM (SELECT * FROM orders WHERE (region = 'emea')) AS o WHERE o.amount > 10"
+        );
+    }
+
+    #[test]
+    fn test_unaliased_table_is_aliased_to_its_name() {
+        let rewritten = region_enforcer("postgres")
+            .enforce("SELECT orders.id FROM public.orders")
+            .unwrap();
+        assert_eq!(
+            rewritten,
+            "SELECT orders.id FROM (SELECT * FROM public.orders WHERE (region = 'emea')) AS orders"
+        );
+    }
+
+    #[test]
+    fn test_wraps_joins_subqueries_and_ctes() {
+        let rewritten = region_enforcer("duckdb")
+            .enforce(
+                "WITH recent AS (SELECT * FROM orders WHERE day > '2024-01-01') \
+                 SELECT c.name FROM customers c JOIN recent r ON r.customer_id = c.id \
+                 WHERE c.id IN (SELECT customer_id FROM orders)",
+            )
+            .unwrap();
+        assert_eq!(rewritten.matches("WHERE (region = 'emea')").count(), 2);
+        assert!(!rewritten.contains("FROM customers WHERE (region"));
+    }
+
+    #[test]
+    fn test_escapes_string_values() {
+        let enforcer = AccessPolicyEnforcer::new(
+            "postgres",
+            vec![policy(
+                "region",
+                &["orders"],
+                "region = {{ filters.region }}",
+            )],
+        )
+        .with_filters(filters(json!({"region": "x' OR '1'='1"})));
+        let rewritten = enforcer.enforce("SELECT * FROM orders").unwrap();
+        assert!(rewritten.contains("(region = 'x'' OR ''1''=''1')"));
+    }
+
+    #[test]
+    fn test_binds_arrays_and_user_id() {
+        let user_id = Uuid::nil();
+        let enforcer = AccessPolicyEnforcer::new(
+            "bigquery",
+            vec![policy(
+                "owner",
+                &["sales.orders"],
+                "region IN {{ filters.regions }} OR owner_id = {{user.id}}",
+            )],
+        )
+        .with_filters(filters(json!({"regions": ["emea", "apac"]})))
+        .with_user_id(Some(user_id));
+        let rewritten = enforcer
+            .enforce("SELECT * FROM `project.sales.orders`")
+            .unwrap();
+        assert!(rewritten.contains(&format!(
+            "WHERE (region IN ('emea', 'apac') OR owner_id = '{user_id}')) AS `orders`"
+        )));
+    }
+
+    #[test]
+    fn test_missing_binding_is_rejected() {
+        let enforcer = AccessPolicyEnforcer::new(
+            "postgres",
+            vec![policy(
+                "region",
+                &["orders"],
+                "region = {{ filters.region }}",
+            )],
+        );
+        let err = enforcer.enforce("SELECT * FROM orders").unwrap_err();
+        assert!(matches!(err, OxyError::AuthorizationError(_)));
+        assert!(err.to_string().contains("filters.region"));
+    }
+
+    #[test]
+    fn test_unparseable_sql_is_rejected() {
+        let err = region_enforcer("postgres")
+            .enforce("SELEC * FORM orders")
+            .unwrap_err();
+        assert!(matches!(err, OxyError::AuthorizationError(_)));
+    }
+
+    #[test]
+    fn test_writes_to_protected_tables_are_rejected() {
+        let enforcer = region_enforcer("postgres");
+        assert!(enforcer.enforce("DELETE FROM orders").is_err());
+        assert!(
+            enforcer
+                .enforce("INSERT INTO archive SELECT * FROM orders")
+                .is_err()
+        );
+        assert!(enforcer.enforce("DELETE FROM customers").is_ok());
+    }
+
+    #[test]
+    fn test_names_match_on_suffix() {
+        let parts = |s: &str| name_parts(s);
+        assert!(names_match(&parts("orders"), &parts("public.orders")));
+        assert!(names_match(&parts("PUBLIC.\"Orders\""), &parts("orders")));
+        assert!(!names_match(
+            &parts("public.orders"),
+            &parts("audit.orders")
+        ));
+        assert!(!names_match(&parts("orders"), &parts("orders_archive")));
+    }
+
+    #[test]
+    fn test_name_parts_respects_quoted_dots() {
+        assert_eq!(name_parts("\"my.schema\".\"T\""), vec!["my.schema", "t"]);
+        assert_eq!(
+            name_parts("[my.schema].orders"),
+            vec!["my.schema", "orders"]
+        );
+        assert_eq!(name_parts("\"a\"\"b\".c"), vec!["a\"b", "c"]);
+        assert_eq!(
+            name_parts("`proj.sales.Orders`"),
+            vec!["proj", "sales", "orders"]
+        );
+        assert!(!names_match(
+            &name_parts("\"my.schema\".\"t\""),
+            &name_parts("schema.t")
+        ));
+        assert!(names_match(
+            &name_parts("`proj.sales.orders`"),
+            &name_parts("orders")
+        ));
+    }
+}
@@ -1,6 +1,9 @@
 use std::sync::Arc;
 
-use arrow::{array::RecordBatch, datatypes::SchemaRef};
+use arrow::{
+    array::{Array, AsArray, RecordBatch},
+    datatypes::{DataType, SchemaRef},
+};
 use bigquery::BigQuerySource;
 use bigquery_transport::BigQueryArrowTransport;
 use connectorx::prelude::{ArrowDestination, CXQuery, Dispatcher, SourceConn, get_arrow};
@@ -12,7 +15,7 @@ use oxy_shared::errors::OxyError;
 use super::{
     constants::{
         BIGQUERY_DIALECT, CREATE_CONN, EXECUTE_QUERY, FAILED_TO_RUN_BLOCKING_TASK,
-        LOAD_ARROW_RESULT, WRITE_RESULT,
+        LOAD_ARROW_RESULT, MSSQL_DIALECT, TRINO_DIALECT, TRINO_HTTPS_DIALECT, WRITE_RESULT,
     },
     engine::Engine,
     utils::{connector_internal_error, write_to_ipc},
@@ -66,6 +69,32 @@ impl Engine for ConnectorX {
     async fn explain_query(&self, query: &str) -> Result<(Vec<RecordBatch>, SchemaRef), OxyError> {
         match self.dialect.as_str() {
             BIGQUERY_DIALECT => Box::pin(self.dry_run(query)).await, // We're forming the loop here because the dialect is bigquery
+            MSSQL_DIALECT => {
+                // T-SQL has no `EXPLAIN`, and `SET SHOWPLAN_*` must be the only
+                // statement in its batch. `sys.dm_exec_describe_first_result_set`
+                // compiles the query without running it, so CTEs and a bare
+                // `ORDER BY` validate too. It reports failures as a row rather
+                // than raising; the aggregate always yields one row to inspect.
+                let explain_query = format!(
+                    "SELECT COUNT(*) AS validated, MAX(error_message) AS error_message \
+                     FROM sys.dm_exec_describe_first_result_set(N'{}', NULL, 0)",
+                    query.trim().trim_end_matches(';').replace('\'', "''")
+                );
+                let (batches, schema) = self.run_query_with_limit(&explain_query, None).await?;
+                if let Some(message) = describe_error(&batches) {
+                    return Err(OxyError::DBError(message));
+                }
+                Ok((batches, schema))
+            }
+            TRINO_DIALECT | TRINO_HTTPS_DIALECT => {
+                // `EXPLAIN (` would be parsed as an option list, so the query
+                // is not parenthesised. VALIDATE plans without executing.
+                let explain_query = format!(
+                    "EXPLAIN (TYPE VALIDATE) {}",
+                    query.trim().trim_end_matches(';')
+                );
+                self.run_query_with_limit(&explain_query, None).await
+            }
             _ => {
                 let explain_query = format!("EXPLAIN ({})", query.trim().trim_end_matches(';'));
                 self.run_query_with_limit(&explain_query, None).await
@@ -137,3 +166,16 @@ impl Engine for ConnectorX {
         Ok((result, schema))
     }
 }
+
+/// The first non-null `error_message` of an MSSQL describe-result probe.
+fn describe_error(batches: &[RecordBatch]) -> Option<String> {
+    batches.iter().find_map(|batch| {
+        // connectorx may hand NVARCHAR back as Utf8 or LargeUtf8.
+        let column =
+            arrow::compute::cast(batch.column_by_name("error_message")?, &DataType::Utf8).ok()?;
+        let messages = column.as_string::<i32>();
+        (0..messages.len())
+            .find(|&i| messages.is_valid(i))
+            .map(|i| messages.value(i).to_string())
+    })
+}
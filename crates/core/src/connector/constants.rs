@@ -1,4 +1,7 @@
 pub(super) const BIGQUERY_DIALECT: &str = "bigquery";
+pub(super) const MSSQL_DIALECT: &str = "mssql";
+pub(super) const TRINO_DIALECT: &str = "trino";
+pub(super) const TRINO_HTTPS_DIALECT: &str = "trino+https";
 pub(super) const CREATE_CONN: &str = "Failed to open connection";
 pub(super) const CREATE_TEMP_TABLE: &str = "Failed to create temporary table";
 pub(super) const EXECUTE_QUERY: &str = "Failed to execute query";
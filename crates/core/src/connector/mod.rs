@@ -6,14 +6,19 @@ pub use domo::DOMO;
 use duckdb::DuckDB;
 use engine::Engine;
 use motherduck::MotherDuck;
+use serde_json::json;
 use snowflake::Snowflake;
+use sqlite::Sqlite;
 use std::collections::HashMap;
+use uuid::Uuid;
 
 use crate::{
     adapters::{
+        replay::{CONNECTOR_FIXTURES, FixtureStore, ReplayMode, missing_fixture, read_fixture},
         secrets::SecretsManager,
         session_filters::{FilterProcessor, SessionFilters},
     },
+    audit::{self, AuditAction, AuditEvent},
     config::{
         ConfigManager,
         model::{ConnectionOverride, ConnectionOverrides, Database, DatabaseType, DuckDBOptions},
@@ -22,6 +27,7 @@ use crate::{
 use airhouse::resolve_managed_airhouse_credentials;
 use oxy_shared::errors::OxyError;
 
+mod access_policy;
 mod clickhouse;
 pub mod connection_string;
 mod connectorx;
@@ -33,12 +39,15 @@ mod duckdb_pool;
 mod engine;
 mod motherduck;
 mod snowflake;
+mod sqlite;
 mod utils;
 
+pub use access_policy::{AccessPolicyEnforcer, name_parts, names_match};
 pub use connection_string::{
     ConnectionStringError, ConnectionStringFormatter, ConnectionStringParser,
     PostgresConnectionString,
 };
+use utils::connector_internal_error;
 pub use utils::{load_result, write_to_ipc};
 
 #[enum_dispatch::enum_dispatch(Engine)]
@@ -50,11 +59,18 @@ enum EngineType {
     Snowflake,
     DOMO,
     MotherDuck,
+    Sqlite,
 }
 
 #[derive(Debug)]
 pub struct Connector {
     engine: EngineType,
+    access_policies: AccessPolicyEnforcer,
+    database: String,
+    replay: Option<FixtureStore>,
+    user_id: Option<Uuid>,
+    /// Workspace that `query.executed` audit entries are recorded in
+    audit_workspace: Option<Uuid>,
 }
 
 impl Connector {
@@ -88,6 +104,11 @@ impl Connector {
         connections: Option<ConnectionOverride>,
         sso_url_sender: Option<tokio::sync::mpsc::Sender<String>>,
     ) -> Result<Self, OxyError> {
+        let access_policies = AccessPolicyEnforcer::new(
+            database.dialect(),
+            config_manager.access_policies(&database.name)?,
+        )
+        .with_filters(filters.clone());
         let engine = match &database.database_type {
             DatabaseType::Bigquery(bigquery) => {
                 let key_path_str = bigquery.get_key_path(secrets_manager).await?;
@@ -125,6 +146,18 @@ impl Connector {
                     secrets_manager.clone(),
                 )),
             },
+            DatabaseType::Sqlite(sqlite) => {
+                let files = config_manager
+                    .resolve_glob(&vec![sqlite.path.clone()])
+                    .await?;
+                if files.is_empty() {
+                    return Err(OxyError::ConfigurationError(format!(
+                        "SQLite path '{}' for database '{}' did not match any file",
+                        sqlite.path, database.name
+                    )));
+                }
+                EngineType::Sqlite(Sqlite::new(files, sqlite.read_only))
+            }
             DatabaseType::Postgres(pg) => {
                 let db_path = format!(
                     "{}:{}@{}:{}/{}",
@@ -193,6 +226,42 @@ impl Connector {
                 );
                 EngineType::ConnectorX(ConnectorX::new(database.dialect(), db_path, None))
             }
+            DatabaseType::Mssql(ms) => {
+                // ConnectorX's TDS source reads TLS behaviour from the query
+                // string rather than from a config struct. Azure SQL refuses
+                // logins without `encrypt=true`.
+                let db_path = format!(
+                    "{}:{}@{}:{}/{}?encrypt={}&trust_server_certificate={}",
+                    urlencoding::encode(&ms.get_user(secrets_manager).await?),
+                    urlencoding::encode(&ms.get_password(secrets_manager).await?),
+                    ms.get_host(secrets_manager).await?,
+                    ms.get_port(secrets_manager).await?,
+                    ms.get_database(secrets_manager).await?,
+                    ms.encrypt,
+                    ms.trust_server_certificate,
+                );
+                EngineType::ConnectorX(ConnectorX::new(database.dialect(), db_path, None))
+            }
+            DatabaseType::Trino(tr) => {
+                // ConnectorX picks TLS from the URL scheme and the default
+                // catalog from the path. Trino refuses passwords over plain
+                // HTTP, so only send credentials when one is configured.
+                let user = urlencoding::encode(&tr.get_user(secrets_manager).await?).into_owned();
+                let password = tr.get_password(secrets_manager).await?;
+                let credentials = if password.is_empty() {
+                    user
+                } else {
+                    format!("{user}:{}", urlencoding::encode(&password))
+                };
+                let db_path = format!(
+                    "{credentials}@{}:{}/{}",
+                    tr.get_host(secrets_manager).await?,
+                    tr.get_port(secrets_manager).await?,
+                    tr.catalog,
+                );
+                let scheme = if tr.https { "trino+https" } else { "trino" };
+                EngineType::ConnectorX(ConnectorX::new(scheme.to_string(), db_path, None))
+            }
             DatabaseType::ClickHouse(ch) => {
                 let validated_filters = Self::validate_filters(&ch.filters, filters)?;
 
@@ -230,11 +299,59 @@ impl Connector {
                 MotherDuck::from_config(secrets_manager.clone(), motherduck.clone()).await?,
             ),
         };
-        Ok(Connector { engine })
+        Ok(Connector {
+            engine,
+            access_policies,
+            database: database.name.clone(),
+            replay: FixtureStore::from_env(),
+            user_id: None,
+            audit_workspace: None,
+        })
+    }
+
+    /// Bind the requesting user for `{{ user.id }}` in access policies
+    pub fn with_user_id(mut self, user_id: Option<Uuid>) -> Self {
+        self.access_policies = self.access_policies.with_user_id(user_id);
+        self.user_id = user_id;
+        self
+    }
+
+    /// Record a `query.executed` audit entry in `workspace_id` for every
+    /// query this connector runs, attributed to the bound user. Dry runs and
+    /// `EXPLAIN`s are not recorded.
+    pub fn audited(mut self, workspace_id: Uuid) -> Self {
+        self.audit_workspace = Some(workspace_id);
+        self
+    }
+
+    /// The SQL that will actually run for `query` once access policies are
+    /// applied. Anything keyed on query text (e.g. the result cache) should
+    /// use this so results are never shared across policy bindings.
+    pub fn enforce_access_policies(&self, query: &str) -> Result<String, OxyError> {
+        self.access_policies.enforce(query)
     }
 
     pub async fn run_query(&self, query: &str) -> Result<String, OxyError> {
-        self.engine.run_query(query).await
+        let result = async {
+            let query = self.access_policies.enforce(query)?;
+            if self.replay.is_none() {
+                return self.engine.run_query(&query).await;
+            }
+            let (record_batches, schema_ref) = self
+                .replayed(
+                    "run_query",
+                    &query,
+                    None,
+                    self.engine.run_query_and_load(&query),
+                )
+                .await?;
+            let file_path = format!("/tmp/{}.arrow", Uuid::new_v4());
+            write_to_ipc(&record_batches, &file_path, &schema_ref)
+                .map_err(|err| connector_internal_error(constants::WRITE_RESULT, err.as_ref()))?;
+            Ok(file_path)
+        }
+        .await;
+        self.audit(query, result).await
     }
 
     pub async fn run_query_with_limit(
@@ -242,25 +359,140 @@ impl Connector {
         query: &str,
         dry_run_limit: Option<u64>,
     ) -> Result<(Vec<RecordBatch>, SchemaRef), OxyError> {
-        self.engine.run_query_with_limit(query, dry_run_limit).await
+        let result = async {
+            let query = self.access_policies.enforce(query)?;
+            self.replayed(
+                "run_query_with_limit",
+                &query,
+                dry_run_limit,
+                self.engine.run_query_with_limit(&query, dry_run_limit),
+            )
+            .await
+        }
+        .await;
+        self.audit(query, result).await
     }
 
     pub async fn run_query_and_load(
         &self,
         query: &str,
     ) -> Result<(Vec<RecordBatch>, SchemaRef), OxyError> {
-        self.engine.run_query_and_load(query).await
+        let result = async {
+            let query = self.access_policies.enforce(query)?;
+            self.replayed(
+                "run_query_and_load",
+                &query,
+                None,
+                self.engine.run_query_and_load(&query),
+            )
+            .await
+        }
+        .await;
+        self.audit(query, result).await
+    }
+
+    /// Record `query` in the audit log when the connector is
+    /// [`audited`](Self::audited). The SQL is hashed as written, before
+    /// access policies rewrite it.
+    async fn audit<T>(&self, query: &str, result: Result<T, OxyError>) -> Result<T, OxyError> {
+        if let Some(event) = self.audit_event(query) {
+            audit::record(event.meta("success", result.is_ok())).await;
+        }
+        result
+    }
+
+    /// Record `query` in the audit log when its result was served from the
+    /// result cache instead of running it, marked `cached`.
+    pub async fn audit_cached(&self, query: &str) {
+        if let Some(event) = self.audit_event(query) {
+            audit::record(event.meta("success", true).meta("cached", true)).await;
+        }
+    }
+
+    fn audit_event(&self, query: &str) -> Option<AuditEvent> {
+        let workspace_id = self.audit_workspace?;
+        Some(
+            AuditEvent::new(AuditAction::QueryExecuted)
+                .workspace(workspace_id)
+                .actor(self.user_id)
+                .target("database", &self.database)
+                .meta("sql_sha256", audit::sql_hash(query)),
+        )
     }
 
     pub async fn explain_query(
         &self,
         query: &str,
     ) -> Result<(Vec<RecordBatch>, SchemaRef), OxyError> {
-        self.engine.explain_query(query).await
+        let query = self.access_policies.enforce(query)?;
+        self.replayed(
+            "explain_query",
+            &query,
+            None,
+            self.engine.explain_query(&query),
+        )
+        .await
     }
 
     pub async fn dry_run(&self, query: &str) -> Result<(Vec<RecordBatch>, SchemaRef), OxyError> {
-        self.engine.dry_run(query).await
+        let query = self.access_policies.enforce(query)?;
+        self.replayed("dry_run", &query, None, self.engine.dry_run(&query))
+            .await
+    }
+
+    /// Run `live` unless record/replay is on. Results are stored as Arrow IPC
+    /// and failures as their message, so a replayed agent sees the same
+    /// errors it did while recording.
+    async fn replayed(
+        &self,
+        method: &str,
+        query: &str,
+        limit: Option<u64>,
+        live: impl Future<Output = Result<(Vec<RecordBatch>, SchemaRef), OxyError>>,
+    ) -> Result<(Vec<RecordBatch>, SchemaRef), OxyError> {
+        let Some(replay) = &self.replay else {
+            return live.await;
+        };
+        let request = json!({
+            "database": self.database,
+            "method": method,
+            "query": query,
+            "limit": limit,
+        });
+        let path = replay.path(CONNECTOR_FIXTURES, &request, "arrow")?;
+        let error_path = path.with_extension("error");
+        match replay.mode() {
+            ReplayMode::Replay => {
+                if error_path.exists() {
+                    return Err(OxyError::DBError(read_fixture(&error_path)?));
+                }
+                if !path.exists() {
+                    return Err(missing_fixture(&path));
+                }
+                load_result(&path.to_string_lossy()).map_err(|e| {
+                    OxyError::RuntimeError(format!("Invalid fixture '{}': {e}", path.display()))
+                })
+            }
+            ReplayMode::Record => {
+                let result = live.await;
+                let written = match &result {
+                    Ok((record_batches, schema_ref)) => {
+                        let _ = std::fs::remove_file(&error_path);
+                        write_to_ipc(record_batches, &path, schema_ref).map_err(|e| e.to_string())
+                    }
+                    Err(err) => {
+                        std::fs::write(&error_path, err.to_string()).map_err(|e| e.to_string())
+                    }
+                };
+                written.map_err(|e| {
+                    OxyError::RuntimeError(format!(
+                        "Failed to write fixture '{}': {e}",
+                        path.display()
+                    ))
+                })?;
+                result
+            }
+        }
     }
 
     /// Validate api request filters against configured database filter schemas
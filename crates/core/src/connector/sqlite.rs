@@ -0,0 +1,390 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow::array::{
+    ArrayRef, BinaryBuilder, Float64Builder, Int64Builder, RecordBatch, RecordBatchOptions,
+    StringBuilder,
+};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use rusqlite::types::Value;
+use rusqlite::{Connection, OpenFlags};
+use slugify::slugify;
+
+use super::constants::{CREATE_CONN, EXECUTE_QUERY, FAILED_TO_RUN_BLOCKING_TASK, LOAD_RESULT};
+use super::engine::Engine;
+use super::utils::connector_internal_error;
+use oxy_shared::errors::OxyError;
+
+/// SQLite engine over one or more database files.
+///
+/// A single file is opened directly, so its tables keep their bare names.
+/// Several files are each `ATTACH`ed to an in-memory connection under their
+/// file stem (see [`schema_aliases`]); SQLite still resolves bare table names
+/// against attached schemas when they are unambiguous.
+#[derive(Debug)]
+pub(super) struct Sqlite {
+    files: Vec<String>,
+    read_only: bool,
+}
+
+impl Sqlite {
+    pub fn new(files: Vec<String>, read_only: bool) -> Self {
+        Sqlite { files, read_only }
+    }
+}
+
+impl Engine for Sqlite {
+    async fn run_query_with_limit(
+        &self,
+        query: &str,
+        _dry_run_limit: Option<u64>,
+    ) -> Result<(Vec<RecordBatch>, SchemaRef), OxyError> {
+        let files = self.files.clone();
+        let read_only = self.read_only;
+        let query = query.to_string();
+        // rusqlite is synchronous; keep file IO off the async workers.
+        tokio::task::spawn_blocking(move || {
+            let conn = open_connection(&files, read_only)?;
+            query_to_arrow(&conn, &query)
+        })
+        .await
+        .map_err(|err| connector_internal_error(FAILED_TO_RUN_BLOCKING_TASK, &err))?
+    }
+
+    async fn explain_query(&self, query: &str) -> Result<(Vec<RecordBatch>, SchemaRef), OxyError> {
+        // SQLite's EXPLAIN does not take a parenthesised statement. QUERY PLAN
+        // compiles the statement without running it.
+        let explain_query = format!("EXPLAIN QUERY PLAN {}", query.trim().trim_end_matches(';'));
+        self.run_query_with_limit(&explain_query, None).await
+    }
+}
+
+fn open_connection(files: &[String], read_only: bool) -> Result<Connection, OxyError> {
+    let conn = match files {
+        [] => {
+            return Err(OxyError::DBError(
+                "SQLite path did not match any database file".to_string(),
+            ));
+        }
+        [file] => {
+            let mode = if read_only {
+                OpenFlags::SQLITE_OPEN_READ_ONLY
+            } else {
+                OpenFlags::SQLITE_OPEN_READ_WRITE
+            };
+            Connection::open_with_flags(
+                file,
+                mode | OpenFlags::SQLITE_OPEN_URI | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+            )
+            .map_err(|err| connector_internal_error(CREATE_CONN, &err))?
+        }
+        _ => {
+            let conn = Connection::open_in_memory()
+                .map_err(|err| connector_internal_error(CREATE_CONN, &err))?;
+            for (alias, file) in schema_aliases(files) {
+                conn.execute(
+                    &format!("ATTACH DATABASE ?1 AS \"{alias}\""),
+                    [file_uri(file, read_only)],
+                )
+                .map_err(|err| {
+                    connector_internal_error(&format!("{CREATE_CONN}: cannot attach {file}"), &err)
+                })?;
+            }
+            conn
+        }
+    };
+    if read_only {
+        // Also covers the in-memory `main` / `temp` schemas, which the
+        // read-only open flags do not reach.
+        conn.pragma_update(None, "query_only", true)
+            .map_err(|err| connector_internal_error(CREATE_CONN, &err))?;
+    }
+    Ok(conn)
+}
+
+/// Schema name for each attached file: the slugified file stem, prefixed with
+/// `_` when it starts with a digit and suffixed when two stems collide or
+/// clash with SQLite's reserved `main` / `temp` schemas.
+fn schema_aliases(files: &[String]) -> Vec<(String, &str)> {
+    let mut taken: HashSet<String> = HashSet::from(["main".to_string(), "temp".to_string()]);
+    files
+        .iter()
+        .map(|file| {
+            let stem = Path::new(file)
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default();
+            let mut base = slugify!(stem, separator = "_");
+            if base.is_empty() {
+                base = "db".to_string();
+            } else if base.starts_with(|c: char| c.is_ascii_digit()) {
+                base = format!("_{base}");
+            }
+            let mut alias = base.clone();
+            let mut n = 1;
+            while taken.contains(&alias) {
+                n += 1;
+                alias = format!("{base}_{n}");
+            }
+            taken.insert(alias.clone());
+            (alias, file.as_str())
+        })
+        .collect()
+}
+
+/// `file:` URI for `ATTACH`, so the access mode applies per file.
+fn file_uri(path: &str, read_only: bool) -> String {
+    let escaped = path
+        .replace('%', "%25")
+        .replace('?', "%3f")
+        .replace('#', "%23");
+    let mode = if read_only { "ro" } else { "rw" };
+    format!("file:{escaped}?mode={mode}")
+}
+
+/// Run `query` and convert every row into a single Arrow batch.
+///
+/// SQLite is dynamically typed, so column types come from the storage classes
+/// actually returned: integers stay `Int64`, integers mixed with reals widen
+/// to `Float64`, and anything mixed with text becomes `Utf8`. Columns with no
+/// non-NULL value fall back to the declared type's affinity.
+fn query_to_arrow(
+    conn: &Connection,
+    query: &str,
+) -> Result<(Vec<RecordBatch>, SchemaRef), OxyError> {
+    let mut stmt = conn
+        .prepare(query)
+        .map_err(|err| connector_internal_error(EXECUTE_QUERY, &err))?;
+    let names: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+    let decl_types: Vec<Option<String>> = stmt
+        .columns()
+        .iter()
+        .map(|c| c.decl_type().map(str::to_string))
+        .collect();
+
+    let mut values: Vec<Vec<Value>> = vec![Vec::new(); names.len()];
+    let mut row_count = 0;
+    let mut rows = stmt
+        .query([])
+        .map_err(|err| connector_internal_error(EXECUTE_QUERY, &err))?;
+    while let Some(row) = rows
+        .next()
+        .map_err(|err| connector_internal_error(EXECUTE_QUERY, &err))?
+    {
+        for (idx, column) in values.iter_mut().enumerate() {
+            column.push(
+                row.get::<_, Value>(idx)
+                    .map_err(|err| connector_internal_error(LOAD_RESULT, &err))?,
+            );
+        }
+        row_count += 1;
+    }
+
+    let mut fields = Vec::with_capacity(names.len());
+    let mut arrays: Vec<ArrayRef> = Vec::with_capacity(names.len());
+    for ((name, decl_type), column) in names.iter().zip(&decl_types).zip(&values) {
+        let data_type = infer_data_type(column, decl_type.as_deref());
+        arrays.push(build_array(column, &data_type));
+        fields.push(Field::new(name, data_type, true));
+    }
+    let schema: SchemaRef = Arc::new(Schema::new(fields));
+    let batch = RecordBatch::try_new_with_options(
+        schema.clone(),
+        arrays,
+        &RecordBatchOptions::new().with_row_count(Some(row_count)),
+    )
+    .map_err(|err| connector_internal_error(LOAD_RESULT, &err))?;
+    Ok((vec![batch], schema))
+}
+
+fn infer_data_type(column: &[Value], decl_type: Option<&str>) -> DataType {
+    let (mut integer, mut real, mut text, mut blob) = (false, false, false, false);
+    for value in column {
+        match value {
+            Value::Null => {}
+            Value::Integer(_) => integer = true,
+            Value::Real(_) => real = true,
+            Value::Text(_) => text = true,
+            Value::Blob(_) => blob = true,
+        }
+    }
+    match (integer, real, text, blob) {
+        (false, false, false, false) => decl_type.map_or(DataType::Utf8, affinity_data_type),
+        (_, _, false, false) if real => DataType::Float64,
+        (true, false, false, false) => DataType::Int64,
+        (false, false, false, true) => DataType::Binary,
+        _ => DataType::Utf8,
+    }
+}
+
+/// Arrow type for SQLite's column affinity rules (§3.1 of "Datatypes In
+/// SQLite").
+fn affinity_data_type(decl_type: &str) -> DataType {
+    let upper = decl_type.to_ascii_uppercase();
+    if upper.contains("INT") {
+        DataType::Int64
+    } else if upper.contains("CHAR") || upper.contains("CLOB") || upper.contains("TEXT") {
+        DataType::Utf8
+    } else if upper.contains("BLOB") {
+        DataType::Binary
+    } else if upper.contains("REAL") || upper.contains("FLOA") || upper.contains("DOUB") {
+        DataType::Float64
+    } else {
+        DataType::Utf8
+    }
+}
+
+fn build_array(column: &[Value], data_type: &DataType) -> ArrayRef {
+    match data_type {
+        DataType::Int64 => {
+            let mut builder = Int64Builder::with_capacity(column.len());
+            for value in column {
+                match value {
+                    Value::Integer(i) => builder.append_value(*i),
+                    _ => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Float64 => {
+            let mut builder = Float64Builder::with_capacity(column.len());
+            for value in column {
+                match value {
+                    Value::Integer(i) => builder.append_value(*i as f64),
+                    Value::Real(f) => builder.append_value(*f),
+                    _ => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Binary => {
+            let mut builder = BinaryBuilder::new();
+            for value in column {
+                match value {
+                    Value::Blob(b) => builder.append_value(b),
+                    _ => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        _ => {
+            let mut builder = StringBuilder::new();
+            for value in column {
+                match value {
+                    Value::Null => builder.append_null(),
+                    Value::Integer(i) => builder.append_value(i.to_string()),
+                    Value::Real(f) => builder.append_value(f.to_string()),
+                    Value::Text(s) => builder.append_value(s),
+                    Value::Blob(b) => builder.append_value(String::from_utf8_lossy(b)),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Array, Float64Array, Int64Array, StringArray};
+
+    fn run(conn: &Connection, sql: &str) -> RecordBatch {
+        let (batches, _) = query_to_arrow(conn, sql).unwrap();
+        batches.into_iter().next().unwrap()
+    }
+
+    #[test]
+    fn storage_classes_drive_column_types() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE t (i INTEGER, r REAL, mixed NUMERIC, s TEXT);
+             INSERT INTO t VALUES (1, 1.5, 2, 'a'), (2, NULL, 2.5, NULL);",
+        )
+        .unwrap();
+        let batch = run(&conn, "SELECT * FROM t");
+        let schema = batch.schema();
+        assert_eq!(schema.field(0).data_type(), &DataType::Int64);
+        assert_eq!(schema.field(1).data_type(), &DataType::Float64);
+        assert_eq!(schema.field(2).data_type(), &DataType::Float64);
+        assert_eq!(schema.field(3).data_type(), &DataType::Utf8);
+
+        let mixed = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(mixed.value(0), 2.0);
+        assert!(batch.column(1).is_null(1));
+    }
+
+    #[test]
+    fn text_mixed_with_numbers_becomes_utf8() {
+        let conn = Connection::open_in_memory().unwrap();
+        let batch = run(&conn, "SELECT 1 AS v UNION ALL SELECT 'n/a'");
+        let col = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(col.value(0), "1");
+        assert_eq!(col.value(1), "n/a");
+    }
+
+    #[test]
+    fn empty_result_uses_declared_affinity() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE t (id BIGINT, name VARCHAR(10))")
+            .unwrap();
+        let batch = run(&conn, "SELECT id, name FROM t");
+        assert_eq!(batch.num_rows(), 0);
+        assert_eq!(batch.schema().field(0).data_type(), &DataType::Int64);
+        assert_eq!(batch.schema().field(1).data_type(), &DataType::Utf8);
+        assert!(
+            batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn aliases_are_unique_and_avoid_reserved_schemas() {
+        let files = vec![
+            "/data/main.db".to_string(),
+            "/data/2024 sales.sqlite".to_string(),
+            "/other/main.db".to_string(),
+        ];
+        let aliases: Vec<String> = schema_aliases(&files)
+            .into_iter()
+            .map(|(alias, _)| alias)
+            .collect();
+        assert_eq!(aliases, vec!["main_2", "_2024_sales", "main_3"]);
+    }
+
+    #[test]
+    fn read_only_attach_rejects_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut files = vec![];
+        for name in ["a", "b"] {
+            let path = dir.path().join(format!("{name}.db"));
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch(&format!(
+                "CREATE TABLE {name}_t (x INTEGER); INSERT INTO {name}_t VALUES (1);"
+            ))
+            .unwrap();
+            files.push(path.display().to_string());
+        }
+
+        let conn = open_connection(&files, true).unwrap();
+        let batch = run(&conn, "SELECT a.a_t.x + b_t.x AS total FROM a.a_t, b_t");
+        let total = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(total.value(0), 2);
+        assert!(conn.execute("INSERT INTO b.b_t VALUES (2)", []).is_err());
+    }
+}
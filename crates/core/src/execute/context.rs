@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
 use minijinja::Value;
 use serde::{Serialize, de::DeserializeOwned};
@@ -6,8 +6,9 @@ use tokio::sync::mpsc::Sender;
 
 use crate::{
     adapters::{session_filters::SessionFilters, workspace::manager::WorkspaceManager},
+    budget::{Budget, SpendLedger},
     checkpoint::{CheckpointContext, CheckpointData},
-    config::model::ConnectionOverrides,
+    config::model::{ConnectionOverrides, RunBudget},
     execute::{
         builders::checkpoint::CheckpointId,
         renderer::Renderer,
@@ -60,6 +61,9 @@ pub struct ExecutionContext {
     pub metric_context: Option<SharedMetricCtx>,
     /// Data app file path (for tools that need to read/write data apps) - set by tools/create_data_app and tools/edit_data_app
     pub data_app_file_path: Option<String>,
+    /// Budget of the innermost agent or workflow run that declared one
+    /// Shared with nested executions so their spend is charged to it
+    pub budget: Option<Arc<Budget>>,
 }
 
 impl ExecutionContext {
@@ -83,6 +87,7 @@ impl ExecutionContext {
             user_id,
             metric_context: None,
             data_app_file_path: None,
+            budget: None,
         }
     }
 
@@ -103,6 +108,7 @@ impl ExecutionContext {
             user_id: self.user_id,
             metric_context: self.metric_context.clone(),
             data_app_file_path: self.data_app_file_path.clone(),
+            budget: self.budget.clone(),
         }
     }
 
@@ -119,6 +125,7 @@ impl ExecutionContext {
             user_id: self.user_id,
             metric_context: self.metric_context.clone(),
             data_app_file_path: self.data_app_file_path.clone(),
+            budget: self.budget.clone(),
         }
     }
 
@@ -136,6 +143,7 @@ impl ExecutionContext {
                 user_id: self.user_id,
                 metric_context: self.metric_context.clone(),
                 data_app_file_path: self.data_app_file_path.clone(),
+                budget: self.budget.clone(),
             }
         } else {
             ExecutionContext {
@@ -150,6 +158,7 @@ impl ExecutionContext {
                 user_id: self.user_id,
                 metric_context: self.metric_context.clone(),
                 data_app_file_path: self.data_app_file_path.clone(),
+                budget: self.budget.clone(),
             }
         }
     }
@@ -167,6 +176,7 @@ impl ExecutionContext {
             user_id: self.user_id,
             metric_context: self.metric_context.clone(),
             data_app_file_path: self.data_app_file_path.clone(),
+            budget: self.budget.clone(),
         }
     }
 
@@ -183,6 +193,7 @@ impl ExecutionContext {
             user_id: self.user_id,
             metric_context: self.metric_context.clone(),
             data_app_file_path: self.data_app_file_path.clone(),
+            budget: self.budget.clone(),
         }
     }
 
@@ -201,6 +212,7 @@ impl ExecutionContext {
             user_id: self.user_id,
             metric_context: self.metric_context.clone(),
             data_app_file_path: self.data_app_file_path.clone(),
+            budget: self.budget.clone(),
         }
     }
 
@@ -217,6 +229,7 @@ impl ExecutionContext {
             user_id: self.user_id,
             metric_context: self.metric_context.clone(),
             data_app_file_path: self.data_app_file_path.clone(),
+            budget: self.budget.clone(),
         }
     }
 
@@ -233,6 +246,7 @@ impl ExecutionContext {
             user_id,
             metric_context: self.metric_context.clone(),
             data_app_file_path: self.data_app_file_path.clone(),
+            budget: self.budget.clone(),
         }
     }
 
@@ -250,6 +264,7 @@ impl ExecutionContext {
             user_id: self.user_id,
             metric_context: Some(metric_context),
             data_app_file_path: self.data_app_file_path.clone(),
+            budget: self.budget.clone(),
         }
     }
 
@@ -280,9 +295,67 @@ impl ExecutionContext {
             user_id: self.user_id,
             metric_context: child_ctx,
             data_app_file_path: self.data_app_file_path.clone(),
+            budget: self.budget.clone(),
         }
     }
 
+    /// Track spend of the run about to start against `limits`. A nested run
+    /// also counts toward the budgets of the runs enclosing it. The outermost
+    /// run opens the spend ledger when config.yml has `budgets`, and refuses
+    /// to start once a workspace or user cap has been reached, or when the
+    /// ledger cannot be opened.
+    pub async fn with_run_budget(
+        &self,
+        scope: &str,
+        limits: Option<&RunBudget>,
+    ) -> Result<Self, OxyError> {
+        let budget = match (&self.budget, limits) {
+            (Some(parent), Some(limits)) => parent.child(scope, limits.clone()),
+            (Some(_), None) => return Ok(self.clone()),
+            (None, limits) => {
+                let ledger = match &self.workspace.config_manager.get_config().budgets {
+                    Some(budgets) => SpendLedger::open(
+                        self.workspace.workspace_id,
+                        self.user_id,
+                        budgets,
+                    )
+                    .await
+                    .map(Some)
+                    .map_err(|err| {
+                        OxyError::DBError(format!(
+                            "Spend caps cannot be enforced, failed to open the spend ledger: {err}"
+                        ))
+                    })?,
+                    None => None,
+                };
+                if let Some(ledger) = &ledger
+                    && let Some(reason) = ledger.cap_reached()?
+                {
+                    return Err(OxyError::BudgetExceeded(reason));
+                }
+                if ledger.is_none() && limits.is_none() {
+                    return Ok(self.clone());
+                }
+                Budget::new(scope, limits.cloned().unwrap_or_default(), ledger)
+            }
+        };
+
+        Ok(ExecutionContext {
+            source: self.source.clone(),
+            writer: self.writer.clone(),
+            renderer: self.renderer.clone(),
+            workspace: self.workspace.clone(),
+            checkpoint: self.checkpoint.clone(),
+            filters: self.filters.clone(),
+            connections: self.connections.clone(),
+            sandbox_info: self.sandbox_info.clone(),
+            user_id: self.user_id,
+            metric_context: self.metric_context.clone(),
+            data_app_file_path: self.data_app_file_path.clone(),
+            budget: Some(budget),
+        })
+    }
+
     // =========================================================================
     // Metric recording helpers
     // =========================================================================
@@ -391,6 +464,36 @@ impl ExecutionContext {
         self.write_kind(EventKind::Usage { usage }).await
     }
 
+    /// Write the usage of a call to `model` and charge it to the run's
+    /// budget, priced with `budgets.prices` from config.yml. See
+    /// [`Budget::charge_call`] for models without a price.
+    pub async fn write_model_usage(&self, model: &str, usage: Usage) -> Result<(), OxyError> {
+        let (input_tokens, output_tokens) = (
+            usage.input_tokens.max(0) as u64,
+            usage.output_tokens.max(0) as u64,
+        );
+        self.write_usage(usage).await?;
+        let Some(budget) = &self.budget else {
+            return Ok(());
+        };
+        budget
+            .charge_call(
+                model,
+                input_tokens,
+                output_tokens,
+                self.workspace.config_manager.get_config().budgets.as_ref(),
+            )
+            .await
+    }
+
+    /// Charge `count` tool calls to the run's budget before they are made.
+    pub async fn charge_tool_calls(&self, count: usize) -> Result<(), OxyError> {
+        match &self.budget {
+            Some(budget) => budget.charge_tool_calls(count as u64).await,
+            None => Ok(()),
+        }
+    }
+
     pub async fn write_progress(&self, progress: ProgressType) -> Result<(), OxyError> {
         self.write_kind(EventKind::Progress { progress }).await
     }
@@ -572,6 +675,7 @@ impl ExecutionContextBuilder {
             user_id: self.user_id,
             metric_context: self.metric_context,
             data_app_file_path: self.data_app_file_path,
+            budget: None,
         })
     }
 }
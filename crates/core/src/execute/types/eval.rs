@@ -1,3 +1,5 @@
+use serde::Serialize;
+
 use crate::config::constants::AGENT_SOURCE_PROMPT;
 use oxy_shared::errors::OxyError;
 
@@ -26,6 +28,31 @@ pub struct TargetOutput {
     pub duration_ms: f64,
     pub input_tokens: i32,
     pub output_tokens: i32,
+    /// Tools invoked during the run, in call order
+    pub tool_calls: Vec<String>,
+    /// SQL generated during the run, in generation order
+    pub sql_queries: Vec<String>,
+    /// Deterministic test assertions checked against this output
+    pub assertions: Vec<AssertionResult>,
+}
+
+/// Outcome of one deterministic test assertion
+#[derive(Clone, Debug, Serialize)]
+pub struct AssertionResult {
+    /// Which assertion ran: `tool`, `sql` or `result`
+    pub kind: String,
+    pub passed: bool,
+    pub message: String,
+}
+
+impl AssertionResult {
+    pub fn new(kind: &str, passed: bool, message: impl Into<String>) -> Self {
+        Self {
+            kind: kind.to_string(),
+            passed,
+            message: message.into(),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -85,6 +112,9 @@ impl TryFrom<OutputGetter<'_>> for TargetOutput {
             duration_ms: 0.0,
             input_tokens: 0,
             output_tokens: 0,
+            tool_calls: vec![],
+            sql_queries: vec![],
+            assertions: vec![],
         })
     }
 }
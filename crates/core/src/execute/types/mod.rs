@@ -16,7 +16,7 @@ mod viz;
 pub use data_app::DataApp;
 pub use display::ProgressType;
 pub use document::Document;
-pub use eval::{OutputGetter, RelevantContextGetter, TargetOutput};
+pub use eval::{AssertionResult, OutputGetter, RelevantContextGetter, TargetOutput};
 pub use event::{Event, EventKind, Source};
 pub use output::{Chunk, Output};
 pub use output_container::{Data, DataContainer, TableData};
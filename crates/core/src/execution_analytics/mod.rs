@@ -9,5 +9,5 @@ mod types;
 pub use storage::ExecutionAnalyticsStorage;
 pub use types::{
     AgentExecutionStats, ExecutionDetail, ExecutionListResponse, ExecutionSummary,
-    ExecutionTimeBucket, ExecutionType, SourceType,
+    ExecutionTimeBucket, ExecutionType, SourceType, SpendBucket, SpendSummary,
 };
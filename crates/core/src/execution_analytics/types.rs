@@ -181,3 +181,26 @@ pub struct ExecutionListResponse {
     pub limit: usize,
     pub offset: usize,
 }
+
+/// LLM and tool spend recorded for budget enforcement
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendSummary {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+    pub tool_calls: u64,
+    /// Days with spend, oldest first
+    pub daily: Vec<SpendBucket>,
+}
+
+/// Spend of one UTC day
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendBucket {
+    pub date: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+    pub tool_calls: u64,
+}
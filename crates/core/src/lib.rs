@@ -2,6 +2,8 @@
 
 pub mod adapters;
 pub mod api_types;
+pub mod audit;
+pub mod budget;
 pub mod checkpoint;
 pub mod config;
 pub mod connector;
@@ -376,6 +376,13 @@ fn build_schema_summary_queries(database: &Database) -> Result<Vec<String>, OxyE
                  GROUP BY schema_name"
             )])
         }
+        DatabaseType::Sqlite(_) => Ok(vec![
+            "SELECT schema AS table_schema, COUNT(*) AS table_count
+             FROM pragma_table_list
+             WHERE schema <> 'temp' AND type IN ('table', 'view') AND name NOT LIKE 'sqlite_%'
+             GROUP BY schema"
+                .to_string(),
+        ]),
         DatabaseType::Postgres(_) | DatabaseType::Redshift(_) => Ok(vec![
             "SELECT table_schema, COUNT(*) AS table_count
              FROM information_schema.tables
@@ -390,6 +397,26 @@ fn build_schema_summary_queries(database: &Database) -> Result<Vec<String>, OxyE
              GROUP BY table_schema"
                 .to_string(),
         ]),
+        DatabaseType::Mssql(_) => Ok(vec![
+            "SELECT TABLE_SCHEMA AS table_schema, COUNT(*) AS table_count
+             FROM INFORMATION_SCHEMA.TABLES
+             WHERE TABLE_SCHEMA NOT IN ('sys', 'INFORMATION_SCHEMA')
+             GROUP BY TABLE_SCHEMA"
+                .to_string(),
+        ]),
+        // Schemas are reported as `catalog.schema` — see build_schema_tables_query.
+        DatabaseType::Trino(_) => database
+            .datasets()
+            .keys()
+            .map(|catalog| {
+                Ok(format!(
+                    "SELECT table_catalog || '.' || table_schema AS table_schema, COUNT(*) AS table_count
+                     FROM {catalog}.information_schema.tables
+                     WHERE table_schema <> 'information_schema'
+                     GROUP BY table_catalog, table_schema"
+                ))
+            })
+            .collect(),
         _ => Err(OxyError::ConfigurationError(format!(
             "Schema discovery not yet supported for database type: {:?}",
             database.database_type
@@ -424,10 +451,32 @@ fn build_schema_tables_query(database: &Database, schema: &str) -> Result<String
              WHERE schema_name = '{escaped}'
              GROUP BY table_name"
         )),
-        DatabaseType::Postgres(_) | DatabaseType::Redshift(_) | DatabaseType::Mysql(_) => {
+        DatabaseType::Sqlite(_) => Ok(format!(
+            "SELECT t.name AS table_name, COUNT(*) AS column_count
+             FROM pragma_table_list AS t
+             JOIN pragma_table_info(t.name, t.schema) AS c
+             WHERE t.schema = '{escaped}' AND t.type IN ('table', 'view') AND t.name NOT LIKE 'sqlite_%'
+             GROUP BY t.name"
+        )),
+        DatabaseType::Postgres(_)
+        | DatabaseType::Redshift(_)
+        | DatabaseType::Mysql(_)
+        | DatabaseType::Mssql(_) => Ok(format!(
+            "SELECT table_name, COUNT(*) AS column_count
+                 FROM information_schema.columns
+                 WHERE table_schema = '{escaped}'
+                 GROUP BY table_name"
+        )),
+        DatabaseType::Trino(_) => {
+            let (catalog, schema) = schema.split_once('.').ok_or_else(|| {
+                OxyError::ConfigurationError(format!(
+                    "Trino schema must be catalog-qualified, got: {schema}"
+                ))
+            })?;
+            let escaped = schema.replace('\'', "''");
             Ok(format!(
                 "SELECT table_name, COUNT(*) AS column_count
-                 FROM information_schema.columns
+                 FROM {catalog}.information_schema.columns
                  WHERE table_schema = '{escaped}'
                  GROUP BY table_name"
             ))
@@ -522,6 +571,14 @@ fn build_inspect_queries(database: &Database) -> Result<Vec<String>, OxyError> {
                  GROUP BY schema_name, table_name"
             )])
         }
+        DatabaseType::Sqlite(_) => Ok(vec![
+            "SELECT t.schema AS table_schema, t.name AS table_name, COUNT(*) AS column_count
+             FROM pragma_table_list AS t
+             JOIN pragma_table_info(t.name, t.schema) AS c
+             WHERE t.schema <> 'temp' AND t.type IN ('table', 'view') AND t.name NOT LIKE 'sqlite_%'
+             GROUP BY t.schema, t.name"
+                .to_string(),
+        ]),
         DatabaseType::Postgres(_) | DatabaseType::Redshift(_) => {
             // Postgres / Redshift (via connectorx). `datasets()` is empty for
             // these since the config has no schemas list — a single
@@ -546,6 +603,25 @@ fn build_inspect_queries(database: &Database) -> Result<Vec<String>, OxyError> {
                     .to_string(),
             ])
         }
+        DatabaseType::Mssql(_) => Ok(vec![
+            "SELECT TABLE_SCHEMA AS table_schema, TABLE_NAME AS table_name, COUNT(*) AS column_count
+             FROM INFORMATION_SCHEMA.COLUMNS
+             WHERE TABLE_SCHEMA NOT IN ('sys', 'INFORMATION_SCHEMA')
+             GROUP BY TABLE_SCHEMA, TABLE_NAME"
+                .to_string(),
+        ]),
+        DatabaseType::Trino(_) => database
+            .datasets()
+            .keys()
+            .map(|catalog| {
+                Ok(format!(
+                    "SELECT table_catalog || '.' || table_schema AS table_schema, table_name, COUNT(*) AS column_count
+                     FROM {catalog}.information_schema.columns
+                     WHERE table_schema <> 'information_schema'
+                     GROUP BY table_catalog, table_schema, table_name"
+                ))
+            })
+            .collect(),
         // DOMO is single-dataset and exposes metadata through its REST client,
         // not SQL — no inspect path today.
         _ => Err(OxyError::ConfigurationError(format!(
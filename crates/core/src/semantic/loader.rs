@@ -305,6 +305,101 @@ impl GetSchemaQuery for Database {
                 Ok(vec![query])
             }
 
+            DatabaseType::Sqlite(_) => {
+                // Each attached file is a schema; `temp` only ever holds
+                // connection-local objects.
+                let query = "SELECT t.schema AS table_schema,
+                            t.name AS table_name,
+                            c.name AS column_name,
+                            c.type AS data_type,
+                            'NO' AS is_partitioning_column,
+                            NULL AS description
+                     FROM pragma_table_list AS t
+                     JOIN pragma_table_info(t.name, t.schema) AS c
+                     WHERE t.schema <> 'temp'
+                        AND t.type IN ('table', 'view')
+                        AND t.name NOT LIKE 'sqlite_%'
+                     ORDER BY t.schema, t.name, c.cid"
+                    .to_string();
+                tracing::debug!("SQLite schema query: {}", query);
+                Ok(vec![query])
+            }
+
+            DatabaseType::Mssql(_) => self
+                .datasets()
+                .iter()
+                .map(|(dataset, tables)| {
+                    let tables_filter = if tables.is_empty() {
+                        String::default()
+                    } else {
+                        let table_conditions = tables
+                            .iter()
+                            .map(|v| {
+                                if v.contains("*") {
+                                    format!("c.TABLE_NAME LIKE '{}'", v.replace("*", "%"))
+                                } else {
+                                    format!("c.TABLE_NAME = '{v}'")
+                                }
+                            })
+                            .join(" OR ");
+                        format!(" AND ({table_conditions})")
+                    };
+
+                    let schema_filter = if dataset.is_empty() {
+                        "c.TABLE_SCHEMA NOT IN ('sys', 'INFORMATION_SCHEMA')".to_string()
+                    } else {
+                        format!("c.TABLE_SCHEMA = '{dataset}'")
+                    };
+
+                    // Column descriptions live in `MS_Description` extended
+                    // properties. No ORDER BY: ConnectorX wraps the query in
+                    // a derived table, where T-SQL rejects ORDER BY without TOP.
+                    let query = format!(
+                        "SELECT c.TABLE_SCHEMA AS table_schema,
+                                c.TABLE_NAME AS table_name,
+                                c.COLUMN_NAME AS column_name,
+                                c.DATA_TYPE AS data_type,
+                                CAST(0 AS BIT) AS is_partitioning_column,
+                                CAST(ep.value AS NVARCHAR(4000)) AS description
+                         FROM INFORMATION_SCHEMA.COLUMNS c
+                         LEFT JOIN sys.extended_properties ep
+                           ON ep.class = 1
+                          AND ep.name = 'MS_Description'
+                          AND ep.major_id = OBJECT_ID(QUOTENAME(c.TABLE_SCHEMA) + '.' + QUOTENAME(c.TABLE_NAME))
+                          AND ep.minor_id = COLUMNPROPERTY(ep.major_id, c.COLUMN_NAME, 'ColumnId')
+                         WHERE {schema_filter}{tables_filter}"
+                    );
+                    tracing::debug!("SQL Server schema query for schema '{}': {}", dataset, query);
+                    Ok(query)
+                })
+                .collect::<Result<Vec<_>, OxyError>>(),
+
+            DatabaseType::Trino(_) => self
+                .datasets()
+                .iter()
+                .map(|(catalog, tables)| {
+                    // Every catalog has its own information_schema. The
+                    // catalog is folded into `table_schema` so schemas with
+                    // the same name in different catalogs stay apart and
+                    // table references come out as catalog.schema.table.
+                    let query = GetSchemaQueryBuilder::default()
+                        .with_column_names(
+                            ColumnNames::default()
+                                .with_dataset("table_catalog || '.' || table_schema AS table_schema")
+                                .with_is_partitioning_column("FALSE AS is_partitioning_column")
+                                .with_description("comment AS description"),
+                        )
+                        .with_filter_tables(tables.clone())
+                        .with_columns_table(format!("{catalog}.information_schema.columns"))
+                        .build();
+                    let query = format!(
+                        "SELECT * FROM ({query}) WHERE table_schema <> '{catalog}.information_schema'"
+                    );
+                    tracing::debug!("Trino schema query for catalog '{}': {}", catalog, query);
+                    Ok(query)
+                })
+                .collect::<Result<Vec<_>, OxyError>>(),
+
             DatabaseType::MotherDuck(_) => {
                 // MotherDuck uses the same information_schema structure as DuckDB
                 // but we filter by schemas specified in config
@@ -382,6 +477,31 @@ impl GetSchemaQuery for Database {
                 Ok(vec![query])
             }
 
+            DatabaseType::Sqlite(_) => {
+                // `sqlite_schema` is per attached file and unqualified means
+                // `main`, so files attached from a glob path carry no DDL.
+                let query = "SELECT 'main' AS table_schema, sql AS ddl
+                    FROM sqlite_schema
+                    WHERE type IN ('table', 'view')
+                        AND name NOT LIKE 'sqlite_%'
+                        AND sql IS NOT NULL"
+                    .to_string();
+                tracing::debug!("SQLite DDL query: {}", query);
+                Ok(vec![query])
+            }
+
+            DatabaseType::Mssql(_) => {
+                // SQL Server has no catalog column holding CREATE TABLE text;
+                // scripting DDL needs SMO. Columns and types are enough here.
+                Ok(vec![])
+            }
+
+            DatabaseType::Trino(_) => {
+                // `SHOW CREATE TABLE` is a statement, not a relation, so it
+                // cannot be batched across tables.
+                Ok(vec![])
+            }
+
             DatabaseType::MotherDuck(_) => {
                 // MotherDuck's information_schema doesn't expose DDL statements via sql column
                 // DDL information is not critical for semantic model generation, so we skip it
@@ -557,13 +677,19 @@ impl SchemaLoader {
             | DatabaseType::Bigquery(_)
             | DatabaseType::Snowflake(_)
             | DatabaseType::DuckDB(_)
-            | DatabaseType::MotherDuck(_) => {
+            | DatabaseType::Sqlite(_)
+            | DatabaseType::MotherDuck(_)
+            | DatabaseType::Mssql(_)
+            | DatabaseType::Trino(_) => {
                 let db_type = match &self.database.database_type {
                     DatabaseType::ClickHouse(_) => "ClickHouse",
+                    DatabaseType::Mssql(_) => "SQL Server",
+                    DatabaseType::Trino(_) => "Trino",
                     DatabaseType::Bigquery(_) => "BigQuery",
                     DatabaseType::Snowflake(_) => "Snowflake",
                     DatabaseType::MotherDuck(_) => "MotherDuck",
                     DatabaseType::DuckDB(_c) => "DuckDB",
+                    DatabaseType::Sqlite(_) => "SQLite",
                     _ => "Unknown",
                 };
                 tracing::debug!(
@@ -667,9 +793,12 @@ impl SchemaLoader {
         match &self.database.database_type {
             DatabaseType::ClickHouse(_)
             | DatabaseType::DuckDB(_)
+            | DatabaseType::Sqlite(_)
             | DatabaseType::MotherDuck(_)
             | DatabaseType::Bigquery(_)
-            | DatabaseType::Snowflake(_) => {
+            | DatabaseType::Snowflake(_)
+            | DatabaseType::Mssql(_)
+            | DatabaseType::Trino(_) => {
                 let queries = self.database.get_ddl_queries()?;
                 let records: Vec<DDLRecord> = fetch_schema_models(queries, &self.connector).await?;
                 let datasets = records.into_iter().fold(HashMap::new(), |mut acc, record| {
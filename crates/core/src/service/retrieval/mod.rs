@@ -8,6 +8,7 @@ use crate::{
     config::ConfigManager,
 };
 use oxy_shared::errors::OxyError;
+use uuid::Uuid;
 
 pub mod enum_index;
 pub use enum_index::{EnumIndexConfig, EnumIndexManager};
@@ -15,6 +16,8 @@ pub use enum_index::{EnumIndexConfig, EnumIndexManager};
 pub struct ReindexInput {
     pub config: ConfigManager,
     pub secrets_manager: SecretsManager,
+    /// Scopes external vector stores (pgvector, Qdrant) to the workspace.
+    pub workspace_id: Uuid,
     pub drop_all_tables: bool,
 }
 
@@ -37,6 +40,7 @@ pub async fn reindex(input: ReindexInput) -> Result<(), OxyError> {
     ingest_retrieval_objects(
         &input.config,
         &input.secrets_manager,
+        input.workspace_id,
         &retrieval_objects,
         input.drop_all_tables,
     )
@@ -46,6 +50,7 @@ pub async fn reindex(input: ReindexInput) -> Result<(), OxyError> {
 pub struct SearchInput {
     pub config: ConfigManager,
     pub secrets_manager: SecretsManager,
+    pub workspace_id: Uuid,
     pub agent_ref: String,
     pub query: String,
 }
@@ -54,6 +59,7 @@ pub async fn search(input: SearchInput) -> Result<Vec<SearchRecord>, OxyError> {
     search_agent(
         &input.config,
         &input.secrets_manager,
+        input.workspace_id,
         &input.agent_ref,
         &input.query,
     )
@@ -14,7 +14,11 @@ use uuid::Uuid;
 use entity::secrets::{self, ActiveModel as SecretActiveModel, Entity as Secret};
 use oxy_shared::errors::OxyError;
 
-use crate::{database::client::establish_connection, utils::get_encryption_key};
+use crate::{
+    audit::{self, AuditAction, AuditEvent},
+    database::client::establish_connection,
+    utils::get_encryption_key,
+};
 
 /// A managed secret that holds a reference to a secret key variable.
 ///
@@ -342,6 +346,15 @@ impl SecretManagerService {
             OxyError::Database(e.to_string())
         })?;
 
+        audit::record_in(
+            db,
+            AuditEvent::new(AuditAction::SecretCreated)
+                .workspace(self.project_id)
+                .actor(saved_secret.created_by)
+                .target("secret", &saved_secret.name),
+        )
+        .await?;
+
         tracing::info!("Secret created successfully: {}", saved_secret.name);
         // Invalidate cache for this secret
         self.invalidate_cache(&params.name).await;
@@ -506,6 +519,7 @@ impl SecretManagerService {
 
         let mut secret_model: SecretActiveModel = secret.into();
 
+        let value_changed = params.value.is_some();
         if let Some(new_value) = params.value {
             let sanitized_value = Self::sanitize_secret_value(&new_value)?;
             let encrypted_value = self.encrypt_value(&sanitized_value)?;
@@ -524,6 +538,16 @@ impl SecretManagerService {
             .await
             .map_err(|e| OxyError::Database(e.to_string()))?;
 
+        audit::record_in(
+            db,
+            AuditEvent::new(AuditAction::SecretUpdated)
+                .workspace(self.project_id)
+                .actor(params.updated_by)
+                .target("secret", name)
+                .meta("value_changed", value_changed),
+        )
+        .await?;
+
         // Invalidate cache for this secret
         self.invalidate_cache(name).await;
 
@@ -540,7 +564,12 @@ impl SecretManagerService {
     }
 
     /// Delete a secret (soft delete)
-    pub async fn delete_secret<C>(&self, db: &C, name: &str) -> Result<(), OxyError>
+    pub async fn delete_secret<C>(
+        &self,
+        db: &C,
+        name: &str,
+        deleted_by: Uuid,
+    ) -> Result<(), OxyError>
     where
         C: sea_orm::ConnectionTrait,
     {
@@ -565,6 +594,15 @@ impl SecretManagerService {
             .await
             .map_err(|e| OxyError::Database(e.to_string()))?;
 
+        audit::record_in(
+            db,
+            AuditEvent::new(AuditAction::SecretDeleted)
+                .workspace(self.project_id)
+                .actor(deleted_by)
+                .target("secret", name),
+        )
+        .await?;
+
         // Remove from cache
         self.invalidate_cache(name).await;
 
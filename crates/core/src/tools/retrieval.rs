@@ -51,6 +51,7 @@ impl Executable<RetrievalInput> for RetrievalExecutable {
         let store = VectorStore::from_retrieval(
             config_manager,
             secrets_manager,
+            execution_context.workspace.workspace_id,
             &input.agent_name,
             &input.retrieval_config,
         )
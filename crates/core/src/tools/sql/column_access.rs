@@ -0,0 +1,363 @@
+//! Column-level access rules for raw SQL, checked by `execute_sql` and by the
+//! app's agentic connectors.
+//!
+//! Raw SQL cannot be rewritten to mask a column without knowing every column
+//! of the table, so queries are checked before they run instead. A query
+//! that reads a table with restricted columns is rejected when it references
+//! one of them anywhere (selected, aliased, wrapped in a function or used in
+//! a filter), selects `*` (also inside a function call, as in
+//! `to_json(c.*)`), or refers to the table's whole row. Column aliases such as
+//! `FROM customers AS c(a, b)` rename the table's columns by position, so they
+//! count as restricted too. Masked values stay available through the
+//! semantic layer.
+//!
+//! Like the row-level [`AccessPolicyEnforcer`](crate::connector::AccessPolicyEnforcer),
+//! this fails closed: SQL that cannot be parsed is rejected.
+
+use std::{collections::HashMap, ops::ControlFlow};
+
+use oxy_semantic::access::{FieldAccess, column_access};
+use sqlparser::{
+    ast::{
+        Expr, Function, FunctionArg, FunctionArgExpr, FunctionArguments, Query, SelectItem,
+        SetExpr, TableFactor, Visit, Visitor,
+    },
+    dialect::{Dialect, GenericDialect, dialect_from_str},
+    parser::Parser,
+};
+
+use crate::{
+    config::ConfigManager,
+    connector::{name_parts, names_match},
+};
+use oxy_shared::errors::OxyError;
+
+type TableRules = HashMap<String, HashMap<String, FieldAccess>>;
+
+/// Rule for the columns of a table alias column list, which may rename any
+/// of the table's columns, restricted ones included
+static RENAMED_COLUMN: FieldAccess = FieldAccess::Hidden;
+
+const WILDCARD_REJECTED: &str = "Query rejected: it reads a table with restricted columns, so it cannot select `*`; list the columns instead";
+
+/// Reject `sql` when it reads columns the caller may not see raw.
+pub(super) fn check_column_access(
+    config_manager: &ConfigManager,
+    database: &str,
+    sql: &str,
+) -> Result<(), OxyError> {
+    match ColumnAccessGuard::for_database(config_manager, database)? {
+        Some(guard) => guard.check(sql),
+        None => Ok(()),
+    }
+}
+
+/// The column access rules of one database for the caller of a
+/// [`ConfigManager`].
+#[derive(Debug, Clone)]
+pub struct ColumnAccessGuard {
+    tables: TableRules,
+    dialect: String,
+}
+
+impl ColumnAccessGuard {
+    /// `None` when no column of `database` is restricted for the caller.
+    pub fn for_database(
+        config_manager: &ConfigManager,
+        database: &str,
+    ) -> Result<Option<Self>, OxyError> {
+        let tables = column_access(
+            config_manager.semantic_views()?,
+            database,
+            Some(config_manager.access_level()),
+        );
+        if tables.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            tables,
+            dialect: config_manager.resolve_database(database)?.dialect(),
+        }))
+    }
+
+    /// Reject `sql` when it reads columns the caller may not see raw.
+    pub fn check(&self, sql: &str) -> Result<(), OxyError> {
+        check_sql(&self.tables, &self.dialect, sql)
+    }
+
+    /// Whether `column` of `table` is restricted. Both compare
+    /// case-insensitively, and `table` may be qualified or not.
+    pub fn restricts(&self, table: &str, column: &str) -> bool {
+        let parts = name_parts(table);
+        let column = column.to_lowercase();
+        self.tables.iter().any(|(restricted, columns)| {
+            names_match(&name_parts(restricted), &parts) && columns.contains_key(&column)
+        })
+    }
+}
+
+fn check_sql(tables: &TableRules, dialect: &str, sql: &str) -> Result<(), OxyError> {
+    let dialect: Box<dyn Dialect> =
+        dialect_from_str(dialect).unwrap_or_else(|| Box::new(GenericDialect {}));
+    let statements = Parser::parse_sql(dialect.as_ref(), sql).map_err(|e| {
+        OxyError::AuthorizationError(format!(
+            "Query rejected: column access rules apply to this database but the query could not be parsed to enforce them: {e}"
+        ))
+    })?;
+
+    for statement in &statements {
+        let mut relations = RestrictedRelations {
+            tables,
+            names: Vec::new(),
+            columns: HashMap::new(),
+            renamed: Vec::new(),
+        };
+        let _ = statement.visit(&mut relations);
+        if relations.names.is_empty() {
+            continue;
+        }
+        if let ControlFlow::Break(err) = statement.visit(&mut ColumnGuard {
+            relations: &relations,
+        }) {
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+/// The restricted tables a statement reads
+struct RestrictedRelations<'a> {
+    tables: &'a TableRules,
+    /// Lowercased names and aliases the tables are referred to by
+    names: Vec<String>,
+    /// Restricted columns by lowercased name, with their table and rule
+    columns: HashMap<&'a str, (&'a str, &'a FieldAccess)>,
+    /// Lowercased alias column names given to the tables, with their table
+    renamed: Vec<(String, &'a str)>,
+}
+
+impl Visitor for RestrictedRelations<'_> {
+    type Break = ();
+
+    fn pre_visit_table_factor(&mut self, table_factor: &TableFactor) -> ControlFlow<()> {
+        let TableFactor::Table { name, alias, .. } = table_factor else {
+            return ControlFlow::Continue(());
+        };
+        let parts = name_parts(&name.to_string());
+        let tables = self.tables;
+        for (table, columns) in tables {
+            if !names_match(&name_parts(table), &parts) {
+                continue;
+            }
+            self.names.extend(parts.last().cloned());
+            if let Some(alias) = alias {
+                self.names.push(alias.name.value.to_lowercase());
+                self.renamed.extend(
+                    alias
+                        .columns
+                        .iter()
+                        .map(|column| (column.name.value.to_lowercase(), table.as_str())),
+                );
+            }
+            self.columns.extend(
+                columns
+                    .iter()
+                    .map(|(column, access)| (column.as_str(), (table.as_str(), access))),
+            );
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+impl RestrictedRelations<'_> {
+    /// The table and rule of restricted column `name`
+    fn column(&self, name: &str) -> Option<(&str, &FieldAccess)> {
+        if let Some((table, access)) = self.columns.get(name) {
+            return Some((*table, *access));
+        }
+        self.renamed
+            .iter()
+            .find(|(column, _)| column == name)
+            .map(|(_, table)| (*table, &RENAMED_COLUMN))
+    }
+}
+
+struct ColumnGuard<'a> {
+    relations: &'a RestrictedRelations<'a>,
+}
+
+impl Visitor for ColumnGuard<'_> {
+    type Break = OxyError;
+
+    fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+        if selects_wildcard(&query.body) {
+            return ControlFlow::Break(OxyError::AuthorizationError(WILDCARD_REJECTED.to_string()));
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_expr(&mut self, expr: &Expr) -> ControlFlow<Self::Break> {
+        if let Expr::Function(function) = expr
+            && passes_wildcard(function)
+        {
+            return ControlFlow::Break(OxyError::AuthorizationError(WILDCARD_REJECTED.to_string()));
+        }
+        let ident = match expr {
+            Expr::Identifier(ident) => ident,
+            Expr::CompoundIdentifier(idents) => match idents.last() {
+                Some(ident) => ident,
+                None => return ControlFlow::Continue(()),
+            },
+            _ => return ControlFlow::Continue(()),
+        };
+        let name = ident.value.to_lowercase();
+        if let Some((table, access)) = self.relations.column(&name) {
+            let reason = match access {
+                FieldAccess::Masked(_) => "can only be read masked, through the semantic layer",
+                _ => "is restricted",
+            };
+            return ControlFlow::Break(OxyError::AuthorizationError(format!(
+                "Query rejected: column '{name}' of table '{table}' {reason}"
+            )));
+        }
+        // A bare table name or alias selects the whole row in some dialects
+        if matches!(expr, Expr::Identifier(_)) && self.relations.names.contains(&name) {
+            return ControlFlow::Break(OxyError::AuthorizationError(format!(
+                "Query rejected: '{name}' refers to whole rows of a table with restricted columns"
+            )));
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Whether `function` is passed `*` or `table.*`. `COUNT(*)` reads no
+/// values, so it is allowed.
+fn passes_wildcard(function: &Function) -> bool {
+    let FunctionArguments::List(list) = &function.args else {
+        return false;
+    };
+    let is_count = function.name.to_string().eq_ignore_ascii_case("count");
+    list.args.iter().any(|arg| {
+        let arg = match arg {
+            FunctionArg::Unnamed(arg)
+            | FunctionArg::Named { arg, .. }
+            | FunctionArg::ExprNamed { arg, .. } => arg,
+        };
+        match arg {
+            FunctionArgExpr::QualifiedWildcard(_) => true,
+            FunctionArgExpr::Wildcard => !is_count,
+            FunctionArgExpr::Expr(_) => false,
+        }
+    })
+}
+
+fn selects_wildcard(body: &SetExpr) -> bool {
+    match body {
+        SetExpr::Select(select) => select.projection.iter().any(|item| {
+            matches!(
+                item,
+                SelectItem::Wildcard(_) | SelectItem::QualifiedWildcard(..)
+            )
+        }),
+        SetExpr::SetOperation { left, right, .. } => {
+            selects_wildcard(left) || selects_wildcard(right)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxy_semantic::MaskingRule;
+
+    fn tables() -> TableRules {
+        HashMap::from([(
+            "customers".to_string(),
+            HashMap::from([
+                ("email".to_string(), FieldAccess::Masked(MaskingRule::Hash)),
+                ("ssn".to_string(), FieldAccess::Hidden),
+            ]),
+        )])
+    }
+
+    fn check(sql: &str) -> Result<(), OxyError> {
+        check_sql(&tables(), "postgres", sql)
+    }
+
+    #[test]
+    fn test_allows_unrestricted_columns() {
+        assert!(check("SELECT country, COUNT(*) FROM customers GROUP BY country").is_ok());
+        assert!(check("SELECT email FROM orders").is_ok());
+        assert!(check("SELECT * FROM orders").is_ok());
+    }
+
+    #[test]
+    fn test_rejects_restricted_columns_however_written() {
+        for sql in [
+            "SELECT ssn FROM customers",
+            "SELECT c.ssn AS id FROM public.customers c",
+            "SELECT UPPER(email) AS contact FROM customers",
+            "SELECT country FROM customers WHERE email LIKE 'a%'",
+            "WITH c AS (SELECT ssn AS x FROM customers) SELECT x FROM c",
+        ] {
+            assert!(
+                matches!(check(sql), Err(OxyError::AuthorizationError(_))),
+                "{sql}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_rejects_wildcards_and_whole_rows() {
+        for sql in [
+            "SELECT * FROM customers",
+            "SELECT c.* FROM customers c",
+            "SELECT country FROM orders UNION SELECT * FROM customers",
+            "SELECT to_json(c) FROM customers c",
+            "SELECT to_json(c.*) FROM customers c",
+            "SELECT row_to_json(customers.*) FROM customers",
+            "SELECT json_agg(c.*) FROM customers AS c GROUP BY c.country",
+        ] {
+            assert!(
+                matches!(check(sql), Err(OxyError::AuthorizationError(_))),
+                "{sql}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_rejects_columns_renamed_by_alias_lists() {
+        for sql in [
+            "SELECT b FROM customers AS x(a, b, c)",
+            "SELECT x.a FROM public.customers x(a)",
+            "SELECT country FROM customers AS x(a, b) WHERE b = '123'",
+        ] {
+            assert!(
+                matches!(check(sql), Err(OxyError::AuthorizationError(_))),
+                "{sql}"
+            );
+        }
+        assert!(check("SELECT COUNT(*) FROM customers AS x(a, b)").is_ok());
+    }
+
+    #[test]
+    fn test_restricts_matches_qualified_table_names() {
+        let guard = ColumnAccessGuard {
+            tables: tables(),
+            dialect: "postgres".to_string(),
+        };
+        assert!(guard.restricts("public.customers", "SSN"));
+        assert!(guard.restricts("\"Customers\"", "email"));
+        assert!(!guard.restricts("customers", "country"));
+        assert!(!guard.restricts("orders", "ssn"));
+    }
+
+    #[test]
+    fn test_unparseable_sql_is_rejected() {
+        assert!(matches!(
+            check("SELEC email FROM customers"),
+            Err(OxyError::AuthorizationError(_))
+        ));
+    }
+}
@@ -7,8 +7,12 @@ use crate::{
         types::{Chunk, EventKind, SQL, Table, TableReference},
     },
     observability::events,
+    theme::StyledText,
     tools::types::SQLInput,
 };
+
+use super::column_access::check_column_access;
+use super::result_cache::{ResultCache, ResultFingerprint, is_cacheable_query};
 use oxy_shared::errors::OxyError;
 
 #[derive(Debug, Clone)]
@@ -72,6 +76,7 @@ impl Executable<SQLInput> for SQLExecutable {
         let config_manager = &execution_context.workspace.config_manager;
         let secrets_manager = &execution_context.workspace.secrets_manager;
         let mut result: Result<Table, OxyError> = async {
+            check_column_access(config_manager, &input.database, &input.sql)?;
             let connector = Connector::from_database(
                 &input.database,
                 config_manager,
@@ -80,8 +85,28 @@ impl Executable<SQLInput> for SQLExecutable {
                 execution_context.filters.clone(),
                 execution_context.connections.clone(),
             )
-            .await?;
-            let file_path = connector.run_query(&input.sql).await?;
+            .await?
+            .with_user_id(execution_context.user_id)
+            .audited(execution_context.workspace.workspace_id);
+            let cached_query = CachedQuery::new(execution_context, &input, &connector).await?;
+            let file_path = match cached_query.as_ref().and_then(CachedQuery::get) {
+                Some(file_path) => {
+                    connector.audit_cached(&input.sql).await;
+                    execution_context
+                        .write_kind(EventKind::Message {
+                            message: "Using cached query result.".primary().to_string(),
+                        })
+                        .await?;
+                    file_path
+                }
+                None => {
+                    let file_path = connector.run_query(&input.sql).await?;
+                    if let Some(cached_query) = &cached_query {
+                        cached_query.put(&file_path);
+                    }
+                    file_path
+                }
+            };
             let table = Table::with_reference(
                 file_path,
                 TableReference {
@@ -146,3 +171,62 @@ impl Executable<SQLInput> for SQLExecutable {
         result
     }
 }
+
+/// A query's entry in the result cache, when the workspace has one
+struct CachedQuery {
+    cache: ResultCache,
+    fingerprint: ResultFingerprint,
+    ttl_secs: Option<u64>,
+}
+
+impl CachedQuery {
+    async fn new(
+        execution_context: &ExecutionContext,
+        input: &SQLInput,
+        connector: &Connector,
+    ) -> Result<Option<Self>, OxyError> {
+        if !is_cacheable_query(&input.sql) {
+            return Ok(None);
+        }
+        let config_manager = &execution_context.workspace.config_manager;
+        let Some(cache) = ResultCache::from_config(config_manager).await? else {
+            return Ok(None);
+        };
+        let database = config_manager.resolve_database(&input.database)?;
+        let connection = execution_context
+            .connections
+            .as_ref()
+            .and_then(|connections| connections.get(&input.database));
+        // Keyed on the SQL after access policies are applied, so rows cached
+        // for one user's bindings are never served to another
+        let sql = connector.enforce_access_policies(&input.sql)?;
+        let fingerprint = ResultFingerprint::new(
+            &database,
+            &sql,
+            input.dry_run_limit,
+            execution_context.filters.as_ref(),
+            connection,
+            config_manager.semantic_fingerprint().to_string(),
+        );
+        Ok(Some(Self {
+            cache,
+            fingerprint,
+            ttl_secs: input.cache_ttl_secs.or(database.cache_ttl_secs),
+        }))
+    }
+
+    // Cache failures never fail the query, they only cost a database round trip
+
+    fn get(&self) -> Option<String> {
+        self.cache.get(&self.fingerprint).unwrap_or_else(|e| {
+            tracing::warn!("Failed to read result cache: {}", e);
+            None
+        })
+    }
+
+    fn put(&self, file_path: &str) {
+        if let Err(e) = self.cache.put(&self.fingerprint, file_path, self.ttl_secs) {
+            tracing::warn!("Failed to write result cache: {}", e);
+        }
+    }
+}
@@ -1,4 +1,7 @@
+mod column_access;
 pub mod execute_sql;
+pub mod result_cache;
 pub mod validate_sql;
 
+pub use column_access::ColumnAccessGuard;
 pub use execute_sql::SQLExecutable;
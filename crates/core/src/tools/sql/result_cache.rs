@@ -0,0 +1,587 @@
+//! Managed cache for SQL query results.
+//!
+//! Results are stored as Arrow IPC files under `<state_dir>/cache/results`,
+//! keyed on the SQL text, the database, the dry-run limit and any session
+//! filters or connection overrides. `index.json` next to them records when
+//! each entry was computed, which database config and semantic layer it was
+//! computed against, and per-database hit statistics.
+//!
+//! An entry is dropped when its TTL passes, when the database config changes,
+//! or when any semantic view or topic changes. Once the cache grows past its
+//! size limit, the least recently used entries are evicted.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use once_cell::sync::Lazy;
+use oxy_semantic::hash_string;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    adapters::session_filters::SessionFilters,
+    config::{
+        ConfigManager,
+        constants::CACHE_SOURCE,
+        model::{ConnectionOverride, Database},
+    },
+};
+use oxy_shared::errors::OxyError;
+
+pub const RESULT_CACHE_DIR: &str = "results";
+const INDEX_FILE: &str = "index.json";
+
+/// Serializes index updates from concurrent queries in this process
+static INDEX_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Identifies a query result and the state it was computed against
+#[derive(Debug, Clone)]
+pub struct ResultFingerprint {
+    pub key: String,
+    pub database: String,
+    pub database_hash: String,
+    pub semantic_hash: String,
+}
+
+impl ResultFingerprint {
+    pub fn new(
+        database: &Database,
+        sql: &str,
+        dry_run_limit: Option<u64>,
+        filters: Option<&SessionFilters>,
+        connection: Option<&ConnectionOverride>,
+        semantic_hash: String,
+    ) -> Self {
+        // The config and semantic hashes are left out of the key so a stale
+        // entry is found, and dropped, instead of lingering until evicted
+        let filters: Option<BTreeMap<&String, &serde_json::Value>> =
+            filters.map(|filters| filters.0.iter().collect());
+        let key_material = serde_json::json!({
+            "database": database.name,
+            "sql": sql,
+            "dry_run_limit": dry_run_limit,
+            "filters": filters,
+            "connection": connection,
+        });
+        Self {
+            key: hash_string(&key_material.to_string()),
+            database: database.name.clone(),
+            database_hash: database_fingerprint(database),
+            semantic_hash,
+        }
+    }
+}
+
+/// Hit statistics for one database
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Entries dropped because their TTL passed
+    pub expired: u64,
+    /// Entries dropped because the database config or semantic layer changed
+    pub invalidated: u64,
+    /// Entries evicted to stay under the size limit
+    pub evicted: u64,
+}
+
+impl CacheStats {
+    pub fn hit_rate(&self) -> Option<f64> {
+        let lookups = self.hits + self.misses;
+        (lookups > 0).then(|| self.hits as f64 / lookups as f64)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    database: String,
+    size_bytes: u64,
+    created_at_ms: u64,
+    last_accessed_at_ms: u64,
+    expires_at_ms: Option<u64>,
+    database_hash: String,
+    semantic_hash: String,
+    hits: u64,
+}
+
+enum Lookup {
+    Missing,
+    Stale,
+    Expired,
+    Fresh,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheIndex {
+    entries: HashMap<String, CacheEntry>,
+    stats: BTreeMap<String, CacheStats>,
+}
+
+impl CacheIndex {
+    fn size_bytes(&self) -> u64 {
+        self.entries.values().map(|entry| entry.size_bytes).sum()
+    }
+
+    fn stats_mut(&mut self, database: &str) -> &mut CacheStats {
+        self.stats.entry(database.to_string()).or_default()
+    }
+}
+
+/// Summary of the cache contents and hit statistics
+#[derive(Debug, Clone, Default)]
+pub struct ResultCacheReport {
+    pub entries: usize,
+    pub size_bytes: u64,
+    pub stats: BTreeMap<String, CacheStats>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResultCache {
+    dir: PathBuf,
+    max_size_bytes: u64,
+    ttl_secs: Option<u64>,
+}
+
+impl ResultCache {
+    pub fn new(dir: PathBuf, max_size_bytes: u64, ttl_secs: Option<u64>) -> Self {
+        Self {
+            dir,
+            max_size_bytes,
+            ttl_secs,
+        }
+    }
+
+    /// Directory holding cached results inside a state directory
+    pub fn dir_for(state_dir: &Path) -> PathBuf {
+        state_dir.join(CACHE_SOURCE).join(RESULT_CACHE_DIR)
+    }
+
+    /// Opens the workspace's result cache, or `None` when `result_cache`
+    /// is not configured or disabled
+    pub async fn from_config(config_manager: &ConfigManager) -> Result<Option<Self>, OxyError> {
+        let Some(config) = config_manager
+            .get_config()
+            .result_cache
+            .as_ref()
+            .filter(|config| config.enabled)
+        else {
+            return Ok(None);
+        };
+        let state_dir = config_manager.resolve_state_dir().await?;
+        Ok(Some(Self::new(
+            Self::dir_for(&state_dir),
+            config.max_size_mb.saturating_mul(1024 * 1024),
+            config.ttl_secs,
+        )))
+    }
+
+    /// Looks up a result, copying it to a new temporary Arrow file so callers
+    /// can treat it like a fresh query result. Expired and stale entries are
+    /// dropped and count as misses.
+    pub fn get(&self, fingerprint: &ResultFingerprint) -> Result<Option<String>, OxyError> {
+        let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut index = self.load_index();
+        let now = now_ms();
+
+        let lookup = match index.entries.get(&fingerprint.key) {
+            None => Lookup::Missing,
+            Some(entry)
+                if entry.database_hash != fingerprint.database_hash
+                    || entry.semantic_hash != fingerprint.semantic_hash =>
+            {
+                Lookup::Stale
+            }
+            Some(entry) if entry.expires_at_ms.is_some_and(|expires| expires <= now) => {
+                Lookup::Expired
+            }
+            Some(_) => Lookup::Fresh,
+        };
+
+        let cached = match lookup {
+            Lookup::Missing => None,
+            Lookup::Stale => {
+                self.remove_entry(&mut index, &fingerprint.key);
+                index.stats_mut(&fingerprint.database).invalidated += 1;
+                None
+            }
+            Lookup::Expired => {
+                self.remove_entry(&mut index, &fingerprint.key);
+                index.stats_mut(&fingerprint.database).expired += 1;
+                None
+            }
+            Lookup::Fresh => {
+                let file_path = std::env::temp_dir().join(format!("{}.arrow", Uuid::new_v4()));
+                match fs::copy(self.entry_path(&fingerprint.key), &file_path) {
+                    Ok(_) => Some(file_path.to_string_lossy().to_string()),
+                    Err(e) => {
+                        tracing::warn!("Dropping unreadable cached result: {}", e);
+                        self.remove_entry(&mut index, &fingerprint.key);
+                        None
+                    }
+                }
+            }
+        };
+
+        match &cached {
+            Some(_) => {
+                if let Some(entry) = index.entries.get_mut(&fingerprint.key) {
+                    entry.last_accessed_at_ms = now;
+                    entry.hits += 1;
+                }
+                index.stats_mut(&fingerprint.database).hits += 1;
+            }
+            None => index.stats_mut(&fingerprint.database).misses += 1,
+        }
+        self.save_index(&index)?;
+        Ok(cached)
+    }
+
+    /// Stores the Arrow file at `file_path` as the result for `fingerprint`.
+    /// `ttl_secs` overrides the cache-wide TTL.
+    pub fn put(
+        &self,
+        fingerprint: &ResultFingerprint,
+        file_path: &str,
+        ttl_secs: Option<u64>,
+    ) -> Result<(), OxyError> {
+        let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let size_bytes = fs::metadata(file_path)
+            .map_err(|e| OxyError::IOError(format!("Failed to read query result: {e}")))?
+            .len();
+        if size_bytes > self.max_size_bytes {
+            tracing::debug!(
+                "Query result of {} bytes exceeds the result cache limit, not caching",
+                size_bytes
+            );
+            return Ok(());
+        }
+
+        let mut index = self.load_index();
+        let now = now_ms();
+        self.sweep(&mut index, fingerprint, now);
+
+        fs::create_dir_all(&self.dir).map_err(|e| {
+            OxyError::IOError(format!(
+                "Failed to create result cache directory {}: {}",
+                self.dir.display(),
+                e
+            ))
+        })?;
+        fs::copy(file_path, self.entry_path(&fingerprint.key))
+            .map_err(|e| OxyError::IOError(format!("Failed to write cached result: {e}")))?;
+
+        let ttl_secs = ttl_secs.or(self.ttl_secs);
+        index.entries.insert(
+            fingerprint.key.clone(),
+            CacheEntry {
+                database: fingerprint.database.clone(),
+                size_bytes,
+                created_at_ms: now,
+                last_accessed_at_ms: now,
+                expires_at_ms: ttl_secs.map(|ttl| now.saturating_add(ttl.saturating_mul(1000))),
+                database_hash: fingerprint.database_hash.clone(),
+                semantic_hash: fingerprint.semantic_hash.clone(),
+                hits: 0,
+            },
+        );
+        self.evict(&mut index);
+        self.save_index(&index)
+    }
+
+    pub fn report(&self) -> ResultCacheReport {
+        let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let index = self.load_index();
+        ResultCacheReport {
+            entries: index.entries.len(),
+            size_bytes: index.size_bytes(),
+            stats: index.stats,
+        }
+    }
+
+    /// Drops expired entries, and entries computed against a database config
+    /// or semantic layer that has since changed
+    fn sweep(&self, index: &mut CacheIndex, fingerprint: &ResultFingerprint, now: u64) {
+        let mut dropped = Vec::new();
+        for (key, entry) in &index.entries {
+            if entry.expires_at_ms.is_some_and(|expires| expires <= now) {
+                dropped.push((key.clone(), entry.database.clone(), true));
+            } else if entry.semantic_hash != fingerprint.semantic_hash
+                || (entry.database == fingerprint.database
+                    && entry.database_hash != fingerprint.database_hash)
+            {
+                dropped.push((key.clone(), entry.database.clone(), false));
+            }
+        }
+        for (key, database, expired) in dropped {
+            self.remove_entry(index, &key);
+            let stats = index.stats_mut(&database);
+            if expired {
+                stats.expired += 1;
+            } else {
+                stats.invalidated += 1;
+            }
+        }
+    }
+
+    /// Evicts least recently used entries until the cache fits its size limit
+    fn evict(&self, index: &mut CacheIndex) {
+        let mut size_bytes = index.size_bytes();
+        if size_bytes <= self.max_size_bytes {
+            return;
+        }
+        let mut by_last_access: Vec<(String, u64)> = index
+            .entries
+            .iter()
+            .map(|(key, entry)| (key.clone(), entry.last_accessed_at_ms))
+            .collect();
+        by_last_access.sort_by_key(|(_, last_accessed)| *last_accessed);
+
+        for (key, _) in by_last_access {
+            if size_bytes <= self.max_size_bytes {
+                break;
+            }
+            if let Some(entry) = self.remove_entry(index, &key) {
+                size_bytes = size_bytes.saturating_sub(entry.size_bytes);
+                index.stats_mut(&entry.database).evicted += 1;
+            }
+        }
+    }
+
+    fn remove_entry(&self, index: &mut CacheIndex, key: &str) -> Option<CacheEntry> {
+        let entry = index.entries.remove(key)?;
+        if let Err(e) = fs::remove_file(self.entry_path(key))
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            tracing::warn!("Failed to remove cached result {}: {}", key, e);
+        }
+        Some(entry)
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.arrow"))
+    }
+
+    fn load_index(&self) -> CacheIndex {
+        let index_path = self.dir.join(INDEX_FILE);
+        let Ok(content) = fs::read_to_string(&index_path) else {
+            return CacheIndex::default();
+        };
+        match serde_json::from_str(&content) {
+            Ok(index) => index,
+            Err(e) => {
+                // Without the index the cached files can't be attributed, so
+                // start over rather than leave them outside the size limit
+                tracing::warn!("Resetting corrupted result cache index: {}", e);
+                if let Err(e) = fs::remove_dir_all(&self.dir) {
+                    tracing::warn!("Failed to clear result cache: {}", e);
+                }
+                CacheIndex::default()
+            }
+        }
+    }
+
+    fn save_index(&self, index: &CacheIndex) -> Result<(), OxyError> {
+        fs::create_dir_all(&self.dir).map_err(|e| {
+            OxyError::IOError(format!(
+                "Failed to create result cache directory {}: {}",
+                self.dir.display(),
+                e
+            ))
+        })?;
+        let json = serde_json::to_string(index).map_err(|e| {
+            OxyError::SerializerError(format!("Failed to serialize result cache index: {e}"))
+        })?;
+        // Write then rename so a crash never leaves a truncated index behind
+        let tmp_path = self.dir.join(format!("{INDEX_FILE}.tmp"));
+        fs::write(&tmp_path, json)
+            .and_then(|_| fs::rename(&tmp_path, self.dir.join(INDEX_FILE)))
+            .map_err(|e| OxyError::IOError(format!("Failed to write result cache index: {e}")))
+    }
+}
+
+/// Hash of a database's connection settings. Cache TTLs are left out so
+/// tuning them doesn't throw away cached results.
+pub fn database_fingerprint(database: &Database) -> String {
+    let config = serde_json::to_value(&database.database_type).unwrap_or_default();
+    hash_string(&config.to_string())
+}
+
+/// Only plain reads are cached; anything else must reach the database
+pub fn is_cacheable_query(sql: &str) -> bool {
+    let mut sql = sql.trim_start();
+    loop {
+        if let Some(rest) = sql.strip_prefix("--") {
+            sql = rest
+                .split_once('\n')
+                .map_or("", |(_, rest)| rest)
+                .trim_start();
+        } else if let Some(rest) = sql.strip_prefix("/*") {
+            sql = rest
+                .split_once("*/")
+                .map_or("", |(_, rest)| rest)
+                .trim_start();
+        } else {
+            break;
+        }
+    }
+    let keyword: String = sql
+        .chars()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect();
+    keyword.eq_ignore_ascii_case("select") || keyword.eq_ignore_ascii_case("with")
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::model::{DatabaseType, Sqlite};
+
+    fn database(path: &str) -> Database {
+        Database {
+            name: "local".to_string(),
+            database_type: DatabaseType::Sqlite(Sqlite {
+                path: path.to_string(),
+                read_only: true,
+            }),
+            cache_ttl_secs: None,
+        }
+    }
+
+    fn fingerprint(sql: &str, semantic_hash: &str) -> ResultFingerprint {
+        ResultFingerprint::new(
+            &database("local.db"),
+            sql,
+            None,
+            None,
+            None,
+            semantic_hash.to_string(),
+        )
+    }
+
+    fn write_result(dir: &Path, name: &str, size: usize) -> String {
+        let path = dir.join(name);
+        fs::write(&path, vec![0u8; size]).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_is_cacheable_query() {
+        assert!(is_cacheable_query("SELECT 1"));
+        assert!(is_cacheable_query(
+            "-- revenue\n/* by month */ with t as (select 1) select * from t"
+        ));
+        assert!(!is_cacheable_query("insert into t values (1)"));
+        assert!(!is_cacheable_query("-- select\ndelete from t"));
+    }
+
+    #[test]
+    fn test_hit_after_put() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ResultCache::new(dir.path().join("results"), 1024, None);
+        let fingerprint = fingerprint("select 1", "semantic");
+
+        assert!(cache.get(&fingerprint).unwrap().is_none());
+        let result = write_result(dir.path(), "result.arrow", 10);
+        cache.put(&fingerprint, &result, None).unwrap();
+
+        let cached = cache.get(&fingerprint).unwrap().unwrap();
+        assert_eq!(fs::read(&cached).unwrap().len(), 10);
+        fs::remove_file(cached).unwrap();
+
+        let stats = &cache.report().stats["local"];
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+        assert_eq!(stats.hit_rate(), Some(0.5));
+    }
+
+    #[test]
+    fn test_semantic_change_invalidates() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ResultCache::new(dir.path().join("results"), 1024, None);
+        let result = write_result(dir.path(), "result.arrow", 10);
+        cache
+            .put(&fingerprint("select 1", "before"), &result, None)
+            .unwrap();
+
+        assert!(
+            cache
+                .get(&fingerprint("select 1", "after"))
+                .unwrap()
+                .is_none()
+        );
+        let report = cache.report();
+        assert_eq!(report.entries, 0);
+        assert_eq!(report.stats["local"].invalidated, 1);
+    }
+
+    #[test]
+    fn test_database_change_invalidates() {
+        let before = ResultFingerprint::new(
+            &database("a.db"),
+            "select 1",
+            None,
+            None,
+            None,
+            String::new(),
+        );
+        let after = ResultFingerprint::new(
+            &database("b.db"),
+            "select 1",
+            None,
+            None,
+            None,
+            String::new(),
+        );
+        assert_eq!(before.key, after.key);
+        assert_ne!(before.database_hash, after.database_hash);
+
+        let mut tuned = database("a.db");
+        tuned.cache_ttl_secs = Some(60);
+        assert_eq!(database_fingerprint(&tuned), before.database_hash);
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ResultCache::new(dir.path().join("results"), 25, None);
+        let result = write_result(dir.path(), "result.arrow", 10);
+
+        cache.put(&fingerprint("a", ""), &result, None).unwrap();
+        cache.put(&fingerprint("b", ""), &result, None).unwrap();
+        // Touch "a" so "b" becomes the least recently used
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let cached = cache.get(&fingerprint("a", "")).unwrap().unwrap();
+        fs::remove_file(cached).unwrap();
+        cache.put(&fingerprint("c", ""), &result, None).unwrap();
+
+        assert!(cache.get(&fingerprint("b", "")).unwrap().is_none());
+        let cached = cache.get(&fingerprint("a", "")).unwrap().unwrap();
+        fs::remove_file(cached).unwrap();
+        let report = cache.report();
+        assert_eq!(report.entries, 2);
+        assert_eq!(report.size_bytes, 20);
+        assert_eq!(report.stats["local"].evicted, 1);
+    }
+
+    #[test]
+    fn test_expired_entry_is_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ResultCache::new(dir.path().join("results"), 1024, Some(3600));
+        let result = write_result(dir.path(), "result.arrow", 10);
+        let fingerprint = fingerprint("select 1", "");
+
+        // A zero TTL expires immediately and takes precedence over the default
+        cache.put(&fingerprint, &result, Some(0)).unwrap();
+        assert!(cache.get(&fingerprint).unwrap().is_none());
+        assert_eq!(cache.report().stats["local"].expired, 1);
+    }
+}
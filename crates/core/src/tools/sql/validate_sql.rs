@@ -45,7 +45,8 @@ impl Executable<SQLInput> for ValidateSQLExecutable {
             execution_context.filters.clone(),
             execution_context.connections.clone(),
         )
-        .await?;
+        .await?
+        .with_user_id(execution_context.user_id);
         let result = match connector.explain_query(&input.sql).await {
             Ok(_) => Output::Bool(true),
             Err(err) => {
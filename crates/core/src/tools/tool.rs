@@ -450,6 +450,7 @@ impl ParamMapper<SQLToolInput, SQLInput> for SQLMapper {
                 dry_run_limit,
                 name: None,
                 persist,
+                cache_ttl_secs: None,
             },
             None,
         ))
@@ -55,6 +55,8 @@ pub struct SQLInput {
     pub sql: String,
     pub dry_run_limit: Option<u64>,
     pub persist: bool,
+    /// Overrides the result cache TTL for this query
+    pub cache_ttl_secs: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -1,3 +1,4 @@
+pub mod render;
 pub mod types;
 pub mod visualize;
 
@@ -0,0 +1,423 @@
+//! Line and bar charts on a category × value grid.
+//!
+//! The category axis is normally `xAxis`; when only `yAxis` is a category
+//! axis the chart is laid out horizontally, as echarts does.
+
+use serde_json::Value;
+
+use super::svg::{
+    Anchor, GRID_COLOR, MUTED_COLOR, Svg, TextStyle, color, format_tick, nice_ticks, text_width,
+    truncate,
+};
+use super::{as_label, as_number, draw_title, title_height};
+use crate::tools::visualize::types::{AxisConfig, ChartType, VisualizeParams};
+
+const LABEL_SIZE: f64 = 12.0;
+const MAX_CATEGORY_LABEL_WIDTH: f64 = 140.0;
+/// Labels are rotated by this much once they no longer fit side by side.
+const LABEL_ROTATION: f64 = 35.0;
+
+struct Series {
+    name: String,
+    is_bar: bool,
+    /// `(category index, value)` in data order; `None` values break lines.
+    points: Vec<(usize, Option<f64>)>,
+}
+
+/// Maps category slots and values onto canvas coordinates.
+struct Frame {
+    left: f64,
+    top: f64,
+    right: f64,
+    bottom: f64,
+    slots: usize,
+    min: f64,
+    max: f64,
+    horizontal: bool,
+}
+
+impl Frame {
+    fn slot(&self) -> f64 {
+        let span = if self.horizontal {
+            self.bottom - self.top
+        } else {
+            self.right - self.left
+        };
+        span / self.slots.max(1) as f64
+    }
+
+    /// Category-axis coordinate where slot `index` begins. Horizontal charts
+    /// put the first category at the bottom.
+    fn slot_start(&self, index: usize) -> f64 {
+        if self.horizontal {
+            self.bottom - (index + 1) as f64 * self.slot()
+        } else {
+            self.left + index as f64 * self.slot()
+        }
+    }
+
+    fn slot_center(&self, index: usize) -> f64 {
+        self.slot_start(index) + self.slot() / 2.0
+    }
+
+    fn value(&self, value: f64) -> f64 {
+        let t = (value - self.min) / (self.max - self.min);
+        if self.horizontal {
+            self.left + t * (self.right - self.left)
+        } else {
+            self.bottom - t * (self.bottom - self.top)
+        }
+    }
+
+    fn point(&self, category: f64, value: f64) -> (f64, f64) {
+        if self.horizontal {
+            (value, category)
+        } else {
+            (category, value)
+        }
+    }
+}
+
+pub(super) fn render(params: &VisualizeParams, width: f64, height: f64) -> String {
+    let horizontal = is_category(params.y_axis.as_ref()) && !is_category(params.x_axis.as_ref());
+    let (category_axis, value_axis) = if horizontal {
+        (params.y_axis.as_ref(), params.x_axis.as_ref())
+    } else {
+        (params.x_axis.as_ref(), params.y_axis.as_ref())
+    };
+
+    let mut categories: Vec<String> = category_axis
+        .and_then(|a| a.data.as_ref())
+        .map(|data| data.iter().map(as_label).collect())
+        .unwrap_or_default();
+    let series: Vec<Series> = params
+        .series
+        .iter()
+        .filter(|s| !matches!(s.series_type, ChartType::Pie))
+        .enumerate()
+        .map(|(i, s)| {
+            let data = s.data.as_deref().unwrap_or_default();
+            let points = data
+                .iter()
+                .enumerate()
+                .map(|(j, item)| {
+                    let (category, value) = split_point(item, horizontal);
+                    let index = match category {
+                        Some(label) => match categories.iter().position(|c| *c == label) {
+                            Some(index) => index,
+                            None => {
+                                categories.push(label);
+                                categories.len() - 1
+                            }
+                        },
+                        None => j,
+                    };
+                    (index, value)
+                })
+                .collect();
+            Series {
+                name: s
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| format!("Series {}", i + 1)),
+                is_bar: matches!(s.series_type, ChartType::Bar),
+                points,
+            }
+        })
+        .collect();
+
+    // Positional data may run past the declared categories.
+    let slots = series
+        .iter()
+        .flat_map(|s| s.points.iter().map(|(i, _)| i + 1))
+        .max()
+        .unwrap_or(0)
+        .max(categories.len());
+    while categories.len() < slots {
+        categories.push((categories.len() + 1).to_string());
+    }
+
+    let values = series
+        .iter()
+        .flat_map(|s| s.points.iter().filter_map(|(_, v)| *v));
+    let (min, max) = values.fold((0.0f64, 0.0f64), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    let ticks = nice_ticks(min, max, 5);
+    let tick_labels: Vec<String> = ticks.iter().map(|t| format_tick(*t)).collect();
+    let category_labels: Vec<String> = categories
+        .iter()
+        .map(|c| truncate(c, MAX_CATEGORY_LABEL_WIDTH, LABEL_SIZE))
+        .collect();
+
+    let mut doc = Svg::new(width, height);
+    let title = params.title.as_deref().filter(|t| !t.is_empty());
+    if let Some(title) = title {
+        draw_title(&mut doc, title, width);
+    }
+    let mut top = title_height(title);
+    if series.len() > 1 {
+        draw_legend(&mut doc, &series, width, top + 4.0);
+        top += 32.0;
+    }
+    top += 8.0;
+
+    let category_name = axis_name(category_axis);
+    let value_name = axis_name(value_axis);
+    let widest = |labels: &[String]| {
+        labels
+            .iter()
+            .map(|l| text_width(l, LABEL_SIZE))
+            .fold(0.0, f64::max)
+    };
+
+    // The axis on the left holds value ticks (vertical) or category labels
+    // (horizontal); the bottom axis holds the other.
+    let (left_labels, left_name) = if horizontal {
+        (widest(&category_labels), category_name)
+    } else {
+        (widest(&tick_labels), value_name)
+    };
+    let left = 16.0 + left_labels + 10.0 + if left_name.is_some() { 24.0 } else { 0.0 };
+    let right = width - 32.0;
+
+    let mut frame = Frame {
+        left,
+        top,
+        right,
+        bottom: height,
+        slots,
+        min: ticks[0],
+        max: ticks[ticks.len() - 1],
+        horizontal,
+    };
+    let rotate = !horizontal && widest(&category_labels) > frame.slot() * 0.9;
+    let bottom_labels = if rotate {
+        widest(&category_labels) * LABEL_ROTATION.to_radians().sin() + 20.0
+    } else {
+        22.0
+    };
+    let bottom_name = if horizontal {
+        value_name
+    } else {
+        category_name
+    };
+    frame.bottom = height - 12.0 - bottom_labels - if bottom_name.is_some() { 24.0 } else { 0.0 };
+
+    draw_value_axis(&mut doc, &frame, &ticks, &tick_labels);
+    draw_category_axis(&mut doc, &frame, &category_labels, rotate);
+    draw_axis_names(&mut doc, &frame, left_name, bottom_name, height);
+
+    let bar_count = series.iter().filter(|s| s.is_bar).count();
+    let mut bar_index = 0;
+    for (i, s) in series.iter().enumerate() {
+        if s.is_bar {
+            draw_bars(&mut doc, &frame, s, color(i), bar_index, bar_count);
+            bar_index += 1;
+        }
+    }
+    // Lines go on top of bars.
+    for (i, s) in series.iter().enumerate() {
+        if !s.is_bar {
+            draw_line(&mut doc, &frame, s, color(i));
+        }
+    }
+    doc.finish()
+}
+
+fn is_category(axis: Option<&AxisConfig>) -> bool {
+    axis.is_some_and(|a| a.axis_type == "category")
+}
+
+fn axis_name(axis: Option<&AxisConfig>) -> Option<&str> {
+    axis.and_then(|a| a.name.as_deref())
+        .filter(|n| !n.is_empty())
+}
+
+/// Split a data item into its category (for `[x, y]` pairs and `{name,
+/// value}` objects) and value.
+fn split_point(item: &Value, horizontal: bool) -> (Option<String>, Option<f64>) {
+    match item {
+        Value::Array(pair) if pair.len() >= 2 => {
+            let (category, value) = if horizontal {
+                (&pair[1], &pair[0])
+            } else {
+                (&pair[0], &pair[1])
+            };
+            (Some(as_label(category)), as_number(value))
+        }
+        Value::Object(obj) => (
+            obj.get("name").map(as_label),
+            obj.get("value").and_then(as_number),
+        ),
+        other => (None, as_number(other)),
+    }
+}
+
+fn draw_legend(doc: &mut Svg, series: &[Series], width: f64, y: f64) {
+    const SWATCH: f64 = 14.0;
+    const GAP: f64 = 18.0;
+    let names: Vec<String> = series
+        .iter()
+        .map(|s| truncate(&s.name, 200.0, LABEL_SIZE))
+        .collect();
+    let total: f64 = names
+        .iter()
+        .map(|n| SWATCH + 6.0 + text_width(n, LABEL_SIZE) + GAP)
+        .sum::<f64>()
+        - GAP;
+    let mut x = ((width - total) / 2.0).max(16.0);
+    for (i, name) in names.iter().enumerate() {
+        doc.rect(x, y + 3.0, SWATCH, 10.0, color(i));
+        doc.text(x + SWATCH + 6.0, y + 12.0, name, TextStyle::new(LABEL_SIZE));
+        x += SWATCH + 6.0 + text_width(name, LABEL_SIZE) + GAP;
+    }
+}
+
+fn draw_value_axis(doc: &mut Svg, frame: &Frame, ticks: &[f64], labels: &[String]) {
+    for (tick, label) in ticks.iter().zip(labels) {
+        let pos = frame.value(*tick);
+        let style = TextStyle::new(LABEL_SIZE).fill(MUTED_COLOR);
+        if frame.horizontal {
+            doc.line(pos, frame.top, pos, frame.bottom, GRID_COLOR, 1.0);
+            doc.text(
+                pos,
+                frame.bottom + 18.0,
+                label,
+                style.anchor(Anchor::Middle),
+            );
+        } else {
+            doc.line(frame.left, pos, frame.right, pos, GRID_COLOR, 1.0);
+            doc.text(
+                frame.left - 8.0,
+                pos + 4.0,
+                label,
+                style.anchor(Anchor::End),
+            );
+        }
+    }
+}
+
+fn draw_category_axis(doc: &mut Svg, frame: &Frame, labels: &[String], rotate: bool) {
+    // The category axis line sits on zero, like echarts.
+    let zero = frame.value(0.0);
+    if frame.horizontal {
+        doc.line(zero, frame.top, zero, frame.bottom, MUTED_COLOR, 1.0);
+    } else {
+        doc.line(frame.left, zero, frame.right, zero, MUTED_COLOR, 1.0);
+    }
+
+    // Thin labels out when there are more than fit, keeping every n-th.
+    let per_label = if frame.horizontal || rotate {
+        LABEL_SIZE + 4.0
+    } else {
+        labels
+            .iter()
+            .map(|l| text_width(l, LABEL_SIZE))
+            .fold(0.0, f64::max)
+            + 8.0
+    };
+    let step = (per_label / frame.slot()).ceil().max(1.0) as usize;
+
+    for (i, label) in labels.iter().enumerate().step_by(step) {
+        let center = frame.slot_center(i);
+        let style = TextStyle::new(LABEL_SIZE).fill(MUTED_COLOR);
+        if frame.horizontal {
+            doc.text(
+                frame.left - 8.0,
+                center + 4.0,
+                label,
+                style.anchor(Anchor::End),
+            );
+        } else if rotate {
+            doc.text(
+                center,
+                frame.bottom + 14.0,
+                label,
+                style.anchor(Anchor::End).rotate(-LABEL_ROTATION),
+            );
+        } else {
+            doc.text(
+                center,
+                frame.bottom + 18.0,
+                label,
+                style.anchor(Anchor::Middle),
+            );
+        }
+    }
+}
+
+fn draw_axis_names(
+    doc: &mut Svg,
+    frame: &Frame,
+    left: Option<&str>,
+    bottom: Option<&str>,
+    height: f64,
+) {
+    let style = TextStyle::new(13.0).anchor(Anchor::Middle);
+    if let Some(name) = left {
+        let y = (frame.top + frame.bottom) / 2.0;
+        let name = truncate(name, frame.bottom - frame.top, 13.0);
+        doc.text(22.0, y, &name, style.rotate(-90.0));
+    }
+    if let Some(name) = bottom {
+        let x = (frame.left + frame.right) / 2.0;
+        let name = truncate(name, frame.right - frame.left, 13.0);
+        doc.text(x, height - 16.0, &name, style);
+    }
+}
+
+fn draw_bars(
+    doc: &mut Svg,
+    frame: &Frame,
+    series: &Series,
+    fill: &str,
+    index: usize,
+    count: usize,
+) {
+    let group = frame.slot() * 0.7;
+    let thickness = group / count.max(1) as f64;
+    let zero = frame.value(0.0);
+    for (slot, value) in &series.points {
+        let Some(value) = value else { continue };
+        let start = frame.slot_start(*slot) + frame.slot() * 0.15 + index as f64 * thickness;
+        let end = frame.value(*value);
+        let (x1, y1) = frame.point(start, zero);
+        let (x2, y2) = frame.point(start + thickness, end);
+        doc.rect(
+            x1.min(x2),
+            y1.min(y2),
+            (x2 - x1).abs(),
+            (y2 - y1).abs(),
+            fill,
+        );
+    }
+}
+
+fn draw_line(doc: &mut Svg, frame: &Frame, series: &Series, stroke: &str) {
+    let mut segment: Vec<(f64, f64)> = Vec::new();
+    let mut markers = Vec::new();
+    for (slot, value) in &series.points {
+        match value {
+            Some(value) => {
+                let point = frame.point(frame.slot_center(*slot), frame.value(*value));
+                segment.push(point);
+                markers.push(point);
+            }
+            None => flush_segment(doc, &mut segment, stroke),
+        }
+    }
+    flush_segment(doc, &mut segment, stroke);
+
+    // Markers only while they stay readable.
+    if markers.len() <= 60 {
+        for (x, y) in markers {
+            doc.circle(x, y, 3.0, "#ffffff", stroke);
+        }
+    }
+}
+
+fn flush_segment(doc: &mut Svg, segment: &mut Vec<(f64, f64)>, stroke: &str) {
+    if segment.len() > 1 {
+        doc.polyline(segment, stroke, 2.0);
+    }
+    segment.clear();
+}
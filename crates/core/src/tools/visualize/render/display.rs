@@ -0,0 +1,277 @@
+//! Chart documents for app displays.
+//!
+//! Mirrors the aggregation the web app runs in the browser, so exported
+//! images match what users see: the x axis is the distinct `x` values in
+//! sort order and `y` is summed per x value, split into one series per
+//! distinct `series` value when set. Pie slices sum `value` per `name`.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
+
+use serde_json::{Map, Value};
+
+use super::{ChartDocument, TableSpec, as_label, as_number};
+use crate::config::model::Display;
+use crate::tools::visualize::types::{AxisConfig, ChartType, SeriesConfig, VisualizeParams};
+
+impl ChartDocument {
+    /// Build the document for a chart or table display from the rows of the
+    /// task it references. Other display kinds have no image form.
+    pub fn from_display(display: &Display, rows: &[Value]) -> Option<Self> {
+        let rows: Vec<&Map<String, Value>> = rows.iter().filter_map(Value::as_object).collect();
+        let doc = match display {
+            Display::LineChart(chart) => ChartDocument::Chart(cartesian(
+                &rows,
+                &chart.x,
+                &chart.y,
+                chart.series.as_deref(),
+                ChartType::Line,
+                chart.title.clone(),
+                (chart.x_axis_label.clone(), chart.y_axis_label.clone()),
+            )),
+            Display::BarChart(chart) => ChartDocument::Chart(cartesian(
+                &rows,
+                &chart.x,
+                &chart.y,
+                chart.series.as_deref(),
+                ChartType::Bar,
+                chart.title.clone(),
+                (None, None),
+            )),
+            Display::PieChart(chart) => {
+                ChartDocument::Chart(pie(&rows, &chart.name, &chart.value, chart.title.clone()))
+            }
+            Display::Table(table) => {
+                let columns: Vec<String> = rows
+                    .first()
+                    .map(|row| row.keys().cloned().collect())
+                    .unwrap_or_default();
+                let rows = rows
+                    .iter()
+                    .map(|row| columns.iter().map(|c| field(row, c).clone()).collect())
+                    .collect();
+                ChartDocument::Table(TableSpec {
+                    title: table.title.clone(),
+                    columns,
+                    rows,
+                })
+            }
+            _ => return None,
+        };
+        Some(doc)
+    }
+}
+
+fn cartesian(
+    rows: &[&Map<String, Value>],
+    x: &str,
+    y: &str,
+    series: Option<&str>,
+    kind: ChartType,
+    title: Option<String>,
+    (x_name, y_name): (Option<String>, Option<String>),
+) -> VisualizeParams {
+    let categories = distinct_sorted(rows.iter().map(|row| field(row, x)));
+    let position: HashMap<String, usize> = categories
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (as_label(c), i))
+        .collect();
+
+    // Series in first-seen order, one per distinct `series` value.
+    let mut names: Vec<String> = Vec::new();
+    let mut sums: Vec<Vec<Option<f64>>> = Vec::new();
+    for row in rows {
+        let name = match series {
+            Some(series) => as_label(field(row, series)),
+            None => y.to_string(),
+        };
+        let index = match names.iter().position(|n| *n == name) {
+            Some(index) => index,
+            None => {
+                names.push(name);
+                sums.push(vec![None; categories.len()]);
+                names.len() - 1
+            }
+        };
+        let slot = position[&as_label(field(row, x))];
+        if let Some(value) = as_number(field(row, y)) {
+            let sum = &mut sums[index][slot];
+            *sum = Some(sum.unwrap_or(0.0) + value);
+        }
+    }
+
+    VisualizeParams {
+        x_axis: Some(AxisConfig {
+            axis_type: "category".to_string(),
+            name: x_name,
+            data: Some(categories),
+        }),
+        y_axis: Some(AxisConfig {
+            axis_type: "value".to_string(),
+            name: y_name,
+            data: None,
+        }),
+        series: names
+            .into_iter()
+            .zip(sums)
+            .map(|(name, sums)| SeriesConfig {
+                name: Some(name),
+                series_type: kind.clone(),
+                data: Some(sums.into_iter().map(number).collect()),
+            })
+            .collect(),
+        title,
+    }
+}
+
+fn pie(
+    rows: &[&Map<String, Value>],
+    name: &str,
+    value: &str,
+    title: Option<String>,
+) -> VisualizeParams {
+    let names = distinct_sorted(rows.iter().map(|row| field(row, name)));
+    let data = names
+        .iter()
+        .map(|slice| {
+            let label = as_label(slice);
+            let total: f64 = rows
+                .iter()
+                .filter(|row| as_label(field(row, name)) == label)
+                .filter_map(|row| as_number(field(row, value)))
+                .sum();
+            serde_json::json!({ "name": label, "value": total })
+        })
+        .collect();
+    VisualizeParams {
+        x_axis: None,
+        y_axis: None,
+        series: vec![SeriesConfig {
+            name: title.clone(),
+            series_type: ChartType::Pie,
+            data: Some(data),
+        }],
+        title,
+    }
+}
+
+fn field<'a>(row: &'a Map<String, Value>, name: &str) -> &'a Value {
+    row.get(name).unwrap_or(&Value::Null)
+}
+
+/// Distinct values ordered like `ORDER BY`: numbers numerically, before
+/// text, with NULLs last.
+fn distinct_sorted<'a>(values: impl Iterator<Item = &'a Value>) -> Vec<Value> {
+    let mut seen = BTreeSet::new();
+    let mut out: Vec<Value> = values
+        .filter(|v| seen.insert(as_label(v)))
+        .cloned()
+        .collect();
+    out.sort_by(|a, b| match (a, b) {
+        (Value::Null, Value::Null) => Ordering::Equal,
+        (Value::Null, _) => Ordering::Greater,
+        (_, Value::Null) => Ordering::Less,
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Value::Number(_), _) => Ordering::Less,
+        (_, Value::Number(_)) => Ordering::Greater,
+        (a, b) => as_label(a).cmp(&as_label(b)),
+    });
+    out
+}
+
+fn number(value: Option<f64>) -> Value {
+    value
+        .and_then(serde_json::Number::from_f64)
+        .map(Value::Number)
+        .unwrap_or(Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::model::{BarChartDisplay, PieChartDisplay};
+    use serde_json::json;
+
+    fn rows() -> Vec<Value> {
+        vec![
+            json!({"month": "2024-02", "region": "EU", "revenue": 5}),
+            json!({"month": "2024-01", "region": "US", "revenue": 10}),
+            json!({"month": "2024-01", "region": "US", "revenue": 2.5}),
+            json!({"month": "2024-02", "region": "US", "revenue": 7}),
+        ]
+    }
+
+    #[test]
+    fn bar_display_sums_per_series() {
+        let display = Display::BarChart(BarChartDisplay {
+            x: "month".into(),
+            y: "revenue".into(),
+            title: Some("Revenue".into()),
+            data: "revenue_by_month".into(),
+            series: Some("region".into()),
+            y_format: None,
+        });
+        let Some(ChartDocument::Chart(params)) = ChartDocument::from_display(&display, &rows())
+        else {
+            panic!("expected a chart");
+        };
+        assert_eq!(
+            params.x_axis.unwrap().data.unwrap(),
+            vec![json!("2024-01"), json!("2024-02")]
+        );
+        let series: Vec<_> = params
+            .series
+            .iter()
+            .map(|s| (s.name.clone().unwrap(), s.data.clone().unwrap()))
+            .collect();
+        assert_eq!(
+            series,
+            vec![
+                ("EU".to_string(), vec![Value::Null, json!(5.0)]),
+                ("US".to_string(), vec![json!(12.5), json!(7.0)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn pie_display_sums_per_name() {
+        let display = Display::PieChart(PieChartDisplay {
+            name: "region".into(),
+            value: "revenue".into(),
+            title: None,
+            data: "revenue_by_month".into(),
+            value_format: None,
+        });
+        let Some(ChartDocument::Chart(params)) = ChartDocument::from_display(&display, &rows())
+        else {
+            panic!("expected a chart");
+        };
+        assert_eq!(
+            params.series[0].data.clone().unwrap(),
+            vec![
+                json!({"name": "EU", "value": 5.0}),
+                json!({"name": "US", "value": 19.5}),
+            ]
+        );
+    }
+
+    #[test]
+    fn distinct_values_sort_like_sql() {
+        let values = [
+            json!(10),
+            json!("b"),
+            Value::Null,
+            json!(2),
+            json!("a"),
+            json!(2),
+        ];
+        assert_eq!(
+            distinct_sorted(values.iter()),
+            vec![json!(2), json!(10), json!("a"), json!("b"), Value::Null]
+        );
+    }
+}
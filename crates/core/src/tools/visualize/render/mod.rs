@@ -0,0 +1,303 @@
+//! Native chart rendering.
+//!
+//! Turns the chart JSON written by the `visualize` tool — plus table snapshots
+//! from app displays — into SVG, and rasterizes that SVG to PNG with resvg.
+//! Rendering happens in-process, so it works without a browser or network
+//! access. Layout follows echarts' defaults (palette, axis placement, legend)
+//! closely enough that exports look like the web app, but it is not a full
+//! echarts implementation: only the options `VisualizeParams` exposes are
+//! honoured.
+
+mod cartesian;
+mod display;
+mod pie;
+mod svg;
+mod table;
+
+use std::sync::{Arc, OnceLock};
+
+use oxy_shared::errors::OxyError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::types::{ChartType, VisualizeParams};
+
+pub const DEFAULT_WIDTH: u32 = 1200;
+pub const DEFAULT_HEIGHT: u32 = 700;
+
+/// Extra directory scanned for fonts on top of the system ones. Useful on
+/// minimal CI images that ship without any fonts installed.
+const FONT_DIR_ENV: &str = "OXY_CHART_FONT_DIR";
+
+/// Selects the renderer for exports that have no CLI flag (API, Slack).
+const CHART_RENDERER_ENV: &str = "OXY_CHART_RENDERER";
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChartRenderer {
+    /// Render in-process; needs no browser or network access
+    #[default]
+    Native,
+    /// Screenshot the charts in headless Chromium; needs the web app running
+    Chrome,
+}
+
+impl ChartRenderer {
+    /// Renderer chosen through `OXY_CHART_RENDERER`. Anything other than
+    /// `chrome` means native.
+    pub fn from_env() -> Self {
+        match std::env::var(CHART_RENDERER_ENV) {
+            Ok(value) if value.trim().eq_ignore_ascii_case("chrome") => ChartRenderer::Chrome,
+            _ => ChartRenderer::Native,
+        }
+    }
+}
+
+/// Anything the native renderer can draw.
+///
+/// Deserialization is untagged: objects with `columns` and `rows` are tables,
+/// everything else is parsed as `visualize` tool output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ChartDocument {
+    Table(TableSpec),
+    Chart(VisualizeParams),
+}
+
+/// A tabular result rendered as an image.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableSpec {
+    #[serde(default)]
+    pub title: Option<String>,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl ChartDocument {
+    pub fn from_json(value: &Value) -> Result<Self, OxyError> {
+        serde_json::from_value(value.clone())
+            .map_err(|e| OxyError::SerializerError(format!("Invalid chart document: {e}")))
+    }
+
+    /// Render to an SVG document. Tables size their height to the rows shown,
+    /// using `height` only as an upper bound.
+    pub fn render_svg(&self, width: u32, height: u32) -> String {
+        let (width, height) = (width as f64, height as f64);
+        match self {
+            ChartDocument::Table(spec) => table::render(spec, width, height),
+            ChartDocument::Chart(params) => {
+                let drawable = params
+                    .series
+                    .iter()
+                    .filter(|s| s.data.as_ref().is_some_and(|d| !d.is_empty()))
+                    .count();
+                if drawable == 0 {
+                    empty(params.title.as_deref(), width, height)
+                } else if params
+                    .series
+                    .iter()
+                    .all(|s| matches!(s.series_type, ChartType::Pie))
+                {
+                    pie::render(params, width, height)
+                } else {
+                    cartesian::render(params, width, height)
+                }
+            }
+        }
+    }
+
+    pub fn render_png(&self, width: u32, height: u32) -> Result<Vec<u8>, OxyError> {
+        svg_to_png(&self.render_svg(width, height))
+    }
+}
+
+/// Rasterize an SVG document to PNG at its intrinsic size.
+pub fn svg_to_png(svg: &str) -> Result<Vec<u8>, OxyError> {
+    let mut options = resvg::usvg::Options::default();
+    options.fontdb = fontdb();
+    let tree = resvg::usvg::Tree::from_str(svg, &options)
+        .map_err(|e| OxyError::RuntimeError(format!("Failed to parse chart SVG: {e}")))?;
+    let size = tree.size().to_int_size();
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or_else(|| OxyError::RuntimeError("Chart has an empty canvas".to_string()))?;
+    resvg::render(
+        &tree,
+        resvg::tiny_skia::Transform::identity(),
+        &mut pixmap.as_mut(),
+    );
+    pixmap
+        .encode_png()
+        .map_err(|e| OxyError::RuntimeError(format!("Failed to encode chart PNG: {e}")))
+}
+
+/// Font database shared by every render; loading system fonts is slow, so it
+/// happens once per process.
+fn fontdb() -> Arc<resvg::usvg::fontdb::Database> {
+    static FONTS: OnceLock<Arc<resvg::usvg::fontdb::Database>> = OnceLock::new();
+    FONTS
+        .get_or_init(|| {
+            let mut db = resvg::usvg::fontdb::Database::new();
+            db.load_system_fonts();
+            if let Ok(dir) = std::env::var(FONT_DIR_ENV) {
+                db.load_fonts_dir(dir);
+            }
+            if db.is_empty() {
+                tracing::warn!(
+                    "No fonts found for chart rendering; text will be missing. \
+                     Install a sans-serif font or set {FONT_DIR_ENV}."
+                );
+            }
+            Arc::new(db)
+        })
+        .clone()
+}
+
+fn empty(title: Option<&str>, width: f64, height: f64) -> String {
+    let mut doc = svg::Svg::new(width, height);
+    if let Some(title) = title {
+        draw_title(&mut doc, title, width);
+    }
+    doc.text(
+        width / 2.0,
+        height / 2.0,
+        "No data",
+        svg::TextStyle::new(14.0)
+            .anchor(svg::Anchor::Middle)
+            .fill(svg::MUTED_COLOR),
+    );
+    doc.finish()
+}
+
+/// Height reserved at the top of the canvas for the title, if any.
+fn title_height(title: Option<&str>) -> f64 {
+    if title.is_some_and(|t| !t.is_empty()) {
+        48.0
+    } else {
+        16.0
+    }
+}
+
+fn draw_title(doc: &mut svg::Svg, title: &str, width: f64) {
+    doc.text(
+        width / 2.0,
+        30.0,
+        &svg::truncate(title, width - 32.0, 18.0),
+        svg::TextStyle::new(18.0).anchor(svg::Anchor::Middle).bold(),
+    );
+}
+
+/// Numeric value of a data point; numeric strings count, as echarts accepts
+/// them.
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse::<f64>().ok().filter(|f| f.is_finite()),
+        _ => None,
+    }
+}
+
+/// Display text for a category or cell value.
+fn as_label(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Number(n) => match n.as_f64() {
+            Some(f) if n.is_f64() => svg::format_number(f, 6),
+            _ => n.to_string(),
+        },
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn render(value: Value) -> String {
+        ChartDocument::from_json(&value)
+            .unwrap()
+            .render_svg(DEFAULT_WIDTH, DEFAULT_HEIGHT)
+    }
+
+    #[test]
+    fn documents_deserialize_by_shape() {
+        let table = json!({"columns": ["a"], "rows": [[1]]});
+        assert!(matches!(
+            ChartDocument::from_json(&table).unwrap(),
+            ChartDocument::Table(_)
+        ));
+        let chart = json!({"series": [{"type": "bar", "data": [1, 2]}]});
+        assert!(matches!(
+            ChartDocument::from_json(&chart).unwrap(),
+            ChartDocument::Chart(_)
+        ));
+        assert!(ChartDocument::from_json(&json!({"rows": []})).is_err());
+    }
+
+    #[test]
+    fn bar_chart_draws_one_rect_per_point() {
+        let svg = render(json!({
+            "title": "Revenue <by> month",
+            "xAxis": {"type": "category", "data": ["Jan", "Feb", "Mar"]},
+            "yAxis": {"type": "value"},
+            "series": [{"type": "bar", "name": "revenue", "data": [10, "20", null]}]
+        }));
+        assert!(svg.contains("Revenue &lt;by&gt; month"));
+        assert!(svg.contains(">Feb</text>"));
+        // Background + two bars; the null point is skipped.
+        assert_eq!(svg.matches("<rect").count(), 3);
+    }
+
+    #[test]
+    fn line_chart_breaks_at_nulls() {
+        let svg = render(json!({
+            "xAxis": {"type": "category", "data": ["a", "b", "c", "d", "e"]},
+            "yAxis": {"type": "value"},
+            "series": [
+                {"type": "line", "name": "x", "data": [1, 2, null, 4, 5]},
+                {"type": "line", "name": "y", "data": [[ "a", 3 ], [ "e", 1 ]]}
+            ]
+        }));
+        assert_eq!(svg.matches("<polyline").count(), 3);
+        // Two series get a legend.
+        assert!(svg.contains(">y</text>"));
+    }
+
+    #[test]
+    fn pie_chart_labels_slices_with_percentages() {
+        let svg = render(json!({
+            "series": [{"type": "pie", "data": [
+                {"name": "North", "value": 30},
+                {"name": "South", "value": 10}
+            ]}]
+        }));
+        assert_eq!(svg.matches("<path").count(), 2);
+        assert!(svg.contains("North: 75%"));
+        assert!(svg.contains("South: 25%"));
+    }
+
+    #[test]
+    fn empty_chart_says_so() {
+        let svg = render(json!({"title": "Nothing", "series": []}));
+        assert!(svg.contains("No data"));
+    }
+
+    #[test]
+    fn table_truncates_long_results() {
+        let rows: Vec<Value> = (0..120).map(|i| json!([i, format!("row {i}")])).collect();
+        let svg = render(json!({"columns": ["id", "name"], "rows": rows}));
+        assert!(svg.contains(">row 0</text>"));
+        assert!(!svg.contains(">row 119</text>"));
+        assert!(svg.contains("more rows"));
+    }
+
+    #[test]
+    fn svg_rasterizes_to_png() {
+        let doc = ChartDocument::from_json(&json!({
+            "series": [{"type": "bar", "data": [1, 2, 3]}]
+        }))
+        .unwrap();
+        let png = doc.render_png(320, 200).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    }
+}
@@ -0,0 +1,130 @@
+//! Pie charts. Several pie series are laid out side by side.
+
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+use serde_json::Value;
+
+use super::svg::{Anchor, MUTED_COLOR, Svg, TextStyle, color, format_number, truncate};
+use super::{as_label, as_number, draw_title, title_height};
+use crate::tools::visualize::types::VisualizeParams;
+
+const LABEL_SIZE: f64 = 12.0;
+/// Slices smaller than this share of the pie are left unlabeled.
+const MIN_LABELED_SHARE: f64 = 0.02;
+
+pub(super) fn render(params: &VisualizeParams, width: f64, height: f64) -> String {
+    let mut doc = Svg::new(width, height);
+    let title = params.title.as_deref().filter(|t| !t.is_empty());
+    if let Some(title) = title {
+        draw_title(&mut doc, title, width);
+    }
+    let top = title_height(title);
+
+    let pies: Vec<_> = params
+        .series
+        .iter()
+        .filter(|s| s.data.as_ref().is_some_and(|d| !d.is_empty()))
+        .collect();
+    let cell = width / pies.len().max(1) as f64;
+    let captioned = pies.len() > 1;
+    for (i, series) in pies.iter().enumerate() {
+        let slices = slices(series.data.as_deref().unwrap_or_default());
+        let cx = cell * (i as f64 + 0.5);
+        let cy = top + (height - top) / 2.0;
+        let radius = (cell.min(height - top) / 2.0 * 0.6).max(1.0);
+        draw_pie(&mut doc, &slices, cx, cy, radius, cell);
+        if captioned && let Some(name) = series.name.as_deref() {
+            doc.text(
+                cx,
+                height - 16.0,
+                &truncate(name, cell - 16.0, 13.0),
+                TextStyle::new(13.0).anchor(Anchor::Middle).bold(),
+            );
+        }
+    }
+    doc.finish()
+}
+
+/// `(name, value)` for each positive slice; data items may be `{name,
+/// value}` objects, `[name, value]` pairs or bare numbers.
+fn slices(data: &[Value]) -> Vec<(String, f64)> {
+    data.iter()
+        .enumerate()
+        .filter_map(|(i, item)| {
+            let (name, value) = match item {
+                Value::Object(obj) => (
+                    obj.get("name").map(as_label),
+                    obj.get("value").and_then(as_number),
+                ),
+                Value::Array(pair) if pair.len() >= 2 => {
+                    (Some(as_label(&pair[0])), as_number(&pair[1]))
+                }
+                other => (None, as_number(other)),
+            };
+            let value = value.filter(|v| *v > 0.0)?;
+            Some((name.unwrap_or_else(|| (i + 1).to_string()), value))
+        })
+        .collect()
+}
+
+fn draw_pie(doc: &mut Svg, slices: &[(String, f64)], cx: f64, cy: f64, radius: f64, cell: f64) {
+    let total: f64 = slices.iter().map(|(_, v)| v).sum();
+    if total <= 0.0 {
+        doc.text(
+            cx,
+            cy,
+            "No data",
+            TextStyle::new(14.0)
+                .anchor(Anchor::Middle)
+                .fill(MUTED_COLOR),
+        );
+        return;
+    }
+
+    // Start at twelve o'clock and go clockwise, like echarts.
+    let mut angle = -FRAC_PI_2;
+    for (i, (name, value)) in slices.iter().enumerate() {
+        let share = value / total;
+        let sweep = share * TAU;
+        let fill = color(i);
+        if share >= 0.9999 {
+            doc.circle(cx, cy, radius, fill, "#ffffff");
+        } else {
+            let (x0, y0) = polar(cx, cy, radius, angle);
+            let (x1, y1) = polar(cx, cy, radius, angle + sweep);
+            let large = if sweep > std::f64::consts::PI { 1 } else { 0 };
+            let d = format!(
+                "M{cx:.2},{cy:.2} L{x0:.2},{y0:.2} A{radius:.2},{radius:.2} 0 {large} 1 {x1:.2},{y1:.2} Z"
+            );
+            doc.path(&d, fill, "#ffffff");
+        }
+
+        if share >= MIN_LABELED_SHARE {
+            let mid = angle + sweep / 2.0;
+            let (lx0, ly0) = polar(cx, cy, radius, mid);
+            let (lx1, ly1) = polar(cx, cy, radius + 14.0, mid);
+            let right = mid.cos() >= 0.0;
+            let lx2 = if right { lx1 + 10.0 } else { lx1 - 10.0 };
+            doc.line(lx0, ly0, lx1, ly1, fill, 1.0);
+            doc.line(lx1, ly1, lx2, ly1, fill, 1.0);
+            let label = format!("{name}: {}%", format_number(share * 100.0, 1));
+            let room = (cell / 2.0 - radius - 32.0).max(60.0);
+            let (x, anchor) = if right {
+                (lx2 + 4.0, Anchor::Start)
+            } else {
+                (lx2 - 4.0, Anchor::End)
+            };
+            doc.text(
+                x,
+                ly1 + 4.0,
+                &truncate(&label, room, LABEL_SIZE),
+                TextStyle::new(LABEL_SIZE).anchor(anchor),
+            );
+        }
+        angle += sweep;
+    }
+}
+
+fn polar(cx: f64, cy: f64, radius: f64, angle: f64) -> (f64, f64) {
+    (cx + radius * angle.cos(), cy + radius * angle.sin())
+}
@@ -0,0 +1,307 @@
+//! SVG building blocks shared by the chart renderers: a string-backed
+//! writer, the echarts default palette, tick generation and number
+//! formatting.
+
+use std::fmt::Write;
+
+/// echarts' default series palette, so exported charts match the web app.
+const PALETTE: [&str; 9] = [
+    "#5470c6", "#91cc75", "#fac858", "#ee6666", "#73c0de", "#3ba272", "#fc8452", "#9a60b4",
+    "#ea7ccc",
+];
+
+pub(super) const FONT_FAMILY: &str = "DejaVu Sans, Liberation Sans, Helvetica, Arial, sans-serif";
+pub(super) const TEXT_COLOR: &str = "#464646";
+pub(super) const MUTED_COLOR: &str = "#6e7079";
+pub(super) const GRID_COLOR: &str = "#e0e6f1";
+pub(super) const BORDER_COLOR: &str = "#d9dde6";
+
+pub(super) fn color(index: usize) -> &'static str {
+    PALETTE[index % PALETTE.len()]
+}
+
+#[derive(Clone, Copy)]
+pub(super) enum Anchor {
+    Start,
+    Middle,
+    End,
+}
+
+#[derive(Clone, Copy)]
+pub(super) struct TextStyle {
+    size: f64,
+    anchor: Anchor,
+    fill: &'static str,
+    bold: bool,
+    rotate: Option<f64>,
+}
+
+impl TextStyle {
+    pub fn new(size: f64) -> Self {
+        Self {
+            size,
+            anchor: Anchor::Start,
+            fill: TEXT_COLOR,
+            bold: false,
+            rotate: None,
+        }
+    }
+
+    pub fn anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    pub fn fill(mut self, fill: &'static str) -> Self {
+        self.fill = fill;
+        self
+    }
+
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// Rotate around the text's anchor point, in degrees.
+    pub fn rotate(mut self, degrees: f64) -> Self {
+        self.rotate = Some(degrees);
+        self
+    }
+}
+
+/// Append-only SVG document with a white background.
+pub(super) struct Svg {
+    buf: String,
+}
+
+impl Svg {
+    pub fn new(width: f64, height: f64) -> Self {
+        let mut buf = String::new();
+        let _ = write!(
+            buf,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="{FONT_FAMILY}">"#
+        );
+        let _ = write!(
+            buf,
+            r##"<rect x="0" y="0" width="{width}" height="{height}" fill="#ffffff"/>"##
+        );
+        Self { buf }
+    }
+
+    pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, fill: &str) {
+        let _ = write!(
+            self.buf,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{fill}"/>"#,
+            r(x),
+            r(y),
+            r(width.max(0.0)),
+            r(height.max(0.0))
+        );
+    }
+
+    pub fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, stroke: &str, width: f64) {
+        let _ = write!(
+            self.buf,
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{stroke}" stroke-width="{width}"/>"#,
+            r(x1),
+            r(y1),
+            r(x2),
+            r(y2)
+        );
+    }
+
+    pub fn polyline(&mut self, points: &[(f64, f64)], stroke: &str, width: f64) {
+        let points = points
+            .iter()
+            .map(|(x, y)| format!("{},{}", r(*x), r(*y)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let _ = write!(
+            self.buf,
+            r#"<polyline points="{points}" fill="none" stroke="{stroke}" stroke-width="{width}" stroke-linejoin="round"/>"#
+        );
+    }
+
+    pub fn circle(&mut self, cx: f64, cy: f64, radius: f64, fill: &str, stroke: &str) {
+        let _ = write!(
+            self.buf,
+            r#"<circle cx="{}" cy="{}" r="{radius}" fill="{fill}" stroke="{stroke}" stroke-width="1.5"/>"#,
+            r(cx),
+            r(cy)
+        );
+    }
+
+    pub fn path(&mut self, d: &str, fill: &str, stroke: &str) {
+        let _ = write!(
+            self.buf,
+            r#"<path d="{d}" fill="{fill}" stroke="{stroke}" stroke-width="1"/>"#
+        );
+    }
+
+    pub fn text(&mut self, x: f64, y: f64, content: &str, style: TextStyle) {
+        let anchor = match style.anchor {
+            Anchor::Start => "start",
+            Anchor::Middle => "middle",
+            Anchor::End => "end",
+        };
+        let weight = if style.bold { "bold" } else { "normal" };
+        let transform = style
+            .rotate
+            .map(|deg| format!(r#" transform="rotate({deg} {} {})""#, r(x), r(y)))
+            .unwrap_or_default();
+        let _ = write!(
+            self.buf,
+            r#"<text x="{}" y="{}" font-size="{}" font-weight="{weight}" fill="{}" text-anchor="{anchor}"{transform}>{}</text>"#,
+            r(x),
+            r(y),
+            style.size,
+            style.fill,
+            escape(content)
+        );
+    }
+
+    pub fn finish(mut self) -> String {
+        self.buf.push_str("</svg>");
+        self.buf
+    }
+}
+
+/// Round coordinates to two decimals to keep documents small and stable.
+fn r(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+pub(super) fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Approximate rendered width of `text`. There is no font shaping at layout
+/// time, so this uses an average sans-serif glyph width.
+pub(super) fn text_width(text: &str, size: f64) -> f64 {
+    text.chars().count() as f64 * size * 0.6
+}
+
+/// Shorten `text` with an ellipsis so it fits in `max_width`.
+pub(super) fn truncate(text: &str, max_width: f64, size: f64) -> String {
+    if text_width(text, size) <= max_width {
+        return text.to_string();
+    }
+    let max_chars = ((max_width / (size * 0.6)).floor() as usize).saturating_sub(1);
+    let mut out: String = text.chars().take(max_chars).collect();
+    out.push('…');
+    out
+}
+
+/// Evenly spaced "nice" tick values (steps of 1, 2 or 5 × 10ⁿ) covering
+/// `[min, max]`.
+pub(super) fn nice_ticks(min: f64, max: f64, target: usize) -> Vec<f64> {
+    let (mut lo, mut hi) = if min.is_finite() && max.is_finite() {
+        (min, max)
+    } else {
+        (0.0, 1.0)
+    };
+    if hi - lo < f64::EPSILON {
+        if lo == 0.0 {
+            hi = 1.0;
+        } else {
+            let pad = lo.abs() * 0.5;
+            lo -= pad;
+            hi += pad;
+        }
+    }
+    let step = nice_step((hi - lo) / target.max(1) as f64);
+    let start = (lo / step).floor() * step;
+    let end = (hi / step).ceil() * step;
+    let count = ((end - start) / step).round() as usize;
+    (0..=count).map(|i| start + i as f64 * step).collect()
+}
+
+fn nice_step(raw: f64) -> f64 {
+    let exponent = raw.log10().floor();
+    let magnitude = 10f64.powf(exponent);
+    let fraction = raw / magnitude;
+    let nice = if fraction <= 1.0 {
+        1.0
+    } else if fraction <= 2.0 {
+        2.0
+    } else if fraction <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+    nice * magnitude
+}
+
+/// Compact axis label: `25K`, `2.5M`, `0.25`.
+pub(super) fn format_tick(value: f64) -> String {
+    let abs = value.abs();
+    let (scaled, suffix) = if abs >= 1e9 {
+        (value / 1e9, "B")
+    } else if abs >= 1e6 {
+        (value / 1e6, "M")
+    } else if abs >= 1e4 {
+        (value / 1e3, "K")
+    } else {
+        (value, "")
+    };
+    format!("{}{suffix}", format_number(scaled, 2))
+}
+
+/// Plain number with at most `decimals` fractional digits and no trailing
+/// zeros.
+pub(super) fn format_number(value: f64, decimals: usize) -> String {
+    let text = format!("{value:.decimals$}");
+    let text = if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        text
+    };
+    if text == "-0" { "0".to_string() } else { text }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ticks_use_round_steps() {
+        assert_eq!(
+            nice_ticks(0.0, 93.0, 5),
+            vec![0.0, 20.0, 40.0, 60.0, 80.0, 100.0]
+        );
+        assert_eq!(
+            nice_ticks(-3.0, 7.0, 5),
+            vec![-4.0, -2.0, 0.0, 2.0, 4.0, 6.0, 8.0]
+        );
+        // A flat series still gets a usable axis.
+        assert_eq!(nice_ticks(0.0, 0.0, 5).last(), Some(&1.0));
+    }
+
+    #[test]
+    fn tick_labels_are_compact() {
+        assert_eq!(format_tick(1500.0), "1500");
+        assert_eq!(format_tick(25_000.0), "25K");
+        assert_eq!(format_tick(2_500_000.0), "2.5M");
+        assert_eq!(format_tick(0.25), "0.25");
+        assert_eq!(format_tick(-0.0), "0");
+    }
+
+    #[test]
+    fn text_is_escaped_and_truncated() {
+        assert_eq!(escape("a < b & \"c\""), "a &lt; b &amp; &quot;c&quot;");
+        assert_eq!(truncate("abcdefghij", 6.0 * 5.0, 10.0), "abcd…");
+        assert_eq!(truncate("abc", 100.0, 10.0), "abc");
+    }
+}
@@ -0,0 +1,112 @@
+//! Tables rendered as images, for chat integrations that cannot show rich
+//! tables inline.
+
+use serde_json::Value;
+
+use super::svg::{Anchor, BORDER_COLOR, MUTED_COLOR, Svg, TextStyle, text_width, truncate};
+use super::{TableSpec, as_label, draw_title, title_height};
+
+const FONT_SIZE: f64 = 13.0;
+const ROW_HEIGHT: f64 = 28.0;
+const PADDING: f64 = 10.0;
+const MAX_ROWS: usize = 50;
+const STRIPE_COLOR: &str = "#f5f7fa";
+const HEADER_COLOR: &str = "#eef1f6";
+
+pub(super) fn render(spec: &TableSpec, width: f64, max_height: f64) -> String {
+    let title = spec.title.as_deref().filter(|t| !t.is_empty());
+    let top = title_height(title);
+    let fits = ((max_height - top - 2.0 * ROW_HEIGHT - 16.0) / ROW_HEIGHT).floor();
+    let shown = spec.rows.len().min(MAX_ROWS).min(fits.max(1.0) as usize);
+    let hidden = spec.rows.len() - shown;
+    let height =
+        top + ROW_HEIGHT * (shown + 1) as f64 + if hidden > 0 { ROW_HEIGHT } else { 0.0 } + 16.0;
+
+    let mut doc = Svg::new(width, height);
+    if let Some(title) = title {
+        draw_title(&mut doc, title, width);
+    }
+
+    let rows = &spec.rows[..shown];
+    let widths = column_widths(spec, rows, width - 32.0);
+    let numeric: Vec<bool> = (0..spec.columns.len())
+        .map(|c| {
+            let mut cells = rows
+                .iter()
+                .filter_map(|r| r.get(c))
+                .filter(|v| !v.is_null());
+            let first = cells.next();
+            first.is_some_and(Value::is_number) && cells.all(Value::is_number)
+        })
+        .collect();
+
+    let left = 16.0;
+    let right = width - 16.0;
+    doc.rect(left, top, right - left, ROW_HEIGHT, HEADER_COLOR);
+    let mut y = top;
+    for (r, row) in std::iter::once(None)
+        .chain(rows.iter().map(Some))
+        .enumerate()
+    {
+        if r > 0 && r % 2 == 0 {
+            doc.rect(left, y, right - left, ROW_HEIGHT, STRIPE_COLOR);
+        }
+        let mut x = left;
+        for (c, column_width) in widths.iter().enumerate() {
+            let text = match row {
+                None => spec.columns[c].clone(),
+                Some(row) => row.get(c).map(as_label).unwrap_or_default(),
+            };
+            let text = truncate(&text, column_width - 2.0 * PADDING, FONT_SIZE);
+            let style = TextStyle::new(FONT_SIZE);
+            let style = if row.is_none() { style.bold() } else { style };
+            if numeric[c] {
+                doc.text(
+                    x + column_width - PADDING,
+                    y + 18.0,
+                    &text,
+                    style.anchor(Anchor::End),
+                );
+            } else {
+                doc.text(x + PADDING, y + 18.0, &text, style);
+            }
+            x += column_width;
+        }
+        y += ROW_HEIGHT;
+        doc.line(left, y, right, y, BORDER_COLOR, 1.0);
+    }
+
+    if hidden > 0 {
+        let noun = if hidden == 1 { "row" } else { "rows" };
+        doc.text(
+            left + PADDING,
+            y + 18.0,
+            &format!("… {hidden} more {noun}"),
+            TextStyle::new(FONT_SIZE).fill(MUTED_COLOR),
+        );
+    }
+    doc.finish()
+}
+
+/// Share `available` between columns in proportion to their widest cell,
+/// with every column getting at least a minimal slice.
+fn column_widths(spec: &TableSpec, rows: &[Vec<Value>], available: f64) -> Vec<f64> {
+    let natural: Vec<f64> = spec
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(c, name)| {
+            rows.iter()
+                .filter_map(|r| r.get(c))
+                .map(|v| text_width(&as_label(v), FONT_SIZE))
+                .fold(text_width(name, FONT_SIZE), f64::max)
+                .clamp(40.0, 400.0)
+                + 2.0 * PADDING
+        })
+        .collect();
+    let total: f64 = natural.iter().sum();
+    if total <= 0.0 {
+        return natural;
+    }
+    natural.iter().map(|w| w / total * available).collect()
+}
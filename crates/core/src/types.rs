@@ -184,7 +184,7 @@ pub struct SemanticQueryParams {
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub measures: Vec<String>,
     #[schemars(
-        description = "List of dimensions to include in the query. Format: <view_name>.<dimension_name>"
+        description = "List of dimensions to include in the query. Format: <view_name>.<dimension_name>, or <view_name>.<dimension_name>.<grain> for a date dimension at a grain (day, week, month, quarter, year, fiscal_quarter, fiscal_year)"
     )]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub dimensions: Vec<String>,
@@ -207,6 +207,12 @@ pub struct SemanticQueryParams {
     )]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub variables: Option<HashMap<String, Value>>,
+    /// Zero-fill the time buckets the query returns no rows for
+    #[schemars(
+        description = "Return a row with zero measures for every time bucket between the first and last one, including buckets with no data. Requires exactly one time dimension with a granularity, or one <view_name>.<dimension_name>.<grain> dimension."
+    )]
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub time_spine: bool,
 }
 
 impl std::hash::Hash for SemanticQueryParams {
@@ -225,6 +231,7 @@ impl std::hash::Hash for SemanticQueryParams {
         }
         self.limit.hash(state);
         self.offset.hash(state);
+        self.time_spine.hash(state);
         // Variables affect query results, so include them in hash
         if let Some(variables) = &self.variables {
             for (key, value) in variables {
@@ -266,6 +273,7 @@ impl SemanticQuery {
             offset: self.offset,
             variables: None,
             time_dimensions: self.time_dimensions.clone(),
+            time_spine: false,
         };
         serde_json::to_string_pretty(&semantic_query_params)
             .unwrap_or_else(|_| "Failed to serialize SemanticQueryParams".to_string())
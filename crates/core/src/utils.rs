@@ -34,6 +34,21 @@ pub const MAX_OUTPUT_LENGTH: usize = 1000;
 /// full `oxy` crate.
 pub use oxy_platform::secrets::get_encryption_key;
 
+/// Salt for the `hash` column masking rule, derived from the encryption key
+/// so hashed values stay stable across runs but cannot be reversed by
+/// hashing guessed inputs.
+pub fn masking_salt() -> &'static str {
+    static SALT: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+    SALT.get_or_init(|| {
+        use hmac::Mac;
+        let mut mac =
+            <hmac::Hmac<sha2::Sha256> as hmac::KeyInit>::new_from_slice(&get_encryption_key())
+                .expect("HMAC key");
+        mac.update(b"oxy-column-masking");
+        hex::encode(mac.finalize().into_bytes())
+    })
+}
+
 pub fn truncate_with_ellipsis(s: &str, max_width: Option<usize>) -> String {
     // We should truncate at grapheme-boundary and compute character-widths,
     // yet the dependencies on unicode-segmentation and unicode-width are
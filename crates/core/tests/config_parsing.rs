@@ -129,3 +129,222 @@ fn test_clickhouse_without_filters() {
         panic!("Expected ClickHouse database type");
     }
 }
+
+#[test]
+fn test_mssql_config_parsing() {
+    unsafe {
+        std::env::set_var("OPENAI_API_KEY", "test_key");
+    }
+
+    let config_yaml = r#"
+        databases:
+          - name: warehouse
+            type: mssql
+            host: myserver.database.windows.net
+            user: analyst
+            password_var: MSSQL_PASSWORD
+            database: sales
+            schemas:
+              dbo: ["orders", "customers"]
+          - name: local_sqlserver
+            type: mssql
+            host: localhost
+            password: Str0ng!Passw0rd
+            encrypt: false
+            trust_server_certificate: true
+
+        models:
+          - name: test_model
+            vendor: openai
+            model_ref: gpt-4
+            key_var: OPENAI_API_KEY
+    "#;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.yml");
+    std::fs::write(&config_path, config_yaml).unwrap();
+
+    let config = parse_config(&config_path, temp_dir.path().to_path_buf())
+        .expect("mssql config should parse");
+
+    let azure = &config.databases[0];
+    assert_eq!(azure.database_type_name(), "mssql");
+    assert_eq!(azure.dialect(), "mssql");
+    if let DatabaseType::Mssql(ms) = &azure.database_type {
+        assert!(ms.encrypt, "encrypt should default to true");
+        assert!(!ms.trust_server_certificate);
+        assert_eq!(ms.password_var.as_deref(), Some("MSSQL_PASSWORD"));
+        assert_eq!(ms.schemas["dbo"], vec!["orders", "customers"]);
+    } else {
+        panic!("Expected Mssql database type");
+    }
+    assert_eq!(azure.datasets().len(), 1);
+
+    let local = &config.databases[1];
+    if let DatabaseType::Mssql(ms) = &local.database_type {
+        assert!(!ms.encrypt);
+        assert!(ms.trust_server_certificate);
+        assert!(ms.port.is_none(), "port falls back to 1433 at resolve time");
+    } else {
+        panic!("Expected Mssql database type");
+    }
+    // No schemas configured → a single "all schemas" entry.
+    assert_eq!(
+        local.datasets(),
+        std::collections::HashMap::from([(String::new(), vec!["*".to_string()])])
+    );
+}
+
+#[test]
+fn test_trino_config_parsing() {
+    unsafe {
+        std::env::set_var("OPENAI_API_KEY", "test_key");
+    }
+
+    let config_yaml = r#"
+        databases:
+          - name: lakehouse
+            type: trino
+            host: trino.internal
+            port: "8443"
+            user: analyst
+            password_var: TRINO_PASSWORD
+            catalog: iceberg
+            schema: analytics
+            catalogs: ["iceberg", "hive"]
+            https: true
+          - name: local_trino
+            type: trino
+            catalog: tpch
+
+        models:
+          - name: test_model
+            vendor: openai
+            model_ref: gpt-4
+            key_var: OPENAI_API_KEY
+    "#;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.yml");
+    std::fs::write(&config_path, config_yaml).unwrap();
+
+    let config = parse_config(&config_path, temp_dir.path().to_path_buf())
+        .expect("trino config should parse");
+
+    let lakehouse = &config.databases[0];
+    assert_eq!(lakehouse.database_type_name(), "trino");
+    assert_eq!(lakehouse.dialect(), "trino");
+    if let DatabaseType::Trino(tr) = &lakehouse.database_type {
+        assert!(tr.https);
+        assert_eq!(tr.schema.as_deref(), Some("analytics"));
+        assert_eq!(tr.password_var.as_deref(), Some("TRINO_PASSWORD"));
+    } else {
+        panic!("Expected Trino database type");
+    }
+    let mut catalogs: Vec<String> = lakehouse.datasets().into_keys().collect();
+    catalogs.sort();
+    assert_eq!(catalogs, vec!["hive", "iceberg"]);
+
+    // No catalogs listed → only the default catalog is introspected.
+    let local = &config.databases[1];
+    if let DatabaseType::Trino(tr) = &local.database_type {
+        assert!(!tr.https);
+        assert_eq!(tr.introspected_catalogs(), vec!["tpch"]);
+    } else {
+        panic!("Expected Trino database type");
+    }
+}
+
+#[test]
+fn test_sqlite_config_parsing() {
+    unsafe {
+        std::env::set_var("OPENAI_API_KEY", "test_key");
+    }
+
+    let config_yaml = r#"
+        databases:
+          - name: products
+            type: sqlite
+            path: data/products.sqlite
+          - name: shards
+            type: sqlite
+            path: data/shards/*.db
+            read_only: false
+
+        models:
+          - name: test_model
+            vendor: openai
+            model_ref: gpt-4
+            key_var: OPENAI_API_KEY
+    "#;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.yml");
+    std::fs::write(&config_path, config_yaml).unwrap();
+
+    let config = parse_config(&config_path, temp_dir.path().to_path_buf())
+        .expect("sqlite config should parse");
+
+    let products = &config.databases[0];
+    assert_eq!(products.database_type_name(), "sqlite");
+    assert_eq!(products.dialect(), "sqlite");
+    if let DatabaseType::Sqlite(sqlite) = &products.database_type {
+        assert_eq!(sqlite.path, "data/products.sqlite");
+        assert!(sqlite.read_only, "read_only defaults to true");
+    } else {
+        panic!("Expected Sqlite database type");
+    }
+
+    if let DatabaseType::Sqlite(sqlite) = &config.databases[1].database_type {
+        assert_eq!(sqlite.path, "data/shards/*.db");
+        assert!(!sqlite.read_only);
+    } else {
+        panic!("Expected Sqlite database type");
+    }
+}
+
+#[test]
+fn test_result_cache_config_parsing() {
+    unsafe {
+        std::env::set_var("OPENAI_API_KEY", "test_key");
+    }
+
+    let config_yaml = r#"
+        result_cache:
+          ttl_secs: 3600
+
+        databases:
+          - name: products
+            type: sqlite
+            path: data/products.sqlite
+            cache_ttl_secs: 300
+          - name: orders
+            type: sqlite
+            path: data/orders.sqlite
+
+        models:
+          - name: test_model
+            vendor: openai
+            model_ref: gpt-4
+            key_var: OPENAI_API_KEY
+    "#;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.yml");
+    std::fs::write(&config_path, config_yaml).unwrap();
+
+    let config = parse_config(&config_path, temp_dir.path().to_path_buf())
+        .expect("result cache config should parse");
+
+    let result_cache = config.result_cache.expect("result_cache should be set");
+    assert!(result_cache.enabled, "enabled defaults to true");
+    assert_eq!(result_cache.ttl_secs, Some(3600));
+    assert_eq!(result_cache.max_size_mb, 1024);
+
+    assert_eq!(config.databases[0].cache_ttl_secs, Some(300));
+    assert_eq!(config.databases[1].cache_ttl_secs, None);
+    assert!(matches!(
+        config.databases[0].database_type,
+        DatabaseType::Sqlite(_)
+    ));
+}
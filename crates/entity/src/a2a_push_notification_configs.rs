@@ -0,0 +1,38 @@
+//! `SeaORM` Entity for A2A Push Notification Configs
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "a2a_push_notification_configs")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub agent_name: String,
+    pub config_id: String,
+    pub url: String,
+    pub token: Option<String>,
+    pub authentication: Option<Json>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::a2a_tasks::Entity",
+        from = "Column::TaskId",
+        to = "super::a2a_tasks::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Tasks,
+}
+
+impl Related<super::a2a_tasks::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tasks.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -41,6 +41,8 @@ pub enum Relation {
     TaskStatus,
     #[sea_orm(has_many = "super::a2a_artifacts::Entity")]
     Artifacts,
+    #[sea_orm(has_many = "super::a2a_push_notification_configs::Entity")]
+    PushNotificationConfigs,
 }
 
 impl Related<super::threads::Entity> for Entity {
@@ -73,4 +75,10 @@ impl Related<super::a2a_artifacts::Entity> for Entity {
     }
 }
 
+impl Related<super::a2a_push_notification_configs::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PushNotificationConfigs.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -16,6 +16,8 @@ pub struct Model {
     pub updated_at: DateTimeWithTimeZone,
     pub is_active: bool,
     pub project_id: Uuid,
+    /// Custom role that narrows what the key may do, for service accounts
+    pub custom_role_id: Option<Uuid>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
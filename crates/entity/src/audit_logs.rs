@@ -0,0 +1,26 @@
+use sea_orm::entity::prelude::*;
+
+/// One entry of the append-only audit log. Rows cannot be updated, and are
+/// only deleted by the retention cleanup.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "audit_logs")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub created_at: DateTimeWithTimeZone,
+    pub org_id: Option<Uuid>,
+    pub workspace_id: Option<Uuid>,
+    pub actor_id: Option<Uuid>,
+    /// The actor's email when the action happened
+    pub actor_email: Option<String>,
+    /// Dotted action name, e.g. `secret.created`
+    pub action: String,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    pub metadata: Json,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,41 @@
+use sea_orm::entity::prelude::*;
+
+/// The custom role a member has in a workspace, at most one per member.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "custom_role_assignments")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub user_id: Uuid,
+    pub custom_role_id: Uuid,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::custom_roles::Entity",
+        from = "Column::CustomRoleId",
+        to = "super::custom_roles::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Restrict"
+    )]
+    CustomRoles,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Users,
+}
+
+impl Related<super::custom_roles::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CustomRoles.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,205 @@
+use sea_orm::entity::prelude::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// A named workspace permission. Built-in roles map to fixed sets of these;
+/// custom roles list them explicitly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Permission {
+    ReadFiles,
+    EditFiles,
+    RunAgents,
+    RunWorkflows,
+    RunApps,
+    RunTests,
+    QueryDatabases,
+    ManageDatabases,
+    QuerySemantic,
+    ViewTraces,
+    ManageSecrets,
+    ManageMembers,
+    ManageOwnApiKeys,
+}
+
+impl Permission {
+    pub const ALL: [Permission; 13] = [
+        Permission::ReadFiles,
+        Permission::EditFiles,
+        Permission::RunAgents,
+        Permission::RunWorkflows,
+        Permission::RunApps,
+        Permission::RunTests,
+        Permission::QueryDatabases,
+        Permission::ManageDatabases,
+        Permission::QuerySemantic,
+        Permission::ViewTraces,
+        Permission::ManageSecrets,
+        Permission::ManageMembers,
+        Permission::ManageOwnApiKeys,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::ReadFiles => "files.read",
+            Permission::EditFiles => "files.edit",
+            Permission::RunAgents => "agents.run",
+            Permission::RunWorkflows => "workflows.run",
+            Permission::RunApps => "apps.run",
+            Permission::RunTests => "tests.run",
+            Permission::QueryDatabases => "databases.query",
+            Permission::ManageDatabases => "databases.manage",
+            Permission::QuerySemantic => "semantic.query",
+            Permission::ViewTraces => "traces.view",
+            Permission::ManageSecrets => "secrets.manage",
+            Permission::ManageMembers => "members.manage",
+            Permission::ManageOwnApiKeys => "api_keys.manage",
+        }
+    }
+
+    /// Whether grants of this permission can be limited to resources: file
+    /// paths for agents, workflows and apps, database names and topic names.
+    pub fn is_scoped(&self) -> bool {
+        matches!(
+            self,
+            Permission::RunAgents
+                | Permission::RunWorkflows
+                | Permission::RunApps
+                | Permission::QueryDatabases
+                | Permission::QuerySemantic
+        )
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Permission {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Permission::ALL
+            .into_iter()
+            .find(|permission| permission.as_str() == s)
+            .ok_or_else(|| format!("Invalid permission: {s}"))
+    }
+}
+
+/// One entry of a custom role: a permission, optionally limited to a
+/// resource. Written as `agents.run` or `agents.run:agents/sales.agent.yml`;
+/// a resource ending in `*` matches every resource with that prefix.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grant {
+    pub permission: Permission,
+    pub resource: Option<String>,
+}
+
+impl Grant {
+    pub fn new(permission: Permission) -> Self {
+        Self {
+            permission,
+            resource: None,
+        }
+    }
+
+    /// Whether this grant allows `permission`. `resource: None` asks whether
+    /// the permission is granted for anything at all.
+    pub fn allows(&self, permission: Permission, resource: Option<&str>) -> bool {
+        if self.permission != permission {
+            return false;
+        }
+        match (&self.resource, resource) {
+            (None, _) | (Some(_), None) => true,
+            (Some(pattern), Some(resource)) => match pattern.strip_suffix('*') {
+                Some(prefix) => resource.starts_with(prefix),
+                None => pattern == resource,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Grant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.resource {
+            Some(resource) => write!(f, "{}:{resource}", self.permission),
+            None => f.write_str(self.permission.as_str()),
+        }
+    }
+}
+
+impl FromStr for Grant {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (permission, resource) = match s.trim().split_once(':') {
+            Some((permission, resource)) => (permission, Some(resource.trim())),
+            None => (s.trim(), None),
+        };
+        let permission = Permission::from_str(permission)?;
+        match resource {
+            None => Ok(Grant::new(permission)),
+            Some("") => Err(format!("Empty resource in '{s}'")),
+            Some(_) if !permission.is_scoped() => Err(format!(
+                "Permission {permission} cannot be limited to a resource"
+            )),
+            Some(resource) => Ok(Grant {
+                permission,
+                resource: Some(resource.to_string()),
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "custom_roles")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    /// JSON array of grant strings, see [`Grant`]
+    pub permissions: Json,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+impl Model {
+    /// The role's grants. Entries are validated when the role is saved, so
+    /// anything unparseable here is skipped rather than widening access.
+    pub fn grants(&self) -> Vec<Grant> {
+        self.permissions
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|value| value.as_str()?.parse().ok())
+            .collect()
+    }
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::workspaces::Entity",
+        from = "Column::WorkspaceId",
+        to = "super::workspaces::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Workspaces,
+}
+
+impl Related<super::workspaces::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Workspaces.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[cfg(test)]
+#[path = "custom_roles_tests.rs"]
+mod custom_roles_tests;
@@ -0,0 +1,74 @@
+use super::*;
+
+#[test]
+fn permission_round_trips_through_str() {
+    for permission in Permission::ALL {
+        assert_eq!(Permission::from_str(permission.as_str()), Ok(permission));
+    }
+    assert!(Permission::from_str("files.delete").is_err());
+}
+
+#[test]
+fn grant_parses_plain_and_scoped() {
+    assert_eq!(
+        Grant::from_str("files.read"),
+        Ok(Grant::new(Permission::ReadFiles))
+    );
+    assert_eq!(
+        Grant::from_str(" databases.query:warehouse "),
+        Ok(Grant {
+            permission: Permission::QueryDatabases,
+            resource: Some("warehouse".to_string()),
+        })
+    );
+    assert_eq!(
+        Grant::from_str("agents.run:agents/sales.agent.yml")
+            .unwrap()
+            .to_string(),
+        "agents.run:agents/sales.agent.yml"
+    );
+}
+
+#[test]
+fn grant_rejects_scope_on_unscoped_permission() {
+    assert!(Grant::from_str("secrets.manage:OPENAI_API_KEY").is_err());
+    assert!(Grant::from_str("agents.run:").is_err());
+}
+
+#[test]
+fn grant_matches_resources() {
+    let exact: Grant = "databases.query:warehouse".parse().unwrap();
+    assert!(exact.allows(Permission::QueryDatabases, Some("warehouse")));
+    assert!(!exact.allows(Permission::QueryDatabases, Some("warehouse_raw")));
+    assert!(exact.allows(Permission::QueryDatabases, None));
+    assert!(!exact.allows(Permission::ManageDatabases, None));
+
+    let prefix: Grant = "agents.run:agents/finance/*".parse().unwrap();
+    assert!(prefix.allows(Permission::RunAgents, Some("agents/finance/pnl.agent.yml")));
+    assert!(!prefix.allows(Permission::RunAgents, Some("agents/sales.agent.yml")));
+
+    let unscoped = Grant::new(Permission::RunAgents);
+    assert!(unscoped.allows(Permission::RunAgents, Some("anything.agent.yml")));
+}
+
+#[test]
+fn model_grants_skip_invalid_entries() {
+    let now = chrono::Utc::now().fixed_offset();
+    let role = Model {
+        id: Uuid::new_v4(),
+        workspace_id: Uuid::new_v4(),
+        name: "analyst".to_string(),
+        description: None,
+        permissions: serde_json::json!(["agents.run", "bogus", 3, "traces.view"]),
+        created_by: None,
+        created_at: now,
+        updated_at: now,
+    };
+    assert_eq!(
+        role.grants(),
+        vec![
+            Grant::new(Permission::RunAgents),
+            Grant::new(Permission::ViewTraces)
+        ]
+    );
+}
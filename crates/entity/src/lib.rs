@@ -4,12 +4,16 @@ pub mod prelude;
 
 pub mod a2a_artifacts;
 pub mod a2a_messages;
+pub mod a2a_push_notification_configs;
 pub mod a2a_task_status;
 pub mod a2a_tasks;
 pub mod apalis_jobs;
 pub mod api_keys;
 pub mod artifacts;
+pub mod audit_logs;
 pub mod checkpoints;
+pub mod custom_role_assignments;
+pub mod custom_roles;
 pub mod feature_flag;
 pub mod git_namespaces;
 pub mod github_accounts;
@@ -22,6 +26,11 @@ pub mod org_secrets;
 pub mod organizations;
 pub mod run_sequences;
 pub mod runs;
+pub mod scim_group_members;
+pub mod scim_groups;
+pub mod scim_role_mappings;
+pub mod scim_tokens;
+pub mod scim_users;
 pub mod secrets;
 pub mod settings;
 pub mod slack_channel_defaults;
@@ -31,6 +40,7 @@ pub mod slack_seen_events;
 pub mod slack_threads;
 pub mod slack_user_links;
 pub mod slack_user_preferences;
+pub mod sso_identities;
 pub mod stripe_webhook_events;
 pub mod tasks;
 pub mod test_case_human_verdicts;
@@ -39,6 +49,8 @@ pub mod test_run_cases;
 pub mod test_run_sequences;
 pub mod test_runs;
 pub mod threads;
+pub mod usage_spend;
 pub mod users;
+pub mod workflow_schedules;
 pub mod workspace_members;
 pub mod workspaces;
@@ -2,12 +2,16 @@
 
 pub use super::a2a_artifacts::Entity as A2aArtifacts;
 pub use super::a2a_messages::Entity as A2aMessages;
+pub use super::a2a_push_notification_configs::Entity as A2aPushNotificationConfigs;
 pub use super::a2a_task_status::Entity as A2aTaskStatus;
 pub use super::a2a_tasks::Entity as A2aTasks;
 pub use super::apalis_jobs::Entity as ApalisJobs;
 pub use super::api_keys::Entity as ApiKeys;
 pub use super::artifacts::Entity as Artifacts;
+pub use super::audit_logs::Entity as AuditLogs;
 pub use super::checkpoints::Entity as Checkpoints;
+pub use super::custom_role_assignments::Entity as CustomRoleAssignments;
+pub use super::custom_roles::Entity as CustomRoles;
 pub use super::git_namespaces::Entity as GitNamespaces;
 pub use super::github_accounts::Entity as GithubAccounts;
 pub use super::logs::Entity as Logs;
@@ -18,6 +22,11 @@ pub use super::org_secrets::Entity as OrgSecrets;
 pub use super::organizations::Entity as Organizations;
 pub use super::run_sequences::Entity as RunSequences;
 pub use super::runs::Entity as Runs;
+pub use super::scim_group_members::Entity as ScimGroupMembers;
+pub use super::scim_groups::Entity as ScimGroups;
+pub use super::scim_role_mappings::Entity as ScimRoleMappings;
+pub use super::scim_tokens::Entity as ScimTokens;
+pub use super::scim_users::Entity as ScimUsers;
 pub use super::secrets::Entity as Secrets;
 pub use super::settings::Entity as Settings;
 pub use super::slack_channel_defaults::Entity as SlackChannelDefaults;
@@ -27,6 +36,7 @@ pub use super::slack_seen_events::Entity as SlackSeenEvents;
 pub use super::slack_threads::Entity as SlackThreads;
 pub use super::slack_user_links::Entity as SlackUserLinks;
 pub use super::slack_user_preferences::Entity as SlackUserPreferences;
+pub use super::sso_identities::Entity as SsoIdentities;
 pub use super::tasks::Entity as Tasks;
 pub use super::test_case_human_verdicts::Entity as TestCaseHumanVerdicts;
 pub use super::test_project_runs::Entity as TestProjectRuns;
@@ -34,6 +44,8 @@ pub use super::test_run_cases::Entity as TestRunCases;
 pub use super::test_run_sequences::Entity as TestRunSequences;
 pub use super::test_runs::Entity as TestRuns;
 pub use super::threads::Entity as Threads;
+pub use super::usage_spend::Entity as UsageSpend;
 pub use super::users::Entity as Users;
+pub use super::workflow_schedules::Entity as WorkflowSchedules;
 pub use super::workspace_members::Entity as WorkspaceMembers;
 pub use super::workspaces::Entity as Workspaces;
@@ -1,4 +1,4 @@
-use oxy_shared::HeaderValue;
+use oxy_shared::{HeaderValue, RateLimitConfig};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
@@ -15,6 +15,13 @@ pub struct AnthropicModelConfig {
     pub api_url: Option<String>,
     #[serde(default)]
     pub headers: Option<HashMap<String, HeaderValue>>,
+    /// Names of other entries in `models` to try, in order, when this model
+    /// is rate limited, failing or its circuit breaker is open
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallbacks: Vec<String>,
+    /// Client-side rate limit for this model's API key
+    #[serde(default)]
+    pub rate_limit: Option<RateLimitConfig>,
 }
 
 impl AnthropicModelConfig {
@@ -37,4 +44,14 @@ impl AnthropicModelConfig {
     pub fn headers(&self) -> Option<&HashMap<String, HeaderValue>> {
         self.headers.as_ref()
     }
+
+    /// Get the models to fall back to, in order
+    pub fn fallbacks(&self) -> &[String] {
+        &self.fallbacks
+    }
+
+    /// Get the client-side rate limit (if any)
+    pub fn rate_limit(&self) -> Option<&RateLimitConfig> {
+        self.rate_limit.as_ref()
+    }
 }
@@ -1,3 +1,4 @@
+use oxy_shared::RateLimitConfig;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -7,6 +8,13 @@ pub struct GeminiModelConfig {
     pub name: String,
     pub model_ref: String,
     pub key_var: String,
+    /// Names of other entries in `models` to try, in order, when this model
+    /// is rate limited, failing or its circuit breaker is open
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallbacks: Vec<String>,
+    /// Client-side rate limit for this model's API key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimitConfig>,
 }
 
 impl GeminiModelConfig {
@@ -24,4 +32,14 @@ impl GeminiModelConfig {
     pub fn key_var(&self) -> Option<&str> {
         Some(&self.key_var)
     }
+
+    /// Get the models to fall back to, in order
+    pub fn fallbacks(&self) -> &[String] {
+        &self.fallbacks
+    }
+
+    /// Get the client-side rate limit (if any)
+    pub fn rate_limit(&self) -> Option<&RateLimitConfig> {
+        self.rate_limit.as_ref()
+    }
 }
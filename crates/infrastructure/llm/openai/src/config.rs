@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 use std::collections::HashMap;
 
-use oxy_shared::AzureModel;
+use oxy_shared::{AzureModel, RateLimitConfig};
 
 // Re-export HeaderValue so existing code importing from oxy_openai still works
 pub use oxy_shared::HeaderValue;
@@ -29,6 +29,13 @@ pub struct OpenAIModelConfig {
     pub azure: Option<AzureModel>,
     #[serde(default)]
     pub headers: Option<HashMap<String, HeaderValue>>,
+    /// Names of other entries in `models` to try, in order, when this model
+    /// is rate limited, failing or its circuit breaker is open
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallbacks: Vec<String>,
+    /// Client-side rate limit for this model's API key
+    #[serde(default)]
+    pub rate_limit: Option<RateLimitConfig>,
 }
 
 impl OpenAIModelConfig {
@@ -51,4 +58,14 @@ impl OpenAIModelConfig {
     pub fn headers(&self) -> Option<&HashMap<String, HeaderValue>> {
         self.headers.as_ref()
     }
+
+    /// Get the models to fall back to, in order
+    pub fn fallbacks(&self) -> &[String] {
+        &self.fallbacks
+    }
+
+    /// Get the client-side rate limit (if any)
+    pub fn rate_limit(&self) -> Option<&RateLimitConfig> {
+        self.rate_limit.as_ref()
+    }
 }
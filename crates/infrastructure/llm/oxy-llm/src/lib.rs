@@ -12,7 +12,7 @@ mod validation;
 // Re-export the unified Model enum and all provider config types
 pub use model::{
     AnthropicModelConfig, AzureModel, GeminiModelConfig, HeaderValue, Model, OPENAI_API_URL,
-    OllamaModelConfig, OpenAIModelConfig, default_openai_api_url,
+    OllamaModelConfig, OpenAIModelConfig, RateLimitConfig, default_openai_api_url,
 };
 
 // Re-export the trait
@@ -9,7 +9,7 @@ pub use oxy_ollama::OllamaModelConfig;
 pub use oxy_openai::{HeaderValue, OPENAI_API_URL, OpenAIModelConfig, default_openai_api_url};
 
 // Re-export from dependencies for convenience
-pub use oxy_shared::AzureModel;
+pub use oxy_shared::{AzureModel, RateLimitConfig};
 
 /// LLM model configuration supporting multiple vendors
 #[derive(Deserialize, Debug, Clone, Serialize, JsonSchema)]
@@ -77,6 +77,37 @@ impl Model {
         }
     }
 
+    /// Get the names of the models to fall back to, in order
+    pub fn fallbacks(&self) -> &[String] {
+        match self {
+            Model::OpenAI { config } => config.fallbacks(),
+            Model::Ollama { config } => config.fallbacks(),
+            Model::Google { config } => config.fallbacks(),
+            Model::Anthropic { config } => config.fallbacks(),
+        }
+    }
+
+    /// Get the client-side rate limit (if any)
+    pub fn rate_limit(&self) -> Option<&RateLimitConfig> {
+        match self {
+            Model::OpenAI { config } => config.rate_limit(),
+            Model::Ollama { config } => config.rate_limit(),
+            Model::Google { config } => config.rate_limit(),
+            Model::Anthropic { config } => config.rate_limit(),
+        }
+    }
+
+    /// Identify the API key this model calls its provider with. Models with
+    /// the same provider key share a rate limit.
+    pub fn provider_key(&self) -> String {
+        match self {
+            Model::OpenAI { config } => format!("openai:{}", config.key_var),
+            Model::Ollama { config } => format!("ollama:{}", config.api_url),
+            Model::Google { config } => format!("google:{}", config.key_var),
+            Model::Anthropic { config } => format!("anthropic:{}", config.key_var),
+        }
+    }
+
     /// Get inner OpenAI config if this is an OpenAI model
     pub fn as_openai(&self) -> Option<&OpenAIModelConfig> {
         match self {
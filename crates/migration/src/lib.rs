@@ -79,6 +79,16 @@ mod m20260416_000003_drop_branches_and_workspace_repos;
 mod m20260424_000001_create_org_billing;
 mod m20260424_000002_create_stripe_webhook_events;
 mod m20260430_000001_create_feature_flags;
+mod m20261017_000001_create_workflow_schedules;
+mod m20261018_000001_create_usage_spend;
+mod m20261018_000002_create_scim_tables;
+mod m20261018_000003_create_audit_logs;
+mod m20261018_000004_create_custom_roles;
+mod m20261018_000005_create_a2a_push_notification_configs;
+mod m20261018_000006_create_sso_identities;
+mod m20261018_000007_restrict_audit_log_purge;
+mod m20261018_000008_add_workflow_schedule_lease;
+mod m20261018_000009_add_scim_user_account_flags;
 // Legacy single-tenant Slack tables. The original CREATE migrations were
 // deleted when the universal multi-tenant Slack bot replaced them, but
 // dev/prod databases that had already applied them required the files
@@ -197,6 +207,16 @@ impl MigratorTrait for Migrator {
             Box::new(m20260422_000001_create_slack_seen_events::Migration),
             Box::new(m20260424_000001_create_slack_channel_defaults::Migration),
             Box::new(m20260427_000001_slack_oauth_state_add_channel::Migration),
+            Box::new(m20261017_000001_create_workflow_schedules::Migration),
+            Box::new(m20261018_000001_create_usage_spend::Migration),
+            Box::new(m20261018_000002_create_scim_tables::Migration),
+            Box::new(m20261018_000003_create_audit_logs::Migration),
+            Box::new(m20261018_000004_create_custom_roles::Migration),
+            Box::new(m20261018_000005_create_a2a_push_notification_configs::Migration),
+            Box::new(m20261018_000006_create_sso_identities::Migration),
+            Box::new(m20261018_000007_restrict_audit_log_purge::Migration),
+            Box::new(m20261018_000008_add_workflow_schedule_lease::Migration),
+            Box::new(m20261018_000009_add_scim_user_account_flags::Migration),
         ]
     }
 }
@@ -0,0 +1,40 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Scheduler bookkeeping for workflows with a `schedule:` block. One row per
+/// workflow; `last_scheduled_at` is the cron slot that was last claimed, which
+/// is what lets the scheduler catch up after downtime and keeps replicas from
+/// firing the same slot twice. No FK on `workspace_id`: local mode uses the
+/// nil UUID without a `workspaces` row (same as `runs.project_id`).
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TABLE workflow_schedules (
+                    workspace_id UUID NOT NULL,
+                    source_id TEXT NOT NULL,
+                    last_scheduled_at TIMESTAMPTZ NOT NULL,
+                    last_run_index INTEGER,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    PRIMARY KEY (workspace_id, source_id)
+                );
+                "#,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS workflow_schedules CASCADE")
+            .await?;
+        Ok(())
+    }
+}
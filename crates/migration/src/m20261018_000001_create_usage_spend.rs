@@ -0,0 +1,43 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Token and cost totals per workspace, user and UTC day, written as agent
+/// and workflow runs spend and read to enforce the daily and monthly caps in
+/// `budgets`. Runs without a user are recorded under the nil UUID. No FK on
+/// `workspace_id`: local mode uses the nil UUID without a `workspaces` row
+/// (same as `runs.project_id`).
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TABLE usage_spend (
+                    workspace_id UUID NOT NULL,
+                    user_id UUID NOT NULL,
+                    day DATE NOT NULL,
+                    input_tokens BIGINT NOT NULL DEFAULT 0,
+                    output_tokens BIGINT NOT NULL DEFAULT 0,
+                    cost_usd DOUBLE PRECISION NOT NULL DEFAULT 0,
+                    tool_calls BIGINT NOT NULL DEFAULT 0,
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    PRIMARY KEY (workspace_id, user_id, day)
+                );
+                CREATE INDEX idx_usage_spend_workspace_day ON usage_spend (workspace_id, day);
+                "#,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS usage_spend CASCADE")
+            .await?;
+        Ok(())
+    }
+}
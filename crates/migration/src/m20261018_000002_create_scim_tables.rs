@@ -0,0 +1,86 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// SCIM 2.0 provisioning, per org:
+/// - `scim_tokens`: bearer tokens the identity provider authenticates with,
+///   stored as SHA-256 hashes.
+/// - `scim_users`: users provisioned into the org, kept after deactivation
+///   so the identity provider can reactivate them.
+/// - `scim_groups` / `scim_group_members`: groups pushed by the identity
+///   provider.
+/// - `scim_role_mappings`: the org role (`workspace_id` NULL) or workspace
+///   role granted to members of a group, matched by display name so that
+///   mappings can be set up before the group is pushed.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TABLE scim_tokens (
+                    id UUID PRIMARY KEY,
+                    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+                    name TEXT NOT NULL,
+                    token_hash TEXT NOT NULL UNIQUE,
+                    created_by UUID NOT NULL,
+                    last_used_at TIMESTAMPTZ,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+                );
+                CREATE INDEX idx_scim_tokens_org ON scim_tokens (org_id);
+
+                CREATE TABLE scim_users (
+                    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+                    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                    external_id TEXT,
+                    active BOOLEAN NOT NULL DEFAULT TRUE,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    PRIMARY KEY (org_id, user_id)
+                );
+
+                CREATE TABLE scim_groups (
+                    id UUID PRIMARY KEY,
+                    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+                    display_name TEXT NOT NULL,
+                    external_id TEXT,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    UNIQUE (org_id, display_name)
+                );
+
+                CREATE TABLE scim_group_members (
+                    group_id UUID NOT NULL REFERENCES scim_groups(id) ON DELETE CASCADE,
+                    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                    PRIMARY KEY (group_id, user_id)
+                );
+                CREATE INDEX idx_scim_group_members_user ON scim_group_members (user_id);
+
+                CREATE TABLE scim_role_mappings (
+                    id UUID PRIMARY KEY,
+                    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+                    group_name TEXT NOT NULL,
+                    workspace_id UUID REFERENCES workspaces(id) ON DELETE CASCADE,
+                    role TEXT NOT NULL,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+                );
+                CREATE INDEX idx_scim_role_mappings_org ON scim_role_mappings (org_id);
+                "#,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "DROP TABLE IF EXISTS scim_role_mappings, scim_group_members, scim_groups, \
+                 scim_users, scim_tokens CASCADE",
+            )
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Append-only audit log of security-relevant actions.
+///
+/// `org_id`, `workspace_id` and `actor_id` deliberately have no foreign keys
+/// so entries outlive the org, workspace or user they mention, and the
+/// actor's email is copied in for the same reason. Triggers reject UPDATE and
+/// TRUNCATE outright, and DELETE unless the transaction has set
+/// `oxy.audit_purge = 'on'`, which only the retention cleanup does.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TABLE audit_logs (
+                    id UUID PRIMARY KEY,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    org_id UUID,
+                    workspace_id UUID,
+                    actor_id UUID,
+                    actor_email TEXT,
+                    action TEXT NOT NULL,
+                    target_type TEXT,
+                    target_id TEXT,
+                    metadata JSONB NOT NULL DEFAULT '{}'::jsonb
+                );
+                CREATE INDEX idx_audit_logs_org_created ON audit_logs (org_id, created_at DESC);
+                CREATE INDEX idx_audit_logs_workspace_created ON audit_logs (workspace_id, created_at DESC);
+                CREATE INDEX idx_audit_logs_created ON audit_logs (created_at);
+
+                CREATE FUNCTION audit_logs_reject_change() RETURNS trigger AS $$
+                BEGIN
+                    IF TG_OP = 'DELETE' AND current_setting('oxy.audit_purge', true) = 'on' THEN
+                        RETURN OLD;
+                    END IF;
+                    RAISE EXCEPTION 'audit_logs is append-only';
+                END;
+                $$ LANGUAGE plpgsql;
+
+                CREATE TRIGGER audit_logs_no_update_delete
+                    BEFORE UPDATE OR DELETE ON audit_logs
+                    FOR EACH ROW EXECUTE FUNCTION audit_logs_reject_change();
+                CREATE TRIGGER audit_logs_no_truncate
+                    BEFORE TRUNCATE ON audit_logs
+                    FOR EACH STATEMENT EXECUTE FUNCTION audit_logs_reject_change();
+                "#,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "DROP TABLE IF EXISTS audit_logs CASCADE; \
+                 DROP FUNCTION IF EXISTS audit_logs_reject_change();",
+            )
+            .await?;
+        Ok(())
+    }
+}
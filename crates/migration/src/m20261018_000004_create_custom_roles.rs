@@ -0,0 +1,63 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Custom workspace roles: named sets of permissions that narrow what a
+/// member's built-in role allows.
+///
+/// A role is assigned to a member through `custom_role_assignments` (at most
+/// one per member and workspace) or to an API key through
+/// `api_keys.custom_role_id`. Both references are `ON DELETE RESTRICT`: a
+/// role that is still assigned cannot be deleted, since dropping the
+/// assignment would silently widen the member's or key's access.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TABLE custom_roles (
+                    id UUID PRIMARY KEY,
+                    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+                    name TEXT NOT NULL,
+                    description TEXT,
+                    permissions JSONB NOT NULL DEFAULT '[]'::jsonb,
+                    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    UNIQUE (workspace_id, name)
+                );
+
+                CREATE TABLE custom_role_assignments (
+                    id UUID PRIMARY KEY,
+                    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+                    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+                    custom_role_id UUID NOT NULL REFERENCES custom_roles(id) ON DELETE RESTRICT,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    UNIQUE (workspace_id, user_id)
+                );
+                CREATE INDEX idx_custom_role_assignments_role ON custom_role_assignments (custom_role_id);
+
+                ALTER TABLE api_keys
+                    ADD COLUMN custom_role_id UUID REFERENCES custom_roles(id) ON DELETE RESTRICT;
+                CREATE INDEX idx_api_keys_custom_role ON api_keys (custom_role_id);
+                "#,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE api_keys DROP COLUMN IF EXISTS custom_role_id; \
+                 DROP TABLE IF EXISTS custom_role_assignments; \
+                 DROP TABLE IF EXISTS custom_roles;",
+            )
+            .await?;
+        Ok(())
+    }
+}
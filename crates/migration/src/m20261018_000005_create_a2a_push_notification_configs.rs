@@ -0,0 +1,46 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Webhook configs A2A clients register for a task through
+/// `tasks/pushNotificationConfig/set`.
+///
+/// `config_id` is the client-visible ID from the A2A config, which need not
+/// be a UUID, so it is unique per task rather than the primary key. `token`
+/// is kept in plain text because it is the key notifications are signed with.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TABLE a2a_push_notification_configs (
+                    id UUID PRIMARY KEY,
+                    task_id UUID NOT NULL REFERENCES a2a_tasks(id) ON DELETE CASCADE,
+                    agent_name TEXT NOT NULL,
+                    config_id TEXT NOT NULL,
+                    url TEXT NOT NULL,
+                    token TEXT,
+                    authentication JSONB,
+                    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+                    UNIQUE (task_id, config_id)
+                );
+                CREATE INDEX idx_a2a_push_notification_configs_agent_task
+                    ON a2a_push_notification_configs (agent_name, task_id);
+                "#,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP TABLE IF EXISTS a2a_push_notification_configs;")
+            .await?;
+        Ok(())
+    }
+}
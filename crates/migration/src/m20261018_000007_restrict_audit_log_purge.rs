@@ -0,0 +1,79 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Only let the retention cleanup delete audit entries.
+///
+/// The `oxy.audit_purge` setting could be set by any session, so any role
+/// that can write `audit_logs` could delete entries with it. Deletes now have
+/// to go through `purge_audit_logs(days)`, a `SECURITY DEFINER` function
+/// that only removes entries at least a day old. `EXECUTE` on it is revoked
+/// from `PUBLIC`: when the server connects as a role that does not own the
+/// table, grant that role `EXECUTE` on the function instead of `DELETE` on
+/// the table.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE OR REPLACE FUNCTION audit_logs_reject_change() RETURNS trigger AS $$
+                DECLARE
+                    stack TEXT;
+                BEGIN
+                    IF TG_OP = 'DELETE' THEN
+                        GET DIAGNOSTICS stack = PG_CONTEXT;
+                        IF stack LIKE '%function purge_audit_logs(integer)%' THEN
+                            RETURN OLD;
+                        END IF;
+                    END IF;
+                    RAISE EXCEPTION 'audit_logs is append-only';
+                END;
+                $$ LANGUAGE plpgsql;
+
+                CREATE FUNCTION purge_audit_logs(days INTEGER) RETURNS BIGINT
+                SECURITY DEFINER
+                SET search_path = pg_catalog, public
+                AS $$
+                DECLARE
+                    purged BIGINT;
+                BEGIN
+                    IF days IS NULL OR days < 1 THEN
+                        RAISE EXCEPTION 'audit entries are kept for at least a day';
+                    END IF;
+                    DELETE FROM audit_logs WHERE created_at < now() - make_interval(days => days);
+                    GET DIAGNOSTICS purged = ROW_COUNT;
+                    RETURN purged;
+                END;
+                $$ LANGUAGE plpgsql;
+
+                REVOKE ALL ON FUNCTION purge_audit_logs(INTEGER) FROM PUBLIC;
+                "#,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DROP FUNCTION IF EXISTS purge_audit_logs(INTEGER);
+
+                CREATE OR REPLACE FUNCTION audit_logs_reject_change() RETURNS trigger AS $$
+                BEGIN
+                    IF TG_OP = 'DELETE' AND current_setting('oxy.audit_purge', true) = 'on' THEN
+                        RETURN OLD;
+                    END IF;
+                    RAISE EXCEPTION 'audit_logs is append-only';
+                END;
+                $$ LANGUAGE plpgsql;
+                "#,
+            )
+            .await?;
+        Ok(())
+    }
+}
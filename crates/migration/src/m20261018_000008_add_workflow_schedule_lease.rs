@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Lease on the scheduled run of a workflow.
+///
+/// The replica that starts a scheduled run takes the lease and renews it
+/// while the run is in progress, so other replicas can tell the previous
+/// run is still active without seeing its task. A lease left behind by a
+/// replica that went down expires on its own.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE workflow_schedules
+                    ADD COLUMN run_lease_owner UUID,
+                    ADD COLUMN run_lease_expires_at TIMESTAMPTZ;
+                "#,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE workflow_schedules
+                    DROP COLUMN IF EXISTS run_lease_expires_at,
+                    DROP COLUMN IF EXISTS run_lease_owner;
+                "#,
+            )
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// What an org's SCIM may do to a shared Oxy account.
+///
+/// - `created_user`: the org's SCIM created the account, so it may change
+///   the account's email.
+/// - `deactivated_account`: the org's SCIM marked the account deleted when
+///   it left its last org, so it may reactivate it.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE scim_users
+                    ADD COLUMN created_user BOOLEAN NOT NULL DEFAULT FALSE,
+                    ADD COLUMN deactivated_account BOOLEAN NOT NULL DEFAULT FALSE;
+                "#,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                ALTER TABLE scim_users
+                    DROP COLUMN IF EXISTS deactivated_account,
+                    DROP COLUMN IF EXISTS created_user;
+                "#,
+            )
+            .await?;
+        Ok(())
+    }
+}
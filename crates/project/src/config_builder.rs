@@ -38,6 +38,9 @@ impl ConfigBuilder {
             slack_legacy: None,
             mcp: None,
             a2a: None,
+            result_cache: None,
+            budgets: None,
+            access_policies: Vec::new(),
             protected_branches: None,
             base_branch: None,
             repositories: vec![],
@@ -118,6 +118,7 @@ impl DatabaseConfigBuilder {
                 database: postgres_config.database,
                 database_var: None,
             }),
+            cache_ttl_secs: None,
         })
     }
 
@@ -159,6 +160,7 @@ impl DatabaseConfigBuilder {
                 database: redshift_config.database,
                 database_var: None,
             }),
+            cache_ttl_secs: None,
         })
     }
 
@@ -200,6 +202,7 @@ impl DatabaseConfigBuilder {
                 database: mysql_config.database,
                 database_var: None,
             }),
+            cache_ttl_secs: None,
         })
     }
 
@@ -255,6 +258,7 @@ impl DatabaseConfigBuilder {
                 settings_prefix: None,
                 filters: HashMap::new(),
             }),
+            cache_ttl_secs: None,
         })
     }
 
@@ -293,6 +297,7 @@ impl DatabaseConfigBuilder {
                 datasets: HashMap::new(),
                 dry_run_limit: bigquery_config.dry_run_limit,
             }),
+            cache_ttl_secs: None,
         })
     }
 
@@ -311,6 +316,7 @@ impl DatabaseConfigBuilder {
                         .unwrap_or_else(|| "data".to_string()),
                 },
             }),
+            cache_ttl_secs: None,
         })
     }
 
@@ -394,6 +400,7 @@ impl DatabaseConfigBuilder {
                 filters: HashMap::new(),
                 auth_type,
             }),
+            cache_ttl_secs: None,
         })
     }
 
@@ -0,0 +1,504 @@
+//! Column-level access control for semantic fields.
+//!
+//! Dimensions and measures may declare an `access` level and dimensions a
+//! `masking` rule. A caller's clearance is compared against them to decide
+//! whether a field is shown as-is, masked, or hidden entirely. A clearance of
+//! `None` means no column access rules apply.
+
+use std::collections::{HashMap, HashSet};
+
+use sha2::{Digest, Sha256};
+
+use crate::models::{AccessLevel, Dimension, MaskingRule, Measure, View};
+
+/// Placeholder returned for redacted values.
+pub const REDACTED: &str = "***";
+
+/// How a field is exposed to a caller.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldAccess {
+    Visible,
+    Masked(MaskingRule),
+    Hidden,
+}
+
+impl Dimension {
+    /// Access level needed to see raw values. Dimensions with a masking rule
+    /// but no explicit level default to `restricted`.
+    pub fn required_access(&self) -> AccessLevel {
+        match (self.access, &self.masking) {
+            (Some(access), _) => access,
+            (None, Some(_)) => AccessLevel::Restricted,
+            (None, None) => AccessLevel::Public,
+        }
+    }
+}
+
+impl Measure {
+    /// Access level needed to query the measure.
+    pub fn required_access(&self) -> AccessLevel {
+        self.access.unwrap_or(AccessLevel::Public)
+    }
+}
+
+/// Resolve how `dimension` is exposed to a caller with `clearance`.
+///
+/// `only_in_aggregate` dimensions are hidden: measures over the same column
+/// still work, but the raw values cannot be selected or grouped by.
+pub fn dimension_access(dimension: &Dimension, clearance: Option<AccessLevel>) -> FieldAccess {
+    let Some(clearance) = clearance else {
+        return FieldAccess::Visible;
+    };
+    if clearance >= dimension.required_access() {
+        return FieldAccess::Visible;
+    }
+    match &dimension.masking {
+        Some(MaskingRule::OnlyInAggregate) | None => FieldAccess::Hidden,
+        Some(rule) => FieldAccess::Masked(rule.clone()),
+    }
+}
+
+/// Resolve how `measure` is exposed to a caller with `clearance`.
+pub fn measure_access(measure: &Measure, clearance: Option<AccessLevel>) -> FieldAccess {
+    match clearance {
+        Some(clearance) if clearance < measure.required_access() => FieldAccess::Hidden,
+        _ => FieldAccess::Visible,
+    }
+}
+
+/// Wrap `expr` in SQL that applies `rule` for the given database dialect
+/// (as returned by `Database::dialect()`).
+///
+/// `hash` prefixes values with `salt` before hashing, so masked values
+/// cannot be reversed by hashing likely inputs. `salt` must be a plain
+/// alphanumeric string; it is inlined as a SQL literal.
+///
+/// Returns `None` for `only_in_aggregate`, which has no row-level form.
+/// Dialects without a SHA-256 function fall back to redaction for `hash`.
+pub fn mask_sql(rule: &MaskingRule, expr: &str, dialect: &str, salt: &str) -> Option<String> {
+    let text = cast_to_text(expr, dialect);
+    let masked = match rule {
+        MaskingRule::Hash => {
+            let salted = format!("CONCAT('{salt}', {text})");
+            match dialect {
+                "postgres" => format!("encode(sha256(convert_to({salted}, 'UTF8')), 'hex')"),
+                "duckdb" => format!("sha256({salted})"),
+                "bigquery" => format!("TO_HEX(SHA256({salted}))"),
+                "snowflake" | "mysql" => format!("SHA2({salted}, 256)"),
+                "clickhouse" => format!("lower(hex(SHA256({salted})))"),
+                "mssql" => {
+                    format!("LOWER(CONVERT(VARCHAR(64), HASHBYTES('SHA2_256', {salted}), 2))")
+                }
+                "trino" => format!("lower(to_hex(sha256(to_utf8({salted}))))"),
+                _ => format!("'{REDACTED}'"),
+            }
+        }
+        MaskingRule::Redact => format!("'{REDACTED}'"),
+        MaskingRule::Truncate { length } => match dialect {
+            "bigquery" | "sqlite" => format!("SUBSTR({text}, 1, {length})"),
+            _ => format!("SUBSTRING({text}, 1, {length})"),
+        },
+        MaskingRule::OnlyInAggregate => return None,
+    };
+    Some(masked)
+}
+
+fn cast_to_text(expr: &str, dialect: &str) -> String {
+    match dialect {
+        "clickhouse" => format!("toString({expr})"),
+        "postgres" | "sqlite" => format!("CAST(({expr}) AS TEXT)"),
+        "bigquery" => format!("CAST(({expr}) AS STRING)"),
+        "mysql" => format!("CAST(({expr}) AS CHAR)"),
+        "mssql" => format!("CAST(({expr}) AS NVARCHAR(MAX))"),
+        _ => format!("CAST(({expr}) AS VARCHAR)"),
+    }
+}
+
+/// Apply `rule` to a single value that has already been fetched.
+///
+/// Produces the same output as [`mask_sql`] for `hash`, `redact` and
+/// `truncate`. Returns `None` for `only_in_aggregate`.
+pub fn mask_value(rule: &MaskingRule, value: &str, salt: &str) -> Option<String> {
+    match rule {
+        MaskingRule::Hash => Some(hex::encode(Sha256::digest(format!("{salt}{value}")))),
+        MaskingRule::Redact => Some(REDACTED.to_string()),
+        MaskingRule::Truncate { length } => Some(value.chars().take(*length).collect()),
+        MaskingRule::OnlyInAggregate => None,
+    }
+}
+
+/// Copy of `view` without the fields hidden from `clearance`, and without
+/// sample values for masked dimensions. Expressions are left untouched, so
+/// this is suitable for metadata shown to users and prompts.
+pub fn redact_view(view: &View, clearance: Option<AccessLevel>) -> View {
+    let mut view = view.clone();
+    view.dimensions
+        .retain_mut(|dimension| match dimension_access(dimension, clearance) {
+            FieldAccess::Visible => true,
+            FieldAccess::Masked(_) => {
+                dimension.samples = None;
+                true
+            }
+            FieldAccess::Hidden => false,
+        });
+    let hidden: HashSet<String> = hidden_measures(&view, clearance)
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    if let Some(measures) = view.measures.as_mut() {
+        measures.retain(|measure| !hidden.contains(&measure.name));
+    }
+    view
+}
+
+/// Copy of `view` for query compilation: masked dimensions' expressions are
+/// rewritten so compiled SQL returns masked values.
+///
+/// Nothing is removed, since measures and joins may still depend on hidden
+/// columns. Callers must reject queries that reference [`hidden_fields`].
+pub fn restrict_view(
+    view: &View,
+    clearance: Option<AccessLevel>,
+    dialect: &str,
+    salt: &str,
+) -> View {
+    let keys = entity_keys(view);
+    let mut view = view.clone();
+    for dimension in &mut view.dimensions {
+        if keys.contains(&dimension.name.as_str()) {
+            continue;
+        }
+        if let FieldAccess::Masked(rule) = dimension_access(dimension, clearance)
+            && let Some(masked) = mask_sql(&rule, &dimension.expr, dialect, salt)
+        {
+            dimension.expr = masked;
+            dimension.original_expr = None;
+        }
+    }
+    view
+}
+
+/// Names of the fields in `view` that `clearance` may not reference at all.
+///
+/// Masked entity keys are included: their expressions stay raw so joins keep
+/// working, so they cannot be selected either.
+pub fn hidden_fields(view: &View, clearance: Option<AccessLevel>) -> Vec<String> {
+    let keys = entity_keys(view);
+    let dimensions = view
+        .dimensions
+        .iter()
+        .filter(|d| match dimension_access(d, clearance) {
+            FieldAccess::Visible => false,
+            FieldAccess::Masked(_) => keys.contains(&d.name.as_str()),
+            FieldAccess::Hidden => true,
+        })
+        .map(|d| d.name.clone());
+    let measures = hidden_measures(view, clearance)
+        .into_iter()
+        .map(str::to_string);
+    dimensions.chain(measures).collect()
+}
+
+/// Names of the measures hidden from `clearance`, including measures built
+/// from a hidden one.
+fn hidden_measures(view: &View, clearance: Option<AccessLevel>) -> HashSet<&str> {
+    let mut hidden: HashSet<&str> = view
+        .measures
+        .iter()
+        .flatten()
+        .filter(|m| measure_access(m, clearance) == FieldAccess::Hidden)
+        .map(|m| m.name.as_str())
+        .collect();
+    loop {
+        let before = hidden.len();
+        for measure in view.measures.iter().flatten() {
+            if measure
+                .referenced_measures()
+                .iter()
+                .any(|name| hidden.contains(name.as_str()))
+            {
+                hidden.insert(&measure.name);
+            }
+        }
+        if hidden.len() == before {
+            return hidden;
+        }
+    }
+}
+
+fn entity_keys(view: &View) -> Vec<&str> {
+    view.entities
+        .iter()
+        .flat_map(|entity| {
+            entity
+                .keys
+                .iter()
+                .flatten()
+                .chain(entity.key.iter())
+                .map(String::as_str)
+        })
+        .collect()
+}
+
+/// Column rules for raw SQL against `database`, keyed by the table a view
+/// reads from and then by lowercased column name.
+///
+/// Only dimensions whose expression is a plain column reference can be
+/// matched to a column, and only views with a `table` can be matched to a
+/// table. When views over the same table disagree on a column, the strictest
+/// rule wins.
+pub fn column_access(
+    views: &[View],
+    database: &str,
+    clearance: Option<AccessLevel>,
+) -> HashMap<String, HashMap<String, FieldAccess>> {
+    let mut tables: HashMap<String, HashMap<String, FieldAccess>> = HashMap::new();
+    if clearance.is_none() {
+        return tables;
+    }
+    let views = views
+        .iter()
+        .filter(|v| v.datasource.as_deref().is_none_or(|ds| ds == database));
+    for view in views {
+        let Some(table) = &view.table else {
+            continue;
+        };
+        for dimension in &view.dimensions {
+            let access = dimension_access(dimension, clearance);
+            if access == FieldAccess::Visible {
+                continue;
+            }
+            let Some(column) = column_name(dimension.get_original_expr()) else {
+                continue;
+            };
+            let columns = tables.entry(table.clone()).or_default();
+            match columns.get(&column) {
+                Some(FieldAccess::Hidden) => {}
+                Some(FieldAccess::Masked(_)) if access != FieldAccess::Hidden => {}
+                _ => {
+                    columns.insert(column, access);
+                }
+            }
+        }
+    }
+    tables
+}
+
+/// Column name of a bare, optionally qualified or quoted, column reference.
+fn column_name(expr: &str) -> Option<String> {
+    let last = expr.trim().rsplit('.').next()?;
+    let name = last.trim_matches(|c| matches!(c, '"' | '`' | '[' | ']'));
+    let is_identifier = !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+    is_identifier.then(|| name.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{DimensionBuilder, EntityBuilder, MeasureBuilder, ViewBuilder};
+
+    fn email() -> Dimension {
+        DimensionBuilder::new()
+            .name("email")
+            .string_type()
+            .expr("customers.email")
+            .sample("a@example.com")
+            .access(AccessLevel::Internal)
+            .masking(MaskingRule::Hash)
+            .build()
+            .unwrap()
+    }
+
+    fn ssn() -> Dimension {
+        DimensionBuilder::new()
+            .name("ssn")
+            .string_type()
+            .expr("ssn")
+            .access(AccessLevel::Restricted)
+            .build()
+            .unwrap()
+    }
+
+    fn view() -> View {
+        ViewBuilder::new()
+            .name("customers")
+            .datasource("warehouse")
+            .table("customers")
+            .entity(
+                EntityBuilder::new()
+                    .name("customer")
+                    .primary()
+                    .key("email")
+                    .build()
+                    .unwrap(),
+            )
+            .dimension(email())
+            .dimension(ssn())
+            .dimension(
+                DimensionBuilder::new()
+                    .name("country")
+                    .string_type()
+                    .expr("country")
+                    .build()
+                    .unwrap(),
+            )
+            .measure(
+                MeasureBuilder::new()
+                    .name("lifetime_value")
+                    .sum()
+                    .expr("ltv")
+                    .access(AccessLevel::Restricted)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_no_clearance_sees_everything() {
+        assert_eq!(dimension_access(&ssn(), None), FieldAccess::Visible);
+        assert!(hidden_fields(&view(), None).is_empty());
+        assert!(column_access(&[view()], "warehouse", None).is_empty());
+    }
+
+    #[test]
+    fn test_dimension_access_by_clearance() {
+        assert_eq!(
+            dimension_access(&email(), Some(AccessLevel::Public)),
+            FieldAccess::Masked(MaskingRule::Hash)
+        );
+        assert_eq!(
+            dimension_access(&email(), Some(AccessLevel::Internal)),
+            FieldAccess::Visible
+        );
+        assert_eq!(
+            dimension_access(&ssn(), Some(AccessLevel::Internal)),
+            FieldAccess::Hidden
+        );
+    }
+
+    #[test]
+    fn test_masking_without_access_defaults_to_restricted() {
+        let mut dimension = email();
+        dimension.access = None;
+        assert_eq!(dimension.required_access(), AccessLevel::Restricted);
+    }
+
+    #[test]
+    fn test_only_in_aggregate_is_hidden() {
+        let mut dimension = email();
+        dimension.masking = Some(MaskingRule::OnlyInAggregate);
+        assert_eq!(
+            dimension_access(&dimension, Some(AccessLevel::Public)),
+            FieldAccess::Hidden
+        );
+    }
+
+    #[test]
+    fn test_redact_view() {
+        let redacted = redact_view(&view(), Some(AccessLevel::Public));
+        let names: Vec<_> = redacted.dimensions.iter().map(|d| &d.name).collect();
+        assert_eq!(names, ["email", "country"]);
+        assert!(redacted.dimensions[0].samples.is_none());
+        assert!(redacted.measures.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_measures_built_from_hidden_measures_are_hidden() {
+        let mut view = view();
+        let measures = view.measures.as_mut().unwrap();
+        measures.push(
+            MeasureBuilder::new()
+                .name("customer_count")
+                .count()
+                .build()
+                .unwrap(),
+        );
+        measures.push(
+            MeasureBuilder::new()
+                .name("value_per_customer")
+                .ratio("lifetime_value", "customer_count")
+                .build()
+                .unwrap(),
+        );
+        let hidden = hidden_fields(&view, Some(AccessLevel::Internal));
+        assert!(hidden.contains(&"value_per_customer".to_string()));
+        assert!(!hidden.contains(&"customer_count".to_string()));
+    }
+
+    #[test]
+    fn test_restrict_view_masks_expressions() {
+        let mut view = view();
+        view.entities[0].key = Some("country".to_string());
+        let restricted = restrict_view(&view, Some(AccessLevel::Public), "duckdb", "s4lt");
+        assert_eq!(
+            restricted.dimensions[0].expr,
+            "sha256(CONCAT('s4lt', CAST((customers.email) AS VARCHAR)))"
+        );
+        assert_eq!(restricted.dimensions.len(), 3);
+        assert_eq!(
+            hidden_fields(&view, Some(AccessLevel::Public)),
+            ["ssn", "lifetime_value"]
+        );
+    }
+
+    #[test]
+    fn test_masked_entity_key_stays_raw_and_hidden() {
+        let restricted = restrict_view(&view(), Some(AccessLevel::Public), "duckdb", "s4lt");
+        assert_eq!(restricted.dimensions[0].expr, "customers.email");
+        assert_eq!(
+            hidden_fields(&view(), Some(AccessLevel::Public)),
+            ["email", "ssn", "lifetime_value"]
+        );
+    }
+
+    #[test]
+    fn test_mask_value() {
+        assert_eq!(
+            mask_value(&MaskingRule::Hash, "c", "ab").unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_ne!(
+            mask_value(&MaskingRule::Hash, "abc", "").unwrap(),
+            mask_value(&MaskingRule::Hash, "abc", "s4lt").unwrap()
+        );
+        assert_eq!(
+            mask_value(&MaskingRule::Redact, "abc", "s4lt").unwrap(),
+            REDACTED
+        );
+        assert_eq!(
+            mask_value(&MaskingRule::Truncate { length: 2 }, "abc", "s4lt").unwrap(),
+            "ab"
+        );
+        assert!(mask_value(&MaskingRule::OnlyInAggregate, "abc", "s4lt").is_none());
+    }
+
+    #[test]
+    fn test_column_access() {
+        let tables = column_access(&[view()], "warehouse", Some(AccessLevel::Public));
+        assert_eq!(tables.len(), 1);
+        let columns = &tables["customers"];
+        assert_eq!(
+            columns.get("email"),
+            Some(&FieldAccess::Masked(MaskingRule::Hash))
+        );
+        assert_eq!(columns.get("ssn"), Some(&FieldAccess::Hidden));
+        assert!(!columns.contains_key("country"));
+        assert!(column_access(&[view()], "other", Some(AccessLevel::Public)).is_empty());
+
+        let mut untabled = view();
+        untabled.table = None;
+        assert!(column_access(&[untabled], "warehouse", Some(AccessLevel::Public)).is_empty());
+    }
+
+    #[test]
+    fn test_masking_rule_yaml() {
+        let rule: MaskingRule = serde_yaml::from_str("type: truncate\nlength: 4").unwrap();
+        assert_eq!(rule, MaskingRule::Truncate { length: 4 });
+        let rule: MaskingRule = serde_yaml::from_str("type: only_in_aggregate").unwrap();
+        assert_eq!(rule, MaskingRule::OnlyInAggregate);
+    }
+}
@@ -110,6 +110,10 @@ pub struct DimensionBuilder {
     label: Option<String>,
     samples: Option<Vec<String>>,
     synonyms: Option<Vec<String>>,
+    access: Option<AccessLevel>,
+    masking: Option<MaskingRule>,
+    grains: Option<Vec<TimeGrain>>,
+    fiscal_year_start: Option<u32>,
 }
 
 impl DimensionBuilder {
@@ -122,6 +126,10 @@ impl DimensionBuilder {
             label: None,
             samples: None,
             synonyms: None,
+            access: None,
+            masking: None,
+            grains: None,
+            fiscal_year_start: None,
         }
     }
 
@@ -207,6 +215,26 @@ impl DimensionBuilder {
         self
     }
 
+    pub fn access(mut self, access: AccessLevel) -> Self {
+        self.access = Some(access);
+        self
+    }
+
+    pub fn masking(mut self, masking: MaskingRule) -> Self {
+        self.masking = Some(masking);
+        self
+    }
+
+    pub fn grains<I: IntoIterator<Item = TimeGrain>>(mut self, grains: I) -> Self {
+        self.grains = Some(grains.into_iter().collect());
+        self
+    }
+
+    pub fn fiscal_year_start(mut self, month: u32) -> Self {
+        self.fiscal_year_start = Some(month);
+        self
+    }
+
     pub fn build(self) -> Result<Dimension, String> {
         let dimension = Dimension {
             name: self.name.ok_or("Dimension name is required")?,
@@ -216,6 +244,10 @@ impl DimensionBuilder {
             original_expr: None,
             samples: self.samples,
             synonyms: self.synonyms,
+            access: self.access,
+            masking: self.masking,
+            grains: self.grains,
+            fiscal_year_start: self.fiscal_year_start,
         };
 
         let validation = dimension.validate();
@@ -247,6 +279,8 @@ pub struct MeasureBuilder {
     filters: Option<Vec<MeasureFilter>>,
     samples: Option<Vec<String>>,
     synonyms: Option<Vec<String>>,
+    access: Option<AccessLevel>,
+    composition: MeasureComposition,
 }
 
 impl MeasureBuilder {
@@ -260,6 +294,8 @@ impl MeasureBuilder {
             filters: None,
             samples: None,
             synonyms: None,
+            access: None,
+            composition: MeasureComposition::default(),
         }
     }
 
@@ -308,6 +344,67 @@ impl MeasureBuilder {
         self
     }
 
+    /// Expression over other measures, set with [`Self::expr`] using
+    /// `{measure_name}` references.
+    pub fn derived(mut self) -> Self {
+        self.measure_type = Some(MeasureType::Derived);
+        self
+    }
+
+    pub fn ratio<S: Into<String>>(mut self, numerator: S, denominator: S) -> Self {
+        self.measure_type = Some(MeasureType::Ratio);
+        self.composition.numerator = Some(numerator.into());
+        self.composition.denominator = Some(denominator.into());
+        self
+    }
+
+    pub fn cumulative<S: Into<String>>(mut self, measure: S, time_dimension: S) -> Self {
+        self.measure_type = Some(MeasureType::Cumulative);
+        self.composition.measure = Some(measure.into());
+        self.composition.time_dimension = Some(time_dimension.into());
+        self
+    }
+
+    pub fn window<S: Into<String>>(
+        mut self,
+        measure: S,
+        time_dimension: S,
+        periods: usize,
+    ) -> Self {
+        self.measure_type = Some(MeasureType::Window);
+        self.composition.measure = Some(measure.into());
+        self.composition.time_dimension = Some(time_dimension.into());
+        self.composition.window = Some(periods);
+        self
+    }
+
+    pub fn period_over_period<S: Into<String>>(mut self, measure: S, time_dimension: S) -> Self {
+        self.measure_type = Some(MeasureType::PeriodOverPeriod);
+        self.composition.measure = Some(measure.into());
+        self.composition.time_dimension = Some(time_dimension.into());
+        self
+    }
+
+    pub fn grain(mut self, grain: TimeGrain) -> Self {
+        self.composition.grain = Some(grain);
+        self
+    }
+
+    pub fn aggregation(mut self, aggregation: WindowAggregation) -> Self {
+        self.composition.aggregation = Some(aggregation);
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.composition.offset = Some(offset);
+        self
+    }
+
+    pub fn comparison(mut self, comparison: PeriodComparison) -> Self {
+        self.composition.comparison = Some(comparison);
+        self
+    }
+
     pub fn measure_type(mut self, measure_type: MeasureType) -> Self {
         self.measure_type = Some(measure_type);
         self
@@ -370,6 +467,11 @@ impl MeasureBuilder {
         self
     }
 
+    pub fn access(mut self, access: AccessLevel) -> Self {
+        self.access = Some(access);
+        self
+    }
+
     pub fn build(self) -> Result<Measure, String> {
         let measure = Measure {
             name: self.name.ok_or("Measure name is required")?,
@@ -380,6 +482,8 @@ impl MeasureBuilder {
             filters: self.filters,
             samples: self.samples,
             synonyms: self.synonyms,
+            access: self.access,
+            composition: self.composition,
         };
 
         let validation = measure.validate();
@@ -513,6 +617,7 @@ impl ViewBuilder {
             } else {
                 Some(self.measures)
             },
+            access_policies: None,
         };
 
         let validation = view.validate();
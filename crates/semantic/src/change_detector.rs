@@ -221,6 +221,14 @@ impl ChangeDetector {
         })
     }
 
+    /// Compute a single hash over all semantic files.
+    /// Changes whenever a view or topic is added, removed or edited.
+    pub fn semantic_fingerprint(&self) -> Result<String, SemanticLayerError> {
+        let file_hashes = self.scan_semantic_files()?;
+        let json_str = serde_json::to_string(&file_hashes).unwrap_or_default();
+        Ok(hash_string(&json_str))
+    }
+
     /// Scan semantic files and compute their hashes.
     /// Scans from the project root to match the parser's scope.
     fn scan_semantic_files(&self) -> Result<BTreeMap<String, String>, SemanticLayerError> {
@@ -505,6 +513,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_semantic_fingerprint_tracks_semantic_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let semantic_dir = temp_dir.path().join("semantics");
+        let target_dir = temp_dir.path().join(".semantics");
+
+        std::fs::create_dir_all(&semantic_dir).unwrap();
+        std::fs::create_dir_all(&target_dir).unwrap();
+        std::fs::write(semantic_dir.join("orders.view.yml"), "name: orders").unwrap();
+
+        let detector = ChangeDetector::new(&semantic_dir, &target_dir);
+        let before = detector.semantic_fingerprint().unwrap();
+
+        // Unrelated files don't affect the fingerprint
+        std::fs::write(semantic_dir.join("notes.yml"), "todo").unwrap();
+        assert_eq!(detector.semantic_fingerprint().unwrap(), before);
+
+        std::fs::write(
+            semantic_dir.join("orders.view.yml"),
+            "name: orders\ndescription: Orders",
+        )
+        .unwrap();
+        assert_ne!(detector.semantic_fingerprint().unwrap(), before);
+    }
+
     #[test]
     fn test_detect_changes_config_changed() {
         let temp_dir = TempDir::new().unwrap();
@@ -333,6 +333,7 @@ mod tests {
             }],
             dimensions: vec![],
             measures: None,
+            access_policies: None,
         };
 
         let shipments_view = View {
@@ -360,6 +361,7 @@ mod tests {
             ],
             dimensions: vec![],
             measures: None,
+            access_policies: None,
         };
 
         let semantic_layer = SemanticLayer {
@@ -404,6 +406,7 @@ mod tests {
             }],
             dimensions: vec![],
             measures: None,
+            access_policies: None,
         };
 
         let view2 = View {
@@ -426,6 +429,7 @@ mod tests {
             }],
             dimensions: vec![],
             measures: None,
+            access_policies: None,
         };
 
         let semantic_layer = SemanticLayer {
@@ -461,6 +465,7 @@ mod tests {
             }],
             dimensions: vec![],
             measures: None,
+            access_policies: None,
         };
 
         let orders_view = View {
@@ -479,6 +484,7 @@ mod tests {
             }],
             dimensions: vec![],
             measures: None,
+            access_policies: None,
         };
 
         let semantic_layer = SemanticLayer {
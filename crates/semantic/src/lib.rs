@@ -1,11 +1,14 @@
 // Essential semantic layer functionality
+pub mod access;
 pub mod build_manifest;
 pub mod builder;
 pub mod change_detector;
 pub mod entity_graph;
 pub mod errors;
+pub mod metrics;
 pub mod models;
 pub mod parser;
+pub mod time;
 pub mod types;
 pub mod validation;
 pub mod variables;
@@ -19,9 +22,10 @@ pub use builder::{
 pub use change_detector::{ChangeDetectionResult, ChangeDetector, hash_database_config};
 pub use errors::SemanticLayerError;
 pub use models::{
-    AccessLevel, DatabaseDetails, Dimension, DimensionType, Entity, EntityType, Measure,
-    MeasureFilter, MeasureType, SemanticLayer, SemanticTableRef, Topic, TopicArrayFilter,
-    TopicDateRangeFilter, TopicFilter, TopicFilterType, TopicScalarFilter, View,
+    AccessLevel, DatabaseDetails, Dimension, DimensionType, Entity, EntityType, MaskingRule,
+    Measure, MeasureComposition, MeasureFilter, MeasureType, PeriodComparison, SemanticLayer,
+    SemanticTableRef, TimeGrain, Topic, TopicArrayFilter, TopicDateRangeFilter, TopicFilter,
+    TopicFilterType, TopicScalarFilter, View, ViewAccessPolicy, WindowAggregation,
 };
 pub use parser::{ParseResult, ParserConfig, SemanticLayerParser, parse_semantic_layer_from_dir};
 pub use types::SyncMetrics;
@@ -0,0 +1,467 @@
+//! Measures built from other measures.
+//!
+//! `derived` and `ratio` measures are inlined into the aggregate SQL of the
+//! measures they reference, so query engines only ever see `custom` measures
+//! ([`expand_measures`]). Windowed measures (`cumulative`, `window` and
+//! `period_over_period`) need the grouped result first: callers compile the
+//! underlying measure, then apply [`window_sql`] over the grouped rows.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::errors::SemanticLayerError;
+use crate::models::{
+    Measure, MeasureComposition, MeasureType, PeriodComparison, View, WindowAggregation,
+};
+
+impl Measure {
+    /// Names of the measures this one is built from, in the order they
+    /// appear.
+    pub fn referenced_measures(&self) -> Vec<String> {
+        let composition = &self.composition;
+        match self.measure_type {
+            MeasureType::Derived => {
+                static REFERENCE_REGEX: OnceLock<Regex> = OnceLock::new();
+                let regex = REFERENCE_REGEX.get_or_init(|| {
+                    Regex::new(r"\{([A-Za-z_][A-Za-z0-9_]*)\}")
+                        .expect("Measure reference regex should be valid")
+                });
+                let mut names: Vec<String> = Vec::new();
+                for capture in regex.captures_iter(self.expr.as_deref().unwrap_or_default()) {
+                    let name = capture[1].to_string();
+                    if !names.contains(&name) {
+                        names.push(name);
+                    }
+                }
+                names
+            }
+            MeasureType::Ratio => composition
+                .numerator
+                .iter()
+                .chain(composition.denominator.iter())
+                .cloned()
+                .collect(),
+            MeasureType::Cumulative | MeasureType::Window | MeasureType::PeriodOverPeriod => {
+                composition.measure.iter().cloned().collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Find a chain of measure references in `view` that loops back on itself.
+///
+/// Returns the measure names along the cycle, starting and ending with the
+/// same name.
+pub fn find_measure_cycle(view: &View) -> Option<Vec<String>> {
+    let measures = measures_by_name(view);
+    let mut done: Vec<&str> = Vec::new();
+    for measure in view.measures.iter().flatten() {
+        let mut path = Vec::new();
+        if let Some(cycle) = visit(&measures, &measure.name, &mut path, &mut done) {
+            return Some(cycle);
+        }
+    }
+    None
+}
+
+fn visit<'a>(
+    measures: &HashMap<&'a str, &'a Measure>,
+    name: &'a str,
+    path: &mut Vec<&'a str>,
+    done: &mut Vec<&'a str>,
+) -> Option<Vec<String>> {
+    if let Some(start) = path.iter().position(|n| *n == name) {
+        let mut cycle: Vec<String> = path[start..].iter().map(|n| n.to_string()).collect();
+        cycle.push(name.to_string());
+        return Some(cycle);
+    }
+    if done.contains(&name) {
+        return None;
+    }
+    let measure = measures.get(name)?;
+    path.push(name);
+    for reference in measure.referenced_measures() {
+        if let Some((key, _)) = measures.get_key_value(reference.as_str())
+            && let Some(cycle) = visit(measures, key, path, done)
+        {
+            return Some(cycle);
+        }
+    }
+    path.pop();
+    done.push(name);
+    None
+}
+
+/// Copy of `view` where `derived` and `ratio` measures are rewritten as
+/// `custom` measures over the aggregate SQL of the measures they reference.
+///
+/// Windowed measures are left as they are.
+pub fn expand_measures(view: &View) -> Result<View, SemanticLayerError> {
+    let mut expanded = view.clone();
+    if let Some(measures) = &view.measures {
+        expanded.measures = Some(expand_measure_list(measures)?);
+    }
+    Ok(expanded)
+}
+
+/// [`expand_measures`] over a view's measure list.
+pub fn expand_measure_list(measures: &[Measure]) -> Result<Vec<Measure>, SemanticLayerError> {
+    let by_name: HashMap<&str, &Measure> = measures.iter().map(|m| (m.name.as_str(), m)).collect();
+    let mut expanded = measures.to_vec();
+    for measure in &mut expanded {
+        if !matches!(
+            measure.measure_type,
+            MeasureType::Derived | MeasureType::Ratio
+        ) {
+            continue;
+        }
+        let expr = aggregate_sql(&by_name, measure, &mut Vec::new())?;
+        measure.measure_type = MeasureType::Custom;
+        measure.expr = Some(expr);
+        measure.original_expr = None;
+        measure.filters = None;
+        measure.composition = MeasureComposition::default();
+    }
+    Ok(expanded)
+}
+
+/// Aggregate SQL for `measure`, inlining the measures it references.
+fn aggregate_sql(
+    measures: &HashMap<&str, &Measure>,
+    measure: &Measure,
+    stack: &mut Vec<String>,
+) -> Result<String, SemanticLayerError> {
+    if stack.contains(&measure.name) {
+        stack.push(measure.name.clone());
+        return Err(SemanticLayerError::ValidationError(format!(
+            "Measure reference cycle: {}",
+            stack.join(" -> ")
+        )));
+    }
+    stack.push(measure.name.clone());
+
+    let mut resolve = |name: &str| -> Result<String, SemanticLayerError> {
+        let referenced = measures.get(name).ok_or_else(|| {
+            SemanticLayerError::ValidationError(format!(
+                "Measure '{}' references unknown measure '{}'",
+                measure.name, name
+            ))
+        })?;
+        Ok(format!("({})", aggregate_sql(measures, referenced, stack)?))
+    };
+
+    let expr = measure.expr.as_deref().unwrap_or_default();
+    let condition = filter_condition(measure);
+    let sql = match measure.measure_type {
+        MeasureType::Count => match (&measure.expr, &condition) {
+            (_, Some(condition)) => {
+                let value = measure.expr.as_deref().unwrap_or("1");
+                format!("COUNT(CASE WHEN {condition} THEN {value} END)")
+            }
+            (Some(expr), None) => format!("COUNT({expr})"),
+            (None, None) => "COUNT(*)".to_string(),
+        },
+        MeasureType::Sum => format!("SUM({})", filtered(expr, &condition)),
+        MeasureType::Average => format!("AVG({})", filtered(expr, &condition)),
+        MeasureType::Min => format!("MIN({})", filtered(expr, &condition)),
+        MeasureType::Max => format!("MAX({})", filtered(expr, &condition)),
+        MeasureType::CountDistinct => {
+            format!("COUNT(DISTINCT {})", filtered(expr, &condition))
+        }
+        MeasureType::Custom => expr.to_string(),
+        MeasureType::Derived => {
+            let mut sql = expr.to_string();
+            for name in measure.referenced_measures() {
+                let inlined = resolve(&name)?;
+                sql = sql.replace(&format!("{{{name}}}"), &inlined);
+            }
+            sql
+        }
+        MeasureType::Ratio => {
+            let numerator = measure.composition.numerator.as_deref().unwrap_or_default();
+            let denominator = measure
+                .composition
+                .denominator
+                .as_deref()
+                .unwrap_or_default();
+            format!(
+                "{} * 1.0 / NULLIF({}, 0)",
+                resolve(numerator)?,
+                resolve(denominator)?
+            )
+        }
+        MeasureType::Median
+        | MeasureType::Cumulative
+        | MeasureType::Window
+        | MeasureType::PeriodOverPeriod => {
+            let caller = stack
+                .len()
+                .checked_sub(2)
+                .map(|i| stack[i].clone())
+                .unwrap_or_else(|| measure.name.clone());
+            return Err(SemanticLayerError::ValidationError(format!(
+                "Measure '{}' cannot reference {} measure '{}'",
+                caller,
+                measure.measure_type.to_string().to_lowercase(),
+                measure.name
+            )));
+        }
+    };
+
+    stack.pop();
+    Ok(sql)
+}
+
+fn filter_condition(measure: &Measure) -> Option<String> {
+    let filters = measure.filters.as_ref().filter(|f| !f.is_empty())?;
+    Some(
+        filters
+            .iter()
+            .map(|f| format!("({})", f.expr))
+            .collect::<Vec<_>>()
+            .join(" AND "),
+    )
+}
+
+fn filtered(expr: &str, condition: &Option<String>) -> String {
+    match condition {
+        Some(condition) => format!("CASE WHEN {condition} THEN {expr} END"),
+        None => expr.to_string(),
+    }
+}
+
+/// Window-function SQL for a windowed `measure` over grouped rows.
+///
+/// `value` is the column holding the underlying measure, `order_by` the time
+/// column, and `partition_by` the other grouping columns, all already quoted.
+/// Periods are counted in rows, so gaps in the time column are not filled.
+pub fn window_sql(
+    measure: &Measure,
+    value: &str,
+    order_by: &str,
+    partition_by: &[String],
+) -> Result<String, SemanticLayerError> {
+    let partition = if partition_by.is_empty() {
+        String::new()
+    } else {
+        format!("PARTITION BY {} ", partition_by.join(", "))
+    };
+    let composition = &measure.composition;
+    let sql = match measure.measure_type {
+        MeasureType::Cumulative => format!(
+            "SUM({value}) OVER ({partition}ORDER BY {order_by} \
+             ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW)"
+        ),
+        MeasureType::Window => {
+            let function = match composition.aggregation.unwrap_or_default() {
+                WindowAggregation::Sum => "SUM",
+                WindowAggregation::Average => "AVG",
+                WindowAggregation::Min => "MIN",
+                WindowAggregation::Max => "MAX",
+            };
+            let preceding = composition.window.unwrap_or(1).saturating_sub(1);
+            format!(
+                "{function}({value}) OVER ({partition}ORDER BY {order_by} \
+                 ROWS BETWEEN {preceding} PRECEDING AND CURRENT ROW)"
+            )
+        }
+        MeasureType::PeriodOverPeriod => {
+            let offset = composition.offset.unwrap_or(1);
+            let previous = format!("LAG({value}, {offset}) OVER ({partition}ORDER BY {order_by})");
+            match composition.comparison.unwrap_or_default() {
+                PeriodComparison::Previous => previous,
+                PeriodComparison::Difference => format!("{value} - {previous}"),
+                PeriodComparison::PercentChange => {
+                    format!("({value} - {previous}) * 1.0 / NULLIF({previous}, 0)")
+                }
+            }
+        }
+        _ => {
+            return Err(SemanticLayerError::ValidationError(format!(
+                "Measure '{}' is not a windowed measure",
+                measure.name
+            )));
+        }
+    };
+    Ok(sql)
+}
+
+fn measures_by_name(view: &View) -> HashMap<&str, &Measure> {
+    view.measures
+        .iter()
+        .flatten()
+        .map(|m| (m.name.as_str(), m))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{DimensionBuilder, EntityBuilder, MeasureBuilder, ViewBuilder};
+
+    fn view(measures: Vec<Measure>) -> View {
+        let mut view = ViewBuilder::new()
+            .name("orders")
+            .table("orders")
+            .entity(
+                EntityBuilder::new()
+                    .name("order")
+                    .primary()
+                    .key("id")
+                    .build()
+                    .unwrap(),
+            )
+            .dimension(
+                DimensionBuilder::new()
+                    .name("order_date")
+                    .date_type()
+                    .expr("order_date")
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        view.measures = Some(measures);
+        view
+    }
+
+    fn base_measures() -> Vec<Measure> {
+        vec![
+            MeasureBuilder::new()
+                .name("revenue")
+                .sum()
+                .expr("amount")
+                .build()
+                .unwrap(),
+            MeasureBuilder::new()
+                .name("order_count")
+                .count()
+                .build()
+                .unwrap(),
+            MeasureBuilder::new()
+                .name("refunds")
+                .sum()
+                .expr("amount")
+                .filter("status = 'refunded'".to_string(), None)
+                .build()
+                .unwrap(),
+        ]
+    }
+
+    fn expanded_expr(view: &View, name: &str) -> String {
+        let expanded = expand_measures(view).unwrap();
+        let measure = expanded
+            .measures
+            .unwrap()
+            .into_iter()
+            .find(|m| m.name == name)
+            .unwrap();
+        assert_eq!(measure.measure_type, MeasureType::Custom);
+        measure.expr.unwrap()
+    }
+
+    #[test]
+    fn test_ratio_inlines_numerator_and_denominator() {
+        let mut measures = base_measures();
+        measures.push(
+            MeasureBuilder::new()
+                .name("average_order_value")
+                .ratio("revenue", "order_count")
+                .build()
+                .unwrap(),
+        );
+        let view = view(measures);
+        assert_eq!(
+            expanded_expr(&view, "average_order_value"),
+            "(SUM(amount)) * 1.0 / NULLIF((COUNT(*)), 0)"
+        );
+    }
+
+    #[test]
+    fn test_derived_inlines_nested_references_and_filters() {
+        let mut measures = base_measures();
+        measures.push(
+            MeasureBuilder::new()
+                .name("net_revenue")
+                .derived()
+                .expr("{revenue} - {refunds}")
+                .build()
+                .unwrap(),
+        );
+        measures.push(
+            MeasureBuilder::new()
+                .name("net_per_order")
+                .ratio("net_revenue", "order_count")
+                .build()
+                .unwrap(),
+        );
+        let view = view(measures);
+        assert_eq!(
+            expanded_expr(&view, "net_revenue"),
+            "(SUM(amount)) - (SUM(CASE WHEN (status = 'refunded') THEN amount END))"
+        );
+        assert!(expanded_expr(&view, "net_per_order").starts_with("((SUM(amount)) - "));
+    }
+
+    #[test]
+    fn test_cycle_is_detected() {
+        let measures = vec![
+            MeasureBuilder::new()
+                .name("a")
+                .derived()
+                .expr("{b} + 1")
+                .build()
+                .unwrap(),
+            MeasureBuilder::new()
+                .name("b")
+                .derived()
+                .expr("{a} * 2")
+                .build()
+                .unwrap(),
+        ];
+        let view = view(measures);
+        assert_eq!(
+            find_measure_cycle(&view),
+            Some(vec!["a".to_string(), "b".to_string(), "a".to_string()])
+        );
+        assert!(expand_measures(&view).is_err());
+    }
+
+    #[test]
+    fn test_window_sql() {
+        let cumulative = MeasureBuilder::new()
+            .name("running_revenue")
+            .cumulative("revenue", "order_date")
+            .build()
+            .unwrap();
+        assert_eq!(
+            window_sql(&cumulative, "v", "t", &["r".to_string()]).unwrap(),
+            "SUM(v) OVER (PARTITION BY r ORDER BY t \
+             ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW)"
+        );
+
+        let rolling = MeasureBuilder::new()
+            .name("revenue_7d")
+            .window("revenue", "order_date", 7)
+            .build()
+            .unwrap();
+        assert_eq!(
+            window_sql(&rolling, "v", "t", &[]).unwrap(),
+            "AVG(v) OVER (ORDER BY t ROWS BETWEEN 6 PRECEDING AND CURRENT ROW)"
+        );
+
+        let growth = MeasureBuilder::new()
+            .name("revenue_growth")
+            .period_over_period("revenue", "order_date")
+            .comparison(PeriodComparison::PercentChange)
+            .build()
+            .unwrap();
+        assert_eq!(
+            window_sql(&growth, "v", "t", &[]).unwrap(),
+            "(v - LAG(v, 1) OVER (ORDER BY t)) * 1.0 / NULLIF(LAG(v, 1) OVER (ORDER BY t), 0)"
+        );
+    }
+}
@@ -106,6 +106,20 @@ pub struct Dimension {
     pub samples: Option<Vec<String>>,
     /// Alternative names or terms that refer to this dimension
     pub synonyms: Option<Vec<String>>,
+    /// Minimum access level needed to see the dimension's raw values
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access: Option<AccessLevel>,
+    /// How values are shown to users below `access`; the dimension is
+    /// hidden from them when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub masking: Option<MaskingRule>,
+    /// Grains a date or datetime dimension can be queried at, as
+    /// `<dimension>.<grain>`; every calendar grain when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grains: Option<Vec<TimeGrain>>,
+    /// Month (1-12) the fiscal year starts in, required by the fiscal grains
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fiscal_year_start: Option<u32>,
 }
 
 /// Represents the type of a measure aggregation
@@ -120,6 +134,36 @@ pub enum MeasureType {
     CountDistinct,
     Median,
     Custom,
+    /// Expression over other measures of the view, referenced as `{name}`
+    Derived,
+    /// `numerator` divided by `denominator`, both measures of the view
+    Ratio,
+    /// Running total of `measure` along `time_dimension`
+    Cumulative,
+    /// Aggregate of `measure` over the last `window` periods
+    Window,
+    /// `measure` compared with its value `offset` periods earlier
+    PeriodOverPeriod,
+}
+
+impl MeasureType {
+    /// Whether the measure is computed over grouped results with a window
+    /// function rather than aggregated directly.
+    pub fn is_windowed(&self) -> bool {
+        matches!(
+            self,
+            MeasureType::Cumulative | MeasureType::Window | MeasureType::PeriodOverPeriod
+        )
+    }
+
+    /// Whether the measure adds up across periods, so a period without rows
+    /// has a value of zero rather than none.
+    pub fn is_additive(&self) -> bool {
+        matches!(
+            self,
+            MeasureType::Count | MeasureType::CountDistinct | MeasureType::Sum
+        )
+    }
 }
 
 impl Display for MeasureType {
@@ -136,11 +180,123 @@ impl Display for MeasureType {
                 MeasureType::CountDistinct => "Count Distinct",
                 MeasureType::Median => "Median",
                 MeasureType::Custom => "Custom",
+                MeasureType::Derived => "Derived",
+                MeasureType::Ratio => "Ratio",
+                MeasureType::Cumulative => "Cumulative",
+                MeasureType::Window => "Window",
+                MeasureType::PeriodOverPeriod => "Period Over Period",
+            }
+        )
+    }
+}
+
+/// Period a date or datetime dimension is truncated to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeGrain {
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+    /// Quarter of a fiscal year starting in the dimension's `fiscal_year_start`
+    FiscalQuarter,
+    /// Year starting in the dimension's `fiscal_year_start`
+    FiscalYear,
+}
+
+impl TimeGrain {
+    /// Calendar grains, available on every date and datetime dimension that
+    /// does not declare `grains`
+    pub const CALENDAR: [TimeGrain; 5] = [
+        TimeGrain::Day,
+        TimeGrain::Week,
+        TimeGrain::Month,
+        TimeGrain::Quarter,
+        TimeGrain::Year,
+    ];
+
+    pub fn is_fiscal(&self) -> bool {
+        matches!(self, TimeGrain::FiscalQuarter | TimeGrain::FiscalYear)
+    }
+}
+
+impl Display for TimeGrain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                TimeGrain::Day => "day",
+                TimeGrain::Week => "week",
+                TimeGrain::Month => "month",
+                TimeGrain::Quarter => "quarter",
+                TimeGrain::Year => "year",
+                TimeGrain::FiscalQuarter => "fiscal_quarter",
+                TimeGrain::FiscalYear => "fiscal_year",
             }
         )
     }
 }
 
+/// Aggregation applied over the periods of a `window` measure
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowAggregation {
+    Sum,
+    #[default]
+    Average,
+    Min,
+    Max,
+}
+
+/// What a `period_over_period` measure returns
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PeriodComparison {
+    /// The value of the earlier period
+    Previous,
+    /// Current value minus the earlier value
+    #[default]
+    Difference,
+    /// Change relative to the earlier value, as a fraction
+    PercentChange,
+}
+
+/// Inputs of measures built from other measures of the same view
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default, PartialEq)]
+pub struct MeasureComposition {
+    /// Numerator measure of a `ratio`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub numerator: Option<String>,
+    /// Denominator measure of a `ratio`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub denominator: Option<String>,
+    /// Measure a `cumulative`, `window` or `period_over_period` measure is
+    /// computed from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub measure: Option<String>,
+    /// Date or datetime dimension that orders the periods
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_dimension: Option<String>,
+    /// Grain used when the query does not group by `time_dimension`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grain: Option<TimeGrain>,
+    /// Number of periods a `window` measure spans, including the current one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window: Option<usize>,
+    /// Aggregation of a `window` measure, `average` by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aggregation: Option<WindowAggregation>,
+    /// How many periods back a `period_over_period` measure compares with,
+    /// 1 by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+    /// Value a `period_over_period` measure returns, `difference` by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comparison: Option<PeriodComparison>,
+}
+
 /// Represents a filter condition for measures
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MeasureFilter {
@@ -163,7 +319,8 @@ pub struct Measure {
     pub measure_type: MeasureType,
     /// Human-readable description of what this measure represents
     pub description: Option<String>,
-    /// SQL expression for the measure (required for most types, not for count)
+    /// SQL expression for the measure (required for most types, not for count).
+    /// For `derived` measures, other measures are referenced as `{name}`
     pub expr: Option<String>,
     /// Original expression before variable encoding (if variables were used)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -174,6 +331,12 @@ pub struct Measure {
     pub samples: Option<Vec<String>>,
     /// Alternative names or terms that refer to this measure
     pub synonyms: Option<Vec<String>>,
+    /// Minimum access level needed to query the measure
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access: Option<AccessLevel>,
+    /// Measures this one is built from, for derived, ratio and windowed types
+    #[serde(flatten)]
+    pub composition: MeasureComposition,
 }
 
 impl Dimension {
@@ -261,10 +424,31 @@ pub struct View {
     pub dimensions: Vec<Dimension>,
     /// List of measures (aggregations) available in this view
     pub measures: Option<Vec<Measure>>,
+    /// Row-level access policies applied to the view's table
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_policies: Option<Vec<ViewAccessPolicy>>,
 }
 
-/// Represents access control levels for topics
+/// Row-level access policy declared on a view.
+///
+/// Applies to the view's `table` in its `datasource`, the same way a
+/// `access_policies` entry in config.yml does.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct ViewAccessPolicy {
+    /// Policy name, reported when a query is rejected
+    pub name: String,
+    /// SQL boolean expression over the table's columns; may reference
+    /// `{{ filters.<name> }}` and `{{ user.id }}`
+    pub predicate: String,
+}
+
+/// Represents access control levels for topics and fields
+///
+/// Levels are ordered: a caller cleared for `restricted` can also see
+/// `internal` and `public` fields.
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, PartialOrd, Ord,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum AccessLevel {
     Public,
@@ -272,6 +456,20 @@ pub enum AccessLevel {
     Restricted,
 }
 
+/// How a dimension is shown to callers below its access level
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MaskingRule {
+    /// Replace values with their hex-encoded SHA-256 digest
+    Hash,
+    /// Replace values with a fixed placeholder
+    Redact,
+    /// Keep only the first `length` characters
+    Truncate { length: usize },
+    /// Never return raw values; measures over the column still work
+    OnlyInAggregate,
+}
+
 /// Configuration for topic's retrieval by agents
 /// This mirrors RouteRetrievalConfig in oxy::core
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
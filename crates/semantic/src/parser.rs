@@ -997,8 +997,13 @@ dimensions:
                         dimension_type: DimensionType::Number,
                         samples: None,
                         synonyms: None,
+                        access: None,
+                        masking: None,
+                        grains: None,
+                        fiscal_year_start: None,
                     }],
                     measures: None,
+                    access_policies: None,
                 }],
                 topics: None,
                 metadata: None,
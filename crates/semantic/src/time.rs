@@ -0,0 +1,269 @@
+//! Time grains of date and datetime dimensions.
+//!
+//! Queries ask for a dimension at a grain as `<dimension>.<grain>`. Calendar
+//! grains are truncated by the query engine; fiscal grains become a date
+//! dimension whose SQL is built here, labelled by the date each fiscal
+//! period starts on. The time spine lists every period between two buckets
+//! so trend results can be zero-filled.
+
+use std::str::FromStr;
+
+use chrono::{Days, Months, NaiveDate};
+
+use crate::errors::SemanticLayerError;
+use crate::models::{Dimension, DimensionType, TimeGrain};
+
+impl FromStr for TimeGrain {
+    type Err = SemanticLayerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(TimeGrain::Day),
+            "week" => Ok(TimeGrain::Week),
+            "month" => Ok(TimeGrain::Month),
+            "quarter" => Ok(TimeGrain::Quarter),
+            "year" => Ok(TimeGrain::Year),
+            "fiscal_quarter" => Ok(TimeGrain::FiscalQuarter),
+            "fiscal_year" => Ok(TimeGrain::FiscalYear),
+            _ => Err(SemanticLayerError::ValidationError(format!(
+                "Unknown time grain '{s}'"
+            ))),
+        }
+    }
+}
+
+impl TimeGrain {
+    /// Start of the period following the one starting on `start`.
+    pub fn next_period(&self, start: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            TimeGrain::Day => start.checked_add_days(Days::new(1)),
+            TimeGrain::Week => start.checked_add_days(Days::new(7)),
+            TimeGrain::Month => start.checked_add_months(Months::new(1)),
+            TimeGrain::Quarter | TimeGrain::FiscalQuarter => {
+                start.checked_add_months(Months::new(3))
+            }
+            TimeGrain::Year | TimeGrain::FiscalYear => start.checked_add_months(Months::new(12)),
+        }
+    }
+}
+
+impl Dimension {
+    /// Grains the dimension can be queried at: its `grains` when declared,
+    /// otherwise the calendar grains, plus the fiscal ones when it has a
+    /// `fiscal_year_start`.
+    pub fn available_grains(&self) -> Vec<TimeGrain> {
+        if !matches!(
+            self.dimension_type,
+            DimensionType::Date | DimensionType::Datetime
+        ) {
+            return Vec::new();
+        }
+        if let Some(grains) = &self.grains {
+            return grains.clone();
+        }
+        let mut grains = TimeGrain::CALENDAR.to_vec();
+        if self.fiscal_year_start.is_some() {
+            grains.extend([TimeGrain::FiscalQuarter, TimeGrain::FiscalYear]);
+        }
+        grains
+    }
+}
+
+/// Split a `<field>.<grain>` reference into the field and the grain.
+///
+/// Returns `None` when the last segment is not a grain name.
+pub fn split_grain(field: &str) -> Option<(&str, TimeGrain)> {
+    let (field, grain) = field.rsplit_once('.')?;
+    Some((field, grain.parse().ok()?))
+}
+
+/// Date dimension holding the start of each fiscal `grain` period of
+/// `dimension`, named `<dimension>_<grain>`.
+///
+/// `dialect` is the database dialect as returned by `Database::dialect()`.
+pub fn fiscal_dimension(
+    dimension: &Dimension,
+    grain: TimeGrain,
+    dialect: &str,
+) -> Result<Dimension, SemanticLayerError> {
+    let Some(start_month) = dimension.fiscal_year_start.filter(|m| (1..=12).contains(m)) else {
+        return Err(SemanticLayerError::ValidationError(format!(
+            "Dimension '{}' needs a 'fiscal_year_start' month to be queried by {grain}",
+            dimension.name
+        )));
+    };
+    let expr =
+        fiscal_period_sql(&dimension.expr, grain, start_month, dialect).ok_or_else(|| {
+            SemanticLayerError::ValidationError(format!(
+                "Grain '{grain}' is not supported for '{dialect}' databases"
+            ))
+        })?;
+    Ok(Dimension {
+        name: format!("{}_{grain}", dimension.name),
+        dimension_type: DimensionType::Date,
+        description: dimension.description.clone(),
+        expr,
+        original_expr: None,
+        samples: None,
+        synonyms: None,
+        access: dimension.access,
+        masking: dimension.masking.clone(),
+        grains: Some(vec![grain]),
+        fiscal_year_start: dimension.fiscal_year_start,
+    })
+}
+
+/// SQL for the start date of the fiscal `grain` period containing `expr`.
+///
+/// The date is moved back to the calendar year the fiscal year would start
+/// in, truncated, then moved forward again.
+fn fiscal_period_sql(
+    expr: &str,
+    grain: TimeGrain,
+    start_month: u32,
+    dialect: &str,
+) -> Option<String> {
+    let unit = match grain {
+        TimeGrain::FiscalQuarter => "quarter",
+        TimeGrain::FiscalYear => "year",
+        _ => return None,
+    };
+    let shift = i64::from(start_month) - 1;
+    let date = add_months(&cast_to_date(expr, dialect)?, -shift, dialect)?;
+    add_months(&truncate(&date, unit, dialect)?, shift, dialect)
+}
+
+fn cast_to_date(expr: &str, dialect: &str) -> Option<String> {
+    match dialect {
+        "clickhouse" => Some(format!("toDate({expr})")),
+        "sqlite" => Some(format!("date({expr})")),
+        "postgres" | "duckdb" | "snowflake" | "trino" | "bigquery" | "mysql" | "mssql" => {
+            Some(format!("CAST({expr} AS DATE)"))
+        }
+        _ => None,
+    }
+}
+
+fn add_months(date: &str, months: i64, dialect: &str) -> Option<String> {
+    if months == 0 {
+        return Some(date.to_string());
+    }
+    let sql = match dialect {
+        // A date plus an interval is a timestamp in both
+        "postgres" | "duckdb" if months < 0 => {
+            format!("({date} - INTERVAL '{} month')::date", -months)
+        }
+        "postgres" | "duckdb" => format!("({date} + INTERVAL '{months} month')::date"),
+        "snowflake" | "mssql" => format!("DATEADD(month, {months}, {date})"),
+        "trino" => format!("date_add('month', {months}, {date})"),
+        "bigquery" | "mysql" => format!("DATE_ADD({date}, INTERVAL {months} MONTH)"),
+        "clickhouse" => format!("addMonths({date}, {months})"),
+        "sqlite" => format!("date({date}, '{months:+} months')"),
+        _ => return None,
+    };
+    Some(sql)
+}
+
+fn truncate(date: &str, unit: &str, dialect: &str) -> Option<String> {
+    let sql = match (dialect, unit) {
+        ("postgres" | "duckdb" | "snowflake" | "trino", _) => {
+            format!("CAST(DATE_TRUNC('{unit}', {date}) AS DATE)")
+        }
+        ("bigquery", _) => format!("DATE_TRUNC({date}, {})", unit.to_uppercase()),
+        ("mysql", "year") => format!("MAKEDATE(YEAR({date}), 1)"),
+        ("mysql", _) => {
+            format!("(MAKEDATE(YEAR({date}), 1) + INTERVAL (QUARTER({date}) - 1) QUARTER)")
+        }
+        ("mssql", "year") => format!("DATEFROMPARTS(YEAR({date}), 1, 1)"),
+        ("mssql", _) => {
+            format!("DATEFROMPARTS(YEAR({date}), (DATEPART(quarter, {date}) - 1) * 3 + 1, 1)")
+        }
+        ("clickhouse", "year") => format!("toStartOfYear({date})"),
+        ("clickhouse", _) => format!("toStartOfQuarter({date})"),
+        ("sqlite", "year") => format!("date({date}, 'start of year')"),
+        ("sqlite", _) => format!(
+            "date({date}, 'start of month', '-' || ((CAST(strftime('%m', {date}) AS INTEGER) - 1) % 3) || ' months')"
+        ),
+        _ => return None,
+    };
+    Some(sql)
+}
+
+/// Start of every `grain` period from the one starting on `first` through
+/// the one starting on `last`.
+///
+/// `first` must be a period start; trend queries return bucket starts.
+pub fn time_spine(first: NaiveDate, last: NaiveDate, grain: TimeGrain) -> Vec<NaiveDate> {
+    let mut spine = Vec::new();
+    let mut period = Some(first);
+    while let Some(start) = period.filter(|start| *start <= last) {
+        spine.push(start);
+        period = grain.next_period(start);
+    }
+    spine
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order_date() -> Dimension {
+        serde_yaml::from_str(
+            "name: order_date
+type: date
+expr: ordered_at
+fiscal_year_start: 4
+",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_split_grain() {
+        assert_eq!(
+            split_grain("orders.order_date.month"),
+            Some(("orders.order_date", TimeGrain::Month))
+        );
+        assert_eq!(
+            split_grain("order_date.fiscal_year"),
+            Some(("order_date", TimeGrain::FiscalYear))
+        );
+        assert_eq!(split_grain("orders.order_date"), None);
+    }
+
+    #[test]
+    fn test_available_grains() {
+        let mut dimension = order_date();
+        assert_eq!(dimension.available_grains().len(), 7);
+        dimension.grains = Some(vec![TimeGrain::Month]);
+        assert_eq!(dimension.available_grains(), [TimeGrain::Month]);
+        dimension.dimension_type = DimensionType::String;
+        assert!(dimension.available_grains().is_empty());
+    }
+
+    #[test]
+    fn test_fiscal_dimension() {
+        let dimension = fiscal_dimension(&order_date(), TimeGrain::FiscalYear, "postgres").unwrap();
+        assert_eq!(dimension.name, "order_date_fiscal_year");
+        assert_eq!(
+            dimension.expr,
+            "(CAST(DATE_TRUNC('year', (CAST(ordered_at AS DATE) - INTERVAL '3 month')::date) AS DATE) \
+             + INTERVAL '3 month')::date"
+        );
+        assert!(fiscal_dimension(&order_date(), TimeGrain::FiscalQuarter, "domo").is_err());
+    }
+
+    #[test]
+    fn test_time_spine() {
+        let date = |m, d| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+        assert_eq!(
+            time_spine(date(1, 1), date(4, 1), TimeGrain::Month),
+            [date(1, 1), date(2, 1), date(3, 1), date(4, 1)]
+        );
+        assert_eq!(
+            time_spine(date(2, 1), date(8, 1), TimeGrain::FiscalQuarter),
+            [date(2, 1), date(5, 1), date(8, 1)]
+        );
+        assert!(time_spine(date(2, 1), date(1, 1), TimeGrain::Day).is_empty());
+    }
+}
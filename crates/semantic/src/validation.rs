@@ -1,8 +1,9 @@
 use crate::SemanticLayerError;
 use crate::entity_graph::EntityGraph;
+use crate::metrics::find_measure_cycle;
 use crate::models::*;
 use regex::Regex;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::OnceLock;
 
 /// Validation result for semantic layer components
@@ -181,6 +182,47 @@ impl SemanticValidator for Dimension {
             result.add_error("Dimension expr cannot be empty".to_string());
         }
 
+        if let Some(MaskingRule::Truncate { length: 0 }) = self.masking {
+            result.add_error(format!(
+                "Dimension '{}' truncate masking length must be greater than 0",
+                self.name
+            ));
+        }
+
+        // Validate time grains
+        let is_time = matches!(
+            self.dimension_type,
+            DimensionType::Date | DimensionType::Datetime
+        );
+        if !is_time && (self.grains.is_some() || self.fiscal_year_start.is_some()) {
+            result.add_error(format!(
+                "Dimension '{}' declares time grains but is not a date or datetime dimension",
+                self.name
+            ));
+        }
+        if let Some(month) = self.fiscal_year_start
+            && !(1..=12).contains(&month)
+        {
+            result.add_error(format!(
+                "Dimension '{}' fiscal_year_start must be a month between 1 and 12",
+                self.name
+            ));
+        }
+        if let Some(grains) = &self.grains {
+            if grains.is_empty() {
+                result.add_error(format!(
+                    "Dimension '{}' grains list cannot be empty when specified",
+                    self.name
+                ));
+            }
+            if self.fiscal_year_start.is_none() && grains.iter().any(TimeGrain::is_fiscal) {
+                result.add_error(format!(
+                    "Dimension '{}' declares fiscal grains without a fiscal_year_start",
+                    self.name
+                ));
+            }
+        }
+
         // Validate synonyms
         if let Some(synonyms) = &self.synonyms {
             if synonyms.is_empty() {
@@ -250,6 +292,55 @@ impl SemanticValidator for Measure {
                     result.add_error("Custom measures require an 'expr' field".to_string());
                 }
             }
+            MeasureType::Derived => {
+                if self.referenced_measures().is_empty() {
+                    result.add_error(format!(
+                        "Derived measure '{}' requires an 'expr' referencing other measures as {{name}}",
+                        self.name
+                    ));
+                }
+            }
+            MeasureType::Ratio => {
+                let composition = &self.composition;
+                if composition.numerator.is_none() || composition.denominator.is_none() {
+                    result.add_error(format!(
+                        "Ratio measure '{}' requires 'numerator' and 'denominator' fields",
+                        self.name
+                    ));
+                }
+            }
+            MeasureType::Cumulative | MeasureType::Window | MeasureType::PeriodOverPeriod => {
+                let composition = &self.composition;
+                if composition.measure.is_none() || composition.time_dimension.is_none() {
+                    result.add_error(format!(
+                        "{} measure '{}' requires 'measure' and 'time_dimension' fields",
+                        self.measure_type, self.name
+                    ));
+                }
+                if self.measure_type == MeasureType::Window && composition.window.unwrap_or(0) == 0
+                {
+                    result.add_error(format!(
+                        "Window measure '{}' requires a 'window' of at least 1 period",
+                        self.name
+                    ));
+                }
+                if composition.offset == Some(0) {
+                    result.add_error(format!(
+                        "Measure '{}' offset must be greater than 0",
+                        self.name
+                    ));
+                }
+                if self.expr.is_some() || self.filters.is_some() {
+                    result.add_warning(format!(
+                        "Measure '{}' ignores 'expr' and 'filters'; they apply to '{}' instead",
+                        self.name,
+                        composition
+                            .measure
+                            .as_deref()
+                            .unwrap_or("the underlying measure")
+                    ));
+                }
+            }
             _ => {
                 // Other measure types require expr
                 if self.expr.is_none() {
@@ -313,6 +404,90 @@ impl SemanticValidator for Measure {
     }
 }
 
+/// Check that measures built from other measures reference measures and
+/// time dimensions that exist, can be combined, and do not form a cycle.
+fn validate_measure_references(view: &View, result: &mut ValidationResult) {
+    let measures: HashMap<&str, &Measure> = view
+        .measures
+        .iter()
+        .flatten()
+        .map(|m| (m.name.as_str(), m))
+        .collect();
+
+    for measure in view.measures.iter().flatten() {
+        for name in measure.referenced_measures() {
+            let Some(referenced) = measures.get(name.as_str()) else {
+                result.add_error(format!(
+                    "Measure '{}' references unknown measure '{}'",
+                    measure.name, name
+                ));
+                continue;
+            };
+            let referenced_type = &referenced.measure_type;
+            if referenced_type.is_windowed() {
+                result.add_error(format!(
+                    "Measure '{}' cannot reference {} measure '{}'",
+                    measure.name,
+                    referenced_type.to_string().to_lowercase(),
+                    name
+                ));
+            } else if matches!(
+                measure.measure_type,
+                MeasureType::Derived | MeasureType::Ratio
+            ) && *referenced_type == MeasureType::Median
+            {
+                result.add_error(format!(
+                    "Measure '{}' cannot reference median measure '{}'",
+                    measure.name, name
+                ));
+            } else if measure.measure_type == MeasureType::Cumulative
+                && !matches!(referenced_type, MeasureType::Sum | MeasureType::Count)
+            {
+                result.add_error(format!(
+                    "Cumulative measure '{}' requires a sum or count measure, but '{}' is {}",
+                    measure.name,
+                    name,
+                    referenced_type.to_string().to_lowercase()
+                ));
+            }
+        }
+
+        if let Some(time_dimension) = &measure.composition.time_dimension {
+            match view.dimensions.iter().find(|d| &d.name == time_dimension) {
+                None => result.add_error(format!(
+                    "Measure '{}' references unknown time dimension '{}'",
+                    measure.name, time_dimension
+                )),
+                Some(dimension)
+                    if !matches!(
+                        dimension.dimension_type,
+                        DimensionType::Date | DimensionType::Datetime
+                    ) =>
+                {
+                    result.add_error(format!(
+                        "Measure '{}' time dimension '{}' must be a date or datetime dimension",
+                        measure.name, time_dimension
+                    ))
+                }
+                Some(dimension) => {
+                    if let Some(grain) = measure.composition.grain
+                        && (grain.is_fiscal() || !dimension.available_grains().contains(&grain))
+                    {
+                        result.add_error(format!(
+                            "Measure '{}' cannot step through time dimension '{}' by {}",
+                            measure.name, time_dimension, grain
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(cycle) = find_measure_cycle(view) {
+        result.add_error(format!("Measure reference cycle: {}", cycle.join(" -> ")));
+    }
+}
+
 impl SemanticValidator for View {
     fn validate(&self) -> ValidationResult {
         let mut result = ValidationResult::new();
@@ -345,6 +520,12 @@ impl SemanticValidator for View {
             result.add_warning("View should specify either 'table' or 'sql', not both".to_string());
         }
 
+        // Access policies are enforced against the physical table, so a
+        // SQL-only view has nothing to attach them to
+        if self.access_policies.as_ref().is_some_and(|p| !p.is_empty()) && self.table.is_none() {
+            result.add_error("View access_policies require the 'table' field".to_string());
+        }
+
         // Validate entities
         if self.entities.is_empty() {
             result.add_error("View must have at least one entity".to_string());
@@ -410,6 +591,8 @@ impl SemanticValidator for View {
             }
         }
 
+        validate_measure_references(self, &mut result);
+
         // Check for name conflicts between entities, dimensions, and measures
         let mut all_names = HashSet::new();
 
@@ -996,8 +1179,13 @@ mod tests {
                 original_expr: None,
                 samples: None,
                 synonyms: None,
+                access: None,
+                masking: None,
+                grains: None,
+                fiscal_year_start: None,
             }],
             measures: None,
+            access_policies: None,
         };
 
         let customers_view = View {
@@ -1022,8 +1210,13 @@ mod tests {
                 original_expr: None,
                 samples: None,
                 synonyms: None,
+                access: None,
+                masking: None,
+                grains: None,
+                fiscal_year_start: None,
             }],
             measures: None,
+            access_policies: None,
         };
 
         // Create an unreachable view (no entity connection)
@@ -1049,8 +1242,13 @@ mod tests {
                 original_expr: None,
                 samples: None,
                 synonyms: None,
+                access: None,
+                masking: None,
+                grains: None,
+                fiscal_year_start: None,
             }],
             measures: None,
+            access_policies: None,
         };
 
         // Topic with reachable views
@@ -1138,4 +1336,154 @@ mod tests {
         assert_eq!(topic.name, "sales");
         assert!(topic.description.is_none());
     }
+
+    fn measures_view(measures_yaml: &str) -> View {
+        let yaml = format!(
+            "name: orders
+table: orders
+entities:
+  - name: order
+    type: primary
+    key: id
+dimensions:
+  - name: order_date
+    type: date
+    expr: order_date
+  - name: status
+    type: string
+    expr: status
+measures:
+  - name: revenue
+    type: sum
+    expr: amount
+  - name: average_amount
+    type: average
+    expr: amount
+  - name: order_count
+    type: count
+{measures_yaml}"
+        );
+        serde_yaml::from_str(&yaml).unwrap()
+    }
+
+    #[test]
+    fn test_composed_measures_deserialize_and_validate() {
+        let view = measures_view(
+            "  - name: average_order_value
+    type: ratio
+    numerator: revenue
+    denominator: order_count
+  - name: running_revenue
+    type: cumulative
+    measure: revenue
+    time_dimension: order_date
+    grain: month
+  - name: revenue_growth
+    type: period_over_period
+    measure: revenue
+    time_dimension: order_date
+    comparison: percent_change
+",
+        );
+        let result = view.validate();
+        assert!(result.is_valid, "{:?}", result.errors);
+        let growth = &view.measures.as_ref().unwrap()[5];
+        assert_eq!(growth.measure_type, MeasureType::PeriodOverPeriod);
+        assert_eq!(
+            growth.composition.comparison,
+            Some(PeriodComparison::PercentChange)
+        );
+    }
+
+    #[test]
+    fn test_composed_measure_reference_errors() {
+        let view = measures_view(
+            "  - name: margin
+    type: derived
+    expr: \"{revenue} - {cost}\"
+  - name: running_average
+    type: cumulative
+    measure: average_amount
+    time_dimension: order_date
+  - name: weekly_revenue
+    type: window
+    measure: revenue
+    time_dimension: status
+    window: 7
+",
+        );
+        let result = view.validate();
+        assert!(!result.is_valid);
+        assert!(
+            result
+                .errors
+                .iter()
+                .any(|e| e.contains("unknown measure 'cost'"))
+        );
+        assert!(
+            result
+                .errors
+                .iter()
+                .any(|e| e.contains("requires a sum or count measure"))
+        );
+        assert!(
+            result
+                .errors
+                .iter()
+                .any(|e| e.contains("must be a date or datetime dimension"))
+        );
+    }
+
+    #[test]
+    fn test_measure_reference_cycle() {
+        let view = measures_view(
+            "  - name: a
+    type: derived
+    expr: \"{b} / {order_count}\"
+  - name: b
+    type: ratio
+    numerator: a
+    denominator: revenue
+",
+        );
+        let result = view.validate();
+        assert!(
+            result
+                .errors
+                .iter()
+                .any(|e| e == "Measure reference cycle: a -> b -> a")
+        );
+    }
+
+    #[test]
+    fn test_dimension_grain_errors() {
+        let dimension: Dimension = serde_yaml::from_str(
+            "name: order_date
+type: date
+expr: order_date
+grains: [month, fiscal_quarter]
+",
+        )
+        .unwrap();
+        let result = dimension.validate();
+        assert!(
+            result
+                .errors
+                .iter()
+                .any(|e| e.contains("fiscal grains without a fiscal_year_start")),
+            "{:?}",
+            result.errors
+        );
+
+        let dimension: Dimension = serde_yaml::from_str(
+            "name: status
+type: string
+expr: status
+fiscal_year_start: 13
+",
+        )
+        .unwrap();
+        let result = dimension.validate();
+        assert_eq!(result.errors.len(), 2, "{:?}", result.errors);
+    }
 }
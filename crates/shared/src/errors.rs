@@ -63,6 +63,8 @@ pub enum OxyError {
     InitializationError(String),
     #[error("{0}")]
     JobError(String),
+    #[error("Budget exceeded: {0}")]
+    BudgetExceeded(String),
     #[error("{0}")]
     LanceDBError(#[from] lancedb::Error),
     #[error("{0}")]
@@ -104,6 +106,7 @@ impl OxyError {
             OxyError::CryptographyError(_) => "cryptography",
             OxyError::InitializationError(_) => "initialization",
             OxyError::JobError(_) => "job",
+            OxyError::BudgetExceeded(_) => "budget",
             OxyError::LanceDBError(_) => "lancedb",
             OxyError::SerdeArrowError(_) => "serde_arrow",
             OxyError::ToolCallError { .. } => "tool_call",
@@ -143,6 +146,7 @@ impl OxyError {
             OxyError::CryptographyError(_) => sentry::Level::Error,
             OxyError::InitializationError(_) => sentry::Level::Error,
             OxyError::JobError(_) => sentry::Level::Error,
+            OxyError::BudgetExceeded(_) => sentry::Level::Warning,
             OxyError::LanceDBError(_) => sentry::Level::Error,
             OxyError::SerdeArrowError(_) => sentry::Level::Error,
             OxyError::ToolCallError { .. } => sentry::Level::Error,
@@ -286,6 +290,7 @@ impl From<OxyError> for StatusCode {
             OxyError::CryptographyError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             OxyError::InitializationError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             OxyError::JobError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            OxyError::BudgetExceeded(_) => StatusCode::TOO_MANY_REQUESTS,
             OxyError::LanceDBError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             OxyError::SerdeArrowError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             OxyError::ToolCallError { .. } => StatusCode::INTERNAL_SERVER_ERROR,
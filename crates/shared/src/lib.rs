@@ -19,4 +19,6 @@ pub mod utils;
 // Re-export commonly used items
 pub use errors::OxyError;
 pub use key_validation::{KeyValidationError, KeyValidationErrorKind};
-pub use openai_config::{AzureModel, ConfigType, CustomOpenAIConfig, HeaderValue};
+pub use openai_config::{
+    AzureModel, ConfigType, CustomOpenAIConfig, HeaderValue, RateLimitConfig,
+};
@@ -9,7 +9,7 @@ use axum::http::{HeaderMap, HeaderName, HeaderValue as HttpHeaderValue};
 use schemars::JsonSchema;
 use secrecy::SecretString;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, str::FromStr};
+use std::{collections::HashMap, num::NonZeroU32, str::FromStr};
 
 /// Header value that can be either a direct string or an environment variable reference.
 /// Used in model configurations to specify custom HTTP headers.
@@ -51,6 +51,19 @@ pub struct AzureModel {
     pub azure_api_version: String,
 }
 
+/// Client-side rate limit for the API key of a model. Models that use the
+/// same key share one limit.
+#[derive(Deserialize, Debug, Clone, Copy, Serialize, JsonSchema, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitConfig {
+    /// Requests allowed per minute
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_per_minute: Option<NonZeroU32>,
+    /// Input plus output tokens allowed per minute
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens_per_minute: Option<NonZeroU32>,
+}
+
 /// Custom OpenAI configuration that supports additional headers
 #[derive(Debug, Clone)]
 pub struct CustomOpenAIConfig {
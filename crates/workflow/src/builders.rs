@@ -326,6 +326,17 @@ impl WorkflowLauncher {
             .config_manager
             .resolve_workflow(&workflow_input.workflow_ref)
             .await?;
+        // Same as above: the context without the budget must not outlive this
+        // block, or it keeps the writer channel open.
+        let execution_context = {
+            let base_context = execution_context;
+            base_context
+                .with_run_budget(
+                    &format!("workflow '{}'", workflow_input.workflow_ref),
+                    workflow_config.budget.as_ref(),
+                )
+                .await?
+        };
         let attributes = HashMap::from([
             ("run_id".to_string(), run_info.get_run_index().to_string()),
             (
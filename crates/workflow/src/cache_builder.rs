@@ -12,7 +12,7 @@ use oxy_shared::errors::OxyError;
 use serde::de::DeserializeOwned;
 use slugify::slugify;
 use sqlformat::{FormatOptions, QueryParams, format};
-use std::{io::Write, path::PathBuf};
+use std::{io::Write, path::PathBuf, time::Duration};
 
 use crate::task_builder::TaskInput;
 
@@ -85,6 +85,12 @@ impl CacheStorage<TaskInput, OutputContainer> for TaskCacheStorage {
             .cacheable
             .cache_key(execution_context, &input.task)
             .await?;
+        if let Some(ttl_secs) = input.task.cache.as_ref().and_then(|cache| cache.ttl_secs)
+            && file_cache.is_older_than(&key, Duration::from_secs(ttl_secs))
+        {
+            tracing::debug!("Cache expired: {}", key);
+            return None;
+        }
         let maybe_sql = file_cache.read_str(&key)?;
 
         if let Some(cache_key) = self.compute_cache_key(&input.task.name, &key, true) {
@@ -213,6 +219,14 @@ impl FileCache {
         std::fs::read_to_string(&path).ok()
     }
 
+    fn is_older_than(&self, key: &str, max_age: Duration) -> bool {
+        std::fs::metadata(key)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > max_age)
+    }
+
     fn deserialize<R: DeserializeOwned>(&self, json: &str) -> Option<R> {
         match serde_json::from_str::<R>(json) {
             Ok(value) => Some(value),
@@ -5,7 +5,7 @@ use tokio::task::JoinHandle;
 use tracing::Instrument;
 
 use oxy::{
-    adapters::openai::{IntoOpenAIConfig, OpenAIClient},
+    adapters::model_chain::ModelChain,
     execute::{
         Executable, ExecutionContext,
         builders::{
@@ -171,19 +171,13 @@ impl ConsistencyPicker<OutputContainer> for AgentPicker {
         let config_manager = execution_context.workspace.config_manager.clone();
         let agent_config = config_manager.resolve_agent(self.agent_ref.clone()).await?;
         let model = config_manager.resolve_model(&agent_config.model)?;
-        let client = OpenAIClient::with_config(
-            model
-                .into_openai_config(&execution_context.workspace.secrets_manager)
-                .await?,
-        );
-        let agent = build_openai_executable(
-            client,
-            model.model_name().to_string(),
-            vec![],
-            None,
-            None,
-            false,
-        );
+        let models = ModelChain::from_model(
+            model,
+            &config_manager,
+            &execution_context.workspace.secrets_manager,
+        )
+        .await?;
+        let agent = build_openai_executable(models, vec![], None, None, false);
         let mut consistency_evaluator = ExecutableBuilder::new()
             .concurrency_control(
                 10,
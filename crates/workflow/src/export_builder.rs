@@ -19,16 +19,20 @@ use oxy::{
     utils::get_file_directories,
 };
 use oxy_shared::errors::OxyError;
+use parquet::{arrow::ArrowWriter, basic::Compression, file::properties::WriterProperties};
 use tokio::{sync::mpsc::Sender, task::JoinHandle};
 
 use crate::task_builder::TaskInput;
 
+mod xlsx;
+
 #[derive(Clone)]
 pub(super) struct TaskExporter;
 
 pub struct ExporterEventHandler {
     tx: Sender<Event>,
     prompt: String,
+    task_name: String,
     export_info: TaskExport,
 }
 
@@ -48,6 +52,7 @@ impl EventHandler for ExporterEventHandler {
             export_execute_sql(
                 &self.export_info,
                 &self.prompt,
+                &self.task_name,
                 &sql,
                 schema,
                 batches,
@@ -85,6 +90,7 @@ impl Exporter<TaskInput, OutputContainer> for TaskExporter {
         input: TaskInput,
         output_handle: JoinHandle<Result<OutputContainer, OxyError>>,
     ) -> Result<OutputContainer, OxyError> {
+        let task_name = input.task.name;
         let (export_info, prompt) = match input.task.task_type {
             TaskType::Agent(AgentTask { prompt, export, .. }) => (export, prompt),
             TaskType::ExecuteSQL(ExecuteSQLTask { export, .. }) => (export, String::new()),
@@ -108,6 +114,7 @@ impl Exporter<TaskInput, OutputContainer> for TaskExporter {
             tx: execution_context.writer.clone(),
             export_info: export_info.clone(),
             prompt,
+            task_name,
         };
         let event_handle =
             tokio::spawn(async move { buf_writer.write_to_handler(event_handler).await });
@@ -115,7 +122,14 @@ impl Exporter<TaskInput, OutputContainer> for TaskExporter {
         event_handle.await??;
         let output = output?;
         if let OutputContainer::Single(Output::Text(text)) = &output {
-            export_formatter(text, export_info.path);
+            match export_info.format {
+                ExportFormat::Parquet | ExportFormat::XLSX => tracing::warn!(
+                    format = ?export_info.format,
+                    path = %export_info.path,
+                    "Text output can't be exported as a table, skipping"
+                ),
+                _ => export_formatter(text, export_info.path),
+            }
         }
         Ok(output)
     }
@@ -124,6 +138,7 @@ impl Exporter<TaskInput, OutputContainer> for TaskExporter {
 fn export_execute_sql<P: AsRef<Path>>(
     task_export: &TaskExport,
     prompt: &str,
+    task_name: &str,
     sql: &str,
     schema: &Arc<Schema>,
     datasets: &[RecordBatch],
@@ -135,6 +150,8 @@ fn export_execute_sql<P: AsRef<Path>>(
                 ExportFormat::SQL => export_sql(&file_path, prompt, sql),
                 ExportFormat::CSV => export_csv(&file_path, schema, datasets),
                 ExportFormat::JSON => export_json(&file_path, datasets),
+                ExportFormat::Parquet => export_parquet(&file_path, schema, datasets),
+                ExportFormat::XLSX => xlsx::export_xlsx(&file_path, task_name, schema, datasets),
                 _ => {
                     tracing::warn!("Unsupported export format");
                     return;
@@ -199,6 +216,23 @@ fn export_json<P: AsRef<Path>>(
     Ok(())
 }
 
+fn export_parquet<P: AsRef<Path>>(
+    file_path: P,
+    schema: &Arc<Schema>,
+    datasets: &[RecordBatch],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let file = File::create(file_path)?;
+    let props = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut writer = ArrowWriter::try_new(file, schema.clone(), Some(props))?;
+    for batch in datasets {
+        writer.write(batch)?;
+    }
+    writer.close()?;
+    Ok(())
+}
+
 fn export_formatter<P: AsRef<Path>>(task_output: &str, export_file_path: P) {
     match get_file_directories(export_file_path.as_ref()) {
         Ok(file_path) => {
@@ -214,3 +248,46 @@ fn export_formatter<P: AsRef<Path>>(task_output: &str, export_file_path: P) {
         ),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::{
+        array::{Date32Array, Decimal128Array},
+        datatypes::{DataType, Field},
+    };
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    #[test]
+    fn test_export_parquet_preserves_types() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("amount", DataType::Decimal128(10, 2), true),
+            Field::new("ordered_on", DataType::Date32, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(
+                    Decimal128Array::from(vec![Some(12_345), None])
+                        .with_precision_and_scale(10, 2)
+                        .unwrap(),
+                ),
+                Arc::new(Date32Array::from(vec![None, Some(19_723)])),
+            ],
+        )
+        .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("orders.parquet");
+        export_parquet(&path, &schema, std::slice::from_ref(&batch)).unwrap();
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches: Vec<RecordBatch> = reader.map(|batch| batch.unwrap()).collect();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].schema().fields(), schema.fields());
+        assert_eq!(batches[0].columns(), batch.columns());
+    }
+}
@@ -0,0 +1,634 @@
+//! Excel export: one worksheet per task, with typed cells.
+//!
+//! Exporting into a workbook that already exists keeps its other sheets, so
+//! tasks that share an export path build up one multi-sheet workbook. A task
+//! replaces its own sheet on every run. The workbook is rewritten from the
+//! cell values, so the number format of each exported column is kept in a
+//! very hidden sheet to format the kept sheets the same way again.
+
+use std::{collections::BTreeMap, error::Error, path::Path, sync::Mutex};
+
+use arrow::{
+    array::{Array, ArrayRef, AsArray, PrimitiveArray, RecordBatch},
+    compute::cast,
+    datatypes::{
+        ArrowTimestampType, DataType, Date32Type, Date64Type, Float64Type, Int64Type, Schema,
+        Time32MillisecondType, Time32SecondType, Time64MicrosecondType, Time64NanosecondType,
+        TimeUnit, TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+        TimestampSecondType, UInt64Type,
+    },
+    util::display::{ArrayFormatter, FormatOptions},
+};
+use calamine::{Data, ExcelDateTime, Range, Reader, open_workbook_auto};
+use rust_xlsxwriter::{Format, Formula, Workbook, Worksheet, XlsxError};
+
+/// Excel's row limit, including the header row
+const MAX_ROWS: usize = 1_048_576;
+const MAX_SHEET_NAME_CHARS: usize = 31;
+/// Excel stores numbers as doubles, so integers past 2^53 and decimals with
+/// more than 15 significant digits are written as text to keep every digit
+const MAX_EXACT_INTEGER: u64 = 1 << 53;
+const MAX_EXACT_DECIMAL_PRECISION: u8 = 15;
+
+const DATE_FORMAT: &str = "yyyy-mm-dd";
+const DATETIME_FORMAT: &str = "yyyy-mm-dd hh:mm:ss";
+const TIME_FORMAT: &str = "hh:mm:ss";
+const GENERAL_FORMAT: &str = "General";
+
+/// Very hidden sheet listing the number format of every exported column
+const FORMATS_SHEET: &str = "_oxy_formats";
+
+/// Serializes read-modify-write of workbooks shared by concurrent tasks
+static WORKBOOK_LOCK: Mutex<()> = Mutex::new(());
+
+pub(super) fn export_xlsx<P: AsRef<Path>>(
+    file_path: P,
+    sheet_name: &str,
+    schema: &Schema,
+    batches: &[RecordBatch],
+) -> Result<(), Box<dyn Error>> {
+    let rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+    if rows >= MAX_ROWS {
+        return Err(format!(
+            "{rows} rows exceed the Excel limit of {} rows per sheet",
+            MAX_ROWS - 1
+        )
+        .into());
+    }
+
+    let _guard = WORKBOOK_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let file_path = file_path.as_ref();
+    let sheet_name = sanitize_sheet_name(sheet_name);
+    let mut workbook = Workbook::new();
+    let mut formats = BTreeMap::new();
+    let mut written = false;
+
+    if file_path.exists() {
+        let mut existing = open_workbook_auto(file_path)?;
+        let mut kept_formats = if existing.sheet_names().iter().any(|n| n == FORMATS_SHEET) {
+            read_formats(&existing.worksheet_range(FORMATS_SHEET)?)
+        } else {
+            BTreeMap::new()
+        };
+        for name in existing.sheet_names() {
+            if name == FORMATS_SHEET {
+                continue;
+            }
+            let worksheet = workbook.add_worksheet();
+            worksheet.set_name(&name)?;
+            if name == sheet_name {
+                write_table(worksheet, schema, batches)?;
+                written = true;
+            } else {
+                let column_formats = kept_formats.remove(&name);
+                copy_sheet(
+                    worksheet,
+                    &existing.worksheet_range(&name)?,
+                    &existing.worksheet_formula(&name)?,
+                    column_formats.as_deref(),
+                )?;
+                if let Some(column_formats) = column_formats {
+                    formats.insert(name, column_formats);
+                }
+            }
+        }
+    }
+    if !written {
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(&sheet_name)?;
+        write_table(worksheet, schema, batches)?;
+    }
+    let column_formats = schema
+        .fields()
+        .iter()
+        .map(|field| num_format(field.data_type()))
+        .collect();
+    formats.insert(sheet_name, column_formats);
+    write_formats(workbook.add_worksheet(), &formats)?;
+
+    workbook.save(file_path)?;
+    Ok(())
+}
+
+fn write_table(
+    worksheet: &mut Worksheet,
+    schema: &Schema,
+    batches: &[RecordBatch],
+) -> Result<(), Box<dyn Error>> {
+    let header = Format::new().set_bold();
+    for (col, field) in schema.fields().iter().enumerate() {
+        worksheet.write_string_with_format(0, column_index(col)?, field.name(), &header)?;
+    }
+
+    let mut first_row = 1;
+    for batch in batches {
+        for (col, column) in batch.columns().iter().enumerate() {
+            write_column(worksheet, first_row, column_index(col)?, column)?;
+        }
+        first_row += batch.num_rows() as u32;
+    }
+
+    worksheet.set_freeze_panes(1, 0)?;
+    worksheet.autofit();
+    Ok(())
+}
+
+/// Writes one column of a batch starting at `first_row`. Nulls are left as
+/// empty cells.
+fn write_column(
+    worksheet: &mut Worksheet,
+    first_row: u32,
+    col: u16,
+    column: &ArrayRef,
+) -> Result<(), Box<dyn Error>> {
+    let row = |i: usize| first_row + i as u32;
+    match column.data_type() {
+        DataType::Null => {}
+        DataType::Boolean => {
+            let values = column.as_boolean();
+            for i in valid_rows(column) {
+                worksheet.write_boolean(row(i), col, values.value(i))?;
+            }
+        }
+        DataType::Int64 => {
+            let values = column.as_primitive::<Int64Type>();
+            for i in valid_rows(column) {
+                let value = values.value(i);
+                if value.unsigned_abs() > MAX_EXACT_INTEGER {
+                    worksheet.write_string(row(i), col, value.to_string())?;
+                } else {
+                    worksheet.write_number(row(i), col, value as f64)?;
+                }
+            }
+        }
+        DataType::UInt64 => {
+            let values = column.as_primitive::<UInt64Type>();
+            for i in valid_rows(column) {
+                let value = values.value(i);
+                if value > MAX_EXACT_INTEGER {
+                    worksheet.write_string(row(i), col, value.to_string())?;
+                } else {
+                    worksheet.write_number(row(i), col, value as f64)?;
+                }
+            }
+        }
+        DataType::Decimal32(precision, scale)
+        | DataType::Decimal64(precision, scale)
+        | DataType::Decimal128(precision, scale)
+        | DataType::Decimal256(precision, scale)
+            if *precision <= MAX_EXACT_DECIMAL_PRECISION =>
+        {
+            let format = Format::new().set_num_format(decimal_format(*scale));
+            let values = cast(column, &DataType::Float64)?;
+            let values = values.as_primitive::<Float64Type>();
+            for i in valid_rows(column) {
+                worksheet.write_number_with_format(row(i), col, values.value(i), &format)?;
+            }
+        }
+        data_type if data_type.is_numeric() && !is_decimal(data_type) => {
+            let values = cast(column, &DataType::Float64)?;
+            let values = values.as_primitive::<Float64Type>();
+            for i in valid_rows(column) {
+                worksheet.write_number(row(i), col, values.value(i))?;
+            }
+        }
+        DataType::Date32 | DataType::Date64 => {
+            let format = Format::new().set_num_format(DATE_FORMAT);
+            for i in valid_rows(column) {
+                let date = match column.data_type() {
+                    DataType::Date32 => column.as_primitive::<Date32Type>().value_as_date(i),
+                    _ => column.as_primitive::<Date64Type>().value_as_date(i),
+                };
+                if let Some(date) = date {
+                    worksheet.write_datetime_with_format(row(i), col, date, &format)?;
+                }
+            }
+        }
+        DataType::Timestamp(unit, timezone) => {
+            let timezone = timezone.as_deref();
+            match unit {
+                TimeUnit::Second => write_timestamps(
+                    worksheet,
+                    first_row,
+                    col,
+                    column.as_primitive::<TimestampSecondType>(),
+                    timezone,
+                )?,
+                TimeUnit::Millisecond => write_timestamps(
+                    worksheet,
+                    first_row,
+                    col,
+                    column.as_primitive::<TimestampMillisecondType>(),
+                    timezone,
+                )?,
+                TimeUnit::Microsecond => write_timestamps(
+                    worksheet,
+                    first_row,
+                    col,
+                    column.as_primitive::<TimestampMicrosecondType>(),
+                    timezone,
+                )?,
+                TimeUnit::Nanosecond => write_timestamps(
+                    worksheet,
+                    first_row,
+                    col,
+                    column.as_primitive::<TimestampNanosecondType>(),
+                    timezone,
+                )?,
+            }
+        }
+        DataType::Time32(_) | DataType::Time64(_) => {
+            let format = Format::new().set_num_format(TIME_FORMAT);
+            for i in valid_rows(column) {
+                let time = match column.data_type() {
+                    DataType::Time32(TimeUnit::Second) => {
+                        column.as_primitive::<Time32SecondType>().value_as_time(i)
+                    }
+                    DataType::Time32(_) => column
+                        .as_primitive::<Time32MillisecondType>()
+                        .value_as_time(i),
+                    DataType::Time64(TimeUnit::Microsecond) => column
+                        .as_primitive::<Time64MicrosecondType>()
+                        .value_as_time(i),
+                    _ => column
+                        .as_primitive::<Time64NanosecondType>()
+                        .value_as_time(i),
+                };
+                if let Some(time) = time {
+                    worksheet.write_datetime_with_format(row(i), col, time, &format)?;
+                }
+            }
+        }
+        // Strings, wide decimals and nested types are written as displayed
+        _ => {
+            let formatter = ArrayFormatter::try_new(column.as_ref(), &FormatOptions::default())?;
+            for i in valid_rows(column) {
+                worksheet.write_string(row(i), col, formatter.value(i).to_string())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Timestamps are written in their column's time zone, since Excel has none
+fn write_timestamps<T: ArrowTimestampType>(
+    worksheet: &mut Worksheet,
+    first_row: u32,
+    col: u16,
+    values: &PrimitiveArray<T>,
+    timezone: Option<&str>,
+) -> Result<(), XlsxError> {
+    let format = Format::new().set_num_format(DATETIME_FORMAT);
+    let timezone = timezone.and_then(|tz| tz.parse::<arrow::array::timezone::Tz>().ok());
+    for i in (0..values.len()).filter(|i| values.is_valid(*i)) {
+        let datetime = match timezone {
+            Some(tz) => values
+                .value_as_datetime_with_tz(i, tz)
+                .map(|datetime| datetime.naive_local()),
+            None => values.value_as_datetime(i),
+        };
+        if let Some(datetime) = datetime {
+            worksheet.write_datetime_with_format(first_row + i as u32, col, datetime, &format)?;
+        }
+    }
+    Ok(())
+}
+
+/// Copies a sheet of the existing workbook. Sheets this module exported are
+/// laid out and formatted again from `column_formats`; on other sheets, only
+/// dates and times get a format, chosen from their value.
+fn copy_sheet(
+    worksheet: &mut Worksheet,
+    range: &Range<Data>,
+    formulas: &Range<String>,
+    column_formats: Option<&[String]>,
+) -> Result<(), XlsxError> {
+    let Some((first_row, first_col)) = range.start() else {
+        return Ok(());
+    };
+    let header = Format::new().set_bold();
+    let format = |row: u32, col: u16, value: &Data| match column_formats {
+        Some(_) if row == 0 => Some(header.clone()),
+        Some(formats) => formats
+            .get(col as usize)
+            .map(|format| Format::new().set_num_format(format)),
+        None => match value {
+            Data::DateTime(value) => Some(Format::new().set_num_format(datetime_format(value))),
+            _ => None,
+        },
+    };
+    for (row, col, value) in range.cells() {
+        let (row, col) = (first_row + row as u32, (first_col as usize + col) as u16);
+        let format = format(row, col, value).unwrap_or_default();
+        match value {
+            Data::Int(value) => {
+                worksheet.write_number_with_format(row, col, *value as f64, &format)?
+            }
+            Data::Float(value) => worksheet.write_number_with_format(row, col, *value, &format)?,
+            Data::Bool(value) => worksheet.write_boolean_with_format(row, col, *value, &format)?,
+            Data::String(value) | Data::DateTimeIso(value) | Data::DurationIso(value) => {
+                worksheet.write_string_with_format(row, col, value, &format)?
+            }
+            Data::DateTime(value) => {
+                worksheet.write_number_with_format(row, col, value.as_f64(), &format)?
+            }
+            Data::Error(_) | Data::Empty => continue,
+        };
+    }
+
+    // Formulas are written over their cached values, which become the result
+    // shown until Excel recalculates
+    if let Some((formula_row, formula_col)) = formulas.start() {
+        for (row, col, formula) in formulas.used_cells() {
+            if formula.is_empty() {
+                continue;
+            }
+            let (row, col) = (
+                formula_row + row as u32,
+                (formula_col as usize + col) as u16,
+            );
+            let value = range.get_value((row, col as u32)).unwrap_or(&Data::Empty);
+            let format = format(row, col, value).unwrap_or_default();
+            let formula = Formula::new(formula).set_result(value.to_string());
+            worksheet.write_formula_with_format(row, col, formula, &format)?;
+        }
+    }
+
+    if column_formats.is_some() {
+        worksheet.set_freeze_panes(1, 0)?;
+        worksheet.autofit();
+    }
+    Ok(())
+}
+
+/// Format for a date or time on a sheet without recorded column formats
+fn datetime_format(value: &ExcelDateTime) -> &'static str {
+    let serial = value.as_f64();
+    if serial < 1.0 {
+        TIME_FORMAT
+    } else if serial.fract() == 0.0 {
+        DATE_FORMAT
+    } else {
+        DATETIME_FORMAT
+    }
+}
+
+/// Number format `write_column` gives a column of `data_type`
+fn num_format(data_type: &DataType) -> String {
+    match data_type {
+        DataType::Decimal32(precision, scale)
+        | DataType::Decimal64(precision, scale)
+        | DataType::Decimal128(precision, scale)
+        | DataType::Decimal256(precision, scale)
+            if *precision <= MAX_EXACT_DECIMAL_PRECISION =>
+        {
+            decimal_format(*scale)
+        }
+        DataType::Date32 | DataType::Date64 => DATE_FORMAT.to_string(),
+        DataType::Timestamp(_, _) => DATETIME_FORMAT.to_string(),
+        DataType::Time32(_) | DataType::Time64(_) => TIME_FORMAT.to_string(),
+        _ => GENERAL_FORMAT.to_string(),
+    }
+}
+
+/// Column formats by sheet, from a sheet written by [`write_formats`]
+fn read_formats(range: &Range<Data>) -> BTreeMap<String, Vec<String>> {
+    let mut formats: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for row in range.rows() {
+        if let [Data::String(sheet), Data::String(format)] = row {
+            formats
+                .entry(sheet.clone())
+                .or_default()
+                .push(format.clone());
+        }
+    }
+    formats
+}
+
+/// One row per exported column, in order: sheet name and number format
+fn write_formats(
+    worksheet: &mut Worksheet,
+    formats: &BTreeMap<String, Vec<String>>,
+) -> Result<(), XlsxError> {
+    worksheet.set_name(FORMATS_SHEET)?;
+    worksheet.set_very_hidden(true);
+    let rows = formats
+        .iter()
+        .flat_map(|(sheet, formats)| formats.iter().map(move |format| (sheet, format)));
+    for (row, (sheet, format)) in rows.enumerate() {
+        worksheet.write_string(row as u32, 0, sheet)?;
+        worksheet.write_string(row as u32, 1, format)?;
+    }
+    Ok(())
+}
+
+fn valid_rows(column: &ArrayRef) -> impl Iterator<Item = usize> + '_ {
+    (0..column.len()).filter(|i| column.is_valid(*i))
+}
+
+fn is_decimal(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Decimal32(_, _)
+            | DataType::Decimal64(_, _)
+            | DataType::Decimal128(_, _)
+            | DataType::Decimal256(_, _)
+    )
+}
+
+/// Number format showing exactly `scale` decimal places
+fn decimal_format(scale: i8) -> String {
+    match scale {
+        scale if scale > 0 => format!("0.{}", "0".repeat(scale as usize)),
+        _ => "0".to_string(),
+    }
+}
+
+fn column_index(col: usize) -> Result<u16, Box<dyn Error>> {
+    u16::try_from(col).map_err(|_| format!("Too many columns for Excel: {col}").into())
+}
+
+/// Excel sheet names are at most 31 characters and can't contain `[]:*?/\`
+fn sanitize_sheet_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| match c {
+            '[' | ']' | ':' | '*' | '?' | '/' | '\\' => '_',
+            c => c,
+        })
+        .take(MAX_SHEET_NAME_CHARS)
+        .collect();
+    let name = name.trim_matches('\'');
+    if name.is_empty() {
+        "Sheet1".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{io::Read, sync::Arc};
+
+    use arrow::{
+        array::{Date32Array, Decimal128Array, Int64Array, StringArray},
+        datatypes::Field,
+    };
+
+    fn batch() -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("amount", DataType::Decimal128(10, 2), true),
+            Field::new("ordered_on", DataType::Date32, true),
+            Field::new("note", DataType::Utf8, true),
+        ]));
+        RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from(vec![1, 9_007_199_254_740_993])),
+                Arc::new(
+                    Decimal128Array::from(vec![Some(12_345), None])
+                        .with_precision_and_scale(10, 2)
+                        .unwrap(),
+                ),
+                Arc::new(Date32Array::from(vec![Some(19_723), None])),
+                Arc::new(StringArray::from(vec![None, Some("late")])),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn read_sheet(path: &Path, name: &str) -> Range<Data> {
+        let mut workbook = open_workbook_auto(path).unwrap();
+        workbook.worksheet_range(name).unwrap()
+    }
+
+    /// Style index of `cell` on the `sheet`th worksheet, 0 when unstyled
+    fn cell_style(path: &Path, sheet: usize, cell: &str) -> String {
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(path).unwrap()).unwrap();
+        let mut xml = String::new();
+        archive
+            .by_name(&format!("xl/worksheets/sheet{sheet}.xml"))
+            .unwrap()
+            .read_to_string(&mut xml)
+            .unwrap();
+        let tag = xml.split(&format!("<c r=\"{cell}\"")).nth(1).unwrap();
+        let tag = &tag[..tag.find('>').unwrap()];
+        match tag.split(" s=\"").nth(1) {
+            Some(style) => style[..style.find('"').unwrap()].to_string(),
+            None => "0".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_sanitize_sheet_name() {
+        assert_eq!(sanitize_sheet_name("revenue/by:month"), "revenue_by_month");
+        assert_eq!(sanitize_sheet_name("'quoted'"), "quoted");
+        assert_eq!(sanitize_sheet_name(""), "Sheet1");
+        assert_eq!(sanitize_sheet_name(&"x".repeat(40)).len(), 31);
+    }
+
+    #[test]
+    fn test_decimal_format() {
+        assert_eq!(decimal_format(2), "0.00");
+        assert_eq!(decimal_format(0), "0");
+        assert_eq!(decimal_format(-2), "0");
+    }
+
+    #[test]
+    fn test_export_typed_cells() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.xlsx");
+        let batch = batch();
+        export_xlsx(&path, "orders", &batch.schema(), &[batch]).unwrap();
+
+        let range = read_sheet(&path, "orders");
+        assert_eq!(range.get((0, 0)), Some(&Data::String("id".to_string())));
+        assert_eq!(range.get((1, 0)), Some(&Data::Float(1.0)));
+        // Past 2^53, so kept as text
+        assert_eq!(
+            range.get((2, 0)),
+            Some(&Data::String("9007199254740993".to_string()))
+        );
+        assert_eq!(range.get((1, 1)), Some(&Data::Float(123.45)));
+        assert_eq!(range.get((2, 1)), Some(&Data::Empty));
+        assert!(matches!(range.get((1, 2)), Some(Data::DateTime(_))));
+        assert_eq!(range.get((2, 2)), Some(&Data::Empty));
+        assert_eq!(range.get((2, 3)), Some(&Data::String("late".to_string())));
+    }
+
+    #[test]
+    fn test_export_keeps_other_sheets() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.xlsx");
+        let batch = batch();
+        export_xlsx(
+            &path,
+            "orders",
+            &batch.schema(),
+            std::slice::from_ref(&batch),
+        )
+        .unwrap();
+        export_xlsx(
+            &path,
+            "returns",
+            &batch.schema(),
+            std::slice::from_ref(&batch),
+        )
+        .unwrap();
+        // Re-exporting a task replaces its sheet in place
+        export_xlsx(&path, "orders", &batch.schema(), &[batch.slice(0, 1)]).unwrap();
+
+        let workbook = open_workbook_auto(&path).unwrap();
+        assert_eq!(
+            workbook.sheet_names(),
+            vec!["orders", "returns", FORMATS_SHEET]
+        );
+        assert_eq!(read_sheet(&path, "orders").height(), 2);
+        assert_eq!(read_sheet(&path, "returns").height(), 3);
+
+        // The copied sheet keeps the bold header and the decimal and date
+        // formats its task wrote, like the sheet written just now
+        for cell in ["A1", "B2", "C2"] {
+            assert_ne!(cell_style(&path, 2, cell), "0", "{cell} has no format");
+            assert_eq!(cell_style(&path, 2, cell), cell_style(&path, 1, cell));
+        }
+        let returns = read_sheet(&path, "returns");
+        assert_eq!(returns.get((1, 1)), Some(&Data::Float(123.45)));
+        assert!(matches!(returns.get((1, 2)), Some(Data::DateTime(_))));
+    }
+
+    #[test]
+    fn test_export_keeps_formulas() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.xlsx");
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet().set_name("notes").unwrap();
+        worksheet.write_number(0, 0, 2).unwrap();
+        worksheet
+            .write_formula(0, 1, Formula::new("=A1*2").set_result("4"))
+            .unwrap();
+        workbook.save(&path).unwrap();
+
+        let batch = batch();
+        export_xlsx(&path, "orders", &batch.schema(), &[batch]).unwrap();
+
+        let mut workbook = open_workbook_auto(&path).unwrap();
+        let formulas = workbook.worksheet_formula("notes").unwrap();
+        assert_eq!(formulas.get_value((0, 1)), Some(&"A1*2".to_string()));
+        assert_eq!(
+            read_sheet(&path, "notes").get_value((0, 1)),
+            Some(&Data::Float(4.0))
+        );
+    }
+
+    #[test]
+    fn test_num_format() {
+        assert_eq!(num_format(&DataType::Decimal32(9, 2)), "0.00");
+        assert_eq!(num_format(&DataType::Decimal64(12, 4)), "0.0000");
+        assert_eq!(num_format(&DataType::Decimal128(38, 2)), GENERAL_FORMAT);
+        assert_eq!(num_format(&DataType::Date32), DATE_FORMAT);
+        assert_eq!(num_format(&DataType::Utf8), GENERAL_FORMAT);
+    }
+}
@@ -12,7 +12,9 @@ pub mod looker_builder;
 pub mod loop_concurrency_builder;
 pub mod omni_builder;
 pub mod semantic_builder;
+pub mod semantic_time;
 pub mod semantic_validator_builder;
+pub mod semantic_windows;
 pub mod sql_builder;
 pub mod streaming_workflow_persister;
 pub mod task_builder;
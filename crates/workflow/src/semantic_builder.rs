@@ -1,6 +1,8 @@
 use arrow::{array::RecordBatch, datatypes::Schema};
 use chrono::{Local, NaiveDate};
 use oxy::types::TimeGranularity;
+use oxy_semantic::access::{hidden_fields, restrict_view};
+use oxy_semantic::metrics::expand_measures;
 use oxy_semantic::variables::RuntimeVariableResolver;
 use serde_json::Value as JsonValue;
 use std::{
@@ -24,9 +26,11 @@ use oxy::{
 };
 use oxy_shared::errors::OxyError;
 
+use crate::semantic_time::{TimeSpine, resolve_grains};
 use crate::semantic_validator_builder::{
     SemanticQueryValidation, ValidatedSemanticQuery, validate_semantic_query_task,
 };
+use crate::semantic_windows::WindowedQuery;
 
 #[tracing::instrument(skip_all, err, fields(
     oxy.name = workflow_events::task::semantic_query::NAME_RENDER,
@@ -116,6 +120,7 @@ pub fn render_semantic_query(
             offset: task.query.offset,
             variables: variables.clone(),
             time_dimensions,
+            time_spine: task.query.time_spine,
         },
         export: task.export.clone(),
         variables,
@@ -267,7 +272,8 @@ impl SemanticQueryExecutable {
             &input.views,
             config_manager,
             &date_fields,
-        )?;
+        )?
+        .sql;
 
         let variables = input.task.variables.clone().unwrap_or_default();
 
@@ -426,7 +432,10 @@ impl SemanticQueryExecutable {
         let date_fields = collect_date_fields(&input.views);
 
         // Compile semantic query to SQL using airlayer engine
-        let mut sql_query = match compile_with_airlayer(
+        let CompiledQuery {
+            sql: mut sql_query,
+            time_spine,
+        } = match compile_with_airlayer(
             &input.task,
             &input.topic.name,
             input.topic.base_view.as_ref(),
@@ -435,7 +444,7 @@ impl SemanticQueryExecutable {
             config_manager,
             &date_fields,
         ) {
-            Ok(sql) => sql,
+            Ok(compiled) => compiled,
             Err(e) => {
                 tracing::error!(
                     "Failed to compile semantic query for topic '{}': {e}",
@@ -483,7 +492,7 @@ impl SemanticQueryExecutable {
             .execute_sql_and_save_results(
                 &sql_query,
                 &database,
-                &input.topic.name,
+                time_spine.as_ref(),
                 execution_context,
             )
             .await
@@ -621,7 +630,7 @@ impl SemanticQueryExecutable {
         &self,
         sql: &str,
         database_ref: &str,
-        _topic: &str,
+        time_spine: Option<&TimeSpine>,
         execution_context: &ExecutionContext,
     ) -> Result<(String, Vec<RecordBatch>, Arc<Schema>), OxyError> {
         workflow_events::task::semantic_query::execute_sql_input(database_ref, sql);
@@ -640,11 +649,16 @@ impl SemanticQueryExecutable {
             execution_context.filters.clone(),
             execution_context.connections.clone(),
         )
-        .await?;
+        .await?
+        .with_user_id(execution_context.user_id)
+        .audited(execution_context.workspace.workspace_id);
 
         // Execute SQL query
         tracing::debug!("Executing SQL query: {}", sql);
-        let (record_batches, schema_ref) = connector.run_query_and_load(sql).await?;
+        let (mut record_batches, schema_ref) = connector.run_query_and_load(sql).await?;
+        if let Some(time_spine) = time_spine {
+            record_batches = time_spine.fill(&record_batches, &schema_ref)?;
+        }
 
         // Generate a unique file path
         let file_path = format!("/tmp/{}.arrow", Uuid::new_v4());
@@ -661,6 +675,13 @@ impl SemanticQueryExecutable {
     }
 }
 
+/// SQL compiled from a semantic query, and the time spine to fill its
+/// result with.
+struct CompiledQuery {
+    sql: String,
+    time_spine: Option<TimeSpine>,
+}
+
 /// Compile a semantic query to SQL using the airlayer in-process engine.
 fn compile_with_airlayer(
     task: &SemanticQueryTask,
@@ -670,8 +691,19 @@ fn compile_with_airlayer(
     views: &[oxy_semantic::View],
     config_manager: &oxy::config::ConfigManager,
     date_fields: &HashSet<String>,
-) -> Result<String, OxyError> {
-    // 1. Convert oxy-semantic views to airlayer views
+) -> Result<CompiledQuery, OxyError> {
+    // 1. Resolve `<dimension>.<grain>` references, apply the caller's
+    //    column-level access rules, inline derived and ratio measures, then
+    //    convert oxy-semantic views to airlayer views
+    let grained = resolve_grains(task, topic_name, views, |view| {
+        view_dialect(view, config_manager)
+    })?;
+    let task = &grained.task;
+    let views = restrict_views(task, topic_name, &grained.views, config_manager)?
+        .iter()
+        .map(expand_measures)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| OxyError::ValidationError(e.to_string()))?;
     let airlayer_views: Vec<airlayer::View> = views.iter().map(convert_view_to_airlayer).collect();
 
     // 2. Build datasource→dialect map from oxygen-internal's config databases
@@ -682,8 +714,18 @@ fn compile_with_airlayer(
     let engine = airlayer::SemanticEngine::from_semantic_layer(layer, dialects)
         .map_err(|e| OxyError::RuntimeError(format!("airlayer engine error: {e}")))?;
 
-    // 4. Convert query params to airlayer QueryRequest
-    let request = build_airlayer_query(task, topic_name, base_view, default_filters, date_fields)?;
+    // 4. Convert query params to airlayer QueryRequest. Windowed measures
+    //    are compiled as their underlying measures and applied afterwards
+    let windowed = WindowedQuery::plan(task, topic_name, &views)?;
+    let query_task = windowed.as_ref().map_or(task, |w| &w.task);
+    let time_spine = TimeSpine::plan(&grained, query_task, topic_name)?;
+    let request = build_airlayer_query(
+        query_task,
+        topic_name,
+        base_view,
+        default_filters,
+        date_fields,
+    )?;
 
     // 5. Compile
     let result = engine
@@ -695,7 +737,97 @@ fn compile_with_airlayer(
     // but oxygen-internal's Engine trait only accepts raw SQL with no param binding.
     let sql = substitute_params(&result.sql, &result.params);
 
-    Ok(sql)
+    // 7. Apply windowed measures over the grouped result
+    let sql = match windowed {
+        Some(windowed) => {
+            let view = task
+                .query
+                .measures
+                .iter()
+                .filter_map(|field| {
+                    qualify_field(field, topic_name)
+                        .split_once('.')
+                        .map(|(v, _)| v.to_string())
+                })
+                .find_map(|name| views.iter().find(|view| view.name == name));
+            let dialect = match view {
+                Some(view) => view_dialect(view, config_manager)?,
+                None => default_dialect(config_manager),
+            };
+            windowed.wrap(&sql, &dialect)?
+        }
+        None => sql,
+    };
+    Ok(CompiledQuery { sql, time_spine })
+}
+
+/// Dialect of the database `view` reads from, as returned by
+/// `Database::dialect()`.
+fn view_dialect(
+    view: &oxy_semantic::View,
+    config_manager: &oxy::config::ConfigManager,
+) -> Result<String, OxyError> {
+    match &view.datasource {
+        Some(datasource) => Ok(config_manager.resolve_database(datasource)?.dialect()),
+        None => Ok(default_dialect(config_manager)),
+    }
+}
+
+fn default_dialect(config_manager: &oxy::config::ConfigManager) -> String {
+    config_manager
+        .list_databases()
+        .first()
+        .map(|db| db.dialect())
+        .unwrap_or_default()
+}
+
+/// Reject queries that reference fields hidden from the caller and mask the
+/// expressions of the fields they may only see masked.
+fn restrict_views(
+    task: &SemanticQueryTask,
+    topic_name: &str,
+    views: &[oxy_semantic::View],
+    config_manager: &oxy::config::ConfigManager,
+) -> Result<Vec<oxy_semantic::View>, OxyError> {
+    let clearance = config_manager.access_level();
+
+    let hidden: HashSet<String> = views
+        .iter()
+        .flat_map(|view| {
+            hidden_fields(view, Some(clearance))
+                .into_iter()
+                .map(move |field| format!("{}.{}", view.name, field))
+        })
+        .collect();
+    let query = &task.query;
+    let referenced = query
+        .dimensions
+        .iter()
+        .chain(&query.measures)
+        .chain(query.time_dimensions.iter().map(|td| &td.dimension))
+        .chain(query.filters.iter().map(|f| &f.field))
+        .chain(query.orders.iter().map(|o| &o.field));
+    for field in referenced {
+        let field = qualify_field(field, topic_name);
+        if hidden.contains(&field) {
+            return Err(OxyError::AuthorizationError(format!(
+                "Access to semantic field '{field}' is restricted"
+            )));
+        }
+    }
+
+    views
+        .iter()
+        .map(|view| {
+            let dialect = view_dialect(view, config_manager)?;
+            Ok(restrict_view(
+                view,
+                Some(clearance),
+                &dialect,
+                oxy::utils::masking_salt(),
+            ))
+        })
+        .collect()
 }
 
 /// Substitute positional parameter placeholders ($1, $2, ...) and ? placeholders
@@ -757,10 +889,13 @@ fn convert_view_to_airlayer(view: &oxy_semantic::View) -> airlayer::View {
             .iter()
             .map(convert_dimension_to_airlayer)
             .collect(),
-        measures: view
-            .measures
-            .as_ref()
-            .map(|ms| ms.iter().map(convert_measure_to_airlayer).collect()),
+        // Windowed measures are applied over the compiled query instead
+        measures: view.measures.as_ref().map(|ms| {
+            ms.iter()
+                .filter(|m| !m.measure_type.is_windowed())
+                .map(convert_measure_to_airlayer)
+                .collect()
+        }),
         // TODO: pass through segments when oxy-semantic adds support
         segments: vec![],
         pre_aggregations: None,
@@ -822,7 +957,16 @@ fn convert_measure_to_airlayer(measure: &oxy_semantic::Measure) -> airlayer::Mea
                 airlayer::schema::models::MeasureType::CountDistinct
             }
             oxy_semantic::MeasureType::Median => airlayer::schema::models::MeasureType::Median,
-            oxy_semantic::MeasureType::Custom => airlayer::schema::models::MeasureType::Custom,
+            // Derived and ratio measures are expanded into custom ones and
+            // windowed measures filtered out before conversion
+            oxy_semantic::MeasureType::Custom
+            | oxy_semantic::MeasureType::Derived
+            | oxy_semantic::MeasureType::Ratio
+            | oxy_semantic::MeasureType::Cumulative
+            | oxy_semantic::MeasureType::Window
+            | oxy_semantic::MeasureType::PeriodOverPeriod => {
+                airlayer::schema::models::MeasureType::Custom
+            }
         },
         description: measure.description.clone(),
         expr: measure.expr.clone(),
@@ -964,7 +1108,7 @@ fn build_airlayer_query(
 }
 
 /// Qualify a field name with the topic name if not already qualified.
-fn qualify_field(field: &str, topic_name: &str) -> String {
+pub(crate) fn qualify_field(field: &str, topic_name: &str) -> String {
     if field.contains('.') {
         field.to_string()
     } else {
@@ -973,7 +1117,7 @@ fn qualify_field(field: &str, topic_name: &str) -> String {
 }
 
 /// Convert TimeGranularity to airlayer granularity string.
-fn granularity_to_string(g: &TimeGranularity) -> String {
+pub(crate) fn granularity_to_string(g: &TimeGranularity) -> String {
     match g {
         TimeGranularity::Year => "year",
         TimeGranularity::Quarter => "quarter",
@@ -1163,6 +1307,7 @@ pub fn compile_validated_to_sql(
         config_manager,
         &date_fields,
     )
+    .map(|compiled| compiled.sql)
 }
 
 /// Get the database reference from a [`ValidatedSemanticQuery`] (inspects view datasources).
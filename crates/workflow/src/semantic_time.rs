@@ -0,0 +1,612 @@
+//! Time grains and the time spine of semantic queries.
+//!
+//! A dimension requested as `<view>.<dimension>.<grain>` is grouped by that
+//! grain: calendar grains become time dimensions at the matching
+//! granularity, fiscal grains a date dimension added to the view that holds
+//! the start of each fiscal period.
+//!
+//! Queries with `time_spine` set are filled after they run: every bucket
+//! between the first and last one returned gets a row for each combination
+//! of the other dimensions. Additive measures are zero in those rows and
+//! the others null.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use arrow::array::{
+    ArrayRef, AsArray, BooleanArray, Date32Array, Int64Array, RecordBatch, UInt32Array,
+    new_null_array,
+};
+use arrow::compute::kernels::zip::zip;
+use arrow::compute::{cast, concat_batches, take};
+use arrow::datatypes::{DataType, Date32Type, SchemaRef};
+use arrow::error::ArrowError;
+use arrow::row::{RowConverter, SortField};
+use chrono::NaiveDate;
+use oxy::config::model::SemanticQueryTask;
+use oxy::types::{TimeDimension, TimeGranularity};
+use oxy_semantic::time::{fiscal_dimension, split_grain, time_spine};
+use oxy_semantic::{Dimension, TimeGrain, View};
+use oxy_shared::errors::OxyError;
+
+use crate::semantic_builder::{granularity_to_string, qualify_field};
+use crate::semantic_windows::{column_alias, time_alias};
+
+/// Largest result the time spine fills; bigger ones are returned as they are.
+const MAX_FILLED_ROWS: usize = 1_000_000;
+
+/// A semantic query with its `<dimension>.<grain>` references resolved.
+pub(crate) struct GrainedQuery {
+    pub task: SemanticQueryTask,
+    /// The topic's views, with the fiscal period dimensions the query uses
+    pub views: Vec<View>,
+    /// Fields of the fiscal period dimensions the query groups by
+    fiscal: Vec<(String, TimeGrain)>,
+}
+
+enum GrainField {
+    Time(TimeDimension),
+    Fiscal(String, TimeGrain),
+}
+
+/// Resolve the grain references in the dimensions and orders of `task`, and
+/// check the granularity of its time dimensions against the grains their
+/// dimensions declare.
+///
+/// `dialect` returns the database dialect of a view, for fiscal grains.
+pub(crate) fn resolve_grains(
+    task: &SemanticQueryTask,
+    topic_name: &str,
+    views: &[View],
+    dialect: impl Fn(&View) -> Result<String, OxyError>,
+) -> Result<GrainedQuery, OxyError> {
+    for td in &task.query.time_dimensions {
+        let Some(granularity) = &td.granularity else {
+            continue;
+        };
+        let field = qualify_field(&td.dimension, topic_name);
+        if let Some((_, dimension)) = find_dimension(views, &field)
+            && dimension.grains.is_some()
+            && !granularity_to_grain(granularity)
+                .is_some_and(|grain| dimension.available_grains().contains(&grain))
+        {
+            return Err(unavailable_grain(
+                &field,
+                &granularity_to_string(granularity),
+                dimension,
+            ));
+        }
+    }
+
+    let mut grained = GrainedQuery {
+        task: task.clone(),
+        views: views.to_vec(),
+        fiscal: Vec::new(),
+    };
+    grained.task.query.dimensions = Vec::new();
+    for field in &task.query.dimensions {
+        match grained.resolve(field, topic_name, &dialect)? {
+            Some(GrainField::Time(td)) => {
+                if !grained.task.query.time_dimensions.contains(&td) {
+                    grained.task.query.time_dimensions.push(td);
+                }
+            }
+            Some(GrainField::Fiscal(field, grain)) => {
+                if !grained.task.query.dimensions.contains(&field) {
+                    grained.task.query.dimensions.push(field.clone());
+                    grained.fiscal.push((field, grain));
+                }
+            }
+            None => grained.task.query.dimensions.push(field.clone()),
+        }
+    }
+    for index in 0..grained.task.query.orders.len() {
+        let field = grained.task.query.orders[index].field.clone();
+        let resolved = match grained.resolve(&field, topic_name, &dialect)? {
+            Some(GrainField::Time(td)) => td.dimension,
+            Some(GrainField::Fiscal(field, _)) => field,
+            None => continue,
+        };
+        grained.task.query.orders[index].field = resolved;
+    }
+    Ok(grained)
+}
+
+impl GrainedQuery {
+    /// Resolve `field` when it is a grain reference, adding the fiscal
+    /// period dimension it needs to its view.
+    fn resolve(
+        &mut self,
+        field: &str,
+        topic_name: &str,
+        dialect: &impl Fn(&View) -> Result<String, OxyError>,
+    ) -> Result<Option<GrainField>, OxyError> {
+        let Some((base, grain)) = split_grain(field) else {
+            return Ok(None);
+        };
+        let base = qualify_field(base, topic_name);
+        let Some((view_index, dimension)) = find_dimension(&self.views, &base) else {
+            return Ok(None);
+        };
+        if !dimension.available_grains().contains(&grain) {
+            return Err(unavailable_grain(&base, &grain.to_string(), dimension));
+        }
+        if let Some(granularity) = grain_to_granularity(grain) {
+            return Ok(Some(GrainField::Time(TimeDimension {
+                dimension: base,
+                granularity: Some(granularity),
+            })));
+        }
+
+        let view = &self.views[view_index];
+        let period = fiscal_dimension(dimension, grain, &dialect(view)?)
+            .map_err(|e| OxyError::ValidationError(e.to_string()))?;
+        let field = format!("{}.{}", view.name, period.name);
+        let view = &mut self.views[view_index];
+        if !view.dimensions.iter().any(|d| d.name == period.name) {
+            view.dimensions.push(period);
+        }
+        Ok(Some(GrainField::Fiscal(field, grain)))
+    }
+}
+
+fn find_dimension<'a>(views: &'a [View], field: &str) -> Option<(usize, &'a Dimension)> {
+    let (view_name, dimension_name) = field.split_once('.')?;
+    let index = views.iter().position(|view| view.name == view_name)?;
+    let dimension = views[index]
+        .dimensions
+        .iter()
+        .find(|dimension| dimension.name == dimension_name)?;
+    Some((index, dimension))
+}
+
+fn unavailable_grain(field: &str, grain: &str, dimension: &Dimension) -> OxyError {
+    let grains = dimension.available_grains();
+    if grains.is_empty() {
+        return OxyError::ValidationError(format!(
+            "Dimension '{field}' cannot be queried by {grain}: it is not a date or datetime dimension"
+        ));
+    }
+    let grains: Vec<String> = grains.iter().map(ToString::to_string).collect();
+    OxyError::ValidationError(format!(
+        "Dimension '{field}' cannot be queried by {grain}. Available grains: {}",
+        grains.join(", ")
+    ))
+}
+
+pub(crate) fn grain_to_granularity(grain: TimeGrain) -> Option<TimeGranularity> {
+    match grain {
+        TimeGrain::Day => Some(TimeGranularity::Day),
+        TimeGrain::Week => Some(TimeGranularity::Week),
+        TimeGrain::Month => Some(TimeGranularity::Month),
+        TimeGrain::Quarter => Some(TimeGranularity::Quarter),
+        TimeGrain::Year => Some(TimeGranularity::Year),
+        TimeGrain::FiscalQuarter | TimeGrain::FiscalYear => None,
+    }
+}
+
+fn granularity_to_grain(granularity: &TimeGranularity) -> Option<TimeGrain> {
+    match granularity {
+        TimeGranularity::Day => Some(TimeGrain::Day),
+        TimeGranularity::Week => Some(TimeGrain::Week),
+        TimeGranularity::Month => Some(TimeGrain::Month),
+        TimeGranularity::Quarter => Some(TimeGrain::Quarter),
+        TimeGranularity::Year => Some(TimeGrain::Year),
+        TimeGranularity::Hour | TimeGranularity::Minute | TimeGranularity::Second => None,
+    }
+}
+
+/// Fills the time buckets missing from the result of a semantic query.
+#[derive(Debug)]
+pub(crate) struct TimeSpine {
+    /// Output column holding the time buckets
+    time_column: String,
+    grain: TimeGrain,
+    /// The other grouping columns; each combination of them is filled
+    group_columns: Vec<String>,
+    /// Measure columns, and whether each is null in filled rows. Filled rows
+    /// hold zero in additive measures and null in the others
+    measure_columns: Vec<(String, bool)>,
+    descending: bool,
+}
+
+impl TimeSpine {
+    /// Plan the time spine of `grained`, given `compiled`, the query sent to
+    /// airlayer. Returns `None` when the query does not ask for one.
+    pub(crate) fn plan(
+        grained: &GrainedQuery,
+        compiled: &SemanticQueryTask,
+        topic_name: &str,
+    ) -> Result<Option<Self>, OxyError> {
+        let task = &grained.task;
+        if !task.query.time_spine {
+            return Ok(None);
+        }
+
+        // (field, output column, grain) of each column grouped by a grain
+        let mut buckets = Vec::new();
+        for td in &compiled.query.time_dimensions {
+            let Some(granularity) = &td.granularity else {
+                continue;
+            };
+            let grain = granularity_to_grain(granularity).ok_or_else(|| {
+                OxyError::ValidationError(format!(
+                    "The time spine does not support the {} granularity of '{}'",
+                    granularity_to_string(granularity),
+                    td.dimension
+                ))
+            })?;
+            buckets.push((td.dimension.clone(), time_alias(td), grain));
+        }
+        for (field, grain) in &grained.fiscal {
+            buckets.push((field.clone(), column_alias(field), *grain));
+        }
+        let [(bucket_field, time_column, grain)] = <[_; 1]>::try_from(buckets).map_err(|_| {
+            OxyError::ValidationError(
+                "The time spine needs the query to group by exactly one dimension at a grain"
+                    .to_string(),
+            )
+        })?;
+
+        let group_columns = compiled
+            .query
+            .dimensions
+            .iter()
+            .map(|field| column_alias(field))
+            .chain(compiled.query.time_dimensions.iter().map(time_alias))
+            .filter(|column| *column != time_column)
+            .collect();
+        let measure_columns = task
+            .query
+            .measures
+            .iter()
+            .map(|field| {
+                let field = qualify_field(field, topic_name);
+                let additive = field.split_once('.').is_some_and(|(view_name, name)| {
+                    grained
+                        .views
+                        .iter()
+                        .filter(|view| view.name == view_name)
+                        .flat_map(|view| view.measures.iter().flatten())
+                        .any(|measure| measure.name == name && measure.measure_type.is_additive())
+                });
+                (column_alias(&field), !additive)
+            })
+            .collect();
+        let descending = task
+            .query
+            .orders
+            .iter()
+            .find(|order| qualify_field(&order.field, topic_name) == bucket_field)
+            .is_some_and(|order| order.direction.eq_ignore_ascii_case("desc"));
+
+        Ok(Some(Self {
+            time_column,
+            grain,
+            group_columns,
+            measure_columns,
+            descending,
+        }))
+    }
+
+    /// Fill `batches` with a row for every missing bucket of every group.
+    ///
+    /// Filled results are ordered by bucket, then by the order each group
+    /// first appears in. Results whose buckets are not aligned to the grain
+    /// are returned as they are.
+    pub(crate) fn fill(
+        &self,
+        batches: &[RecordBatch],
+        schema: &SchemaRef,
+    ) -> Result<Vec<RecordBatch>, OxyError> {
+        let batch = concat_batches(schema, batches).map_err(arrow_error)?;
+        let column_index = |name: &str| {
+            schema.index_of(name).map_err(|_| {
+                OxyError::RuntimeError(format!(
+                    "Time spine column '{name}' is missing from the query result"
+                ))
+            })
+        };
+        let time_index = column_index(&self.time_column)?;
+        let dates = cast(batch.column(time_index), &DataType::Date32).map_err(arrow_error)?;
+        let dates: Vec<Option<NaiveDate>> = dates
+            .as_primitive::<Date32Type>()
+            .iter()
+            .map(|days| days.map(Date32Type::to_naive_date))
+            .collect();
+        let (Some(first), Some(last)) = (
+            dates.iter().flatten().min().copied(),
+            dates.iter().flatten().max().copied(),
+        ) else {
+            return Ok(batches.to_vec());
+        };
+
+        // The first row of each group, and the group of each row
+        let group_indices = self
+            .group_columns
+            .iter()
+            .map(|name| column_index(name))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut groups = vec![0];
+        let mut group_of = vec![0; batch.num_rows()];
+        if !group_indices.is_empty() {
+            let converter = RowConverter::new(
+                group_indices
+                    .iter()
+                    .map(|&index| SortField::new(schema.field(index).data_type().clone()))
+                    .collect(),
+            )
+            .map_err(arrow_error)?;
+            let columns: Vec<ArrayRef> = group_indices
+                .iter()
+                .map(|&index| batch.column(index).clone())
+                .collect();
+            let rows = converter.convert_columns(&columns).map_err(arrow_error)?;
+            let mut keys = HashMap::new();
+            groups.clear();
+            for (row, group) in group_of.iter_mut().enumerate() {
+                *group = *keys.entry(rows.row(row).owned()).or_insert_with(|| {
+                    groups.push(row);
+                    groups.len() - 1
+                });
+            }
+        }
+
+        let mut spine = time_spine(first, last, self.grain);
+        let buckets: HashSet<NaiveDate> = spine.iter().copied().collect();
+        if dates.iter().flatten().any(|date| !buckets.contains(date)) {
+            tracing::warn!(
+                "Time spine skipped: '{}' holds values that are not {} buckets",
+                self.time_column,
+                self.grain
+            );
+            return Ok(batches.to_vec());
+        }
+        if spine.len().saturating_mul(groups.len()) > MAX_FILLED_ROWS {
+            tracing::warn!(
+                "Time spine skipped: filling '{}' would return more than {MAX_FILLED_ROWS} rows",
+                self.time_column
+            );
+            return Ok(batches.to_vec());
+        }
+        if self.descending {
+            spine.reverse();
+        }
+
+        let mut rows_by_bucket: HashMap<(usize, NaiveDate), Vec<u32>> = HashMap::new();
+        for (row, date) in dates.iter().enumerate() {
+            if let Some(date) = date {
+                rows_by_bucket
+                    .entry((group_of[row], *date))
+                    .or_default()
+                    .push(row as u32);
+            }
+        }
+        let mut indices = Vec::new();
+        let mut filled = Vec::new();
+        for bucket in &spine {
+            for (group, &first_row) in groups.iter().enumerate() {
+                match rows_by_bucket.get(&(group, *bucket)) {
+                    Some(rows) => {
+                        indices.extend_from_slice(rows);
+                        filled.extend(rows.iter().map(|_| None));
+                    }
+                    None => {
+                        indices.push(first_row as u32);
+                        filled.push(Some(*bucket));
+                    }
+                }
+            }
+        }
+        // Rows without a bucket stay at the end
+        for (row, date) in dates.iter().enumerate() {
+            if date.is_none() {
+                indices.push(row as u32);
+                filled.push(None);
+            }
+        }
+
+        let len = indices.len();
+        let indices = UInt32Array::from(indices);
+        let mask = BooleanArray::from(filled.iter().map(Option::is_some).collect::<Vec<_>>());
+        let mut columns = Vec::with_capacity(batch.num_columns());
+        for (index, field) in schema.fields().iter().enumerate() {
+            let taken = take(batch.column(index), &indices, None).map_err(arrow_error)?;
+            let fill: ArrayRef = if index == time_index {
+                let buckets = Date32Array::from(
+                    filled
+                        .iter()
+                        .map(|bucket| bucket.map(Date32Type::from_naive_date))
+                        .collect::<Vec<_>>(),
+                );
+                cast(&buckets, field.data_type()).map_err(arrow_error)?
+            } else {
+                match self
+                    .measure_columns
+                    .iter()
+                    .find(|(name, _)| name == field.name())
+                {
+                    Some((_, true)) => new_null_array(field.data_type(), len),
+                    Some((_, false)) => cast(&Int64Array::from(vec![0; len]), field.data_type())
+                        .map_err(arrow_error)?,
+                    None => {
+                        columns.push(taken);
+                        continue;
+                    }
+                }
+            };
+            columns.push(zip(&mask, &fill, &taken).map_err(arrow_error)?);
+        }
+        let batch = RecordBatch::try_new(schema.clone(), columns).map_err(arrow_error)?;
+        Ok(vec![batch])
+    }
+}
+
+fn arrow_error(e: ArrowError) -> OxyError {
+    OxyError::RuntimeError(format!("Failed to fill time spine: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Float64Array, StringArray};
+    use arrow::datatypes::{Field, Schema};
+
+    fn orders_view() -> View {
+        serde_yaml::from_str(
+            "name: orders
+table: orders
+entities:
+  - name: order
+    type: primary
+    key: id
+dimensions:
+  - name: order_date
+    type: date
+    expr: order_date
+    grains: [month, quarter, fiscal_year]
+    fiscal_year_start: 4
+  - name: region
+    type: string
+    expr: region
+measures:
+  - name: revenue
+    type: sum
+    expr: amount
+",
+        )
+        .unwrap()
+    }
+
+    fn task(yaml: &str) -> SemanticQueryTask {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    fn postgres(_: &View) -> Result<String, OxyError> {
+        Ok("postgres".to_string())
+    }
+
+    #[test]
+    fn test_calendar_grain_becomes_time_dimension() {
+        let task = task(
+            "topic: orders
+dimensions: [orders.order_date.month, orders.region]
+measures: [orders.revenue]
+orders:
+  - field: orders.order_date.month
+    direction: asc
+",
+        );
+        let grained = resolve_grains(&task, "orders", &[orders_view()], postgres).unwrap();
+        assert_eq!(grained.task.query.dimensions, ["orders.region"]);
+        assert_eq!(
+            grained.task.query.time_dimensions,
+            [TimeDimension {
+                dimension: "orders.order_date".to_string(),
+                granularity: Some(TimeGranularity::Month),
+            }]
+        );
+        assert_eq!(grained.task.query.orders[0].field, "orders.order_date");
+    }
+
+    #[test]
+    fn test_fiscal_grain_adds_period_dimension() {
+        let task = task("topic: orders\ndimensions: [order_date.fiscal_year]\n");
+        let grained = resolve_grains(&task, "orders", &[orders_view()], postgres).unwrap();
+        assert_eq!(
+            grained.task.query.dimensions,
+            ["orders.order_date_fiscal_year"]
+        );
+        assert!(
+            grained.views[0]
+                .dimensions
+                .iter()
+                .any(|d| d.name == "order_date_fiscal_year")
+        );
+    }
+
+    #[test]
+    fn test_undeclared_grain_is_rejected() {
+        let query = task("topic: orders\ndimensions: [orders.order_date.day]\n");
+        let error = resolve_grains(&query, "orders", &[orders_view()], postgres)
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("Available grains: month"));
+
+        let query = task(
+            "topic: orders
+time_dimensions:
+  - dimension: orders.order_date
+    granularity: week
+",
+        );
+        assert!(resolve_grains(&query, "orders", &[orders_view()], postgres).is_err());
+    }
+
+    #[test]
+    fn test_time_spine_fills_missing_buckets() {
+        let task = task(
+            "topic: orders
+dimensions: [orders.order_date.month, orders.region]
+measures: [orders.revenue]
+time_spine: true
+",
+        );
+        let grained = resolve_grains(&task, "orders", &[orders_view()], postgres).unwrap();
+        let spine = TimeSpine::plan(&grained, &grained.task, "orders")
+            .unwrap()
+            .unwrap();
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("orders__region", DataType::Utf8, true),
+            Field::new("orders__order_date_month", DataType::Utf8, true),
+            Field::new("orders__revenue", DataType::Float64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec!["east", "west", "east"])),
+                Arc::new(StringArray::from(vec![
+                    "2024-01-01",
+                    "2024-01-01",
+                    "2024-03-01",
+                ])),
+                Arc::new(Float64Array::from(vec![1.0, 2.0, 3.0])),
+            ],
+        )
+        .unwrap();
+
+        let filled = spine.fill(&[batch], &schema).unwrap();
+        let filled = &filled[0];
+        let column = |index: usize| filled.column(index).clone();
+        assert_eq!(
+            column(0).as_string::<i32>().iter().collect::<Vec<_>>(),
+            [
+                Some("east"),
+                Some("west"),
+                Some("east"),
+                Some("west"),
+                Some("east"),
+                Some("west")
+            ]
+        );
+        assert_eq!(
+            column(1).as_string::<i32>().iter().collect::<Vec<_>>(),
+            [
+                Some("2024-01-01"),
+                Some("2024-01-01"),
+                Some("2024-02-01"),
+                Some("2024-02-01"),
+                Some("2024-03-01"),
+                Some("2024-03-01")
+            ]
+        );
+        assert_eq!(
+            column(2)
+                .as_primitive::<arrow::datatypes::Float64Type>()
+                .values()
+                .to_vec(),
+            [1.0, 2.0, 0.0, 0.0, 3.0, 0.0]
+        );
+    }
+}
@@ -363,6 +363,7 @@ fn validate_task_against_metadata(
 
 /// Builds sets of valid fully-qualified dimension and measure field names from views.
 /// Enforces the `topic.field` form so only fully-qualified references are accepted.
+/// Date and datetime dimensions are also valid at each of their grains (`view.dimension.grain`).
 fn build_field_sets(_topic_name: &str, views: &[View]) -> (HashSet<String>, HashSet<String>) {
     let mut dimensions = HashSet::new();
     let mut measures = HashSet::new();
@@ -370,6 +371,9 @@ fn build_field_sets(_topic_name: &str, views: &[View]) -> (HashSet<String>, Hash
     for view in views {
         for dimension in &view.dimensions {
             dimensions.insert(format!("{}.{}", view.name, dimension.name));
+            for grain in dimension.available_grains() {
+                dimensions.insert(format!("{}.{}.{}", view.name, dimension.name, grain));
+            }
         }
         if let Some(view_measures) = &view.measures {
             for measure in view_measures {
@@ -742,6 +746,10 @@ mod tests {
                     original_expr: None,
                     samples: None,
                     synonyms: None,
+                    access: None,
+                    masking: None,
+                    grains: None,
+                    fiscal_year_start: None,
                 },
                 Dimension {
                     name: "order_date".to_string(),
@@ -751,6 +759,10 @@ mod tests {
                     original_expr: None,
                     samples: None,
                     synonyms: None,
+                    access: None,
+                    masking: None,
+                    grains: None,
+                    fiscal_year_start: None,
                 },
                 Dimension {
                     name: "status".to_string(),
@@ -760,9 +772,14 @@ mod tests {
                     original_expr: None,
                     samples: None,
                     synonyms: None,
+                    access: None,
+                    masking: None,
+                    grains: None,
+                    fiscal_year_start: None,
                 },
             ],
             measures: None,
+            access_policies: None,
         }]
     }
 
@@ -787,6 +804,7 @@ mod tests {
                 limit: None,
                 offset: None,
                 variables: None,
+                time_spine: false,
                 time_dimensions: vec![TimeDimension {
                     dimension: "orders.created_at".to_string(),
                     granularity: Some(TimeGranularity::Month),
@@ -820,6 +838,7 @@ mod tests {
                 limit: None,
                 offset: None,
                 variables: None,
+                time_spine: false,
                 time_dimensions: vec![TimeDimension {
                     dimension: "orders.unknown_field".to_string(),
                     granularity: Some(TimeGranularity::Day),
@@ -853,6 +872,7 @@ mod tests {
                 limit: None,
                 offset: None,
                 variables: None,
+                time_spine: false,
                 time_dimensions: vec![TimeDimension {
                     dimension: "orders.status".to_string(), // String type, not date/datetime
                     granularity: Some(TimeGranularity::Day),
@@ -885,6 +905,7 @@ mod tests {
                 limit: None,
                 offset: None,
                 variables: None,
+                time_spine: false,
                 time_dimensions: vec![TimeDimension {
                     dimension: "orders.created_at".to_string(), // Conflict!
                     granularity: Some(TimeGranularity::Month),
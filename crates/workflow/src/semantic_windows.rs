@@ -0,0 +1,385 @@
+//! Windowed semantic measures (`cumulative`, `window`, `period_over_period`).
+//!
+//! airlayer has no notion of measures computed over grouped rows, so the
+//! query sent to it asks for the underlying measures instead, grouped by the
+//! measure's time dimension. The compiled SQL is then wrapped in a select
+//! that applies the window functions from [`oxy_semantic::metrics`].
+//!
+//! The wrapper refers to airlayer's output column names: `<view>__<field>`,
+//! with `_<granularity>` appended for time dimensions grouped by a grain.
+
+use oxy::config::model::SemanticQueryTask;
+use oxy::types::TimeDimension;
+use oxy_semantic::metrics::window_sql;
+use oxy_semantic::{Measure, View};
+use oxy_shared::errors::OxyError;
+
+use crate::semantic_builder::{granularity_to_string, qualify_field};
+use crate::semantic_time::grain_to_granularity;
+
+/// A semantic query with windowed measures, split into the query airlayer
+/// compiles and the select applied over its result.
+pub(crate) struct WindowedQuery {
+    /// Query for airlayer: windowed measures replaced by the measures they
+    /// are computed from, without ordering or paging
+    pub task: SemanticQueryTask,
+    columns: Vec<OutputColumn>,
+    orders: Vec<(String, bool)>,
+    limit: Option<u64>,
+    offset: Option<u64>,
+}
+
+enum OutputColumn {
+    Field(String),
+    Windowed {
+        alias: String,
+        measure: Measure,
+        value: String,
+        order_by: String,
+        partition_by: Vec<String>,
+    },
+}
+
+impl WindowedQuery {
+    /// Split `task` when it requests windowed measures of `views`.
+    ///
+    /// Returns `None` when it requests none, so the query can be compiled
+    /// as it is.
+    pub(crate) fn plan(
+        task: &SemanticQueryTask,
+        topic_name: &str,
+        views: &[View],
+    ) -> Result<Option<Self>, OxyError> {
+        let requested: Vec<(String, Option<&Measure>)> = task
+            .query
+            .measures
+            .iter()
+            .map(|field| {
+                let field = qualify_field(field, topic_name);
+                let measure = find_measure(views, &field).filter(|m| m.measure_type.is_windowed());
+                (field, measure)
+            })
+            .collect();
+        if requested.iter().all(|(_, measure)| measure.is_none()) {
+            return Ok(None);
+        }
+
+        let mut query = task.clone();
+        query.query.dimensions = task
+            .query
+            .dimensions
+            .iter()
+            .map(|field| qualify_field(field, topic_name))
+            .collect();
+        query.query.time_dimensions = task
+            .query
+            .time_dimensions
+            .iter()
+            .map(|td| TimeDimension {
+                dimension: qualify_field(&td.dimension, topic_name),
+                granularity: td.granularity.clone(),
+            })
+            .collect();
+        query.query.measures = Vec::new();
+        query.query.orders = Vec::new();
+        query.query.limit = None;
+        query.query.offset = None;
+
+        // Underlying measures, and the time column each windowed measure
+        // steps through
+        let mut windows = Vec::new();
+        for (field, measure) in &requested {
+            let Some(measure) = measure else {
+                push_unique(&mut query.query.measures, field.clone());
+                continue;
+            };
+            let view_name = field
+                .split_once('.')
+                .map(|(view, _)| view)
+                .unwrap_or_default();
+            let composition = &measure.composition;
+            let (Some(base), Some(time_dimension)) =
+                (&composition.measure, &composition.time_dimension)
+            else {
+                return Err(OxyError::ValidationError(format!(
+                    "Measure '{field}' requires 'measure' and 'time_dimension' fields"
+                )));
+            };
+            let value = format!("{view_name}.{base}");
+            push_unique(&mut query.query.measures, value.clone());
+            let order_by = time_column(&mut query, field, measure, view_name, time_dimension)?;
+            windows.push((field.clone(), (*measure).clone(), value, order_by));
+        }
+
+        let groups: Vec<String> = query
+            .query
+            .dimensions
+            .iter()
+            .map(|field| column_alias(field))
+            .chain(query.query.time_dimensions.iter().map(time_alias))
+            .collect();
+        let mut columns: Vec<OutputColumn> =
+            groups.iter().cloned().map(OutputColumn::Field).collect();
+        for (field, _) in &requested {
+            match windows.iter().find(|(windowed, ..)| windowed == field) {
+                Some((_, measure, value, order_by)) => columns.push(OutputColumn::Windowed {
+                    alias: column_alias(field),
+                    measure: measure.clone(),
+                    value: column_alias(value),
+                    order_by: order_by.clone(),
+                    partition_by: groups.iter().filter(|g| *g != order_by).cloned().collect(),
+                }),
+                None => columns.push(OutputColumn::Field(column_alias(field))),
+            }
+        }
+
+        let orders = task
+            .query
+            .orders
+            .iter()
+            .map(|order| {
+                let field = qualify_field(&order.field, topic_name);
+                let alias = query
+                    .query
+                    .time_dimensions
+                    .iter()
+                    .find(|td| td.dimension == field)
+                    .map(time_alias)
+                    .unwrap_or_else(|| column_alias(&field));
+                (alias, order.direction.eq_ignore_ascii_case("desc"))
+            })
+            .collect();
+
+        Ok(Some(Self {
+            task: query,
+            columns,
+            orders,
+            limit: task.query.limit,
+            offset: task.query.offset,
+        }))
+    }
+
+    /// Wrap `sql`, compiled from [`Self::task`], in the select that computes
+    /// the windowed measures and applies the original ordering and paging.
+    pub(crate) fn wrap(&self, sql: &str, dialect: &str) -> Result<String, OxyError> {
+        let quote = |alias: &str| quote_identifier(alias, dialect);
+        let mut select = Vec::with_capacity(self.columns.len());
+        for column in &self.columns {
+            match column {
+                OutputColumn::Field(alias) => select.push(quote(alias)),
+                OutputColumn::Windowed {
+                    alias,
+                    measure,
+                    value,
+                    order_by,
+                    partition_by,
+                } => {
+                    let partition_by: Vec<String> =
+                        partition_by.iter().map(|alias| quote(alias)).collect();
+                    let expr = window_sql(measure, &quote(value), &quote(order_by), &partition_by)
+                        .map_err(|e| OxyError::ValidationError(e.to_string()))?;
+                    select.push(format!("{expr} AS {}", quote(alias)));
+                }
+            }
+        }
+
+        let inner = sql.trim().trim_end_matches(';');
+        let mut wrapped = format!(
+            "SELECT {} FROM ({inner}) AS windowed_base",
+            select.join(", ")
+        );
+        let order_by: Vec<String> = self
+            .orders
+            .iter()
+            .map(|(alias, desc)| format!("{} {}", quote(alias), if *desc { "DESC" } else { "ASC" }))
+            .collect();
+        let paged = self.limit.is_some() || self.offset.is_some();
+        if !order_by.is_empty() {
+            wrapped.push_str(&format!(" ORDER BY {}", order_by.join(", ")));
+        } else if paged && dialect == "mssql" {
+            wrapped.push_str(" ORDER BY (SELECT NULL)");
+        }
+        if dialect == "mssql" {
+            if paged {
+                wrapped.push_str(&format!(" OFFSET {} ROWS", self.offset.unwrap_or(0)));
+            }
+            if let Some(limit) = self.limit {
+                wrapped.push_str(&format!(" FETCH NEXT {limit} ROWS ONLY"));
+            }
+        } else {
+            if let Some(limit) = self.limit {
+                wrapped.push_str(&format!(" LIMIT {limit}"));
+            }
+            if let Some(offset) = self.offset {
+                wrapped.push_str(&format!(" OFFSET {offset}"));
+            }
+        }
+        Ok(wrapped)
+    }
+}
+
+/// Find the output column of `measure`'s time dimension, grouping the query
+/// by it at the measure's grain when it is not grouped by it already.
+fn time_column(
+    query: &mut SemanticQueryTask,
+    field: &str,
+    measure: &Measure,
+    view_name: &str,
+    time_dimension: &str,
+) -> Result<String, OxyError> {
+    let dimension = format!("{view_name}.{time_dimension}");
+    if query.query.dimensions.contains(&dimension) {
+        return Ok(column_alias(&dimension));
+    }
+    if let Some(td) = query
+        .query
+        .time_dimensions
+        .iter()
+        .find(|td| td.dimension == dimension)
+    {
+        return Ok(time_alias(td));
+    }
+    let granularity = measure
+        .composition
+        .grain
+        .and_then(grain_to_granularity)
+        .ok_or_else(|| {
+            OxyError::ValidationError(format!(
+                "Measure '{field}' needs the query to group by '{dimension}', or a calendar 'grain' in its definition"
+            ))
+        })?;
+    let td = TimeDimension {
+        dimension,
+        granularity: Some(granularity),
+    };
+    let alias = time_alias(&td);
+    query.query.time_dimensions.push(td);
+    Ok(alias)
+}
+
+fn find_measure<'a>(views: &'a [View], field: &str) -> Option<&'a Measure> {
+    let (view_name, measure_name) = field.split_once('.')?;
+    views
+        .iter()
+        .find(|view| view.name == view_name)?
+        .measures
+        .iter()
+        .flatten()
+        .find(|measure| measure.name == measure_name)
+}
+
+fn push_unique(fields: &mut Vec<String>, field: String) {
+    if !fields.contains(&field) {
+        fields.push(field);
+    }
+}
+
+pub(crate) fn column_alias(field: &str) -> String {
+    field.replace('.', "__")
+}
+
+pub(crate) fn time_alias(td: &TimeDimension) -> String {
+    match &td.granularity {
+        Some(granularity) => format!(
+            "{}_{}",
+            column_alias(&td.dimension),
+            granularity_to_string(granularity)
+        ),
+        None => column_alias(&td.dimension),
+    }
+}
+
+fn quote_identifier(identifier: &str, dialect: &str) -> String {
+    match dialect {
+        "bigquery" | "mysql" => format!("`{identifier}`"),
+        _ => format!("\"{identifier}\""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxy::types::TimeGranularity;
+
+    fn orders_view() -> View {
+        serde_yaml::from_str(
+            "name: orders
+table: orders
+entities:
+  - name: order
+    type: primary
+    key: id
+dimensions:
+  - name: order_date
+    type: date
+    expr: order_date
+  - name: region
+    type: string
+    expr: region
+measures:
+  - name: revenue
+    type: sum
+    expr: amount
+  - name: running_revenue
+    type: cumulative
+    measure: revenue
+    time_dimension: order_date
+    grain: month
+",
+        )
+        .unwrap()
+    }
+
+    fn task(yaml: &str) -> SemanticQueryTask {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_plain_query_is_not_wrapped() {
+        let task = task("topic: orders\nmeasures: [orders.revenue]\n");
+        assert!(
+            WindowedQuery::plan(&task, "orders", &[orders_view()])
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_cumulative_measure_groups_by_its_grain() {
+        let task = task(
+            "topic: orders
+dimensions: [region]
+measures: [orders.running_revenue]
+orders:
+  - field: orders.region
+    direction: desc
+limit: 10
+",
+        );
+        let windowed = WindowedQuery::plan(&task, "orders", &[orders_view()])
+            .unwrap()
+            .unwrap();
+        assert_eq!(windowed.task.query.measures, ["orders.revenue"]);
+        assert_eq!(windowed.task.query.limit, None);
+        assert_eq!(
+            windowed.task.query.time_dimensions[0].granularity,
+            Some(TimeGranularity::Month)
+        );
+        assert_eq!(
+            windowed.wrap("SELECT 1;", "postgres").unwrap(),
+            "SELECT \"orders__region\", \"orders__order_date_month\", \
+             SUM(\"orders__revenue\") OVER (PARTITION BY \"orders__region\" \
+             ORDER BY \"orders__order_date_month\" \
+             ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) AS \"orders__running_revenue\" \
+             FROM (SELECT 1) AS windowed_base ORDER BY \"orders__region\" DESC LIMIT 10"
+        );
+    }
+
+    #[test]
+    fn test_windowed_measure_without_grain_needs_time_dimension() {
+        let mut view = orders_view();
+        view.measures.as_mut().unwrap()[1].composition.grain = None;
+        let task = task("topic: orders\nmeasures: [orders.running_revenue]\n");
+        assert!(WindowedQuery::plan(&task, "orders", &[view]).is_err());
+    }
+}
@@ -15,7 +15,9 @@ use oxy::{
 use oxy_shared::errors::OxyError;
 
 #[derive(Clone)]
-struct SQLTaskMapper;
+struct SQLTaskMapper {
+    cache_ttl_secs: Option<u64>,
+}
 
 #[async_trait::async_trait]
 impl ParamMapper<ExecuteSQLTask, SQLInput> for SQLTaskMapper {
@@ -85,14 +87,18 @@ impl ParamMapper<ExecuteSQLTask, SQLInput> for SQLTaskMapper {
                 dry_run_limit: input.dry_run_limit,
                 name: None,
                 persist: false,
+                cache_ttl_secs: self.cache_ttl_secs,
             },
             None,
         ))
     }
 }
 
-pub fn build_sql_task_executable() -> impl Executable<ExecuteSQLTask, Response = Table> {
+/// `cache_ttl_secs` is the task's cache TTL, applied to its query results
+pub fn build_sql_task_executable(
+    cache_ttl_secs: Option<u64>,
+) -> impl Executable<ExecuteSQLTask, Response = Table> {
     ExecutableBuilder::new()
-        .map(SQLTaskMapper)
+        .map(SQLTaskMapper { cache_ttl_secs })
         .executable(SQLExecutable::new())
 }
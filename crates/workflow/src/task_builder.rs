@@ -348,10 +348,12 @@ impl Executable<TaskInput> for TaskExecutable {
                     .await?;
                 Ok(output.into())
             }
-            TaskType::ExecuteSQL(execute_sqltask) => build_sql_task_executable()
-                .execute(&execution_context, execute_sqltask)
-                .await
-                .map(|output| output.into()),
+            TaskType::ExecuteSQL(execute_sqltask) => {
+                build_sql_task_executable(task.cache.as_ref().and_then(|cache| cache.ttl_secs))
+                    .execute(&execution_context, execute_sqltask)
+                    .await
+                    .map(|output| output.into())
+            }
             TaskType::OmniQuery(omni_query_task) => {
                 let output = build_omni_query_task_executable()
                     .execute(&execution_context, omni_query_task)
@@ -113,6 +113,7 @@ impl WorkflowMapper {
             description: temp_workflow.description,
             retrieval: temp_workflow.retrieval,
             consistency_prompt: temp_workflow.consistency_prompt,
+            schedule: temp_workflow.schedule,
         })
     }
 }
@@ -49,6 +49,7 @@ import AdminLayout from "./pages/admin/AdminLayout";
 import AppPage from "./pages/app";
 import GoogleCallback from "./pages/auth/GoogleCallback";
 import MagicLinkCallback from "./pages/auth/MagicLinkCallback";
+import OidcCallback from "./pages/auth/OidcCallback";
 import OktaCallback from "./pages/auth/OktaCallback";
 import CheckoutCancelledPage from "./pages/billing/CheckoutCancelled";
 import CheckoutSuccessPage from "./pages/billing/CheckoutSuccess";
@@ -359,6 +360,7 @@ const getCloudRouter = (authConfig: AuthConfigResponse) =>
             <Route path={ROUTES.AUTH.LOGIN} element={<LoginPage />} />
             <Route path={ROUTES.AUTH.GOOGLE_CALLBACK} element={<GoogleCallback />} />
             <Route path={ROUTES.AUTH.OKTA_CALLBACK} element={<OktaCallback />} />
+            <Route path={ROUTES.AUTH.OIDC_CALLBACK} element={<OidcCallback />} />
             <Route path={ROUTES.AUTH.MAGIC_LINK_CALLBACK} element={<MagicLinkCallback />} />
           </>
         )}
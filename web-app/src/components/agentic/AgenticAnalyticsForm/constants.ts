@@ -10,7 +10,9 @@ export const THINKING_OPTIONS = [
 export const VENDOR_OPTIONS = [
   { value: "anthropic", label: "Anthropic" },
   { value: "openai", label: "OpenAI" },
-  { value: "openai_compat", label: "OpenAI-compatible (Ollama, vLLM, …)" }
+  { value: "openai_compat", label: "OpenAI-compatible (Ollama, vLLM, …)" },
+  { value: "gemini", label: "Google Gemini" },
+  { value: "bedrock", label: "AWS Bedrock (Claude)" }
 ] as const;
 
 export const SEMANTIC_ENGINE_VENDORS = [
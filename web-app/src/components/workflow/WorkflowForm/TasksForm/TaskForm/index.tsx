@@ -59,7 +59,9 @@ const EXPORT_FORMATS = [
   { value: "csv", label: "CSV" },
   { value: "json", label: "JSON" },
   { value: "txt", label: "Text" },
-  { value: "docx", label: "Word Document" }
+  { value: "docx", label: "Word Document" },
+  { value: "parquet", label: "Parquet" },
+  { value: "xlsx", label: "Excel Workbook" }
 ];
 
 export const TaskForm: React.FC<TaskFormProps> = ({ index, onRemove, basePath = "tasks" }) => {
@@ -3,12 +3,14 @@ import type {
   AgentExecutionStats,
   ExecutionDetail,
   ExecutionSummary,
-  ExecutionTimeBucket
+  ExecutionTimeBucket,
+  SpendSummary
 } from "@/pages/ide/observability/execution-analytics/types";
 import {
   type AgentStatsQuery,
   ExecutionAnalyticsService,
   type ExecutionsQuery,
+  type SpendQuery,
   type SummaryQuery,
   type TimeSeriesQuery
 } from "@/services/api/executionAnalytics";
@@ -23,7 +25,9 @@ export const executionAnalyticsKeys = {
   agentStats: (projectId: string, params?: AgentStatsQuery) =>
     [...executionAnalyticsKeys.all, "agentStats", projectId, params] as const,
   executions: (projectId: string, params?: ExecutionsQuery) =>
-    [...executionAnalyticsKeys.all, "executions", projectId, params] as const
+    [...executionAnalyticsKeys.all, "executions", projectId, params] as const,
+  spend: (projectId: string, params?: SpendQuery) =>
+    [...executionAnalyticsKeys.all, "spend", projectId, params] as const
 };
 
 export const useExecutionSummary = (
@@ -69,3 +73,14 @@ export const useExecutions = (
     queryFn: () => ExecutionAnalyticsService.getExecutions(projectId!, params),
     enabled: enabled && !!projectId
   });
+
+export const useExecutionSpend = (
+  projectId: string | undefined,
+  params?: SpendQuery,
+  enabled = true
+) =>
+  useQuery<SpendSummary, Error>({
+    queryKey: executionAnalyticsKeys.spend(projectId!, params),
+    queryFn: () => ExecutionAnalyticsService.getSpend(projectId!, params),
+    enabled: enabled && !!projectId
+  });
@@ -0,0 +1,73 @@
+import { useMutation } from "@tanstack/react-query";
+import { useNavigate } from "react-router-dom";
+import { useAuth } from "@/contexts/AuthContext";
+import ROUTES from "@/libs/utils/routes";
+import { AuthService } from "@/services/api";
+import type { AuthResponse, OidcAuthRequest } from "@/types/auth";
+import { handlePostLoginOrgs } from "./postLoginRedirect";
+
+const OIDC_STATE_KEY = "oidc_oauth_state";
+const OIDC_SESSION_KEY = "oidc_oauth_session";
+
+const clearOidcSession = () => {
+  sessionStorage.removeItem(OIDC_STATE_KEY);
+  sessionStorage.removeItem(OIDC_SESSION_KEY);
+};
+
+export const useOidcAuth = () => {
+  const { login } = useAuth();
+  const navigate = useNavigate();
+
+  return useMutation<AuthResponse, Error, Omit<OidcAuthRequest, "session">>({
+    mutationFn: ({ code, state }) =>
+      AuthService.oidcAuth({
+        code,
+        state,
+        session: sessionStorage.getItem(OIDC_SESSION_KEY) ?? ""
+      }),
+    onSuccess: (data) => {
+      clearOidcSession();
+      login(data.token, data.user);
+      navigate(handlePostLoginOrgs(data.user, data.orgs));
+    },
+    onError: (error) => {
+      console.error("OIDC auth failed:", error);
+      clearOidcSession();
+      navigate(ROUTES.AUTH.LOGIN);
+    }
+  });
+};
+
+export const initiateOidcAuth = async () => {
+  // The backend discovers the provider and builds the URL, including the
+  // PKCE challenge; see useGoogleAuth.initiateGoogleAuth for the CSRF notes.
+  const { authorization_url, state, session } = await AuthService.oidcAuthorize();
+  sessionStorage.setItem(OIDC_STATE_KEY, state);
+  sessionStorage.setItem(OIDC_SESSION_KEY, session);
+  window.location.href = authorization_url;
+};
+
+/**
+ * Validates the OAuth state parameter to prevent CSRF attacks
+ * @param receivedState - The state parameter received in the callback
+ * @returns true if state is valid, false otherwise
+ */
+export const validateOidcState = (receivedState: string | null): boolean => {
+  if (!receivedState) {
+    console.error("CSRF validation failed: No state parameter received");
+    return false;
+  }
+
+  const storedState = sessionStorage.getItem(OIDC_STATE_KEY);
+  if (!storedState) {
+    console.error("CSRF validation failed: No stored state found");
+    return false;
+  }
+
+  if (receivedState !== storedState) {
+    console.error("CSRF validation failed: State mismatch");
+    return false;
+  }
+
+  return true;
+};
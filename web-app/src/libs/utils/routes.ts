@@ -6,6 +6,7 @@ const ROUTES = {
     LOGIN: "/login",
     GOOGLE_CALLBACK: "/auth/google/callback",
     OKTA_CALLBACK: "/auth/okta/callback",
+    OIDC_CALLBACK: "/auth/oidc/callback",
     MAGIC_LINK_CALLBACK: "/auth/magic-link/callback",
     GITHUB_AUTH_CALLBACK: "/auth/github/callback"
   },
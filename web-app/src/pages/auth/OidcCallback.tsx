@@ -0,0 +1,51 @@
+import { useEffect, useRef } from "react";
+import { useNavigate, useSearchParams } from "react-router-dom";
+import { Spinner } from "@/components/ui/shadcn/spinner";
+import { useOidcAuth, validateOidcState } from "@/hooks/auth/useSsoAuth";
+import ROUTES from "@/libs/utils/routes";
+
+const OidcCallback = () => {
+  const [searchParams] = useSearchParams();
+  const navigate = useNavigate();
+  const oidcAuthMutation = useOidcAuth();
+  const authAttempted = useRef(false);
+
+  useEffect(() => {
+    if (authAttempted.current) return;
+    authAttempted.current = true;
+
+    const code = searchParams.get("code");
+    const state = searchParams.get("state");
+    const error = searchParams.get("error");
+    const errorDescription = searchParams.get("error_description");
+
+    // Check for OAuth errors first
+    if (error) {
+      console.error("OIDC error:", error, errorDescription);
+      navigate(`${ROUTES.AUTH.LOGIN}?error=oauth_failed`);
+      return;
+    }
+
+    // Validate CSRF state token (critical security check)
+    if (!validateOidcState(state)) {
+      console.error("CSRF validation failed - potential attack detected");
+      navigate(`${ROUTES.AUTH.LOGIN}?error=csrf_validation_failed`);
+      return;
+    }
+
+    if (code && state) {
+      oidcAuthMutation.mutate({ code, state });
+    } else {
+      navigate(`${ROUTES.AUTH.LOGIN}?error=no_code`);
+    }
+  }, [searchParams, navigate, oidcAuthMutation]);
+
+  return (
+    <div className='flex h-full w-full flex-col items-center justify-center gap-5'>
+      <Spinner className='size-6' />
+      <p>Completing single sign-on...</p>
+    </div>
+  );
+};
+
+export default OidcCallback;
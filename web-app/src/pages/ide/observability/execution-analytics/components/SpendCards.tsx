@@ -0,0 +1,48 @@
+import { ArrowDownToLine, ArrowUpFromLine, DollarSign, Wrench } from "lucide-react";
+import { useExecutionSpend } from "@/hooks/api/useExecutionAnalytics";
+import { formatNumber, StatsCard } from "./SummaryCards";
+
+interface SpendCardsProps {
+  projectId: string;
+  days: number;
+}
+
+export default function SpendCards({ projectId, days }: SpendCardsProps) {
+  const { data: spend, isLoading } = useExecutionSpend(projectId, { days });
+  // Spend is only recorded when `budgets` is configured
+  const tracked = (spend?.daily.length ?? 0) > 0;
+  const period = tracked ? `last ${days} days` : "set budgets in config.yml to track";
+
+  return (
+    <div className='grid grid-cols-2 gap-4 lg:grid-cols-4'>
+      <StatsCard
+        title='LLM Spend'
+        value={`$${(spend?.costUsd ?? 0).toFixed(2)}`}
+        subtitle={period}
+        icon={<DollarSign className='h-5 w-5' />}
+        isLoading={isLoading}
+      />
+      <StatsCard
+        title='Input Tokens'
+        value={formatNumber(spend?.inputTokens ?? 0)}
+        subtitle={period}
+        icon={<ArrowDownToLine className='h-5 w-5' />}
+        isLoading={isLoading}
+      />
+      <StatsCard
+        title='Output Tokens'
+        value={formatNumber(spend?.outputTokens ?? 0)}
+        subtitle={period}
+        icon={<ArrowUpFromLine className='h-5 w-5' />}
+        isLoading={isLoading}
+      />
+      <StatsCard
+        title='Tool Calls'
+        value={formatNumber(spend?.toolCalls ?? 0)}
+        subtitle={period}
+        icon={<Wrench className='h-5 w-5' />}
+        isLoading={isLoading}
+      />
+    </div>
+  );
+}
@@ -8,7 +8,7 @@ interface SummaryCardsProps {
   isLoading: boolean;
 }
 
-function formatNumber(num: number): string {
+export function formatNumber(num: number): string {
   if (num >= 1000000) return `${(num / 1000000).toFixed(1)}M`;
   if (num >= 1000) return `${(num / 1000).toFixed(1)}K`;
   return num.toString();
@@ -28,7 +28,7 @@ interface StatsCardProps {
   isLoading?: boolean;
 }
 
-function StatsCard({ title, value, subtitle, icon, isLoading }: StatsCardProps) {
+export function StatsCard({ title, value, subtitle, icon, isLoading }: StatsCardProps) {
   return (
     <Card className='overflow-hidden bg-transparent shadow-none'>
       <CardContent className='p-4'>
@@ -11,6 +11,7 @@ import AgentBreakdownTable from "./components/AgentBreakdownTable";
 import DistributionChart from "./components/DistributionChart";
 import ExecutionList from "./components/ExecutionList";
 import InfoLegend from "./components/InfoLegend";
+import SpendCards from "./components/SpendCards";
 import SummaryCards from "./components/SummaryCards";
 import TrendChart from "./components/TrendChart";
 
@@ -93,6 +94,8 @@ export default function ExecutionAnalytics() {
 
             <SummaryCards summary={summary} isLoading={isLoading} />
 
+            <SpendCards projectId={projectId} days={days} />
+
             <div className='grid gap-4 md:grid-cols-2'>
               <DistributionChart summary={summary} isLoading={isLoading} />
 
@@ -163,3 +163,20 @@ export interface ExecutionDetail {
   agentRef?: string;
   toolInput?: string;
 }
+
+// Spend recorded for budget enforcement (when `budgets` is set in config.yml)
+export interface SpendBucket {
+  date: string;
+  inputTokens: number;
+  outputTokens: number;
+  costUsd: number;
+  toolCalls: number;
+}
+
+export interface SpendSummary {
+  inputTokens: number;
+  outputTokens: number;
+  costUsd: number;
+  toolCalls: number;
+  daily: SpendBucket[];
+}
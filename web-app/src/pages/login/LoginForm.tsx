@@ -13,6 +13,7 @@ import { cn } from "@/libs/shadcn/utils";
 import LoginWithGitHubButton from "./LoginWithGitHubButton";
 import LoginWithGoogleButton from "./LoginWithGoogleButton";
 import LoginWithOktaButton from "./LoginWithOktaButton";
+import LoginWithSsoButton from "./LoginWithSsoButton";
 
 type MagicLinkFormData = {
   email: string;
@@ -135,7 +136,8 @@ const Divider = ({ label }: { label: string }) => (
 const LoginForm = () => {
   const { authConfig } = useAuth();
 
-  const hasOAuth = authConfig.google || authConfig.okta || authConfig.github;
+  const hasOAuth =
+    authConfig.google || authConfig.okta || authConfig.github || authConfig.oidc;
   const hasMagicLink = authConfig.magic_link;
 
   return (
@@ -163,6 +165,9 @@ const LoginForm = () => {
             domain={authConfig.okta.domain}
           />
         )}
+        {authConfig.oidc && (
+          <LoginWithSsoButton disabled={false} displayName={authConfig.oidc.display_name} />
+        )}
       </div>
     </div>
   );
@@ -0,0 +1,29 @@
+import { KeyRound } from "lucide-react";
+import { toast } from "sonner";
+import { Button } from "@/components/ui/shadcn/button";
+import { initiateOidcAuth } from "@/hooks/auth/useSsoAuth";
+
+interface Props {
+  disabled: boolean;
+  displayName: string;
+}
+
+const LoginWithSsoButton = ({ disabled, displayName }: Props) => {
+  const handleSsoAuth = async () => {
+    try {
+      await initiateOidcAuth();
+    } catch (err) {
+      console.error(`Failed to start ${displayName} login:`, err);
+      toast.error(`Couldn't start ${displayName} login. Please try again.`);
+    }
+  };
+
+  return (
+    <Button type='button' className='w-full' onClick={handleSsoAuth} disabled={disabled}>
+      <KeyRound className='mr-2 h-4 w-4' />
+      Login with {displayName}
+    </Button>
+  );
+};
+
+export default LoginWithSsoButton;
@@ -7,6 +7,8 @@ import type {
   MagicLinkVerifyRequest,
   MessageResponse,
   OAuthStateResponse,
+  OidcAuthorizeResponse,
+  OidcAuthRequest,
   OktaAuthRequest
 } from "@/types/auth";
 import { apiClient } from "./axios";
@@ -32,6 +34,20 @@ export class AuthService {
     return response.data;
   }
 
+  /**
+   * Start an OIDC login: returns the provider's authorization URL with the
+   * signed state and PKCE session to keep until the callback.
+   */
+  static async oidcAuthorize(): Promise<OidcAuthorizeResponse> {
+    const response = await apiClient.post("/auth/oidc/authorize");
+    return response.data;
+  }
+
+  static async oidcAuth(request: OidcAuthRequest): Promise<AuthResponse> {
+    const response = await apiClient.post("/auth/oidc", request);
+    return response.data;
+  }
+
   static async githubAuth(request: GitHubAuthRequest): Promise<AuthResponse> {
     const response = await apiClient.post("/auth/github", request);
     return response.data;
@@ -7,6 +7,8 @@ import { apiBaseURL } from "../env";
 const publicAPIPaths = [
   "/auth/google",
   "/auth/okta",
+  "/auth/oidc",
+  "/auth/oidc/authorize",
   "/auth/config",
   "/auth/magic-link/request",
   "/auth/magic-link/verify",
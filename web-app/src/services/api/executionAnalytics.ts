@@ -2,7 +2,8 @@ import type {
   AgentExecutionStats,
   ExecutionDetail,
   ExecutionSummary,
-  ExecutionTimeBucket
+  ExecutionTimeBucket,
+  SpendSummary
 } from "@/pages/ide/observability/execution-analytics/types";
 import { apiClient } from "./axios";
 
@@ -26,6 +27,10 @@ export interface AgentStatsQuery {
   limit?: number;
 }
 
+export interface SpendQuery {
+  days?: number;
+}
+
 export interface ExecutionsQuery {
   days?: number;
   limit?: number;
@@ -83,6 +88,19 @@ export class ExecutionAnalyticsService {
     return response.data;
   }
 
+  static async getSpend(projectId: string, params?: SpendQuery): Promise<SpendSummary> {
+    const urlParams = new URLSearchParams();
+    if (params?.days !== undefined) urlParams.append("days", params.days.toString());
+
+    let url = `/${projectId}/execution-analytics/spend`;
+    const paramsStr = urlParams.toString();
+    if (paramsStr) {
+      url += `?${paramsStr}`;
+    }
+    const response = await apiClient.get(url);
+    return response.data;
+  }
+
   static async getExecutions(
     projectId: string,
     params?: ExecutionsQuery
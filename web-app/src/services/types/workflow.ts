@@ -6,7 +6,7 @@ export enum TaskType {
   WORKFLOW = "workflow"
 }
 
-export type ExportFormat = "csv" | "json" | "sql" | "docx";
+export type ExportFormat = "csv" | "json" | "sql" | "docx" | "parquet" | "xlsx";
 
 export type ExportConfig = {
   format: ExportFormat;
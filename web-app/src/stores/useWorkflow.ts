@@ -43,7 +43,7 @@ export type BaseTaskConfig = {
   export?: ExportConfig;
 };
 
-export type ExportFormat = "csv" | "json" | "sql" | "docx";
+export type ExportFormat = "csv" | "json" | "sql" | "docx" | "parquet" | "xlsx";
 
 export type ExportConfig = {
   format: ExportFormat;
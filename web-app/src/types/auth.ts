@@ -10,6 +10,21 @@ export interface OktaAuthRequest {
   state: string;
 }
 
+export interface OidcAuthorizeResponse {
+  authorization_url: string;
+  state: string;
+  /** Signed PKCE verifier and nonce. Kept in the browser, never sent to the provider. */
+  session: string;
+}
+
+export interface OidcAuthRequest {
+  code: string;
+  /** See `GoogleAuthRequest.state`. */
+  state: string;
+  /** The `session` returned alongside the state by `POST /auth/oidc/authorize`. */
+  session: string;
+}
+
 export interface GitHubAuthRequest {
   code: string;
   /** See `GoogleAuthRequest.state`. */
@@ -78,6 +93,7 @@ export interface AuthConfigResponse {
     client_id: string;
     domain: string;
   };
+  oidc?: { display_name: string };
   magic_link?: boolean;
   github?: { client_id: string };
   enterprise?: boolean;